@@ -0,0 +1,202 @@
+//! EIP-7702 delegating account implementation.
+//!
+//! Wraps a local wallet whose EOA delegates its code to a contract through
+//! EIP-7702 authorizations, letting the solver use smart-contract features
+//! such as batching and access control without deploying its own contract.
+//! Authorizations are attached to transactions by the delivery service.
+
+use crate::implementations::local::{LocalWallet, LocalWalletSchema};
+use crate::{AccountError, AccountInterface};
+use alloy_eips::eip7702::Authorization as UnsignedAuthorization;
+use alloy_primitives::Address as AlloyAddress;
+use alloy_signer::Signer;
+use async_trait::async_trait;
+use solver_types::{
+	Address, Authorization, ConfigSchema, Field, FieldType, Schema, SchemaDefinition, SecretString,
+	Signature, Transaction,
+};
+
+/// Local wallet delegating its code to a contract through EIP-7702.
+pub struct Eip7702Account {
+	/// Wallet holding the key of the delegating EOA.
+	wallet: LocalWallet,
+	/// Address of the contract code the EOA delegates to.
+	delegate_address: Address,
+}
+
+impl Eip7702Account {
+	/// Creates a new Eip7702Account delegating to `delegate_address`.
+	pub fn new(private_key_hex: &str, delegate_address: Address) -> Result<Self, AccountError> {
+		Ok(Self {
+			wallet: LocalWallet::new(private_key_hex)?,
+			delegate_address,
+		})
+	}
+}
+
+/// Configuration schema for Eip7702Account.
+pub struct Eip7702AccountSchema;
+
+impl Eip7702AccountSchema {
+	/// Static validation method for use before instance creation
+	pub fn validate_config(config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let instance = Self;
+		instance.validate(config)
+	}
+
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		// The private key is validated as for local wallets
+		let mut schema = LocalWalletSchema::config_fields();
+		schema.required.push(
+			Field::new("delegate_address", FieldType::String)
+				.with_description("Contract the account delegates its code to")
+				.with_validator(
+					|value| match value.as_str().map(str::parse::<AlloyAddress>) {
+						Some(Ok(_)) => Ok(()),
+						_ => Err("delegate_address must be a valid address".to_string()),
+					},
+				),
+		);
+		schema
+	}
+}
+
+impl ConfigSchema for Eip7702AccountSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
+	}
+}
+
+#[async_trait]
+impl AccountInterface for Eip7702Account {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(Eip7702AccountSchema)
+	}
+
+	async fn address(&self) -> Result<Address, AccountError> {
+		self.wallet.address().await
+	}
+
+	async fn sign_transaction(&self, tx: &Transaction) -> Result<Signature, AccountError> {
+		self.wallet.sign_transaction(tx).await
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.wallet.sign_message(message).await
+	}
+
+	fn get_private_key(&self) -> SecretString {
+		self.wallet.get_private_key()
+	}
+
+	fn capabilities(&self) -> Vec<String> {
+		let mut capabilities = self.wallet.capabilities();
+		capabilities.push("sign_delegation".to_string());
+		capabilities
+	}
+
+	fn delegate_address(&self) -> Option<Address> {
+		Some(self.delegate_address.clone())
+	}
+
+	async fn sign_delegation(
+		&self,
+		delegate_code_address: Address,
+		chain_id: u64,
+		nonce: u64,
+	) -> Result<Authorization, AccountError> {
+		if delegate_code_address.0.len() != 20 {
+			return Err(AccountError::SigningFailed(
+				"Invalid delegate address length".to_string(),
+			));
+		}
+
+		let authorization = UnsignedAuthorization {
+			chain_id,
+			address: AlloyAddress::from_slice(&delegate_code_address.0),
+			nonce,
+		};
+		let signature = self
+			.wallet
+			.signer()
+			.sign_hash(&authorization.signature_hash())
+			.await
+			.map_err(|e| {
+				AccountError::SigningFailed(format!("Failed to sign authorization: {}", e))
+			})?;
+
+		Ok(authorization.into_signed(signature))
+	}
+}
+
+/// Factory function to create an EIP-7702 account from configuration.
+///
+/// Configuration parameters:
+/// - `private_key`: Private key of the delegating EOA
+/// - `delegate_address`: Address of the contract code to delegate to
+pub fn create_account(config: &toml::Value) -> Result<Box<dyn AccountInterface>, AccountError> {
+	Eip7702AccountSchema::validate_config(config)
+		.map_err(|e| AccountError::InvalidKey(format!("Invalid configuration: {}", e)))?;
+
+	let private_key = config
+		.get("private_key")
+		.and_then(|v| v.as_str())
+		.expect("private_key already validated");
+	let delegate_address = config
+		.get("delegate_address")
+		.and_then(|v| v.as_str())
+		.and_then(|v| v.parse::<AlloyAddress>().ok())
+		.expect("delegate_address already validated");
+
+	Ok(Box::new(Eip7702Account::new(
+		private_key,
+		Address(delegate_address.to_vec()),
+	)?))
+}
+
+/// Registry for the EIP-7702 account implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "eip7702";
+	type Factory = crate::AccountFactory;
+
+	fn factory() -> Self::Factory {
+		create_account
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7702AccountSchema)
+	}
+}
+
+impl crate::AccountRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const PRIVATE_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+	#[tokio::test]
+	async fn test_sign_delegation_recovers_authority() {
+		let delegate = Address(vec![0x11; 20]);
+		let account = Eip7702Account::new(PRIVATE_KEY, delegate.clone()).unwrap();
+
+		let authorization = account.sign_delegation(delegate, 10, 7).await.unwrap();
+		assert_eq!(authorization.chain_id, 10);
+		assert_eq!(authorization.nonce, 7);
+		assert_eq!(authorization.address, AlloyAddress::repeat_byte(0x11));
+
+		let authority = authorization.recover_authority().unwrap();
+		assert_eq!(
+			Address(authority.to_vec()),
+			account.address().await.unwrap()
+		);
+	}
+}
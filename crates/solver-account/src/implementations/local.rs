@@ -11,8 +11,8 @@ use alloy_signer::Signer;
 use alloy_signer_local::PrivateKeySigner;
 use async_trait::async_trait;
 use solver_types::{
-	with_0x_prefix, Address, ConfigSchema, Field, FieldType, Schema, SecretString, Signature,
-	Transaction,
+	with_0x_prefix, Address, ConfigSchema, Field, FieldType, Schema, SchemaDefinition,
+	SecretString, Signature, Transaction,
 };
 
 /// Local wallet implementation using Alloy's signer.
@@ -38,6 +38,11 @@ impl LocalWallet {
 		Ok(Self { signer })
 	}
 
+	/// Returns the underlying signer.
+	pub(crate) fn signer(&self) -> &PrivateKeySigner {
+		&self.signer
+	}
+
 	/// Returns the private key as a SecretString with 0x prefix.
 	pub fn get_private_key(&self) -> SecretString {
 		SecretString::from(&with_0x_prefix(&hex::encode(self.signer.to_bytes())) as &str)
@@ -53,38 +58,44 @@ impl LocalWalletSchema {
 		let instance = Self;
 		instance.validate(config)
 	}
+
+	/// Fields accepted by the configuration.
+	pub(crate) fn config_fields() -> Schema {
+		Schema::new(
+			// Required fields
+			vec![Field::new("private_key", FieldType::String)
+				.with_description("Hex-encoded 32-byte private key, with or without 0x prefix")
+				.with_validator(|value| match value.as_str() {
+					Some(key) => {
+						let key_without_prefix = key.strip_prefix("0x").unwrap_or(key);
+
+						if key_without_prefix.len() != 64 {
+							return Err(
+								"Private key must be 64 hex characters (32 bytes)".to_string()
+							);
+						}
+
+						if hex::decode(key_without_prefix).is_err() {
+							return Err("Private key must be valid hexadecimal".to_string());
+						}
+
+						Ok(())
+					},
+					None => Err("Expected string value for private_key".to_string()),
+				})],
+			// Optional fields
+			vec![],
+		)
+	}
 }
 
 impl ConfigSchema for LocalWalletSchema {
 	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema =
-			Schema::new(
-				// Required fields
-				vec![Field::new("private_key", FieldType::String).with_validator(
-					|value| match value.as_str() {
-						Some(key) => {
-							let key_without_prefix = key.strip_prefix("0x").unwrap_or(key);
-
-							if key_without_prefix.len() != 64 {
-								return Err(
-									"Private key must be 64 hex characters (32 bytes)".to_string()
-								);
-							}
-
-							if hex::decode(key_without_prefix).is_err() {
-								return Err("Private key must be valid hexadecimal".to_string());
-							}
-
-							Ok(())
-						},
-						None => Err("Expected string value for private_key".to_string()),
-					},
-				)],
-				// Optional fields
-				vec![],
-			);
+		Self::config_fields().validate(config)
+	}
 
-		schema.validate(config)
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -185,6 +196,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_account
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(LocalWalletSchema)
+	}
 }
 
 impl crate::AccountRegistry for Registry {}
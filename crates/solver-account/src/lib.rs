@@ -6,12 +6,15 @@
 
 use async_trait::async_trait;
 use solver_types::{
-	Address, ConfigSchema, ImplementationRegistry, SecretString, Signature, Transaction,
+	AccountInfo, Address, Authorization, ConfigSchema, ImplementationRegistry, SecretString,
+	Signature, Transaction,
 };
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Re-export implementations
 pub mod implementations {
+	pub mod eip7702;
 	pub mod local;
 }
 
@@ -65,6 +68,36 @@ pub trait AccountInterface: Send + Sync {
 	/// This is required for all account implementations as it's used by
 	/// delivery implementations for transaction signing.
 	fn get_private_key(&self) -> SecretString;
+
+	/// Returns the operations supported by this account.
+	///
+	/// All accounts sign transactions and messages; implementations supporting
+	/// more operations should extend this list.
+	fn capabilities(&self) -> Vec<String> {
+		vec!["sign_transaction".to_string(), "sign_message".to_string()]
+	}
+
+	/// Returns the address of the contract code this account delegates to
+	/// through EIP-7702, if it delegates at all.
+	fn delegate_address(&self) -> Option<Address> {
+		None
+	}
+
+	/// Signs an EIP-7702 authorization delegating this account's code to
+	/// `delegate_code_address` on `chain_id`.
+	///
+	/// The `nonce` must be the account's nonce at the time the authorization
+	/// is processed. Accounts that do not delegate return an error.
+	async fn sign_delegation(
+		&self,
+		_delegate_code_address: Address,
+		_chain_id: u64,
+		_nonce: u64,
+	) -> Result<Authorization, AccountError> {
+		Err(AccountError::Implementation(
+			"EIP-7702 delegation is not supported by this account".to_string(),
+		))
+	}
 }
 
 /// Type alias for account factory functions.
@@ -84,9 +117,25 @@ pub trait AccountRegistry: ImplementationRegistry<Factory = AccountFactory> {}
 /// Returns a vector of (name, factory) tuples for all available account implementations.
 /// This is used by the factory registry to automatically register all implementations.
 pub fn get_all_implementations() -> Vec<(&'static str, AccountFactory)> {
-	use implementations::local;
+	use implementations::{eip7702, local};
 
-	vec![(local::Registry::NAME, local::Registry::factory())]
+	vec![
+		(local::Registry::NAME, local::Registry::factory()),
+		(eip7702::Registry::NAME, eip7702::Registry::factory()),
+	]
+}
+
+/// Get the configuration schemas of all registered account implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_implementations`].
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{eip7702, local};
+
+	vec![
+		(local::Registry::NAME, local::Registry::config_schema()),
+		(eip7702::Registry::NAME, eip7702::Registry::config_schema()),
+	]
 }
 
 /// Service that manages account operations.
@@ -95,7 +144,9 @@ pub fn get_all_implementations() -> Vec<(&'static str, AccountFactory)> {
 /// wrapping an underlying account implementation.
 pub struct AccountService {
 	/// The underlying account implementation implementation.
-	implementation: Box<dyn AccountInterface>,
+	implementation: Arc<dyn AccountInterface>,
+	/// All loaded account implementations by name, including this one.
+	accounts: Vec<(String, Arc<dyn AccountInterface>)>,
 }
 
 impl AccountService {
@@ -103,8 +154,37 @@ impl AccountService {
 	///
 	/// The implementation must implement the AccountInterface trait and will be used
 	/// for all account operations performed by this service.
-	pub fn new(implementation: Box<dyn AccountInterface>) -> Self {
-		Self { implementation }
+	pub fn new(implementation: impl Into<Arc<dyn AccountInterface>>) -> Self {
+		Self {
+			implementation: implementation.into(),
+			accounts: Vec::new(),
+		}
+	}
+
+	/// Sets the loaded account implementations listed by `list_accounts`.
+	pub fn with_accounts(mut self, accounts: Vec<(String, Arc<dyn AccountInterface>)>) -> Self {
+		self.accounts = accounts;
+		self
+	}
+
+	/// Lists all loaded account implementations with their addresses.
+	///
+	/// Accounts whose address cannot be retrieved are skipped.
+	pub async fn list_accounts(&self) -> Vec<AccountInfo> {
+		let mut accounts = Vec::with_capacity(self.accounts.len());
+		for (name, implementation) in &self.accounts {
+			match implementation.address().await {
+				Ok(address) => accounts.push(AccountInfo {
+					name: name.clone(),
+					address,
+					capabilities: implementation.capabilities(),
+				}),
+				Err(e) => {
+					tracing::warn!(account = %name, error = %e, "Failed to get account address");
+				},
+			}
+		}
+		accounts
 	}
 
 	/// Retrieves the address associated with the managed account.
@@ -121,6 +201,26 @@ impl AccountService {
 		self.implementation.sign_transaction(tx).await
 	}
 
+	/// Returns the address of the contract code the account delegates to
+	/// through EIP-7702, if any.
+	pub fn delegate_address(&self) -> Option<Address> {
+		self.implementation.delegate_address()
+	}
+
+	/// Signs an EIP-7702 authorization delegating the account's code.
+	///
+	/// This method delegates to the underlying implementation's sign_delegation method.
+	pub async fn sign_delegation(
+		&self,
+		delegate_code_address: Address,
+		chain_id: u64,
+		nonce: u64,
+	) -> Result<Authorization, AccountError> {
+		self.implementation
+			.sign_delegation(delegate_code_address, chain_id, nonce)
+			.await
+	}
+
 	/// Returns the private key as a SecretString.
 	///
 	/// This is used by delivery implementations for transaction signing.
@@ -12,33 +12,96 @@
 
 mod loader;
 
+use miette::{Diagnostic, NamedSource, SourceSpan};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use solver_types::{networks::deserialize_networks, NetworksConfig};
-use std::collections::HashMap;
+use solver_types::{
+	networks::deserialize_networks,
+	oracle::{ProofRelayConfig, RelayProtocol},
+	Address, GasBidConfig, GasStrategy, NetworksConfig, OrderTemplate, ProviderMode,
+	TwapGranularity,
+};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::str::FromStr;
 use thiserror::Error;
 
+/// Documentation of the configuration format, linked from error reports.
+const CONFIG_DOCS_URL: &str = "https://github.com/openintentsframework/oif-solver#configuration";
+
 /// Errors that can occur during configuration operations.
-#[derive(Debug, Error)]
+///
+/// Errors are miette diagnostics, so that parse errors and missing
+/// environment variables are reported with the offending source snippet.
+#[derive(Debug, Error, Diagnostic)]
 pub enum ConfigError {
 	/// Error that occurs during file I/O operations.
 	#[error("IO error: {0}")]
+	#[diagnostic(
+		code(config::io),
+		help("Check that the configuration file and its includes exist and are readable")
+	)]
 	Io(#[from] std::io::Error),
 	/// Error that occurs when parsing TOML configuration.
-	#[error("Configuration error: {0}")]
-	Parse(String),
+	#[error("Configuration error: {message}")]
+	#[diagnostic(
+		code(config::parse),
+		url("{}", CONFIG_DOCS_URL),
+		help("Check the TOML syntax and the expected fields of the section")
+	)]
+	Parse {
+		/// Description of the error.
+		message: String,
+		/// Configuration source the error was found in.
+		#[source_code]
+		src: Option<NamedSource<String>>,
+		/// Location of the error in the source.
+		#[label("{message}")]
+		span: Option<SourceSpan>,
+	},
+	/// Error that occurs when an environment variable referenced by the
+	/// configuration is not set.
+	#[error("Environment variable '{name}' not found")]
+	#[diagnostic(
+		code(config::env_var),
+		url("{}", CONFIG_DOCS_URL),
+		help("Set {name} or give it a default value with ${{{name}:-default}}")
+	)]
+	MissingEnvVar {
+		/// Name of the environment variable.
+		name: String,
+		/// Configuration source referencing the variable.
+		#[source_code]
+		src: NamedSource<String>,
+		/// Location of the reference in the source.
+		#[label("referenced here")]
+		span: SourceSpan,
+	},
 	/// Error that occurs when configuration validation fails.
 	#[error("Validation error: {0}")]
+	#[diagnostic(
+		code(config::validation),
+		url("{}", CONFIG_DOCS_URL),
+		help("Compare the configuration with config/example.toml")
+	)]
 	Validation(String),
 }
 
+impl ConfigError {
+	/// Creates a parse error without source location.
+	pub fn parse(message: impl Into<String>) -> Self {
+		ConfigError::Parse {
+			message: message.into(),
+			src: None,
+			span: None,
+		}
+	}
+}
+
 impl From<toml::de::Error> for ConfigError {
 	fn from(err: toml::de::Error) -> Self {
 		// Extract just the message without the huge input dump
-		let message = err.message().to_string();
-		ConfigError::Parse(message)
+		ConfigError::parse(err.message())
 	}
 }
 
@@ -68,15 +131,70 @@ pub struct Config {
 	pub settlement: SettlementConfig,
 	/// Configuration for the HTTP API server.
 	pub api: Option<ApiConfig>,
+	/// Configuration for token price feeds.
+	pub price: Option<PriceConfig>,
+	/// Configuration for competitive filling of auctioned intents.
+	pub auction: Option<AuctionConfig>,
+	/// Configuration for exporting telemetry to an external sink.
+	#[serde(default)]
+	pub telemetry: TelemetryConfig,
+	/// Configuration for order status webhooks. Disabled when not configured.
+	#[serde(default)]
+	pub webhooks: Option<WebhookConfig>,
+	/// Additional solver identities run in the same process.
+	///
+	/// Each entry overrides the solver section, and optionally the account and
+	/// strategy, of the base configuration. When empty, the base configuration
+	/// runs as a single solver.
+	#[serde(default)]
+	pub solvers: Vec<TenantConfig>,
+}
+
+/// Configuration of one solver identity in a multi-solver deployment.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TenantConfig {
+	/// Solver section of this identity. The `id` scopes its storage.
+	pub solver: SolverConfig,
+	/// Account used by this identity. Defaults to the base account.
+	#[serde(default)]
+	pub account: Option<AccountConfig>,
+	/// Execution strategy of this identity. Defaults to the base strategy.
+	#[serde(default)]
+	pub strategy: Option<StrategyConfig>,
 }
 
 /// Domain configuration for EIP-712 signatures in quotes.
+///
+/// Each settlement contract has its own domain, so one domain is configured
+/// per chain the settlements are deployed on.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DomainConfig {
 	/// Chain ID where the settlement contract is deployed.
 	pub chain_id: u64,
 	/// Settlement contract address.
 	pub address: String,
+	/// EIP-712 domain name, telling apart the domains of different
+	/// settlement contracts.
+	#[serde(default)]
+	pub name: String,
+}
+
+/// Deserializes the settlement domains from a single table or an array.
+fn deserialize_domains<'de, D>(deserializer: D) -> Result<Vec<DomainConfig>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Domains {
+		One(DomainConfig),
+		Many(Vec<DomainConfig>),
+	}
+
+	Ok(match Domains::deserialize(deserializer)? {
+		Domains::One(domain) => vec![domain],
+		Domains::Many(domains) => domains,
+	})
 }
 
 /// Configuration specific to the solver instance.
@@ -88,6 +206,261 @@ pub struct SolverConfig {
 	/// Defaults to 480 minutes (8 hours) if not specified.
 	#[serde(default = "default_monitoring_timeout_minutes")]
 	pub monitoring_timeout_minutes: u64,
+	/// Seconds a pending fill keeps being monitored after its order's fill
+	/// deadline passed, before the order is marked as failed.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_expiry_grace_period_seconds")]
+	pub expiry_grace_period_seconds: u64,
+	/// When enabled, all transactions are simulated with `eth_call` instead of
+	/// being submitted, returning synthetic hashes and receipts. All other
+	/// processing runs normally up to the fill, allowing paper trading on real
+	/// order flow; simulated fills are not settled or claimed.
+	#[serde(default)]
+	pub simulation_mode: bool,
+	/// Enables debugging tools that modify solver state, such as replaying
+	/// stored intents, which also require `api.admin_token`. Must not be
+	/// enabled in production.
+	#[serde(default)]
+	pub debug_mode: bool,
+	/// Maximum gas spend in ETH per chain over a rolling 24-hour window,
+	/// keyed as `{chain_id}_daily_eth`. Chains without an entry are unlimited.
+	#[serde(default)]
+	pub gas_budget: HashMap<String, f64>,
+	/// Cross-chain price arbitrage detection. Disabled when not configured.
+	#[serde(default)]
+	pub arbitrage: Option<ArbitrageConfig>,
+	/// Gossiping of discovered intents between solver replicas.
+	/// Disabled when not configured.
+	#[serde(default)]
+	pub gossip: Option<GossipConfig>,
+	/// Interval in seconds between checkpoints of the active orders, which
+	/// speed up recovery on restart. Disabled when not configured.
+	#[serde(default)]
+	pub checkpoint_interval_seconds: Option<u64>,
+	/// Whether tokens are bridged automatically between chains according to
+	/// `rebalancing`. Defaults to false to avoid unexpected transfers.
+	#[serde(default)]
+	pub auto_rebalance: bool,
+	/// Bridges used to rebalance token inventory between chains.
+	#[serde(default)]
+	pub rebalancing: Option<RebalancingConfig>,
+	/// Whether fills and the approvals they need, and batched claims, are
+	/// submitted as one EIP-5792 `wallet_sendCalls` batch on chains whose RPC
+	/// supports it.
+	/// Defaults to false.
+	#[serde(default)]
+	pub use_wallet_batch: bool,
+	/// Fill concurrency per output chain ID. Fills on chains without an entry
+	/// are only limited by the general handler concurrency. Submissions on a
+	/// chain are serialized by delivery either way.
+	#[serde(default, deserialize_with = "deserialize_chain_concurrency")]
+	pub concurrency: HashMap<u64, ChainConcurrencyConfig>,
+	/// Persisted log of all solver events, kept for post-mortem debugging.
+	#[serde(default)]
+	pub event_log: EventLogConfig,
+}
+
+/// Configuration of the persisted solver event log.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventLogConfig {
+	/// Days events are kept before the storage cleanup prunes them.
+	/// Defaults to 7 days if not specified.
+	#[serde(default = "default_event_log_retention_days")]
+	pub retention_days: u64,
+	/// Number of events buffered while waiting to be written, beyond which
+	/// events are dropped rather than slowing down the solver.
+	/// Defaults to 1000 if not specified.
+	#[serde(default = "default_event_log_buffer_size")]
+	pub buffer_size: usize,
+}
+
+impl Default for EventLogConfig {
+	fn default() -> Self {
+		Self {
+			retention_days: default_event_log_retention_days(),
+			buffer_size: default_event_log_buffer_size(),
+		}
+	}
+}
+
+/// Returns the default event log retention in days.
+fn default_event_log_retention_days() -> u64 {
+	7
+}
+
+/// Returns the default number of events buffered for the event log.
+fn default_event_log_buffer_size() -> usize {
+	1000
+}
+
+/// Order processing concurrency of a single chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChainConcurrencyConfig {
+	/// Maximum number of fills submitted concurrently on the chain.
+	pub max_concurrent_fills: usize,
+}
+
+/// Deserializes the chain concurrency table, whose chain ID keys are strings in TOML.
+fn deserialize_chain_concurrency<'de, D>(
+	deserializer: D,
+) -> Result<HashMap<u64, ChainConcurrencyConfig>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	HashMap::<String, ChainConcurrencyConfig>::deserialize(deserializer)?
+		.into_iter()
+		.map(|(key, value)| {
+			let chain_id = key.parse::<u64>().map_err(|e| {
+				serde::de::Error::custom(format!("Invalid chain_id '{}': {}", key, e))
+			})?;
+			Ok((chain_id, value))
+		})
+		.collect()
+}
+
+/// Configuration for rebalancing token inventory between chains.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RebalancingConfig {
+	/// Interval in seconds between inventory checks.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_rebalancing_interval_seconds")]
+	pub interval_seconds: u64,
+	/// Minimum time in seconds between two transfers over the same bridge,
+	/// leaving bridged tokens time to arrive.
+	/// Defaults to 3600 seconds if not specified.
+	#[serde(default = "default_rebalancing_cooldown_seconds")]
+	pub cooldown_seconds: u64,
+	/// Bridges, at most one per token pair.
+	#[serde(default)]
+	pub bridges: Vec<BridgeConfig>,
+}
+
+/// A canonical bridge moving a token from one chain to another.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BridgeConfig {
+	/// Chain the tokens are bridged from.
+	pub from_chain: u64,
+	/// Chain the tokens are bridged to.
+	pub to_chain: u64,
+	/// Token on the source chain.
+	pub from_token: Address,
+	/// Token on the destination chain.
+	pub to_token: Address,
+	/// Bridge contract on the source chain, implementing `bridgeERC20To`.
+	///
+	/// Must be an L1 bridge: withdrawals over the L2 standard bridge have to
+	/// be proven and finalized on L1, which rebalancing does not do.
+	pub bridge_address: Address,
+	/// Available balance on the destination chain, in base units, below
+	/// which tokens are bridged.
+	pub low_threshold: String,
+	/// Amount bridged per transfer, in base units.
+	pub amount: String,
+}
+
+/// Configuration for gossiping intents between solver replicas.
+///
+/// Each replica subscribes to the intents discovered by its peers over TCP.
+/// Peers prove knowledge of `shared_secret` when connecting; the connection
+/// itself is not encrypted.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GossipConfig {
+	/// Whether gossiping is enabled.
+	#[serde(default)]
+	pub enabled: bool,
+	/// Address on which peers subscribe to the intents discovered by this replica.
+	/// Defaults to "127.0.0.1:9000" if not specified; replicas on other hosts
+	/// need it set to a reachable interface.
+	#[serde(default = "default_gossip_listen_address")]
+	pub listen_address: String,
+	/// Addresses of the peers to subscribe to, e.g. "solver-2:9000".
+	#[serde(default)]
+	pub peers: Vec<String>,
+	/// How long discovered intents are kept for replay to peers that missed them.
+	/// Defaults to 300 seconds if not specified.
+	#[serde(default = "default_gossip_replay_window_seconds")]
+	pub replay_window_seconds: u64,
+	/// Interval at which replicas share their reputation scores with peers.
+	/// Defaults to 30 seconds if not specified.
+	#[serde(default = "default_reputation_broadcast_interval_seconds")]
+	pub reputation_broadcast_interval_seconds: u64,
+	/// Secret shared by all replicas, used to authenticate peers. Required
+	/// when gossiping is enabled.
+	#[serde(default)]
+	pub shared_secret: Option<String>,
+	/// Seconds a peer has to acknowledge an intent assigned to it before the
+	/// replica processes the intent itself. Defaults to 10 seconds.
+	#[serde(default = "default_gossip_assignment_ack_timeout_seconds")]
+	pub assignment_ack_timeout_seconds: u64,
+}
+
+/// Returns the default address on which gossip peers are accepted.
+fn default_gossip_listen_address() -> String {
+	"127.0.0.1:9000".to_string()
+}
+
+/// Returns the default gossip replay window in seconds.
+fn default_gossip_replay_window_seconds() -> u64 {
+	300
+}
+
+/// Returns the default time a peer has to acknowledge an assigned intent.
+fn default_gossip_assignment_ack_timeout_seconds() -> u64 {
+	10
+}
+
+/// Returns the default interval between reputation broadcasts in seconds.
+fn default_reputation_broadcast_interval_seconds() -> u64 {
+	30
+}
+
+/// Configuration for cross-chain price arbitrage detection.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArbitrageConfig {
+	/// Minimum price difference between chains, in basis points, to report.
+	pub min_spread_bps: u64,
+	/// Interval in seconds between detection runs.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_arbitrage_interval_seconds")]
+	pub interval_seconds: u64,
+}
+
+/// Returns the default interval in seconds between arbitrage detection runs.
+fn default_arbitrage_interval_seconds() -> u64 {
+	60
+}
+
+/// Returns the default interval in seconds between rebalancing checks.
+/// Address of the OP Stack standard bridge predeploy on L2s.
+const OP_L2_STANDARD_BRIDGE: [u8; 20] = [
+	0x42, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+fn default_rebalancing_interval_seconds() -> u64 {
+	60
+}
+
+/// Returns the default time in seconds between transfers over one bridge.
+fn default_rebalancing_cooldown_seconds() -> u64 {
+	3600
+}
+
+impl SolverConfig {
+	/// Returns the daily gas budgets in ETH keyed by chain ID.
+	///
+	/// Keys not of the form `{chain_id}_daily_eth` are ignored here and
+	/// rejected during validation.
+	pub fn daily_gas_budgets(&self) -> HashMap<u64, f64> {
+		self.gas_budget
+			.iter()
+			.filter_map(|(key, eth)| Some((parse_gas_budget_key(key)?, *eth)))
+			.collect()
+	}
+}
+
+/// Parses the chain ID from a `{chain_id}_daily_eth` gas budget key.
+fn parse_gas_budget_key(key: &str) -> Option<u64> {
+	key.strip_suffix("_daily_eth")?.parse().ok()
 }
 
 /// Returns the default monitoring timeout in minutes.
@@ -98,6 +471,14 @@ fn default_monitoring_timeout_minutes() -> u64 {
 	480 // Default to 8 hours
 }
 
+/// Returns the default expiry grace period in seconds.
+///
+/// This provides a default value of 60 seconds during which fills pending at
+/// their order's fill deadline are still monitored.
+fn default_expiry_grace_period_seconds() -> u64 {
+	60
+}
+
 /// Configuration for the storage backend.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StorageConfig {
@@ -119,6 +500,35 @@ pub struct DeliveryConfig {
 	/// Defaults to 12 confirmations if not specified.
 	#[serde(default = "default_confirmations")]
 	pub min_confirmations: u64,
+	/// RPC rate limits per chain ID, applied to all delivery calls of that chain.
+	#[serde(default)]
+	pub rate_limits: HashMap<u64, RpcRateLimitConfig>,
+	/// How fees of submitted transactions are determined.
+	#[serde(default)]
+	pub gas_strategy: GasStrategy,
+	/// Fee bidding parameters used with the `auto` gas strategy.
+	#[serde(default)]
+	pub gas_bid: GasBidConfig,
+	/// How long receipts of transactions that are not yet final are cached.
+	/// Defaults to 10 seconds if not specified.
+	#[serde(default = "default_receipt_cache_ttl_seconds")]
+	pub receipt_cache_ttl_seconds: u64,
+	/// Whether the implementation of each chain is created on its first use
+	/// rather than at startup, so that unreachable chains do not prevent
+	/// the solver from starting. Defaults to false.
+	#[serde(default)]
+	pub lazy_init: bool,
+	/// Chains the solver connects to: the configured RPC endpoints
+	/// (`mainnet`, `testnet`), or local Anvil nodes started with the solver.
+	#[serde(default)]
+	pub provider_mode: ProviderMode,
+}
+
+/// RPC rate limit of a single chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RpcRateLimitConfig {
+	/// Maximum number of requests per second.
+	pub rps: u32,
 }
 
 /// Returns the default number of confirmations required.
@@ -129,8 +539,13 @@ fn default_confirmations() -> u64 {
 	12 // Default to 12 confirmations
 }
 
+/// Returns the default receipt cache TTL in seconds.
+fn default_receipt_cache_ttl_seconds() -> u64 {
+	10
+}
+
 /// Configuration for account management.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct AccountConfig {
 	/// Which implementation to use as primary.
 	pub primary: String,
@@ -144,6 +559,120 @@ pub struct DiscoveryConfig {
 	/// Map of discovery implementation names to their configurations.
 	/// Each implementation has its own configuration format stored as raw TOML values.
 	pub implementations: HashMap<String, toml::Value>,
+	/// Health monitoring of discovery sources. Disabled when not configured.
+	#[serde(default)]
+	pub health: Option<DiscoveryHealthConfig>,
+	/// Enrichers run in order on every discovered intent
+	/// ("token_metadata", "price"). No enrichment when empty.
+	#[serde(default)]
+	pub enrichers: Vec<String>,
+	/// Orders expiring within this many seconds are skipped rather than filled,
+	/// since the fill would not be confirmed before expiry. Defaults to 0.
+	#[serde(default)]
+	pub preflight_expiry_buffer_seconds: u64,
+	/// Token pairs and chains intents are rejected for before validation.
+	#[serde(default)]
+	pub filters: IntentFiltersConfig,
+}
+
+/// Blacklist and whitelist of intents by token pair and chains.
+///
+/// Intents with any input and output matching a blacklist rule are rejected.
+/// When the whitelist is not empty, intents are rejected unless each of their
+/// inputs and outputs matches one of its rules.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct IntentFiltersConfig {
+	#[serde(default)]
+	pub blacklist: Vec<IntentFilterRule>,
+	#[serde(default)]
+	pub whitelist: Vec<IntentFilterRule>,
+}
+
+/// A rule matching intents by the tokens and chains of an input and output.
+///
+/// Fields are glob patterns (`*` matches anything) over lowercase hex token
+/// addresses and decimal chain IDs. Omitted fields match anything.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IntentFilterRule {
+	#[serde(default = "default_filter_pattern")]
+	pub input_token: String,
+	#[serde(default = "default_filter_pattern")]
+	pub output_token: String,
+	#[serde(
+		default = "default_filter_pattern",
+		deserialize_with = "deserialize_chain_pattern"
+	)]
+	pub input_chain: String,
+	#[serde(
+		default = "default_filter_pattern",
+		deserialize_with = "deserialize_chain_pattern"
+	)]
+	pub output_chain: String,
+}
+
+impl IntentFilterRule {
+	/// Returns the patterns of the rule.
+	pub fn patterns(&self) -> [&str; 4] {
+		[
+			&self.input_token,
+			&self.output_token,
+			&self.input_chain,
+			&self.output_chain,
+		]
+	}
+}
+
+/// Returns the default pattern of intent filter fields.
+///
+/// This provides a default value of "*", matching anything.
+fn default_filter_pattern() -> String {
+	"*".to_string()
+}
+
+/// Deserializes a chain pattern given either as a chain ID or as a string.
+fn deserialize_chain_pattern<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum ChainPattern {
+		Id(u64),
+		Pattern(String),
+	}
+
+	Ok(match ChainPattern::deserialize(deserializer)? {
+		ChainPattern::Id(chain_id) => chain_id.to_string(),
+		ChainPattern::Pattern(pattern) => pattern,
+	})
+}
+
+/// Configuration for discovery source health monitoring.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DiscoveryHealthConfig {
+	/// Seconds without a discovered intent after which a source is restarted.
+	pub inactivity_threshold_seconds: u64,
+	/// Maximum consecutive restarts of an inactive source before giving up.
+	/// Defaults to 5 if not specified.
+	#[serde(default = "default_max_restart_attempts")]
+	pub max_restart_attempts: u32,
+	/// Interval in seconds between health checks.
+	/// Defaults to 30 seconds if not specified.
+	#[serde(default = "default_discovery_health_check_interval_seconds")]
+	pub check_interval_seconds: u64,
+	/// Discovery implementations to monitor. Monitors all implementations when empty.
+	#[serde(default)]
+	pub sources: Vec<String>,
+}
+
+/// Returns the default maximum number of discovery source restarts.
+fn default_max_restart_attempts() -> u32 {
+	5
+}
+
+/// Returns the default interval in seconds between discovery health checks.
+fn default_discovery_health_check_interval_seconds() -> u64 {
+	30
 }
 
 /// Configuration for order processing.
@@ -154,6 +683,49 @@ pub struct OrderConfig {
 	pub implementations: HashMap<String, toml::Value>,
 	/// Strategy configuration for order execution.
 	pub strategy: StrategyConfig,
+	/// Templates restricting which orders are executed.
+	/// When empty, all orders are passed to the strategy.
+	#[serde(default)]
+	pub templates: Vec<OrderTemplate>,
+	/// Fill time predictor used to skip orders likely to expire before filling.
+	/// When not specified, no prediction is made.
+	#[serde(default)]
+	pub predictor: Option<PredictorConfig>,
+	/// Minimum seconds left before the fill deadline of an order for it to be
+	/// filled, covering the fill and its confirmation.
+	/// Defaults to 0, skipping only orders past their fill deadline.
+	#[serde(default)]
+	pub min_fill_window_seconds: u64,
+}
+
+/// Configuration of the fill time predictor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PredictorConfig {
+	/// Median of the recorded fill times of the token pair of each order.
+	Historical {
+		/// Fill time in seconds for pairs without recorded fills.
+		#[serde(default = "default_predictor_fill_time_seconds")]
+		default_fill_time_seconds: u64,
+		/// Number of most recent fills the median is taken over.
+		#[serde(default = "default_predictor_max_samples")]
+		max_samples: usize,
+	},
+	/// The same fill time for every order.
+	Static {
+		/// Fill time in seconds.
+		fill_time_seconds: u64,
+	},
+}
+
+/// Returns the default fill time for pairs without recorded fills.
+fn default_predictor_fill_time_seconds() -> u64 {
+	60
+}
+
+/// Returns the default number of fills the median is taken over.
+fn default_predictor_max_samples() -> usize {
+	100
 }
 
 /// Configuration for execution strategies.
@@ -163,6 +735,49 @@ pub struct StrategyConfig {
 	pub primary: String,
 	/// Map of strategy implementation names to their configurations.
 	pub implementations: HashMap<String, toml::Value>,
+	/// Limits the frequency of fills decided by the primary strategy.
+	#[serde(default)]
+	pub twap: Option<TwapConfig>,
+	/// Defers orders to predicted low gas windows while gas is expensive.
+	#[serde(default)]
+	pub gas_window: Option<GasWindowConfig>,
+}
+
+/// Configuration of the time-weighted average execution strategy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TwapConfig {
+	/// Maximum number of orders executed per window.
+	pub max_fills_per_window: u32,
+	/// Length in seconds of the rolling window.
+	pub window_seconds: u64,
+	/// Scope of the limit: "global" (default), "chain" or "token_pair".
+	#[serde(default)]
+	pub granularity: TwapGranularity,
+}
+
+/// Configuration of gas-aware execution timing.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GasWindowConfig {
+	/// Gas price in gwei above which orders are deferred.
+	pub max_gas_price_gwei: u64,
+	/// Interval in seconds between gas price samples.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_gas_sample_interval_seconds")]
+	pub sample_interval_seconds: u64,
+	/// Number of hours searched for a low gas window.
+	/// Defaults to 24 hours if not specified.
+	#[serde(default = "default_gas_window_lookahead_hours")]
+	pub lookahead_hours: u8,
+}
+
+/// Returns the default interval between gas price samples.
+fn default_gas_sample_interval_seconds() -> u64 {
+	60
+}
+
+/// Returns the default number of hours searched for a low gas window.
+fn default_gas_window_lookahead_hours() -> u8 {
+	24
 }
 
 /// Configuration for settlement operations.
@@ -171,8 +786,298 @@ pub struct SettlementConfig {
 	/// Map of settlement implementation names to their configurations.
 	/// Each implementation handles specific settlement mechanisms.
 	pub implementations: HashMap<String, toml::Value>,
-	/// Domain configuration for EIP-712 signatures in quotes.
-	pub domain: Option<DomainConfig>,
+	/// Domains for EIP-712 signatures in quotes, one per settlement chain.
+	///
+	/// Accepts a single `[settlement.domain]` table or an array of
+	/// `[[settlement.domain]]` tables.
+	#[serde(default, deserialize_with = "deserialize_domains")]
+	pub domain: Vec<DomainConfig>,
+	/// Interval in seconds between oracle health probes.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_oracle_health_check_interval_seconds")]
+	pub oracle_health_check_interval_seconds: u64,
+	/// Interval in seconds between queries of the supported routes of each
+	/// settlement contract. Routes are static when not configured.
+	#[serde(default)]
+	pub route_discovery_interval_seconds: Option<u64>,
+	/// Batching of claim transactions.
+	#[serde(default)]
+	pub batch: ClaimBatchConfig,
+	/// Circuit breaker disabling auto-claiming of implementations whose
+	/// claims keep failing.
+	#[serde(default)]
+	pub claim_circuit_breaker: ClaimCircuitBreakerConfig,
+	/// Time in seconds fill proofs are cached before being attested again.
+	/// Defaults to 3600 seconds; 0 disables caching.
+	#[serde(default = "default_proof_cache_ttl_seconds")]
+	pub proof_cache_ttl_seconds: u64,
+	/// Maximum number of settlement implementations whose claims are
+	/// processed concurrently. Claims of one implementation are always
+	/// submitted in order. Defaults to 4.
+	#[serde(default = "default_max_claim_groups_parallel")]
+	pub max_claim_groups_parallel: usize,
+	/// Relay of fill proofs to the origin chain over Wormhole or LayerZero.
+	/// When set, orders become claimable once their relayed proof has been
+	/// received on the origin chain.
+	#[serde(default)]
+	pub relay: Option<ProofRelayConfig>,
+}
+
+impl SettlementConfig {
+	/// Returns the EIP-712 domain of the settlement deployed on a chain.
+	pub fn domain_for_chain(&self, chain_id: u64) -> Option<&DomainConfig> {
+		self.domain
+			.iter()
+			.find(|domain| domain.chain_id == chain_id)
+	}
+}
+
+/// Configuration of the claim success rate circuit breaker.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimCircuitBreakerConfig {
+	/// Success rate in percent over the last hour below which auto-claiming
+	/// of an implementation is disabled. Defaults to 50.
+	#[serde(default = "default_min_success_rate_pct")]
+	pub min_success_rate_pct: f64,
+	/// Number of claims within the last hour required before the circuit
+	/// can open. Defaults to 5.
+	#[serde(default = "default_min_claims")]
+	pub min_claims: usize,
+}
+
+impl Default for ClaimCircuitBreakerConfig {
+	fn default() -> Self {
+		Self {
+			min_success_rate_pct: default_min_success_rate_pct(),
+			min_claims: default_min_claims(),
+		}
+	}
+}
+
+/// Returns the default minimum claim success rate in percent.
+fn default_min_success_rate_pct() -> f64 {
+	50.0
+}
+
+/// Returns the default number of claims required to judge the success rate.
+fn default_min_claims() -> usize {
+	5
+}
+
+/// Returns the default oracle health check interval in seconds.
+///
+/// This provides a default value of 60 seconds between oracle health probes
+/// when no explicit interval is configured.
+fn default_oracle_health_check_interval_seconds() -> u64 {
+	60
+}
+
+/// Returns the default time fill proofs are cached.
+///
+/// This provides a default value of 3600 seconds, after which a proof is
+/// attested again.
+fn default_proof_cache_ttl_seconds() -> u64 {
+	3600
+}
+
+/// Returns the default number of settlement implementations claimed concurrently.
+///
+/// This provides a default value of 4.
+fn default_max_claim_groups_parallel() -> usize {
+	4
+}
+
+/// Configuration for batching claims.
+///
+/// The batch size adapts between `min_size` and `max_size` to amortize the
+/// fixed overhead of a batch over the observed gas cost of each claim. Claims
+/// are only combined into one transaction with `solver.use_wallet_batch`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimBatchConfig {
+	/// Minimum number of claims per batch.
+	/// Defaults to 1 if not specified.
+	#[serde(default = "default_batch_min_size")]
+	pub min_size: usize,
+	/// Maximum number of claims per batch.
+	/// Defaults to 1 if not specified, claiming each order on its own.
+	#[serde(default = "default_batch_max_size")]
+	pub max_size: usize,
+	/// Gas paid once per batch regardless of the number of claims.
+	/// Defaults to 21000 if not specified.
+	#[serde(default = "default_batch_fixed_overhead_gas")]
+	pub fixed_overhead_gas: u64,
+	/// Seconds after which a partial batch is claimed.
+	/// Defaults to 30 seconds if not specified.
+	#[serde(default = "default_batch_timeout_seconds")]
+	pub batch_timeout_seconds: u64,
+}
+
+impl Default for ClaimBatchConfig {
+	fn default() -> Self {
+		Self {
+			min_size: default_batch_min_size(),
+			max_size: default_batch_max_size(),
+			fixed_overhead_gas: default_batch_fixed_overhead_gas(),
+			batch_timeout_seconds: default_batch_timeout_seconds(),
+		}
+	}
+}
+
+/// Returns the default minimum claim batch size.
+fn default_batch_min_size() -> usize {
+	1
+}
+
+/// Returns the default maximum claim batch size.
+fn default_batch_max_size() -> usize {
+	1
+}
+
+/// Returns the default fixed gas overhead of a claim batch.
+///
+/// This is the base cost of a transaction.
+fn default_batch_fixed_overhead_gas() -> u64 {
+	21_000
+}
+
+/// Returns the default claim batch timeout in seconds.
+fn default_batch_timeout_seconds() -> u64 {
+	30
+}
+
+/// Configuration for token price feeds.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PriceConfig {
+	/// Which implementation to use as primary.
+	pub primary: String,
+	/// Map of price feed implementation names to their configurations.
+	pub implementations: HashMap<String, toml::Value>,
+	/// Number of days to retain historical price snapshots.
+	/// Defaults to 30 days if not specified.
+	#[serde(default = "default_history_retention_days")]
+	pub history_retention_days: u64,
+	/// Wrapped native token address per chain, used to price gas costs.
+	#[serde(default)]
+	pub native_tokens: HashMap<u64, String>,
+}
+
+/// Returns the default price history retention in days.
+///
+/// This provides a default value of 30 days for historical price snapshots
+/// when no explicit retention is configured.
+fn default_history_retention_days() -> u64 {
+	30
+}
+
+/// Configuration for auctions on intents that require competitive filling.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuctionConfig {
+	/// Base URL of the auction service receiving bids at `POST /auction/bid`.
+	pub auction_endpoint: String,
+	/// Seconds to wait for competing bids before deciding whether to fill.
+	pub auction_wait_seconds: u64,
+	/// Minimum margin, in basis points, by which this solver's profit must
+	/// exceed the best competing bid. Defaults to 0 if not specified.
+	#[serde(default)]
+	pub min_profit_advantage_bps: u32,
+}
+
+/// Configuration for order status webhooks.
+///
+/// Every order event is posted to each URL. Failed deliveries are retried
+/// with exponential backoff.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookConfig {
+	/// URLs receiving the order events.
+	pub urls: Vec<String>,
+	/// Timeout of a single delivery in seconds.
+	/// Defaults to 10 seconds if not specified.
+	#[serde(default = "default_webhook_timeout_seconds")]
+	pub timeout_seconds: u64,
+	/// Delay before the first retry, doubled after every further failure.
+	/// Defaults to 5 seconds if not specified.
+	#[serde(default = "default_webhook_base_delay_seconds")]
+	pub base_delay_seconds: u64,
+	/// Maximum delay between retries.
+	/// Defaults to 3600 seconds if not specified.
+	#[serde(default = "default_webhook_max_delay_seconds")]
+	pub max_delay_seconds: u64,
+	/// Number of failed deliveries after which a payload is dropped.
+	/// Defaults to 10 if not specified.
+	#[serde(default = "default_webhook_max_attempts")]
+	pub max_attempts: u32,
+}
+
+/// Returns the default timeout of webhook deliveries in seconds.
+///
+/// This provides a default value of 10 seconds.
+fn default_webhook_timeout_seconds() -> u64 {
+	10
+}
+
+/// Returns the default delay before the first webhook retry in seconds.
+///
+/// This provides a default value of 5 seconds.
+fn default_webhook_base_delay_seconds() -> u64 {
+	5
+}
+
+/// Returns the default maximum delay between webhook retries in seconds.
+///
+/// This provides a default value of 3600 seconds (1 hour).
+fn default_webhook_max_delay_seconds() -> u64 {
+	3600
+}
+
+/// Returns the default maximum number of webhook delivery attempts.
+///
+/// This provides a default value of 10 attempts.
+fn default_webhook_max_attempts() -> u32 {
+	10
+}
+
+/// Configuration for telemetry export.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TelemetryConfig {
+	/// Sink receiving counters, gauges and histograms. Defaults to none.
+	#[serde(default)]
+	pub sink: TelemetrySinkConfig,
+}
+
+/// External sink for solver telemetry.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TelemetrySinkConfig {
+	/// Telemetry is not exported.
+	#[default]
+	None,
+	/// StatsD server reached over UDP, using DogStatsD-style tags for labels.
+	Statsd {
+		/// Address of the StatsD server, e.g. `127.0.0.1:8125`.
+		address: String,
+		/// Prefix prepended to every metric name.
+		#[serde(default = "default_telemetry_prefix")]
+		prefix: String,
+	},
+	/// InfluxDB server reached over its HTTP write API.
+	Influxdb {
+		/// Base URL of the InfluxDB server, e.g. `http://localhost:8086`.
+		url: String,
+		/// Organization owning the bucket.
+		org: String,
+		/// Bucket metrics are written to.
+		bucket: String,
+		/// API token used for authentication.
+		token: Option<String>,
+		/// Prefix prepended to every measurement name.
+		#[serde(default = "default_telemetry_prefix")]
+		prefix: String,
+	},
+}
+
+/// Returns the default prefix for exported metric names.
+fn default_telemetry_prefix() -> String {
+	"oif_solver".to_string()
 }
 
 /// Implementation references for API functionality.
@@ -213,6 +1118,30 @@ pub struct ApiConfig {
 	pub rate_limiting: Option<RateLimitConfig>,
 	/// CORS configuration.
 	pub cors: Option<CorsConfig>,
+	/// Bearer token required by admin endpoints that change solver behavior.
+	/// These endpoints are disabled when no token is configured. The other
+	/// admin endpoints accept it or an API key with the admin permission.
+	#[serde(default)]
+	pub admin_token: Option<String>,
+	/// Whether the Server-Sent Events endpoint for order updates is enabled.
+	#[serde(default)]
+	pub sse_enabled: bool,
+	/// Whether order and admin endpoints require an API key with the matching
+	/// permission. Keys are managed through /admin/api-keys.
+	#[serde(default)]
+	pub require_api_keys: bool,
+	/// Whether read-only admin endpoints are served without the admin token
+	/// or an admin API key. Only meant for local development.
+	#[serde(default)]
+	pub public_admin_endpoints: bool,
+	/// Number of orders that may be executed against a single quote.
+	/// Defaults to 1 if not specified.
+	#[serde(default = "default_quote_max_fills")]
+	pub quote_max_fills: u32,
+	/// Number of requests of a batch quote request processed concurrently.
+	/// Defaults to 8 if not specified.
+	#[serde(default = "default_batch_concurrency")]
+	pub batch_concurrency: usize,
 }
 
 /// Rate limiting configuration.
@@ -259,6 +1188,20 @@ fn default_api_timeout() -> u64 {
 	30
 }
 
+/// Returns the default number of fills allowed per quote.
+///
+/// This provides a default of 1, so that each quote backs a single order.
+fn default_quote_max_fills() -> u32 {
+	1
+}
+
+/// Returns the default number of batched quote requests processed at once.
+///
+/// This provides a default of 8 concurrent requests per batch.
+fn default_batch_concurrency() -> usize {
+	8
+}
+
 /// Returns the default maximum request size in bytes.
 ///
 /// This provides a default maximum request size of 1MB (1024 * 1024 bytes)
@@ -267,13 +1210,68 @@ fn default_max_request_size() -> usize {
 	1024 * 1024 // 1MB
 }
 
-/// Resolves environment variables in a string.
+/// Configuration source with its environment variables resolved.
+///
+/// Keeps the raw source next to the resolved one, so that errors found in
+/// the resolved source are reported against the raw source. Snippets then
+/// show variable references instead of their values, which may be secrets.
+pub(crate) struct ResolvedSource {
+	/// Name of the source, usually its file path.
+	name: String,
+	/// Source as written, with unresolved variable references.
+	raw: String,
+	/// Source with the variable references replaced by their values.
+	pub(crate) resolved: String,
+	/// Ranges of the references in the raw source and of their values in the
+	/// resolved source, in source order.
+	replacements: Vec<(std::ops::Range<usize>, std::ops::Range<usize>)>,
+}
+
+impl ResolvedSource {
+	/// Creates a parse error pointing at the location of a TOML error found
+	/// in the resolved source.
+	pub(crate) fn toml_error(&self, err: toml::de::Error) -> ConfigError {
+		ConfigError::Parse {
+			message: err.message().to_string(),
+			span: err.span().map(|span| {
+				let start = self.raw_offset(span.start, false);
+				let end = self.raw_offset(span.end, true).max(start);
+				SourceSpan::from(start..end)
+			}),
+			src: Some(NamedSource::new(&self.name, self.raw.clone())),
+		}
+	}
+
+	/// Maps an offset in the resolved source to the raw source.
+	///
+	/// Offsets within a resolved value map to the start of its reference,
+	/// or to its end if `end` is set.
+	fn raw_offset(&self, offset: usize, end: bool) -> usize {
+		let mut mapped = offset;
+		for (raw, resolved) in &self.replacements {
+			if offset < resolved.start || (end && offset == resolved.start) {
+				break;
+			}
+			if offset < resolved.end || (end && offset == resolved.end) {
+				return if end { raw.end } else { raw.start };
+			}
+			mapped = offset - resolved.end + raw.end;
+		}
+		mapped
+	}
+}
+
+/// Resolves environment variables in a string.
 ///
 /// Replaces ${VAR_NAME} with the value of the environment variable VAR_NAME.
 /// Supports default values with ${VAR_NAME:-default_value}.
 ///
-/// Input strings are limited to 1MB to prevent ReDoS attacks.
-pub(crate) fn resolve_env_vars(input: &str) -> Result<String, ConfigError> {
+/// Input strings are limited to 1MB to prevent ReDoS attacks. A missing
+/// variable is reported with its location in the source named `source_name`.
+pub(crate) fn resolve_env_vars(
+	input: &str,
+	source_name: &str,
+) -> Result<ResolvedSource, ConfigError> {
 	// Limit input size to prevent ReDoS attacks
 	const MAX_INPUT_SIZE: usize = 1024 * 1024; // 1MB
 	if input.len() > MAX_INPUT_SIZE {
@@ -285,7 +1283,7 @@ pub(crate) fn resolve_env_vars(input: &str) -> Result<String, ConfigError> {
 	}
 
 	let re = Regex::new(r"\$\{([A-Z_][A-Z0-9_]{0,127})(?::-([^}]{0,256}))?\}")
-		.map_err(|e| ConfigError::Parse(format!("Regex error: {}", e)))?;
+		.map_err(|e| ConfigError::parse(format!("Regex error: {}", e)))?;
 
 	let mut result = input.to_string();
 	let mut replacements = Vec::new();
@@ -301,10 +1299,11 @@ pub(crate) fn resolve_env_vars(input: &str) -> Result<String, ConfigError> {
 				if let Some(default) = default_value {
 					default.to_string()
 				} else {
-					return Err(ConfigError::Validation(format!(
-						"Environment variable '{}' not found",
-						var_name
-					)));
+					return Err(ConfigError::MissingEnvVar {
+						name: var_name.to_string(),
+						src: NamedSource::new(source_name, input.to_string()),
+						span: (full_match.start(), full_match.len()).into(),
+					});
 				}
 			},
 		};
@@ -317,7 +1316,23 @@ pub(crate) fn resolve_env_vars(input: &str) -> Result<String, ConfigError> {
 		result.replace_range(start..end, value);
 	}
 
-	Ok(result)
+	// Locate the resolved values, shifted by the preceding replacements
+	let mut shift = 0isize;
+	let replacements = replacements
+		.into_iter()
+		.map(|(start, end, value)| {
+			let resolved_start = (start as isize + shift) as usize;
+			shift += value.len() as isize - (end - start) as isize;
+			(start..end, resolved_start..resolved_start + value.len())
+		})
+		.collect();
+
+	Ok(ResolvedSource {
+		name: source_name.to_string(),
+		raw: input.to_string(),
+		resolved: result,
+		replacements,
+	})
 }
 
 impl Config {
@@ -338,6 +1353,33 @@ impl Config {
 		loader.load_config(file_name).await
 	}
 
+	/// Returns one configuration per solver identity.
+	///
+	/// Without `[[solvers]]` entries this is the configuration itself. Otherwise
+	/// each entry is applied on top of the base configuration.
+	pub fn tenants(&self) -> Vec<Config> {
+		if self.solvers.is_empty() {
+			return vec![self.clone()];
+		}
+
+		let mut base = self.clone();
+		base.solvers.clear();
+		self.solvers
+			.iter()
+			.map(|tenant| {
+				let mut config = base.clone();
+				config.solver = tenant.solver.clone();
+				if let Some(account) = &tenant.account {
+					config.account = account.clone();
+				}
+				if let Some(strategy) = &tenant.strategy {
+					config.order.strategy = strategy.clone();
+				}
+				config
+			})
+			.collect()
+	}
+
 	/// Validates the configuration to ensure all required fields are properly set.
 	///
 	/// This method performs comprehensive validation across all configuration sections:
@@ -437,6 +1479,163 @@ impl Config {
 				"min_confirmations cannot exceed 100".into(),
 			));
 		}
+		let gas_bid = &self.delivery.gas_bid;
+		if gas_bid.target_percentile > 100 {
+			return Err(ConfigError::Validation(
+				"delivery.gas_bid.target_percentile cannot exceed 100".into(),
+			));
+		}
+		if gas_bid.base_fee_multiplier.is_nan() || gas_bid.base_fee_multiplier < 1.0 {
+			return Err(ConfigError::Validation(
+				"delivery.gas_bid.base_fee_multiplier must be at least 1.0".into(),
+			));
+		}
+		if gas_bid.min_priority_fee_gwei > gas_bid.max_priority_fee_gwei {
+			return Err(ConfigError::Validation(
+				"delivery.gas_bid.min_priority_fee_gwei cannot exceed max_priority_fee_gwei".into(),
+			));
+		}
+		if self.delivery.provider_mode == (ProviderMode::Anvil { port: 0 }) {
+			return Err(ConfigError::Validation(
+				"delivery.provider_mode.port must be greater than 0".into(),
+			));
+		}
+		for (chain_id, limit) in &self.delivery.rate_limits {
+			if limit.rps == 0 {
+				return Err(ConfigError::Validation(format!(
+					"delivery.rate_limits.{}.rps must be at least 1",
+					chain_id
+				)));
+			}
+		}
+
+		if let Some(rebalancing) = &self.solver.rebalancing {
+			let mut pairs = HashSet::new();
+			for bridge in &rebalancing.bridges {
+				for chain_id in [bridge.from_chain, bridge.to_chain] {
+					if !self.networks.contains_key(&chain_id) {
+						return Err(ConfigError::Validation(format!(
+							"solver.rebalancing bridge references unknown network {}",
+							chain_id
+						)));
+					}
+				}
+				if bridge.bridge_address.0 == OP_L2_STANDARD_BRIDGE {
+					return Err(ConfigError::Validation(format!(
+						"solver.rebalancing bridge from chain {} uses the L2 standard bridge, whose withdrawals must be proven and finalized on L1, which rebalancing does not support",
+						bridge.from_chain
+					)));
+				}
+				for (field, value) in [
+					("low_threshold", &bridge.low_threshold),
+					("amount", &bridge.amount),
+				] {
+					if value.is_empty() || !value.chars().all(|c| c.is_ascii_digit()) {
+						return Err(ConfigError::Validation(format!(
+							"solver.rebalancing bridge {} must be a decimal amount",
+							field
+						)));
+					}
+				}
+				let pair = (
+					bridge.from_chain,
+					bridge.from_token.clone(),
+					bridge.to_chain,
+					bridge.to_token.clone(),
+				);
+				if !pairs.insert(pair) {
+					return Err(ConfigError::Validation(format!(
+						"solver.rebalancing has several bridges from chain {} to chain {} for the same token pair",
+						bridge.from_chain, bridge.to_chain
+					)));
+				}
+			}
+		}
+
+		for (chain_id, concurrency) in &self.solver.concurrency {
+			if concurrency.max_concurrent_fills == 0 {
+				return Err(ConfigError::Validation(format!(
+					"solver.concurrency.{}.max_concurrent_fills must be at least 1",
+					chain_id
+				)));
+			}
+		}
+
+		for (key, eth) in &self.solver.gas_budget {
+			if parse_gas_budget_key(key).is_none() {
+				return Err(ConfigError::Validation(format!(
+					"Invalid solver.gas_budget key '{}', expected '{{chain_id}}_daily_eth'",
+					key
+				)));
+			}
+			if !eth.is_finite() || *eth <= 0.0 {
+				return Err(ConfigError::Validation(format!(
+					"solver.gas_budget.{} must be greater than 0",
+					key
+				)));
+			}
+		}
+
+		if let Some(ref arbitrage) = self.solver.arbitrage {
+			if self.price.is_none() {
+				return Err(ConfigError::Validation(
+					"solver.arbitrage requires a [price] section".into(),
+				));
+			}
+			if arbitrage.interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"solver.arbitrage.interval_seconds must be greater than 0".into(),
+				));
+			}
+		}
+
+		// Auction bids are priced in USD
+		if self.auction.is_some() && self.price.is_none() {
+			return Err(ConfigError::Validation(
+				"auction requires a [price] section".into(),
+			));
+		}
+
+		if let Some(ref gossip) = self.solver.gossip {
+			if gossip.enabled
+				&& gossip
+					.listen_address
+					.parse::<std::net::SocketAddr>()
+					.is_err()
+			{
+				return Err(ConfigError::Validation(format!(
+					"solver.gossip.listen_address '{}' is not a valid socket address",
+					gossip.listen_address
+				)));
+			}
+			if gossip.reputation_broadcast_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"solver.gossip.reputation_broadcast_interval_seconds must be greater than 0"
+						.into(),
+				));
+			}
+			if gossip.enabled
+				&& gossip
+					.shared_secret
+					.as_deref()
+					.is_none_or(|secret| secret.is_empty())
+			{
+				return Err(ConfigError::Validation(
+					"solver.gossip.shared_secret is required when gossiping is enabled".into(),
+				));
+			}
+		}
+
+		if self.solver.event_log.retention_days == 0 {
+			return Err(ConfigError::Validation(
+				"solver.event_log.retention_days must be greater than 0".into(),
+			));
+		}
+		if self.solver.event_log.buffer_size == 0 {
+			return Err(ConfigError::Validation(
+				"solver.event_log.buffer_size must be greater than 0".into(),
+			));
+		}
 
 		// Validate account config
 		if self.account.implementations.is_empty() {
@@ -451,6 +1650,106 @@ impl Config {
 				"At least one discovery implementation required".into(),
 			));
 		}
+		if let Some(ref health) = self.discovery.health {
+			if health.inactivity_threshold_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Discovery health inactivity_threshold_seconds must be greater than 0".into(),
+				));
+			}
+			if health.check_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Discovery health check_interval_seconds must be greater than 0".into(),
+				));
+			}
+			for source in &health.sources {
+				if !self.discovery.implementations.contains_key(source) {
+					return Err(ConfigError::Validation(format!(
+						"Discovery health source '{}' not found in discovery.implementations",
+						source
+					)));
+				}
+			}
+		}
+		for enricher in &self.discovery.enrichers {
+			match enricher.as_str() {
+				"token_metadata" => {},
+				"price" if self.price.is_none() => {
+					return Err(ConfigError::Validation(
+						"Discovery enricher 'price' requires a [price] section".into(),
+					));
+				},
+				"price" => {},
+				other => {
+					return Err(ConfigError::Validation(format!(
+						"Unknown discovery enricher '{}'",
+						other
+					)));
+				},
+			}
+		}
+
+		if let Some(webhooks) = &self.webhooks {
+			if webhooks.max_attempts == 0 {
+				return Err(ConfigError::Validation(
+					"webhooks.max_attempts must be greater than 0".into(),
+				));
+			}
+			if webhooks.base_delay_seconds > webhooks.max_delay_seconds {
+				return Err(ConfigError::Validation(
+					"webhooks.base_delay_seconds must not exceed webhooks.max_delay_seconds".into(),
+				));
+			}
+		}
+
+		if let Some(twap) = &self.order.strategy.twap {
+			if twap.max_fills_per_window == 0 || twap.window_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"order.strategy.twap max_fills_per_window and window_seconds must be greater than 0"
+						.into(),
+				));
+			}
+		}
+		if let Some(gas_window) = &self.order.strategy.gas_window {
+			if gas_window.sample_interval_seconds == 0 || gas_window.lookahead_hours == 0 {
+				return Err(ConfigError::Validation(
+					"order.strategy.gas_window sample_interval_seconds and lookahead_hours must be greater than 0"
+						.into(),
+				));
+			}
+		}
+		if self.settlement.max_claim_groups_parallel == 0 {
+			return Err(ConfigError::Validation(
+				"settlement.max_claim_groups_parallel must be greater than 0".into(),
+			));
+		}
+		if let Some(relay) = &self.settlement.relay {
+			if relay.messengers.is_empty() {
+				return Err(ConfigError::Validation(
+					"settlement.relay.messengers cannot be empty".into(),
+				));
+			}
+			let protocol_configured = match relay.relay_protocol {
+				RelayProtocol::Wormhole => relay.wormhole.is_some(),
+				RelayProtocol::LayerZero => relay.layerzero.is_some(),
+			};
+			if !protocol_configured {
+				return Err(ConfigError::Validation(
+					"settlement.relay requires the parameters of its relay_protocol".into(),
+				));
+			}
+		}
+
+		let filters = &self.discovery.filters;
+		for rule in filters.blacklist.iter().chain(&filters.whitelist) {
+			for pattern in rule.patterns() {
+				glob::Pattern::new(pattern).map_err(|e| {
+					ConfigError::Validation(format!(
+						"Invalid discovery filter pattern '{}': {}",
+						pattern, e
+					))
+				})?;
+			}
+		}
 
 		// Validate order config
 		if self.order.implementations.is_empty() {
@@ -468,6 +1767,20 @@ impl Config {
 				"At least one strategy implementation required".into(),
 			));
 		}
+		for template in &self.order.templates {
+			if template.min_amount > template.max_amount {
+				return Err(ConfigError::Validation(format!(
+					"Order template for {} -> {} has min_amount greater than max_amount",
+					template.input_token, template.output_token
+				)));
+			}
+			if template.rate.is_zero() || template.rate.is_sign_negative() {
+				return Err(ConfigError::Validation(format!(
+					"Order template for {} -> {} must have a positive rate",
+					template.input_token, template.output_token
+				)));
+			}
+		}
 
 		// Validate settlement config
 		if self.settlement.implementations.is_empty() {
@@ -475,6 +1788,34 @@ impl Config {
 				"At least one settlement implementation required".into(),
 			));
 		}
+		if self.settlement.oracle_health_check_interval_seconds == 0 {
+			return Err(ConfigError::Validation(
+				"settlement.oracle_health_check_interval_seconds must be greater than 0".into(),
+			));
+		}
+		if self.settlement.route_discovery_interval_seconds == Some(0) {
+			return Err(ConfigError::Validation(
+				"settlement.route_discovery_interval_seconds must be greater than 0".into(),
+			));
+		}
+		let batch = &self.settlement.batch;
+		if batch.min_size == 0 || batch.max_size < batch.min_size {
+			return Err(ConfigError::Validation(
+				"settlement.batch sizes must satisfy 1 <= min_size <= max_size".into(),
+			));
+		}
+		if batch.batch_timeout_seconds == 0 {
+			return Err(ConfigError::Validation(
+				"settlement.batch.batch_timeout_seconds must be greater than 0".into(),
+			));
+		}
+		let min_success_rate_pct = self.settlement.claim_circuit_breaker.min_success_rate_pct;
+		if !(0.0..=100.0).contains(&min_success_rate_pct) {
+			return Err(ConfigError::Validation(
+				"settlement.claim_circuit_breaker.min_success_rate_pct must be between 0 and 100"
+					.into(),
+			));
+		}
 
 		// Validate API config if enabled
 		if let Some(ref api) = self.api {
@@ -491,6 +1832,47 @@ impl Config {
 			}
 		}
 
+		// Validate price config if present
+		if let Some(ref price) = self.price {
+			if !price.implementations.contains_key(&price.primary) {
+				return Err(ConfigError::Validation(format!(
+					"Primary price implementation '{}' not found in price.implementations",
+					price.primary
+				)));
+			}
+			if price.history_retention_days == 0 {
+				return Err(ConfigError::Validation(
+					"price.history_retention_days must be greater than 0".into(),
+				));
+			}
+		}
+
+		// Validate solver identities
+		let mut solver_ids = HashSet::new();
+		for tenant in &self.solvers {
+			let id = &tenant.solver.id;
+			if id.is_empty() || id.contains([':', '/']) {
+				return Err(ConfigError::Validation(format!(
+					"Invalid solvers id '{}': must be non-empty and not contain ':' or '/'",
+					id
+				)));
+			}
+			if !solver_ids.insert(id.as_str()) {
+				return Err(ConfigError::Validation(format!(
+					"Duplicate solvers id '{}'",
+					id
+				)));
+			}
+			if let Some(ref account) = tenant.account {
+				if account.implementations.is_empty() {
+					return Err(ConfigError::Validation(format!(
+						"Account implementation of solver '{}' cannot be empty",
+						id
+					)));
+				}
+			}
+		}
+
 		// Validate settlement configurations and coverage
 		self.validate_settlement_coverage()?;
 
@@ -508,6 +1890,7 @@ impl Config {
 	/// 2. No two settlements may cover same standard+network
 	/// 3. Every order standard must have at least one settlement
 	/// 4. All network_ids must exist in networks configuration
+	/// 5. If domains are configured, every network_id must have one
 	fn validate_settlement_coverage(&self) -> Result<(), ConfigError> {
 		// Track coverage: (standard, network_id) -> implementation_name
 		let mut coverage: HashMap<(String, u64), String> = HashMap::new();
@@ -561,6 +1944,16 @@ impl Config {
 						impl_name, network_id
 					)));
 				}
+
+				// Quotes are signed against the domain of the input chain
+				if !self.settlement.domain.is_empty()
+					&& self.settlement.domain_for_chain(network_id).is_none()
+				{
+					return Err(ConfigError::Validation(format!(
+						"Settlement '{}' covers network {} which has no settlement.domain",
+						impl_name, network_id
+					)));
+				}
 			}
 		}
 
@@ -582,64 +1975,404 @@ impl Config {
 	}
 }
 
-/// Implementation of FromStr trait for Config to enable parsing from string.
-///
-/// This allows configuration to be parsed from TOML strings using the standard
-/// string parsing interface. Environment variables are resolved and the
-/// configuration is automatically validated after parsing.
-impl FromStr for Config {
-	type Err = ConfigError;
+/// Implementation of FromStr trait for Config to enable parsing from string.
+///
+/// This allows configuration to be parsed from TOML strings using the standard
+/// string parsing interface. Environment variables are resolved and the
+/// configuration is automatically validated after parsing.
+impl Config {
+	/// Parses and validates a configuration, reporting errors against the
+	/// source named `source_name`.
+	pub(crate) fn parse_named(s: &str, source_name: &str) -> Result<Self, ConfigError> {
+		Config::parse_resolved(&resolve_env_vars(s, source_name)?)
+	}
+
+	/// Parses and validates a configuration whose environment variables are
+	/// already resolved.
+	pub(crate) fn parse_resolved(source: &ResolvedSource) -> Result<Self, ConfigError> {
+		let config: Config = toml::from_str(&source.resolved).map_err(|e| source.toml_error(e))?;
+		config.validate()?;
+		Ok(config)
+	}
+}
+
+impl FromStr for Config {
+	type Err = ConfigError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Config::parse_named(s, "configuration")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_env_var_resolution() {
+		// Set up test environment variables
+		std::env::set_var("TEST_HOST", "localhost");
+		std::env::set_var("TEST_PORT", "5432");
+
+		let input = "host = \"${TEST_HOST}:${TEST_PORT}\"";
+		let result = resolve_env_vars(input, "test.toml").unwrap();
+		assert_eq!(result.resolved, "host = \"localhost:5432\"");
+
+		// Clean up
+		std::env::remove_var("TEST_HOST");
+		std::env::remove_var("TEST_PORT");
+	}
+
+	#[test]
+	fn test_env_var_with_default() {
+		let input = "value = \"${MISSING_VAR:-default_value}\"";
+		let result = resolve_env_vars(input, "test.toml").unwrap();
+		assert_eq!(result.resolved, "value = \"default_value\"");
+	}
+
+	#[test]
+	fn test_missing_env_var_error() {
+		let input = "value = \"${MISSING_VAR}\"";
+		let err = resolve_env_vars(input, "test.toml").err().unwrap();
+		assert!(err.to_string().contains("MISSING_VAR"));
+		match err {
+			ConfigError::MissingEnvVar { span, .. } => {
+				assert_eq!(span, SourceSpan::from((9, 14)));
+			},
+			other => panic!("unexpected error: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_parse_error_snippet_hides_resolved_values() {
+		std::env::set_var("TEST_SNIPPET_SECRET", "secret-value");
+
+		let input = "[solver]\nid = ${TEST_SNIPPET_SECRET}\n";
+		let err = Config::parse_named(input, "main.toml").unwrap_err();
+		match err {
+			ConfigError::Parse {
+				src: Some(src),
+				span: Some(span),
+				..
+			} => {
+				assert_eq!(src.inner(), input);
+				// The span lies within the reference to the variable
+				let reference = input.find("${").unwrap();
+				assert!(span.offset() >= reference);
+				assert!(span.offset() + span.len() <= input.find('}').unwrap() + 1);
+			},
+			other => panic!("unexpected error: {:?}", other),
+		}
+
+		std::env::remove_var("TEST_SNIPPET_SECRET");
+	}
+
+	#[test]
+	fn test_resolved_offsets_map_to_raw_source() {
+		std::env::set_var("TEST_OFFSET_VALUE", "abcdef");
+
+		let source = resolve_env_vars("a = \"${TEST_OFFSET_VALUE}\" b", "test.toml").unwrap();
+		assert_eq!(source.resolved, "a = \"abcdef\" b");
+		// Before, within and after the resolved value
+		assert_eq!(source.raw_offset(2, false), 2);
+		assert_eq!(source.raw_offset(7, false), 5);
+		assert_eq!(source.raw_offset(7, true), 25);
+		assert_eq!(source.raw_offset(13, false), 27);
+
+		std::env::remove_var("TEST_OFFSET_VALUE");
+	}
+
+	#[test]
+	fn test_parse_error_diagnostic() {
+		let err = Config::parse_named("[solver]\nid = ", "main.toml").unwrap_err();
+		assert!(matches!(
+			&err,
+			ConfigError::Parse {
+				src: Some(_),
+				span: Some(_),
+				..
+			}
+		));
+		assert_eq!(err.code().unwrap().to_string(), "config::parse");
+		assert!(err.help().is_some());
+		assert_eq!(err.url().unwrap().to_string(), CONFIG_DOCS_URL);
+	}
+
+	#[test]
+	fn test_config_with_env_vars() {
+		// Set environment variable
+		std::env::set_var("TEST_SOLVER_ID", "test-solver");
+
+		let config_str = r#"
+[solver]
+id = "${TEST_SOLVER_ID}"
+monitoring_timeout_minutes = 5
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "${TEST_PRIVATE_KEY:-0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80}"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.test]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+
+[settlement]
+[settlement.implementations.test]
+order = "test"
+network_ids = [1, 2]
+"#;
+
+		let config: Config = config_str.parse().unwrap();
+		assert_eq!(config.solver.id, "test-solver");
+
+		// Clean up
+		std::env::remove_var("TEST_SOLVER_ID");
+	}
+
+	#[test]
+	fn test_duplicate_settlement_coverage_rejected() {
+		let config_str = r#"
+[solver]
+id = "test"
+monitoring_timeout_minutes = 5
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.3]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.3.rpc_urls]]
+http = "http://localhost:8547"
+[[networks.3.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.eip7683]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+
+[settlement.implementations.impl1]
+order = "eip7683"
+network_ids = [1, 2]
+
+[settlement.implementations.impl2]
+order = "eip7683"
+network_ids = [2, 3]  # Network 2 overlaps with impl1
+"#;
+
+		let result = Config::from_str(config_str);
+		assert!(result.is_err());
+		let err = result.unwrap_err();
+		// The test should fail because network 2 is covered by both impl1 and impl2
+		// Check for the key parts of the error message
+		let error_msg = err.to_string();
+		assert!(
+			error_msg.contains("network 2")
+				&& error_msg.contains("impl1")
+				&& error_msg.contains("impl2"),
+			"Expected duplicate coverage error for network 2, got: {}",
+			err
+		);
+	}
+
+	#[test]
+	fn test_missing_settlement_standard_rejected() {
+		let config_str = r#"
+[solver]
+id = "test"
+monitoring_timeout_minutes = 5
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.eip7683]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+
+[settlement.implementations.impl1]
+# Missing 'standard' field
+network_ids = [1, 2]
+"#;
+
+		let result = Config::from_str(config_str);
+		assert!(result.is_err());
+		let err = result.unwrap_err();
+		assert!(err.to_string().contains("missing 'order' field"));
+	}
+
+	#[test]
+	fn test_settlement_references_invalid_network() {
+		let config_str = r#"
+[solver]
+id = "test"
+monitoring_timeout_minutes = 5
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
 
-	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let resolved = resolve_env_vars(s)?;
-		let config: Config = toml::from_str(&resolved)?;
-		config.validate()?;
-		Ok(config)
-	}
-}
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+[delivery]
+[delivery.implementations.test]
 
-	#[test]
-	fn test_env_var_resolution() {
-		// Set up test environment variables
-		std::env::set_var("TEST_HOST", "localhost");
-		std::env::set_var("TEST_PORT", "5432");
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
 
-		let input = "host = \"${TEST_HOST}:${TEST_PORT}\"";
-		let result = resolve_env_vars(input).unwrap();
-		assert_eq!(result, "host = \"localhost:5432\"");
+[discovery]
+[discovery.implementations.test]
 
-		// Clean up
-		std::env::remove_var("TEST_HOST");
-		std::env::remove_var("TEST_PORT");
-	}
+[order]
+[order.implementations.eip7683]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
 
-	#[test]
-	fn test_env_var_with_default() {
-		let input = "value = \"${MISSING_VAR:-default_value}\"";
-		let result = resolve_env_vars(input).unwrap();
-		assert_eq!(result, "value = \"default_value\"");
-	}
+[settlement.implementations.impl1]
+order = "eip7683"
+network_ids = [1, 2, 999]  # Network 999 doesn't exist
+"#;
 
-	#[test]
-	fn test_missing_env_var_error() {
-		let input = "value = \"${MISSING_VAR}\"";
-		let result = resolve_env_vars(input);
+		let result = Config::from_str(config_str);
 		assert!(result.is_err());
-		assert!(result.unwrap_err().to_string().contains("MISSING_VAR"));
+		let err = result.unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("references network 999 which doesn't exist"));
 	}
 
 	#[test]
-	fn test_config_with_env_vars() {
-		// Set environment variable
-		std::env::set_var("TEST_SOLVER_ID", "test-solver");
-
-		let config_str = r#"
+	fn test_settlement_network_without_domain() {
+		let base = r#"
 [solver]
-id = "${TEST_SOLVER_ID}"
+id = "test"
 monitoring_timeout_minutes = 5
 
 [networks.1]
@@ -673,36 +2406,58 @@ cleanup_interval_seconds = 3600
 [account]
 primary = "local"
 [account.implementations.local]
-private_key = "${TEST_PRIVATE_KEY:-0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80}"
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
 
 [discovery]
 [discovery.implementations.test]
 
 [order]
-[order.implementations.test]
+[order.implementations.eip7683]
 [order.strategy]
 primary = "simple"
 [order.strategy.implementations.simple]
 
-[settlement]
-[settlement.implementations.test]
-order = "test"
+[settlement.implementations.impl1]
+order = "eip7683"
 network_ids = [1, 2]
-"#;
 
-		let config: Config = config_str.parse().unwrap();
-		assert_eq!(config.solver.id, "test-solver");
+[[settlement.domain]]
+name = "Escrow A"
+chain_id = 1
+address = "0x1234567890123456789012345678901234567890"
+"#;
 
-		// Clean up
-		std::env::remove_var("TEST_SOLVER_ID");
+		let err = Config::from_str(base).unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("covers network 2 which has no settlement.domain"));
+
+		let config = Config::from_str(&format!(
+			r#"{}
+[[settlement.domain]]
+name = "Escrow B"
+chain_id = 2
+address = "0x0987654321098765432109876543210987654321"
+"#,
+			base
+		))
+		.unwrap();
+		assert_eq!(
+			config.settlement.domain_for_chain(2).unwrap().name,
+			"Escrow B"
+		);
 	}
 
 	#[test]
-	fn test_duplicate_settlement_coverage_rejected() {
-		let config_str = r#"
+	fn test_rebalancing_bridges() {
+		let config_with_bridges = |bridges: &str| {
+			format!(
+				r#"
 [solver]
 id = "test"
 monitoring_timeout_minutes = 5
+auto_rebalance = true
+{}
 
 [networks.1]
 input_settler_address = "0x1234567890123456789012345678901234567890"
@@ -724,12 +2479,95 @@ address = "0xabcdef1234567890abcdef1234567890abcdef12"
 symbol = "TEST"
 decimals = 18
 
-[networks.3]
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.eip7683]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+
+[settlement.implementations.impl1]
+order = "eip7683"
+network_ids = [1, 2]
+"#,
+				bridges
+			)
+		};
+		let bridge = r#"
+[[solver.rebalancing.bridges]]
+from_chain = 1
+to_chain = 2
+from_token = "0xabcdef1234567890abcdef1234567890abcdef12"
+to_token = "0xabcdef1234567890abcdef1234567890abcdef12"
+bridge_address = "0x1234567890123456789012345678901234567890"
+low_threshold = "1000"
+amount = "5000"
+"#;
+
+		let config = Config::from_str(&config_with_bridges(bridge)).unwrap();
+		let rebalancing = config.solver.rebalancing.unwrap();
+		assert_eq!(rebalancing.interval_seconds, 60);
+		assert_eq!(rebalancing.bridges[0].amount, "5000");
+
+		// One bridge per token pair
+		let err = Config::from_str(&config_with_bridges(&bridge.repeat(2))).unwrap_err();
+		assert!(err.to_string().contains("same token pair"));
+
+		let err =
+			Config::from_str(&config_with_bridges(&bridge.replace("5000", "5e3"))).unwrap_err();
+		assert!(err.to_string().contains("amount must be a decimal amount"));
+
+		// Withdrawals from OP Stack L2s are not supported
+		let err = Config::from_str(&config_with_bridges(&bridge.replace(
+			"0x1234567890123456789012345678901234567890",
+			"0x4200000000000000000000000000000000000010",
+		)))
+		.unwrap_err();
+		assert!(err.to_string().contains("L2 standard bridge"));
+	}
+
+	#[test]
+	fn test_chain_concurrency() {
+		let config_with_concurrency = |max_concurrent_fills: usize| {
+			format!(
+				r#"
+[solver]
+id = "test"
+monitoring_timeout_minutes = 5
+[solver.concurrency.137]
+max_concurrent_fills = {}
+
+[networks.1]
 input_settler_address = "0x1234567890123456789012345678901234567890"
 output_settler_address = "0x0987654321098765432109876543210987654321"
-[[networks.3.rpc_urls]]
-http = "http://localhost:8547"
-[[networks.3.tokens]]
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.137]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.137.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.137.tokens]]
 address = "0xabcdef1234567890abcdef1234567890abcdef12"
 symbol = "TEST"
 decimals = 18
@@ -758,31 +2596,26 @@ primary = "simple"
 
 [settlement.implementations.impl1]
 order = "eip7683"
-network_ids = [1, 2]
+network_ids = [1, 137]
+"#,
+				max_concurrent_fills
+			)
+		};
 
-[settlement.implementations.impl2]
-order = "eip7683"
-network_ids = [2, 3]  # Network 2 overlaps with impl1
-"#;
+		let config = Config::from_str(&config_with_concurrency(5)).unwrap();
+		assert_eq!(config.solver.concurrency[&137].max_concurrent_fills, 5);
 
-		let result = Config::from_str(config_str);
-		assert!(result.is_err());
-		let err = result.unwrap_err();
-		// The test should fail because network 2 is covered by both impl1 and impl2
-		// Check for the key parts of the error message
-		let error_msg = err.to_string();
-		assert!(
-			error_msg.contains("network 2")
-				&& error_msg.contains("impl1")
-				&& error_msg.contains("impl2"),
-			"Expected duplicate coverage error for network 2, got: {}",
-			err
-		);
+		let err = Config::from_str(&config_with_concurrency(0)).unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("max_concurrent_fills must be at least 1"));
 	}
 
 	#[test]
-	fn test_missing_settlement_standard_rejected() {
-		let config_str = r#"
+	fn test_oracle_health_check_interval() {
+		let config_with_interval = |interval: u64| {
+			format!(
+				r#"
 [solver]
 id = "test"
 monitoring_timeout_minutes = 5
@@ -797,12 +2630,12 @@ address = "0xabcdef1234567890abcdef1234567890abcdef12"
 symbol = "TEST"
 decimals = 18
 
-[networks.2]
+[networks.137]
 input_settler_address = "0x1234567890123456789012345678901234567890"
 output_settler_address = "0x0987654321098765432109876543210987654321"
-[[networks.2.rpc_urls]]
+[[networks.137.rpc_urls]]
 http = "http://localhost:8546"
-[[networks.2.tokens]]
+[[networks.137.tokens]]
 address = "0xabcdef1234567890abcdef1234567890abcdef12"
 symbol = "TEST"
 decimals = 18
@@ -829,20 +2662,30 @@ private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff8
 primary = "simple"
 [order.strategy.implementations.simple]
 
+[settlement]
+oracle_health_check_interval_seconds = {}
 [settlement.implementations.impl1]
-# Missing 'standard' field
-network_ids = [1, 2]
-"#;
+order = "eip7683"
+network_ids = [1, 137]
+"#,
+				interval
+			)
+		};
 
-		let result = Config::from_str(config_str);
-		assert!(result.is_err());
-		let err = result.unwrap_err();
-		assert!(err.to_string().contains("missing 'order' field"));
+		let config = Config::from_str(&config_with_interval(30)).unwrap();
+		assert_eq!(config.settlement.oracle_health_check_interval_seconds, 30);
+
+		let err = Config::from_str(&config_with_interval(0)).unwrap_err();
+		assert!(err
+			.to_string()
+			.contains("oracle_health_check_interval_seconds must be greater than 0"));
 	}
 
 	#[test]
-	fn test_settlement_references_invalid_network() {
-		let config_str = r#"
+	fn test_auction_requires_price() {
+		let config_with = |extra: &str| {
+			format!(
+				r#"
 [solver]
 id = "test"
 monitoring_timeout_minutes = 5
@@ -857,12 +2700,12 @@ address = "0xabcdef1234567890abcdef1234567890abcdef12"
 symbol = "TEST"
 decimals = 18
 
-[networks.2]
+[networks.137]
 input_settler_address = "0x1234567890123456789012345678901234567890"
 output_settler_address = "0x0987654321098765432109876543210987654321"
-[[networks.2.rpc_urls]]
+[[networks.137.rpc_urls]]
 http = "http://localhost:8546"
-[[networks.2.tokens]]
+[[networks.137.tokens]]
 address = "0xabcdef1234567890abcdef1234567890abcdef12"
 symbol = "TEST"
 decimals = 18
@@ -889,17 +2732,30 @@ private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff8
 primary = "simple"
 [order.strategy.implementations.simple]
 
+[settlement]
 [settlement.implementations.impl1]
 order = "eip7683"
-network_ids = [1, 2, 999]  # Network 999 doesn't exist
-"#;
+network_ids = [1, 137]
+
+[auction]
+auction_endpoint = "http://localhost:8080"
+auction_wait_seconds = 5
+{}
+"#,
+				extra
+			)
+		};
 
-		let result = Config::from_str(config_str);
-		assert!(result.is_err());
-		let err = result.unwrap_err();
+		let err = Config::from_str(&config_with("")).unwrap_err();
 		assert!(err
 			.to_string()
-			.contains("references network 999 which doesn't exist"));
+			.contains("auction requires a [price] section"));
+
+		let config = Config::from_str(&config_with(
+			"[price]\nprimary = \"test\"\n[price.implementations.test]",
+		))
+		.unwrap();
+		assert!(config.auction.is_some());
 	}
 
 	#[test]
@@ -964,4 +2820,86 @@ network_ids = [1, 2]
 			.to_string()
 			.contains("Order standard 'eip9999' has no settlement implementations"));
 	}
+
+	#[test]
+	fn test_solvers_override_base_config() {
+		let config_str = r#"
+[solver]
+id = "base"
+monitoring_timeout_minutes = 5
+
+[networks.1]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.1.rpc_urls]]
+http = "http://localhost:8545"
+[[networks.1.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[networks.2]
+input_settler_address = "0x1234567890123456789012345678901234567890"
+output_settler_address = "0x0987654321098765432109876543210987654321"
+[[networks.2.rpc_urls]]
+http = "http://localhost:8546"
+[[networks.2.tokens]]
+address = "0xabcdef1234567890abcdef1234567890abcdef12"
+symbol = "TEST"
+decimals = 18
+
+[storage]
+primary = "memory"
+cleanup_interval_seconds = 3600
+[storage.implementations.memory]
+
+[delivery]
+[delivery.implementations.test]
+
+[account]
+primary = "local"
+[account.implementations.local]
+private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+
+[discovery]
+[discovery.implementations.test]
+
+[order]
+[order.implementations.test]
+[order.strategy]
+primary = "simple"
+[order.strategy.implementations.simple]
+
+[settlement]
+[settlement.implementations.test]
+order = "test"
+network_ids = [1, 2]
+
+[[solvers]]
+[solvers.solver]
+id = "alpha"
+monitoring_timeout_minutes = 5
+
+[[solvers]]
+[solvers.solver]
+id = "beta"
+monitoring_timeout_minutes = 10
+[solvers.strategy]
+primary = "aggressive"
+[solvers.strategy.implementations.aggressive]
+"#;
+
+		let config: Config = config_str.parse().unwrap();
+		let tenants = config.tenants();
+		assert_eq!(tenants.len(), 2);
+		assert_eq!(tenants[0].solver.id, "alpha");
+		assert_eq!(tenants[0].order.strategy.primary, "simple");
+		assert_eq!(tenants[1].solver.id, "beta");
+		assert_eq!(tenants[1].order.strategy.primary, "aggressive");
+		assert!(tenants.iter().all(|tenant| tenant.solvers.is_empty()));
+
+		let duplicated = config_str.replace("id = \"beta\"", "id = \"alpha\"");
+		let err = Config::from_str(&duplicated).unwrap_err();
+		assert!(err.to_string().contains("Duplicate solvers id 'alpha'"));
+	}
 }
@@ -3,7 +3,7 @@
 //! This module provides functionality to load configuration from multiple files
 //! and validate that sections are unique across files to prevent merge conflicts.
 
-use crate::{resolve_env_vars, Config, ConfigError};
+use crate::{resolve_env_vars, Config, ConfigError, ResolvedSource};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
@@ -35,16 +35,16 @@ impl ConfigLoader {
 		let config_path = self.resolve_path(config_path)?;
 
 		// Load the main configuration file
-		let main_content = self.load_file(&config_path).await?;
-		let main_toml: toml::Value = toml::from_str(&main_content)?;
+		let main_source = self.load_file(&config_path).await?;
+		let main_toml: toml::Value =
+			toml::from_str(&main_source.resolved).map_err(|e| main_source.toml_error(e))?;
 
 		// Check for includes
 		let includes = self.extract_includes(&main_toml)?;
 
 		// If no includes, just parse and return the main config
 		if includes.is_empty() {
-			let config: Config = main_content.parse()?;
-			return Ok(config);
+			return Config::parse_resolved(&main_source);
 		}
 
 		// Build combined TOML with validation
@@ -52,17 +52,17 @@ impl ConfigLoader {
 			.load_and_combine(main_toml, includes, config_path.clone())
 			.await?;
 
-		// Convert to Config and validate
-		let config_str = toml::to_string(&combined_toml).map_err(|e| {
-			ConfigError::Parse(format!("Failed to serialize combined config: {}", e))
-		})?;
-		let config: Config = config_str.parse()?;
-
+		// Convert to Config and validate. The combined configuration has no
+		// source of its own, so errors are reported without a snippet.
+		let config: Config = combined_toml
+			.try_into()
+			.map_err(|e: toml::de::Error| ConfigError::parse(e.message()))?;
+		config.validate()?;
 		Ok(config)
 	}
 
 	/// Loads a file and resolves environment variables.
-	async fn load_file(&mut self, path: &Path) -> Result<String, ConfigError> {
+	async fn load_file(&mut self, path: &Path) -> Result<ResolvedSource, ConfigError> {
 		// Check for circular includes
 		let canonical_path = path.canonicalize().map_err(|e| {
 			ConfigError::Io(std::io::Error::new(
@@ -79,7 +79,7 @@ impl ConfigLoader {
 		}
 
 		let content = std::fs::read_to_string(path)?;
-		resolve_env_vars(&content)
+		resolve_env_vars(&content, &path.display().to_string())
 	}
 
 	/// Extracts include directives from the configuration.
@@ -133,8 +133,9 @@ impl ConfigLoader {
 		// Load and validate each included file
 		for include_path in includes {
 			let resolved_path = self.resolve_path(&include_path)?;
-			let include_content = self.load_file(&resolved_path).await?;
-			let include_toml: toml::Value = toml::from_str(&include_content)?;
+			let include_source = self.load_file(&resolved_path).await?;
+			let include_toml: toml::Value = toml::from_str(&include_source.resolved)
+				.map_err(|e| include_source.toml_error(e))?;
 
 			// Validate no duplicate sections
 			if let Some(include_table) = include_toml.as_table() {
@@ -0,0 +1,187 @@
+//! Cross-chain price arbitrage detection.
+//!
+//! Periodically prices every configured token on every chain and reports
+//! tokens whose price differs between chains by more than the configured
+//! spread. Detection only: opportunities are published as events for
+//! strategies to act on, no trades are executed here.
+
+use crate::engine::event_bus::EventBus;
+use solver_price::PriceService;
+use solver_types::{NetworksConfig, SolverEvent};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// A price discrepancy of the same token between two chains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageOpportunity {
+	/// Symbol of the token.
+	pub token: String,
+	/// Chain where the token is cheaper.
+	pub from_chain: u64,
+	/// Chain where the token is more expensive.
+	pub to_chain: u64,
+	/// Price difference relative to the cheaper chain, in basis points.
+	pub spread_bps: u64,
+}
+
+/// Detects cross-chain price discrepancies of configured tokens.
+///
+/// Tokens on different chains are considered the same token when they share
+/// a symbol in the networks configuration.
+pub struct ArbitrageDetector {
+	networks: NetworksConfig,
+	price: Arc<PriceService>,
+	event_bus: EventBus,
+	min_spread_bps: u64,
+}
+
+impl ArbitrageDetector {
+	/// Creates a new ArbitrageDetector.
+	pub fn new(
+		networks: NetworksConfig,
+		price: Arc<PriceService>,
+		event_bus: EventBus,
+		min_spread_bps: u64,
+	) -> Self {
+		Self {
+			networks,
+			price,
+			event_bus,
+			min_spread_bps,
+		}
+	}
+
+	/// Prices all tokens and publishes an event for every opportunity found.
+	pub async fn detect(&self) -> Vec<ArbitrageOpportunity> {
+		let mut prices: HashMap<String, Vec<(u64, f64)>> = HashMap::new();
+
+		for (chain_id, network) in &self.networks {
+			for token in &network.tokens {
+				match self
+					.price
+					.get_token_price(chain_id.as_u64(), &token.address.to_string())
+					.await
+				{
+					Ok(price) => prices
+						.entry(token.symbol.clone())
+						.or_default()
+						.push((chain_id.as_u64(), price.price_usd)),
+					Err(e) => {
+						tracing::debug!(
+							chain_id = %chain_id,
+							token = %token.symbol,
+							error = %e,
+							"Skipping token without price"
+						);
+					},
+				}
+			}
+		}
+
+		let opportunities = find_opportunities(&prices, self.min_spread_bps);
+		for opportunity in &opportunities {
+			tracing::info!(
+				token = %opportunity.token,
+				from_chain = opportunity.from_chain,
+				to_chain = opportunity.to_chain,
+				spread_bps = opportunity.spread_bps,
+				"Arbitrage opportunity detected"
+			);
+			self.event_bus
+				.publish(SolverEvent::ArbitrageOpportunity {
+					token: opportunity.token.clone(),
+					from_chain: opportunity.from_chain,
+					to_chain: opportunity.to_chain,
+					spread_bps: opportunity.spread_bps,
+				})
+				.ok();
+		}
+
+		opportunities
+	}
+
+	/// Spawns a task that runs detection at the given interval.
+	pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(interval);
+			loop {
+				ticker.tick().await;
+				self.detect().await;
+			}
+		})
+	}
+}
+
+/// Finds chain pairs whose price for the same token differs by more than `min_spread_bps`.
+fn find_opportunities(
+	prices: &HashMap<String, Vec<(u64, f64)>>,
+	min_spread_bps: u64,
+) -> Vec<ArbitrageOpportunity> {
+	let mut opportunities = Vec::new();
+
+	for (token, chain_prices) in prices {
+		for (i, (chain_a, price_a)) in chain_prices.iter().enumerate() {
+			for (chain_b, price_b) in &chain_prices[i + 1..] {
+				let ((from_chain, low), (to_chain, high)) = if price_a <= price_b {
+					((*chain_a, *price_a), (*chain_b, *price_b))
+				} else {
+					((*chain_b, *price_b), (*chain_a, *price_a))
+				};
+				if low <= 0.0 {
+					continue;
+				}
+
+				let spread_bps = ((high - low) / low * 10_000.0).round() as u64;
+				if spread_bps > min_spread_bps {
+					opportunities.push(ArbitrageOpportunity {
+						token: token.clone(),
+						from_chain,
+						to_chain,
+						spread_bps,
+					});
+				}
+			}
+		}
+	}
+
+	opportunities
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_find_opportunities_above_spread() {
+		let prices = HashMap::from([
+			(
+				"USDC".to_string(),
+				vec![(1, 1.0), (10, 1.0005), (8453, 1.02)],
+			),
+			("WETH".to_string(), vec![(1, 3000.0)]),
+		]);
+
+		let mut opportunities = find_opportunities(&prices, 100);
+		opportunities.sort_by_key(|o| o.from_chain);
+
+		assert_eq!(
+			opportunities,
+			vec![
+				ArbitrageOpportunity {
+					token: "USDC".to_string(),
+					from_chain: 1,
+					to_chain: 8453,
+					spread_bps: 200,
+				},
+				ArbitrageOpportunity {
+					token: "USDC".to_string(),
+					from_chain: 10,
+					to_chain: 8453,
+					spread_bps: 195,
+				},
+			]
+		);
+	}
+}
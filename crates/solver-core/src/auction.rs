@@ -0,0 +1,204 @@
+//! Auction coordination for competitively filled intents.
+//!
+//! Intents flagged with `requires_auction` are not filled immediately. The
+//! coordinator waits for the auction window, submits this solver's bid to the
+//! configured auction endpoint and compares it against the competing bids
+//! returned. The order is only filled when this solver's profit exceeds every
+//! competing bid by the configured margin. Profits are compared in basis
+//! points of the USD value of the order's inputs, so bids on orders between
+//! different tokens are comparable.
+
+use serde::{Deserialize, Serialize};
+use solver_config::AuctionConfig;
+use solver_storage::StorageService;
+use solver_types::{Address, Order, StorageKey};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Errors that can occur while running an auction.
+#[derive(Debug, Error)]
+pub enum AuctionError {
+	#[error("Order cannot be priced: {0}")]
+	Pricing(String),
+	#[error("Auction endpoint error: {0}")]
+	Endpoint(String),
+	#[error("Storage error: {0}")]
+	Storage(String),
+}
+
+/// A bid submitted to or received from the auction endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionBid {
+	/// Order the bid is placed on.
+	pub order_id: String,
+	/// Address of the bidding solver.
+	pub solver: String,
+	/// Profit the solver expects from filling the order, in basis points of
+	/// the USD value of its inputs.
+	pub profit_bps: i64,
+}
+
+/// Response returned by the auction endpoint after submitting a bid.
+#[derive(Debug, Deserialize)]
+struct AuctionBidResponse {
+	/// All bids received for the order so far.
+	#[serde(default)]
+	bids: Vec<AuctionBid>,
+}
+
+/// Auction state stored for each auctioned order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuctionRecord {
+	/// This solver's bid.
+	pub own_bid: AuctionBid,
+	/// Bids from competing solvers.
+	pub competing_bids: Vec<AuctionBid>,
+	/// Whether this solver won the auction.
+	pub won: bool,
+}
+
+/// Runs auctions for intents that require competitive filling.
+pub struct AuctionCoordinator {
+	config: AuctionConfig,
+	storage: Arc<StorageService>,
+	client: reqwest::Client,
+}
+
+impl AuctionCoordinator {
+	/// Creates a new AuctionCoordinator with the given configuration.
+	pub fn new(config: AuctionConfig, storage: Arc<StorageService>) -> Self {
+		Self {
+			config,
+			storage,
+			client: reqwest::Client::new(),
+		}
+	}
+
+	/// Returns how long the auction waits for competing bids.
+	pub fn wait_duration(&self) -> Duration {
+		Duration::from_secs(self.config.auction_wait_seconds)
+	}
+
+	/// Runs the auction for an order and returns true if this solver should fill it.
+	///
+	/// Waits for the auction window, submits this solver's bid of
+	/// `profit_bps` and stores the resulting bids under
+	/// `StorageKey::AuctionBids`.
+	pub async fn decide(
+		&self,
+		order: &Order,
+		solver: &Address,
+		profit_bps: i64,
+	) -> Result<bool, AuctionError> {
+		let own_bid = AuctionBid {
+			order_id: order.id.clone(),
+			solver: solver.to_string(),
+			profit_bps,
+		};
+
+		tokio::time::sleep(self.wait_duration()).await;
+
+		let url = format!(
+			"{}/auction/bid",
+			self.config.auction_endpoint.trim_end_matches('/')
+		);
+		let response = self
+			.client
+			.post(&url)
+			.json(&own_bid)
+			.send()
+			.await
+			.and_then(|response| response.error_for_status())
+			.map_err(|e| AuctionError::Endpoint(e.to_string()))?
+			.json::<AuctionBidResponse>()
+			.await
+			.map_err(|e| AuctionError::Endpoint(format!("Invalid bid response: {}", e)))?;
+
+		let competing_bids: Vec<AuctionBid> = response
+			.bids
+			.into_iter()
+			.filter(|bid| {
+				bid.order_id == order.id && !bid.solver.eq_ignore_ascii_case(&own_bid.solver)
+			})
+			.collect();
+		let won = outbids(
+			own_bid.profit_bps,
+			&competing_bids,
+			self.config.min_profit_advantage_bps,
+		);
+
+		tracing::info!(
+			order_id = %solver_types::truncate_id(&order.id),
+			profit_bps = own_bid.profit_bps,
+			competing_bids = competing_bids.len(),
+			won,
+			"Auction completed"
+		);
+
+		let record = AuctionRecord {
+			own_bid,
+			competing_bids,
+			won,
+		};
+		self.storage
+			.store(StorageKey::AuctionBids.as_str(), &order.id, &record, None)
+			.await
+			.map_err(|e| AuctionError::Storage(e.to_string()))?;
+
+		Ok(won)
+	}
+}
+
+/// Returns the profit of filling an order worth `input_usd` for outputs
+/// worth `output_usd`, in basis points of the input value.
+///
+/// Orders without input value have no profit. Results are clamped to the
+/// i64 range.
+pub fn profit_bps(input_usd: f64, output_usd: f64) -> i64 {
+	if input_usd.is_nan() || input_usd <= 0.0 {
+		return 0;
+	}
+	// Float to integer casts saturate and map NaN to zero
+	((input_usd - output_usd) / input_usd * 10_000.0) as i64
+}
+
+/// Returns true if `own_profit_bps` exceeds every competing bid by at least `min_advantage_bps`.
+fn outbids(own_profit_bps: i64, competing_bids: &[AuctionBid], min_advantage_bps: u32) -> bool {
+	competing_bids.iter().all(|bid| {
+		own_profit_bps > bid.profit_bps
+			&& own_profit_bps.saturating_sub(bid.profit_bps) >= i64::from(min_advantage_bps)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn bid(profit_bps: i64) -> AuctionBid {
+		AuctionBid {
+			order_id: "order".to_string(),
+			solver: "0x0000000000000000000000000000000000000002".to_string(),
+			profit_bps,
+		}
+	}
+
+	#[test]
+	fn test_profit_bps() {
+		assert_eq!(profit_bps(10_000.0, 9_950.0), 50);
+		assert_eq!(profit_bps(10_000.0, 10_100.0), -100);
+		assert_eq!(profit_bps(0.0, 1.0), 0);
+		assert_eq!(profit_bps(f64::NAN, 1.0), 0);
+		assert_eq!(profit_bps(1e-300, 1e300), i64::MIN);
+	}
+
+	#[test]
+	fn test_outbids_requires_margin_over_every_bid() {
+		assert!(outbids(50, &[], 10));
+		assert!(outbids(50, &[bid(20), bid(40)], 10));
+		assert!(!outbids(50, &[bid(20), bid(45)], 10));
+		// Ties never win, even without a margin
+		assert!(!outbids(50, &[bid(50)], 0));
+	}
+}
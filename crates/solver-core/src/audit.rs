@@ -0,0 +1,318 @@
+//! Audit trail of order fills.
+//!
+//! Every proven fill produces an audit record with the order, its fill
+//! proof, execution parameters and the fill receipt. Records are written once
+//! the fill proof has been generated, since the proof is only available after
+//! the fill has been attested. Records are keyed by the
+//! SHA-256 hash of their JSON serialization, so any alteration of a record is
+//! detectable, and the audit namespace is append-only.
+
+use alloy_primitives::hex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_storage::{QueryFilter, StorageError, StorageIndexes, StorageService};
+use solver_types::{AuditQuery, ExecutionParams, FillProof, Order, StorageKey, TransactionReceipt};
+use thiserror::Error;
+
+/// Number of audit records returned per page by default.
+pub const DEFAULT_AUDIT_PAGE_SIZE: usize = 100;
+
+/// Maximum number of audit records returned per page.
+pub const MAX_AUDIT_PAGE_SIZE: usize = 1000;
+
+/// Errors that can occur while recording a fill.
+#[derive(Debug, Error)]
+pub enum AuditError {
+	#[error("Order has no output chain")]
+	NoFillChain,
+	#[error("Failed to retrieve fill receipt: {0}")]
+	Delivery(#[from] DeliveryError),
+	#[error("Storage error: {0}")]
+	Storage(#[from] StorageError),
+}
+
+/// Auditable record of an order fill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+	/// The order at the time its fill was confirmed.
+	pub order: Order,
+	/// Proof of the fill. Only missing from records written before fills
+	/// were recorded at proof time.
+	pub fill_proof: Option<FillProof>,
+	/// Receipt of the fill transaction.
+	pub receipt: TransactionReceipt,
+	/// Parameters the fill was executed with.
+	pub execution_params: Option<ExecutionParams>,
+	/// Timestamp at which the record was written.
+	pub recorded_at: u64,
+}
+
+impl AuditRecord {
+	/// Returns the hex-encoded SHA-256 hash of the record's JSON serialization.
+	pub fn hash(&self) -> Result<String, StorageError> {
+		let bytes =
+			serde_json::to_vec(self).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		Ok(hex::encode(Sha256::digest(bytes)))
+	}
+}
+
+/// An audit record with its hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+	pub hash: String,
+	pub record: AuditRecord,
+}
+
+/// A page of audit entries, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditPage {
+	pub entries: Vec<AuditEntry>,
+	/// Number of entries matching the query across all pages.
+	pub total: usize,
+	pub offset: usize,
+	pub limit: usize,
+}
+
+/// Writes the audit record of a proven fill, returning its hash.
+pub async fn record_fill(
+	storage: &StorageService,
+	order: &Order,
+	fill_proof: &FillProof,
+	receipt: &TransactionReceipt,
+	now: u64,
+) -> Result<String, StorageError> {
+	let record = AuditRecord {
+		order: order.clone(),
+		fill_proof: Some(fill_proof.clone()),
+		receipt: receipt.clone(),
+		execution_params: order.execution_params.clone(),
+		recorded_at: now,
+	};
+	let hash = record.hash()?;
+	let indexes = StorageIndexes::new()
+		.with_field("order_id", &order.id)
+		.with_numeric("recorded_at", now);
+	storage
+		.store_if_absent(StorageKey::AuditLog.as_str(), &hash, &record, Some(indexes))
+		.await?;
+	Ok(hash)
+}
+
+/// Writes the audit record of a fill once its proof has been generated,
+/// returning its hash.
+///
+/// The fill receipt is retrieved from the order's fill chain. A fill that
+/// already has a record with a proof is not recorded again, so that the
+/// monitors restarted by recovery do not duplicate records.
+pub async fn record_proven_fill(
+	storage: &StorageService,
+	delivery: &DeliveryService,
+	order: &Order,
+	fill_proof: &FillProof,
+	now: u64,
+) -> Result<String, AuditError> {
+	if let Some(entry) = order_audit_entry(storage, &order.id).await? {
+		if entry.record.fill_proof.is_some() {
+			return Ok(entry.hash);
+		}
+	}
+	let chain_id = *order
+		.output_chain_ids
+		.first()
+		.ok_or(AuditError::NoFillChain)?;
+	let receipt = delivery.get_receipt(fill_proof.tx_hash(), chain_id).await?;
+	Ok(record_fill(storage, order, fill_proof, &receipt, now).await?)
+}
+
+/// Returns the most recent audit entry of an order, if any.
+pub async fn order_audit_entry(
+	storage: &StorageService,
+	order_id: &str,
+) -> Result<Option<AuditEntry>, StorageError> {
+	let records = storage
+		.query::<AuditRecord>(
+			StorageKey::AuditLog.as_str(),
+			QueryFilter::Equals("order_id".to_string(), serde_json::json!(order_id)),
+		)
+		.await?;
+	Ok(records
+		.into_iter()
+		.map(|(hash, record)| AuditEntry { hash, record })
+		.max_by_key(|entry| entry.record.recorded_at))
+}
+
+/// Returns the page of audit entries recorded within the query's bounds.
+pub async fn audit_entries(
+	storage: &StorageService,
+	query: &AuditQuery,
+) -> Result<AuditPage, StorageError> {
+	let filter = match query.from_ts.filter(|ts| *ts > 0) {
+		Some(from_ts) => {
+			QueryFilter::GreaterThan("recorded_at".to_string(), serde_json::json!(from_ts - 1))
+		},
+		None => QueryFilter::All,
+	};
+	let mut entries: Vec<AuditEntry> = storage
+		.query::<AuditRecord>(StorageKey::AuditLog.as_str(), filter)
+		.await?
+		.into_iter()
+		.filter(|(_, record)| query.to_ts.is_none_or(|to_ts| record.recorded_at <= to_ts))
+		.map(|(hash, record)| AuditEntry { hash, record })
+		.collect();
+	entries.sort_by(|a, b| {
+		a.record
+			.recorded_at
+			.cmp(&b.record.recorded_at)
+			.then_with(|| a.hash.cmp(&b.hash))
+	});
+
+	let total = entries.len();
+	let limit = query
+		.limit
+		.unwrap_or(DEFAULT_AUDIT_PAGE_SIZE)
+		.clamp(1, MAX_AUDIT_PAGE_SIZE);
+	let entries = entries.into_iter().skip(query.offset).take(limit).collect();
+	Ok(AuditPage {
+		entries,
+		total,
+		offset: query.offset,
+		limit,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_delivery::DeliveryService;
+	use solver_storage::implementations::file::create_storage;
+	use solver_storage::write_protected::WriteProtectedStorage;
+	use solver_types::{Address, OrderStatus, StandardFillProof, TransactionHash};
+	use std::collections::HashMap;
+
+	fn order(id: &str) -> Order {
+		Order {
+			id: id.to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Executed,
+			data: serde_json::json!({}),
+			solver_address: Address(vec![0; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![2],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: Some(TransactionHash(vec![1; 32])),
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn receipt() -> TransactionReceipt {
+		TransactionReceipt {
+			hash: TransactionHash(vec![1; 32]),
+			block_number: 10,
+			success: true,
+			gas_used: 21_000,
+			effective_gas_price: 0,
+		}
+	}
+
+	fn fill_proof() -> FillProof {
+		FillProof::Standard(StandardFillProof {
+			tx_hash: TransactionHash(vec![1; 32]),
+			block_number: 10,
+			attestation_data: None,
+			filled_timestamp: 100,
+			oracle_address: "0x0000000000000000000000000000000000000001".to_string(),
+			proof_version: 1,
+		})
+	}
+
+	fn audit_storage(dir: &tempfile::TempDir) -> StorageService {
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let backend = create_storage(&toml::Value::Table(config)).unwrap();
+		StorageService::new(
+			Box::new(WriteProtectedStorage::new(
+				backend,
+				[StorageKey::AuditLog.as_str()],
+			)),
+			None,
+		)
+	}
+
+	#[tokio::test]
+	async fn test_records_and_pages_fills() {
+		let dir = tempfile::tempdir().unwrap();
+		let storage = audit_storage(&dir);
+
+		let first = record_fill(&storage, &order("a"), &fill_proof(), &receipt(), 100)
+			.await
+			.unwrap();
+		record_fill(&storage, &order("b"), &fill_proof(), &receipt(), 200)
+			.await
+			.unwrap();
+		record_fill(&storage, &order("c"), &fill_proof(), &receipt(), 300)
+			.await
+			.unwrap();
+
+		let entry = order_audit_entry(&storage, "a").await.unwrap().unwrap();
+		assert_eq!(entry.hash, first);
+		assert_eq!(entry.record.hash().unwrap(), first);
+		assert!(entry.record.fill_proof.is_some());
+		assert!(order_audit_entry(&storage, "d").await.unwrap().is_none());
+
+		let page = audit_entries(
+			&storage,
+			&AuditQuery {
+				from_ts: Some(200),
+				to_ts: Some(300),
+				offset: 1,
+				limit: Some(10),
+			},
+		)
+		.await
+		.unwrap();
+		assert_eq!(page.total, 2);
+		assert_eq!(page.entries.len(), 1);
+		assert_eq!(page.entries[0].record.order.id, "c");
+
+		// Records cannot be altered once written
+		assert!(storage
+			.remove(StorageKey::AuditLog.as_str(), &first)
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_proven_fill_is_recorded_once() {
+		let dir = tempfile::tempdir().unwrap();
+		let storage = audit_storage(&dir);
+		let delivery = DeliveryService::new(HashMap::new(), 1);
+
+		// Without a record, the receipt must be retrieved from the fill chain
+		assert!(matches!(
+			record_proven_fill(&storage, &delivery, &order("a"), &fill_proof(), 100).await,
+			Err(AuditError::Delivery(_))
+		));
+
+		let hash = record_fill(&storage, &order("a"), &fill_proof(), &receipt(), 100)
+			.await
+			.unwrap();
+		let again = record_proven_fill(&storage, &delivery, &order("a"), &fill_proof(), 200)
+			.await
+			.unwrap();
+		assert_eq!(again, hash);
+		let page = audit_entries(&storage, &AuditQuery::default())
+			.await
+			.unwrap();
+		assert_eq!(page.total, 1);
+	}
+}
@@ -0,0 +1,169 @@
+//! Adaptive sizing of claim batches.
+//!
+//! Claiming orders in batches amortizes the fixed cost of a batch, but delays
+//! the claims of the orders waiting for the batch to fill up. The optimizer
+//! balances both from the observed gas used per claim, net of the fixed
+//! overhead and shared between the claims of a transaction, which is tracked
+//! as an exponential moving average of confirmed claims. The fixed overhead is
+//! weighted by the latest claim gas price relative to its moving average, so
+//! batches grow while gas is expensive and shrink while it is cheap.
+
+use solver_config::ClaimBatchConfig;
+use std::sync::Mutex;
+
+/// Weight of the latest sample in the moving averages.
+const EMA_ALPHA: f64 = 0.2;
+
+/// Observed claim costs.
+#[derive(Debug, Default)]
+struct ClaimCosts {
+	/// Moving average of the gas used per claim.
+	per_claim_gas: Option<f64>,
+	/// Moving average of the effective gas price of claims.
+	average_gas_price: Option<f64>,
+	/// Effective gas price of the latest claim.
+	latest_gas_price: Option<f64>,
+}
+
+/// Computes the optimal claim batch size from observed claim gas usage.
+pub struct BatchSizeOptimizer {
+	min_size: usize,
+	max_size: usize,
+	fixed_overhead_gas: u64,
+	costs: Mutex<ClaimCosts>,
+}
+
+impl BatchSizeOptimizer {
+	/// Creates a new BatchSizeOptimizer with the configured bounds.
+	pub fn new(config: &ClaimBatchConfig) -> Self {
+		Self {
+			min_size: config.min_size,
+			max_size: config.max_size,
+			fixed_overhead_gas: config.fixed_overhead_gas,
+			costs: Mutex::new(ClaimCosts::default()),
+		}
+	}
+
+	/// Updates the averages with a successful claim transaction carrying
+	/// `claims` claims.
+	pub fn record_claim_gas(&self, gas_used: u64, effective_gas_price: u128, claims: usize) {
+		let per_claim =
+			gas_used.saturating_sub(self.fixed_overhead_gas) as f64 / claims.max(1) as f64;
+		let gas_price = effective_gas_price as f64;
+
+		let mut costs = self.costs.lock().unwrap_or_else(|e| e.into_inner());
+		costs.per_claim_gas = Some(ema(costs.per_claim_gas, per_claim));
+		if effective_gas_price > 0 {
+			costs.average_gas_price = Some(ema(costs.average_gas_price, gas_price));
+			costs.latest_gas_price = Some(gas_price);
+		}
+	}
+
+	/// Returns the moving average of the gas used per claim, if known.
+	pub fn per_claim_gas(&self) -> Option<f64> {
+		self.costs
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.per_claim_gas
+	}
+
+	/// Returns the number of claims to accumulate before submitting a batch.
+	///
+	/// Uses the minimum size until the first claim has been observed.
+	pub fn optimal_size(&self) -> usize {
+		let costs = self.costs.lock().unwrap_or_else(|e| e.into_inner());
+		let Some(per_claim_gas) = costs.per_claim_gas else {
+			return self.min_size;
+		};
+		let gas_price_ratio = match (costs.latest_gas_price, costs.average_gas_price) {
+			(Some(latest), Some(average)) if average > 0.0 => latest / average,
+			_ => 1.0,
+		};
+		optimal_batch_size(
+			self.fixed_overhead_gas as f64 * gas_price_ratio,
+			per_claim_gas,
+			self.min_size,
+			self.max_size,
+		)
+	}
+}
+
+/// Moves a moving average towards a sample by `EMA_ALPHA`.
+fn ema(previous: Option<f64>, sample: f64) -> f64 {
+	match previous {
+		Some(previous) => EMA_ALPHA * sample + (1.0 - EMA_ALPHA) * previous,
+		None => sample,
+	}
+}
+
+/// Computes `floor(sqrt(2 * fixed_overhead_gas / per_claim_gas))`, clamped to
+/// `[min_size, max_size]`.
+fn optimal_batch_size(
+	fixed_overhead_gas: f64,
+	per_claim_gas: f64,
+	min_size: usize,
+	max_size: usize,
+) -> usize {
+	if per_claim_gas <= 0.0 {
+		return max_size;
+	}
+	let optimal = (2.0 * fixed_overhead_gas / per_claim_gas).sqrt().floor() as usize;
+	optimal.clamp(min_size, max_size)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn optimizer() -> BatchSizeOptimizer {
+		BatchSizeOptimizer::new(&ClaimBatchConfig {
+			min_size: 1,
+			max_size: 10,
+			fixed_overhead_gas: 800_000,
+			batch_timeout_seconds: 30,
+		})
+	}
+
+	#[test]
+	fn test_optimal_size_follows_claim_gas() {
+		assert_eq!(optimal_batch_size(800_000.0, 50_000.0, 1, 10), 5);
+		assert_eq!(optimal_batch_size(800_000.0, 50_000.0, 1, 3), 3);
+		assert_eq!(optimal_batch_size(21_000.0, 100_000.0, 2, 10), 2);
+
+		let optimizer = optimizer();
+		assert_eq!(optimizer.optimal_size(), 1);
+
+		// The fixed overhead is not attributed to the claims
+		optimizer.record_claim_gas(850_000, 1, 1);
+		assert_eq!(optimizer.per_claim_gas(), Some(50_000.0));
+		assert_eq!(optimizer.optimal_size(), 5);
+
+		// A more expensive claim moves the average by EMA_ALPHA of the difference
+		optimizer.record_claim_gas(900_000, 1, 1);
+		assert!((optimizer.per_claim_gas().unwrap() - 60_000.0).abs() < 1e-6);
+		assert_eq!(optimizer.optimal_size(), 5);
+	}
+
+	#[test]
+	fn test_batched_claims_share_the_transaction_gas() {
+		let optimizer = optimizer();
+		optimizer.record_claim_gas(1_000_000, 1, 4);
+		assert_eq!(optimizer.per_claim_gas(), Some(50_000.0));
+	}
+
+	#[test]
+	fn test_optimal_size_follows_gas_price() {
+		let optimizer = optimizer();
+		optimizer.record_claim_gas(850_000, 10, 1);
+		assert_eq!(optimizer.optimal_size(), 5);
+
+		// Expensive gas makes the overhead weigh more, so batches grow
+		optimizer.record_claim_gas(850_000, 60, 1);
+		// Average price 20, latest 60: sqrt(2 * 800k * 3 / 50k) = 9.8
+		assert_eq!(optimizer.optimal_size(), 9);
+
+		// Cheap gas shrinks them
+		optimizer.record_claim_gas(850_000, 1, 1);
+		assert_eq!(optimizer.optimal_size(), 1);
+	}
+}
@@ -2,19 +2,38 @@
 //!
 //! Provides a flexible way to compose a SolverEngine from various service
 //! implementations using factory functions. Supports pluggable storage,
-//! account, delivery, discovery, order implementations and
-//! settlement and execution strategies.
+//! account, delivery, discovery, order, settlement and price feed
+//! implementations and execution strategies.
 
-use crate::engine::{event_bus::EventBus, SolverEngine};
+use crate::engine::{event_bus::EventBus, DiscoveryRole, SolverEngine};
 use solver_account::{AccountError, AccountInterface, AccountService};
-use solver_config::Config;
-use solver_delivery::{DeliveryError, DeliveryInterface, DeliveryService};
+use solver_config::{AccountConfig, Config, PredictorConfig};
+use solver_delivery::{
+	AnvilNodes, DeliveryFuture, DeliveryInterface, DeliveryService, GasPriceHistoryAnalyzer,
+	InstrumentedDelivery, LazyDeliveryFactory, RateLimitedProvider, RpcCallStats,
+};
+use solver_discovery::enrichment::{
+	IntentEnricher, PriceEnricher, TokenMetadataEnricher, PRICE_ENRICHER, TOKEN_METADATA_ENRICHER,
+};
 use solver_discovery::{DiscoveryError, DiscoveryInterface, DiscoveryService};
-use solver_order::{ExecutionStrategy, OrderError, OrderInterface, OrderService, StrategyError};
-use solver_settlement::{SettlementError, SettlementInterface, SettlementService};
+use solver_order::implementations::strategies::expiry_filter::ExpiryFilter;
+use solver_order::implementations::strategies::gas_aware::GasAwareStrategy;
+use solver_order::implementations::strategies::twap::TwapStrategy;
+use solver_order::prediction::{HistoricalFillPredictor, StaticPredictor};
+use solver_order::{
+	ExecutionStrategy, FillPredictor, GasWindowPredictor, OrderError, OrderInterface, OrderService,
+	StrategyError,
+};
+use solver_price::{HistoricalPriceStore, PriceFeedError, PriceFeedInterface, PriceService};
+use solver_settlement::{
+	ClaimSuccessRateCircuitBreaker, ProofCache, ProofRelayer, SettlementError, SettlementInterface,
+	SettlementService,
+};
+use solver_storage::write_protected::WriteProtectedStorage;
 use solver_storage::{StorageError, StorageInterface, StorageService};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during solver engine construction.
@@ -29,12 +48,77 @@ pub enum BuilderError {
 	MissingComponent(String),
 }
 
+/// Wraps a delivery implementation to record its call statistics and apply
+/// the configured RPC rate limits.
+fn wrap_delivery(
+	implementation: Box<dyn DeliveryInterface>,
+	rpc_stats: &Arc<RpcCallStats>,
+	rate_limits: &HashMap<u64, u32>,
+) -> Arc<dyn DeliveryInterface> {
+	// Time calls to the provider itself, excluding rate-limit waits
+	let implementation: Arc<dyn DeliveryInterface> = Arc::new(InstrumentedDelivery::new(
+		implementation.into(),
+		rpc_stats.clone(),
+	));
+	if rate_limits.is_empty() {
+		return implementation;
+	}
+	Arc::new(RateLimitedProvider::new(implementation, rate_limits))
+}
+
+/// Resolves the `accounts` table of a delivery implementation, mapping chain
+/// IDs to the accounts that sign transactions on them.
+///
+/// Chains without an entry are signed with the primary account.
+fn network_accounts(
+	implementation: &str,
+	config: &toml::Value,
+	account_services: &HashMap<String, Arc<AccountService>>,
+) -> Result<HashMap<u64, Arc<AccountService>>, BuilderError> {
+	let mut network_accounts = HashMap::new();
+	let Some(accounts_table) = config.get("accounts").and_then(|v| v.as_table()) else {
+		return Ok(network_accounts);
+	};
+	for (network_id_str, account_name_value) in accounts_table {
+		let (Ok(network_id), Some(account_name)) =
+			(network_id_str.parse::<u64>(), account_name_value.as_str())
+		else {
+			continue;
+		};
+		let account_service = account_services.get(account_name).ok_or_else(|| {
+			let mut available: Vec<_> = account_services.keys().cloned().collect();
+			available.sort();
+			BuilderError::Config(format!(
+				"Delivery implementation '{}' uses account '{}' for chain {}, but no such account is loaded. Available: [{}]",
+				implementation,
+				account_name,
+				network_id,
+				available.join(", ")
+			))
+		})?;
+		network_accounts.insert(network_id, account_service.clone());
+	}
+	Ok(network_accounts)
+}
+
+/// Predicts gas prices from the gas price history sampled through delivery.
+struct GasHistoryPredictor(GasPriceHistoryAnalyzer);
+
+#[async_trait::async_trait]
+impl GasWindowPredictor for GasHistoryPredictor {
+	async fn predict_low_gas_window(&self, chain_id: u64, lookahead_hours: u8) -> Vec<(u64, u64)> {
+		self.0
+			.predict_low_gas_window(chain_id, lookahead_hours)
+			.await
+	}
+}
+
 /// Container for all factory functions needed to build a SolverEngine.
 ///
 /// This struct holds factory functions for creating implementations of each
 /// service type required by the solver engine. Each factory function takes
 /// a TOML configuration value and returns the corresponding service implementation.
-pub struct SolverFactories<SF, AF, DF, DIF, OF, SEF, STF> {
+pub struct SolverFactories<SF, AF, DF, DIF, OF, SEF, STF, PF> {
 	pub storage_factories: HashMap<String, SF>,
 	pub account_factories: HashMap<String, AF>,
 	pub delivery_factories: HashMap<String, DF>,
@@ -42,33 +126,98 @@ pub struct SolverFactories<SF, AF, DF, DIF, OF, SEF, STF> {
 	pub order_factories: HashMap<String, OF>,
 	pub settlement_factories: HashMap<String, SEF>,
 	pub strategy_factories: HashMap<String, STF>,
+	pub price_factories: HashMap<String, PF>,
 }
 
-/// Builder for constructing a SolverEngine with pluggable implementations.
+/// Builder for constructing SolverEngines with pluggable implementations.
+///
+/// Builds one engine per configuration. Engines built together share a single
+/// storage backend, with entries scoped by solver ID when there are several
+/// engines, as well as discovery, price feeds and, for solvers using the same
+/// account, the delivery service.
 pub struct SolverBuilder {
-	config: Config,
+	configs: Vec<Config>,
 }
 
 impl SolverBuilder {
 	/// Creates a new SolverBuilder with the given configuration.
 	pub fn new(config: Config) -> Self {
-		Self { config }
+		Self::with_configs(vec![config])
 	}
 
-	/// Builds the SolverEngine using factories for each component type.
-	pub async fn build<SF, AF, DF, DIF, OF, SEF, STF>(
+	/// Creates a new SolverBuilder for several solvers run in one process.
+	///
+	/// The configurations are expected to differ only in their solver, account
+	/// and strategy sections, as returned by `Config::tenants`. Shared services
+	/// are created from the first configuration.
+	pub fn with_configs(configs: Vec<Config>) -> Self {
+		Self { configs }
+	}
+
+	/// Builds the SolverEngine of the first configuration.
+	pub async fn build<SF, AF, DF, DIF, OF, SEF, STF, PF>(
 		self,
-		factories: SolverFactories<SF, AF, DF, DIF, OF, SEF, STF>,
+		factories: SolverFactories<SF, AF, DF, DIF, OF, SEF, STF, PF>,
 	) -> Result<SolverEngine, BuilderError>
 	where
 		SF: Fn(&toml::Value) -> Result<Box<dyn StorageInterface>, StorageError>,
 		AF: Fn(&toml::Value) -> Result<Box<dyn AccountInterface>, AccountError>,
 		DF: Fn(
+				&toml::Value,
+				&solver_types::NetworksConfig,
+				&solver_types::SecretString,
+				&std::collections::HashMap<u64, solver_types::SecretString>,
+			) -> DeliveryFuture
+			+ Clone
+			+ Send
+			+ Sync
+			+ 'static,
+		DIF: Fn(
+			&toml::Value,
+			&solver_types::NetworksConfig,
+		) -> Result<Box<dyn DiscoveryInterface>, DiscoveryError>,
+		OF: Fn(
+			&toml::Value,
+			&solver_types::NetworksConfig,
+			&Arc<dyn solver_types::oracle::OracleRouteSource>,
+		) -> Result<Box<dyn OrderInterface>, OrderError>,
+		SEF: Fn(
+			&toml::Value,
+			&solver_types::NetworksConfig,
+		) -> Result<Box<dyn SettlementInterface>, SettlementError>,
+		STF: Fn(&toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError>,
+		PF: Fn(
 			&toml::Value,
 			&solver_types::NetworksConfig,
-			&solver_types::SecretString,
-			&std::collections::HashMap<u64, solver_types::SecretString>,
-		) -> Result<Box<dyn DeliveryInterface>, DeliveryError>,
+		) -> Result<Box<dyn PriceFeedInterface>, PriceFeedError>,
+	{
+		self.build_all(factories)
+			.await?
+			.into_iter()
+			.next()
+			.ok_or_else(|| BuilderError::MissingComponent("solver configuration".into()))
+	}
+
+	/// Builds one SolverEngine per configuration using factories for each component type.
+	///
+	/// The first engine runs discovery and forwards discovered intents to the others.
+	pub async fn build_all<SF, AF, DF, DIF, OF, SEF, STF, PF>(
+		self,
+		factories: SolverFactories<SF, AF, DF, DIF, OF, SEF, STF, PF>,
+	) -> Result<Vec<SolverEngine>, BuilderError>
+	where
+		SF: Fn(&toml::Value) -> Result<Box<dyn StorageInterface>, StorageError>,
+		AF: Fn(&toml::Value) -> Result<Box<dyn AccountInterface>, AccountError>,
+		DF: Fn(
+				&toml::Value,
+				&solver_types::NetworksConfig,
+				&solver_types::SecretString,
+				&std::collections::HashMap<u64, solver_types::SecretString>,
+			) -> DeliveryFuture
+			+ Clone
+			+ Send
+			+ Sync
+			+ 'static,
 		DIF: Fn(
 			&toml::Value,
 			&solver_types::NetworksConfig,
@@ -76,23 +225,45 @@ impl SolverBuilder {
 		OF: Fn(
 			&toml::Value,
 			&solver_types::NetworksConfig,
-			&solver_types::oracle::OracleRoutes,
+			&Arc<dyn solver_types::oracle::OracleRouteSource>,
 		) -> Result<Box<dyn OrderInterface>, OrderError>,
 		SEF: Fn(
 			&toml::Value,
 			&solver_types::NetworksConfig,
 		) -> Result<Box<dyn SettlementInterface>, SettlementError>,
 		STF: Fn(&toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError>,
+		PF: Fn(
+			&toml::Value,
+			&solver_types::NetworksConfig,
+		) -> Result<Box<dyn PriceFeedInterface>, PriceFeedError>,
 	{
+		let mut configs = self.configs;
+		let first = configs
+			.first()
+			.ok_or_else(|| BuilderError::MissingComponent("solver configuration".into()))?;
+
+		// Start the local nodes of the provider mode and connect every component to them
+		let anvil_nodes = AnvilNodes::start(first.delivery.provider_mode, &first.networks)
+			.await
+			.map_err(|e| BuilderError::Config(format!("Failed to start local nodes: {}", e)))?
+			.map(Arc::new);
+		if let Some(nodes) = &anvil_nodes {
+			for config in &mut configs {
+				config.networks = nodes.networks(&config.networks);
+			}
+		}
+		let base = configs[0].clone();
+		let multi_solver = configs.len() > 1;
+
 		// Create storage implementations
 		let mut storage_impls = HashMap::new();
-		for (name, config) in &self.config.storage.implementations {
+		for (name, config) in &base.storage.implementations {
 			if let Some(factory) = factories.storage_factories.get(name) {
 				match factory(config) {
 					Ok(implementation) => {
 						// Validation already happened in the factory
 						storage_impls.insert(name.clone(), implementation);
-						let is_primary = &self.config.storage.primary == name;
+						let is_primary = &base.storage.primary == name;
 						tracing::info!(component = "storage", implementation = %name, enabled = %is_primary, "Loaded");
 					},
 					Err(e) => {
@@ -118,7 +289,7 @@ impl SolverBuilder {
 		}
 
 		// Get the primary storage implementation
-		let primary_storage = &self.config.storage.primary;
+		let primary_storage = &base.storage.primary;
 		let storage_backend = storage_impls.remove(primary_storage).ok_or_else(|| {
 			BuilderError::Config(format!(
 				"Primary storage '{}' failed to load or has invalid configuration",
@@ -126,27 +297,77 @@ impl SolverBuilder {
 			))
 		})?;
 
-		let storage = Arc::new(StorageService::new(storage_backend));
+		// Audit records must never be altered once written
+		let storage_backend = Box::new(WriteProtectedStorage::new(
+			storage_backend,
+			[solver_types::StorageKey::AuditLog.as_str()],
+		));
+		let storage = Arc::new(StorageService::new(storage_backend, None));
+
+		// Create the price service if price feeds are configured
+		let price = match &base.price {
+			Some(price_config) => {
+				let mut price_impls = HashMap::new();
+				for (name, config) in &price_config.implementations {
+					if let Some(factory) = factories.price_factories.get(name) {
+						match factory(config, &base.networks) {
+							Ok(implementation) => {
+								price_impls.insert(name.clone(), implementation);
+								let is_primary = &price_config.primary == name;
+								tracing::info!(component = "price", implementation = %name, enabled = %is_primary, "Loaded");
+							},
+							Err(e) => {
+								tracing::error!(
+									component = "price",
+									implementation = %name,
+									error = %e,
+									"Failed to create price implementation"
+								);
+								return Err(BuilderError::Config(format!(
+									"Failed to create price implementation '{}': {}",
+									name, e
+								)));
+							},
+						}
+					}
+				}
+
+				let implementation =
+					price_impls.remove(&price_config.primary).ok_or_else(|| {
+						BuilderError::Config(format!(
+							"Primary price feed '{}' failed to load or has invalid configuration",
+							price_config.primary
+						))
+					})?;
+				let history =
+					HistoricalPriceStore::new(storage.clone(), price_config.history_retention_days);
+
+				Some(Arc::new(
+					PriceService::new(implementation).with_history(history),
+				))
+			},
+			None => None,
+		};
 
-		// Create account implementations
-		let mut account_impls = HashMap::new();
-		for (name, config) in &self.config.account.implementations {
-			if let Some(factory) = factories.account_factories.get(name) {
-				match factory(config) {
+		// Create discovery implementations
+		let mut discovery_implementations = HashMap::new();
+		for (name, config) in &base.discovery.implementations {
+			if let Some(factory) = factories.discovery_factories.get(name) {
+				match factory(config, &base.networks) {
 					Ok(implementation) => {
-						account_impls.insert(name.clone(), implementation);
-						let is_primary = &self.config.account.primary == name;
-						tracing::info!(component = "account", implementation = %name, enabled = %is_primary, "Loaded");
+						// Validation already happened in the factory
+						discovery_implementations.insert(name.clone(), implementation);
+						tracing::info!(component = "discovery", implementation = %name, "Loaded");
 					},
 					Err(e) => {
 						tracing::error!(
-							component = "account",
+							component = "discovery",
 							implementation = %name,
 							error = %e,
-							"Failed to create account implementation"
+							"Failed to create discovery implementation"
 						);
 						return Err(BuilderError::Config(format!(
-							"Failed to create account implementation '{}': {}",
+							"Failed to create discovery implementation '{}': {}",
 							name, e
 						)));
 					},
@@ -154,344 +375,644 @@ impl SolverBuilder {
 			}
 		}
 
-		if account_impls.is_empty() {
-			return Err(BuilderError::Config(
-				"No account implementations available".to_string(),
-			));
+		if discovery_implementations.is_empty() {
+			tracing::warn!(
+				"No discovery implementations available - solver will not discover any new orders"
+			);
 		}
 
-		// Create AccountService for each account implementation
-		let mut account_services = HashMap::new();
-		for (name, implementation) in account_impls {
-			account_services.insert(name.clone(), Arc::new(AccountService::new(implementation)));
+		// Create intent enrichers in configured order
+		let mut enrichers: Vec<Box<dyn IntentEnricher>> = Vec::new();
+		for name in &base.discovery.enrichers {
+			match (name.as_str(), &price) {
+				(TOKEN_METADATA_ENRICHER, _) => {
+					enrichers.push(Box::new(TokenMetadataEnricher::new(base.networks.clone())))
+				},
+				(PRICE_ENRICHER, Some(price)) => {
+					enrichers.push(Box::new(PriceEnricher::new(price.clone())))
+				},
+				_ => {
+					return Err(BuilderError::Config(format!(
+						"Discovery enricher '{}' is unknown or its dependencies are not configured",
+						name
+					)));
+				},
+			}
+			tracing::info!(component = "enrichment", enricher = %name, "Loaded");
 		}
 
-		// Get the primary account service
-		let primary_account = self.config.account.primary.as_str();
-		let account = account_services
-			.get(primary_account)
-			.ok_or_else(|| {
-				BuilderError::Config(format!(
-					"Primary account '{}' not found in loaded accounts",
-					primary_account
-				))
-			})?
-			.clone();
-
-		// Fetch the solver address once during initialization
-		let solver_address = match account.get_address().await {
-			Ok(address) => address,
-			Err(e) => {
-				tracing::error!(
-					component = "account",
-					error = %e,
-					"Failed to get solver address"
-				);
-				return Err(BuilderError::Config(format!(
-					"Failed to get solver address: {}",
-					e
-				)));
-			},
-		};
-
-		// Create delivery implementations
-		let mut delivery_implementations = std::collections::HashMap::new();
-
-		// Get the default private key from the primary account
-		let default_private_key = account.get_private_key();
-
-		for (name, config) in &self.config.delivery.implementations {
-			if let Some(factory) = factories.delivery_factories.get(name) {
-				// Parse per-network account mappings from config
-				let mut network_private_keys = HashMap::new();
-				if let Some(accounts_table) = config.get("accounts").and_then(|v| v.as_table()) {
-					for (network_id_str, account_name_value) in accounts_table {
-						if let Ok(network_id) = network_id_str.parse::<u64>() {
-							if let Some(account_name) = account_name_value.as_str() {
-								if let Some(account_service) = account_services.get(account_name) {
-									let private_key = account_service.get_private_key();
-									network_private_keys.insert(network_id, private_key);
-								} else {
-									tracing::warn!(
-										"Account '{}' not found, skipping",
-										account_name
-									);
-								}
-							}
-						}
+		let discovery =
+			Arc::new(DiscoveryService::new(discovery_implementations).with_enrichers(enrichers));
+
+		let mut deliveries: Vec<(AccountConfig, Arc<DeliveryService>)> = Vec::new();
+		let mut engines: Vec<SolverEngine> = Vec::with_capacity(configs.len());
+		for solver_config in configs {
+			tracing::info!(solver_id = %solver_config.solver.id, "Building solver");
+			// A single solver keeps its entries unscoped, so its existing
+			// state stays reachable
+			let solver_storage = if multi_solver {
+				Arc::new(storage.scoped(&solver_config.solver.id))
+			} else {
+				storage.clone()
+			};
+
+			// Create account implementations
+			let mut account_impls = HashMap::new();
+			for (name, config) in &solver_config.account.implementations {
+				if let Some(factory) = factories.account_factories.get(name) {
+					match factory(config) {
+						Ok(implementation) => {
+							account_impls.insert(name.clone(), implementation);
+							let is_primary = &solver_config.account.primary == name;
+							tracing::info!(component = "account", implementation = %name, enabled = %is_primary, "Loaded");
+						},
+						Err(e) => {
+							tracing::error!(
+								component = "account",
+								implementation = %name,
+								error = %e,
+								"Failed to create account implementation"
+							);
+							return Err(BuilderError::Config(format!(
+								"Failed to create account implementation '{}': {}",
+								name, e
+							)));
+						},
 					}
 				}
+			}
 
-				match factory(
-					config,
-					&self.config.networks,
-					&default_private_key,
-					&network_private_keys,
-				) {
-					Ok(implementation) => {
-						// Extract network_ids from config to create the mapping
-						if let Some(network_ids) =
-							config.get("network_ids").and_then(|v| v.as_array())
-						{
-							let implementation_arc: Arc<dyn DeliveryInterface> =
-								implementation.into();
-							for network_id_value in network_ids {
-								if let Some(network_id) = network_id_value.as_integer() {
+			if account_impls.is_empty() {
+				return Err(BuilderError::Config(
+					"No account implementations available".to_string(),
+				));
+			}
+
+			// Create AccountService for each account implementation
+			let mut loaded_accounts: Vec<(String, Arc<dyn AccountInterface>)> = account_impls
+				.into_iter()
+				.map(|(name, implementation)| (name, Arc::from(implementation)))
+				.collect();
+			loaded_accounts.sort_by(|a, b| a.0.cmp(&b.0));
+			let mut account_services = HashMap::new();
+			for (name, implementation) in &loaded_accounts {
+				let service = AccountService::new(implementation.clone())
+					.with_accounts(loaded_accounts.clone());
+				account_services.insert(name.clone(), Arc::new(service));
+			}
+
+			// Get the primary account service
+			let primary_account = solver_config.account.primary.as_str();
+			let account = account_services
+				.get(primary_account)
+				.ok_or_else(|| {
+					BuilderError::Config(format!(
+						"Primary account '{}' not found in loaded accounts",
+						primary_account
+					))
+				})?
+				.clone();
+
+			// Fetch the solver address once during initialization
+			let solver_address = match account.get_address().await {
+				Ok(address) => address,
+				Err(e) => {
+					tracing::error!(
+						component = "account",
+						error = %e,
+						"Failed to get solver address"
+					);
+					return Err(BuilderError::Config(format!(
+						"Failed to get solver address: {}",
+						e
+					)));
+				},
+			};
+
+			// Solvers using the same account share a delivery service
+			let cached_delivery = deliveries
+				.iter()
+				.find(|(account_config, _)| *account_config == solver_config.account)
+				.map(|(_, delivery)| delivery.clone());
+			let delivery = match cached_delivery {
+				Some(delivery) => delivery,
+				None => {
+					// Create delivery implementations
+					let mut delivery_implementations = std::collections::HashMap::new();
+					let mut lazy_factories = HashMap::new();
+					let mut sending_accounts = HashMap::new();
+					let rpc_stats = Arc::new(RpcCallStats::new());
+
+					// Get the default private key from the primary account
+					let default_private_key = account.get_private_key();
+
+					// Requests per second of rate-limited chains
+					let rate_limits: HashMap<u64, u32> = solver_config
+						.delivery
+						.rate_limits
+						.iter()
+						.map(|(chain_id, limit)| (*chain_id, limit.rps))
+						.collect();
+
+					for (name, config) in &solver_config.delivery.implementations {
+						if let Some(factory) = factories.delivery_factories.get(name) {
+							// Parse per-network account mappings from config
+							let network_accounts =
+								network_accounts(name, config, &account_services)?;
+							let network_private_keys: HashMap<_, _> = network_accounts
+								.iter()
+								.map(|(network_id, account)| {
+									(*network_id, account.get_private_key())
+								})
+								.collect();
+
+							// Create the implementation of each chain on its first use
+							if solver_config.delivery.lazy_init {
+								let network_ids = config
+									.get("network_ids")
+									.and_then(|v| v.as_array())
+									.ok_or_else(|| {
+										BuilderError::Config(format!(
+											"Delivery implementation '{}' missing network_ids configuration",
+											name
+										))
+									})?;
+								for network_id in network_ids.iter().filter_map(|v| v.as_integer())
+								{
 									let network_id = network_id as u64;
-									delivery_implementations
-										.insert(network_id, implementation_arc.clone());
-									tracing::info!(component = "delivery", implementation = %name, network_id = %network_id, "Loaded");
+									let mut chain_config = config.clone();
+									if let Some(table) = chain_config.as_table_mut() {
+										table.insert(
+											"network_ids".to_string(),
+											toml::Value::Array(vec![toml::Value::Integer(
+												network_id as i64,
+											)]),
+										);
+									}
+									let factory = factory.clone();
+									let networks = solver_config.networks.clone();
+									let default_private_key = default_private_key.clone();
+									let network_private_keys = network_private_keys.clone();
+									let rpc_stats = rpc_stats.clone();
+									let rate_limits = rate_limits.clone();
+									let lazy_factory: LazyDeliveryFactory = Arc::new(move || {
+										let implementation = factory(
+											&chain_config,
+											&networks,
+											&default_private_key,
+											&network_private_keys,
+										);
+										let rpc_stats = rpc_stats.clone();
+										let rate_limits = rate_limits.clone();
+										Box::pin(async move {
+											Ok(wrap_delivery(
+												implementation.await?,
+												&rpc_stats,
+												&rate_limits,
+											))
+										})
+									});
+									lazy_factories.insert(network_id, lazy_factory);
+									let sender =
+										network_accounts.get(&network_id).unwrap_or(&account);
+									sending_accounts.insert(network_id, sender.clone());
+									tracing::info!(component = "delivery", implementation = %name, network_id = %network_id, "Deferred until first use");
 								}
+								continue;
 							}
-						} else {
+
+							match factory(
+								config,
+								&solver_config.networks,
+								&default_private_key,
+								&network_private_keys,
+							)
+							.await
+							{
+								Ok(implementation) => {
+									// Extract network_ids from config to create the mapping
+									if let Some(network_ids) =
+										config.get("network_ids").and_then(|v| v.as_array())
+									{
+										let implementation_arc =
+											wrap_delivery(implementation, &rpc_stats, &rate_limits);
+										for network_id_value in network_ids {
+											if let Some(network_id) = network_id_value.as_integer()
+											{
+												let network_id = network_id as u64;
+												delivery_implementations.insert(
+													solver_types::ChainId::from(network_id),
+													implementation_arc.clone(),
+												);
+												let sender = network_accounts
+													.get(&network_id)
+													.unwrap_or(&account);
+												sending_accounts.insert(network_id, sender.clone());
+												tracing::info!(component = "delivery", implementation = %name, network_id = %network_id, "Loaded");
+											}
+										}
+									} else {
+										tracing::error!(
+											component = "delivery",
+											implementation = %name,
+											"Missing network_ids configuration"
+										);
+										return Err(BuilderError::Config(format!(
+											"Delivery implementation '{}' missing network_ids configuration",
+											name
+										)));
+									}
+								},
+								Err(e) => {
+									tracing::error!(
+										component = "delivery",
+										implementation = %name,
+										error = %e,
+										"Failed to create delivery implementation"
+									);
+									return Err(BuilderError::Config(format!(
+										"Failed to create delivery implementation '{}': {}",
+										name, e
+									)));
+								},
+							}
+						}
+					}
+
+					if delivery_implementations.is_empty() && lazy_factories.is_empty() {
+						tracing::warn!("No delivery implementations available - solver will not be able to submit any transactions");
+					}
+
+					let delivery = Arc::new(
+						DeliveryService::new(
+							delivery_implementations,
+							solver_config.delivery.min_confirmations,
+						)
+						.with_simulation_mode(solver_config.solver.simulation_mode)
+						.with_anvil_nodes(anvil_nodes.clone())
+						.with_wallet_batch(solver_config.solver.use_wallet_batch)
+						.with_gas_strategy(
+							solver_config.delivery.gas_strategy,
+							solver_config.delivery.gas_bid.clone(),
+						)
+						.with_receipt_cache_ttl(Duration::from_secs(
+							solver_config.delivery.receipt_cache_ttl_seconds,
+						))
+						.with_lazy_implementations(lazy_factories)
+						.with_delegating_accounts(sending_accounts)
+						.with_rpc_stats(rpc_stats),
+					);
+					deliveries.push((solver_config.account.clone(), delivery.clone()));
+					delivery
+				},
+			};
+
+			// Create settlement implementations first (needed for oracle routes)
+			let mut settlement_impls = HashMap::new();
+			for (name, config) in &solver_config.settlement.implementations {
+				if let Some(factory) = factories.settlement_factories.get(name) {
+					match factory(config, &solver_config.networks) {
+						Ok(implementation) => {
+							// Validation already happened in the factory
+							settlement_impls.insert(name.clone(), implementation);
+							tracing::info!(component = "settlement", implementation = %name, "Loaded");
+						},
+						Err(e) => {
 							tracing::error!(
-								component = "delivery",
+								component = "settlement",
 								implementation = %name,
-								"Missing network_ids configuration"
+								error = %e,
+								"Failed to create settlement implementation"
 							);
 							return Err(BuilderError::Config(format!(
-								"Delivery implementation '{}' missing network_ids configuration",
-								name
+								"Failed to create settlement implementation '{}': {}",
+								name, e
 							)));
-						}
-					},
-					Err(e) => {
-						tracing::error!(
-							component = "delivery",
-							implementation = %name,
-							error = %e,
-							"Failed to create delivery implementation"
-						);
-						return Err(BuilderError::Config(format!(
-							"Failed to create delivery implementation '{}': {}",
-							name, e
-						)));
-					},
+						},
+					}
 				}
 			}
-		}
 
-		if delivery_implementations.is_empty() {
-			tracing::warn!("No delivery implementations available - solver will not be able to submit any transactions");
-		}
-
-		let delivery = Arc::new(DeliveryService::new(
-			delivery_implementations,
-			self.config.delivery.min_confirmations,
-		));
+			if settlement_impls.is_empty() {
+				tracing::warn!("No settlement implementations available - solver will not be able to monitor and claim settlements");
+			}
 
-		// Create discovery implementations
-		let mut discovery_implementations = HashMap::new();
-		for (name, config) in &self.config.discovery.implementations {
-			if let Some(factory) = factories.discovery_factories.get(name) {
-				match factory(config, &self.config.networks) {
-					Ok(implementation) => {
-						// Validation already happened in the factory
-						discovery_implementations.insert(name.clone(), implementation);
-						tracing::info!(component = "discovery", implementation = %name, "Loaded");
-					},
-					Err(e) => {
-						tracing::error!(
-							component = "discovery",
-							implementation = %name,
-							error = %e,
-							"Failed to create discovery implementation"
-						);
-						return Err(BuilderError::Config(format!(
-							"Failed to create discovery implementation '{}': {}",
-							name, e
-						)));
-					},
-				}
+			let circuit_config = &solver_config.settlement.claim_circuit_breaker;
+			let mut settlement = SettlementService::new(settlement_impls)
+				.with_claim_circuit_breaker(ClaimSuccessRateCircuitBreaker::new(
+					circuit_config.min_success_rate_pct,
+					circuit_config.min_claims,
+				))
+				.with_proof_cache(ProofCache::new(Duration::from_secs(
+					solver_config.settlement.proof_cache_ttl_seconds,
+				)));
+			if let Some(relay) = &solver_config.settlement.relay {
+				let relayer =
+					ProofRelayer::new(relay.clone(), &solver_config.networks).map_err(|e| {
+						BuilderError::Config(format!("Failed to create proof relayer: {}", e))
+					})?;
+				tracing::info!(component = "settlement", protocol = ?relay.relay_protocol, "Relaying fill proofs");
+				settlement = settlement.with_proof_relayer(relayer);
 			}
-		}
+			let settlement = Arc::new(settlement);
 
-		if discovery_implementations.is_empty() {
-			tracing::warn!(
-				"No discovery implementations available - solver will not discover any new orders"
+			// Orders are validated against the current routes of the settlement
+			// implementations, which change as routes are discovered
+			tracing::info!(
+				oracle_routes = %settlement.build_oracle_routes().supported_routes.len(),
+				"Built oracle routes from settlement implementations"
 			);
-		}
+			let oracle_routes: Arc<dyn solver_types::oracle::OracleRouteSource> =
+				settlement.clone();
+
+			// Create order implementations (now with oracle routes)
+			let mut order_impls = HashMap::new();
+			for (name, config) in &solver_config.order.implementations {
+				if let Some(factory) = factories.order_factories.get(name) {
+					match factory(config, &solver_config.networks, &oracle_routes) {
+						Ok(implementation) => {
+							// Validation already happened in the factory
+							order_impls.insert(name.clone(), implementation);
+							tracing::info!(component = "order", implementation = %name, "Loaded");
+						},
+						Err(e) => {
+							tracing::error!(
+								component = "order",
+								implementation = %name,
+								error = %e,
+								"Failed to create order implementation"
+							);
+							return Err(BuilderError::Config(format!(
+								"Failed to create order implementation '{}': {}",
+								name, e
+							)));
+						},
+					}
+				}
+			}
 
-		let discovery = Arc::new(DiscoveryService::new(discovery_implementations));
+			if order_impls.is_empty() {
+				tracing::warn!("No order implementations available - solver will not be able to process any orders");
+			}
 
-		// Create settlement implementations first (needed for oracle routes)
-		let mut settlement_impls = HashMap::new();
-		for (name, config) in &self.config.settlement.implementations {
-			if let Some(factory) = factories.settlement_factories.get(name) {
-				match factory(config, &self.config.networks) {
-					Ok(implementation) => {
-						// Validation already happened in the factory
-						settlement_impls.insert(name.clone(), implementation);
-						tracing::info!(component = "settlement", implementation = %name, "Loaded");
-					},
-					Err(e) => {
-						tracing::error!(
-							component = "settlement",
-							implementation = %name,
-							error = %e,
-							"Failed to create settlement implementation"
-						);
-						return Err(BuilderError::Config(format!(
-							"Failed to create settlement implementation '{}': {}",
-							name, e
-						)));
-					},
+			// Create strategy implementations
+			let mut strategy_impls = HashMap::new();
+			for (name, config) in &solver_config.order.strategy.implementations {
+				if let Some(factory) = factories.strategy_factories.get(name) {
+					match factory(config) {
+						Ok(implementation) => {
+							strategy_impls.insert(name.clone(), implementation);
+							let is_primary = &solver_config.order.strategy.primary == name;
+							tracing::info!(component = "strategy", implementation = %name, enabled = %is_primary, "Loaded");
+						},
+						Err(e) => {
+							tracing::error!(
+								component = "strategy",
+								implementation = %name,
+								error = %e,
+								"Failed to create strategy implementation"
+							);
+							return Err(BuilderError::Config(format!(
+								"Failed to create strategy implementation '{}': {}",
+								name, e
+							)));
+						},
+					}
 				}
 			}
-		}
 
-		if settlement_impls.is_empty() {
-			tracing::warn!("No settlement implementations available - solver will not be able to monitor and claim settlements");
-		}
+			if strategy_impls.is_empty() {
+				return Err(BuilderError::Config(
+					"No strategy implementations available".to_string(),
+				));
+			}
 
-		let settlement = Arc::new(SettlementService::new(settlement_impls));
+			// Use the primary strategy implementation
+			let primary_strategy = solver_config.order.strategy.primary.as_str();
+			let strategy = strategy_impls.remove(primary_strategy).ok_or_else(|| {
+				BuilderError::Config(format!(
+					"Primary strategy '{}' failed to load or has invalid configuration",
+					primary_strategy
+				))
+			})?;
+
+			// Spread fills over time
+			let strategy: Box<dyn ExecutionStrategy> = match &solver_config.order.strategy.twap {
+				Some(twap) => Box::new(TwapStrategy::new(
+					strategy,
+					solver_storage.clone(),
+					twap.max_fills_per_window,
+					twap.window_seconds,
+					twap.granularity,
+				)),
+				None => strategy,
+			};
+
+			// Defer orders to predicted low gas windows while gas is expensive
+			let strategy: Box<dyn ExecutionStrategy> =
+				match &solver_config.order.strategy.gas_window {
+					Some(gas_window) => Box::new(GasAwareStrategy::new(
+						strategy,
+						Arc::new(GasHistoryPredictor(GasPriceHistoryAnalyzer::new(
+							solver_storage.clone(),
+							delivery.clone(),
+							solver_config
+								.networks
+								.keys()
+								.map(|chain_id| chain_id.as_u64())
+								.collect(),
+						))),
+						gas_window.max_gas_price_gwei,
+						gas_window.lookahead_hours,
+						solver_config.order.min_fill_window_seconds,
+					)),
+					None => strategy,
+				};
+
+			// Skip orders expiring before a fill could be confirmed
+			let strategy: Box<dyn ExecutionStrategy> = Box::new(ExpiryFilter::new(
+				strategy,
+				solver_config.discovery.preflight_expiry_buffer_seconds,
+			));
 
-		// Build oracle routes from settlement implementations
-		let oracle_routes = settlement.build_oracle_routes();
-		tracing::info!(
-			oracle_routes = %oracle_routes.supported_routes.len(),
-			"Built oracle routes from settlement implementations"
-		);
+			let mut order = OrderService::with_templates(
+				order_impls,
+				strategy,
+				solver_config.order.templates.clone(),
+				solver_config.networks.clone(),
+			);
+			if let Some(predictor_config) = &solver_config.order.predictor {
+				let predictor: Arc<dyn FillPredictor> = match predictor_config {
+					PredictorConfig::Historical {
+						default_fill_time_seconds,
+						max_samples,
+					} => Arc::new(HistoricalFillPredictor::new(
+						solver_storage.clone(),
+						Duration::from_secs(*default_fill_time_seconds),
+						*max_samples,
+					)),
+					PredictorConfig::Static { fill_time_seconds } => Arc::new(
+						StaticPredictor::new(Duration::from_secs(*fill_time_seconds)),
+					),
+				};
+				order = order.with_predictor(predictor);
+			}
+			let order = Arc::new(order);
+
+			// Create and initialize the TokenManager
+			let approval_benchmark =
+				Arc::new(crate::engine::token_manager::TokenApprovalBenchmark::new(
+					delivery.clone(),
+					solver_storage.clone(),
+				));
+			let token_manager = Arc::new(
+				crate::engine::token_manager::TokenManager::new(
+					solver_config.networks.clone(),
+					delivery.clone(),
+					account.clone(),
+				)
+				.with_approval_benchmark(approval_benchmark),
+			);
 
-		// Create order implementations (now with oracle routes)
-		let mut order_impls = HashMap::new();
-		for (name, config) in &self.config.order.implementations {
-			if let Some(factory) = factories.order_factories.get(name) {
-				match factory(config, &self.config.networks, &oracle_routes) {
-					Ok(implementation) => {
-						// Validation already happened in the factory
-						order_impls.insert(name.clone(), implementation);
-						tracing::info!(component = "order", implementation = %name, "Loaded");
-					},
-					Err(e) => {
-						tracing::error!(
-							component = "order",
-							implementation = %name,
-							error = %e,
-							"Failed to create order implementation"
-						);
-						return Err(BuilderError::Config(format!(
-							"Failed to create order implementation '{}': {}",
-							name, e
-						)));
-					},
-				}
+			// Ensure all token approvals are set
+			match token_manager.ensure_approvals().await {
+				Ok(()) => {
+					tracing::info!(
+						component = "token_manager",
+						networks = solver_config.networks.len(),
+						"Token manager initialized with approvals"
+					);
+				},
+				Err(e) => {
+					tracing::error!(
+						component = "token_manager",
+						error = %e,
+						"Failed to ensure token approvals"
+					);
+					return Err(BuilderError::Config(format!(
+						"Failed to ensure token approvals: {}",
+						e
+					)));
+				},
 			}
-		}
 
-		if order_impls.is_empty() {
-			tracing::warn!("No order implementations available - solver will not be able to process any orders");
-		}
+			// Log initial balances for monitoring
+			match token_manager.check_balances().await {
+				Ok(balances) => {
+					for ((chain_id, token), balance) in &balances {
+						let formatted_balance = format!(
+							"{} {}",
+							solver_types::format_token_amount(balance, token.decimals),
+							token.symbol
+						);
 
-		// Create strategy implementations
-		let mut strategy_impls = HashMap::new();
-		for (name, config) in &self.config.order.strategy.implementations {
-			if let Some(factory) = factories.strategy_factories.get(name) {
-				match factory(config) {
-					Ok(implementation) => {
-						strategy_impls.insert(name.clone(), implementation);
-						let is_primary = &self.config.order.strategy.primary == name;
-						tracing::info!(component = "strategy", implementation = %name, enabled = %is_primary, "Loaded");
-					},
-					Err(e) => {
-						tracing::error!(
-							component = "strategy",
-							implementation = %name,
-							error = %e,
-							"Failed to create strategy implementation"
+						tracing::info!(
+							chain_id = chain_id,
+							token = %token.symbol,
+							balance = %formatted_balance,
+							"Initial solver balance"
 						);
-						return Err(BuilderError::Config(format!(
-							"Failed to create strategy implementation '{}': {}",
-							name, e
-						)));
-					},
-				}
+					}
+				},
+				Err(e) => {
+					tracing::warn!(
+						error = %e,
+						"Failed to check initial balances"
+					);
+				},
 			}
-		}
 
-		if strategy_impls.is_empty() {
-			return Err(BuilderError::Config(
-				"No strategy implementations available".to_string(),
+			let telemetry =
+				crate::telemetry::create_sink(&solver_config.telemetry.sink).map_err(|e| {
+					BuilderError::Config(format!("Failed to create telemetry sink: {}", e))
+				})?;
+
+			engines.push(SolverEngine::new(
+				solver_config,
+				solver_storage,
+				account,
+				solver_address,
+				delivery,
+				discovery.clone(),
+				order,
+				settlement,
+				EventBus::new(1000),
+				token_manager,
+				price.clone(),
+				telemetry,
 			));
 		}
 
-		// Use the primary strategy implementation
-		let primary_strategy = self.config.order.strategy.primary.as_str();
-		let strategy = strategy_impls.remove(primary_strategy).ok_or_else(|| {
-			BuilderError::Config(format!(
-				"Primary strategy '{}' failed to load or has invalid configuration",
-				primary_strategy
-			))
-		})?;
+		// Only the first solver runs discovery, forwarding intents to the others
+		let followers = engines
+			.iter()
+			.skip(1)
+			.map(|engine| engine.intent_tx.clone())
+			.collect();
+		for engine in engines.iter_mut().skip(1) {
+			engine.discovery_role = DiscoveryRole::Follower;
+		}
+		if let Some(leader) = engines.first_mut() {
+			leader.discovery_role = DiscoveryRole::Leader(followers);
+		}
 
-		let order = Arc::new(OrderService::new(order_impls, strategy));
+		Ok(engines)
+	}
+}
 
-		// Create and initialize the TokenManager
-		let token_manager = Arc::new(crate::engine::token_manager::TokenManager::new(
-			self.config.networks.clone(),
-			delivery.clone(),
-			account.clone(),
-		));
+#[cfg(test)]
+mod tests {
+	use super::*;
 
-		// Ensure all token approvals are set
-		match token_manager.ensure_approvals().await {
-			Ok(()) => {
-				tracing::info!(
-					component = "token_manager",
-					networks = self.config.networks.len(),
-					"Token manager initialized with approvals"
-				);
-			},
-			Err(e) => {
-				tracing::error!(
-					component = "token_manager",
-					error = %e,
-					"Failed to ensure token approvals"
-				);
-				return Err(BuilderError::Config(format!(
-					"Failed to ensure token approvals: {}",
-					e
-				)));
-			},
-		}
+	// Second and third default Foundry/Anvil accounts
+	const DEFAULT_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+	const OP_FILLER_KEY: &str =
+		"0x5de4111afa1a4b94908f83103eb1f1706367c2e68ca870fc3fb9a804cdab365a";
 
-		// Log initial balances for monitoring
-		match token_manager.check_balances().await {
-			Ok(balances) => {
-				for ((chain_id, token), balance) in &balances {
-					let formatted_balance = format!(
-						"{} {}",
-						solver_types::format_token_amount(balance, token.decimals),
-						token.symbol
-					);
+	fn account(private_key: &str) -> Arc<AccountService> {
+		let config: toml::Value =
+			toml::from_str(&format!("private_key = \"{}\"", private_key)).unwrap();
+		Arc::new(AccountService::new(
+			solver_account::implementations::local::create_account(&config).unwrap(),
+		))
+	}
 
-					tracing::info!(
-						chain_id = chain_id,
-						token = %token.symbol,
-						balance = %formatted_balance,
-						"Initial solver balance"
-					);
-				}
-			},
-			Err(e) => {
-				tracing::warn!(
-					error = %e,
-					"Failed to check initial balances"
-				);
+	#[tokio::test]
+	async fn test_network_accounts_route_chains_to_accounts() {
+		let primary = account(DEFAULT_KEY);
+		let op_filler = account(OP_FILLER_KEY);
+		let account_services = HashMap::from([
+			("local".to_string(), primary.clone()),
+			("op_filler".to_string(), op_filler.clone()),
+		]);
+
+		let config: toml::Value =
+			toml::from_str("network_ids = [1, 10]\n[accounts]\n10 = \"op_filler\"").unwrap();
+		let accounts = network_accounts("evm_alloy", &config, &account_services).unwrap();
+
+		// Chain 10 is signed with the op_filler key, chain 1 falls back to the primary
+		let sender = accounts.get(&10).unwrap();
+		assert_eq!(
+			sender.get_private_key().expose_secret(),
+			op_filler.get_private_key().expose_secret()
+		);
+		assert_eq!(
+			sender.get_address().await.unwrap(),
+			op_filler.get_address().await.unwrap()
+		);
+		assert_ne!(
+			sender.get_address().await.unwrap(),
+			primary.get_address().await.unwrap()
+		);
+		assert!(!accounts.contains_key(&1));
+
+		let config: toml::Value = toml::from_str("[accounts]\n10 = \"missing\"").unwrap();
+		match network_accounts("evm_alloy", &config, &account_services) {
+			Err(BuilderError::Config(msg)) => {
+				assert!(msg.contains("'missing'"));
+				assert!(msg.contains("local, op_filler"));
 			},
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
 		}
-
-		Ok(SolverEngine::new(
-			self.config,
-			storage,
-			account,
-			solver_address,
-			delivery,
-			discovery,
-			order,
-			settlement,
-			EventBus::new(1000),
-			token_manager,
-		))
 	}
 }
@@ -0,0 +1,181 @@
+//! Cancellation of orders requested by their users.
+//!
+//! A user cancels an order the solver has not started executing by signing the
+//! message `cancel:{order_id}:{timestamp}` with EIP-191 `personal_sign`. The
+//! signer must be the sponsor of the order, or its user for orders without a
+//! sponsor. Signatures are only accepted close to their timestamp, so a
+//! leaked cancellation cannot be replayed much later.
+
+use alloy_primitives::{Address as AlloyAddress, PrimitiveSignature};
+use serde::{Deserialize, Serialize};
+use solver_types::{Eip7683OrderData, Order, OrderStatus};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Maximum distance in seconds between the signed timestamp and the time the
+/// cancellation is processed.
+pub const CANCELLATION_MAX_AGE_SECONDS: u64 = 300;
+
+/// Errors that can occur when cancelling an order.
+#[derive(Debug, Error)]
+pub enum CancellationError {
+	#[error("Order not found: {0}")]
+	NotFound(String),
+	#[error("Order in status {0} can no longer be cancelled")]
+	NotCancellable(OrderStatus),
+	#[error("Cancellation signed at {0} is expired or not yet valid")]
+	Expired(u64),
+	#[error("Invalid signature: {0}")]
+	InvalidSignature(String),
+	#[error("Signer {0} is not the sponsor of the order")]
+	Unauthorized(AlloyAddress),
+	#[error("Storage error: {0}")]
+	Storage(String),
+}
+
+/// A cancellation of an order signed by its user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderCancellation {
+	pub order_id: String,
+	/// Hex-encoded `personal_sign` signature of [`OrderCancellation::message`].
+	pub signature: String,
+	/// Unix timestamp included in the signed message.
+	pub timestamp: u64,
+}
+
+impl OrderCancellation {
+	/// Returns the message the user signs to cancel the order.
+	pub fn message(&self) -> String {
+		format!("cancel:{}:{}", self.order_id, self.timestamp)
+	}
+
+	/// Recovers the address that signed the cancellation.
+	pub fn signer(&self) -> Result<AlloyAddress, CancellationError> {
+		PrimitiveSignature::from_str(&self.signature)
+			.map_err(|e| CancellationError::InvalidSignature(e.to_string()))?
+			.recover_address_from_msg(self.message())
+			.map_err(|e| CancellationError::InvalidSignature(e.to_string()))
+	}
+
+	/// Verifies that the cancellation applies to `order` at `now`: the order
+	/// has no execution in flight yet, the signature is recent and was made by
+	/// the order's sponsor.
+	pub fn verify(&self, order: &Order, now: u64) -> Result<(), CancellationError> {
+		if order.status != OrderStatus::Created {
+			return Err(CancellationError::NotCancellable(order.status.clone()));
+		}
+		if now.abs_diff(self.timestamp) > CANCELLATION_MAX_AGE_SECONDS {
+			return Err(CancellationError::Expired(self.timestamp));
+		}
+
+		let signer = self.signer()?;
+		if order_sponsor(order) != Some(signer) {
+			return Err(CancellationError::Unauthorized(signer));
+		}
+		Ok(())
+	}
+}
+
+/// Returns the address entitled to cancel an order: its sponsor, falling
+/// back to its user.
+fn order_sponsor(order: &Order) -> Option<AlloyAddress> {
+	let order_data = serde_json::from_value::<Eip7683OrderData>(order.data.clone()).ok()?;
+	order_data
+		.sponsor
+		.as_deref()
+		.unwrap_or(&order_data.user)
+		.parse()
+		.ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+	use alloy_signer::SignerSync;
+	use alloy_signer_local::PrivateKeySigner;
+	use solver_types::standards::eip7683::GasLimitOverrides;
+	use solver_types::Address;
+
+	fn order(user: AlloyAddress) -> Order {
+		let data = Eip7683OrderData {
+			user: user.to_string(),
+			nonce: U256::from(1),
+			origin_chain_id: U256::from(1),
+			expires: 0,
+			fill_deadline: 0,
+			input_oracle: AlloyAddress::ZERO.to_string(),
+			inputs: vec![],
+			order_id: [0u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		};
+		Order {
+			id: "order-1".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::to_value(&data).unwrap(),
+			solver_address: Address(vec![0; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![1],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn sign(signer: &PrivateKeySigner, timestamp: u64) -> OrderCancellation {
+		let mut cancellation = OrderCancellation {
+			order_id: "order-1".to_string(),
+			signature: String::new(),
+			timestamp,
+		};
+		let signature = signer
+			.sign_message_sync(cancellation.message().as_bytes())
+			.unwrap();
+		cancellation.signature = signature.to_string();
+		cancellation
+	}
+
+	#[test]
+	fn test_verify_cancellation() {
+		let user = PrivateKeySigner::random();
+		let mut order = order(user.address());
+		let now = 10_000;
+
+		assert!(sign(&user, now - 10).verify(&order, now).is_ok());
+		assert!(matches!(
+			sign(&user, now - CANCELLATION_MAX_AGE_SECONDS - 1).verify(&order, now),
+			Err(CancellationError::Expired(_))
+		));
+		assert!(matches!(
+			sign(&PrivateKeySigner::random(), now).verify(&order, now),
+			Err(CancellationError::Unauthorized(_))
+		));
+
+		// Only the sponsor may cancel sponsored orders
+		let sponsor = PrivateKeySigner::random();
+		let mut data: Eip7683OrderData = serde_json::from_value(order.data.clone()).unwrap();
+		data.sponsor = Some(sponsor.address().to_string());
+		order.data = serde_json::to_value(&data).unwrap();
+		assert!(sign(&sponsor, now).verify(&order, now).is_ok());
+		assert!(sign(&user, now).verify(&order, now).is_err());
+
+		// Orders being executed can no longer be cancelled
+		order.status = OrderStatus::Pending;
+		assert!(matches!(
+			sign(&sponsor, now).verify(&order, now),
+			Err(CancellationError::NotCancellable(_))
+		));
+	}
+}
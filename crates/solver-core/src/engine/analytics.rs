@@ -0,0 +1,271 @@
+//! Order analytics for the solver engine.
+//!
+//! Aggregates throughput statistics over stored orders matching a query.
+//! Each filter is served by a storage index and the matching order sets are
+//! intersected. Results are cached briefly so repeated calls are cheap.
+
+use super::{EngineError, SolverEngine};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use solver_storage::QueryFilter;
+use solver_types::{
+	current_timestamp, Order, OrderAnalytics, OrderAnalyticsQuery, OrderStatus, StorageKey,
+};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// How long aggregated analytics are served from the cache.
+const CACHE_TTL_SECONDS: u64 = 60;
+
+/// Aggregated analytics with the time they were computed.
+#[derive(Serialize, Deserialize)]
+struct CachedAnalytics {
+	computed_at: u64,
+	analytics: OrderAnalytics,
+}
+
+impl SolverEngine {
+	/// Returns aggregated statistics over the orders matching `query`.
+	///
+	/// Volumes are only computed when a price feed is configured.
+	pub async fn order_analytics(
+		&self,
+		query: &OrderAnalyticsQuery,
+	) -> Result<OrderAnalytics, EngineError> {
+		let cache_id = cache_id(query);
+		let now = current_timestamp();
+		if let Ok(cached) = self
+			.storage
+			.retrieve::<CachedAnalytics>(StorageKey::AnalyticsCache.as_str(), &cache_id)
+			.await
+		{
+			if now.saturating_sub(cached.computed_at) < CACHE_TTL_SECONDS {
+				return Ok(cached.analytics);
+			}
+		}
+
+		let orders = self.query_orders(query).await?;
+		let analytics = self.aggregate(&orders).await;
+
+		let cached = CachedAnalytics {
+			computed_at: now,
+			analytics: analytics.clone(),
+		};
+		if let Err(e) = self
+			.storage
+			.store_with_ttl(
+				StorageKey::AnalyticsCache.as_str(),
+				&cache_id,
+				&cached,
+				None,
+				Some(Duration::from_secs(CACHE_TTL_SECONDS)),
+			)
+			.await
+		{
+			tracing::debug!(error = %e, "Failed to cache order analytics");
+		}
+
+		Ok(analytics)
+	}
+
+	/// Loads the orders matching all filters of `query`.
+	pub(super) async fn query_orders(
+		&self,
+		query: &OrderAnalyticsQuery,
+	) -> Result<Vec<Order>, EngineError> {
+		let mut filters = analytics_filters(query).into_iter();
+		let first = filters.next().unwrap_or(QueryFilter::All);
+
+		let mut orders: Vec<(String, Order)> = self
+			.storage
+			.query(StorageKey::Orders.as_str(), first)
+			.await
+			.map_err(|e| EngineError::Service(format!("Failed to query orders: {}", e)))?;
+
+		for filter in filters {
+			if orders.is_empty() {
+				break;
+			}
+			let matching: HashSet<String> = self
+				.storage
+				.query::<IgnoredAny>(StorageKey::Orders.as_str(), filter)
+				.await
+				.map_err(|e| EngineError::Service(format!("Failed to query orders: {}", e)))?
+				.into_iter()
+				.map(|(id, _)| id)
+				.collect();
+			orders.retain(|(id, _)| matching.contains(id));
+		}
+
+		Ok(orders.into_iter().map(|(_, order)| order).collect())
+	}
+
+	/// Aggregates statistics over a set of orders.
+	async fn aggregate(&self, orders: &[Order]) -> OrderAnalytics {
+		let mut total_input_volume_usd = 0.0;
+		let mut total_output_volume_usd = 0.0;
+		if let Some(price) = &self.price {
+			for order in orders {
+				match self.order_value_usd(price, order).await {
+					Ok((input_usd, output_usd)) => {
+						total_input_volume_usd += input_usd;
+						total_output_volume_usd += output_usd;
+					},
+					Err(e) => {
+						tracing::debug!(order_id = %order.id, error = %e, "Failed to price order");
+					},
+				}
+			}
+		}
+
+		let mut stats = summarize(orders);
+		stats.total_input_volume_usd = total_input_volume_usd;
+		stats.total_output_volume_usd = total_output_volume_usd;
+		stats
+	}
+}
+
+/// Returns the storage filters selecting the orders of a query.
+///
+/// Timestamp bounds are inclusive, while range filters are strict.
+fn analytics_filters(query: &OrderAnalyticsQuery) -> Vec<QueryFilter> {
+	let mut filters = Vec::new();
+	if let Some(from_ts) = query.from_ts.filter(|ts| *ts > 0) {
+		filters.push(QueryFilter::GreaterThan(
+			"created_at".to_string(),
+			serde_json::json!(from_ts - 1),
+		));
+	}
+	if let Some(to_ts) = query.to_ts {
+		filters.push(QueryFilter::LessThan(
+			"created_at".to_string(),
+			serde_json::json!(to_ts.saturating_add(1)),
+		));
+	}
+	if let Some(status) = &query.status {
+		filters.push(QueryFilter::Equals(
+			"status".to_string(),
+			serde_json::json!(status),
+		));
+	}
+	if let Some(chain_id) = query.chain_id {
+		filters.push(QueryFilter::Equals(
+			"chain_id".to_string(),
+			serde_json::json!(chain_id),
+		));
+	}
+	if let Some(standard) = &query.standard {
+		filters.push(QueryFilter::Equals(
+			"standard".to_string(),
+			serde_json::json!(standard),
+		));
+	}
+	filters
+}
+
+/// Computes counts, fill times and success rate, without volumes.
+fn summarize(orders: &[Order]) -> OrderAnalytics {
+	let fill_times: Vec<u64> = orders
+		.iter()
+		.filter_map(|order| {
+			let proof = order.fill_proof.as_ref()?;
+			Some(proof.filled_timestamp().saturating_sub(order.created_at))
+		})
+		.collect();
+	let avg_fill_time_seconds = (!fill_times.is_empty())
+		.then(|| fill_times.iter().sum::<u64>() as f64 / fill_times.len() as f64);
+
+	let succeeded = orders
+		.iter()
+		.filter(|order| matches!(order.status, OrderStatus::Settled | OrderStatus::Finalized))
+		.count();
+	let failed = orders
+		.iter()
+		.filter(|order| matches!(order.status, OrderStatus::Failed(_)))
+		.count();
+	let success_rate_pct =
+		(succeeded + failed > 0).then(|| succeeded as f64 * 100.0 / (succeeded + failed) as f64);
+
+	OrderAnalytics {
+		count: orders.len(),
+		total_input_volume_usd: 0.0,
+		total_output_volume_usd: 0.0,
+		avg_fill_time_seconds,
+		success_rate_pct,
+	}
+}
+
+/// Derives the cache ID of a query from its parameters.
+fn cache_id(query: &OrderAnalyticsQuery) -> String {
+	fn part<T: ToString>(value: &Option<T>) -> String {
+		value.as_ref().map(T::to_string).unwrap_or_default()
+	}
+	format!(
+		"{}_{}_{}_{}_{}",
+		part(&query.from_ts),
+		part(&query.to_ts),
+		part(&query.status),
+		part(&query.chain_id),
+		part(&query.standard)
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{Address, FillProof, StandardFillProof, TransactionHash, TransactionType};
+
+	fn order(status: OrderStatus, filled_after: Option<u64>) -> Order {
+		Order {
+			id: "order".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 1000,
+			updated_at: 1000,
+			status,
+			data: serde_json::json!({}),
+			solver_address: Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: filled_after.map(|seconds| {
+				FillProof::Standard(StandardFillProof {
+					tx_hash: TransactionHash(vec![0u8; 32]),
+					block_number: 1,
+					attestation_data: None,
+					filled_timestamp: 1000 + seconds,
+					oracle_address: String::new(),
+					proof_version: 1,
+				})
+			}),
+		}
+	}
+
+	#[test]
+	fn test_summarize_orders() {
+		let orders = vec![
+			order(OrderStatus::Finalized, Some(30)),
+			order(OrderStatus::Settled, Some(90)),
+			order(OrderStatus::Failed(TransactionType::Fill), None),
+			order(OrderStatus::Pending, None),
+		];
+
+		let analytics = summarize(&orders);
+		assert_eq!(analytics.count, 4);
+		assert_eq!(analytics.avg_fill_time_seconds, Some(60.0));
+		assert!((analytics.success_rate_pct.unwrap() - 200.0 / 3.0).abs() < 1e-9);
+		assert!(summarize(&[]).success_rate_pct.is_none());
+
+		let filters = analytics_filters(&OrderAnalyticsQuery {
+			from_ts: Some(100),
+			chain_id: Some(1),
+			..Default::default()
+		});
+		assert_eq!(filters.len(), 2);
+		assert!(matches!(&filters[0], QueryFilter::GreaterThan(field, value)
+			if field == "created_at" && value == &serde_json::json!(99)));
+	}
+}
@@ -5,6 +5,7 @@
 //! gas prices and solver balances.
 
 use super::token_manager::TokenManager;
+use crate::gas_budget::GasBudgetManager;
 use crate::SolverError;
 use alloy_primitives::hex;
 use solver_config::Config;
@@ -22,6 +23,7 @@ pub struct ContextBuilder {
 	delivery: Arc<DeliveryService>,
 	solver_address: Address,
 	token_manager: Arc<TokenManager>,
+	gas_budget: Arc<GasBudgetManager>,
 	_config: Config,
 }
 
@@ -31,12 +33,14 @@ impl ContextBuilder {
 		delivery: Arc<DeliveryService>,
 		solver_address: Address,
 		token_manager: Arc<TokenManager>,
+		gas_budget: Arc<GasBudgetManager>,
 		config: Config,
 	) -> Self {
 		Self {
 			delivery,
 			solver_address,
 			token_manager,
+			gas_budget,
 			_config: config,
 		}
 	}
@@ -83,10 +87,14 @@ impl ContextBuilder {
 		// 3. Get solver balances for relevant chains/tokens
 		let solver_balances = self.fetch_solver_balances(&involved_chains).await?;
 
+		// 4. Get remaining gas budgets
+		let gas_budget_remaining = self.gas_budget.remaining().await;
+
 		Ok(ExecutionContext {
 			chain_data,
 			solver_balances,
 			timestamp,
+			gas_budget_remaining,
 		})
 	}
 
@@ -4,28 +4,56 @@
 //! all services (discovery, order processing, delivery, settlement) and manages
 //! the main event loop for processing intents and orders.
 
+pub mod analytics;
 pub mod context;
 pub mod event_bus;
 pub mod lifecycle;
+pub mod preview;
+pub mod report;
 pub mod token_manager;
 
-use self::token_manager::TokenManager;
+use self::token_manager::{TokenDiscovery, TokenGasEstimate, TokenManager};
+use crate::arbitrage::ArbitrageDetector;
+use crate::audit::{self, AuditEntry, AuditPage};
+use crate::batch_size::BatchSizeOptimizer;
+use crate::cancellation::{CancellationError, OrderCancellation};
+use crate::event_persister::{self, EventLogEntry, EventPersister};
+use crate::gas_budget::GasBudgetManager;
+use crate::gossip::GossipService;
+use crate::handlers::order::DeliverySimulator;
 use crate::handlers::{IntentHandler, OrderHandler, SettlementHandler, TransactionHandler};
-use crate::recovery::RecoveryService;
+use crate::inventory::InventoryManager;
+use crate::rebalancing::RebalancingService;
+use crate::recovery::{
+	self, checkpoint::CheckpointService, RecoveredAction, RecoveryReport, RecoveryService,
+};
+use crate::reputation::{PeerReputation, ReputationService};
+use crate::state::dead_letter::{self, DeadLetter};
 use crate::state::OrderStateMachine;
+use crate::telemetry::{self, TelemetrySink};
+use crate::webhooks::{self, EventWebhookPublisher, WebhookRetryEntry, WebhookRetryQueue};
+use alloy_primitives::U256;
+use dashmap::DashMap;
 use solver_account::AccountService;
 use solver_config::Config;
-use solver_delivery::DeliveryService;
-use solver_discovery::DiscoveryService;
+use solver_delivery::{DeliveryService, GasPriceHistoryAnalyzer};
+use solver_discovery::{health::DiscoveryHealthMonitor, DiscoveryService, DiscoveryStartReport};
+use solver_order::nonce_coordinator::NonceCoordinator;
 use solver_order::OrderService;
+use solver_price::PriceService;
 use solver_settlement::SettlementService;
-use solver_storage::StorageService;
-use solver_types::{Address, DeliveryEvent, Intent, OrderEvent, SettlementEvent, SolverEvent};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{
+	current_timestamp, truncate_id, Address, AuditQuery, DeliveryEvent, EventLogQuery,
+	ExecutionParams, Intent, Order, OrderEvent, OrderStatus, OrderTemplate, SettlementEvent,
+	SimulationResult, SolverEvent, StorageKey, TransactionType,
+};
 use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
 
 /// Errors that can occur during engine operations.
 ///
@@ -42,6 +70,16 @@ pub enum EngineError {
 	Handler(String),
 }
 
+/// Role of an engine in discovery shared between solvers of one process.
+#[derive(Clone)]
+pub(crate) enum DiscoveryRole {
+	/// Runs the discovery sources and storage cleanup, forwarding discovered
+	/// intents to the given senders of the other solvers.
+	Leader(Vec<mpsc::UnboundedSender<Intent>>),
+	/// Receives intents from the leading solver.
+	Follower,
+}
+
 /// Main solver engine that orchestrates the order execution lifecycle.
 #[derive(Clone)]
 pub struct SolverEngine {
@@ -68,6 +106,28 @@ pub struct SolverEngine {
 	pub(crate) token_manager: Arc<TokenManager>,
 	/// Event bus for inter-service communication.
 	pub(crate) event_bus: event_bus::EventBus,
+	/// Price service for token price lookups, if configured.
+	pub(crate) price: Option<Arc<PriceService>>,
+	/// External telemetry sink, if configured.
+	pub(crate) telemetry: Option<Arc<dyn TelemetrySink>>,
+	/// Per-chain gas budget tracking.
+	pub(crate) gas_budget: Arc<GasBudgetManager>,
+	/// Output token reservations of pending fills.
+	pub(crate) inventory: Arc<InventoryManager>,
+	/// Metadata of tokens missing from the networks config.
+	pub(crate) token_discovery: Arc<TokenDiscovery>,
+	/// Reputation of this solver and its gossip peers.
+	pub(crate) reputation: Arc<ReputationService>,
+	/// Gossip service of the running engine, if gossip is enabled.
+	pub(crate) gossip: Arc<std::sync::Mutex<Option<Arc<GossipService>>>>,
+	/// Locks of order nonces held by pending off-chain orders.
+	pub(crate) nonces: Arc<NonceCoordinator>,
+	/// Sender for intents to be processed, shared with discovery and replays.
+	pub(crate) intent_tx: mpsc::UnboundedSender<Intent>,
+	/// Receiver for intents, taken by the main loop when the engine runs.
+	pub(crate) intent_rx: Arc<tokio::sync::Mutex<Option<mpsc::UnboundedReceiver<Intent>>>>,
+	/// Whether this engine runs discovery or receives intents from another solver.
+	pub(crate) discovery_role: DiscoveryRole,
 	/// Order state machine
 	#[allow(dead_code)]
 	pub(crate) state_machine: Arc<OrderStateMachine>,
@@ -79,14 +139,11 @@ pub struct SolverEngine {
 	pub(crate) transaction_handler: Arc<TransactionHandler>,
 	/// Settlement handler
 	pub(crate) settlement_handler: Arc<SettlementHandler>,
+	/// Fill semaphores of the chains with a configured fill concurrency,
+	/// keyed by output chain ID.
+	pub(crate) fill_semaphores: Arc<DashMap<u64, Arc<Semaphore>>>,
 }
 
-/// Number of orders to batch together for claim operations.
-///
-/// This constant defines how many orders are batched together when
-/// submitting claim transactions to reduce gas costs.
-static CLAIM_BATCH: usize = 1;
-
 impl SolverEngine {
 	/// Creates a new solver engine with the given services.
 	///
@@ -106,6 +163,8 @@ impl SolverEngine {
 	/// * `settlement` - Service for monitoring and claiming settlements
 	/// * `event_bus` - Event bus for inter-service communication
 	/// * `token_manager` - Manager for token approvals and validation
+	/// * `price` - Optional service for token price lookups
+	/// * `telemetry` - Optional sink for exporting telemetry
 	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		config: Config,
@@ -118,8 +177,26 @@ impl SolverEngine {
 		settlement: Arc<SettlementService>,
 		event_bus: event_bus::EventBus,
 		token_manager: Arc<TokenManager>,
+		price: Option<Arc<PriceService>>,
+		telemetry: Option<Box<dyn TelemetrySink>>,
 	) -> Self {
 		let state_machine = Arc::new(OrderStateMachine::new(storage.clone()));
+		let (intent_tx, intent_rx) = mpsc::unbounded_channel();
+		let gas_budget = Arc::new(GasBudgetManager::new(
+			config.solver.daily_gas_budgets(),
+			storage.clone(),
+			event_bus.clone(),
+		));
+		let inventory = Arc::new(InventoryManager::new(token_manager.clone()));
+		let token_discovery = Arc::new(TokenDiscovery::new(delivery.clone(), storage.clone()));
+		let nonces = Arc::new(NonceCoordinator::new(storage.clone()));
+		let reputation = Arc::new(ReputationService::new(
+			config
+				.solver
+				.gossip
+				.as_ref()
+				.map_or(30, |gossip| gossip.reputation_broadcast_interval_seconds),
+		));
 
 		let intent_handler = Arc::new(IntentHandler::new(
 			order.clone(),
@@ -129,6 +206,11 @@ impl SolverEngine {
 			delivery.clone(),
 			solver_address,
 			token_manager.clone(),
+			gas_budget.clone(),
+			inventory.clone(),
+			nonces.clone(),
+			price.clone(),
+			token_discovery.clone(),
 			config.clone(),
 		));
 
@@ -138,6 +220,8 @@ impl SolverEngine {
 			storage.clone(),
 			state_machine.clone(),
 			event_bus.clone(),
+			nonces.clone(),
+			inventory.clone(),
 		));
 
 		let transaction_handler = Arc::new(TransactionHandler::new(
@@ -146,7 +230,11 @@ impl SolverEngine {
 			storage.clone(),
 			state_machine.clone(),
 			event_bus.clone(),
+			gas_budget.clone(),
+			inventory.clone(),
+			nonces.clone(),
 			config.solver.monitoring_timeout_minutes,
+			config.solver.expiry_grace_period_seconds,
 		));
 
 		let settlement_handler = Arc::new(SettlementHandler::new(
@@ -156,8 +244,24 @@ impl SolverEngine {
 			storage.clone(),
 			state_machine.clone(),
 			event_bus.clone(),
+			BatchSizeOptimizer::new(&config.settlement.batch),
+			config.settlement.max_claim_groups_parallel,
 		));
 
+		let fill_semaphores = Arc::new(
+			config
+				.solver
+				.concurrency
+				.iter()
+				.map(|(chain_id, concurrency)| {
+					(
+						*chain_id,
+						Arc::new(Semaphore::new(concurrency.max_concurrent_fills)),
+					)
+				})
+				.collect::<DashMap<_, _>>(),
+		);
+
 		Self {
 			config,
 			storage,
@@ -168,11 +272,23 @@ impl SolverEngine {
 			settlement,
 			token_manager,
 			event_bus,
+			price,
+			telemetry: telemetry.map(Arc::from),
+			gas_budget,
+			inventory,
+			token_discovery,
+			reputation,
+			gossip: Arc::new(std::sync::Mutex::new(None)),
+			nonces,
+			intent_tx,
+			intent_rx: Arc::new(tokio::sync::Mutex::new(Some(intent_rx))),
+			discovery_role: DiscoveryRole::Leader(Vec::new()),
 			state_machine,
 			intent_handler,
 			order_handler,
 			transaction_handler,
 			settlement_handler,
+			fill_semaphores,
 		}
 	}
 
@@ -211,6 +327,9 @@ impl SolverEngine {
 					report.reconciled_orders
 				);
 
+				self.discover_order_tokens().await;
+				self.restore_reservations(&report).await;
+
 				// Events have already been published by the recovery service
 				Ok(orphaned_intents)
 			},
@@ -222,6 +341,53 @@ impl SolverEngine {
 		}
 	}
 
+	/// Reserves the outputs of the orders whose fill recovery resumed, whose
+	/// in-memory reservations were lost with the restart.
+	async fn restore_reservations(&self, report: &RecoveryReport) {
+		for summary in &report.orders {
+			if !matches!(
+				summary.recovered_action,
+				RecoveredAction::NeedsExecution | RecoveredAction::NeedsFill
+			) {
+				continue;
+			}
+			match self.state_machine.get_order(&summary.order_id).await {
+				// Deferred orders are reserved again once they are executed
+				Ok(order) if order.execution_params.is_some() => {
+					self.inventory.restore_outputs(&order).await;
+				},
+				Ok(_) => {},
+				Err(e) => {
+					tracing::warn!(order_id = %truncate_id(&summary.order_id), error = %e, "Failed to restore inventory reservations");
+				},
+			}
+		}
+	}
+
+	/// Discovers the metadata of tokens used by stored orders that are
+	/// missing from the networks config.
+	async fn discover_order_tokens(&self) {
+		let orders: Vec<Order> = match self
+			.storage
+			.retrieve_all::<Order>(StorageKey::Orders.as_str())
+			.await
+		{
+			Ok(orders) => orders.into_iter().map(|(_, order)| order).collect(),
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to load orders for token discovery");
+				return;
+			},
+		};
+
+		let discovered = self
+			.token_discovery
+			.discover(&self.config.networks, &orders)
+			.await;
+		if discovered > 0 {
+			tracing::info!(discovered, "Discovered metadata of unconfigured tokens");
+		}
+	}
+
 	/// Main execution loop for the solver engine.
 	///
 	/// This method runs the core event-driven processing loop that:
@@ -249,7 +415,11 @@ impl SolverEngine {
 		let orphaned_intents = self.initialize_with_recovery().await?;
 
 		// Start discovery monitoring
-		let (intent_tx, mut intent_rx) = mpsc::unbounded_channel();
+		let mut intent_rx =
+			self.intent_rx.lock().await.take().ok_or_else(|| {
+				EngineError::Service("Solver engine is already running".to_string())
+			})?;
+		let intent_tx = self.intent_tx.clone();
 
 		// Re-inject orphaned intents if any
 		for intent in orphaned_intents {
@@ -258,38 +428,220 @@ impl SolverEngine {
 			}
 		}
 
-		self.discovery
-			.start_all(intent_tx)
-			.await
+		// Start discovery sources, restarting inactive ones if health monitoring is enabled
+		let is_leader = matches!(self.discovery_role, DiscoveryRole::Leader(_));
+		let gossip = match &self.config.solver.gossip {
+			Some(gossip) if gossip.enabled && is_leader => Some(GossipService::new(
+				self.solver_id(),
+				gossip.clone(),
+				self.storage.clone(),
+				self.reputation.clone(),
+			)),
+			_ => None,
+		};
+		let (cancellation_tx, mut cancellation_rx) = mpsc::unbounded_channel();
+		let gossip_handles = gossip
+			.as_ref()
+			.map(|gossip| gossip.spawn(self.local_senders(&intent_tx), cancellation_tx.clone()))
+			.unwrap_or_default();
+		*self.gossip.lock().unwrap_or_else(|e| e.into_inner()) = gossip.clone();
+		let (discovery_tx, forward_handle) = self.discovery_sender(intent_tx, gossip);
+		let discovery_health_handle = match &self.config.discovery.health {
+			_ if !is_leader => None,
+			Some(health) => {
+				let sources = if health.sources.is_empty() {
+					self.config
+						.discovery
+						.implementations
+						.keys()
+						.cloned()
+						.collect()
+				} else {
+					health.sources.clone()
+				};
+				let monitor = Arc::new(DiscoveryHealthMonitor::new(
+					sources,
+					Duration::from_secs(health.inactivity_threshold_seconds),
+					health.max_restart_attempts,
+				));
+				let report = self
+					.discovery
+					.start_all_with_health(discovery_tx.clone(), &monitor)
+					.await
+					.map_err(|e| EngineError::Service(e.to_string()))?;
+				log_discovery_start(&report);
+				Some(monitor.spawn(
+					self.discovery.clone(),
+					discovery_tx,
+					Duration::from_secs(health.check_interval_seconds),
+				))
+			},
+			None => {
+				let report = self
+					.discovery
+					.start_all(discovery_tx)
+					.await
+					.map_err(|e| EngineError::Service(e.to_string()))?;
+				log_discovery_start(&report);
+				None
+			},
+		};
+
+		// Start oracle health monitoring
+		let health_interval =
+			Duration::from_secs(self.config.settlement.oracle_health_check_interval_seconds);
+		let health_checker = self
+			.settlement
+			.oracle_health_checker(&self.config.networks)
 			.map_err(|e| EngineError::Service(e.to_string()))?;
+		let health_event_bus = self.event_bus.clone();
+		let health_handle = health_checker.spawn(health_interval, move |event| {
+			health_event_bus.publish(event).ok();
+		});
+
+		// Start oracle route discovery
+		let route_discovery_handle = match self.config.settlement.route_discovery_interval_seconds {
+			Some(interval_seconds) => {
+				let route_discovery = self
+					.settlement
+					.route_discovery(&self.config.networks)
+					.map_err(|e| EngineError::Service(e.to_string()))?;
+				Some(route_discovery.spawn(Duration::from_secs(interval_seconds)))
+			},
+			None => None,
+		};
+
+		// Start arbitrage detection
+		let arbitrage_handle = match (&self.config.solver.arbitrage, &self.price) {
+			(Some(arbitrage), Some(price)) => {
+				let detector = Arc::new(ArbitrageDetector::new(
+					self.config.networks.clone(),
+					price.clone(),
+					self.event_bus.clone(),
+					arbitrage.min_spread_bps,
+				));
+				Some(detector.spawn(Duration::from_secs(arbitrage.interval_seconds)))
+			},
+			_ => None,
+		};
+
+		// Start checkpointing active orders for fast recovery
+		let checkpoint_handle =
+			self.config
+				.solver
+				.checkpoint_interval_seconds
+				.map(|interval_seconds| {
+					let checkpoints = Arc::new(CheckpointService::new(
+						self.storage.clone(),
+						self.delivery.clone(),
+						self.config
+							.networks
+							.keys()
+							.map(|chain_id| chain_id.as_u64())
+							.collect(),
+					));
+					checkpoints.spawn(Duration::from_secs(interval_seconds))
+				});
+
+		// Start sampling gas prices for gas-aware execution timing
+		let gas_history_handle = self
+			.config
+			.order
+			.strategy
+			.gas_window
+			.as_ref()
+			.map(|gas_window| {
+				let analyzer = Arc::new(GasPriceHistoryAnalyzer::new(
+					self.storage.clone(),
+					self.delivery.clone(),
+					self.config
+						.networks
+						.keys()
+						.map(|chain_id| chain_id.as_u64())
+						.collect(),
+				));
+				analyzer.spawn(Duration::from_secs(gas_window.sample_interval_seconds))
+			});
+
+		// Start bridging tokens to chains whose inventory runs low
+		let rebalancing_handle = match &self.config.solver.rebalancing {
+			Some(rebalancing) if self.config.solver.auto_rebalance => {
+				match self.account.get_address().await {
+					Ok(solver_address) => {
+						let service = Arc::new(RebalancingService::new(
+							rebalancing.bridges.clone(),
+							self.inventory.clone(),
+							self.delivery.clone(),
+							self.storage.clone(),
+							self.event_bus.clone(),
+							solver_address,
+							rebalancing.cooldown_seconds,
+						));
+						Some(service.spawn(Duration::from_secs(rebalancing.interval_seconds)))
+					},
+					Err(e) => {
+						tracing::warn!(error = %e, "Rebalancing disabled: failed to get solver address");
+						None
+					},
+				}
+			},
+			_ => None,
+		};
 
-		// Batch claim processing
+		// Start posting order events to webhooks and retrying failed deliveries
+		let webhook_handles = match &self.config.webhooks {
+			Some(webhooks) => {
+				let queue = Arc::new(WebhookRetryQueue::new(self.storage.clone(), webhooks));
+				let publisher = EventWebhookPublisher::new(webhooks.urls.clone(), queue.clone());
+				vec![publisher.spawn(self.event_bus.subscribe()), queue.spawn()]
+			},
+			None => Vec::new(),
+		};
+
+		// Start persisting all solver events for post-mortem debugging
+		let event_log_handle =
+			match EventPersister::new(self.storage.clone(), &self.config.solver.event_log).await {
+				Ok(persister) => Some(persister.spawn(self.event_bus.subscribe())),
+				Err(e) => {
+					tracing::warn!(error = %e, "Event log disabled: failed to load the stored log");
+					None
+				},
+			};
+
+		// Batch claim processing, flushed when the batch reaches the optimal
+		// size or when its oldest claim has waited for the batch timeout
 		let mut claim_batch = Vec::new();
+		let mut claim_deadline: Option<tokio::time::Instant> = None;
+		let claim_timeout = Duration::from_secs(self.config.settlement.batch.batch_timeout_seconds);
 
-		// Start storage cleanup task
+		// Start storage cleanup task, which covers the storage of all solvers
 		let storage = self.storage.clone();
 		let cleanup_interval_seconds = self.config.storage.cleanup_interval_seconds;
 		let cleanup_interval = tokio::time::interval(Duration::from_secs(cleanup_interval_seconds));
-		tracing::info!(
-			"Starting storage cleanup service, will run every {} seconds",
-			cleanup_interval_seconds
-		);
-		let cleanup_handle = tokio::spawn(async move {
-			let mut interval = cleanup_interval;
-			loop {
-				interval.tick().await;
-				match storage.cleanup_expired().await {
-					Ok(0) => {
-						tracing::debug!("Storage cleanup: no expired entries found");
-					},
-					Ok(count) => {
-						tracing::info!("Storage cleanup: removed {} expired entries", count);
-					},
-					Err(e) => {
-						tracing::warn!("Storage cleanup failed: {}", e);
-					},
+		if is_leader {
+			tracing::info!(
+				"Starting storage cleanup service, will run every {} seconds",
+				cleanup_interval_seconds
+			);
+		}
+		let cleanup_handle = is_leader.then(|| {
+			tokio::spawn(async move {
+				let mut interval = cleanup_interval;
+				loop {
+					interval.tick().await;
+					match storage.cleanup_expired().await {
+						Ok(0) => {
+							tracing::debug!("Storage cleanup: no expired entries found");
+						},
+						Ok(count) => {
+							tracing::info!("Storage cleanup: removed {} expired entries", count);
+						},
+						Err(e) => {
+							tracing::warn!("Storage cleanup failed: {}", e);
+						},
+					}
 				}
-			}
+			})
 		});
 
 		// Create separate semaphores for different event types
@@ -301,6 +653,9 @@ impl SolverEngine {
 			tokio::select! {
 				// Handle discovered intents
 				Some(intent) = intent_rx.recv() => {
+					if let Some(sink) = &self.telemetry {
+						sink.record_counter("intents_received", 1, &[("source", &intent.source)]);
+					}
 					self.spawn_handler(&general_semaphore, move |engine| async move {
 						if let Err(e) = engine.intent_handler.handle(intent).await {
 							return Err(EngineError::Service(format!("Failed to handle intent: {}", e)));
@@ -310,12 +665,29 @@ impl SolverEngine {
 					.await;
 				}
 
+				// Apply cancellations gossiped by peer replicas
+				Some(cancellation) = cancellation_rx.recv() => {
+					self.spawn_handler(&general_semaphore, move |engine| async move {
+						if let Err(e) = engine.apply_cancellation(&cancellation).await {
+							tracing::debug!(order_id = %truncate_id(&cancellation.order_id), error = %e, "Ignoring gossiped cancellation");
+						}
+						Ok(())
+					})
+					.await;
+				}
+
 				// Handle events
 				Ok(event) = event_receiver.recv() => {
+					if let Some(sink) = &self.telemetry {
+						telemetry::record_event(sink.as_ref(), &event);
+					}
 					match event {
 						SolverEvent::Order(OrderEvent::Preparing { intent, order, params }) => {
 							// Preparing sends a prepare transaction - use transaction semaphore
 							self.spawn_handler(&transaction_semaphore, move |engine| async move {
+								if engine.is_cancelled(&order.id).await {
+									return Ok(());
+								}
 								if let Err(e) = engine.order_handler.handle_preparation(intent, order, params).await {
 									return Err(EngineError::Service(format!("Failed to handle order preparation: {}", e)));
 								}
@@ -324,8 +696,20 @@ impl SolverEngine {
 							.await;
 						}
 						SolverEvent::Order(OrderEvent::Executing { order, params }) => {
-							// Executing sends a fill transaction - use transaction semaphore
-							self.spawn_handler(&transaction_semaphore, move |engine| async move {
+							// Executing sends a fill transaction - delivery serializes its
+							// submission, so fills only wait for the concurrency limit of
+							// their output chains, inside the task to keep the loop running
+							let fill_semaphore = self.fill_semaphore(&order.output_chain_ids);
+							self.spawn_handler(&general_semaphore, move |engine| async move {
+								let _fill_permit = match fill_semaphore {
+									Some(semaphore) => Some(semaphore.acquire_owned().await.map_err(|e| {
+										EngineError::Service(format!("Failed to acquire fill permit: {}", e))
+									})?),
+									None => None,
+								};
+								if engine.is_cancelled(&order.id).await {
+									return Ok(());
+								}
 								if let Err(e) = engine.order_handler.handle_execution(order, params).await {
 									return Err(EngineError::Service(format!("Failed to handle order execution: {}", e)));
 								}
@@ -334,6 +718,30 @@ impl SolverEngine {
 							.await;
 						}
 
+						SolverEvent::Order(OrderEvent::Deferred { order_id, retry_after }) => {
+							// Waiting must not hold a permit, so the retry is scheduled separately
+							let engine = self.clone();
+							tokio::spawn(async move {
+								tokio::time::sleep(retry_after).await;
+								if engine.is_cancelled(&order_id).await {
+									return;
+								}
+								if let Err(e) = engine.intent_handler.reevaluate(&order_id).await {
+									tracing::warn!(order_id = %truncate_id(&order_id), error = %e, "Failed to re-evaluate deferred order");
+								}
+							});
+						}
+
+						SolverEvent::Order(OrderEvent::Skipped { order_id, reason }) => {
+							self.spawn_handler(&general_semaphore, move |engine| async move {
+								if let Err(e) = report::record_skip(&engine.storage, &order_id, reason).await {
+									tracing::debug!(order_id = %truncate_id(&order_id), error = %e, "Failed to record skipped order");
+								}
+								Ok(())
+							})
+							.await;
+						}
+
 						SolverEvent::Delivery(DeliveryEvent::TransactionPending { order_id, tx_hash, tx_type, tx_chain_id }) => {
 							// Monitoring doesn't send transactions - use general semaphore
 							self.spawn_handler(&general_semaphore, move |engine| async move {
@@ -344,9 +752,20 @@ impl SolverEngine {
 						}
 
 						SolverEvent::Delivery(DeliveryEvent::TransactionConfirmed { order_id, tx_hash, tx_type, receipt }) => {
+							if tx_type == TransactionType::Claim {
+								self.settlement_handler.record_claim_gas(&tx_hash, &receipt);
+							}
 							// Confirmation handling doesn't directly send transactions - use general semaphore
 							// Note: This may trigger OrderEvent::Executing which will be serialized separately
 							self.spawn_handler(&general_semaphore, move |engine| async move {
+								if tx_type == TransactionType::Claim {
+									if let Err(e) = engine.settlement_handler.record_claim_outcome(&order_id, receipt.success).await {
+										tracing::debug!(error = %e, "Failed to record claim outcome");
+									}
+								}
+								if tx_type == TransactionType::Fill {
+									engine.record_fill_reputation(&order_id, receipt.success).await;
+								}
 								if let Err(e) = engine.transaction_handler.handle_confirmed(order_id, tx_hash, tx_type, receipt).await {
 									return Err(EngineError::Service(format!("Failed to handle transaction confirmation: {}", e)));
 								}
@@ -356,8 +775,19 @@ impl SolverEngine {
 						}
 
 						SolverEvent::Delivery(DeliveryEvent::TransactionFailed { order_id, tx_hash, tx_type, error }) => {
+							if tx_type == TransactionType::Claim {
+								self.settlement_handler.forget_claim_tx(&tx_hash);
+							}
 							// Failure handling doesn't send transactions - use general semaphore
 							self.spawn_handler(&general_semaphore, move |engine| async move {
+								if tx_type == TransactionType::Claim {
+									if let Err(e) = engine.settlement_handler.record_claim_outcome(&order_id, false).await {
+										tracing::debug!(error = %e, "Failed to record claim outcome");
+									}
+								}
+								if tx_type == TransactionType::Fill {
+									engine.record_fill_reputation(&order_id, false).await;
+								}
 								if let Err(e) = engine.transaction_handler.handle_failed(order_id, tx_hash, tx_type, error).await {
 									return Err(EngineError::Service(format!("Failed to handle transaction failure: {}", e)));
 								}
@@ -368,24 +798,43 @@ impl SolverEngine {
 
 						SolverEvent::Settlement(SettlementEvent::ClaimReady { order_id }) => {
 							claim_batch.push(order_id);
-							if claim_batch.len() >= CLAIM_BATCH {
-								let mut batch = std::mem::take(&mut claim_batch);
-								claim_batch.clear();
-								// Claim sends a transaction - use transaction semaphore
-								self.spawn_handler(&transaction_semaphore, move |engine| async move {
-									if let Err(e) = engine.settlement_handler.process_claim_batch(&mut batch).await {
-										return Err(EngineError::Service(format!("Failed to process claim batch: {}", e)));
-									}
-									Ok(())
-								})
-								.await;
+							claim_deadline.get_or_insert_with(|| tokio::time::Instant::now() + claim_timeout);
+							if claim_batch.len() >= self.settlement_handler.optimal_batch_size() {
+								claim_deadline = None;
+								self.spawn_claim_batch(&transaction_semaphore, std::mem::take(&mut claim_batch)).await;
 							}
 						}
 
+						SolverEvent::OracleUnhealthy { chain_id, oracle_address } => {
+							tracing::warn!(
+								chain_id = chain_id,
+								oracle = %oracle_address,
+								"Oracle marked unhealthy, skipping it during selection"
+							);
+						}
+
+						SolverEvent::Settlement(SettlementEvent::FillChallenged { order_id }) => {
+							// Challenge handling doesn't send transactions - use general semaphore
+							self.spawn_handler(&general_semaphore, move |engine| async move {
+								if let Err(e) = engine.settlement_handler.handle_fill_challenged(order_id).await {
+									return Err(EngineError::Service(format!("Failed to handle fill challenge: {}", e)));
+								}
+								Ok(())
+							})
+							.await;
+						}
+
 						_ => {}
 					}
 				}
 
+				// Claim partial batches once they time out
+				_ = tokio::time::sleep_until(claim_deadline.unwrap_or_else(tokio::time::Instant::now)), if claim_deadline.is_some() => {
+					claim_deadline = None;
+					tracing::debug!(batch_size = claim_batch.len(), "Claim batch timed out");
+					self.spawn_claim_batch(&transaction_semaphore, std::mem::take(&mut claim_batch)).await;
+				}
+
 				// Shutdown signal
 				_ = tokio::signal::ctrl_c() => {
 					break;
@@ -394,16 +843,109 @@ impl SolverEngine {
 		}
 
 		// Cleanup
-		cleanup_handle.abort(); // Stop the cleanup task
+		if let Some(handle) = cleanup_handle {
+			handle.abort(); // Stop the cleanup task
+		}
+		health_handle.abort(); // Stop the oracle health checker
+		if let Some(handle) = route_discovery_handle {
+			handle.abort(); // Stop the oracle route discovery
+		}
+		if let Some(handle) = discovery_health_handle {
+			handle.abort(); // Stop the discovery health monitor
+		}
+		if let Some(handle) = arbitrage_handle {
+			handle.abort(); // Stop the arbitrage detector
+		}
+		if let Some(handle) = checkpoint_handle {
+			handle.abort(); // Stop checkpointing
+		}
+		if let Some(handle) = gas_history_handle {
+			handle.abort(); // Stop sampling gas prices
+		}
+		if let Some(handle) = rebalancing_handle {
+			handle.abort(); // Stop rebalancing
+		}
+		for handle in webhook_handles {
+			handle.abort(); // Stop publishing and retrying webhooks
+		}
+		if let Some(handle) = event_log_handle {
+			handle.abort(); // Stop persisting events
+		}
+		if let Some(handle) = forward_handle {
+			handle.abort(); // Stop forwarding intents to other solvers
+		}
+		for handle in gossip_handles {
+			handle.abort(); // Stop gossiping with peer replicas
+		}
 
-		self.discovery
-			.stop_all()
-			.await
-			.map_err(|e| EngineError::Service(e.to_string()))?;
+		if is_leader {
+			self.discovery
+				.stop_all()
+				.await
+				.map_err(|e| EngineError::Service(e.to_string()))?;
+		}
 
 		Ok(())
 	}
 
+	/// Returns the senders of all solvers sharing this engine's discovery.
+	fn local_senders(
+		&self,
+		intent_tx: &mpsc::UnboundedSender<Intent>,
+	) -> Vec<mpsc::UnboundedSender<Intent>> {
+		let mut senders = match &self.discovery_role {
+			DiscoveryRole::Leader(followers) => followers.clone(),
+			DiscoveryRole::Follower => Vec::new(),
+		};
+		senders.push(intent_tx.clone());
+		senders
+	}
+
+	/// Returns the sender passed to discovery sources.
+	///
+	/// When other solvers share this engine's discovery, or intents are
+	/// gossiped to other replicas, intents are sent through a forwarding task
+	/// that delivers them to every solver and peer.
+	fn discovery_sender(
+		&self,
+		intent_tx: mpsc::UnboundedSender<Intent>,
+		gossip: Option<Arc<GossipService>>,
+	) -> (mpsc::UnboundedSender<Intent>, Option<JoinHandle<()>>) {
+		let followers = match &self.discovery_role {
+			DiscoveryRole::Leader(followers) => followers.clone(),
+			DiscoveryRole::Follower => Vec::new(),
+		};
+		if followers.is_empty() && gossip.is_none() {
+			return (intent_tx, None);
+		}
+
+		let (discovery_tx, mut discovery_rx) = mpsc::unbounded_channel::<Intent>();
+		let handle = tokio::spawn(async move {
+			while let Some(intent) = discovery_rx.recv().await {
+				// Intents assigned to a peer with a better reputation are left to it
+				if let Some(gossip) = &gossip {
+					if !gossip.broadcast(&intent) {
+						continue;
+					}
+				}
+				for follower in &followers {
+					if follower.send(intent.clone()).is_err() {
+						tracing::debug!(intent_id = %intent.id, "Solver stopped, dropping intent");
+					}
+				}
+				if intent_tx.send(intent).is_err() {
+					break;
+				}
+			}
+		});
+		(discovery_tx, Some(handle))
+	}
+
+	/// Returns the identifier of this solver.
+	pub fn solver_id(&self) -> &str {
+		&self.config.solver.id
+	}
+
 	/// Returns a reference to the event bus.
 	///
 	/// The event bus is used for inter-service communication and allows
@@ -428,6 +970,11 @@ impl SolverEngine {
 		&self.storage
 	}
 
+	/// Returns a reference to the primary account service.
+	pub fn account(&self) -> &Arc<AccountService> {
+		&self.account
+	}
+
 	/// Returns a reference to the token manager.
 	///
 	/// Provides access to token approval management and validation
@@ -436,6 +983,21 @@ impl SolverEngine {
 		&self.token_manager
 	}
 
+	/// Returns a reference to the token metadata discovery.
+	pub fn token_discovery(&self) -> &Arc<TokenDiscovery> {
+		&self.token_discovery
+	}
+
+	/// Returns a reference to the inventory manager.
+	pub fn inventory(&self) -> &Arc<InventoryManager> {
+		&self.inventory
+	}
+
+	/// Returns a reference to the delivery service.
+	pub fn delivery(&self) -> &Arc<DeliveryService> {
+		&self.delivery
+	}
+
 	/// Returns a reference to the settlement service.
 	pub fn settlement(&self) -> &Arc<SettlementService> {
 		&self.settlement
@@ -446,6 +1008,275 @@ impl SolverEngine {
 		&self.discovery
 	}
 
+	/// Returns a reference to the price service, if configured.
+	pub fn price(&self) -> Option<&Arc<PriceService>> {
+		self.price.as_ref()
+	}
+
+	/// Replaces the order templates used to filter incoming orders.
+	///
+	/// Intended to be called when the configuration is reloaded so that
+	/// template changes apply without restarting the solver.
+	pub fn reload_templates(&self, templates: Vec<OrderTemplate>) {
+		tracing::info!(count = templates.len(), "Reloading order templates");
+		self.order.update_templates(templates);
+	}
+
+	/// Simulates the fill transaction for a stored order without submitting it.
+	///
+	/// Uses the order's execution parameters when present, falling back to
+	/// zero gas price otherwise.
+	pub async fn simulate_fill(&self, order_id: &str) -> Result<SimulationResult, EngineError> {
+		let order = self
+			.state_machine
+			.get_order(order_id)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))?;
+
+		let params = order.execution_params.clone().unwrap_or(ExecutionParams {
+			gas_price: U256::ZERO,
+			priority_fee: None,
+			gas_limit_override: None,
+			output_source: Default::default(),
+		});
+
+		self.order
+			.simulate_fill_transaction(&order, &params, &DeliverySimulator(&self.delivery))
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Returns the report of the most recent state recovery, if any.
+	pub async fn last_recovery_report(&self) -> Result<Option<RecoveryReport>, EngineError> {
+		recovery::last_recovery_report(&self.storage)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Cancels an order on behalf of its user and gossips the cancellation
+	/// to peer replicas.
+	///
+	/// The cancellation must be signed by the order's sponsor, and the order
+	/// must not be executing yet.
+	pub async fn cancel_order(
+		&self,
+		cancellation: OrderCancellation,
+	) -> Result<(), CancellationError> {
+		self.apply_cancellation(&cancellation).await?;
+
+		let gossip = self
+			.gossip
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.clone();
+		if let Some(gossip) = gossip {
+			gossip.broadcast_cancellation(&cancellation);
+		}
+		Ok(())
+	}
+
+	/// Verifies a cancellation and cancels the order, releasing its inventory
+	/// reservations and order nonce.
+	async fn apply_cancellation(
+		&self,
+		cancellation: &OrderCancellation,
+	) -> Result<(), CancellationError> {
+		let order: Order = self
+			.storage
+			.retrieve(StorageKey::Orders.as_str(), &cancellation.order_id)
+			.await
+			.map_err(|e| match e {
+				StorageError::NotFound => {
+					CancellationError::NotFound(cancellation.order_id.clone())
+				},
+				e => CancellationError::Storage(e.to_string()),
+			})?;
+		cancellation.verify(&order, current_timestamp())?;
+
+		let order = self
+			.state_machine
+			.transition_order_status(&order.id, OrderStatus::Cancelled)
+			.await
+			.map_err(|e| CancellationError::Storage(e.to_string()))?;
+		self.inventory.release(&order.id).await;
+		if let Err(e) = self.nonces.unlock(&order).await {
+			tracing::warn!(error = %e, "Failed to unlock order nonce");
+		}
+
+		tracing::info!(order_id = %truncate_id(&order.id), "Order cancelled by user");
+		self.event_bus
+			.publish(SolverEvent::Order(OrderEvent::Cancelled {
+				order_id: order.id,
+				reason: "user requested".to_string(),
+			}))
+			.ok();
+		Ok(())
+	}
+
+	/// Returns whether an order was cancelled, so its pending events are
+	/// ignored.
+	async fn is_cancelled(&self, order_id: &str) -> bool {
+		match self.state_machine.get_order(order_id).await {
+			Ok(order) => order.status == OrderStatus::Cancelled,
+			Err(_) => false,
+		}
+	}
+
+	/// Returns the audit entry of an order's fill, if it was recorded.
+	pub async fn audit_entry(&self, order_id: &str) -> Result<Option<AuditEntry>, EngineError> {
+		audit::order_audit_entry(&self.storage, order_id)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Returns a page of the fill audit log.
+	pub async fn audit_log(&self, query: &AuditQuery) -> Result<AuditPage, EngineError> {
+		audit::audit_entries(&self.storage, query)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Returns the logged solver events from a sequence number on.
+	pub async fn event_log(
+		&self,
+		query: &EventLogQuery,
+	) -> Result<Vec<EventLogEntry>, EngineError> {
+		event_persister::event_log_entries(&self.storage, query)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Returns the webhook deliveries waiting to be retried, the earliest due first.
+	pub async fn webhook_retries(&self) -> Result<Vec<WebhookRetryEntry>, EngineError> {
+		webhooks::pending_retries(&self.storage)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Returns the cached gas estimates of token approvals.
+	pub async fn token_gas_costs(&self) -> Result<Vec<TokenGasEstimate>, EngineError> {
+		self.token_manager
+			.approval_gas_estimates()
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Lists this solver and its gossip peers with their reputation scores.
+	pub fn reputation(&self) -> Vec<PeerReputation> {
+		self.reputation
+			.snapshot(self.solver_id(), current_timestamp())
+	}
+
+	/// Records the outcome of a fill in this solver's reputation, timing it
+	/// from the creation of the order.
+	async fn record_fill_reputation(&self, order_id: &str, success: bool) {
+		match self.state_machine.get_order(order_id).await {
+			Ok(order) => {
+				let fill_time_ms = current_timestamp()
+					.saturating_sub(order.created_at)
+					.saturating_mul(1000);
+				self.reputation.record_fill(success, fill_time_ms);
+			},
+			Err(e) => tracing::debug!(error = %e, "Failed to record fill reputation"),
+		}
+	}
+
+	/// Re-enables auto-claiming for a settlement implementation whose claim
+	/// circuit was opened, queueing the claims held back in the meantime.
+	///
+	/// Returns the number of queued claims.
+	pub fn reset_claim_circuit(&self, implementation: &str) -> Result<usize, EngineError> {
+		self.settlement_handler
+			.reset_claim_circuit(implementation)
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Returns the orders in the dead letter queue, the most recent first.
+	pub async fn dead_letters(&self) -> Result<Vec<DeadLetter>, EngineError> {
+		dead_letter::dead_letters(&self.storage)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Queues the claim of an order in the dead letter queue again.
+	pub async fn retry_dead_letter(&self, order_id: &str) -> Result<(), EngineError> {
+		self.settlement_handler
+			.retry_dead_letter(order_id)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))
+	}
+
+	/// Replays a stored intent as if it had been freshly discovered.
+	///
+	/// Removes the stored order and intent so the intent is processed again
+	/// from scratch. Only available in `solver.debug_mode`. Intents replayed
+	/// before the engine runs are processed once it starts.
+	pub async fn replay_intent(&self, intent_id: &str) -> Result<Intent, EngineError> {
+		if !self.config.solver.debug_mode {
+			return Err(EngineError::Config(
+				"Intent replay requires solver.debug_mode".to_string(),
+			));
+		}
+
+		let intent: Intent = self
+			.storage
+			.retrieve(StorageKey::Intents.as_str(), intent_id)
+			.await
+			.map_err(|e| EngineError::Service(format!("Failed to retrieve intent: {}", e)))?;
+
+		// The intent handler skips intents that are already stored
+		for key in [StorageKey::Orders, StorageKey::Intents] {
+			if let Err(e) = self.storage.remove(key.as_str(), intent_id).await {
+				tracing::debug!(namespace = key.as_str(), error = %e, "Nothing to remove for replay");
+			}
+		}
+
+		tracing::warn!(intent_id = %intent_id, "Replaying intent");
+		self.intent_tx
+			.send(intent.clone())
+			.map_err(|e| EngineError::Service(format!("Failed to replay intent: {}", e)))?;
+
+		Ok(intent)
+	}
+
+	/// Spawns processing of a claim batch.
+	async fn spawn_claim_batch(&self, semaphore: &Arc<Semaphore>, mut batch: Vec<String>) {
+		// Claim sends a transaction - use transaction semaphore
+		self.spawn_handler(semaphore, move |engine| async move {
+			if let Err(e) = engine
+				.settlement_handler
+				.process_claim_batch(&mut batch)
+				.await
+			{
+				return Err(EngineError::Service(format!(
+					"Failed to process claim batch: {}",
+					e
+				)));
+			}
+			Ok(())
+		})
+		.await;
+	}
+
+	/// Returns the fill semaphore of the output chain with the tightest fill
+	/// concurrency, ignoring chains without a configured concurrency.
+	///
+	/// Returns `None` if no output chain has a configured concurrency.
+	fn fill_semaphore(&self, output_chain_ids: &[u64]) -> Option<Arc<Semaphore>> {
+		let concurrency = &self.config.solver.concurrency;
+		let (_, chain_id) = output_chain_ids
+			.iter()
+			.filter_map(|chain_id| {
+				concurrency
+					.get(chain_id)
+					.map(|config| (config.max_concurrent_fills, *chain_id))
+			})
+			.min()?;
+		self.fill_semaphores
+			.get(&chain_id)
+			.map(|semaphore| semaphore.clone())
+	}
+
 	/// Helper method to spawn handler tasks with semaphore-based concurrency control.
 	///
 	/// This method:
@@ -473,3 +1304,16 @@ impl SolverEngine {
 		}
 	}
 }
+
+/// Logs which discovery sources are active after starting them.
+fn log_discovery_start(report: &DiscoveryStartReport) {
+	if report.failed_sources.is_empty() {
+		tracing::info!(started = report.started_count, "Discovery sources started");
+	} else {
+		tracing::warn!(
+			started = report.started_count,
+			failed = ?report.failed_sources,
+			"Some discovery sources failed to start"
+		);
+	}
+}
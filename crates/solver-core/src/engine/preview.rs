@@ -0,0 +1,396 @@
+//! Order preview for the solver engine.
+//!
+//! Runs an intent through validation, the execution strategy and a fill
+//! simulation, then prices the result to estimate profitability. Nothing is
+//! stored or submitted on-chain.
+
+use super::{context::ContextBuilder, token_manager::TokenDiscovery, EngineError, SolverEngine};
+use crate::gas_budget::budget_exhausted;
+use crate::handlers::order::DeliverySimulator;
+use alloy_primitives::{Address as AlloyAddress, U256};
+use rust_decimal::prelude::ToPrimitive;
+use solver_price::PriceService;
+use solver_types::{
+	Address, Eip7683OrderData, ExecutionDecision, ExecutionParams, Intent, NetworksConfig, Order,
+	PreviewResult, TokenAmount,
+};
+
+impl SolverEngine {
+	/// Previews whether an intent would be profitable to fill.
+	///
+	/// Validates the intent, asks the configured strategy for a decision,
+	/// simulates the fill transaction and prices inputs, outputs and gas with
+	/// the price feed. Requires a price feed to be configured.
+	pub async fn preview_order(&self, intent: &Intent) -> Result<PreviewResult, EngineError> {
+		let price = self.price.as_ref().ok_or_else(|| {
+			EngineError::Config("Order preview requires a price feed".to_string())
+		})?;
+
+		let solver_address = self
+			.account
+			.get_address()
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))?;
+
+		let order = self
+			.order
+			.validate_intent(intent, &solver_address)
+			.await
+			.map_err(|e| EngineError::Service(format!("Intent validation failed: {}", e)))?;
+
+		let context = ContextBuilder::new(
+			self.delivery.clone(),
+			solver_address,
+			self.token_manager.clone(),
+			self.gas_budget.clone(),
+			self.config.clone(),
+		)
+		.build_execution_context(intent)
+		.await
+		.map_err(|e| EngineError::Service(e.to_string()))?;
+
+		let simulator = DeliverySimulator(&self.delivery);
+		let order_chains: Vec<u64> = order
+			.input_chain_ids
+			.iter()
+			.chain(&order.output_chain_ids)
+			.copied()
+			.collect();
+		let decision = if budget_exhausted(&context.gas_budget_remaining, &order_chains) {
+			ExecutionDecision::Skip("gas budget exceeded".to_string())
+		} else {
+			self.order
+				.should_execute(&order, &context, &simulator)
+				.await
+		};
+		let (params, strategy_decision) = match decision {
+			ExecutionDecision::Execute(params) => (Some(params), "execute".to_string()),
+			ExecutionDecision::Skip(reason) => (None, format!("skip: {}", reason)),
+			ExecutionDecision::Defer(duration) => (None, format!("defer: {}s", duration.as_secs())),
+		};
+		let params = params.unwrap_or(ExecutionParams {
+			gas_price: U256::ZERO,
+			priority_fee: None,
+			gas_limit_override: None,
+			output_source: Default::default(),
+		});
+
+		let simulation = self
+			.order
+			.simulate_fill_transaction(&order, &params, &simulator)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))?;
+		let gas_units = match (simulation.estimated_gas, simulation.revert_reason) {
+			(Some(gas), _) => gas,
+			(None, reason) => {
+				return Err(EngineError::Service(format!(
+					"Fill simulation reverted: {}",
+					reason.unwrap_or_else(|| "unknown reason".to_string())
+				)))
+			},
+		};
+
+		let gas_price = self
+			.delivery
+			.get_gas_price(simulation.chain_id)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))?;
+		let gas_price_wei = gas_price.parse::<f64>().map_err(|e| {
+			EngineError::Service(format!("Invalid gas price '{}': {}", gas_price, e))
+		})?;
+		let native_token = self
+			.config
+			.price
+			.as_ref()
+			.and_then(|price| price.native_tokens.get(&simulation.chain_id))
+			.ok_or_else(|| {
+				EngineError::Config(format!(
+					"No native token configured for chain {}",
+					simulation.chain_id
+				))
+			})?;
+		let native_price = price
+			.get_token_price(simulation.chain_id, native_token)
+			.await
+			.map_err(|e| EngineError::Service(e.to_string()))?;
+		let estimated_gas_cost_usd =
+			gas_units as f64 * gas_price_wei / 1e18 * native_price.price_usd;
+
+		let (input_usd, output_usd) = self.order_value_usd(price, &order).await?;
+		let estimated_profit_usd = input_usd - output_usd - estimated_gas_cost_usd;
+
+		Ok(PreviewResult {
+			profitable: estimated_profit_usd > 0.0,
+			estimated_profit_usd,
+			estimated_gas_cost_usd,
+			gas_units,
+			strategy_decision,
+		})
+	}
+
+	/// Returns the USD value of an order's inputs and outputs.
+	pub(super) async fn order_value_usd(
+		&self,
+		price: &PriceService,
+		order: &Order,
+	) -> Result<(f64, f64), EngineError> {
+		order_value_usd(price, &self.token_discovery, &self.config.networks, order).await
+	}
+}
+
+/// Returns the USD value of an order's inputs and outputs.
+pub(crate) async fn order_value_usd(
+	price: &PriceService,
+	token_discovery: &TokenDiscovery,
+	networks: &NetworksConfig,
+	order: &Order,
+) -> Result<(f64, f64), EngineError> {
+	let order_data = match order.standard.as_str() {
+		"eip7683" => serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+			.map_err(|e| EngineError::Service(format!("Invalid order data: {}", e)))?,
+		other => {
+			return Err(EngineError::Service(format!(
+				"Order preview not supported for standard '{}'",
+				other
+			)))
+		},
+	};
+	let chain_id = |chain_id: U256| {
+		u64::try_from(chain_id)
+			.map_err(|_| EngineError::Service(format!("Invalid chain ID {}", chain_id)))
+	};
+
+	let origin_chain = chain_id(order_data.origin_chain_id)?;
+	let mut input_usd = 0.0;
+	for [token, amount] in &order_data.inputs {
+		let token = AlloyAddress::from_slice(&token.to_be_bytes::<32>()[12..]);
+		input_usd += token_value_usd(
+			price,
+			token_discovery,
+			networks,
+			origin_chain,
+			token,
+			*amount,
+		)
+		.await?;
+	}
+
+	let mut output_usd = 0.0;
+	for output in &order_data.outputs {
+		let token = AlloyAddress::from_slice(&output.token[12..]);
+		output_usd += token_value_usd(
+			price,
+			token_discovery,
+			networks,
+			chain_id(output.chain_id)?,
+			token,
+			output.amount,
+		)
+		.await?;
+	}
+
+	Ok((input_usd, output_usd))
+}
+
+/// Returns the USD value of a raw token amount, with the decimals of the
+/// token taken from the configuration or discovered on-chain.
+///
+/// Fails for tokens whose decimals are unknown rather than assuming a
+/// number of decimals.
+pub(crate) async fn token_value_usd(
+	price: &PriceService,
+	token_discovery: &TokenDiscovery,
+	networks: &NetworksConfig,
+	chain_id: u64,
+	token: AlloyAddress,
+	amount: U256,
+) -> Result<f64, EngineError> {
+	let token_price = price
+		.get_token_price(chain_id, &token.to_string())
+		.await
+		.map_err(|e| EngineError::Service(e.to_string()))?;
+	let decimals = token_discovery
+		.decimals(networks, chain_id, &Address(token.to_vec()))
+		.await
+		.ok_or_else(|| {
+			EngineError::Service(format!(
+				"Unknown decimals of token {} on chain {}",
+				token, chain_id
+			))
+		})?;
+
+	let value = TokenAmount::new(amount, decimals)
+		.to_usd(&token_price.price_usd.to_string())
+		.map_err(|e| EngineError::Service(e.to_string()))?;
+	Ok(value.to_f64().unwrap_or(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_trait::async_trait;
+	use solver_delivery::DeliveryService;
+	use solver_price::{PriceFeedError, PriceFeedInterface, TokenPrice};
+	use solver_storage::StorageService;
+	use solver_types::{ChainId, ConfigSchema, NetworkConfig, OrderStatus, TokenConfig};
+	use std::{collections::HashMap, sync::Arc};
+
+	/// Price feed quoting every token at two dollars.
+	struct FixedPriceFeed;
+
+	#[async_trait]
+	impl PriceFeedInterface for FixedPriceFeed {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn get_token_price(
+			&self,
+			chain_id: u64,
+			token: &str,
+		) -> Result<TokenPrice, PriceFeedError> {
+			Ok(TokenPrice {
+				chain_id,
+				token: token.to_string(),
+				price_usd: 2.0,
+				timestamp: 0,
+				source: "fixed".to_string(),
+			})
+		}
+	}
+
+	fn network(token: u8, decimals: u8) -> NetworkConfig {
+		NetworkConfig {
+			rpc_urls: Vec::new(),
+			input_settler_address: Address(vec![0; 20]),
+			output_settler_address: Address(vec![0; 20]),
+			tokens: vec![TokenConfig {
+				address: Address(vec![token; 20]),
+				symbol: "TKN".to_string(),
+				decimals,
+			}],
+			input_settler_compact_address: None,
+		}
+	}
+
+	/// Token 0xBB with 6 decimals on Ethereum and token 0xAA with 18
+	/// decimals on Optimism.
+	fn networks() -> NetworksConfig {
+		NetworksConfig::from([
+			(ChainId::MAINNET, network(0xBB, 6)),
+			(ChainId::OPTIMISM, network(0xAA, 18)),
+		])
+	}
+
+	/// Token discovery without any delivery, so unconfigured tokens stay
+	/// unknown.
+	fn token_discovery() -> TokenDiscovery {
+		TokenDiscovery::new(
+			Arc::new(DeliveryService::new(HashMap::new(), 1)),
+			Arc::new(StorageService::new(
+				Box::new(solver_storage::implementations::memory::MemoryStorage::new()),
+				None,
+			)),
+		)
+	}
+
+	/// Returns an order moving 3 units of token 0xBB on the origin chain to
+	/// 1 unit of `output_token` on Optimism.
+	fn test_order(origin_chain_id: &str, output_token: u8) -> Order {
+		let mut token = [0u8; 32];
+		token[12..].copy_from_slice(&[output_token; 20]);
+		Order {
+			id: "order-1".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::json!({
+				"user": "0x0000000000000000000000000000000000000001",
+				"nonce": "0x1",
+				"origin_chain_id": origin_chain_id,
+				"expires": u32::MAX,
+				"fill_deadline": u32::MAX,
+				"input_oracle": "0x0000000000000000000000000000000000000000",
+				"inputs": [[format!("0x{}", "bb".repeat(20)), "0x2dc6c0"]],
+				"order_id": vec![0u8; 32],
+				"gas_limit_overrides": {},
+				"outputs": [{
+					"oracle": vec![0u8; 32],
+					"settler": vec![0u8; 32],
+					"chain_id": "0xa",
+					"token": token,
+					"amount": "0xde0b6b3a7640000",
+					"recipient": vec![0u8; 32],
+					"call": "0x",
+					"context": "0x",
+				}],
+			}),
+			solver_address: Address(vec![0x22; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_order_value_usd() {
+		let price = PriceService::new(Box::new(FixedPriceFeed));
+		let (input_usd, output_usd) = order_value_usd(
+			&price,
+			&token_discovery(),
+			&networks(),
+			&test_order("0x1", 0xAA),
+		)
+		.await
+		.unwrap();
+		assert_eq!(input_usd, 6.0);
+		assert_eq!(output_usd, 2.0);
+	}
+
+	#[tokio::test]
+	async fn test_order_value_usd_rejects_unknown_decimals() {
+		let price = PriceService::new(Box::new(FixedPriceFeed));
+		let err = order_value_usd(
+			&price,
+			&token_discovery(),
+			&networks(),
+			&test_order("0x1", 0xCC),
+		)
+		.await
+		.unwrap_err();
+		assert!(err.to_string().contains("Unknown decimals"));
+	}
+
+	#[tokio::test]
+	async fn test_order_value_usd_rejects_overflowing_chain_id() {
+		let price = PriceService::new(Box::new(FixedPriceFeed));
+		let err = order_value_usd(
+			&price,
+			&token_discovery(),
+			&networks(),
+			&test_order(&format!("0x{}", "ff".repeat(32)), 0xAA),
+		)
+		.await
+		.unwrap_err();
+		assert!(err.to_string().contains("Invalid chain ID"));
+	}
+
+	#[tokio::test]
+	async fn test_order_value_usd_rejects_unsupported_standard() {
+		let price = PriceService::new(Box::new(FixedPriceFeed));
+		let mut order = test_order("0x1", 0xAA);
+		order.standard = "other".to_string();
+		assert!(
+			order_value_usd(&price, &token_discovery(), &networks(), &order)
+				.await
+				.is_err()
+		);
+	}
+}
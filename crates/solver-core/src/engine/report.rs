@@ -0,0 +1,476 @@
+//! Daily solver reports.
+//!
+//! Skipped orders and gas spend are recorded as analytics events while the
+//! solver runs. A report for a day combines these events with the orders
+//! created on that day, priced with the recorded price history, so reports
+//! are computed from storage alone without querying any chain.
+
+use super::{EngineError, SolverEngine};
+use alloy_primitives::{hex, Address as AlloyAddress, U256};
+use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use solver_order::prediction::token_pair;
+use solver_price::PriceService;
+use solver_storage::{QueryFilter, StorageError, StorageIndexes, StorageService};
+use solver_types::{
+	current_timestamp, Address, ChainActivity, DailyReport, Eip7683OrderData, Order,
+	OrderAnalyticsQuery, OrderStatus, PairProfit, StorageKey, TokenAmount, TransactionHash,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+
+/// How long the report of the current day is served from the cache.
+const REPORT_CACHE_TTL_SECONDS: u64 = 300;
+
+/// Length of a report day in seconds.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Number of chains and token pairs listed in a report.
+const TOP_ENTRIES: usize = 5;
+
+/// Event recorded in the analytics table for daily reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalyticsEvent {
+	/// The solver decided not to fill an order.
+	Skipped {
+		order_id: String,
+		reason: String,
+		timestamp: u64,
+	},
+	/// The solver paid gas for a transaction of an order.
+	GasSpent {
+		order_id: String,
+		chain_id: u64,
+		amount_wei: U256,
+		timestamp: u64,
+	},
+}
+
+impl AnalyticsEvent {
+	/// Returns the time at which the event happened.
+	fn timestamp(&self) -> u64 {
+		match self {
+			AnalyticsEvent::Skipped { timestamp, .. }
+			| AnalyticsEvent::GasSpent { timestamp, .. } => *timestamp,
+		}
+	}
+}
+
+/// Records that an order was skipped, replacing any earlier skip of the order.
+pub async fn record_skip(
+	storage: &StorageService,
+	order_id: &str,
+	reason: String,
+) -> Result<(), StorageError> {
+	let event = AnalyticsEvent::Skipped {
+		order_id: order_id.to_string(),
+		reason,
+		timestamp: current_timestamp(),
+	};
+	store_event(storage, &format!("skipped_{}", order_id), &event).await
+}
+
+/// Records the gas paid for a confirmed transaction of an order.
+pub async fn record_gas_spend(
+	storage: &StorageService,
+	order_id: &str,
+	tx_hash: &TransactionHash,
+	chain_id: u64,
+	amount_wei: U256,
+) -> Result<(), StorageError> {
+	let event = AnalyticsEvent::GasSpent {
+		order_id: order_id.to_string(),
+		chain_id,
+		amount_wei,
+		timestamp: current_timestamp(),
+	};
+	store_event(storage, &format!("gas_{}", hex::encode(&tx_hash.0)), &event).await
+}
+
+/// Stores an analytics event indexed by the time it happened.
+async fn store_event(
+	storage: &StorageService,
+	id: &str,
+	event: &AnalyticsEvent,
+) -> Result<(), StorageError> {
+	storage
+		.store(
+			StorageKey::Analytics.as_str(),
+			id,
+			event,
+			Some(StorageIndexes::new().with_numeric("timestamp", event.timestamp())),
+		)
+		.await
+}
+
+impl SolverEngine {
+	/// Returns the report of a UTC day.
+	///
+	/// The report of the current day is cached briefly, since it changes as
+	/// the solver runs. Values are only computed when a price feed with price
+	/// history is configured.
+	pub async fn daily_report(&self, day: NaiveDate) -> Result<DailyReport, EngineError> {
+		let date = day.format("%Y-%m-%d").to_string();
+		let start =
+			u64::try_from(day.and_time(chrono::NaiveTime::MIN).and_utc().timestamp()).unwrap_or(0);
+		let end = start + SECONDS_PER_DAY;
+		let cache_id = format!("report_{}", date);
+		let is_today = (start..end).contains(&current_timestamp());
+		if is_today {
+			if let Ok(report) = self
+				.storage
+				.retrieve::<DailyReport>(StorageKey::AnalyticsCache.as_str(), &cache_id)
+				.await
+			{
+				return Ok(report);
+			}
+		}
+
+		let orders = self
+			.query_orders(&OrderAnalyticsQuery {
+				from_ts: Some(start),
+				to_ts: Some(end - 1),
+				..Default::default()
+			})
+			.await?;
+		let events: Vec<AnalyticsEvent> = self
+			.storage
+			.query(
+				StorageKey::Analytics.as_str(),
+				QueryFilter::GreaterThan(
+					"timestamp".to_string(),
+					serde_json::json!(start.saturating_sub(1)),
+				),
+			)
+			.await
+			.map_err(|e| EngineError::Service(format!("Failed to query analytics: {}", e)))?
+			.into_iter()
+			.map(|(_, event)| event)
+			.filter(|event: &AnalyticsEvent| event.timestamp() < end)
+			.collect();
+
+		let mut revenue_usd = HashMap::new();
+		let mut gas_usd = HashMap::new();
+		if let Some(price) = &self.price {
+			for order in orders.iter().filter(|order| is_filled(order)) {
+				let filled_at = order
+					.fill_proof
+					.as_ref()
+					.map_or(order.updated_at, |proof| proof.filled_timestamp());
+				if let Some(revenue) = self.historical_revenue_usd(price, order, filled_at).await {
+					revenue_usd.insert(order.id.clone(), revenue);
+				}
+			}
+			for event in &events {
+				if let AnalyticsEvent::GasSpent {
+					order_id,
+					chain_id,
+					amount_wei,
+					timestamp,
+				} = event
+				{
+					if let Some(cost) = self
+						.historical_gas_usd(price, *chain_id, *amount_wei, *timestamp)
+						.await
+					{
+						*gas_usd.entry(order_id.clone()).or_insert(0.0) += cost;
+					}
+				}
+			}
+		}
+
+		let report = compile_report(date, &orders, &events, &revenue_usd, &gas_usd);
+		if is_today {
+			if let Err(e) = self
+				.storage
+				.store_with_ttl(
+					StorageKey::AnalyticsCache.as_str(),
+					&cache_id,
+					&report,
+					None,
+					Some(Duration::from_secs(REPORT_CACHE_TTL_SECONDS)),
+				)
+				.await
+			{
+				tracing::debug!(error = %e, "Failed to cache daily report");
+			}
+		}
+
+		Ok(report)
+	}
+
+	/// Returns the input value of an order minus its output value, priced at
+	/// the given time. Returns None if any token lacks a price snapshot.
+	async fn historical_revenue_usd(
+		&self,
+		price: &PriceService,
+		order: &Order,
+		ts: u64,
+	) -> Option<f64> {
+		let order_data = serde_json::from_value::<Eip7683OrderData>(order.data.clone()).ok()?;
+		let origin_chain = u64::try_from(order_data.origin_chain_id).ok()?;
+		let mut revenue = 0.0;
+		for [token, amount] in &order_data.inputs {
+			let token = AlloyAddress::from_slice(&token.to_be_bytes::<32>()[12..]);
+			revenue += self
+				.historical_value_usd(price, origin_chain, token, *amount, ts)
+				.await?;
+		}
+		for output in &order_data.outputs {
+			let token = AlloyAddress::from_slice(&output.token[12..]);
+			revenue -= self
+				.historical_value_usd(
+					price,
+					u64::try_from(output.chain_id).ok()?,
+					token,
+					output.amount,
+					ts,
+				)
+				.await?;
+		}
+		Some(revenue)
+	}
+
+	/// Returns the USD value of a raw token amount at the given time.
+	async fn historical_value_usd(
+		&self,
+		price: &PriceService,
+		chain_id: u64,
+		token: AlloyAddress,
+		amount: U256,
+		ts: u64,
+	) -> Option<f64> {
+		let token_price = price
+			.get_price_at_timestamp(&token.to_string(), chain_id, ts)
+			.await?;
+		let decimals = self
+			.token_discovery
+			.decimals(&self.config.networks, chain_id, &Address(token.to_vec()))
+			.await?;
+		TokenAmount::new(amount, decimals)
+			.to_usd(&token_price.price_usd.to_string())
+			.ok()?
+			.to_f64()
+	}
+
+	/// Returns the USD value of gas paid on a chain at the given time.
+	async fn historical_gas_usd(
+		&self,
+		price: &PriceService,
+		chain_id: u64,
+		amount_wei: U256,
+		ts: u64,
+	) -> Option<f64> {
+		let native_token = self
+			.config
+			.price
+			.as_ref()
+			.and_then(|price| price.native_tokens.get(&chain_id))?;
+		let native_price = price
+			.get_price_at_timestamp(native_token, chain_id, ts)
+			.await?;
+		TokenAmount::new(amount_wei, 18)
+			.to_usd(&native_price.price_usd.to_string())
+			.ok()?
+			.to_f64()
+	}
+}
+
+/// Returns true if the fill of an order has been confirmed.
+fn is_filled(order: &Order) -> bool {
+	matches!(
+		order.status,
+		OrderStatus::Executed | OrderStatus::Settled | OrderStatus::Finalized
+	)
+}
+
+/// Builds a report from the orders created on a day, the analytics events of
+/// the day and the priced revenue and gas spend of orders keyed by order ID.
+fn compile_report(
+	date: String,
+	orders: &[Order],
+	events: &[AnalyticsEvent],
+	revenue_usd: &HashMap<String, f64>,
+	gas_usd: &HashMap<String, f64>,
+) -> DailyReport {
+	let skipped: HashSet<&str> = events
+		.iter()
+		.filter_map(|event| match event {
+			AnalyticsEvent::Skipped { order_id, .. } => Some(order_id.as_str()),
+			AnalyticsEvent::GasSpent { .. } => None,
+		})
+		.collect();
+
+	let mut chain_orders: HashMap<u64, usize> = HashMap::new();
+	let mut pairs: HashMap<String, PairProfit> = HashMap::new();
+	for order in orders {
+		let chains: BTreeSet<u64> = order
+			.input_chain_ids
+			.iter()
+			.chain(&order.output_chain_ids)
+			.copied()
+			.collect();
+		for chain_id in chains {
+			*chain_orders.entry(chain_id).or_insert(0) += 1;
+		}
+
+		if !is_filled(order) {
+			continue;
+		}
+		let Some(pair) = token_pair(order) else {
+			continue;
+		};
+		let entry = pairs.entry(pair.clone()).or_insert(PairProfit {
+			pair,
+			fills: 0,
+			net_pnl_usd: 0.0,
+		});
+		entry.fills += 1;
+		entry.net_pnl_usd += revenue_usd.get(&order.id).copied().unwrap_or(0.0)
+			- gas_usd.get(&order.id).copied().unwrap_or(0.0);
+	}
+
+	let mut most_active_chains: Vec<ChainActivity> = chain_orders
+		.into_iter()
+		.map(|(chain_id, orders)| ChainActivity { chain_id, orders })
+		.collect();
+	most_active_chains.sort_by(|a, b| b.orders.cmp(&a.orders).then(a.chain_id.cmp(&b.chain_id)));
+	most_active_chains.truncate(TOP_ENTRIES);
+
+	let mut most_profitable_pairs: Vec<PairProfit> = pairs.into_values().collect();
+	most_profitable_pairs.sort_by(|a, b| {
+		b.net_pnl_usd
+			.total_cmp(&a.net_pnl_usd)
+			.then_with(|| a.pair.cmp(&b.pair))
+	});
+	most_profitable_pairs.truncate(TOP_ENTRIES);
+
+	let gross_revenue_usd: f64 = revenue_usd.values().sum();
+	let gas_spend_usd: f64 = gas_usd.values().sum();
+	DailyReport {
+		date,
+		intents_received: orders.len(),
+		intents_filled: orders.iter().filter(|order| is_filled(order)).count(),
+		intents_skipped: skipped.len(),
+		intents_failed: orders
+			.iter()
+			.filter(|order| matches!(order.status, OrderStatus::Failed(_)))
+			.count(),
+		gross_revenue_usd,
+		gas_spend_usd,
+		net_pnl_usd: gross_revenue_usd - gas_spend_usd,
+		most_active_chains,
+		most_profitable_pairs,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{
+		standards::eip7683::{GasLimitOverrides, MandateOutput},
+		TransactionType,
+	};
+
+	fn order(id: &str, status: OrderStatus, output_chain: u64) -> Order {
+		let data = Eip7683OrderData {
+			user: solver_types::with_0x_prefix(&"11".repeat(20)),
+			nonce: U256::from(1),
+			origin_chain_id: U256::from(1),
+			expires: 0,
+			fill_deadline: 0,
+			input_oracle: solver_types::with_0x_prefix(&"00".repeat(20)),
+			inputs: vec![[U256::from(0xAA), U256::from(100)]],
+			order_id: [0u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![MandateOutput {
+				oracle: [0u8; 32],
+				settler: [0u8; 32],
+				chain_id: U256::from(output_chain),
+				token: [0xBB; 32],
+				amount: U256::from(100),
+				recipient: [0u8; 32],
+				call: Vec::new(),
+				context: Vec::new(),
+			}],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		};
+		Order {
+			id: id.to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 1000,
+			updated_at: 1000,
+			status,
+			data: serde_json::to_value(&data).unwrap(),
+			solver_address: Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![output_chain],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	#[test]
+	fn test_compile_report() {
+		let orders = vec![
+			order("a", OrderStatus::Finalized, 10),
+			order("b", OrderStatus::Settled, 10),
+			order("c", OrderStatus::Finalized, 137),
+			order("d", OrderStatus::Failed(TransactionType::Fill), 10),
+			order("e", OrderStatus::Created, 8453),
+		];
+		let events = vec![
+			AnalyticsEvent::Skipped {
+				order_id: "e".to_string(),
+				reason: "unprofitable".to_string(),
+				timestamp: 1000,
+			},
+			AnalyticsEvent::Skipped {
+				order_id: "e".to_string(),
+				reason: "unprofitable".to_string(),
+				timestamp: 1100,
+			},
+		];
+		let revenue_usd = HashMap::from([
+			("a".to_string(), 3.0),
+			("b".to_string(), 2.0),
+			("c".to_string(), 1.0),
+		]);
+		let gas_usd = HashMap::from([("a".to_string(), 0.5), ("c".to_string(), 0.5)]);
+
+		let report = compile_report(
+			"2024-01-01".to_string(),
+			&orders,
+			&events,
+			&revenue_usd,
+			&gas_usd,
+		);
+		assert_eq!(report.intents_received, 5);
+		assert_eq!(report.intents_filled, 3);
+		assert_eq!(report.intents_skipped, 1);
+		assert_eq!(report.intents_failed, 1);
+		assert_eq!(report.gross_revenue_usd, 6.0);
+		assert_eq!(report.gas_spend_usd, 1.0);
+		assert_eq!(report.net_pnl_usd, 5.0);
+		assert_eq!(report.most_active_chains[0].chain_id, 1);
+		assert_eq!(report.most_active_chains[0].orders, 5);
+		assert_eq!(report.most_active_chains[1].chain_id, 10);
+		assert_eq!(report.most_profitable_pairs.len(), 2);
+		assert_eq!(report.most_profitable_pairs[0].fills, 2);
+		assert_eq!(report.most_profitable_pairs[0].net_pnl_usd, 4.5);
+
+		let csv = report.to_csv();
+		assert!(csv.starts_with("section,key,value\nsummary,date,2024-01-01\n"));
+		assert!(csv.contains("summary,net_pnl_usd,5\n"));
+	}
+}
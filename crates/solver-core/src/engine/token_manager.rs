@@ -18,15 +18,31 @@
 //! The manager automatically sets MAX_UINT256 approvals for all configured tokens
 //! to their respective input and output settler contracts. This eliminates the need
 //! for per-transaction approvals and reduces gas costs during order execution.
+//!
+//! Approval gas limits are set from estimates of each token's `approve` call,
+//! measured by `TokenApprovalBenchmark` and cached for a week, since approval
+//! costs vary widely between ERC-20 implementations.
+//!
+//! # Token Discovery
+//!
+//! Tokens that appear in orders but are missing from the configuration have
+//! their ERC-20 metadata read from the token contract by `TokenDiscovery` when
+//! the order is validated, and cached for a day. Expired metadata is read
+//! again on its next use.
 
 use alloy_primitives::{hex, U256};
+use alloy_sol_types::{sol, SolCall};
+use serde::{Deserialize, Serialize};
 use solver_account::AccountService;
 use solver_delivery::DeliveryService;
+use solver_storage::{StorageError, StorageService};
 use solver_types::{
-	with_0x_prefix, Address, NetworksConfig, TokenConfig, Transaction, TransactionHash,
+	current_timestamp, with_0x_prefix, Address, Eip7683OrderData, NetworksConfig, Order,
+	StorageKey, TokenConfig, Transaction, TransactionHash,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during token management operations.
@@ -70,6 +86,8 @@ pub struct TokenManager {
 	delivery: Arc<DeliveryService>,
 	/// Service for managing the solver's account and signatures.
 	account: Arc<AccountService>,
+	/// Benchmark providing the gas limits of approval transactions, if set.
+	approval_benchmark: Option<Arc<TokenApprovalBenchmark>>,
 }
 
 impl TokenManager {
@@ -89,6 +107,22 @@ impl TokenManager {
 			networks,
 			delivery,
 			account,
+			approval_benchmark: None,
+		}
+	}
+
+	/// Sets the gas limits of approval transactions from the benchmark's
+	/// cached estimates instead of a fixed limit.
+	pub fn with_approval_benchmark(mut self, benchmark: Arc<TokenApprovalBenchmark>) -> Self {
+		self.approval_benchmark = Some(benchmark);
+		self
+	}
+
+	/// Returns the cached approval gas estimates, or none without a benchmark.
+	pub async fn approval_gas_estimates(&self) -> Result<Vec<TokenGasEstimate>, StorageError> {
+		match &self.approval_benchmark {
+			Some(benchmark) => benchmark.estimates().await,
+			None => Ok(Vec::new()),
 		}
 	}
 
@@ -113,13 +147,17 @@ impl TokenManager {
 		let max_uint256 = U256::MAX;
 		let max_uint256_str = max_uint256.to_string();
 
+		if let Some(benchmark) = &self.approval_benchmark {
+			benchmark.benchmark(&self.networks).await;
+		}
+
 		for (chain_id, network) in &self.networks {
 			for token in &network.tokens {
 				// Check allowance for input settler
 				let current_allowance_input = self
 					.delivery
 					.get_allowance(
-						*chain_id,
+						chain_id.as_u64(),
 						&solver_address_str,
 						&hex::encode(&network.input_settler_address.0),
 						&hex::encode(&token.address.0),
@@ -133,7 +171,7 @@ impl TokenManager {
 						chain_id
 					);
 					self.submit_approval(
-						*chain_id,
+						chain_id.as_u64(),
 						&token.address,
 						&network.input_settler_address,
 						max_uint256,
@@ -145,7 +183,7 @@ impl TokenManager {
 				let current_allowance_output = self
 					.delivery
 					.get_allowance(
-						*chain_id,
+						chain_id.as_u64(),
 						&solver_address_str,
 						&hex::encode(&network.output_settler_address.0),
 						&hex::encode(&token.address.0),
@@ -159,7 +197,7 @@ impl TokenManager {
 						chain_id
 					);
 					self.submit_approval(
-						*chain_id,
+						chain_id.as_u64(),
 						&token.address,
 						&network.output_settler_address,
 						max_uint256,
@@ -194,33 +232,12 @@ impl TokenManager {
 		spender: &Address,
 		amount: U256,
 	) -> Result<TransactionHash, TokenManagerError> {
-		// Create approval transaction data
-		// ERC20 approve(address spender, uint256 amount)
-		// Function selector: 0x095ea7b3
-		let selector = [0x09, 0x5e, 0xa7, 0xb3];
-		let mut call_data = Vec::new();
-		call_data.extend_from_slice(&selector);
-
-		// Add spender address (32 bytes, left-padded with zeros)
-		call_data.extend_from_slice(&[0; 12]); // Pad to 32 bytes
-		call_data.extend_from_slice(&spender.0);
-
-		// Add amount (32 bytes)
-		let amount_bytes = amount.to_be_bytes::<32>();
-		call_data.extend_from_slice(&amount_bytes);
-
-		let tx = Transaction {
-			chain_id,
-			to: Some(token_address.clone()),
-			data: call_data,
-			value: U256::ZERO,
-			gas_limit: Some(100000),
-			gas_price: None,
-			max_fee_per_gas: None,
-			max_priority_fee_per_gas: None,
-			nonce: None,
-		};
-
+		let mut tx = approval_transaction(chain_id, token_address, spender, amount);
+		if let Some(benchmark) = &self.approval_benchmark {
+			if let Some(gas_limit) = benchmark.gas_limit(chain_id, token_address, spender).await {
+				tx.gas_limit = Some(gas_limit);
+			}
+		}
 		let tx_hash = self.delivery.deliver(tx).await?;
 
 		Ok(tx_hash)
@@ -248,13 +265,13 @@ impl TokenManager {
 				let balance = self
 					.delivery
 					.get_balance(
-						*chain_id,
+						chain_id.as_u64(),
 						&solver_address_str,
 						Some(&hex::encode(&token.address.0)),
 					)
 					.await?;
 
-				balances.insert((*chain_id, token.clone()), balance);
+				balances.insert((chain_id.as_u64(), token.clone()), balance);
 			}
 		}
 
@@ -369,3 +386,757 @@ impl TokenManager {
 		&self.networks
 	}
 }
+
+sol! {
+	/// Optional metadata functions of ERC-20 tokens.
+	interface IERC20Metadata {
+		function name() external view returns (string);
+		function symbol() external view returns (string);
+		function decimals() external view returns (uint8);
+	}
+
+	/// EIP-712 domain version of tokens supporting signed transfers.
+	interface IEIP712Version {
+		function version() external view returns (string);
+	}
+}
+
+/// How long approval gas estimates are cached.
+const TOKEN_GAS_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Margin added to approval gas estimates, in percent.
+const APPROVAL_GAS_MARGIN_PERCENT: u64 = 20;
+
+/// Estimated gas of approving a spender for the maximum amount of a token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenGasEstimate {
+	pub chain_id: u64,
+	/// Token address (hex with 0x prefix).
+	pub token: String,
+	/// Spender address (hex with 0x prefix).
+	pub spender: String,
+	/// Gas used by the `approve` call.
+	pub gas_used: u64,
+	/// Gas limit set on approval transactions, the gas used plus a margin.
+	pub gas_limit: u64,
+	/// Timestamp of the estimate.
+	pub estimated_at: u64,
+}
+
+/// Measures the gas of token approvals to set accurate approval gas limits.
+///
+/// Each configured token's `approve(settler, max_uint256)` call is estimated
+/// once for its input and output settlers and cached in storage for a week.
+pub struct TokenApprovalBenchmark {
+	/// Service used to estimate the approval calls.
+	delivery: Arc<DeliveryService>,
+	/// Storage caching the estimates.
+	storage: Arc<StorageService>,
+}
+
+impl TokenApprovalBenchmark {
+	/// Creates a new `TokenApprovalBenchmark` instance.
+	pub fn new(delivery: Arc<DeliveryService>, storage: Arc<StorageService>) -> Self {
+		Self { delivery, storage }
+	}
+
+	/// Estimates the approvals of all configured tokens without a cached estimate.
+	///
+	/// Approvals that cannot be estimated are logged and skipped, leaving
+	/// their transactions with the default gas limit.
+	pub async fn benchmark(&self, networks: &NetworksConfig) {
+		for (chain_id, network) in networks {
+			let chain_id = chain_id.as_u64();
+			for token in &network.tokens {
+				for spender in [
+					&network.input_settler_address,
+					&network.output_settler_address,
+				] {
+					if self
+						.cached(chain_id, &token.address, spender)
+						.await
+						.is_some()
+					{
+						continue;
+					}
+					if let Err(e) = self.estimate(chain_id, &token.address, spender).await {
+						tracing::warn!(
+							chain_id,
+							token = %token.symbol,
+							error = %e,
+							"Failed to estimate approval gas"
+						);
+					}
+				}
+			}
+		}
+	}
+
+	/// Returns the gas limit of an approval from its cached estimate, if any.
+	pub async fn gas_limit(
+		&self,
+		chain_id: u64,
+		token: &Address,
+		spender: &Address,
+	) -> Option<u64> {
+		self.cached(chain_id, token, spender)
+			.await
+			.map(|estimate| estimate.gas_limit)
+	}
+
+	/// Returns all cached estimates ordered by chain, token and spender.
+	pub async fn estimates(&self) -> Result<Vec<TokenGasEstimate>, StorageError> {
+		let mut estimates: Vec<TokenGasEstimate> = self
+			.storage
+			.retrieve_all(StorageKey::TokenGasCache.as_str())
+			.await?
+			.into_iter()
+			.map(|(_, estimate)| estimate)
+			.collect();
+		estimates.sort_by(|a, b| {
+			(a.chain_id, &a.token, &a.spender).cmp(&(b.chain_id, &b.token, &b.spender))
+		});
+		Ok(estimates)
+	}
+
+	/// Estimates an approval and caches the result.
+	async fn estimate(
+		&self,
+		chain_id: u64,
+		token: &Address,
+		spender: &Address,
+	) -> Result<TokenGasEstimate, TokenManagerError> {
+		let mut tx = approval_transaction(chain_id, token, spender, U256::MAX);
+		tx.gas_limit = None;
+		let trace = self.delivery.estimate_gas(&tx).await?;
+		let gas_used = match (trace.success, trace.gas_used) {
+			(true, Some(gas_used)) => gas_used,
+			_ => {
+				return Err(TokenManagerError::ParseError(format!(
+					"approve reverted: {}",
+					trace.revert_reason.unwrap_or_default()
+				)))
+			},
+		};
+
+		let estimate = TokenGasEstimate {
+			chain_id,
+			token: with_0x_prefix(&hex::encode(&token.0)),
+			spender: with_0x_prefix(&hex::encode(&spender.0)),
+			gas_used,
+			gas_limit: gas_used + gas_used * APPROVAL_GAS_MARGIN_PERCENT / 100,
+			estimated_at: current_timestamp(),
+		};
+		tracing::info!(
+			chain_id,
+			token = %estimate.token,
+			spender = %estimate.spender,
+			gas_used,
+			"Estimated approval gas"
+		);
+		if let Err(e) = self
+			.storage
+			.store_with_ttl(
+				StorageKey::TokenGasCache.as_str(),
+				&gas_estimate_key(chain_id, token, spender),
+				&estimate,
+				None,
+				Some(TOKEN_GAS_TTL),
+			)
+			.await
+		{
+			tracing::warn!(error = %e, "Failed to cache approval gas estimate");
+		}
+		Ok(estimate)
+	}
+
+	/// Returns the cached estimate of an approval, if any.
+	async fn cached(
+		&self,
+		chain_id: u64,
+		token: &Address,
+		spender: &Address,
+	) -> Option<TokenGasEstimate> {
+		self.storage
+			.retrieve(
+				StorageKey::TokenGasCache.as_str(),
+				&gas_estimate_key(chain_id, token, spender),
+			)
+			.await
+			.ok()
+	}
+}
+
+/// Returns the storage key of an approval gas estimate.
+fn gas_estimate_key(chain_id: u64, token: &Address, spender: &Address) -> String {
+	format!(
+		"{}_{}",
+		metadata_key(chain_id, token),
+		with_0x_prefix(&hex::encode(&spender.0))
+	)
+}
+
+/// How long discovered token metadata is cached.
+const TOKEN_METADATA_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// ERC-20 metadata of a token read from its contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+	/// Token name, if the token implements `name()` returning a string.
+	#[serde(default)]
+	pub name: Option<String>,
+	/// Token symbol, if the token implements `symbol()` returning a string.
+	#[serde(default)]
+	pub symbol: Option<String>,
+	/// Number of decimal places of the token.
+	pub decimals: u8,
+}
+
+/// Discovers the ERC-20 metadata of tokens missing from the networks config.
+///
+/// Tokens that appear in orders are not required to be configured. Their
+/// name, symbol and decimals are read from the token contract and cached in
+/// storage, so that amounts of such tokens can be formatted and priced. Only
+/// the decimals are required, since tokens such as MKR return their name and
+/// symbol as `bytes32`.
+pub struct TokenDiscovery {
+	/// Service used to call the token contracts.
+	delivery: Arc<DeliveryService>,
+	/// Storage caching discovered metadata.
+	storage: Arc<StorageService>,
+}
+
+impl TokenDiscovery {
+	/// Creates a new `TokenDiscovery` instance.
+	pub fn new(delivery: Arc<DeliveryService>, storage: Arc<StorageService>) -> Self {
+		Self { delivery, storage }
+	}
+
+	/// Discovers the metadata of the unconfigured tokens of the given orders.
+	///
+	/// Tokens with cached metadata are skipped. Tokens that cannot be reached
+	/// are logged and skipped.
+	///
+	/// # Returns
+	///
+	/// Returns the number of newly discovered tokens.
+	pub async fn discover(&self, networks: &NetworksConfig, orders: &[Order]) -> usize {
+		let mut tokens: Vec<(u64, Address)> = orders
+			.iter()
+			.flat_map(order_tokens)
+			.filter(|(chain_id, token)| configured_token(networks, *chain_id, token).is_none())
+			.collect();
+		tokens.sort_by(|a, b| (a.0, &a.1 .0).cmp(&(b.0, &b.1 .0)));
+		tokens.dedup();
+
+		let mut discovered = 0;
+		for (chain_id, token) in tokens {
+			if self.cached(chain_id, &token).await.is_some() {
+				continue;
+			}
+			if self.metadata(chain_id, &token).await.is_some() {
+				discovered += 1;
+			}
+		}
+		discovered
+	}
+
+	/// Returns the metadata of an unconfigured token, reading it from the
+	/// token contract and caching it unless it is cached already.
+	///
+	/// Tokens that cannot be reached are logged and return `None`.
+	pub async fn metadata(&self, chain_id: u64, token: &Address) -> Option<TokenMetadata> {
+		if let Some(metadata) = self.cached(chain_id, token).await {
+			return Some(metadata);
+		}
+		let metadata = match self.fetch(chain_id, token).await {
+			Ok(metadata) => metadata,
+			Err(e) => {
+				tracing::warn!(
+					chain_id,
+					token = %with_0x_prefix(&hex::encode(&token.0)),
+					error = %e,
+					"Failed to reach token for metadata"
+				);
+				return None;
+			},
+		};
+		tracing::info!(
+			chain_id,
+			token = %with_0x_prefix(&hex::encode(&token.0)),
+			symbol = metadata.symbol.as_deref().unwrap_or("unknown"),
+			decimals = metadata.decimals,
+			"Discovered token metadata"
+		);
+		if let Err(e) = self
+			.storage
+			.store_with_ttl(
+				StorageKey::TokenMetadata.as_str(),
+				&metadata_key(chain_id, token),
+				&metadata,
+				None,
+				Some(TOKEN_METADATA_TTL),
+			)
+			.await
+		{
+			tracing::warn!(error = %e, "Failed to cache token metadata");
+		}
+		Some(metadata)
+	}
+
+	/// Returns the decimals of a token, preferring the networks config over
+	/// discovered metadata. Metadata missing from the cache is read again.
+	pub async fn decimals(
+		&self,
+		networks: &NetworksConfig,
+		chain_id: u64,
+		token: &Address,
+	) -> Option<u8> {
+		match configured_token(networks, chain_id, token) {
+			Some(config) => Some(config.decimals),
+			None => self
+				.metadata(chain_id, token)
+				.await
+				.map(|metadata| metadata.decimals),
+		}
+	}
+
+	/// Reads the EIP-712 domain name and version of a token from its contract.
+	///
+	/// Tokens supporting signed transfers, such as ERC-3009 tokens, sign
+	/// against a domain made of their name, version, chain and address.
+	pub async fn eip712_domain(
+		&self,
+		chain_id: u64,
+		token: &Address,
+	) -> Result<(String, String), TokenManagerError> {
+		let name = self
+			.call(chain_id, token, IERC20Metadata::nameCall {})
+			.await?
+			._0;
+		let version = self
+			.call(chain_id, token, IEIP712Version::versionCall {})
+			.await?
+			._0;
+		Ok((name, version))
+	}
+
+	/// Returns the cached metadata of a token, if any.
+	async fn cached(&self, chain_id: u64, token: &Address) -> Option<TokenMetadata> {
+		self.storage
+			.retrieve(
+				StorageKey::TokenMetadata.as_str(),
+				&metadata_key(chain_id, token),
+			)
+			.await
+			.ok()
+	}
+
+	/// Reads the metadata of a token from its contract.
+	///
+	/// Fails only if the decimals cannot be read.
+	async fn fetch(
+		&self,
+		chain_id: u64,
+		token: &Address,
+	) -> Result<TokenMetadata, TokenManagerError> {
+		let name = self
+			.call(chain_id, token, IERC20Metadata::nameCall {})
+			.await
+			.ok()
+			.map(|name| name._0);
+		let symbol = self
+			.call(chain_id, token, IERC20Metadata::symbolCall {})
+			.await
+			.ok()
+			.map(|symbol| symbol._0);
+		let decimals = self
+			.call(chain_id, token, IERC20Metadata::decimalsCall {})
+			.await?
+			._0;
+		Ok(TokenMetadata {
+			name,
+			symbol,
+			decimals,
+		})
+	}
+
+	/// Calls a view function of a token.
+	async fn call<C: SolCall>(
+		&self,
+		chain_id: u64,
+		token: &Address,
+		call: C,
+	) -> Result<C::Return, TokenManagerError> {
+		let tx = Transaction {
+			chain_id,
+			to: Some(token.clone()),
+			data: call.abi_encode(),
+			value: U256::ZERO,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			nonce: None,
+			memo: None,
+			authorization_list: None,
+		};
+		let trace = self.delivery.estimate_gas(&tx).await?;
+		if !trace.success {
+			return Err(TokenManagerError::ParseError(format!(
+				"{} reverted: {}",
+				C::SIGNATURE,
+				trace.revert_reason.unwrap_or_default()
+			)));
+		}
+		let data = hex::decode(&trace.return_data)
+			.map_err(|e| TokenManagerError::ParseError(e.to_string()))?;
+		C::abi_decode_returns(&data, true)
+			.map_err(|e| TokenManagerError::ParseError(format!("{}: {}", C::SIGNATURE, e)))
+	}
+}
+
+/// Returns the configuration of a token, if it is configured on the chain.
+fn configured_token<'a>(
+	networks: &'a NetworksConfig,
+	chain_id: u64,
+	token: &Address,
+) -> Option<&'a TokenConfig> {
+	networks
+		.get(&chain_id)
+		.and_then(|network| network.tokens.iter().find(|t| t.address == *token))
+}
+
+/// Returns the storage key of a token's metadata.
+fn metadata_key(chain_id: u64, token: &Address) -> String {
+	format!("{}_{}", chain_id, with_0x_prefix(&hex::encode(&token.0)))
+}
+
+/// Returns the chain ID and address of each input and output token of an order.
+///
+/// Tokens on chain IDs that do not fit in a `u64` are left out.
+fn order_tokens(order: &Order) -> Vec<(u64, Address)> {
+	let Ok(data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+		return Vec::new();
+	};
+
+	let inputs = u64::try_from(data.origin_chain_id)
+		.ok()
+		.into_iter()
+		.flat_map(|origin_chain_id| {
+			data.inputs.iter().map(move |[token, _]| {
+				(
+					origin_chain_id,
+					Address(token.to_be_bytes::<32>()[12..].to_vec()),
+				)
+			})
+		});
+	let outputs = data.outputs.iter().filter_map(|output| {
+		u64::try_from(output.chain_id)
+			.ok()
+			.map(|chain_id| (chain_id, Address(output.token[12..].to_vec())))
+	});
+	inputs.chain(outputs).collect()
+}
+
+/// Builds an ERC20 approval transaction of `amount` of a token for `spender`.
+pub(crate) fn approval_transaction(
+	chain_id: u64,
+	token_address: &Address,
+	spender: &Address,
+	amount: U256,
+) -> Transaction {
+	// ERC20 approve(address spender, uint256 amount)
+	// Function selector: 0x095ea7b3
+	let selector = [0x09, 0x5e, 0xa7, 0xb3];
+	let mut call_data = Vec::new();
+	call_data.extend_from_slice(&selector);
+
+	// Add spender address (32 bytes, left-padded with zeros)
+	call_data.extend_from_slice(&[0; 12]); // Pad to 32 bytes
+	call_data.extend_from_slice(&spender.0);
+
+	// Add amount (32 bytes)
+	let amount_bytes = amount.to_be_bytes::<32>();
+	call_data.extend_from_slice(&amount_bytes);
+
+	Transaction {
+		chain_id,
+		to: Some(token_address.clone()),
+		data: call_data,
+		value: U256::ZERO,
+		gas_limit: Some(100000),
+		gas_price: None,
+		max_fee_per_gas: None,
+		max_priority_fee_per_gas: None,
+		nonce: None,
+		memo: None,
+		authorization_list: None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_trait::async_trait;
+	use solver_delivery::{DeliveryError, DeliveryInterface};
+	use solver_types::{
+		ChainId, ConfigSchema, FeeHistory, NetworkConfig, OrderStatus, TransactionReceipt,
+		TransactionTrace,
+	};
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// Returns an order moving token 0xBB on the origin chain to token 0xAA on
+	/// Optimism.
+	fn test_order(origin_chain_id: &str) -> Order {
+		let mut output_token = [0u8; 32];
+		output_token[12..].copy_from_slice(&[0xAA; 20]);
+		Order {
+			id: "order-1".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::json!({
+				"user": "0x0000000000000000000000000000000000000001",
+				"nonce": "0x1",
+				"origin_chain_id": origin_chain_id,
+				"expires": u32::MAX,
+				"fill_deadline": u32::MAX,
+				"input_oracle": "0x0000000000000000000000000000000000000000",
+				"inputs": [[format!("0x{}", "bb".repeat(20)), "0x64"]],
+				"order_id": vec![0u8; 32],
+				"gas_limit_overrides": {},
+				"outputs": [{
+					"oracle": vec![0u8; 32],
+					"settler": vec![0u8; 32],
+					"chain_id": "0xa",
+					"token": output_token,
+					"amount": "0x64",
+					"recipient": vec![0u8; 32],
+					"call": "0x",
+					"context": "0x",
+				}],
+			}),
+			solver_address: Address(vec![0x22; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	#[test]
+	fn test_order_tokens_skip_configured() {
+		let configured = Address(vec![0xAA; 20]);
+		let networks = NetworksConfig::from([(
+			ChainId::OPTIMISM,
+			NetworkConfig {
+				rpc_urls: Vec::new(),
+				input_settler_address: Address(vec![0; 20]),
+				output_settler_address: Address(vec![0; 20]),
+				tokens: vec![TokenConfig {
+					address: configured.clone(),
+					symbol: "USDC".to_string(),
+					decimals: 6,
+				}],
+				input_settler_compact_address: None,
+			},
+		)]);
+
+		let order = test_order("0x1");
+
+		let tokens = order_tokens(&order);
+		assert_eq!(
+			tokens,
+			vec![(1, Address(vec![0xBB; 20])), (10, configured.clone())]
+		);
+		assert!(configured_token(&networks, 10, &configured).is_some());
+		assert!(configured_token(&networks, 1, &Address(vec![0xBB; 20])).is_none());
+		assert_eq!(
+			metadata_key(1, &Address(vec![0xBB; 20])),
+			format!("1_0x{}", "bb".repeat(20))
+		);
+	}
+
+	#[tokio::test]
+	async fn test_approval_gas_limit_from_cached_estimate() {
+		let storage = Arc::new(StorageService::new(
+			Box::new(solver_storage::implementations::memory::MemoryStorage::new()),
+			None,
+		));
+		let benchmark = TokenApprovalBenchmark::new(
+			Arc::new(DeliveryService::new(HashMap::new(), 1)),
+			storage.clone(),
+		);
+		let (token, spender) = (Address(vec![0xAA; 20]), Address(vec![0xBB; 20]));
+		assert_eq!(benchmark.gas_limit(10, &token, &spender).await, None);
+
+		let estimate = TokenGasEstimate {
+			chain_id: 10,
+			token: with_0x_prefix(&"aa".repeat(20)),
+			spender: with_0x_prefix(&"bb".repeat(20)),
+			gas_used: 46_000,
+			gas_limit: 55_200,
+			estimated_at: 0,
+		};
+		storage
+			.store(
+				StorageKey::TokenGasCache.as_str(),
+				&gas_estimate_key(10, &token, &spender),
+				&estimate,
+				None,
+			)
+			.await
+			.unwrap();
+		assert_eq!(
+			benchmark.gas_limit(10, &token, &spender).await,
+			Some(55_200)
+		);
+		assert_eq!(benchmark.estimates().await.unwrap(), vec![estimate]);
+
+		// Chains without a delivery implementation cannot be estimated
+		assert!(benchmark.estimate(1, &token, &spender).await.is_err());
+	}
+
+	#[test]
+	fn test_order_tokens_skip_overflowing_chain_ids() {
+		let order = test_order(&format!("0x{}", "ff".repeat(32)));
+		assert_eq!(order_tokens(&order), vec![(10, Address(vec![0xAA; 20]))]);
+	}
+
+	/// Token returning its name and symbol as `bytes32`, counting calls.
+	struct Bytes32Token {
+		calls: AtomicUsize,
+	}
+
+	#[async_trait]
+	impl DeliveryInterface for Bytes32Token {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn submit(&self, _tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn wait_for_confirmation(
+			&self,
+			_hash: &TransactionHash,
+			_chain_id: u64,
+			_confirmations: u64,
+		) -> Result<TransactionReceipt, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_receipt(
+			&self,
+			_hash: &TransactionHash,
+			_chain_id: u64,
+		) -> Result<TransactionReceipt, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_gas_price(&self, _chain_id: u64) -> Result<String, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_balance(
+			&self,
+			_address: &str,
+			_token: Option<&str>,
+			_chain_id: u64,
+		) -> Result<String, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_allowance(
+			&self,
+			_owner: &str,
+			_spender: &str,
+			_token_address: &str,
+			_chain_id: u64,
+		) -> Result<String, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_nonce(&self, _address: &str, _chain_id: u64) -> Result<u64, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_code(&self, _address: &str, _chain_id: u64) -> Result<Vec<u8>, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_block_number(&self, _chain_id: u64) -> Result<u64, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_fee_history(
+			&self,
+			_chain_id: u64,
+			_block_count: u64,
+			_reward_percentiles: &[f64],
+		) -> Result<FeeHistory, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn estimate_gas(&self, tx: &Transaction) -> Result<TransactionTrace, DeliveryError> {
+			self.calls.fetch_add(1, Ordering::SeqCst);
+			let mut word = [0u8; 32];
+			if tx.data.starts_with(&IERC20Metadata::decimalsCall::SELECTOR) {
+				word[31] = 18;
+			} else {
+				word[..3].copy_from_slice(b"MKR");
+			}
+			Ok(TransactionTrace {
+				success: true,
+				gas_used: Some(21_000),
+				return_data: with_0x_prefix(&hex::encode(word)),
+				revert_reason: None,
+				logs: Vec::new(),
+			})
+		}
+	}
+
+	#[tokio::test]
+	async fn test_discovery_keeps_decimals_and_rereads_expired_metadata() {
+		let token = Arc::new(Bytes32Token {
+			calls: AtomicUsize::new(0),
+		});
+		let delivery = Arc::new(DeliveryService::new(
+			HashMap::from([(ChainId(1), token.clone() as Arc<dyn DeliveryInterface>)]),
+			1,
+		));
+		let storage = Arc::new(StorageService::new(
+			Box::new(solver_storage::implementations::memory::MemoryStorage::new()),
+			None,
+		));
+		let discovery = TokenDiscovery::new(delivery, storage.clone());
+		let networks = NetworksConfig::new();
+		let address = Address(vec![0xBB; 20]);
+
+		assert_eq!(discovery.discover(&networks, &[test_order("0x1")]).await, 1);
+		let metadata = discovery.metadata(1, &address).await.unwrap();
+		assert_eq!(metadata.decimals, 18);
+		assert_eq!(metadata.name, None);
+		assert_eq!(metadata.symbol, None);
+		assert_eq!(token.calls.load(Ordering::SeqCst), 3);
+
+		// Expired metadata is read from the token again on its next use
+		storage
+			.remove(
+				StorageKey::TokenMetadata.as_str(),
+				&metadata_key(1, &address),
+			)
+			.await
+			.unwrap();
+		assert_eq!(discovery.decimals(&networks, 1, &address).await, Some(18));
+		assert_eq!(token.calls.load(Ordering::SeqCst), 6);
+	}
+}
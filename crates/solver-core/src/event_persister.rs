@@ -0,0 +1,209 @@
+//! Persisted log of all solver events for post-mortem debugging.
+//!
+//! The persister appends every event published on the event bus to the event
+//! log namespace, numbered by a sequence that keeps increasing across
+//! restarts. Events are written with a TTL of the configured retention, so
+//! the regular storage cleanup prunes old events.
+//!
+//! Writing never slows down the solver: events are buffered in a bounded
+//! channel drained by a dedicated task, and dropped when the buffer is full.
+
+use serde::{Deserialize, Serialize};
+use solver_config::EventLogConfig;
+use solver_storage::{QueryFilter, StorageError, StorageIndexes, StorageService};
+use solver_types::{current_timestamp, EventLogQuery, SolverEvent, StorageKey};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+/// Number of events returned per page by default.
+pub const DEFAULT_EVENT_LOG_PAGE_SIZE: usize = 100;
+
+/// Maximum number of events returned per page.
+pub const MAX_EVENT_LOG_PAGE_SIZE: usize = 1000;
+
+/// A persisted solver event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLogEntry {
+	/// Position of the event in the log.
+	pub seq: u64,
+	/// Timestamp at which the event was persisted.
+	pub recorded_at: u64,
+	pub event: SolverEvent,
+}
+
+/// Sequence number of a stored entry, read without deserializing its event.
+#[derive(Deserialize)]
+struct StoredSeq {
+	seq: u64,
+}
+
+/// Appends the events of the event bus to the event log.
+pub struct EventPersister {
+	storage: Arc<StorageService>,
+	retention: Duration,
+	buffer_size: usize,
+	/// Sequence number of the next event.
+	next_seq: u64,
+}
+
+impl EventPersister {
+	/// Creates a new EventPersister continuing the sequence of the stored log.
+	pub async fn new(
+		storage: Arc<StorageService>,
+		config: &EventLogConfig,
+	) -> Result<Self, StorageError> {
+		let next_seq = storage
+			.query::<StoredSeq>(StorageKey::EventLog.as_str(), QueryFilter::All)
+			.await?
+			.into_iter()
+			.map(|(_, stored)| stored.seq + 1)
+			.max()
+			.unwrap_or(0);
+		Ok(Self {
+			storage,
+			retention: Duration::from_secs(config.retention_days.saturating_mul(86400)),
+			buffer_size: config.buffer_size,
+			next_seq,
+		})
+	}
+
+	/// Appends an event to the log, returning its sequence number.
+	pub async fn append(&mut self, event: SolverEvent, now: u64) -> Result<u64, StorageError> {
+		let entry = EventLogEntry {
+			seq: self.next_seq,
+			recorded_at: now,
+			event,
+		};
+		let indexes = StorageIndexes::new()
+			.with_numeric("seq", entry.seq)
+			.with_numeric("recorded_at", now);
+		self.storage
+			.store_with_ttl(
+				StorageKey::EventLog.as_str(),
+				&event_id(entry.seq),
+				&entry,
+				Some(indexes),
+				Some(self.retention),
+			)
+			.await?;
+		self.next_seq += 1;
+		Ok(entry.seq)
+	}
+
+	/// Spawns the tasks persisting the events received on `events`.
+	///
+	/// Events are forwarded to a writer task through a channel holding up to
+	/// the configured buffer size. The writer stops once the returned task
+	/// is aborted and the buffered events are written.
+	pub fn spawn(mut self, mut events: broadcast::Receiver<SolverEvent>) -> JoinHandle<()> {
+		let (sender, mut receiver) = mpsc::channel(self.buffer_size);
+		tokio::spawn(async move {
+			while let Some(event) = receiver.recv().await {
+				if let Err(e) = self.append(event, current_timestamp()).await {
+					tracing::warn!(error = %e, "Failed to persist solver event");
+				}
+			}
+		});
+
+		tokio::spawn(async move {
+			loop {
+				match events.recv().await {
+					Ok(event) => {
+						if sender.try_send(event).is_err() {
+							tracing::warn!("Event log buffer full, dropping solver event");
+						}
+					},
+					Err(broadcast::error::RecvError::Lagged(skipped)) => {
+						tracing::warn!(skipped, "Event log lagged behind the event bus");
+					},
+					Err(broadcast::error::RecvError::Closed) => break,
+				}
+			}
+		})
+	}
+}
+
+/// Returns the storage ID of an event, ordering IDs by sequence number.
+fn event_id(seq: u64) -> String {
+	format!("{:020}", seq)
+}
+
+/// Returns the logged events from the query's sequence number on, in order.
+pub async fn event_log_entries(
+	storage: &StorageService,
+	query: &EventLogQuery,
+) -> Result<Vec<EventLogEntry>, StorageError> {
+	let filter = match query.from_seq {
+		0 => QueryFilter::All,
+		from_seq => QueryFilter::GreaterThan("seq".to_string(), serde_json::json!(from_seq - 1)),
+	};
+	let mut entries: Vec<EventLogEntry> = storage
+		.query::<EventLogEntry>(StorageKey::EventLog.as_str(), filter)
+		.await?
+		.into_iter()
+		.map(|(_, entry)| entry)
+		.collect();
+	entries.sort_by_key(|entry| entry.seq);
+
+	let limit = query
+		.limit
+		.unwrap_or(DEFAULT_EVENT_LOG_PAGE_SIZE)
+		.clamp(1, MAX_EVENT_LOG_PAGE_SIZE);
+	entries.truncate(limit);
+	Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::file::create_storage;
+
+	fn event(chain_id: u64) -> SolverEvent {
+		SolverEvent::OracleUnhealthy {
+			chain_id,
+			oracle_address: solver_types::Address(vec![0; 20]),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_sequence_continues_across_restarts() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let backend = create_storage(&toml::Value::Table(config)).unwrap();
+		let storage = Arc::new(StorageService::new(backend, None));
+
+		let mut persister = EventPersister::new(storage.clone(), &EventLogConfig::default())
+			.await
+			.unwrap();
+		for chain_id in 1..=3 {
+			persister.append(event(chain_id), 100).await.unwrap();
+		}
+
+		let mut restarted = EventPersister::new(storage.clone(), &EventLogConfig::default())
+			.await
+			.unwrap();
+		assert_eq!(restarted.append(event(4), 200).await.unwrap(), 3);
+
+		let entries = event_log_entries(
+			&storage,
+			&EventLogQuery {
+				from_seq: 1,
+				limit: Some(2),
+			},
+		)
+		.await
+		.unwrap();
+		let seqs: Vec<u64> = entries.iter().map(|entry| entry.seq).collect();
+		assert_eq!(seqs, vec![1, 2]);
+		assert!(matches!(
+			entries[0].event,
+			SolverEvent::OracleUnhealthy { chain_id: 2, .. }
+		));
+	}
+}
@@ -0,0 +1,212 @@
+//! Per-chain gas budget enforcement.
+//!
+//! Tracks the gas the solver spends on each chain over a rolling 24-hour
+//! window so that a single congested chain cannot drain the solver's native
+//! balance. Orders touching a chain whose budget is exhausted are skipped.
+
+use crate::engine::event_bus::EventBus;
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+use solver_storage::StorageService;
+use solver_types::{current_timestamp, SolverEvent, StorageKey};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Length of the rolling budget window in seconds.
+const BUDGET_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+/// Utilization, in percent, at which a budget warning is emitted.
+const WARNING_THRESHOLD_PERCENT: u64 = 80;
+
+/// A single gas payment recorded against a chain's budget.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GasSpendEntry {
+	/// Unix timestamp of the spend.
+	timestamp: u64,
+	/// Amount spent in wei.
+	amount_wei: U256,
+}
+
+/// Gas spend recorded for a chain within the budget window.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct GasSpendRecord {
+	entries: Vec<GasSpendEntry>,
+}
+
+impl GasSpendRecord {
+	/// Drops entries older than the budget window.
+	fn prune(&mut self, now: u64) {
+		let cutoff = now.saturating_sub(BUDGET_WINDOW_SECONDS);
+		self.entries.retain(|entry| entry.timestamp > cutoff);
+	}
+
+	/// Returns the total spend of all entries.
+	fn total(&self) -> U256 {
+		self.entries.iter().fold(U256::ZERO, |acc, entry| {
+			acc.saturating_add(entry.amount_wei)
+		})
+	}
+}
+
+/// Tracks gas spend per chain against configured daily budgets.
+pub struct GasBudgetManager {
+	/// Daily budget in wei per chain.
+	budgets: HashMap<u64, U256>,
+	storage: Arc<StorageService>,
+	event_bus: EventBus,
+	/// Serializes read-modify-write updates of spend records.
+	update_lock: Mutex<()>,
+}
+
+impl GasBudgetManager {
+	/// Creates a new GasBudgetManager from daily budgets in ETH keyed by chain ID.
+	pub fn new(
+		budgets_eth: HashMap<u64, f64>,
+		storage: Arc<StorageService>,
+		event_bus: EventBus,
+	) -> Self {
+		let budgets = budgets_eth
+			.into_iter()
+			.map(|(chain_id, eth)| (chain_id, eth_to_wei(eth)))
+			.collect();
+
+		Self {
+			budgets,
+			storage,
+			event_bus,
+			update_lock: Mutex::new(()),
+		}
+	}
+
+	/// Records gas spent on a chain.
+	///
+	/// Emits `SolverEvent::GasBudgetWarning` when this spend takes the chain's
+	/// rolling utilization to or above 80% of its budget.
+	pub async fn record_spend(&self, chain_id: u64, amount_wei: U256) {
+		let Some(budget) = self.budgets.get(&chain_id).copied() else {
+			return;
+		};
+
+		let _guard = self.update_lock.lock().await;
+		let now = current_timestamp();
+		let mut record = self.load(chain_id).await;
+		record.prune(now);
+
+		let previous = record.total();
+		record.entries.push(GasSpendEntry {
+			timestamp: now,
+			amount_wei,
+		});
+		let spent = record.total();
+
+		if let Err(e) = self
+			.storage
+			.store(
+				StorageKey::GasSpend.as_str(),
+				&chain_id.to_string(),
+				&record,
+				None,
+			)
+			.await
+		{
+			tracing::warn!(chain_id, error = %e, "Failed to store gas spend");
+		}
+
+		if !reaches_warning(previous, budget) && reaches_warning(spent, budget) {
+			tracing::warn!(
+				chain_id,
+				spent_wei = %spent,
+				budget_wei = %budget,
+				"Gas budget utilization above {}%",
+				WARNING_THRESHOLD_PERCENT
+			);
+			self.event_bus
+				.publish(SolverEvent::GasBudgetWarning {
+					chain_id,
+					spent_wei: spent,
+					budget_wei: budget,
+				})
+				.ok();
+		}
+	}
+
+	/// Returns the remaining budget in wei for each chain with a configured budget.
+	pub async fn remaining(&self) -> HashMap<u64, U256> {
+		let now = current_timestamp();
+		let mut remaining = HashMap::new();
+
+		for (chain_id, budget) in &self.budgets {
+			let mut record = self.load(*chain_id).await;
+			record.prune(now);
+			remaining.insert(*chain_id, budget.saturating_sub(record.total()));
+		}
+
+		remaining
+	}
+
+	/// Loads the spend record for a chain, returning an empty record if none exists.
+	async fn load(&self, chain_id: u64) -> GasSpendRecord {
+		self.storage
+			.retrieve(StorageKey::GasSpend.as_str(), &chain_id.to_string())
+			.await
+			.unwrap_or_default()
+	}
+}
+
+/// Returns true if any of the given chains has an exhausted gas budget.
+pub fn budget_exhausted(remaining: &HashMap<u64, U256>, chains: &[u64]) -> bool {
+	chains
+		.iter()
+		.any(|chain_id| remaining.get(chain_id).is_some_and(|left| left.is_zero()))
+}
+
+/// Returns true if `spent` is at or above the warning threshold of `budget`.
+fn reaches_warning(spent: U256, budget: U256) -> bool {
+	spent.saturating_mul(U256::from(100))
+		>= budget.saturating_mul(U256::from(WARNING_THRESHOLD_PERCENT))
+}
+
+/// Converts an amount in ETH to wei.
+fn eth_to_wei(eth: f64) -> U256 {
+	U256::from((eth * 1e18) as u128)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_prune_drops_entries_outside_window() {
+		let now = 10 * BUDGET_WINDOW_SECONDS;
+		let mut record = GasSpendRecord {
+			entries: vec![
+				GasSpendEntry {
+					timestamp: now - BUDGET_WINDOW_SECONDS,
+					amount_wei: U256::from(5),
+				},
+				GasSpendEntry {
+					timestamp: now - 60,
+					amount_wei: U256::from(7),
+				},
+			],
+		};
+
+		record.prune(now);
+		assert_eq!(record.total(), U256::from(7));
+	}
+
+	#[test]
+	fn test_warning_threshold() {
+		let budget = eth_to_wei(1.0);
+		assert!(!reaches_warning(eth_to_wei(0.79), budget));
+		assert!(reaches_warning(eth_to_wei(0.8), budget));
+	}
+
+	#[test]
+	fn test_budget_exhausted_only_for_configured_chains() {
+		let remaining = HashMap::from([(1, U256::ZERO), (10, U256::from(1))]);
+		assert!(budget_exhausted(&remaining, &[10, 1]));
+		assert!(!budget_exhausted(&remaining, &[10, 8453]));
+	}
+}
@@ -0,0 +1,929 @@
+//! Intent gossiping between solver replicas.
+//!
+//! Each replica accepts subscriptions from its peers over TCP and pushes the
+//! intents it discovers to them as newline-delimited JSON messages. Messages
+//! carry a per-replica sequence number; when subscribing, a replica sends the
+//! last sequence it received from each origin, and the peer replays the
+//! intents it discovered since then within the replay window. Received
+//! intents are deduplicated against storage before reaching the local engine,
+//! and are never forwarded again, so intents do not loop between replicas.
+//!
+//! Both ends of a subscription periodically send their reputation score. A
+//! replica assigns each intent it discovers to the subscribed peer with the
+//! best reputation, or to itself, and only the assigned replica processes it.
+//! The assigned peer acknowledges the intent; if it does not within the
+//! acknowledgement timeout, the replica processes the intent itself.
+//!
+//! Peers authenticate each other with the shared secret when subscribing:
+//! each end sends a random challenge, which the other answers with an HMAC
+//! of the challenge and its node ID. Messages are only accepted from the
+//! authenticated peer of a connection.
+//!
+//! Orders cancelled by their users are gossiped the same way, so a replica
+//! that received the intent stops processing it too.
+
+use crate::cancellation::OrderCancellation;
+use crate::reputation::{ReputationScore, ReputationService};
+use alloy_primitives::hex;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use solver_config::GossipConfig;
+use solver_storage::StorageService;
+use solver_types::{current_timestamp, Intent, StorageKey};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// Capacity of the channel of intents pushed to subscribed peers.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Delay before reconnecting to a peer after the connection fails.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Number of recently received intent IDs kept for deduplication.
+const SEEN_CAPACITY: usize = 10_000;
+
+/// Time a peer has to complete the subscription handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Domain separators of the handshake signatures.
+const SUBSCRIBE_DOMAIN: &str = "oif-gossip-subscribe";
+const WELCOME_DOMAIN: &str = "oif-gossip-welcome";
+
+/// Message exchanged between replicas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum GossipMessage {
+	/// Sent by a replica to a peer connecting to it.
+	Challenge { node_id: String, nonce: String },
+	/// Sent by a replica in answer to the challenge of the peer it connects
+	/// to, with `auth` signing the challenge and `node_id`, and a challenge
+	/// of its own. The peer replays the intents it discovered after
+	/// `last_sequences[peer]`, then streams new ones.
+	Subscribe {
+		node_id: String,
+		last_sequences: HashMap<String, u64>,
+		nonce: String,
+		auth: String,
+	},
+	/// Sent by a replica accepting a subscription, with `auth` signing the
+	/// subscriber's challenge and the replica's node ID.
+	Welcome { auth: String },
+	/// An intent discovered by the replica `origin`.
+	Intent {
+		origin: String,
+		sequence: u64,
+		timestamp: u64,
+		intent: Intent,
+		/// Replica assigned to process the intent. Any replica may process
+		/// intents without an assignee.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		assignee: Option<String>,
+	},
+	/// A cancellation of an order received by the replica `origin`.
+	Cancellation {
+		origin: String,
+		sequence: u64,
+		timestamp: u64,
+		cancellation: OrderCancellation,
+	},
+	/// The reputation score of the replica `node_id`.
+	Reputation {
+		node_id: String,
+		score: ReputationScore,
+	},
+	/// Sent by a replica that received an intent assigned to it.
+	Ack { intent_id: String },
+}
+
+/// Intents discovered and cancellations received by this replica, kept for
+/// replay to peers.
+struct ReplayLog {
+	next_sequence: u64,
+	messages: VecDeque<GossipMessage>,
+}
+
+/// Intents and sequences received from peers.
+#[derive(Default)]
+struct ReceivedState {
+	/// Last sequence received per origin replica.
+	last_sequences: HashMap<String, u64>,
+	/// Recently received intent IDs, oldest first.
+	seen_order: VecDeque<String>,
+	seen: HashSet<String>,
+}
+
+impl ReceivedState {
+	/// Records a received intent ID, returning false if it was already seen.
+	fn mark_seen(&mut self, intent_id: &str) -> bool {
+		if !self.seen.insert(intent_id.to_string()) {
+			return false;
+		}
+		self.seen_order.push_back(intent_id.to_string());
+		if self.seen_order.len() > SEEN_CAPACITY {
+			if let Some(oldest) = self.seen_order.pop_front() {
+				self.seen.remove(&oldest);
+			}
+		}
+		true
+	}
+}
+
+/// Gossips discovered intents to and from peer replicas.
+pub struct GossipService {
+	/// Unique identifier of this replica, used as origin of its messages.
+	node_id: String,
+	config: GossipConfig,
+	storage: Arc<StorageService>,
+	log: Mutex<ReplayLog>,
+	received: Mutex<ReceivedState>,
+	/// Intents discovered by this replica, pushed to subscribed peers.
+	outgoing: broadcast::Sender<GossipMessage>,
+	/// Reputation of this replica and its peers.
+	reputation: Arc<ReputationService>,
+	/// Node IDs of the peers currently subscribed to this replica.
+	subscribers: Mutex<HashSet<String>>,
+	/// Assignees of the intents assigned to peers and not acknowledged yet,
+	/// by intent ID.
+	assignments: Mutex<HashMap<String, String>>,
+	/// Senders to the local solvers, set when the service is spawned.
+	local: Mutex<Vec<mpsc::UnboundedSender<Intent>>>,
+}
+
+impl GossipService {
+	/// Creates a new GossipService for the solver `solver_id`.
+	///
+	/// `storage` is checked to skip intents the solver already processed.
+	pub fn new(
+		solver_id: &str,
+		config: GossipConfig,
+		storage: Arc<StorageService>,
+		reputation: Arc<ReputationService>,
+	) -> Arc<Self> {
+		let started_at = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_nanos();
+		let (outgoing, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+		Arc::new(Self {
+			node_id: format!("{}-{}-{:x}", solver_id, std::process::id(), started_at),
+			config,
+			storage,
+			log: Mutex::new(ReplayLog {
+				next_sequence: 1,
+				messages: VecDeque::new(),
+			}),
+			received: Mutex::new(ReceivedState::default()),
+			outgoing,
+			reputation,
+			subscribers: Mutex::new(HashSet::new()),
+			assignments: Mutex::new(HashMap::new()),
+			local: Mutex::new(Vec::new()),
+		})
+	}
+
+	/// Returns the unique identifier of this replica.
+	pub fn node_id(&self) -> &str {
+		&self.node_id
+	}
+
+	/// Broadcasts an intent discovered by this replica to all subscribed peers.
+	///
+	/// The intent is assigned to the subscribed peer with the best reputation,
+	/// if any ranks above this replica. Returns whether this replica should
+	/// process the intent itself; intents assigned to a peer are processed
+	/// later if the peer does not acknowledge them in time.
+	pub fn broadcast(self: &Arc<Self>, intent: &Intent) -> bool {
+		let now = current_timestamp();
+		let assignee = {
+			let subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+			self.reputation.preferred_peer(subscribers.iter(), now)
+		};
+		if let Some(assignee) = &assignee {
+			tracing::debug!(intent_id = %intent.id, assignee = %assignee, "Assigning intent to peer");
+			self.await_ack(intent.clone(), assignee.clone());
+		}
+		let process_locally = assignee.is_none();
+
+		self.publish(now, |sequence| GossipMessage::Intent {
+			origin: self.node_id.clone(),
+			sequence,
+			timestamp: now,
+			intent: intent.clone(),
+			assignee,
+		});
+		process_locally
+	}
+
+	/// Processes an intent assigned to a peer locally unless the peer
+	/// acknowledges it within the acknowledgement timeout.
+	fn await_ack(self: &Arc<Self>, intent: Intent, assignee: String) {
+		self.assignments
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.insert(intent.id.clone(), assignee);
+		let service = Arc::clone(self);
+		tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_secs(
+				service.config.assignment_ack_timeout_seconds,
+			))
+			.await;
+			let unacknowledged = service
+				.assignments
+				.lock()
+				.unwrap_or_else(|e| e.into_inner())
+				.remove(&intent.id);
+			if let Some(assignee) = unacknowledged {
+				tracing::warn!(intent_id = %intent.id, assignee = %assignee, "Assigned intent not acknowledged, processing it locally");
+				service.forward_local(&intent);
+			}
+		});
+	}
+
+	/// Records the acknowledgement of an assigned intent by a peer.
+	fn acknowledge(&self, peer_id: &str, intent_id: &str) {
+		let mut assignments = self.assignments.lock().unwrap_or_else(|e| e.into_inner());
+		if assignments
+			.get(intent_id)
+			.is_some_and(|assignee| assignee == peer_id)
+		{
+			tracing::debug!(intent_id = %intent_id, peer = %peer_id, "Assigned intent acknowledged");
+			assignments.remove(intent_id);
+		}
+	}
+
+	/// Sends an intent to the local solvers.
+	fn forward_local(&self, intent: &Intent) {
+		for sender in self.local.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+			if sender.send(intent.clone()).is_err() {
+				tracing::debug!(intent_id = %intent.id, "Solver stopped, dropping gossiped intent");
+			}
+		}
+	}
+
+	/// Returns the hex HMAC of `parts` under the shared secret.
+	fn sign(&self, parts: &[&str]) -> String {
+		hex::encode(self.mac(parts).finalize().into_bytes())
+	}
+
+	/// Returns whether `auth` is the HMAC of `parts` under the shared secret.
+	fn verify(&self, parts: &[&str], auth: &str) -> bool {
+		hex::decode(auth).is_ok_and(|auth| self.mac(parts).verify_slice(&auth).is_ok())
+	}
+
+	fn mac(&self, parts: &[&str]) -> Hmac<Sha256> {
+		let secret = self.config.shared_secret.as_deref().unwrap_or_default();
+		let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+			.expect("HMAC accepts keys of any length");
+		for part in parts {
+			mac.update(part.as_bytes());
+			mac.update(b"\n");
+		}
+		mac
+	}
+
+	/// Broadcasts a cancellation received by this replica to all subscribed
+	/// peers.
+	pub fn broadcast_cancellation(&self, cancellation: &OrderCancellation) {
+		let now = current_timestamp();
+		self.publish(now, |sequence| GossipMessage::Cancellation {
+			origin: self.node_id.clone(),
+			sequence,
+			timestamp: now,
+			cancellation: cancellation.clone(),
+		});
+	}
+
+	/// Logs the message built for the next sequence and pushes it to the
+	/// subscribed peers.
+	fn publish(&self, now: u64, message: impl FnOnce(u64) -> GossipMessage) {
+		let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+		let message = message(log.next_sequence);
+		log.next_sequence += 1;
+		log.messages.push_back(message.clone());
+		prune_log(
+			&mut log.messages,
+			now.saturating_sub(self.config.replay_window_seconds),
+		);
+
+		// Without subscribers the message is only kept for replay
+		let _ = self.outgoing.send(message);
+	}
+
+	/// Returns the reputation message of this replica.
+	fn reputation_message(&self) -> GossipMessage {
+		GossipMessage::Reputation {
+			node_id: self.node_id.clone(),
+			score: self.reputation.local_score(current_timestamp()),
+		}
+	}
+
+	/// Records the reputation reported by the authenticated peer `peer_id`.
+	fn receive_reputation(&self, peer_id: &str, node_id: String, score: ReputationScore) {
+		if node_id != peer_id {
+			tracing::warn!(peer = %peer_id, node_id = %node_id, "Ignoring reputation reported for another replica");
+			return;
+		}
+		self.reputation
+			.update_peer(node_id, score, current_timestamp());
+	}
+
+	/// Starts accepting subscriptions and subscribes to all configured peers.
+	///
+	/// Intents received from peers, and assigned intents peers did not
+	/// acknowledge, are sent to every sender in `local`, and cancellations
+	/// to `cancellations`.
+	pub fn spawn(
+		self: &Arc<Self>,
+		local: Vec<mpsc::UnboundedSender<Intent>>,
+		cancellations: mpsc::UnboundedSender<OrderCancellation>,
+	) -> Vec<JoinHandle<()>> {
+		*self.local.lock().unwrap_or_else(|e| e.into_inner()) = local;
+		let mut handles = vec![tokio::spawn(Arc::clone(self).accept_subscribers())];
+		for peer in self.config.peers.clone() {
+			let service = Arc::clone(self);
+			let cancellations = cancellations.clone();
+			handles.push(tokio::spawn(async move {
+				service.subscribe_to_peer(peer, cancellations).await;
+			}));
+		}
+		handles
+	}
+
+	/// Accepts subscriptions of peers, serving each on its own task.
+	///
+	/// The connection tasks are aborted when this task is.
+	async fn accept_subscribers(self: Arc<Self>) {
+		let listener = match TcpListener::bind(&self.config.listen_address).await {
+			Ok(listener) => listener,
+			Err(e) => {
+				tracing::error!(
+					address = %self.config.listen_address,
+					error = %e,
+					"Failed to start gossip listener"
+				);
+				return;
+			},
+		};
+		tracing::info!(
+			address = %self.config.listen_address,
+			node_id = %self.node_id,
+			"Accepting gossip subscriptions"
+		);
+
+		let mut connections = JoinSet::new();
+		loop {
+			match listener.accept().await {
+				Ok((stream, address)) => {
+					// Forget the connections that closed
+					while connections.try_join_next().is_some() {}
+					let service = Arc::clone(&self);
+					connections.spawn(async move {
+						if let Err(e) = service.serve_subscriber(stream).await {
+							tracing::debug!(peer = %address, error = %e, "Gossip subscriber disconnected");
+						}
+					});
+				},
+				Err(e) => tracing::warn!(error = %e, "Failed to accept gossip connection"),
+			}
+		}
+	}
+
+	/// Authenticates a subscriber, replays the intents it missed, then
+	/// streams new ones.
+	async fn serve_subscriber(&self, stream: TcpStream) -> std::io::Result<()> {
+		let (reader, mut writer) = stream.into_split();
+		let mut lines = BufReader::new(reader).lines();
+
+		let challenge = new_nonce();
+		write_message(
+			&mut writer,
+			&GossipMessage::Challenge {
+				node_id: self.node_id.clone(),
+				nonce: challenge.clone(),
+			},
+		)
+		.await?;
+		let (peer_id, after_sequence) = match read_message(&mut lines).await? {
+			Some(GossipMessage::Subscribe {
+				node_id,
+				last_sequences,
+				nonce,
+				auth,
+			}) => {
+				if !self.verify(&[SUBSCRIBE_DOMAIN, &challenge, &node_id], &auth) {
+					tracing::warn!(node_id = %node_id, "Rejected gossip subscriber with invalid credentials");
+					return Ok(());
+				}
+				write_message(
+					&mut writer,
+					&GossipMessage::Welcome {
+						auth: self.sign(&[WELCOME_DOMAIN, &nonce, &self.node_id]),
+					},
+				)
+				.await?;
+				tracing::info!(peer = %node_id, "Gossip peer subscribed");
+				let after_sequence = last_sequences.get(&self.node_id).copied().unwrap_or(0);
+				(node_id, after_sequence)
+			},
+			_ => return Ok(()),
+		};
+
+		self.subscribers
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.insert(peer_id.clone());
+		let result = self
+			.stream_to_subscriber(&peer_id, &mut lines, &mut writer, after_sequence)
+			.await;
+		self.subscribers
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.remove(&peer_id);
+		result
+	}
+
+	/// Streams intents and reputation scores to a subscriber, recording the
+	/// reputation scores and acknowledgements it sends back.
+	async fn stream_to_subscriber(
+		&self,
+		peer_id: &str,
+		lines: &mut tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
+		writer: &mut tokio::net::tcp::OwnedWriteHalf,
+		after_sequence: u64,
+	) -> std::io::Result<()> {
+		// Subscribe before replaying so no intent falls between replay and stream
+		let mut outgoing = self.outgoing.subscribe();
+		let replay = self.replay_after(after_sequence);
+		let mut last_sent = after_sequence;
+		for message in replay {
+			last_sent = message_sequence(&message);
+			write_message(writer, &message).await?;
+		}
+
+		let mut reputation_ticker = tokio::time::interval(Duration::from_secs(
+			self.config.reputation_broadcast_interval_seconds,
+		));
+		loop {
+			tokio::select! {
+				message = outgoing.recv() => match message {
+					Ok(message) if message_sequence(&message) > last_sent => {
+						last_sent = message_sequence(&message);
+						write_message(writer, &message).await?;
+					},
+					Ok(_) => {},
+					Err(broadcast::error::RecvError::Lagged(skipped)) => {
+						// The peer replays the skipped intents when it resubscribes
+						tracing::warn!(skipped, "Gossip subscriber lagging, disconnecting");
+						return Ok(());
+					},
+					Err(broadcast::error::RecvError::Closed) => return Ok(()),
+				},
+				line = lines.next_line() => match line? {
+					Some(line) => match serde_json::from_str(&line) {
+						Ok(GossipMessage::Reputation { node_id, score }) => {
+							self.receive_reputation(peer_id, node_id, score);
+						},
+						Ok(GossipMessage::Ack { intent_id }) => self.acknowledge(peer_id, &intent_id),
+						_ => {},
+					},
+					None => return Ok(()),
+				},
+				_ = reputation_ticker.tick() => {
+					write_message(writer, &self.reputation_message()).await?;
+				},
+			}
+		}
+	}
+
+	/// Returns the logged intents after `sequence` within the replay window.
+	fn replay_after(&self, sequence: u64) -> Vec<GossipMessage> {
+		let cutoff = current_timestamp().saturating_sub(self.config.replay_window_seconds);
+		let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+		prune_log(&mut log.messages, cutoff);
+		log.messages
+			.iter()
+			.filter(|message| message_sequence(message) > sequence)
+			.cloned()
+			.collect()
+	}
+
+	/// Keeps a subscription to a peer open, reconnecting when it fails.
+	async fn subscribe_to_peer(
+		&self,
+		peer: String,
+		cancellations: mpsc::UnboundedSender<OrderCancellation>,
+	) {
+		loop {
+			match self.receive_from_peer(&peer, &cancellations).await {
+				Ok(()) => tracing::debug!(peer = %peer, "Gossip peer closed the connection"),
+				Err(e) => tracing::debug!(peer = %peer, error = %e, "Gossip peer unavailable"),
+			}
+			tokio::time::sleep(RECONNECT_DELAY).await;
+		}
+	}
+
+	/// Subscribes to a peer and forwards the intents and cancellations it sends.
+	async fn receive_from_peer(
+		&self,
+		peer: &str,
+		cancellations: &mpsc::UnboundedSender<OrderCancellation>,
+	) -> std::io::Result<()> {
+		let stream = TcpStream::connect(peer).await?;
+		let (reader, mut writer) = stream.into_split();
+		let mut lines = BufReader::new(reader).lines();
+
+		let (peer_id, challenge) = match read_message(&mut lines).await? {
+			Some(GossipMessage::Challenge { node_id, nonce }) => (node_id, nonce),
+			_ => return Err(invalid_handshake("expected a challenge")),
+		};
+		let nonce = new_nonce();
+		let subscribe = GossipMessage::Subscribe {
+			node_id: self.node_id.clone(),
+			last_sequences: self
+				.received
+				.lock()
+				.unwrap_or_else(|e| e.into_inner())
+				.last_sequences
+				.clone(),
+			nonce: nonce.clone(),
+			auth: self.sign(&[SUBSCRIBE_DOMAIN, &challenge, &self.node_id]),
+		};
+		write_message(&mut writer, &subscribe).await?;
+		match read_message(&mut lines).await? {
+			Some(GossipMessage::Welcome { auth })
+				if self.verify(&[WELCOME_DOMAIN, &nonce, &peer_id], &auth) => {},
+			_ => return Err(invalid_handshake("peer failed to authenticate")),
+		}
+		tracing::info!(peer = %peer, node_id = %peer_id, "Subscribed to gossip peer");
+
+		let mut reputation_ticker = tokio::time::interval(Duration::from_secs(
+			self.config.reputation_broadcast_interval_seconds,
+		));
+		loop {
+			tokio::select! {
+				line = lines.next_line() => {
+					let Some(line) = line? else {
+						return Ok(());
+					};
+					match serde_json::from_str::<GossipMessage>(&line) {
+						// Peers only send what they discovered or received themselves
+						Ok(GossipMessage::Intent { origin, .. } | GossipMessage::Cancellation { origin, .. })
+							if origin != peer_id =>
+						{
+							tracing::warn!(peer = %peer, origin = %origin, "Ignoring gossip message relayed from another replica");
+						},
+						Ok(GossipMessage::Intent {
+							origin,
+							sequence,
+							intent,
+							assignee,
+							..
+						}) => {
+							let intent_id = intent.id.clone();
+							let assigned = assignee.as_deref() == Some(self.node_id.as_str());
+							self.receive(origin, sequence, intent, assignee).await;
+							if assigned {
+								write_message(&mut writer, &GossipMessage::Ack { intent_id }).await?;
+							}
+						},
+						Ok(GossipMessage::Cancellation {
+							origin,
+							sequence,
+							cancellation,
+							..
+						}) => {
+							if self.advance_sequence(&origin, sequence) {
+								tracing::debug!(order_id = %cancellation.order_id, origin = %origin, "Received gossiped cancellation");
+								let _ = cancellations.send(cancellation);
+							}
+						},
+						Ok(GossipMessage::Reputation { node_id, score }) => {
+							self.receive_reputation(&peer_id, node_id, score)
+						},
+						Ok(_) => {},
+						Err(e) => tracing::warn!(peer = %peer, error = %e, "Invalid gossip message"),
+					}
+				},
+				_ = reputation_ticker.tick() => {
+					write_message(&mut writer, &self.reputation_message()).await?;
+				},
+			}
+		}
+	}
+
+	/// Records the sequence of a message received from `origin`, returning
+	/// false if the message was already received.
+	fn advance_sequence(&self, origin: &str, sequence: u64) -> bool {
+		let mut received = self.received.lock().unwrap_or_else(|e| e.into_inner());
+		let last = received
+			.last_sequences
+			.entry(origin.to_string())
+			.or_insert(0);
+		if sequence <= *last {
+			return false;
+		}
+		if sequence > *last + 1 && *last > 0 {
+			tracing::warn!(
+				origin = %origin,
+				missed = sequence - *last - 1,
+				"Gossip messages missed beyond the replay window"
+			);
+		}
+		*last = sequence;
+		true
+	}
+
+	/// Forwards an intent received from a peer unless it was already processed
+	/// or assigned to another replica.
+	async fn receive(
+		&self,
+		origin: String,
+		sequence: u64,
+		intent: Intent,
+		assignee: Option<String>,
+	) {
+		if !self.advance_sequence(&origin, sequence) {
+			return;
+		}
+		if !self
+			.received
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.mark_seen(&intent.id)
+		{
+			return;
+		}
+
+		if assignee
+			.as_ref()
+			.is_some_and(|assignee| *assignee != self.node_id)
+		{
+			tracing::debug!(intent_id = %intent.id, origin = %origin, "Gossiped intent assigned to another replica");
+			return;
+		}
+
+		match self
+			.storage
+			.exists(StorageKey::Intents.as_str(), &intent.id)
+			.await
+		{
+			Ok(true) => return,
+			Ok(false) => {},
+			Err(e) => tracing::debug!(error = %e, "Failed to check gossiped intent"),
+		}
+
+		tracing::debug!(intent_id = %intent.id, origin = %origin, "Received gossiped intent");
+		self.forward_local(&intent);
+	}
+}
+
+/// Drops logged messages older than `cutoff`.
+fn prune_log(messages: &mut VecDeque<GossipMessage>, cutoff: u64) {
+	while messages
+		.front()
+		.is_some_and(|message| message_timestamp(message) < cutoff)
+	{
+		messages.pop_front();
+	}
+}
+
+fn message_sequence(message: &GossipMessage) -> u64 {
+	match message {
+		GossipMessage::Intent { sequence, .. } | GossipMessage::Cancellation { sequence, .. } => {
+			*sequence
+		},
+		_ => 0,
+	}
+}
+
+fn message_timestamp(message: &GossipMessage) -> u64 {
+	match message {
+		GossipMessage::Intent { timestamp, .. } | GossipMessage::Cancellation { timestamp, .. } => {
+			*timestamp
+		},
+		_ => 0,
+	}
+}
+
+/// Returns a random challenge for the subscription handshake.
+fn new_nonce() -> String {
+	uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Returns the error of a failed subscription handshake.
+fn invalid_handshake(reason: &str) -> std::io::Error {
+	std::io::Error::new(
+		std::io::ErrorKind::PermissionDenied,
+		format!("Gossip handshake failed: {}", reason),
+	)
+}
+
+/// Reads a handshake message, failing if the peer does not send one in time.
+///
+/// Returns None if the connection closed or the message is invalid.
+async fn read_message(
+	lines: &mut tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
+) -> std::io::Result<Option<GossipMessage>> {
+	let line = tokio::time::timeout(HANDSHAKE_TIMEOUT, lines.next_line())
+		.await
+		.map_err(|_| {
+			std::io::Error::new(std::io::ErrorKind::TimedOut, "Gossip handshake timed out")
+		})??;
+	Ok(line.and_then(|line| serde_json::from_str(&line).ok()))
+}
+
+/// Writes a message as a single line of JSON.
+async fn write_message(
+	writer: &mut (impl AsyncWriteExt + Unpin),
+	message: &GossipMessage,
+) -> std::io::Result<()> {
+	let mut line = serde_json::to_vec(message)?;
+	line.push(b'\n');
+	writer.write_all(&line).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	fn test_intent(id: &str) -> Intent {
+		Intent {
+			id: id.to_string(),
+			source: "test".to_string(),
+			standard: "eip7683".to_string(),
+			metadata: solver_types::IntentMetadata {
+				requires_auction: false,
+				exclusive_until: None,
+				discovered_at: 0,
+				tokens: vec![],
+				origin_block_number: None,
+				origin_block_timestamp: None,
+			},
+			data: serde_json::json!({}),
+			quote_id: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_late_subscriber_receives_replay() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let address = listener.local_addr().unwrap().to_string();
+		drop(listener);
+
+		let config = GossipConfig {
+			enabled: true,
+			listen_address: address.clone(),
+			peers: vec![],
+			replay_window_seconds: 300,
+			reputation_broadcast_interval_seconds: 30,
+			shared_secret: Some("secret".to_string()),
+			assignment_ack_timeout_seconds: 10,
+		};
+		let origin = GossipService::new(
+			"a",
+			config.clone(),
+			storage.clone(),
+			Arc::new(ReputationService::new(30)),
+		);
+		let origin_handles = origin.spawn(vec![], mpsc::unbounded_channel().0);
+		origin.broadcast(&test_intent("first"));
+		// Let the listener start before the replica connects
+		tokio::time::sleep(Duration::from_millis(100)).await;
+
+		let replica = GossipService::new(
+			"b",
+			GossipConfig {
+				listen_address: "127.0.0.1:0".to_string(),
+				peers: vec![address],
+				..config
+			},
+			storage,
+			Arc::new(ReputationService::new(30)),
+		);
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let (cancel_tx, mut cancel_rx) = mpsc::unbounded_channel();
+		let replica_handles = replica.spawn(vec![tx], cancel_tx);
+
+		// The intent broadcast before subscribing is replayed once
+		let intent = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(intent.id, "first");
+
+		origin.broadcast(&test_intent("second"));
+		let intent = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(intent.id, "second");
+
+		// Cancellations are sequenced with the intents
+		let cancellation = OrderCancellation {
+			order_id: "second".to_string(),
+			signature: "0x00".to_string(),
+			timestamp: 0,
+		};
+		origin.broadcast_cancellation(&cancellation);
+		let received = tokio::time::timeout(Duration::from_secs(5), cancel_rx.recv())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(received, cancellation);
+
+		for handle in origin_handles.into_iter().chain(replica_handles) {
+			handle.abort();
+		}
+	}
+	#[tokio::test]
+	async fn test_subscriber_with_wrong_secret_is_rejected() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let address = listener.local_addr().unwrap().to_string();
+		drop(listener);
+
+		let config = GossipConfig {
+			enabled: true,
+			listen_address: address.clone(),
+			peers: vec![],
+			replay_window_seconds: 300,
+			reputation_broadcast_interval_seconds: 30,
+			shared_secret: Some("secret".to_string()),
+			assignment_ack_timeout_seconds: 10,
+		};
+		let origin = GossipService::new(
+			"a",
+			config.clone(),
+			storage.clone(),
+			Arc::new(ReputationService::new(30)),
+		);
+		let origin_handles = origin.spawn(vec![], mpsc::unbounded_channel().0);
+		origin.broadcast(&test_intent("first"));
+		tokio::time::sleep(Duration::from_millis(100)).await;
+
+		let replica = GossipService::new(
+			"b",
+			GossipConfig {
+				listen_address: "127.0.0.1:0".to_string(),
+				peers: vec![address],
+				shared_secret: Some("other".to_string()),
+				..config
+			},
+			storage,
+			Arc::new(ReputationService::new(30)),
+		);
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let replica_handles = replica.spawn(vec![tx], mpsc::unbounded_channel().0);
+
+		assert!(tokio::time::timeout(Duration::from_millis(500), rx.recv())
+			.await
+			.is_err());
+
+		for handle in origin_handles.into_iter().chain(replica_handles) {
+			handle.abort();
+		}
+	}
+
+	#[tokio::test]
+	async fn test_stopping_closes_subscriber_connections() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let address = listener.local_addr().unwrap().to_string();
+		drop(listener);
+
+		let config = GossipConfig {
+			enabled: true,
+			listen_address: address.clone(),
+			peers: vec![],
+			replay_window_seconds: 300,
+			reputation_broadcast_interval_seconds: 30,
+			shared_secret: Some("secret".to_string()),
+			assignment_ack_timeout_seconds: 10,
+		};
+		let origin = GossipService::new("a", config, storage, Arc::new(ReputationService::new(30)));
+		let handles = origin.spawn(vec![], mpsc::unbounded_channel().0);
+		tokio::time::sleep(Duration::from_millis(100)).await;
+
+		let stream = TcpStream::connect(&address).await.unwrap();
+		let mut lines = BufReader::new(stream).lines();
+		let challenge = lines.next_line().await.unwrap().unwrap();
+		assert!(challenge.contains("challenge"));
+
+		for handle in handles {
+			handle.abort();
+		}
+		let closed = tokio::time::timeout(Duration::from_secs(5), lines.next_line())
+			.await
+			.unwrap();
+		assert!(matches!(closed, Ok(None) | Err(_)));
+	}
+}
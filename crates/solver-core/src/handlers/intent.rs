@@ -3,20 +3,38 @@
 //! Responsible for validating intents, creating orders, storing them,
 //! and determining execution strategy through the order service.
 
-use crate::engine::{context::ContextBuilder, event_bus::EventBus, token_manager::TokenManager};
-use crate::state::OrderStateMachine;
-use solver_config::Config;
+use crate::auction::{profit_bps, AuctionCoordinator, AuctionError};
+use crate::engine::preview::{order_value_usd, token_value_usd};
+use crate::engine::{
+	context::ContextBuilder,
+	event_bus::EventBus,
+	token_manager::{TokenDiscovery, TokenManager},
+};
+use crate::gas_budget::{budget_exhausted, GasBudgetManager};
+use crate::handlers::order::DeliverySubmitter;
+use crate::inventory::InventoryManager;
+use crate::state::{quote::record_quote_fill, OrderStateMachine};
+use alloy_primitives::{hex, Address as AlloyAddress};
+use glob::{MatchOptions, Pattern};
+use solver_config::{Config, IntentFilterRule, IntentFiltersConfig};
 use solver_delivery::DeliveryService;
-use solver_order::OrderService;
-use solver_storage::StorageService;
+use solver_order::nonce_coordinator::{NonceCoordinator, NonceLock};
+use solver_order::{OrderService, TransactionSimulator};
+use solver_price::PriceService;
+use solver_storage::{StorageIndexes, StorageService};
 use solver_types::{
-	truncate_id, Address, DiscoveryEvent, ExecutionDecision, Intent, OrderEvent, SolverEvent,
+	current_timestamp, truncate_id, with_0x_prefix, Address, DiscoveryEvent, Eip7683OrderData,
+	ExecutionDecision, ExecutionParams, Intent, Order, OrderEvent, OrderStatus, SolverEvent,
 	StorageKey,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tracing::instrument;
 
+/// Delay before retrying an order whose order nonce is locked by another order.
+const NONCE_LOCK_DEFER: Duration = Duration::from_secs(30);
+
 /// Errors that can occur during intent processing.
 ///
 /// These errors represent failures in validating intents,
@@ -44,6 +62,14 @@ pub struct IntentHandler {
 	delivery: Arc<DeliveryService>,
 	solver_address: Address,
 	token_manager: Arc<TokenManager>,
+	gas_budget: Arc<GasBudgetManager>,
+	inventory: Arc<InventoryManager>,
+	nonces: Arc<NonceCoordinator>,
+	price: Option<Arc<PriceService>>,
+	token_discovery: Arc<TokenDiscovery>,
+	auction: Option<AuctionCoordinator>,
+	deadline_enforcer: FillDeadlineEnforcer,
+	filter: IntentFilter,
 	config: Config,
 }
 
@@ -57,8 +83,18 @@ impl IntentHandler {
 		delivery: Arc<DeliveryService>,
 		solver_address: Address,
 		token_manager: Arc<TokenManager>,
+		gas_budget: Arc<GasBudgetManager>,
+		inventory: Arc<InventoryManager>,
+		nonces: Arc<NonceCoordinator>,
+		price: Option<Arc<PriceService>>,
+		token_discovery: Arc<TokenDiscovery>,
 		config: Config,
 	) -> Self {
+		let auction = config
+			.auction
+			.clone()
+			.map(|auction_config| AuctionCoordinator::new(auction_config, storage.clone()));
+
 		Self {
 			order_service,
 			storage,
@@ -67,6 +103,14 @@ impl IntentHandler {
 			delivery,
 			solver_address,
 			token_manager,
+			gas_budget,
+			inventory,
+			nonces,
+			price,
+			token_discovery,
+			auction,
+			deadline_enforcer: FillDeadlineEnforcer::new(config.order.min_fill_window_seconds),
+			filter: IntentFilter::new(&config.discovery.filters),
 			config,
 		}
 	}
@@ -100,6 +144,30 @@ impl IntentHandler {
 
 		tracing::info!("Discovered intent");
 
+		// Reject intents that expired before they could be processed
+		if is_expired(&intent, current_timestamp()) {
+			tracing::warn!(reason = "expired", "Intent rejected");
+			self.event_bus
+				.publish(SolverEvent::Discovery(DiscoveryEvent::IntentRejected {
+					intent_id: intent.id,
+					reason: "expired".to_string(),
+				}))
+				.ok();
+			return Ok(());
+		}
+
+		// Reject token pairs and chains excluded by the discovery filters
+		if let Some(reason) = self.filter.rejection(&intent) {
+			tracing::info!(reason, "Intent rejected");
+			self.event_bus
+				.publish(SolverEvent::Discovery(DiscoveryEvent::IntentRejected {
+					intent_id: intent.id,
+					reason: reason.to_string(),
+				}))
+				.ok();
+			return Ok(());
+		}
+
 		// Validate intent
 		match self
 			.order_service
@@ -107,63 +175,50 @@ impl IntentHandler {
 			.await
 		{
 			Ok(order) => {
-				self.event_bus
-					.publish(SolverEvent::Discovery(DiscoveryEvent::IntentValidated {
-						intent_id: intent.id.clone(),
-						order: order.clone(),
-					}))
-					.ok();
-
 				// Store intent for deduplication
 				self.storage
-					.store(StorageKey::Intents.as_str(), &order.id, &intent, None)
+					.store(
+						StorageKey::Intents.as_str(),
+						&order.id,
+						&intent,
+						Some(intent_indexes(&intent)),
+					)
 					.await
 					.map_err(|e| IntentError::Storage(e.to_string()))?;
 
-				// Store order
-				self.state_machine
-					.store_order(&order)
+				// Store order, unless a concurrent discovery already stored it
+				let inserted = self
+					.state_machine
+					.store_order_if_absent(&order)
 					.await
 					.map_err(|e| IntentError::Storage(e.to_string()))?;
-
-				// Check execution strategy
-				let builder = ContextBuilder::new(
-					self.delivery.clone(),
-					self.solver_address.clone(),
-					self.token_manager.clone(),
-					self.config.clone(),
-				);
-				let context = builder
-					.build_execution_context(&intent)
-					.await
-					.map_err(|e| IntentError::Service(e.to_string()))?;
-				match self.order_service.should_execute(&order, &context).await {
-					ExecutionDecision::Execute(params) => {
-						self.event_bus
-							.publish(SolverEvent::Order(OrderEvent::Preparing {
-								intent: intent.clone(),
-								order,
-								params,
-							}))
-							.ok();
-					},
-					ExecutionDecision::Skip(reason) => {
-						self.event_bus
-							.publish(SolverEvent::Order(OrderEvent::Skipped {
-								order_id: order.id,
-								reason,
-							}))
-							.ok();
-					},
-					ExecutionDecision::Defer(duration) => {
-						self.event_bus
-							.publish(SolverEvent::Order(OrderEvent::Deferred {
-								order_id: order.id,
-								retry_after: duration,
-							}))
-							.ok();
-					},
+				if !inserted {
+					let existing = self
+						.state_machine
+						.get_order(&order.id)
+						.await
+						.map_err(|e| IntentError::Storage(e.to_string()))?;
+					tracing::debug!(
+						status = %existing.status,
+						"Order already stored by another discovery source, skipping"
+					);
+					return Ok(());
 				}
+
+				// Unconfigured tokens of the order need their metadata to be
+				// formatted and priced
+				self.token_discovery
+					.discover(&self.config.networks, std::slice::from_ref(&order))
+					.await;
+
+				self.event_bus
+					.publish(SolverEvent::Discovery(DiscoveryEvent::IntentValidated {
+						intent_id: intent.id.clone(),
+						order: order.clone(),
+					}))
+					.ok();
+
+				self.evaluate(&intent, order).await?;
 			},
 			Err(e) => {
 				tracing::warn!(
@@ -181,4 +236,666 @@ impl IntentHandler {
 
 		Ok(())
 	}
+
+	/// Re-evaluates a deferred order whose execution has not started yet.
+	///
+	/// Orders that have progressed since they were deferred, or whose intent
+	/// expired in the meantime, are left alone.
+	#[instrument(skip_all, fields(order_id = %truncate_id(order_id)))]
+	pub async fn reevaluate(&self, order_id: &str) -> Result<(), IntentError> {
+		let order = self
+			.state_machine
+			.get_order(order_id)
+			.await
+			.map_err(|e| IntentError::Storage(e.to_string()))?;
+		if order.status != OrderStatus::Created {
+			tracing::debug!(status = %order.status, "Deferred order already progressed");
+			return Ok(());
+		}
+
+		let intent: Intent = self
+			.storage
+			.retrieve(StorageKey::Intents.as_str(), order_id)
+			.await
+			.map_err(|e| IntentError::Storage(e.to_string()))?;
+		if is_expired(&intent, current_timestamp()) {
+			self.event_bus
+				.publish(SolverEvent::Order(OrderEvent::Skipped {
+					order_id: order.id,
+					reason: "expired".to_string(),
+				}))
+				.ok();
+			return Ok(());
+		}
+
+		self.evaluate(&intent, order).await
+	}
+
+	/// Asks the execution strategy whether to execute a stored order and
+	/// publishes the resulting order event.
+	async fn evaluate(&self, intent: &Intent, order: Order) -> Result<(), IntentError> {
+		// Check execution strategy
+		let builder = ContextBuilder::new(
+			self.delivery.clone(),
+			self.solver_address.clone(),
+			self.token_manager.clone(),
+			self.gas_budget.clone(),
+			self.config.clone(),
+		);
+		let context = builder
+			.build_execution_context(intent)
+			.await
+			.map_err(|e| IntentError::Service(e.to_string()))?;
+		let order_chains: Vec<u64> = order
+			.input_chain_ids
+			.iter()
+			.chain(&order.output_chain_ids)
+			.copied()
+			.collect();
+		let submitter = DeliverySubmitter {
+			delivery: &self.delivery,
+			storage: &self.storage,
+			state_machine: &self.state_machine,
+			event_bus: &self.event_bus,
+		};
+		let decision = if budget_exhausted(&context.gas_budget_remaining, &order_chains) {
+			ExecutionDecision::Skip("gas budget exceeded".to_string())
+		} else {
+			let decision = self
+				.order_service
+				.should_execute(&order, &context, &submitter)
+				.await;
+			match self
+				.deadline_enforcer
+				.enforce(&order, decision, current_timestamp())
+			{
+				ExecutionDecision::Execute(params)
+					if !params.output_source.is_solver_inventory() =>
+				{
+					match self.unprofitable_swap(&order, &params, &submitter).await {
+						Some(reason) => ExecutionDecision::Skip(reason),
+						None => ExecutionDecision::Execute(params),
+					}
+				},
+				decision => decision,
+			}
+		};
+		match decision {
+			ExecutionDecision::Execute(params) => {
+				if intent.metadata.requires_auction {
+					if let Some(auction) = &self.auction {
+						let decision = async {
+							let profit_bps = self.order_profit_bps(&order).await?;
+							auction
+								.decide(&order, &self.solver_address, profit_bps)
+								.await
+						};
+						let won = match decision.await {
+							Ok(won) => won,
+							Err(e) => {
+								tracing::warn!(error = %e, "Auction failed");
+								false
+							},
+						};
+						if !won {
+							self.event_bus
+								.publish(SolverEvent::Order(OrderEvent::Deferred {
+									order_id: order.id,
+									retry_after: auction.wait_duration(),
+								}))
+								.ok();
+							return Ok(());
+						}
+					}
+				}
+
+				// Orders opened on behalf of a user are serialized per order nonce
+				if intent.source == "off-chain" {
+					match self.nonces.lock(&order, &submitter).await {
+						Ok(NonceLock::Acquired) => {
+							tracing::debug!("Locked order nonce");
+						},
+						Ok(NonceLock::Held(holder)) => {
+							tracing::debug!(holder = %truncate_id(&holder), "Order nonce locked by another order");
+							self.event_bus
+								.publish(SolverEvent::Order(OrderEvent::Deferred {
+									order_id: order.id,
+									retry_after: NONCE_LOCK_DEFER,
+								}))
+								.ok();
+							return Ok(());
+						},
+						Ok(NonceLock::Consumed) => {
+							self.event_bus
+								.publish(SolverEvent::Order(OrderEvent::Skipped {
+									order_id: order.id,
+									reason: "order nonce already used".to_string(),
+								}))
+								.ok();
+							return Ok(());
+						},
+						Err(e) => {
+							tracing::warn!(error = %e, "Failed to lock order nonce");
+							self.event_bus
+								.publish(SolverEvent::Order(OrderEvent::Skipped {
+									order_id: order.id,
+									reason: "order nonce unavailable".to_string(),
+								}))
+								.ok();
+							return Ok(());
+						},
+					}
+				}
+
+				// Hold back the output tokens until the fill confirms or fails
+				if let Err(e) = self.inventory.reserve_outputs(&order).await {
+					tracing::warn!(error = %e, "Inventory reservation failed");
+					if let Err(e) = self.nonces.unlock(&order).await {
+						tracing::warn!(error = %e, "Failed to unlock order nonce");
+					}
+					self.event_bus
+						.publish(SolverEvent::Order(OrderEvent::Skipped {
+							order_id: order.id,
+							reason: "insufficient inventory".to_string(),
+						}))
+						.ok();
+					return Ok(());
+				}
+
+				// Each quote can only be filled the configured number of times
+				if let Some(quote_id) = &order.quote_id {
+					let max_fills = self
+						.config
+						.api
+						.as_ref()
+						.map_or(1, |api| api.quote_max_fills);
+					let skip_reason = match record_quote_fill(
+						&self.storage,
+						quote_id,
+						&order.id,
+						max_fills,
+					)
+					.await
+					{
+						Ok(Some(_)) => None,
+						Ok(None) => {
+							tracing::info!(quote_id = %quote_id, "Quote fill limit reached");
+							Some("quote fill limit reached")
+						},
+						// Without a recorded fill the limit cannot be enforced
+						Err(e) => {
+							tracing::warn!(quote_id = %quote_id, error = %e, "Failed to record quote fill");
+							Some("quote fill unavailable")
+						},
+					};
+					if let Some(reason) = skip_reason {
+						self.inventory.release(&order.id).await;
+						if let Err(e) = self.nonces.unlock(&order).await {
+							tracing::warn!(error = %e, "Failed to unlock order nonce");
+						}
+						self.event_bus
+							.publish(SolverEvent::Order(OrderEvent::Skipped {
+								order_id: order.id,
+								reason: reason.to_string(),
+							}))
+							.ok();
+						return Ok(());
+					}
+				}
+
+				self.event_bus
+					.publish(SolverEvent::Order(OrderEvent::Preparing {
+						intent: intent.clone(),
+						order,
+						params,
+					}))
+					.ok();
+			},
+			ExecutionDecision::Skip(reason) => {
+				self.event_bus
+					.publish(SolverEvent::Order(OrderEvent::Skipped {
+						order_id: order.id,
+						reason,
+					}))
+					.ok();
+			},
+			ExecutionDecision::Defer(duration) => {
+				self.event_bus
+					.publish(SolverEvent::Order(OrderEvent::Deferred {
+						order_id: order.id,
+						retry_after: duration,
+					}))
+					.ok();
+			},
+		}
+
+		Ok(())
+	}
+
+	/// Returns the profit of filling an order in basis points of the USD
+	/// value of its inputs, as bid in auctions.
+	async fn order_profit_bps(&self, order: &Order) -> Result<i64, AuctionError> {
+		let price = self.price.as_ref().ok_or_else(|| {
+			AuctionError::Pricing("auction bids require a price feed".to_string())
+		})?;
+		let (input_usd, output_usd) =
+			order_value_usd(price, &self.token_discovery, &self.config.networks, order)
+				.await
+				.map_err(|e| AuctionError::Pricing(e.to_string()))?;
+		Ok(profit_bps(input_usd, output_usd))
+	}
+
+	/// Returns why buying the outputs of an order on a DEX is not worth it,
+	/// if it is not.
+	///
+	/// The quoted cost of the swap and the outputs paid from the solver's
+	/// balance must be worth less than the inputs of the order.
+	async fn unprofitable_swap(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
+	) -> Option<String> {
+		let Some(price) = &self.price else {
+			return Some("output swap requires a price feed".to_string());
+		};
+		let quote = match self
+			.order_service
+			.quote_output_swap(order, params, simulator)
+			.await
+		{
+			Ok(Some(quote)) => quote,
+			Ok(None) => return Some("output swap not supported".to_string()),
+			Err(e) => return Some(format!("output swap quote failed: {}", e)),
+		};
+		let Ok(order_data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+			return Some("output swap requires EIP-7683 order data".to_string());
+		};
+		let value_usd = |chain_id, token, amount| {
+			token_value_usd(
+				price,
+				&self.token_discovery,
+				&self.config.networks,
+				chain_id,
+				token,
+				amount,
+			)
+		};
+
+		let result: Result<(f64, f64), String> = async {
+			let origin_chain = u64::try_from(order_data.origin_chain_id)
+				.map_err(|_| "invalid origin chain".to_string())?;
+			let mut input_usd = 0.0;
+			for [token, amount] in &order_data.inputs {
+				let token = AlloyAddress::from_slice(&token.to_be_bytes::<32>()[12..]);
+				input_usd += value_usd(origin_chain, token, *amount)
+					.await
+					.map_err(|e| e.to_string())?;
+			}
+
+			let mut cost_usd = value_usd(quote.chain_id, quote.funding_token, quote.amount_in_max)
+				.await
+				.map_err(|e| e.to_string())?;
+			// The swap buys the first cross-chain output, other outputs are
+			// paid from the solver's balance
+			let swapped = order_data
+				.outputs
+				.iter()
+				.position(|o| o.chain_id != order_data.origin_chain_id);
+			for (index, output) in order_data.outputs.iter().enumerate() {
+				if Some(index) == swapped {
+					continue;
+				}
+				let chain_id = u64::try_from(output.chain_id)
+					.map_err(|_| "invalid output chain".to_string())?;
+				let token = AlloyAddress::from_slice(&output.token[12..]);
+				cost_usd += value_usd(chain_id, token, output.amount)
+					.await
+					.map_err(|e| e.to_string())?;
+			}
+			Ok((input_usd, cost_usd))
+		}
+		.await;
+
+		match result {
+			Ok((input_usd, cost_usd)) if input_usd > cost_usd => None,
+			Ok((input_usd, cost_usd)) => {
+				tracing::info!(
+					input_usd,
+					cost_usd,
+					"Output swap costs more than the inputs"
+				);
+				Some("output swap unprofitable".to_string())
+			},
+			Err(e) => Some(format!("output swap cannot be priced: {}", e)),
+		}
+	}
+}
+
+/// Skips orders whose fill deadline is too close to fill them in time.
+///
+/// Applied once the strategy decided to execute an order, so that the fill
+/// and its confirmation fit in the time left before the fill deadline.
+pub struct FillDeadlineEnforcer {
+	/// Minimum seconds left before the fill deadline.
+	min_fill_window_seconds: u64,
+}
+
+impl FillDeadlineEnforcer {
+	/// Creates a new FillDeadlineEnforcer requiring the given window.
+	pub fn new(min_fill_window_seconds: u64) -> Self {
+		Self {
+			min_fill_window_seconds,
+		}
+	}
+
+	/// Returns the decision to take on an order, replacing a decision to
+	/// execute it by a skip if its fill deadline is too close at `now`.
+	pub fn enforce(
+		&self,
+		order: &Order,
+		decision: ExecutionDecision,
+		now: u64,
+	) -> ExecutionDecision {
+		match decision {
+			ExecutionDecision::Execute(_) if !self.allows(&order.data, now) => {
+				ExecutionDecision::Skip("fill deadline too close".to_string())
+			},
+			decision => decision,
+		}
+	}
+
+	/// Returns whether enough time is left to fill an order with the given data.
+	///
+	/// Orders whose data has no EIP-7683 fill deadline are always allowed.
+	fn allows(&self, order_data: &serde_json::Value, now: u64) -> bool {
+		order_data_fill_deadline(order_data)
+			.is_none_or(|deadline| deadline > now && deadline - now >= self.min_fill_window_seconds)
+	}
+}
+
+/// Filter rejecting intents by token pair and chains before validation.
+pub struct IntentFilter {
+	blacklist: Vec<FilterRule>,
+	whitelist: Vec<FilterRule>,
+}
+
+/// Compiled patterns of an [`IntentFilterRule`].
+struct FilterRule([Pattern; 4]);
+
+impl FilterRule {
+	/// Compiles a configured rule. Patterns are validated with the
+	/// configuration, invalid ones match nothing.
+	fn new(rule: &IntentFilterRule) -> Self {
+		Self(
+			rule.patterns()
+				.map(|pattern| Pattern::new(pattern).unwrap_or_default()),
+		)
+	}
+
+	/// Returns whether the rule matches an input and output of an intent.
+	fn matches(&self, leg: &IntentLeg) -> bool {
+		let options = MatchOptions {
+			case_sensitive: false,
+			..MatchOptions::default()
+		};
+		self.0
+			.iter()
+			.zip(leg)
+			.all(|(pattern, value)| pattern.matches_with(value, options))
+	}
+}
+
+/// Input token, output token, input chain and output chain of an intent.
+type IntentLeg = [String; 4];
+
+impl IntentFilter {
+	/// Creates a new IntentFilter from the discovery filter configuration.
+	pub fn new(config: &IntentFiltersConfig) -> Self {
+		Self {
+			blacklist: config.blacklist.iter().map(FilterRule::new).collect(),
+			whitelist: config.whitelist.iter().map(FilterRule::new).collect(),
+		}
+	}
+
+	/// Returns the reason to reject an intent, or None if it passes the filter.
+	///
+	/// An intent is blacklisted if any pair of its inputs and outputs matches
+	/// a blacklist rule, and whitelisted if every pair matches a whitelist
+	/// rule. Intents without EIP-7683 data are left to validation, unless a
+	/// whitelist is set, which they cannot match.
+	pub fn rejection(&self, intent: &Intent) -> Option<&'static str> {
+		if self.blacklist.is_empty() && self.whitelist.is_empty() {
+			return None;
+		}
+		let legs = intent_legs(&intent.data).filter(|legs| !legs.is_empty());
+		let Some(legs) = legs else {
+			return (!self.whitelist.is_empty()).then_some("filtered by whitelist");
+		};
+		let matches =
+			|rules: &[FilterRule], leg: &IntentLeg| rules.iter().any(|rule| rule.matches(leg));
+
+		if legs.iter().any(|leg| matches(&self.blacklist, leg)) {
+			Some("filtered by blacklist")
+		} else if !self.whitelist.is_empty()
+			&& !legs.iter().all(|leg| matches(&self.whitelist, leg))
+		{
+			Some("filtered by whitelist")
+		} else {
+			None
+		}
+	}
+}
+
+/// Returns every combination of an input and an output of EIP-7683 intent data.
+fn intent_legs(data: &serde_json::Value) -> Option<Vec<IntentLeg>> {
+	let order_data = serde_json::from_value::<Eip7683OrderData>(data.clone()).ok()?;
+	let input_chain = order_data.origin_chain_id.to_string();
+	let mut legs = Vec::new();
+	for [input_token, _] in &order_data.inputs {
+		let input_token = with_0x_prefix(&hex::encode(&input_token.to_be_bytes::<32>()[12..]));
+		for output in &order_data.outputs {
+			legs.push([
+				input_token.clone(),
+				with_0x_prefix(&hex::encode(&output.token[12..])),
+				input_chain.clone(),
+				output.chain_id.to_string(),
+			]);
+		}
+	}
+	Some(legs)
+}
+
+/// Returns the EIP-7683 fill deadline of an order, if any.
+pub(crate) fn fill_deadline(order: &Order) -> Option<u64> {
+	order_data_fill_deadline(&order.data)
+}
+
+/// Returns the EIP-7683 fill deadline of intent or order data, if any.
+fn order_data_fill_deadline(data: &serde_json::Value) -> Option<u64> {
+	serde_json::from_value::<Eip7683OrderData>(data.clone())
+		.ok()
+		.map(|order_data| u64::from(order_data.fill_deadline))
+}
+
+/// Builds the storage indexes of an intent.
+///
+/// On-chain intents are indexed by the number and timestamp of their origin
+/// block so they can be queried by block range.
+pub(crate) fn intent_indexes(intent: &Intent) -> StorageIndexes {
+	let mut indexes = StorageIndexes::new();
+	if let Some(block_number) = intent.metadata.origin_block_number {
+		indexes = indexes.with_numeric("origin_block_number", block_number);
+	}
+	if let Some(block_timestamp) = intent.metadata.origin_block_timestamp {
+		indexes = indexes.with_numeric("origin_block_timestamp", block_timestamp);
+	}
+	indexes
+}
+
+/// Returns true if the intent's order expired at or before `now`.
+///
+/// Intents whose data has no EIP-7683 expiry are left to order validation.
+fn is_expired(intent: &Intent, now: u64) -> bool {
+	serde_json::from_value::<Eip7683OrderData>(intent.data.clone())
+		.is_ok_and(|order_data| u64::from(order_data.expires) <= now)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+	use solver_storage::IndexType;
+	use solver_types::standards::eip7683::{GasLimitOverrides, MandateOutput};
+	use solver_types::IntentMetadata;
+
+	fn intent(expires: u64) -> Intent {
+		let order_data = Eip7683OrderData {
+			user: "0x0000000000000000000000000000000000000001".to_string(),
+			nonce: U256::ZERO,
+			origin_chain_id: U256::from(1),
+			expires: expires as u32,
+			fill_deadline: expires as u32,
+			input_oracle: "0x0000000000000000000000000000000000000002".to_string(),
+			inputs: vec![],
+			order_id: [0u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		};
+
+		Intent {
+			id: "intent".to_string(),
+			source: "test".to_string(),
+			standard: "eip7683".to_string(),
+			metadata: IntentMetadata {
+				requires_auction: false,
+				exclusive_until: None,
+				discovered_at: 0,
+				tokens: vec![],
+				origin_block_number: None,
+				origin_block_timestamp: None,
+			},
+			data: serde_json::to_value(order_data).unwrap(),
+			quote_id: None,
+		}
+	}
+
+	#[test]
+	fn test_fill_deadline_enforcer_requires_window() {
+		let now = current_timestamp();
+		let enforcer = FillDeadlineEnforcer::new(60);
+		assert!(enforcer.allows(&intent(now + 60).data, now));
+		assert!(!enforcer.allows(&intent(now + 30).data, now));
+		assert!(!enforcer.allows(&intent(now - 1).data, now));
+		assert!(enforcer.allows(&serde_json::json!({}), now));
+	}
+
+	#[test]
+	fn test_expired_intent_is_rejected() {
+		let now = current_timestamp();
+		assert!(is_expired(&intent(now - 1), now));
+		assert!(!is_expired(&intent(now + 60), now));
+	}
+
+	#[test]
+	fn test_intent_filter_blacklist_and_whitelist() {
+		let mut intent = intent(0);
+		let mut order_data: Eip7683OrderData = serde_json::from_value(intent.data).unwrap();
+		let mut token = [0u8; 32];
+		token[12..].copy_from_slice(&[0xaa; 20]);
+		order_data.inputs = vec![[U256::from_be_bytes(token), U256::from(100)]];
+		token[12..].copy_from_slice(&[0xbb; 20]);
+		order_data.outputs = vec![MandateOutput {
+			oracle: [0; 32],
+			settler: [0; 32],
+			chain_id: U256::from(10),
+			token,
+			amount: U256::from(99),
+			recipient: [0; 32],
+			call: vec![],
+			context: vec![],
+		}];
+		intent.data = serde_json::to_value(order_data).unwrap();
+
+		let rule = |input_token: &str, output_chain: &str| IntentFilterRule {
+			input_token: input_token.to_string(),
+			output_token: "*".to_string(),
+			input_chain: "1".to_string(),
+			output_chain: output_chain.to_string(),
+		};
+		let filter = |blacklist, whitelist| {
+			IntentFilter::new(&IntentFiltersConfig {
+				blacklist,
+				whitelist,
+			})
+		};
+
+		assert_eq!(filter(vec![], vec![]).rejection(&intent), None);
+		assert_eq!(
+			filter(vec![rule("0xAAAA*", "*")], vec![]).rejection(&intent),
+			Some("filtered by blacklist")
+		);
+		assert_eq!(
+			filter(vec![rule("*", "8453")], vec![]).rejection(&intent),
+			None
+		);
+		assert_eq!(
+			filter(vec![], vec![rule("*", "8453")]).rejection(&intent),
+			Some("filtered by whitelist")
+		);
+		assert_eq!(
+			filter(vec![], vec![rule("*", "1*")]).rejection(&intent),
+			None
+		);
+
+		// Every leg must be whitelisted
+		let mut order_data: Eip7683OrderData = serde_json::from_value(intent.data.clone()).unwrap();
+		let mut output = order_data.outputs[0].clone();
+		output.chain_id = U256::from(8453);
+		order_data.outputs.push(output);
+		let mut two_legs = intent.clone();
+		two_legs.data = serde_json::to_value(order_data).unwrap();
+		assert_eq!(
+			filter(vec![], vec![rule("*", "1*")]).rejection(&two_legs),
+			Some("filtered by whitelist")
+		);
+		assert_eq!(
+			filter(vec![], vec![rule("*", "1*"), rule("*", "8453")]).rejection(&two_legs),
+			None
+		);
+
+		// Intents that cannot be parsed only pass without a whitelist
+		let mut unparseable = intent.clone();
+		unparseable.data = serde_json::json!({});
+		assert_eq!(
+			filter(vec![rule("*", "*")], vec![]).rejection(&unparseable),
+			None
+		);
+		assert_eq!(
+			filter(vec![], vec![rule("*", "*")]).rejection(&unparseable),
+			Some("filtered by whitelist")
+		);
+	}
+
+	#[test]
+	fn test_intent_indexes_origin_block() {
+		let mut intent = intent(0);
+		assert!(intent_indexes(&intent).fields.is_empty());
+
+		intent.metadata.origin_block_number = Some(1_234);
+		intent.metadata.origin_block_timestamp = Some(1_700_000_000);
+		let indexes = intent_indexes(&intent);
+		assert!(matches!(
+			indexes.fields.get("origin_block_number"),
+			Some(IndexType::Numeric(1_234))
+		));
+		assert!(matches!(
+			indexes.fields.get("origin_block_timestamp"),
+			Some(IndexType::Numeric(1_700_000_000))
+		));
+	}
 }
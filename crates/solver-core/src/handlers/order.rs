@@ -4,15 +4,21 @@
 //! and fill transactions, updating order state and publishing appropriate events.
 
 use crate::engine::event_bus::EventBus;
+use crate::engine::token_manager::approval_transaction;
+use crate::inventory::{order_outputs, InventoryManager};
 use crate::state::OrderStateMachine;
-use alloy_primitives::hex;
-use solver_delivery::DeliveryService;
-use solver_order::OrderService;
-use solver_storage::StorageService;
+use alloy_primitives::{hex, U256};
+use async_trait::async_trait;
+use solver_delivery::{CallsSubmission, DeliveryService};
+use solver_order::nonce_coordinator::{NonceCoordinator, NonceLock};
+use solver_order::{FillCommitment, OrderService, TransactionSimulator};
+use solver_storage::{StorageError, StorageService};
 use solver_types::{
-	truncate_id, DeliveryEvent, ExecutionParams, Intent, Order, OrderEvent, OrderStatus,
-	SolverEvent, StorageKey, TransactionType,
+	truncate_id, with_0x_prefix, Address, DeliveryEvent, ExecutionParams, Intent, Order,
+	OrderEvent, OrderStatus, SolverEvent, StorageKey, Transaction, TransactionHash,
+	TransactionReceipt, TransactionTrace, TransactionType,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 use tracing::instrument;
@@ -31,6 +37,178 @@ pub enum OrderError {
 	State(String),
 }
 
+/// Adapter that simulates order transactions through the delivery service.
+pub(crate) struct DeliverySimulator<'a>(pub(crate) &'a DeliveryService);
+
+#[async_trait]
+impl TransactionSimulator for DeliverySimulator<'_> {
+	async fn simulate(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, solver_order::OrderError> {
+		self.0
+			.simulate_transaction(tx)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))
+	}
+
+	async fn get_receipt(
+		&self,
+		tx_hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<TransactionReceipt, solver_order::OrderError> {
+		self.0
+			.get_receipt(tx_hash, chain_id)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))
+	}
+
+	async fn get_transaction_count(
+		&self,
+		chain_id: u64,
+		address: &str,
+	) -> Result<u64, solver_order::OrderError> {
+		self.0
+			.get_nonce(chain_id, address)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))
+	}
+
+	async fn get_code(
+		&self,
+		chain_id: u64,
+		address: &str,
+	) -> Result<Vec<u8>, solver_order::OrderError> {
+		self.0
+			.get_code(chain_id, address)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))
+	}
+}
+
+/// Adapter that additionally lets strategies submit transactions ahead of a fill.
+///
+/// Submitted transactions are monitored and linked to their order like any
+/// other order transaction.
+pub(crate) struct DeliverySubmitter<'a> {
+	pub(crate) delivery: &'a DeliveryService,
+	pub(crate) storage: &'a StorageService,
+	pub(crate) state_machine: &'a OrderStateMachine,
+	pub(crate) event_bus: &'a EventBus,
+}
+
+#[async_trait]
+impl TransactionSimulator for DeliverySubmitter<'_> {
+	async fn simulate(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, solver_order::OrderError> {
+		DeliverySimulator(self.delivery).simulate(tx).await
+	}
+
+	async fn submit(
+		&self,
+		order_id: &str,
+		tx: Transaction,
+		tx_type: TransactionType,
+	) -> Result<TransactionHash, solver_order::OrderError> {
+		let chain_id = tx.chain_id;
+		let tx_hash = self
+			.delivery
+			.deliver(tx)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))?;
+
+		self.event_bus
+			.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+				order_id: order_id.to_string(),
+				tx_hash: tx_hash.clone(),
+				tx_type,
+				tx_chain_id: chain_id,
+			}))
+			.ok();
+
+		// Store reverse mapping: tx_hash -> order_id
+		self.storage
+			.store(
+				StorageKey::OrderByTxHash.as_str(),
+				&hex::encode(&tx_hash.0),
+				&order_id.to_string(),
+				None,
+			)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))?;
+
+		self.state_machine
+			.set_transaction_hash(order_id, tx_hash.clone(), tx_type)
+			.await
+			.map_err(|e| solver_order::OrderError::ValidationFailed(e.to_string()))?;
+
+		Ok(tx_hash)
+	}
+
+	async fn get_receipt(
+		&self,
+		tx_hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<TransactionReceipt, solver_order::OrderError> {
+		DeliverySimulator(self.delivery)
+			.get_receipt(tx_hash, chain_id)
+			.await
+	}
+
+	async fn get_transaction_count(
+		&self,
+		chain_id: u64,
+		address: &str,
+	) -> Result<u64, solver_order::OrderError> {
+		DeliverySimulator(self.delivery)
+			.get_transaction_count(chain_id, address)
+			.await
+	}
+
+	async fn get_code(
+		&self,
+		chain_id: u64,
+		address: &str,
+	) -> Result<Vec<u8>, solver_order::OrderError> {
+		DeliverySimulator(self.delivery)
+			.get_code(chain_id, address)
+			.await
+	}
+
+	async fn load_commitment(
+		&self,
+		order_id: &str,
+	) -> Result<Option<FillCommitment>, solver_order::OrderError> {
+		match self
+			.storage
+			.retrieve(StorageKey::CommitReveals.as_str(), order_id)
+			.await
+		{
+			Ok(commitment) => Ok(Some(commitment)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(solver_order::OrderError::Storage(e.to_string())),
+		}
+	}
+
+	async fn store_commitment(
+		&self,
+		order_id: &str,
+		commitment: &FillCommitment,
+	) -> Result<(), solver_order::OrderError> {
+		self.storage
+			.store(
+				StorageKey::CommitReveals.as_str(),
+				order_id,
+				commitment,
+				None,
+			)
+			.await
+			.map_err(|e| solver_order::OrderError::Storage(e.to_string()))
+	}
+}
+
 /// Handler for processing order preparation and execution.
 ///
 /// The OrderHandler manages the generation and submission of prepare
@@ -42,6 +220,8 @@ pub struct OrderHandler {
 	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
+	nonces: Arc<NonceCoordinator>,
+	inventory: Arc<InventoryManager>,
 }
 
 impl OrderHandler {
@@ -51,6 +231,8 @@ impl OrderHandler {
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
+		nonces: Arc<NonceCoordinator>,
+		inventory: Arc<InventoryManager>,
 	) -> Self {
 		Self {
 			order_service,
@@ -58,6 +240,8 @@ impl OrderHandler {
 			storage,
 			state_machine,
 			event_bus,
+			nonces,
+			inventory,
 		}
 	}
 
@@ -70,18 +254,35 @@ impl OrderHandler {
 		params: ExecutionParams,
 	) -> Result<(), OrderError> {
 		// Generate prepare transaction
-		if let Some(prepare_tx) = self
+		let prepare_tx = match self
 			.order_service
 			.generate_prepare_transaction(&intent, &order, &params)
 			.await
-			.map_err(|e| OrderError::Service(e.to_string()))?
 		{
+			Ok(prepare_tx) => prepare_tx,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order, TransactionType::Prepare, error).await);
+			},
+		};
+		if let Some(prepare_tx) = prepare_tx {
+			match self.ensure_nonce_current(&intent, &order).await {
+				Ok(true) => {},
+				Ok(false) => return Ok(()),
+				Err(e) => return Err(self.abandon(&order, TransactionType::Prepare, e).await),
+			}
+			if !self.start_execution(&order, &params).await? {
+				return Ok(());
+			}
+
 			// Submit prepare transaction
-			let prepare_tx_hash = self
-				.delivery
-				.deliver(prepare_tx.clone())
-				.await
-				.map_err(|e| OrderError::Service(e.to_string()))?;
+			let prepare_tx_hash = match self.delivery.deliver(prepare_tx.clone()).await {
+				Ok(tx_hash) => tx_hash,
+				Err(e) => {
+					let error = OrderError::Service(e.to_string());
+					return Err(self.abandon(&order, TransactionType::Prepare, error).await);
+				},
+			};
 
 			self.event_bus
 				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
@@ -103,24 +304,16 @@ impl OrderHandler {
 				.await
 				.map_err(|e| OrderError::Storage(e.to_string()))?;
 
-			// Update order with execution params and prepare tx hash
+			// Update order with prepare tx hash
 			self.state_machine
-				.update_order_with(&order.id, |o| {
-					o.execution_params = Some(params.clone());
-					o.status = OrderStatus::Pending;
-					o.prepare_tx_hash = Some(prepare_tx_hash);
-				})
+				.set_transaction_hash(&order.id, prepare_tx_hash, TransactionType::Prepare)
 				.await
 				.map_err(|e| OrderError::State(e.to_string()))?;
 		} else {
-			// No preparation needed, set execution params and proceed
-			self.state_machine
-				.update_order_with(&order.id, |o| {
-					o.execution_params = Some(params.clone());
-					o.status = OrderStatus::Pending;
-				})
-				.await
-				.map_err(|e| OrderError::State(e.to_string()))?;
+			// No preparation needed, proceed with the fill
+			if !self.start_execution(&order, &params).await? {
+				return Ok(());
+			}
 
 			self.event_bus
 				.publish(SolverEvent::Order(OrderEvent::Executing {
@@ -133,53 +326,313 @@ impl OrderHandler {
 		Ok(())
 	}
 
-	/// Handles order execution by generating and submitting a fill transaction.
-	#[instrument(skip_all, fields(order_id = %truncate_id(&order.id)))]
-	pub async fn handle_execution(
+	/// Re-validates the intent if the user's nonce changed since it was cached.
+	///
+	/// Returns false, after skipping the order, if the intent is no longer valid.
+	async fn ensure_nonce_current(
 		&self,
-		order: Order,
-		params: ExecutionParams,
-	) -> Result<(), OrderError> {
-		// Generate fill transaction
-		let tx = self
-			.order_service
-			.generate_fill_transaction(&order, &params)
+		intent: &Intent,
+		order: &Order,
+	) -> Result<bool, OrderError> {
+		let simulator = DeliverySimulator(&self.delivery);
+		if self
+			.nonces
+			.is_current(order, &simulator)
 			.await
-			.map_err(|e| OrderError::Service(e.to_string()))?;
+			.map_err(|e| OrderError::Service(e.to_string()))?
+		{
+			return Ok(true);
+		}
 
-		// Submit transaction
-		let tx_hash = self
-			.delivery
-			.deliver(tx.clone())
+		tracing::info!("Order nonce no longer available, re-validating intent");
+		let reason = match self
+			.order_service
+			.validate_intent(intent, &order.solver_address)
 			.await
-			.map_err(|e| OrderError::Service(e.to_string()))?;
+		{
+			Ok(_) => match self
+				.nonces
+				.lock(order, &simulator)
+				.await
+				.map_err(|e| OrderError::Service(e.to_string()))?
+			{
+				NonceLock::Acquired => return Ok(true),
+				NonceLock::Held(_) => "nonce locked by another order".to_string(),
+				NonceLock::Consumed => "order nonce already used".to_string(),
+			},
+			Err(e) => e.to_string(),
+		};
 
+		tracing::warn!(reason = %reason, "Intent no longer valid");
+		self.nonces
+			.unlock(order)
+			.await
+			.map_err(|e| OrderError::Storage(e.to_string()))?;
+		self.inventory.release(&order.id).await;
 		self.event_bus
-			.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+			.publish(SolverEvent::Order(OrderEvent::Skipped {
 				order_id: order.id.clone(),
-				tx_hash: tx_hash.clone(),
-				tx_type: TransactionType::Fill,
-				tx_chain_id: tx.chain_id,
+				reason,
 			}))
 			.ok();
+		Ok(false)
+	}
 
-		// Store fill transaction
-		self.state_machine
-			.set_transaction_hash(&order.id, tx_hash.clone(), TransactionType::Fill)
+	/// Marks an order as pending with its execution parameters.
+	///
+	/// Returns false if the order was cancelled in the meantime, in which case
+	/// it must not be executed.
+	async fn start_execution(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+	) -> Result<bool, OrderError> {
+		let started = self
+			.state_machine
+			.update_order_if(
+				&order.id,
+				|o| matches!(o.status, OrderStatus::Created | OrderStatus::Pending),
+				|o| {
+					o.execution_params = Some(params.clone());
+					o.status = OrderStatus::Pending;
+				},
+			)
 			.await
 			.map_err(|e| OrderError::State(e.to_string()))?;
+		if started.is_some() {
+			return Ok(true);
+		}
 
-		// Store reverse mapping: tx_hash -> order_id
-		self.storage
-			.store(
-				StorageKey::OrderByTxHash.as_str(),
-				&hex::encode(&tx_hash.0),
-				&order.id,
-				None,
-			)
+		// Release what was held for the execution after the cancellation
+		tracing::info!("Order was cancelled before its execution started");
+		self.inventory.release(&order.id).await;
+		self.nonces
+			.unlock(order)
 			.await
 			.map_err(|e| OrderError::Storage(e.to_string()))?;
+		Ok(false)
+	}
 
-		Ok(())
+	/// Handles order execution by generating and submitting a fill transaction.
+	#[instrument(skip_all, fields(order_id = %truncate_id(&order.id)))]
+	pub async fn handle_execution(
+		&self,
+		order: Order,
+		params: ExecutionParams,
+	) -> Result<(), OrderError> {
+		// Generate fill transaction
+		let tx = match self
+			.order_service
+			.generate_fill_transaction(&order, &params, &DeliverySimulator(&self.delivery))
+			.await
+		{
+			Ok(tx) => tx,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order, TransactionType::Fill, error).await);
+			},
+		};
+
+		// With call batching, missing approvals of the filled tokens are
+		// submitted together with the fill
+		let mut txs = if self.delivery.is_wallet_batch_enabled() {
+			self.missing_output_approvals(&order, &tx).await
+		} else {
+			Vec::new()
+		};
+		txs.push(tx.clone());
+
+		// Submit transaction
+		let submission = match self.delivery.deliver_calls(txs).await {
+			Ok(submission) => submission,
+			Err(e) => {
+				let error = OrderError::Service(e.to_string());
+				return Err(self.abandon(&order, TransactionType::Fill, error).await);
+			},
+		};
+		let tx_hash = match submission {
+			CallsSubmission::Transaction(tx_hash) => tx_hash,
+			CallsSubmission::Batch { batch_id, chain_id } => {
+				// The fill is linked to the order once the batch is included
+				let delivery = self.delivery.clone();
+				let storage = self.storage.clone();
+				let state_machine = self.state_machine.clone();
+				let event_bus = self.event_bus.clone();
+				let nonces = self.nonces.clone();
+				let inventory = self.inventory.clone();
+				tokio::spawn(async move {
+					let result = match delivery.wait_for_call_batch(&batch_id, chain_id).await {
+						Ok(tx_hash) => {
+							record_submission(
+								&storage,
+								&state_machine,
+								&event_bus,
+								&order.id,
+								tx_hash,
+								TransactionType::Fill,
+								chain_id,
+							)
+							.await
+						},
+						Err(e) => {
+							let error = OrderError::Service(e.to_string());
+							Err(abandon_execution(
+								&state_machine,
+								&nonces,
+								&inventory,
+								&order,
+								TransactionType::Fill,
+								error,
+							)
+							.await)
+						},
+					};
+					if let Err(e) = result {
+						tracing::error!(order_id = %truncate_id(&order.id), batch_id = %batch_id, error = %e, "Failed to track fill call batch");
+					}
+				});
+				return Ok(());
+			},
+		};
+
+		record_submission(
+			&self.storage,
+			&self.state_machine,
+			&self.event_bus,
+			&order.id,
+			tx_hash,
+			TransactionType::Fill,
+			tx.chain_id,
+		)
+		.await
 	}
+
+	/// Fails an order whose transaction could not be submitted, releasing
+	/// what was held for its execution, and returns the error.
+	async fn abandon(
+		&self,
+		order: &Order,
+		tx_type: TransactionType,
+		error: OrderError,
+	) -> OrderError {
+		abandon_execution(
+			&self.state_machine,
+			&self.nonces,
+			&self.inventory,
+			order,
+			tx_type,
+			error,
+		)
+		.await
+	}
+
+	/// Returns approval transactions for the output tokens of an order whose
+	/// allowance for the fill's target does not cover the filled amount.
+	///
+	/// Allowances that cannot be checked are assumed to be sufficient.
+	async fn missing_output_approvals(
+		&self,
+		order: &Order,
+		fill_tx: &Transaction,
+	) -> Vec<Transaction> {
+		let Some(spender) = &fill_tx.to else {
+			return Vec::new();
+		};
+		let mut amounts: HashMap<Address, U256> = HashMap::new();
+		for (chain_id, token, amount) in order_outputs(order) {
+			if chain_id == fill_tx.chain_id {
+				*amounts.entry(token).or_default() += amount;
+			}
+		}
+
+		let mut approvals = Vec::new();
+		for (token, amount) in amounts {
+			let allowance = match self
+				.delivery
+				.get_allowance(
+					fill_tx.chain_id,
+					&with_0x_prefix(&hex::encode(&order.solver_address.0)),
+					&hex::encode(&spender.0),
+					&hex::encode(&token.0),
+				)
+				.await
+			{
+				Ok(allowance) => U256::from_str_radix(&allowance, 10).unwrap_or_default(),
+				Err(e) => {
+					tracing::warn!(error = %e, "Failed to check allowance of output token");
+					continue;
+				},
+			};
+			if allowance < amount {
+				approvals.push(approval_transaction(
+					fill_tx.chain_id,
+					&token,
+					spender,
+					U256::MAX,
+				));
+			}
+		}
+		approvals
+	}
+}
+
+/// Fails an order whose transaction could not be submitted, releasing its
+/// inventory reservations and order nonce, and returns the error.
+async fn abandon_execution(
+	state_machine: &OrderStateMachine,
+	nonces: &NonceCoordinator,
+	inventory: &InventoryManager,
+	order: &Order,
+	tx_type: TransactionType,
+	error: OrderError,
+) -> OrderError {
+	tracing::warn!(order_id = %truncate_id(&order.id), tx_type = ?tx_type, error = %error, "Order transaction not submitted, failing order");
+	inventory.release(&order.id).await;
+	if let Err(e) = nonces.unlock(order).await {
+		tracing::warn!(error = %e, "Failed to unlock order nonce");
+	}
+	if let Err(e) = state_machine
+		.transition_order_status(&order.id, OrderStatus::Failed(tx_type))
+		.await
+	{
+		tracing::warn!(order_id = %truncate_id(&order.id), error = %e, "Failed to mark order as failed");
+	}
+	error
+}
+
+/// Publishes a submitted order transaction and links it to its order.
+async fn record_submission(
+	storage: &StorageService,
+	state_machine: &OrderStateMachine,
+	event_bus: &EventBus,
+	order_id: &str,
+	tx_hash: TransactionHash,
+	tx_type: TransactionType,
+	tx_chain_id: u64,
+) -> Result<(), OrderError> {
+	event_bus
+		.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+			order_id: order_id.to_string(),
+			tx_hash: tx_hash.clone(),
+			tx_type,
+			tx_chain_id,
+		}))
+		.ok();
+
+	// Store fill transaction
+	state_machine
+		.set_transaction_hash(order_id, tx_hash.clone(), tx_type)
+		.await
+		.map_err(|e| OrderError::State(e.to_string()))?;
+
+	// Store reverse mapping: tx_hash -> order_id
+	storage
+		.store(
+			StorageKey::OrderByTxHash.as_str(),
+			&hex::encode(&tx_hash.0),
+			&order_id.to_string(),
+			None,
+		)
+		.await
+		.map_err(|e| OrderError::Storage(e.to_string()))
 }
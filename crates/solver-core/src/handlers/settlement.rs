@@ -3,16 +3,28 @@
 //! Manages the batch processing of orders ready for claiming, generating
 //! claim transactions and submitting them through the delivery service.
 
+use crate::batch_size::BatchSizeOptimizer;
 use crate::engine::event_bus::EventBus;
+use crate::state::dead_letter::{push_dead_letter, take_dead_letter, DeadLetter};
 use crate::state::OrderStateMachine;
 use alloy_primitives::hex;
+use async_trait::async_trait;
+use futures::future::join_all;
 use solver_delivery::DeliveryService;
 use solver_order::OrderService;
-use solver_settlement::SettlementService;
+use solver_settlement::{GasEstimator, SettlementService};
 use solver_storage::StorageService;
-use solver_types::{DeliveryEvent, Order, SolverEvent, StorageKey, TransactionType};
-use std::sync::Arc;
+use solver_types::{
+	current_timestamp, truncate_id, DeliveryEvent, FillProof, Order, OrderStatus, SettlementEvent,
+	SolverEvent, StorageKey, Transaction, TransactionHash, TransactionReceipt, TransactionTrace,
+	TransactionType,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::Semaphore;
 use tracing::instrument;
 
 /// Errors that can occur during settlement processing.
@@ -29,22 +41,51 @@ pub enum SettlementError {
 	State(String),
 }
 
+/// Delay before a claim whose gas could not be estimated is queued again.
+const CLAIM_ESTIMATE_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Number of failed gas estimations after which a claim is set aside.
+const MAX_CLAIM_ESTIMATE_RETRIES: u32 = 5;
+
+/// Gas estimator checking claims through the delivery service.
+struct DeliveryGasEstimator<'a>(&'a DeliveryService);
+
+#[async_trait]
+impl GasEstimator for DeliveryGasEstimator<'_> {
+	async fn estimate_gas(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, solver_settlement::SettlementError> {
+		self.0
+			.simulate_transaction(tx)
+			.await
+			.map_err(|e| solver_settlement::SettlementError::Network(e.to_string()))
+	}
+}
+
 /// Handler for processing settlement claim operations.
 ///
 /// The SettlementHandler manages batch processing of orders ready for claiming,
 /// generating claim transactions and submitting them through the delivery service
 /// to complete the settlement lifecycle.
 pub struct SettlementHandler {
-	#[allow(dead_code)]
 	settlement: Arc<SettlementService>,
 	order_service: Arc<OrderService>,
 	delivery: Arc<DeliveryService>,
 	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
+	batch_optimizer: BatchSizeOptimizer,
+	/// Maximum number of settlement implementations claimed concurrently.
+	max_claim_groups_parallel: usize,
+	/// Failed gas estimations of claims queued again, by order ID.
+	estimate_retries: Mutex<HashMap<String, u32>>,
+	/// Number of claims carried by submitted claim transactions, by hash.
+	claim_txs: Arc<Mutex<HashMap<String, usize>>>,
 }
 
 impl SettlementHandler {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		settlement: Arc<SettlementService>,
 		order_service: Arc<OrderService>,
@@ -52,6 +93,8 @@ impl SettlementHandler {
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
+		batch_optimizer: BatchSizeOptimizer,
+		max_claim_groups_parallel: usize,
 	) -> Self {
 		Self {
 			settlement,
@@ -60,29 +103,197 @@ impl SettlementHandler {
 			storage,
 			state_machine,
 			event_bus,
+			batch_optimizer,
+			max_claim_groups_parallel,
+			estimate_retries: Mutex::new(HashMap::new()),
+			claim_txs: Arc::new(Mutex::new(HashMap::new())),
+		}
+	}
+
+	/// Returns the number of orders to accumulate before processing a claim batch.
+	///
+	/// Claims are only combined into one transaction with call batching, so
+	/// without it they are processed as soon as they are ready.
+	pub fn optimal_batch_size(&self) -> usize {
+		if !self.delivery.is_wallet_batch_enabled() {
+			return 1;
 		}
+		self.batch_optimizer.optimal_size()
+	}
+
+	/// Records the gas used by a confirmed claim transaction to adapt the
+	/// batch size.
+	///
+	/// Every order of a batch reports the shared transaction, which is only
+	/// recorded once.
+	pub fn record_claim_gas(&self, tx_hash: &TransactionHash, receipt: &TransactionReceipt) {
+		let Some(claims) = self.forget_claim_tx(tx_hash) else {
+			return;
+		};
+		if receipt.success {
+			self.batch_optimizer.record_claim_gas(
+				receipt.gas_used,
+				receipt.effective_gas_price,
+				claims,
+			);
+		}
+	}
+
+	/// Stops tracking the number of claims of a claim transaction, returning it.
+	pub fn forget_claim_tx(&self, tx_hash: &TransactionHash) -> Option<usize> {
+		self.claim_txs
+			.lock()
+			.unwrap()
+			.remove(&hex::encode(&tx_hash.0))
+	}
+
+	/// Records the outcome of a claim in the circuit breaker of its
+	/// settlement implementation.
+	///
+	/// Publishes `SolverEvent::ClaimCircuitOpen` if the outcome disabled
+	/// auto-claiming for the implementation.
+	pub async fn record_claim_outcome(
+		&self,
+		order_id: &str,
+		success: bool,
+	) -> Result<(), SettlementError> {
+		let order: Order = self
+			.storage
+			.retrieve(StorageKey::Orders.as_str(), order_id)
+			.await
+			.map_err(|e| SettlementError::Storage(e.to_string()))?;
+		let (name, _) = self
+			.settlement
+			.find_named_settlement_for_order(&order)
+			.map_err(|e| SettlementError::Service(e.to_string()))?;
+
+		let breaker = self.settlement.claim_circuit_breaker();
+		if breaker.record(name, success, current_timestamp()) {
+			let status = breaker.status(name, current_timestamp());
+			tracing::warn!(
+				implementation = %name,
+				success_rate_pct = ?status.success_rate_pct,
+				claims = status.claims,
+				"Claim success rate too low, disabling auto-claiming"
+			);
+			self.event_bus
+				.publish(SolverEvent::ClaimCircuitOpen {
+					implementation_name: name.to_string(),
+				})
+				.ok();
+		}
+		Ok(())
+	}
+
+	/// Re-enables auto-claiming for a settlement implementation.
+	///
+	/// Claims held back while the circuit was open are queued again.
+	/// Returns the number of queued claims.
+	pub fn reset_claim_circuit(&self, implementation: &str) -> Result<usize, SettlementError> {
+		if self.settlement.get(implementation).is_none() {
+			return Err(SettlementError::Service(format!(
+				"Unknown settlement implementation '{}'",
+				implementation
+			)));
+		}
+		let held = self
+			.settlement
+			.claim_circuit_breaker()
+			.reset(implementation);
+		tracing::info!(
+			implementation = %implementation,
+			held_claims = held.len(),
+			"Claim circuit reset, auto-claiming re-enabled"
+		);
+		let count = held.len();
+		for order_id in held {
+			self.event_bus
+				.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+					order_id,
+				}))
+				.ok();
+		}
+		Ok(count)
 	}
 
 	/// Processes a batch of orders ready for claiming.
-	#[instrument(skip_all)]
+	///
+	/// Batches may have any size; they are accumulated until the optimal batch
+	/// size is reached or the batch times out.
+	///
+	/// Orders are grouped by settlement implementation. Groups are processed
+	/// concurrently, up to `max_claim_groups_parallel` at a time. The claims
+	/// of a group on one chain are submitted as one call batch where the
+	/// chain supports it, and one after the other otherwise.
+	///
+	/// Claims are checked with a gas estimation first; orders whose claim
+	/// would revert are skipped and recorded in the dead letter queue.
+	/// Claims of implementations whose claim circuit is open are held back
+	/// until the circuit is reset.
+	#[instrument(skip_all, fields(batch_size = batch.len()))]
 	pub async fn process_claim_batch(
 		&self,
 		batch: &mut Vec<String>,
 	) -> Result<(), SettlementError> {
-		for order_id in batch.drain(..) {
-			// Retrieve order
-			let order: Order = self
-				.storage
-				.retrieve(StorageKey::Orders.as_str(), &order_id)
-				.await
-				.map_err(|e| SettlementError::Storage(e.to_string()))?;
+		let breaker = self.settlement.claim_circuit_breaker();
+		let now = current_timestamp();
+		for name in self.settlement.implementation_names() {
+			let status = breaker.status(name, now);
+			if status.open {
+				tracing::warn!(
+					implementation = %name,
+					success_rate_pct = ?status.success_rate_pct,
+					held_claims = status.held,
+					"Claim circuit open, auto-claiming disabled"
+				);
+			} else {
+				tracing::debug!(
+					implementation = %name,
+					success_rate_pct = ?status.success_rate_pct,
+					claims = status.claims,
+					"Claim circuit closed"
+				);
+			}
+		}
+
+		// Orders of unknown implementations form their own group
+		let mut groups: BTreeMap<String, Vec<(Order, FillProof)>> = BTreeMap::new();
+		let order_ids: Vec<&str> = batch.iter().map(String::as_str).collect();
+		let orders = self
+			.storage
+			.retrieve_batch::<Order>(StorageKey::Orders.as_str(), &order_ids)
+			.await;
+		batch.clear();
+		for (_, order) in orders {
+			let (order, fill_proof) =
+				claimable(order.map_err(|e| SettlementError::Storage(e.to_string()))?)?;
+			let group = match self.settlement.find_named_settlement_for_order(&order) {
+				Ok((name, _)) if breaker.is_open(name) => {
+					tracing::debug!(order_id = %truncate_id(&order.id), implementation = %name, "Holding claim while circuit is open");
+					breaker.hold(name, order.id);
+					continue;
+				},
+				Ok((name, _)) => name.to_string(),
+				Err(_) => String::new(),
+			};
+			groups.entry(group).or_default().push((order, fill_proof));
+		}
 
-			// Retrieve fill proof (already validated when ClaimReady was emitted)
-			let fill_proof = order
-				.fill_proof
-				.clone()
-				.ok_or_else(|| SettlementError::Service("Order missing fill proof".to_string()))?;
+		run_groups_concurrently(
+			groups.into_values().collect(),
+			self.max_claim_groups_parallel,
+			|orders| self.process_claim_group(orders),
+		)
+		.await
+	}
 
+	/// Claims the orders of one settlement implementation.
+	async fn process_claim_group(
+		&self,
+		orders: Vec<(Order, FillProof)>,
+	) -> Result<(), SettlementError> {
+		let mut claims: BTreeMap<u64, Vec<(Order, Transaction)>> = BTreeMap::new();
+		for (order, fill_proof) in orders {
 			// Generate claim transaction
 			let claim_tx = self
 				.order_service
@@ -90,39 +301,332 @@ impl SettlementHandler {
 				.await
 				.map_err(|e| SettlementError::Service(e.to_string()))?;
 
-			// Submit claim transaction through delivery service
-			let claim_tx_hash = self
-				.delivery
-				.deliver(claim_tx.clone())
+			let estimate = self
+				.settlement
+				.pre_validate_claim(
+					&order,
+					&fill_proof,
+					&claim_tx,
+					&DeliveryGasEstimator(&self.delivery),
+				)
+				.await;
+			let failure = match estimate {
+				Ok(_) => None,
+				Err(e @ solver_settlement::SettlementError::ValidationFailed(_)) => {
+					Some(e.to_string())
+				},
+				// The estimation itself failed, so the claim is retried later
+				Err(e) => match self.retry_estimate(&order.id, e.to_string()) {
+					Some(reason) => Some(reason),
+					None => continue,
+				},
+			};
+			self.estimate_retries.lock().unwrap().remove(&order.id);
+			if let Some(reason) = failure {
+				tracing::error!(
+					order_id = %truncate_id(&order.id),
+					reason = %reason,
+					"Claim gas estimation failed, moving order to dead letter queue"
+				);
+				push_dead_letter(&self.storage, &order.id, TransactionType::Claim, reason)
+					.await
+					.map_err(|e| SettlementError::Storage(e.to_string()))?;
+				continue;
+			}
+
+			claims
+				.entry(claim_tx.chain_id)
+				.or_default()
+				.push((order, claim_tx));
+		}
+
+		for (chain_id, claims) in claims {
+			if claims.len() > 1 && self.delivery.batches_calls(chain_id).await {
+				self.submit_claim_batch(chain_id, claims).await?;
+				continue;
+			}
+			for (order, claim_tx) in claims {
+				self.submit_claim(&order, claim_tx).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Submits the claims of several orders on one chain as one call batch.
+	///
+	/// The claims are linked to their orders once the batch is included.
+	async fn submit_claim_batch(
+		&self,
+		chain_id: u64,
+		claims: Vec<(Order, Transaction)>,
+	) -> Result<(), SettlementError> {
+		let txs: Vec<Transaction> = claims.iter().map(|(_, tx)| tx.clone()).collect();
+		let order_ids: Vec<String> = claims.into_iter().map(|(order, _)| order.id).collect();
+		let batch_id = self
+			.delivery
+			.deliver_call_batch(&txs)
+			.await
+			.map_err(|e| SettlementError::Service(e.to_string()))?;
+		tracing::info!(claims = order_ids.len(), batch_id = %batch_id, "Submitted claim batch");
+
+		let delivery = self.delivery.clone();
+		let storage = self.storage.clone();
+		let state_machine = self.state_machine.clone();
+		let event_bus = self.event_bus.clone();
+		let claim_txs = self.claim_txs.clone();
+		tokio::spawn(async move {
+			let tx_hash = match delivery.wait_for_call_batch(&batch_id, chain_id).await {
+				Ok(tx_hash) => tx_hash,
+				Err(e) => {
+					tracing::error!(batch_id = %batch_id, error = %e, "Claim call batch failed");
+					// The claims are attempted again with the next batch
+					for order_id in order_ids {
+						event_bus
+							.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+								order_id,
+							}))
+							.ok();
+					}
+					return;
+				},
+			};
+			claim_txs
+				.lock()
+				.unwrap()
+				.insert(hex::encode(&tx_hash.0), order_ids.len());
+			for order_id in order_ids {
+				if let Err(e) = record_claim_submission(
+					&storage,
+					&state_machine,
+					&event_bus,
+					&order_id,
+					tx_hash.clone(),
+					chain_id,
+				)
 				.await
-				.map_err(|e| SettlementError::Service(e.to_string()))?;
+				{
+					tracing::error!(order_id = %truncate_id(&order_id), error = %e, "Failed to track batched claim");
+				}
+			}
+		});
+		Ok(())
+	}
 
-			self.event_bus
-				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
-					order_id: order.id.clone(),
-					tx_hash: claim_tx_hash.clone(),
-					tx_type: TransactionType::Claim,
-					tx_chain_id: claim_tx.chain_id,
+	/// Queues a claim whose gas could not be estimated again after a delay.
+	///
+	/// Returns the reason to set the claim aside with once it has been
+	/// retried `MAX_CLAIM_ESTIMATE_RETRIES` times, or None if it was queued.
+	fn retry_estimate(&self, order_id: &str, error: String) -> Option<String> {
+		let mut retries = self.estimate_retries.lock().unwrap();
+		let attempts = retries.entry(order_id.to_string()).or_default();
+		*attempts += 1;
+		if *attempts > MAX_CLAIM_ESTIMATE_RETRIES {
+			return Some(format!(
+				"Claim gas estimation failed {} times: {}",
+				attempts, error
+			));
+		}
+
+		tracing::warn!(
+			order_id = %truncate_id(order_id),
+			attempt = *attempts,
+			error = %error,
+			"Claim gas estimation unavailable, retrying later"
+		);
+		let event_bus = self.event_bus.clone();
+		let order_id = order_id.to_string();
+		tokio::spawn(async move {
+			tokio::time::sleep(CLAIM_ESTIMATE_RETRY_DELAY).await;
+			event_bus
+				.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+					order_id,
 				}))
 				.ok();
+		});
+		None
+	}
 
-			// Update order with claim transaction hash
-			self.state_machine
-				.set_transaction_hash(&order.id, claim_tx_hash.clone(), TransactionType::Claim)
-				.await
-				.map_err(|e| SettlementError::State(e.to_string()))?;
-
-			// Store reverse mapping: tx_hash -> order_id
-			self.storage
-				.store(
-					StorageKey::OrderByTxHash.as_str(),
-					&hex::encode(&claim_tx_hash.0),
-					&order.id,
-					None,
-				)
-				.await
-				.map_err(|e| SettlementError::Storage(e.to_string()))?;
+	/// Queues the claim of an order set aside in the dead letter queue again.
+	///
+	/// The dead letter is removed, so the order is recorded again if its
+	/// claim still fails.
+	pub async fn retry_dead_letter(&self, order_id: &str) -> Result<(), SettlementError> {
+		let dead_letter: DeadLetter = self
+			.storage
+			.retrieve(StorageKey::DeadLetters.as_str(), order_id)
+			.await
+			.map_err(|e| SettlementError::Storage(e.to_string()))?;
+		if dead_letter.tx_type != TransactionType::Claim {
+			return Err(SettlementError::Service(format!(
+				"Dead letter of order {} is not a claim",
+				order_id
+			)));
 		}
+		take_dead_letter(&self.storage, order_id)
+			.await
+			.map_err(|e| SettlementError::Storage(e.to_string()))?;
+
+		tracing::info!(order_id = %truncate_id(order_id), "Retrying claim from dead letter queue");
+		self.event_bus
+			.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+				order_id: order_id.to_string(),
+			}))
+			.ok();
+		Ok(())
+	}
+
+	/// Submits a claim transaction for an order and tracks it until confirmation.
+	async fn submit_claim(
+		&self,
+		order: &Order,
+		claim_tx: Transaction,
+	) -> Result<(), SettlementError> {
+		// Submit claim transaction through delivery service
+		let claim_tx_hash = self
+			.delivery
+			.deliver(claim_tx.clone())
+			.await
+			.map_err(|e| SettlementError::Service(e.to_string()))?;
+
+		self.claim_txs
+			.lock()
+			.unwrap()
+			.insert(hex::encode(&claim_tx_hash.0), 1);
+		record_claim_submission(
+			&self.storage,
+			&self.state_machine,
+			&self.event_bus,
+			&order.id,
+			claim_tx_hash,
+			claim_tx.chain_id,
+		)
+		.await
+	}
+
+	/// Handles a fill that was challenged during the challenge period.
+	///
+	/// A challenged fill can never be claimed, so the order is marked as failed.
+	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id)))]
+	pub async fn handle_fill_challenged(&self, order_id: String) -> Result<(), SettlementError> {
+		tracing::warn!("Fill challenged, order cannot be claimed");
+
+		self.state_machine
+			.transition_order_status(&order_id, OrderStatus::Failed(TransactionType::Claim))
+			.await
+			.map_err(|e| SettlementError::State(e.to_string()))?;
+		self.settlement.invalidate_proof(&order_id);
+
 		Ok(())
 	}
 }
+
+/// Publishes a submitted claim transaction and links it to its order.
+async fn record_claim_submission(
+	storage: &StorageService,
+	state_machine: &OrderStateMachine,
+	event_bus: &EventBus,
+	order_id: &str,
+	tx_hash: TransactionHash,
+	tx_chain_id: u64,
+) -> Result<(), SettlementError> {
+	event_bus
+		.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+			order_id: order_id.to_string(),
+			tx_hash: tx_hash.clone(),
+			tx_type: TransactionType::Claim,
+			tx_chain_id,
+		}))
+		.ok();
+
+	// Update order with claim transaction hash
+	state_machine
+		.set_transaction_hash(order_id, tx_hash.clone(), TransactionType::Claim)
+		.await
+		.map_err(|e| SettlementError::State(e.to_string()))?;
+
+	// Store reverse mapping: tx_hash -> order_id
+	storage
+		.store(
+			StorageKey::OrderByTxHash.as_str(),
+			&hex::encode(&tx_hash.0),
+			&order_id.to_string(),
+			None,
+		)
+		.await
+		.map_err(|e| SettlementError::Storage(e.to_string()))
+}
+
+/// Pairs an order ready for claiming with its fill proof, which was already
+/// validated when ClaimReady was emitted.
+fn claimable(order: Order) -> Result<(Order, FillProof), SettlementError> {
+	let fill_proof = order
+		.fill_proof
+		.clone()
+		.ok_or_else(|| SettlementError::Service("Order missing fill proof".to_string()))?;
+	Ok((order, fill_proof))
+}
+
+/// Runs `process` on every group, at most `max_parallel` groups at a time.
+///
+/// All groups run to completion even if some fail; the first error is returned.
+async fn run_groups_concurrently<T, F, Fut>(
+	groups: Vec<T>,
+	max_parallel: usize,
+	process: F,
+) -> Result<(), SettlementError>
+where
+	F: Fn(T) -> Fut,
+	Fut: Future<Output = Result<(), SettlementError>>,
+{
+	let permits = Semaphore::new(max_parallel.max(1));
+	let results = join_all(groups.into_iter().map(|group| {
+		let permits = &permits;
+		let processed = process(group);
+		async move {
+			let _permit = permits.acquire().await;
+			processed.await
+		}
+	}))
+	.await;
+	results.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::{Duration, Instant};
+
+	#[tokio::test]
+	async fn test_claim_groups_run_concurrently() {
+		let process = |delay: u64| async move {
+			tokio::time::sleep(Duration::from_millis(delay)).await;
+			Ok(())
+		};
+
+		let started = Instant::now();
+		run_groups_concurrently(vec![200, 200], 2, process)
+			.await
+			.unwrap();
+		let parallel = started.elapsed();
+
+		let started = Instant::now();
+		run_groups_concurrently(vec![200, 200], 1, process)
+			.await
+			.unwrap();
+		let sequential = started.elapsed();
+
+		assert!(parallel < Duration::from_millis(400));
+		assert!(sequential >= Duration::from_millis(400));
+
+		// Failures of one group do not stop the others
+		let result = run_groups_concurrently(vec![true, false], 2, |fails| async move {
+			if fails {
+				Err(SettlementError::Service("reverted".to_string()))
+			} else {
+				Ok(())
+			}
+		})
+		.await;
+		assert!(result.is_err());
+	}
+}
@@ -5,15 +5,21 @@
 //! transactions and coordinates with settlement monitoring.
 
 use crate::engine::event_bus::EventBus;
-use crate::monitoring::TransactionMonitor;
+use crate::gas_budget::GasBudgetManager;
+use crate::handlers::intent::fill_deadline;
+use crate::inventory::InventoryManager;
+use crate::monitoring::transaction::RECEIPT_POLL_INTERVAL;
+use crate::monitoring::{ReceiptBatcher, TransactionMonitor};
 use crate::state::OrderStateMachine;
 use alloy_primitives::hex;
 use solver_delivery::DeliveryService;
+use solver_order::nonce_coordinator::NonceCoordinator;
+use solver_order::prediction::record_fill_time;
 use solver_settlement::SettlementService;
 use solver_storage::StorageService;
 use solver_types::{
-	truncate_id, DeliveryEvent, Order, OrderEvent, OrderStatus, SolverEvent, StorageKey,
-	TransactionHash, TransactionReceipt, TransactionType,
+	current_timestamp, truncate_id, DeliveryEvent, Order, OrderEvent, OrderStatus, SolverEvent,
+	StorageKey, TransactionHash, TransactionReceipt, TransactionType,
 };
 use std::sync::Arc;
 use thiserror::Error;
@@ -40,33 +46,51 @@ pub enum TransactionError {
 /// tasks for pending transactions and coordinates with settlement monitoring.
 pub struct TransactionHandler {
 	delivery: Arc<DeliveryService>,
+	receipts: Arc<ReceiptBatcher>,
 	settlement: Arc<SettlementService>,
 	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
+	gas_budget: Arc<GasBudgetManager>,
+	inventory: Arc<InventoryManager>,
+	nonces: Arc<NonceCoordinator>,
 	monitoring_timeout_minutes: u64,
+	expiry_grace_period_seconds: u64,
 }
 
 impl TransactionHandler {
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		delivery: Arc<DeliveryService>,
 		settlement: Arc<SettlementService>,
 		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
+		gas_budget: Arc<GasBudgetManager>,
+		inventory: Arc<InventoryManager>,
+		nonces: Arc<NonceCoordinator>,
 		monitoring_timeout_minutes: u64,
+		expiry_grace_period_seconds: u64,
 	) -> Self {
 		Self {
+			receipts: Arc::new(ReceiptBatcher::new(delivery.clone(), RECEIPT_POLL_INTERVAL)),
 			delivery,
 			settlement,
 			storage,
 			state_machine,
 			event_bus,
+			gas_budget,
+			inventory,
+			nonces,
 			monitoring_timeout_minutes,
+			expiry_grace_period_seconds,
 		}
 	}
 
 	/// Spawns a monitoring task for a pending transaction
+	///
+	/// Fill transactions are monitored against the fill deadline of their
+	/// order, extended by the expiry grace period.
 	pub async fn monitor_transaction(
 		&self,
 		order_id: String,
@@ -76,13 +100,30 @@ impl TransactionHandler {
 	) {
 		let monitor = TransactionMonitor::new(
 			self.delivery.clone(),
+			self.receipts.clone(),
 			self.event_bus.clone(),
+			self.gas_budget.clone(),
+			self.storage.clone(),
 			self.monitoring_timeout_minutes,
+			self.expiry_grace_period_seconds,
 		);
 
+		let fill_deadline = match tx_type {
+			TransactionType::Fill | TransactionType::Reveal => {
+				match self.state_machine.get_order(&order_id).await {
+					Ok(order) => fill_deadline(&order),
+					Err(e) => {
+						tracing::debug!(order_id = %truncate_id(&order_id), error = %e, "Failed to load order for fill deadline");
+						None
+					},
+				}
+			},
+			_ => None,
+		};
+
 		tokio::spawn(async move {
 			monitor
-				.monitor(order_id, tx_hash, tx_type, tx_chain_id)
+				.monitor(order_id, tx_hash, tx_type, tx_chain_id, fill_deadline)
 				.await;
 		});
 	}
@@ -118,7 +159,14 @@ impl TransactionHandler {
 				self.handle_fill_confirmed(tx_hash, receipt).await?;
 			},
 			TransactionType::Claim => {
-				self.handle_claim_confirmed(tx_hash, receipt).await?;
+				self.handle_claim_confirmed(order_id, tx_hash).await?;
+			},
+			TransactionType::Commit | TransactionType::Reveal => {
+				// The strategy moves on to the next phase once it sees the receipt
+				tracing::debug!(
+					block_number = receipt.block_number,
+					"Fill commitment transaction confirmed"
+				);
 			},
 		}
 
@@ -136,12 +184,19 @@ impl TransactionHandler {
 	) -> Result<(), TransactionError> {
 		tracing::error!("Transaction failed: {}", error);
 
+		// A failed order no longer needs its output tokens
+		self.inventory.release(&order_id).await;
+
 		// Update order status with specific failure type
-		self.state_machine
+		let order = self
+			.state_machine
 			.transition_order_status(&order_id, OrderStatus::Failed(tx_type))
 			.await
 			.map_err(|e| TransactionError::State(e.to_string()))?;
 
+		self.settlement.invalidate_proof(&order_id);
+		self.unlock_nonce(&order).await;
+
 		Ok(())
 	}
 
@@ -203,9 +258,25 @@ impl TransactionHandler {
 			.await
 			.map_err(|e| TransactionError::Storage(e.to_string()))?;
 
+		// The fill has spent the reserved output tokens
+		self.inventory.release(&order.id).await;
+
+		// Record the fill time for fill time prediction
+		if let Err(e) = record_fill_time(&self.storage, &order, current_timestamp()).await {
+			tracing::debug!(order_id = %truncate_id(&order.id), error = %e, "Failed to record fill time");
+		}
+
+		// Simulated fills are never on chain, so they cannot be attested
+		if self.delivery.is_simulation_mode() {
+			tracing::info!(order_id = %truncate_id(&order.id), "[SIM] Fill simulated, not monitoring settlement");
+			return Ok(());
+		}
+
 		// Spawn monitoring for settlement
 		let settlement_monitor = crate::monitoring::SettlementMonitor::new(
 			self.settlement.clone(),
+			self.delivery.clone(),
+			self.storage.clone(),
 			self.state_machine.clone(),
 			self.event_bus.clone(),
 			self.monitoring_timeout_minutes,
@@ -221,20 +292,17 @@ impl TransactionHandler {
 	}
 
 	/// Handles confirmed claim transactions.
+	///
+	/// The order is taken from the confirmation rather than looked up by
+	/// hash, since batched claims share their transaction.
 	async fn handle_claim_confirmed(
 		&self,
+		order_id: String,
 		tx_hash: TransactionHash,
-		_receipt: TransactionReceipt,
 	) -> Result<(), TransactionError> {
-		// Look up the order ID from the transaction hash
-		let order_id = self
-			.storage
-			.retrieve::<String>(StorageKey::OrderByTxHash.as_str(), &hex::encode(&tx_hash.0))
-			.await
-			.map_err(|e| TransactionError::Storage(e.to_string()))?;
-
 		// Update order with claim transaction hash and mark as finalized
-		self.state_machine
+		let order = self
+			.state_machine
 			.update_order_with(&order_id, |order| {
 				order.claim_tx_hash = Some(tx_hash.clone());
 				order.status = OrderStatus::Finalized;
@@ -242,6 +310,9 @@ impl TransactionHandler {
 			.await
 			.map_err(|e| TransactionError::State(e.to_string()))?;
 
+		self.settlement.invalidate_proof(&order_id);
+		self.unlock_nonce(&order).await;
+
 		// Publish completed event
 		self.event_bus
 			.publish(SolverEvent::Settlement(
@@ -251,4 +322,11 @@ impl TransactionHandler {
 
 		Ok(())
 	}
+
+	/// Releases the order nonce locked by the order, if any.
+	async fn unlock_nonce(&self, order: &Order) {
+		if let Err(e) = self.nonces.unlock(order).await {
+			tracing::warn!(error = %e, "Failed to unlock order nonce");
+		}
+	}
 }
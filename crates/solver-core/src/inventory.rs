@@ -0,0 +1,329 @@
+//! Output token inventory management across chains.
+//!
+//! Filling an order spends the solver's output tokens on the destination
+//! chain long before the fill is confirmed. To keep concurrent orders from
+//! committing the same balance twice, the amounts of pending fills are
+//! reserved against the solver's balance until the fill confirms or fails.
+//! Reservations are kept in memory and restored for resumed orders during
+//! recovery. Only tokens configured on a network are tracked.
+
+use crate::engine::token_manager::TokenManager;
+use alloy_primitives::{hex, U256};
+use serde::Serialize;
+use solver_types::{with_0x_prefix, Address, Eip7683OrderData, Order};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+/// Errors that can occur while reserving inventory.
+#[derive(Debug, Error)]
+pub enum InventoryError {
+	/// The available balance does not cover the requested amount.
+	#[error("Insufficient inventory of {token} on chain {chain_id}: available {available}, requested {requested}")]
+	InsufficientBalance {
+		chain_id: u64,
+		token: String,
+		available: U256,
+		requested: U256,
+	},
+	/// The solver's balance could not be fetched.
+	#[error("Failed to fetch balance: {0}")]
+	Balance(String),
+}
+
+/// Reserved and available amounts of a token, as returned by GET /api/admin/inventory.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InventoryBalance {
+	/// Amount committed to pending fills.
+	pub reserved: U256,
+	/// Balance left for new fills (actual balance minus reserved).
+	pub available: U256,
+}
+
+/// Inventory by chain ID and lowercase hex token address.
+pub type InventorySummary = BTreeMap<u64, BTreeMap<String, InventoryBalance>>;
+
+/// An amount of a token held back for a pending fill.
+#[derive(Debug, Clone)]
+struct Reservation {
+	chain_id: u64,
+	token: String,
+	amount: U256,
+}
+
+/// Reservations of pending fills, keyed by order ID.
+#[derive(Debug, Default)]
+struct Reservations(HashMap<String, Vec<Reservation>>);
+
+impl Reservations {
+	/// Returns the total amount reserved for a token on a chain.
+	fn reserved(&self, chain_id: u64, token: &str) -> U256 {
+		self.0
+			.values()
+			.flatten()
+			.filter(|r| r.chain_id == chain_id && r.token == token)
+			.fold(U256::ZERO, |acc, r| acc.saturating_add(r.amount))
+	}
+
+	/// Adds a reservation for an order.
+	fn add(&mut self, order_id: &str, reservation: Reservation) {
+		self.0
+			.entry(order_id.to_string())
+			.or_default()
+			.push(reservation);
+	}
+
+	/// Drops all reservations of an order, returning whether any existed.
+	fn remove(&mut self, order_id: &str) -> bool {
+		self.0.remove(order_id).is_some()
+	}
+}
+
+/// Tracks output token balances reserved for pending fills.
+pub struct InventoryManager {
+	token_manager: Arc<TokenManager>,
+	/// Held across balance lookups so that reservations cannot race each other.
+	reservations: Mutex<Reservations>,
+}
+
+impl InventoryManager {
+	/// Creates a new InventoryManager reading balances through `token_manager`.
+	pub fn new(token_manager: Arc<TokenManager>) -> Self {
+		Self {
+			token_manager,
+			reservations: Mutex::new(Reservations::default()),
+		}
+	}
+
+	/// Reserves `amount` of a token on a chain for an order's fill.
+	///
+	/// Fails if the available balance does not cover the amount. Tokens not
+	/// configured on the chain are not tracked and always succeed.
+	pub async fn reserve(
+		&self,
+		order_id: &str,
+		chain_id: u64,
+		token: &Address,
+		amount: U256,
+	) -> Result<(), InventoryError> {
+		if !self.token_manager.is_supported(chain_id, token) {
+			return Ok(());
+		}
+
+		let token_key = token_key(token);
+		let mut reservations = self.reservations.lock().await;
+		let balance = self.balance(chain_id, token).await?;
+		let available = balance.saturating_sub(reservations.reserved(chain_id, &token_key));
+		if available < amount {
+			return Err(InventoryError::InsufficientBalance {
+				chain_id,
+				token: token_key,
+				available,
+				requested: amount,
+			});
+		}
+
+		reservations.add(
+			order_id,
+			Reservation {
+				chain_id,
+				token: token_key,
+				amount,
+			},
+		);
+		Ok(())
+	}
+
+	/// Reserves the outputs of an order on their destination chains.
+	///
+	/// Either all outputs are reserved or none are.
+	pub async fn reserve_outputs(&self, order: &Order) -> Result<(), InventoryError> {
+		for (chain_id, token, amount) in order_outputs(order) {
+			if let Err(e) = self.reserve(&order.id, chain_id, &token, amount).await {
+				self.release(&order.id).await;
+				return Err(e);
+			}
+		}
+		Ok(())
+	}
+
+	/// Restores the reservations of an order whose execution was resumed
+	/// after a restart.
+	///
+	/// The outputs were committed before the restart, so they are reserved
+	/// without checking the balance.
+	pub async fn restore_outputs(&self, order: &Order) {
+		let mut reservations = self.reservations.lock().await;
+		reservations.remove(&order.id);
+		for (chain_id, token, amount) in order_outputs(order) {
+			if self.token_manager.is_supported(chain_id, &token) {
+				reservations.add(
+					&order.id,
+					Reservation {
+						chain_id,
+						token: token_key(&token),
+						amount,
+					},
+				);
+			}
+		}
+	}
+
+	/// Releases all reservations held for an order.
+	pub async fn release(&self, order_id: &str) {
+		if self.reservations.lock().await.remove(order_id) {
+			tracing::debug!(order_id, "Released inventory reservations");
+		}
+	}
+
+	/// Returns the reserved and available amounts of every configured token.
+	pub async fn summary(&self) -> Result<InventorySummary, InventoryError> {
+		let reservations = self.reservations.lock().await;
+		let mut summary = InventorySummary::new();
+
+		for (chain_id, network) in self.token_manager.get_networks() {
+			for token in &network.tokens {
+				let token_key = token_key(&token.address);
+				let balance = self.balance(chain_id.as_u64(), &token.address).await?;
+				let reserved = reservations.reserved(chain_id.as_u64(), &token_key);
+				summary.entry(chain_id.as_u64()).or_default().insert(
+					token_key,
+					InventoryBalance {
+						reserved,
+						available: balance.saturating_sub(reserved),
+					},
+				);
+			}
+		}
+
+		Ok(summary)
+	}
+
+	/// Returns the balance of a token on a chain left for new fills.
+	pub async fn available_balance(
+		&self,
+		chain_id: u64,
+		token: &Address,
+	) -> Result<U256, InventoryError> {
+		let reservations = self.reservations.lock().await;
+		let balance = self.balance(chain_id, token).await?;
+		Ok(balance.saturating_sub(reservations.reserved(chain_id, &token_key(token))))
+	}
+
+	/// Fetches the solver's actual balance of a token.
+	async fn balance(&self, chain_id: u64, token: &Address) -> Result<U256, InventoryError> {
+		let balance = self
+			.token_manager
+			.check_balance(chain_id, token)
+			.await
+			.map_err(|e| InventoryError::Balance(e.to_string()))?;
+		U256::from_str_radix(&balance, 10)
+			.map_err(|e| InventoryError::Balance(format!("Invalid balance {}: {}", balance, e)))
+	}
+}
+
+/// Returns the lowercase hex form of a token address used as inventory key.
+fn token_key(token: &Address) -> String {
+	with_0x_prefix(&hex::encode(&token.0))
+}
+
+/// Returns the chain ID, token and amount of each of an order's outputs.
+pub(crate) fn order_outputs(order: &Order) -> Vec<(u64, Address, U256)> {
+	let Ok(data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+		return Vec::new();
+	};
+
+	data.outputs
+		.iter()
+		.filter_map(|output| {
+			Some((
+				u64::try_from(output.chain_id).ok()?,
+				Address(output.token[12..].to_vec()),
+				output.amount,
+			))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn reservation(chain_id: u64, token: &str, amount: u64) -> Reservation {
+		Reservation {
+			chain_id,
+			token: token.to_string(),
+			amount: U256::from(amount),
+		}
+	}
+
+	#[test]
+	fn test_reserved_totals_per_chain_and_token() {
+		let mut reservations = Reservations::default();
+		reservations.add("order-1", reservation(10, "0xaa", 5));
+		reservations.add("order-1", reservation(10, "0xbb", 7));
+		reservations.add("order-2", reservation(10, "0xaa", 3));
+		reservations.add("order-2", reservation(8453, "0xaa", 11));
+
+		assert_eq!(reservations.reserved(10, "0xaa"), U256::from(8));
+		assert_eq!(reservations.reserved(10, "0xbb"), U256::from(7));
+		assert_eq!(reservations.reserved(8453, "0xaa"), U256::from(11));
+
+		// Releasing an order frees all of its reservations
+		assert!(reservations.remove("order-1"));
+		assert!(!reservations.remove("order-1"));
+		assert_eq!(reservations.reserved(10, "0xaa"), U256::from(3));
+		assert_eq!(reservations.reserved(10, "0xbb"), U256::ZERO);
+	}
+
+	#[test]
+	fn test_order_outputs_skip_invalid_chain_ids() {
+		let output = |chain_id: &str| {
+			serde_json::json!({
+				"oracle": vec![0u8; 32],
+				"settler": vec![0u8; 32],
+				"chain_id": chain_id,
+				"token": vec![0x11u8; 32],
+				"amount": "0x64",
+				"recipient": vec![0u8; 32],
+				"call": "0x",
+				"context": "0x",
+			})
+		};
+		let order = Order {
+			id: "order-1".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: solver_types::OrderStatus::Created,
+			data: serde_json::json!({
+				"user": "0x0000000000000000000000000000000000000001",
+				"nonce": "0x1",
+				"origin_chain_id": "0x1",
+				"expires": u32::MAX,
+				"fill_deadline": u32::MAX,
+				"input_oracle": "0x0000000000000000000000000000000000000000",
+				"inputs": [],
+				"order_id": vec![0u8; 32],
+				"gas_limit_overrides": {},
+				"outputs": [output("0xa"), output(&format!("{:#x}", U256::MAX))],
+			}),
+			solver_address: Address(vec![0x22; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		};
+
+		assert_eq!(
+			order_outputs(&order),
+			vec![(10, Address(vec![0x11; 20]), U256::from(100))]
+		);
+	}
+}
@@ -5,12 +5,25 @@
 //! to execute the complete order lifecycle. It includes the event-driven architecture
 //! and modular design for building solver instances.
 
+pub mod arbitrage;
+pub mod auction;
+pub mod audit;
+pub mod batch_size;
 pub mod builder;
+pub mod cancellation;
 pub mod engine;
+pub mod event_persister;
+pub mod gas_budget;
+pub mod gossip;
 pub mod handlers;
+pub mod inventory;
 pub mod monitoring;
+pub mod rebalancing;
 pub mod recovery;
+pub mod reputation;
 pub mod state;
+pub mod telemetry;
+pub mod webhooks;
 
 // Re-export main types
 pub use builder::{BuilderError, SolverBuilder, SolverFactories};
@@ -8,4 +8,4 @@ pub mod settlement;
 pub mod transaction;
 
 pub use settlement::SettlementMonitor;
-pub use transaction::TransactionMonitor;
+pub use transaction::{ReceiptBatcher, TransactionMonitor};
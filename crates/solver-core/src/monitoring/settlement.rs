@@ -1,12 +1,22 @@
 //! Settlement monitoring for filled orders.
 //!
 //! Monitors orders after fill confirmation to determine when they are ready
-//! for claiming, retrieving attestations and checking claim conditions.
+//! for claiming, retrieving attestations, relaying them to the origin chain
+//! when a proof relayer is configured and checking claim conditions. The
+//! progress of each relay is persisted so that proofs are never sent twice,
+//! and the audit record of the fill is written once its proof is available.
 
+use crate::audit;
 use crate::engine::event_bus::EventBus;
 use crate::state::OrderStateMachine;
-use solver_settlement::SettlementService;
-use solver_types::{truncate_id, Order, SettlementEvent, SolverEvent, TransactionHash};
+use alloy_primitives::hex;
+use solver_delivery::DeliveryService;
+use solver_settlement::{RelayState, SettlementService};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{
+	current_timestamp, truncate_id, FillProof, Order, SettlementEvent, SolverEvent, StorageKey,
+	TransactionHash,
+};
 use std::sync::Arc;
 
 /// Monitor for tracking settlement readiness of filled orders.
@@ -16,6 +26,8 @@ use std::sync::Arc;
 /// until the order is claimable or a timeout is reached.
 pub struct SettlementMonitor {
 	settlement: Arc<SettlementService>,
+	delivery: Arc<DeliveryService>,
+	storage: Arc<StorageService>,
 	state_machine: Arc<OrderStateMachine>,
 	event_bus: EventBus,
 	timeout_minutes: u64,
@@ -24,12 +36,16 @@ pub struct SettlementMonitor {
 impl SettlementMonitor {
 	pub fn new(
 		settlement: Arc<SettlementService>,
+		delivery: Arc<DeliveryService>,
+		storage: Arc<StorageService>,
 		state_machine: Arc<OrderStateMachine>,
 		event_bus: EventBus,
 		timeout_minutes: u64,
 	) -> Self {
 		Self {
 			settlement,
+			delivery,
+			storage,
 			state_machine,
 			event_bus,
 			timeout_minutes,
@@ -39,18 +55,32 @@ impl SettlementMonitor {
 	/// Monitors an order for claim readiness after fill confirmation
 	pub async fn monitor_claim_readiness(&self, order: Order, tx_hash: TransactionHash) {
 		let settlement = &self.settlement;
+		let monitoring_timeout = tokio::time::Duration::from_secs(self.timeout_minutes * 60);
+		let check_interval = tokio::time::Duration::from_secs(3);
+		let start_time = tokio::time::Instant::now();
 
-		// Retrieve and extract proof
-		let fill_proof = match settlement.get_attestation(&order, &tx_hash).await {
-			Ok(proof) => proof,
-			Err(e) => {
-				tracing::error!(
-					order_id = %truncate_id(&order.id),
-					error = %e,
-					"Failed to get attestation for fill transaction"
-				);
-				return;
-			},
+		// Retrieve and extract proof, retrying while the attestation is not
+		// available yet
+		let fill_proof = loop {
+			match settlement.get_attestation(&order, &tx_hash).await {
+				Ok(proof) => break proof,
+				Err(e) if start_time.elapsed() > monitoring_timeout => {
+					tracing::error!(
+						order_id = %truncate_id(&order.id),
+						error = %e,
+						"Failed to get attestation for fill transaction"
+					);
+					return;
+				},
+				Err(e) => {
+					tracing::debug!(
+						order_id = %truncate_id(&order.id),
+						error = %e,
+						"Attestation not available yet"
+					);
+					tokio::time::sleep(check_interval).await;
+				},
+			}
 		};
 
 		// Store the fill proof
@@ -68,13 +98,16 @@ impl SettlementMonitor {
 		}
 
 		// Monitor claim readiness
-		let monitoring_timeout = tokio::time::Duration::from_secs(self.timeout_minutes * 60);
-		let check_interval = tokio::time::Duration::from_secs(3);
-		let start_time = tokio::time::Instant::now();
-
+		let mut audited = false;
 		loop {
 			// Check if we've exceeded the timeout
 			if start_time.elapsed() > monitoring_timeout {
+				if !audited {
+					tracing::error!(
+						order_id = %truncate_id(&order.id),
+						"Fill audit record could not be written"
+					);
+				}
 				tracing::warn!(
 					order_id = %truncate_id(&order.id),
 					"Claim readiness monitoring timeout reached after {} minutes",
@@ -83,8 +116,27 @@ impl SettlementMonitor {
 				break;
 			}
 
+			// Every claimed fill must have an audit record, so failed writes
+			// are retried before the order can become claimable
+			if !audited {
+				audited = self.record_audit(&order, &fill_proof).await;
+			}
+
+			// Stop monitoring if the fill has been challenged
+			if settlement.is_challenged(&order).await {
+				self.event_bus
+					.publish(SolverEvent::Settlement(SettlementEvent::FillChallenged {
+						order_id: order.id,
+					}))
+					.ok();
+				break;
+			}
+
+			// Advance the relay of the proof to the origin chain
+			self.relay_proof(&order, &fill_proof).await;
+
 			// Check if we can claim
-			if settlement.can_claim(&order, &fill_proof).await {
+			if audited && settlement.can_claim(&order, &fill_proof).await {
 				self.event_bus
 					.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
 						order_id: order.id,
@@ -97,4 +149,124 @@ impl SettlementMonitor {
 			tokio::time::sleep(check_interval).await;
 		}
 	}
+
+	/// Writes the audit record of the proven fill, returning whether it was
+	/// written.
+	async fn record_audit(&self, order: &Order, fill_proof: &FillProof) -> bool {
+		match audit::record_proven_fill(
+			&self.storage,
+			&self.delivery,
+			order,
+			fill_proof,
+			current_timestamp(),
+		)
+		.await
+		{
+			Ok(hash) => {
+				tracing::debug!(audit_hash = %hash, "Recorded fill audit record");
+				true
+			},
+			Err(e) => {
+				tracing::warn!(
+					order_id = %truncate_id(&order.id),
+					error = %e,
+					"Failed to record fill audit record, retrying"
+				);
+				false
+			},
+		}
+	}
+
+	/// Advances the relay of the fill proof to the origin chain, if a proof
+	/// relayer is configured.
+	///
+	/// The proof is sent once, unless the messenger has already received it,
+	/// and a Wormhole VAA is submitted to the messenger once it is signed.
+	/// Each step is recorded in storage before moving on, so that restarts
+	/// and repeated calls never send a proof or its VAA twice.
+	async fn relay_proof(&self, order: &Order, fill_proof: &FillProof) {
+		let Some(relayer) = self.settlement.proof_relayer() else {
+			return;
+		};
+		let mut state = match self
+			.storage
+			.retrieve::<RelayState>(StorageKey::ProofRelays.as_str(), &order.id)
+			.await
+		{
+			Ok(state) => state,
+			Err(StorageError::NotFound) => RelayState::default(),
+			Err(e) => {
+				tracing::warn!(order_id = %truncate_id(&order.id), error = %e, "Failed to load relay state");
+				return;
+			},
+		};
+		if state.delivery_tx_hash.is_some() {
+			return;
+		}
+		if let Ok(true) = relayer.is_relayed(order, fill_proof).await {
+			return;
+		}
+
+		let result = match &state.send_tx_hash {
+			None => match relayer.relay_transaction(order, fill_proof).await {
+				Ok(tx) => match self.delivery.deliver(tx).await {
+					Ok(tx_hash) => {
+						tracing::info!(
+							order_id = %truncate_id(&order.id),
+							tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+							"Sent fill proof to origin chain"
+						);
+						state.send_tx_hash = Some(tx_hash);
+						Ok(())
+					},
+					Err(e) => Err(e.to_string()),
+				},
+				Err(e) => Err(e.to_string()),
+			},
+			Some(send_tx_hash) => match relayer.delivery_transaction(order, send_tx_hash).await {
+				// Delivered by the protocol
+				Ok(None) => return,
+				Ok(Some(tx)) => match self.delivery.deliver(tx).await {
+					Ok(tx_hash) => {
+						tracing::info!(
+							order_id = %truncate_id(&order.id),
+							tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+							"Delivered relayed fill proof to origin chain"
+						);
+						state.delivery_tx_hash = Some(tx_hash);
+						Ok(())
+					},
+					Err(e) => Err(e.to_string()),
+				},
+				Err(e) => {
+					// Retried on the next check, typically until the VAA is signed
+					tracing::debug!(order_id = %truncate_id(&order.id), error = %e, "Relayed fill proof not deliverable yet");
+					return;
+				},
+			},
+		};
+
+		match result {
+			Ok(()) => {
+				if let Err(e) = self
+					.storage
+					.store(StorageKey::ProofRelays.as_str(), &order.id, &state, None)
+					.await
+				{
+					tracing::error!(
+						order_id = %truncate_id(&order.id),
+						error = %e,
+						"Failed to store relay state"
+					);
+				}
+			},
+			Err(e) => {
+				tracing::error!(
+					order_id = %truncate_id(&order.id),
+					error = %e,
+					"Failed to relay fill proof"
+				);
+			},
+		}
+	}
 }
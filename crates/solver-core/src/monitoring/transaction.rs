@@ -1,36 +1,149 @@
 //! Transaction monitoring for pending blockchain transactions.
 //!
-//! Polls transaction status at regular intervals until confirmation or failure,
-//! publishing appropriate events to the event bus for further processing.
+//! Polls transaction receipts at regular intervals until confirmation or
+//! failure, publishing appropriate events to the event bus for further
+//! processing. Receipts of all transactions monitored on the same chain are
+//! fetched together in one batch per poll interval.
 
 use crate::engine::event_bus::EventBus;
-use alloy_primitives::hex;
-use solver_delivery::{DeliveryError, DeliveryService};
-use solver_types::{truncate_id, DeliveryEvent, SolverEvent, TransactionHash, TransactionType};
+use crate::engine::report::record_gas_spend;
+use crate::gas_budget::GasBudgetManager;
+use alloy_primitives::{hex, U256};
+use solver_delivery::DeliveryService;
+use solver_storage::StorageService;
+use solver_types::{
+	current_timestamp, truncate_id, DeliveryEvent, OrderEvent, SolverEvent, TransactionHash,
+	TransactionReceipt, TransactionType,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Mutex};
 use tracing::instrument;
 
+/// Interval at which pending transaction receipts are polled.
+pub const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Sender answering a receipt poll.
+type ReceiptWaiter = oneshot::Sender<Result<Option<TransactionReceipt>, String>>;
+
+/// Batches receipt polls of concurrently monitored transactions.
+///
+/// Polls are queued per chain. The first poll queued for a chain schedules a
+/// flush after the poll interval, which fetches the receipts of all queued
+/// transactions of that chain with a single `batch_get_receipts` call.
+pub struct ReceiptBatcher {
+	delivery: Arc<DeliveryService>,
+	interval: Duration,
+	pending: Mutex<HashMap<u64, Vec<(TransactionHash, ReceiptWaiter)>>>,
+}
+
+impl ReceiptBatcher {
+	pub fn new(delivery: Arc<DeliveryService>, interval: Duration) -> Self {
+		Self {
+			delivery,
+			interval,
+			pending: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Waits for the next batch of the chain and returns the receipt of the
+	/// transaction, or None if it is not mined yet.
+	pub async fn poll(
+		self: &Arc<Self>,
+		tx_hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<Option<TransactionReceipt>, String> {
+		let (sender, receiver) = oneshot::channel();
+		{
+			let mut pending = self.pending.lock().await;
+			let queue = pending.entry(chain_id).or_default();
+			if queue.is_empty() {
+				let batcher = self.clone();
+				tokio::spawn(async move {
+					tokio::time::sleep(batcher.interval).await;
+					batcher.flush(chain_id).await;
+				});
+			}
+			queue.push((tx_hash.clone(), sender));
+		}
+		receiver
+			.await
+			.map_err(|_| "Receipt batch dropped".to_string())?
+	}
+
+	/// Fetches the receipts of all queued transactions of a chain.
+	async fn flush(&self, chain_id: u64) {
+		let queue = self
+			.pending
+			.lock()
+			.await
+			.remove(&chain_id)
+			.unwrap_or_default();
+		if queue.is_empty() {
+			return;
+		}
+		let hashes: Vec<TransactionHash> = queue.iter().map(|(hash, _)| hash.clone()).collect();
+		tracing::debug!(chain_id, count = hashes.len(), "Polling receipts");
+
+		match self.delivery.batch_get_receipts(&hashes, chain_id).await {
+			Ok(receipts) => {
+				let mut receipts = receipts.into_iter();
+				for (_, waiter) in queue {
+					waiter.send(Ok(receipts.next().flatten())).ok();
+				}
+			},
+			Err(e) => {
+				let error = e.to_string();
+				for (_, waiter) in queue {
+					waiter.send(Err(error.clone())).ok();
+				}
+			},
+		}
+	}
+}
+
 /// Monitor for tracking pending blockchain transactions.
 ///
-/// The TransactionMonitor polls transaction status at regular intervals
-/// until confirmation or failure, publishing appropriate events to the
-/// event bus for further processing by the transaction handler.
+/// The TransactionMonitor polls transaction receipts through a shared
+/// [`ReceiptBatcher`] until confirmation or failure, publishing appropriate
+/// events to the event bus for further processing by the transaction handler.
 pub struct TransactionMonitor {
 	delivery: Arc<DeliveryService>,
+	receipts: Arc<ReceiptBatcher>,
 	event_bus: EventBus,
+	gas_budget: Arc<GasBudgetManager>,
+	storage: Arc<StorageService>,
 	timeout_minutes: u64,
+	expiry_grace_period_seconds: u64,
 }
 
 impl TransactionMonitor {
-	pub fn new(delivery: Arc<DeliveryService>, event_bus: EventBus, timeout_minutes: u64) -> Self {
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		receipts: Arc<ReceiptBatcher>,
+		event_bus: EventBus,
+		gas_budget: Arc<GasBudgetManager>,
+		storage: Arc<StorageService>,
+		timeout_minutes: u64,
+		expiry_grace_period_seconds: u64,
+	) -> Self {
 		Self {
 			delivery,
+			receipts,
 			event_bus,
+			gas_budget,
+			storage,
 			timeout_minutes,
+			expiry_grace_period_seconds,
 		}
 	}
-
 	/// Monitors a pending transaction until it is confirmed or fails.
+	///
+	/// Fills still pending once their order's `fill_deadline` passed keep
+	/// being monitored for the expiry grace period, as they may have been
+	/// included on-chain before the deadline. Fills still pending after the
+	/// grace period are reported as failed and no longer monitored.
 	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_hash = %truncate_id(&hex::encode(&tx_hash.0)), tx_type = ?tx_type))]
 	pub async fn monitor(
 		&self,
@@ -38,11 +151,12 @@ impl TransactionMonitor {
 		tx_hash: TransactionHash,
 		tx_type: TransactionType,
 		tx_chain_id: u64,
+		fill_deadline: Option<u64>,
 	) {
 		let monitoring_timeout = tokio::time::Duration::from_secs(self.timeout_minutes * 60);
-		let poll_interval = tokio::time::Duration::from_secs(3);
 
 		let start_time = tokio::time::Instant::now();
+		let mut in_grace_period = false;
 
 		loop {
 			// Check if we've exceeded the timeout
@@ -57,9 +171,9 @@ impl TransactionMonitor {
 				break;
 			}
 
-			// Try to get transaction status
-			match self.delivery.get_status(&tx_hash, tx_chain_id).await {
-				Ok(true) => {
+			// Wait for the next receipt batch of the chain
+			match self.receipts.poll(&tx_hash, tx_chain_id).await {
+				Ok(Some(receipt)) if receipt.success => {
 					// Transaction is confirmed and successful
 					match self
 						.delivery
@@ -68,6 +182,20 @@ impl TransactionMonitor {
 					{
 						Ok(receipt) => {
 							tracing::info!("Confirmed",);
+							let gas_cost = U256::from(receipt.gas_used)
+								.saturating_mul(U256::from(receipt.effective_gas_price));
+							self.gas_budget.record_spend(tx_chain_id, gas_cost).await;
+							if let Err(e) = record_gas_spend(
+								&self.storage,
+								&order_id,
+								&tx_hash,
+								tx_chain_id,
+								gas_cost,
+							)
+							.await
+							{
+								tracing::debug!(error = %e, "Failed to record gas spend");
+							}
 							self.event_bus
 								.publish(SolverEvent::Delivery(
 									DeliveryEvent::TransactionConfirmed {
@@ -91,7 +219,7 @@ impl TransactionMonitor {
 					}
 					break;
 				},
-				Ok(false) => {
+				Ok(Some(_)) => {
 					// Transaction failed
 					self.event_bus
 						.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
@@ -103,20 +231,51 @@ impl TransactionMonitor {
 						.ok();
 					break;
 				},
+				Ok(None) => {
+					let now = current_timestamp();
+					if let Some(deadline) = fill_deadline.filter(|deadline| now > *deadline) {
+						let grace_end = deadline.saturating_add(self.expiry_grace_period_seconds);
+						if now > grace_end {
+							tracing::warn!(
+								"Fill deadline and grace period passed while waiting for transaction, abandoning it"
+							);
+							self.event_bus
+								.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
+									order_id,
+									tx_hash: tx_hash.clone(),
+									tx_type,
+									error: "Fill deadline passed".to_string(),
+								}))
+								.ok();
+							break;
+						}
+						if !in_grace_period {
+							in_grace_period = true;
+							tracing::warn!(
+								remaining_secs = grace_end - now,
+								"Fill deadline passed while waiting for transaction, monitoring it for the grace period"
+							);
+							self.event_bus
+								.publish(SolverEvent::Order(OrderEvent::ExpiryGracePeriod {
+									order_id: order_id.clone(),
+									remaining_seconds: grace_end - now,
+								}))
+								.ok();
+						}
+					}
+					tracing::info!(
+						elapsed_secs = start_time.elapsed().as_secs(),
+						"Waiting for transaction to be mined"
+					);
+				},
 				Err(e) => {
-					// Transaction not yet confirmed or error
-					let message = match &e {
-						DeliveryError::NoImplementationAvailable => {
-							"Waiting for transaction to be mined"
-						},
-						_ => "Checking transaction status",
-					};
-
-					tracing::info!(elapsed_secs = start_time.elapsed().as_secs(), "{}", message);
+					tracing::info!(
+						elapsed_secs = start_time.elapsed().as_secs(),
+						error = %e,
+						"Checking transaction status"
+					);
 				},
 			}
-
-			tokio::time::sleep(poll_interval).await;
 		}
 	}
 }
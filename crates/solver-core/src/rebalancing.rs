@@ -0,0 +1,553 @@
+//! Automatic rebalancing of token inventory between chains.
+//!
+//! Fills spend the solver's tokens on destination chains while claims pay
+//! out on origin chains, so inventory drifts towards the origin chains over
+//! time. The rebalancing service periodically checks the available balance
+//! of the destination token of each configured bridge, and when it drops
+//! below the bridge's threshold, bridges a fixed amount from the source
+//! chain over the canonical bridge. Bridges cool down after a confirmed
+//! transfer so that tokens still in transit are not bridged twice; the
+//! transfers are persisted, so the cooldown survives restarts.
+//!
+//! Only deposits are supported: withdrawals from an OP Stack L2 must be
+//! proven and finalized on L1, which the configuration rejects.
+
+use crate::engine::event_bus::EventBus;
+use crate::inventory::{InventoryError, InventoryManager};
+use alloy_primitives::{hex, Address as AlloyAddress, Bytes, U256};
+use alloy_sol_types::{sol, SolCall};
+use serde::{Deserialize, Serialize};
+use solver_config::BridgeConfig;
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{
+	current_timestamp, with_0x_prefix, Address, SolverEvent, StorageKey, Transaction,
+	TransactionHash,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+
+sol! {
+	/// Canonical token bridge, as implemented by the OP Stack standard bridge.
+	interface IStandardBridge {
+		function bridgeERC20To(
+			address localToken,
+			address remoteToken,
+			address to,
+			uint256 amount,
+			uint32 minGasLimit,
+			bytes extraData
+		) external;
+	}
+
+	interface IERC20Approve {
+		function approve(address spender, uint256 amount) external returns (bool);
+	}
+}
+
+/// Gas limit of the message finalizing a transfer on the destination chain.
+const BRIDGE_MIN_GAS_LIMIT: u32 = 200_000;
+
+/// Errors that can occur while rebalancing.
+#[derive(Debug, Error)]
+pub enum RebalancingError {
+	#[error("Inventory error: {0}")]
+	Inventory(#[from] InventoryError),
+	#[error("Delivery error: {0}")]
+	Delivery(#[from] DeliveryError),
+	#[error("Storage error: {0}")]
+	Storage(#[from] StorageError),
+	#[error("Invalid bridge configuration: {0}")]
+	Config(String),
+	#[error("Bridge transfer {0} reverted")]
+	Reverted(String),
+}
+
+/// A transfer submitted over a bridge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rebalance {
+	pub from_chain: u64,
+	pub to_chain: u64,
+	/// Token on the destination chain (hex with 0x prefix).
+	pub token: String,
+	pub amount: U256,
+	pub tx_hash: TransactionHash,
+}
+
+/// The last transfer over a bridge, stored until its cooldown ends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BridgeTransfer {
+	tx_hash: TransactionHash,
+	initiated_at: u64,
+}
+
+/// Bridges tokens to chains whose available balance runs low.
+pub struct RebalancingService {
+	bridges: Vec<BridgeConfig>,
+	inventory: Arc<InventoryManager>,
+	delivery: Arc<DeliveryService>,
+	storage: Arc<StorageService>,
+	event_bus: EventBus,
+	/// Recipient of bridged tokens on the destination chain.
+	solver_address: Address,
+	cooldown_seconds: u64,
+}
+
+impl RebalancingService {
+	/// Creates a new RebalancingService over the given bridges.
+	pub fn new(
+		bridges: Vec<BridgeConfig>,
+		inventory: Arc<InventoryManager>,
+		delivery: Arc<DeliveryService>,
+		storage: Arc<StorageService>,
+		event_bus: EventBus,
+		solver_address: Address,
+		cooldown_seconds: u64,
+	) -> Self {
+		Self {
+			bridges,
+			inventory,
+			delivery,
+			storage,
+			event_bus,
+			solver_address,
+			cooldown_seconds,
+		}
+	}
+
+	/// Checks every bridge and submits a transfer for each destination whose
+	/// available balance dropped below the bridge's threshold.
+	pub async fn rebalance(&self) -> Vec<Rebalance> {
+		let mut rebalances = Vec::new();
+		for bridge in &self.bridges {
+			match self.check_bridge(bridge).await {
+				Ok(Some(rebalance)) => rebalances.push(rebalance),
+				Ok(None) => {},
+				Err(e) => tracing::warn!(
+					from_chain = bridge.from_chain,
+					to_chain = bridge.to_chain,
+					error = %e,
+					"Failed to rebalance"
+				),
+			}
+		}
+		rebalances
+	}
+
+	/// Bridges tokens over a bridge if its destination runs low.
+	async fn check_bridge(
+		&self,
+		bridge: &BridgeConfig,
+	) -> Result<Option<Rebalance>, RebalancingError> {
+		let now = current_timestamp();
+		if self.cooling_down(bridge, now).await? {
+			return Ok(None);
+		}
+
+		let low_threshold = parse_amount(&bridge.low_threshold)?;
+		let amount = parse_amount(&bridge.amount)?;
+		let available_to = self
+			.inventory
+			.available_balance(bridge.to_chain, &bridge.to_token)
+			.await?;
+		if available_to >= low_threshold {
+			return Ok(None);
+		}
+		let available_from = self
+			.inventory
+			.available_balance(bridge.from_chain, &bridge.from_token)
+			.await?;
+		if available_from < amount {
+			tracing::warn!(
+				from_chain = bridge.from_chain,
+				to_chain = bridge.to_chain,
+				available = %available_from,
+				required = %amount,
+				"Balance on chain {} is low but the source chain cannot cover the transfer",
+				bridge.to_chain
+			);
+			return Ok(None);
+		}
+
+		let tx_hash = self.bridge(bridge, amount).await?;
+		// Record the transfer before waiting for it, so that a restart while
+		// it is pending does not bridge again
+		let key = bridge_key(bridge);
+		let transfer = BridgeTransfer {
+			tx_hash: tx_hash.clone(),
+			initiated_at: now,
+		};
+		if let Err(e) = self
+			.storage
+			.store_with_ttl(
+				StorageKey::Rebalances.as_str(),
+				&key,
+				&transfer,
+				None,
+				Some(Duration::from_secs(self.cooldown_seconds)),
+			)
+			.await
+		{
+			tracing::warn!(bridge = %key, error = %e, "Failed to persist bridge transfer");
+		}
+		let receipt = self
+			.delivery
+			.confirm_with_default(&tx_hash, bridge.from_chain)
+			.await?;
+		if !receipt.success {
+			// Nothing was bridged, so the bridge does not cool down
+			self.storage
+				.remove(StorageKey::Rebalances.as_str(), &key)
+				.await
+				.ok();
+			return Err(RebalancingError::Reverted(with_0x_prefix(&hex::encode(
+				&tx_hash.0,
+			))));
+		}
+
+		let token = with_0x_prefix(&hex::encode(&bridge.to_token.0));
+		tracing::info!(
+			from_chain = bridge.from_chain,
+			to_chain = bridge.to_chain,
+			token = %token,
+			amount = %amount,
+			tx_hash = %with_0x_prefix(&hex::encode(&tx_hash.0)),
+			"Rebalancing transfer confirmed"
+		);
+		self.event_bus
+			.publish(SolverEvent::RebalancingInitiated {
+				from_chain: bridge.from_chain,
+				to_chain: bridge.to_chain,
+				token: token.clone(),
+				amount,
+			})
+			.ok();
+
+		Ok(Some(Rebalance {
+			from_chain: bridge.from_chain,
+			to_chain: bridge.to_chain,
+			token,
+			amount,
+			tx_hash,
+		}))
+	}
+
+	/// Returns whether a bridge transferred tokens less than the cooldown ago.
+	async fn cooling_down(&self, bridge: &BridgeConfig, now: u64) -> Result<bool, StorageError> {
+		match self
+			.storage
+			.retrieve::<BridgeTransfer>(StorageKey::Rebalances.as_str(), &bridge_key(bridge))
+			.await
+		{
+			Ok(transfer) => Ok(now.saturating_sub(transfer.initiated_at) < self.cooldown_seconds),
+			Err(StorageError::NotFound) => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Approves the bridge if needed and submits the transfer.
+	async fn bridge(
+		&self,
+		bridge: &BridgeConfig,
+		amount: U256,
+	) -> Result<TransactionHash, RebalancingError> {
+		let allowance = self
+			.delivery
+			.get_allowance(
+				bridge.from_chain,
+				&hex::encode(&self.solver_address.0),
+				&hex::encode(&bridge.bridge_address.0),
+				&hex::encode(&bridge.from_token.0),
+			)
+			.await?;
+		let allowance = U256::from_str_radix(&allowance, 10).unwrap_or_default();
+		if allowance < amount {
+			let call = IERC20Approve::approveCall {
+				spender: to_alloy(&bridge.bridge_address)?,
+				amount,
+			};
+			let tx_hash = self
+				.delivery
+				.deliver(transaction(
+					bridge.from_chain,
+					&bridge.from_token,
+					call.abi_encode(),
+				))
+				.await?;
+			// The transfer can only be estimated once the approval is mined
+			self.delivery
+				.confirm_with_default(&tx_hash, bridge.from_chain)
+				.await?;
+		}
+
+		let call = IStandardBridge::bridgeERC20ToCall {
+			localToken: to_alloy(&bridge.from_token)?,
+			remoteToken: to_alloy(&bridge.to_token)?,
+			to: to_alloy(&self.solver_address)?,
+			amount,
+			minGasLimit: BRIDGE_MIN_GAS_LIMIT,
+			extraData: Bytes::new(),
+		};
+		Ok(self
+			.delivery
+			.deliver(transaction(
+				bridge.from_chain,
+				&bridge.bridge_address,
+				call.abi_encode(),
+			))
+			.await?)
+	}
+
+	/// Spawns a task that checks the bridges at the given interval.
+	pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(interval);
+			loop {
+				ticker.tick().await;
+				self.rebalance().await;
+			}
+		})
+	}
+}
+
+/// Returns the storage key of the transfers over a bridge.
+fn bridge_key(bridge: &BridgeConfig) -> String {
+	format!(
+		"{}_{}_{}",
+		bridge.from_chain,
+		bridge.to_chain,
+		hex::encode(&bridge.to_token.0)
+	)
+}
+
+/// Parses a decimal amount of the bridge configuration.
+fn parse_amount(amount: &str) -> Result<U256, RebalancingError> {
+	U256::from_str_radix(amount, 10)
+		.map_err(|e| RebalancingError::Config(format!("Invalid amount {}: {}", amount, e)))
+}
+
+/// Converts a configured address into an EVM address.
+fn to_alloy(address: &Address) -> Result<AlloyAddress, RebalancingError> {
+	AlloyAddress::try_from(address.0.as_slice())
+		.map_err(|e| RebalancingError::Config(format!("Invalid address: {}", e)))
+}
+
+/// Builds a transaction calling `to` on a chain, with fees set by delivery.
+fn transaction(chain_id: u64, to: &Address, data: Vec<u8>) -> Transaction {
+	Transaction {
+		chain_id,
+		to: Some(to.clone()),
+		data,
+		value: U256::ZERO,
+		gas_limit: None,
+		gas_price: None,
+		max_fee_per_gas: None,
+		max_priority_fee_per_gas: None,
+		nonce: None,
+		memo: None,
+		authorization_list: None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::engine::token_manager::TokenManager;
+	use async_trait::async_trait;
+	use solver_account::AccountService;
+	use solver_delivery::DeliveryInterface;
+	use solver_storage::implementations::memory::MemoryStorage;
+	use solver_types::{ChainId, ConfigSchema, FeeHistory, TransactionReceipt, TransactionTrace};
+	use std::collections::HashMap;
+	use std::sync::Mutex;
+
+	const PRIVATE_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+	/// Delivery with fixed token balances per chain whose transfers succeed
+	/// unless `revert` is set.
+	struct MockDelivery {
+		balances: HashMap<u64, u64>,
+		revert: bool,
+		submitted: Mutex<Vec<Transaction>>,
+	}
+
+	#[async_trait]
+	impl DeliveryInterface for MockDelivery {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn submit(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+			let mut submitted = self.submitted.lock().unwrap();
+			submitted.push(tx);
+			Ok(TransactionHash(vec![submitted.len() as u8; 32]))
+		}
+
+		async fn wait_for_confirmation(
+			&self,
+			hash: &TransactionHash,
+			chain_id: u64,
+			_confirmations: u64,
+		) -> Result<TransactionReceipt, DeliveryError> {
+			self.get_receipt(hash, chain_id).await
+		}
+
+		async fn get_receipt(
+			&self,
+			hash: &TransactionHash,
+			_chain_id: u64,
+		) -> Result<TransactionReceipt, DeliveryError> {
+			Ok(TransactionReceipt {
+				hash: hash.clone(),
+				block_number: 1,
+				success: !self.revert,
+				gas_used: 0,
+				effective_gas_price: 0,
+			})
+		}
+
+		async fn get_gas_price(&self, _chain_id: u64) -> Result<String, DeliveryError> {
+			Ok("1".to_string())
+		}
+
+		async fn get_balance(
+			&self,
+			_address: &str,
+			_token: Option<&str>,
+			chain_id: u64,
+		) -> Result<String, DeliveryError> {
+			Ok(self.balances[&chain_id].to_string())
+		}
+
+		async fn get_allowance(
+			&self,
+			_owner: &str,
+			_spender: &str,
+			_token_address: &str,
+			_chain_id: u64,
+		) -> Result<String, DeliveryError> {
+			Ok(U256::MAX.to_string())
+		}
+
+		async fn get_nonce(&self, _address: &str, _chain_id: u64) -> Result<u64, DeliveryError> {
+			Ok(0)
+		}
+
+		async fn get_code(&self, _address: &str, _chain_id: u64) -> Result<Vec<u8>, DeliveryError> {
+			Ok(vec![])
+		}
+
+		async fn get_block_number(&self, _chain_id: u64) -> Result<u64, DeliveryError> {
+			Ok(1)
+		}
+
+		async fn get_fee_history(
+			&self,
+			_chain_id: u64,
+			_block_count: u64,
+			_reward_percentiles: &[f64],
+		) -> Result<FeeHistory, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn estimate_gas(&self, _tx: &Transaction) -> Result<TransactionTrace, DeliveryError> {
+			unimplemented!()
+		}
+	}
+
+	fn bridge() -> BridgeConfig {
+		BridgeConfig {
+			from_chain: 1,
+			to_chain: 10,
+			from_token: Address(vec![0xAA; 20]),
+			to_token: Address(vec![0xBB; 20]),
+			bridge_address: Address(vec![0xCC; 20]),
+			low_threshold: "1000".to_string(),
+			amount: "5000".to_string(),
+		}
+	}
+
+	fn service(delivery: Arc<MockDelivery>, storage: Arc<StorageService>) -> RebalancingService {
+		let implementations: HashMap<ChainId, Arc<dyn DeliveryInterface>> = HashMap::from([
+			(ChainId(1), delivery.clone() as Arc<dyn DeliveryInterface>),
+			(ChainId(10), delivery as Arc<dyn DeliveryInterface>),
+		]);
+		let delivery = Arc::new(DeliveryService::new(implementations, 1));
+		let config: toml::Value =
+			toml::from_str(&format!("private_key = \"{}\"", PRIVATE_KEY)).unwrap();
+		let account = Arc::new(AccountService::new(
+			solver_account::implementations::local::create_account(&config).unwrap(),
+		));
+		let token_manager = Arc::new(TokenManager::new(HashMap::new(), delivery.clone(), account));
+		RebalancingService::new(
+			vec![bridge()],
+			Arc::new(InventoryManager::new(token_manager)),
+			delivery,
+			storage,
+			EventBus::new(16),
+			Address(vec![0x11; 20]),
+			3600,
+		)
+	}
+
+	fn memory_storage() -> Arc<StorageService> {
+		Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None))
+	}
+
+	#[tokio::test]
+	async fn test_cooldown_survives_restart() {
+		let delivery = Arc::new(MockDelivery {
+			balances: HashMap::from([(1, 10_000), (10, 500)]),
+			revert: false,
+			submitted: Mutex::new(Vec::new()),
+		});
+		let storage = memory_storage();
+
+		let rebalances = service(delivery.clone(), storage.clone()).rebalance().await;
+		assert_eq!(rebalances.len(), 1);
+		assert_eq!(rebalances[0].amount, U256::from(5000));
+		let submitted = delivery.submitted.lock().unwrap().clone();
+		assert_eq!(submitted.len(), 1);
+		assert_eq!(submitted[0].chain_id, 1);
+		assert_eq!(submitted[0].to, Some(Address(vec![0xCC; 20])));
+
+		// A restarted service does not bridge the tokens in transit again
+		assert!(service(delivery.clone(), storage)
+			.rebalance()
+			.await
+			.is_empty());
+		assert_eq!(delivery.submitted.lock().unwrap().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn test_reverted_transfer_does_not_cool_down() {
+		let delivery = Arc::new(MockDelivery {
+			balances: HashMap::from([(1, 10_000), (10, 500)]),
+			revert: true,
+			submitted: Mutex::new(Vec::new()),
+		});
+		let service = service(delivery.clone(), memory_storage());
+
+		assert!(service.rebalance().await.is_empty());
+		assert!(service.rebalance().await.is_empty());
+		assert_eq!(delivery.submitted.lock().unwrap().len(), 2);
+	}
+
+	#[tokio::test]
+	async fn test_skips_bridges_that_need_no_or_cannot_cover_transfer() {
+		for balances in [[(1, 10_000), (10, 1000)], [(1, 4999), (10, 500)]] {
+			let delivery = Arc::new(MockDelivery {
+				balances: HashMap::from(balances),
+				revert: false,
+				submitted: Mutex::new(Vec::new()),
+			});
+			assert!(service(delivery.clone(), memory_storage())
+				.rebalance()
+				.await
+				.is_empty());
+			assert!(delivery.submitted.lock().unwrap().is_empty());
+		}
+	}
+}
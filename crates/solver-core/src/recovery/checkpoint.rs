@@ -0,0 +1,381 @@
+//! Periodic checkpoints of the active orders for fast recovery.
+//!
+//! Scanning all stored orders on restart is slow on large deployments. The
+//! checkpoint service periodically records the IDs of the active orders, so
+//! recovery only loads those orders plus the orders updated since the
+//! checkpoint was taken. Each checkpoint is built from the previous one in
+//! the same way, so only the first checkpoint scans all orders.
+
+use super::query_active_orders;
+use serde::{Deserialize, Serialize};
+use solver_delivery::DeliveryService;
+use solver_storage::{QueryFilter, StorageError, StorageService};
+use solver_types::{current_timestamp, Order, OrderStatus, StorageKey};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Storage ID of the latest checkpoint.
+const CHECKPOINT_ID: &str = "latest";
+
+/// Snapshot of the solver state used to speed up recovery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolverCheckpoint {
+	/// IDs of the orders that were not finalized or failed.
+	pub active_order_ids: Vec<String>,
+	/// Latest block number per chain ID.
+	pub last_block: HashMap<u64, u64>,
+	/// Timestamp from which the checkpoint reflects the stored orders.
+	pub timestamp: u64,
+	/// Whether the checkpoint was completely written. A checkpoint left
+	/// incomplete by a solver killed while writing it must not be used.
+	pub checkpoint_complete: bool,
+}
+
+/// Loads the latest checkpoint, if a complete one is stored.
+pub async fn load_checkpoint(storage: &StorageService) -> Option<SolverCheckpoint> {
+	match storage
+		.retrieve::<SolverCheckpoint>(StorageKey::Checkpoints.as_str(), CHECKPOINT_ID)
+		.await
+	{
+		Ok(checkpoint) if checkpoint.checkpoint_complete => Some(checkpoint),
+		Ok(_) => {
+			tracing::warn!("Ignoring checkpoint that was not completely written");
+			None
+		},
+		Err(StorageError::NotFound) => None,
+		Err(e) => {
+			tracing::warn!(error = %e, "Failed to load checkpoint");
+			None
+		},
+	}
+}
+
+/// Loads the active orders, starting from a checkpoint if one is given.
+///
+/// With a checkpoint, only the orders it lists and the orders updated since
+/// it was taken are loaded. Without one, all stored orders are scanned.
+pub async fn active_orders(
+	storage: &StorageService,
+	checkpoint: Option<&SolverCheckpoint>,
+) -> Result<Vec<Order>, StorageError> {
+	let Some(checkpoint) = checkpoint else {
+		return query_active_orders(storage).await;
+	};
+
+	let mut orders: HashMap<String, Order> = storage
+		.query_or_tombstone(
+			StorageKey::Orders.as_str(),
+			QueryFilter::GreaterThan(
+				"updated_at".to_string(),
+				serde_json::json!(checkpoint.timestamp.saturating_sub(1)),
+			),
+		)
+		.await?
+		.into_iter()
+		.collect();
+	let missing: Vec<&str> = checkpoint
+		.active_order_ids
+		.iter()
+		.filter(|order_id| !orders.contains_key(*order_id))
+		.map(String::as_str)
+		.collect();
+	for (order_id, result) in storage
+		.retrieve_batch::<Order>(StorageKey::Orders.as_str(), &missing)
+		.await
+	{
+		match result {
+			Ok(order) => {
+				orders.insert(order_id, order);
+			},
+			Err(StorageError::NotFound) => {},
+			// Move corrupted orders aside so they don't fail every recovery
+			Err(StorageError::Serialization(_)) => {
+				match storage
+					.retrieve_or_tombstone::<Order>(StorageKey::Orders.as_str(), &order_id)
+					.await
+				{
+					Ok(order) => {
+						orders.insert(order_id, order);
+					},
+					Err(StorageError::NotFound) => {},
+					Err(e) => return Err(e),
+				}
+			},
+			Err(e) => return Err(e),
+		}
+	}
+
+	Ok(orders
+		.into_values()
+		.filter(|order| {
+			!matches!(
+				order.status,
+				OrderStatus::Finalized | OrderStatus::Failed(_) | OrderStatus::Cancelled
+			)
+		})
+		.collect())
+}
+
+/// Service periodically writing checkpoints of the active orders.
+pub struct CheckpointService {
+	storage: Arc<StorageService>,
+	delivery: Arc<DeliveryService>,
+	/// Chains whose latest block is recorded.
+	chain_ids: Vec<u64>,
+}
+
+impl CheckpointService {
+	/// Creates a new CheckpointService.
+	pub fn new(
+		storage: Arc<StorageService>,
+		delivery: Arc<DeliveryService>,
+		chain_ids: Vec<u64>,
+	) -> Self {
+		Self {
+			storage,
+			delivery,
+			chain_ids,
+		}
+	}
+
+	/// Writes a new checkpoint, building on the previous one.
+	///
+	/// The checkpoint is first marked incomplete, so that a solver killed
+	/// before the complete checkpoint is stored falls back to a full scan.
+	pub async fn write_checkpoint(&self) -> Result<SolverCheckpoint, StorageError> {
+		let previous = load_checkpoint(&self.storage).await;
+		let mut checkpoint = SolverCheckpoint {
+			active_order_ids: Vec::new(),
+			last_block: HashMap::new(),
+			timestamp: current_timestamp(),
+			checkpoint_complete: false,
+		};
+		self.store(&checkpoint).await?;
+
+		checkpoint.active_order_ids = active_orders(&self.storage, previous.as_ref())
+			.await?
+			.into_iter()
+			.map(|order| order.id)
+			.collect();
+		for &chain_id in &self.chain_ids {
+			match self.delivery.get_block_number(chain_id).await {
+				Ok(block) => {
+					checkpoint.last_block.insert(chain_id, block);
+				},
+				Err(e) => {
+					tracing::debug!(chain_id, error = %e, "Failed to get block number for checkpoint");
+				},
+			}
+		}
+		checkpoint.checkpoint_complete = true;
+		self.store(&checkpoint).await?;
+
+		Ok(checkpoint)
+	}
+
+	/// Stores a checkpoint as the latest one.
+	async fn store(&self, checkpoint: &SolverCheckpoint) -> Result<(), StorageError> {
+		self.storage
+			.store(
+				StorageKey::Checkpoints.as_str(),
+				CHECKPOINT_ID,
+				checkpoint,
+				None,
+			)
+			.await
+	}
+
+	/// Spawns a task that writes a checkpoint at the given interval.
+	pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(interval);
+			loop {
+				ticker.tick().await;
+				match self.write_checkpoint().await {
+					Ok(checkpoint) => {
+						tracing::debug!(
+							active_orders = checkpoint.active_order_ids.len(),
+							"Checkpoint written"
+						);
+					},
+					Err(e) => {
+						tracing::warn!(error = %e, "Failed to write checkpoint");
+					},
+				}
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::file::create_storage;
+	use solver_types::{Address, TransactionType};
+
+	fn order(id: &str, status: OrderStatus, updated_at: u64) -> Order {
+		Order {
+			id: id.to_string(),
+			standard: "eip7683".to_string(),
+			created_at: updated_at,
+			updated_at,
+			status,
+			data: serde_json::json!({}),
+			solver_address: Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	async fn store_order(storage: &StorageService, order: &Order) {
+		storage
+			.store(
+				StorageKey::Orders.as_str(),
+				&order.id,
+				order,
+				Some(crate::state::order::order_indexes(order)),
+			)
+			.await
+			.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_active_orders_from_checkpoint() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let backend = create_storage(&toml::Value::Table(config)).unwrap();
+		let storage = StorageService::new(backend, None);
+
+		// Active at checkpoint time, one of them finalized since
+		store_order(&storage, &order("listed", OrderStatus::Pending, 100)).await;
+		store_order(&storage, &order("finalized", OrderStatus::Finalized, 300)).await;
+		// Untouched since before the checkpoint and not listed
+		store_order(&storage, &order("stale", OrderStatus::Created, 100)).await;
+		// Created after the checkpoint
+		store_order(&storage, &order("new", OrderStatus::Created, 250)).await;
+		store_order(
+			&storage,
+			&order("failed", OrderStatus::Failed(TransactionType::Fill), 250),
+		)
+		.await;
+
+		let checkpoint = SolverCheckpoint {
+			active_order_ids: vec![
+				"listed".to_string(),
+				"finalized".to_string(),
+				"removed".to_string(),
+			],
+			last_block: HashMap::new(),
+			timestamp: 200,
+			checkpoint_complete: false,
+		};
+		storage
+			.store(
+				StorageKey::Checkpoints.as_str(),
+				CHECKPOINT_ID,
+				&checkpoint,
+				None,
+			)
+			.await
+			.unwrap();
+		assert!(load_checkpoint(&storage).await.is_none());
+
+		let mut ids: Vec<String> = active_orders(&storage, Some(&checkpoint))
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|order| order.id)
+			.collect();
+		ids.sort();
+		assert_eq!(ids, vec!["listed".to_string(), "new".to_string()]);
+	}
+
+	#[tokio::test]
+	async fn test_active_orders_tombstones_corrupted_orders() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let storage =
+			StorageService::new(create_storage(&toml::Value::Table(config)).unwrap(), None);
+		store_order(&storage, &order("listed", OrderStatus::Pending, 100)).await;
+		// Corrupted orders listed in the checkpoint and updated since
+		storage
+			.store(StorageKey::Orders.as_str(), "corrupt-listed", &1, None)
+			.await
+			.unwrap();
+		storage
+			.store(
+				StorageKey::Orders.as_str(),
+				"corrupt-updated",
+				&1,
+				Some(crate::state::order::order_indexes(&order(
+					"corrupt-updated",
+					OrderStatus::Created,
+					250,
+				))),
+			)
+			.await
+			.unwrap();
+
+		let checkpoint = SolverCheckpoint {
+			active_order_ids: vec!["listed".to_string(), "corrupt-listed".to_string()],
+			last_block: HashMap::new(),
+			timestamp: 200,
+			checkpoint_complete: true,
+		};
+		let orders = active_orders(&storage, Some(&checkpoint)).await.unwrap();
+		assert_eq!(orders.len(), 1);
+		assert_eq!(orders[0].id, "listed");
+
+		for id in ["corrupt-listed", "corrupt-updated"] {
+			assert!(!storage
+				.exists(StorageKey::Orders.as_str(), id)
+				.await
+				.unwrap());
+			assert!(storage
+				.exists(
+					StorageKey::Corrupted.as_str(),
+					&format!("{}:{}", StorageKey::Orders.as_str(), id)
+				)
+				.await
+				.unwrap());
+		}
+
+		// Without a checkpoint, all stored orders are scanned
+		storage
+			.store(
+				StorageKey::Orders.as_str(),
+				"corrupt-scanned",
+				&1,
+				Some(crate::state::order::order_indexes(&order(
+					"corrupt-scanned",
+					OrderStatus::Created,
+					300,
+				))),
+			)
+			.await
+			.unwrap();
+		let orders = active_orders(&storage, None).await.unwrap();
+		assert_eq!(orders.len(), 1);
+		assert!(!storage
+			.exists(StorageKey::Orders.as_str(), "corrupt-scanned")
+			.await
+			.unwrap());
+	}
+}
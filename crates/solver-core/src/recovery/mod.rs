@@ -3,19 +3,27 @@
 //! This module provides functionality to recover orders from persistent storage,
 //! reconcile with blockchain state, and resume processing of active orders.
 
+pub mod checkpoint;
+
+use crate::audit;
 use crate::state::OrderStateMachine;
 use crate::{engine::event_bus::EventBus, monitoring::SettlementMonitor};
+use serde::{Deserialize, Serialize};
 use solver_delivery::DeliveryService;
 use solver_settlement::SettlementService;
-use solver_storage::{QueryFilter, StorageService};
+use solver_storage::{QueryFilter, StorageError, StorageIndexes, StorageService};
 use solver_types::{
-	Intent, Order, OrderEvent, OrderStatus, SettlementEvent, SolverEvent, StorageKey,
-	TransactionType,
+	current_timestamp, Intent, Order, OrderEvent, OrderStatus, SettlementEvent, SolverEvent,
+	StorageKey, TransactionHash, TransactionType,
 };
 use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use tracing::instrument;
 
+/// Number of recovery reports kept in storage.
+const MAX_RECOVERY_REPORTS: usize = 10;
+
 /// Errors that can occur during recovery operations.
 #[derive(Debug, Error)]
 pub enum RecoveryError {
@@ -48,15 +56,61 @@ enum ReconcileResult {
 	Finalized,
 }
 
+impl ReconcileResult {
+	/// Returns the action recovery takes on the order.
+	fn action(&self) -> RecoveredAction {
+		match self {
+			ReconcileResult::NeedsExecution => RecoveredAction::NeedsExecution,
+			ReconcileResult::NeedsFill => RecoveredAction::NeedsFill,
+			ReconcileResult::NeedsClaim { .. } => RecoveredAction::NeedsClaim,
+			ReconcileResult::Failed(tx_type) => RecoveredAction::Failed(*tx_type),
+			ReconcileResult::Finalized => RecoveredAction::Finalized,
+		}
+	}
+}
+
+/// Action taken by recovery to resume an order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveredAction {
+	/// Execution was resumed, as no transaction was sent yet.
+	NeedsExecution,
+	/// The fill was triggered after a confirmed prepare.
+	NeedsFill,
+	/// The claim was queued or monitored after a confirmed fill.
+	NeedsClaim,
+	/// The order was marked failed at the given transaction.
+	Failed(TransactionType),
+	/// The order was moved to finalized after a confirmed claim.
+	Finalized,
+}
+
+/// Recovery outcome of a single order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveredOrderSummary {
+	pub order_id: String,
+	/// Status stored for the order before recovery.
+	pub previous_status: OrderStatus,
+	pub recovered_action: RecoveredAction,
+	/// Time spent reconciling the order with the blockchain.
+	pub reconcile_duration_ms: u64,
+}
+
 /// Report of the recovery operation.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct RecoveryReport {
+	/// Timestamp at which recovery started.
+	#[serde(default)]
+	pub started_at: u64,
 	/// Total number of orders recovered.
 	pub total_orders: usize,
 	/// Number of orphaned intents found.
 	pub orphaned_intents: usize,
 	/// Number of orders reconciled with blockchain.
 	pub reconciled_orders: usize,
+	/// Outcome of each reconciled order.
+	#[serde(default)]
+	pub orders: Vec<RecoveredOrderSummary>,
 }
 
 /// Service responsible for recovering solver state from storage.
@@ -79,7 +133,8 @@ impl RecoveryService {
 	///
 	/// # Arguments
 	///
-	/// * `storage` - Storage service for accessing persisted state
+	/// * `storage` - Storage service for accessing persisted state, scoped to this
+	///   solver when several share a backend so that only its own orders are recovered
 	/// * `state_machine` - Order state machine for status transitions
 	/// * `delivery` - Delivery service for checking transaction status
 	/// * `settlement` - Settlement service for claim operations
@@ -108,7 +163,10 @@ impl RecoveryService {
 	pub async fn recover_state(&self) -> Result<(RecoveryReport, Vec<Intent>), RecoveryError> {
 		tracing::info!("Starting state recovery from storage");
 
-		let mut report = RecoveryReport::default();
+		let mut report = RecoveryReport {
+			started_at: current_timestamp(),
+			..RecoveryReport::default()
+		};
 
 		// Step 1: Load active orders from storage
 		let orders = self.load_active_orders().await?;
@@ -116,6 +174,7 @@ impl RecoveryService {
 
 		if orders.is_empty() {
 			tracing::info!("No active orders to recover");
+			self.store_report(&report).await;
 			return Ok((report, Vec::new()));
 		}
 
@@ -125,12 +184,15 @@ impl RecoveryService {
 		let orphaned_intents = self.recover_orphaned_intents().await?;
 		report.orphaned_intents = orphaned_intents.len();
 
-		// Step 3: Reconcile each order with blockchain and publish recovery events
+		// Step 3: Reconcile each order with blockchain
+		let mut reconciled = Vec::with_capacity(orders.len());
+		let mut reconcile_durations = Vec::with_capacity(orders.len());
 		for order in orders {
+			let started = Instant::now();
 			match self.reconcile_with_blockchain(&order).await {
 				Ok(result) => {
-					self.publish_recovery_event(order, result).await;
-					report.reconciled_orders += 1;
+					reconciled.push((order, result));
+					reconcile_durations.push(started.elapsed().as_millis() as u64);
 				},
 				Err(e) => {
 					tracing::warn!("Failed to reconcile order {}: {}", order.id, e);
@@ -138,6 +200,23 @@ impl RecoveryService {
 			}
 		}
 
+		// Step 4: Fetch missing fill proofs in a single batch
+		self.fetch_missing_fill_proofs(&mut reconciled).await;
+
+		// Step 5: Publish recovery events
+		for ((order, result), reconcile_duration_ms) in
+			reconciled.into_iter().zip(reconcile_durations)
+		{
+			report.orders.push(RecoveredOrderSummary {
+				order_id: order.id.clone(),
+				previous_status: order.status.clone(),
+				recovered_action: result.action(),
+				reconcile_duration_ms,
+			});
+			self.publish_recovery_event(order, result).await;
+			report.reconciled_orders += 1;
+		}
+
 		tracing::info!(
 			"Recovery complete: {} orders recovered, {} orphaned intents, {} reconciled",
 			report.total_orders,
@@ -145,45 +224,40 @@ impl RecoveryService {
 			report.reconciled_orders
 		);
 
+		self.store_report(&report).await;
 		Ok((report, orphaned_intents))
 	}
 
+	/// Stores a recovery report, keeping only the most recent reports.
+	async fn store_report(&self, report: &RecoveryReport) {
+		if let Err(e) = store_recovery_report(&self.storage, report).await {
+			tracing::warn!(error = %e, "Failed to store recovery report");
+		}
+	}
+
 	/// Loads active (non-terminal) orders from storage.
 	///
-	/// This method queries storage for all orders that are not in terminal states
-	/// (Finalized or Failed variants). These orders may need to be resumed or
+	/// Starts from the latest complete checkpoint if one is stored, and scans
+	/// all stored orders otherwise. These orders may need to be resumed or
 	/// have their state reconciled with the blockchain.
 	///
 	/// # Returns
 	///
 	/// A vector of active orders that need recovery processing.
 	async fn load_active_orders(&self) -> Result<Vec<Order>, RecoveryError> {
-		// Define all terminal status values to exclude using proper serialization
-		let non_terminal_statuses = vec![
-			serde_json::to_value(OrderStatus::Finalized)
-				.expect("OrderStatus::Finalized serialization should not fail"),
-			serde_json::to_value(OrderStatus::Failed(TransactionType::Prepare))
-				.expect("OrderStatus::Failed(Prepare) serialization should not fail"),
-			serde_json::to_value(OrderStatus::Failed(TransactionType::Fill))
-				.expect("OrderStatus::Failed(Fill) serialization should not fail"),
-			serde_json::to_value(OrderStatus::Failed(TransactionType::Claim))
-				.expect("OrderStatus::Failed(Claim) serialization should not fail"),
-		];
-
-		// Query for all non-terminal orders
-		let active_orders = self
-			.storage
-			.query::<Order>(
-				StorageKey::Orders.as_str(),
-				QueryFilter::NotIn("status".to_string(), non_terminal_statuses),
-			)
-			.await
-			.map_err(|e| RecoveryError::Storage(e.to_string()))?;
-
-		// Extract just the orders from the (id, order) tuples
-		let orders: Vec<Order> = active_orders.into_iter().map(|(_, order)| order).collect();
+		let checkpoint = checkpoint::load_checkpoint(&self.storage).await;
+		if let Some(checkpoint) = &checkpoint {
+			tracing::info!(
+				active_orders = checkpoint.active_order_ids.len(),
+				timestamp = checkpoint.timestamp,
+				last_block = ?checkpoint.last_block,
+				"Recovering from checkpoint"
+			);
+		}
 
-		Ok(orders)
+		checkpoint::active_orders(&self.storage, checkpoint.as_ref())
+			.await
+			.map_err(|e| RecoveryError::Storage(e.to_string()))
 	}
 
 	/// Recovers intents that were stored but never converted to orders.
@@ -348,6 +422,59 @@ impl RecoveryService {
 		Ok(ReconcileResult::NeedsExecution)
 	}
 
+	/// Fetches the fill proofs of confirmed fills that have none stored yet.
+	///
+	/// Attestations are requested in one batch rather than one call per order,
+	/// which keeps recovery fast for solvers with many pending claims. Fetched
+	/// proofs are persisted; orders whose attestation fails are left without a
+	/// proof and handed to a settlement monitor.
+	async fn fetch_missing_fill_proofs(&self, reconciled: &mut [(Order, ReconcileResult)]) {
+		let pending: Vec<usize> = reconciled
+			.iter()
+			.enumerate()
+			.filter(|(_, (order, result))| {
+				order.fill_tx_hash.is_some()
+					&& matches!(result, ReconcileResult::NeedsClaim { fill_proof: None })
+			})
+			.map(|(index, _)| index)
+			.collect();
+		if pending.is_empty() {
+			return;
+		}
+
+		let requests: Vec<(&Order, &TransactionHash)> = pending
+			.iter()
+			.filter_map(|&index| {
+				let order = &reconciled[index].0;
+				order.fill_tx_hash.as_ref().map(|tx_hash| (order, tx_hash))
+			})
+			.collect();
+		tracing::info!("Fetching fill proofs for {} orders", requests.len());
+		let proofs = self.settlement.get_attestations_batch(&requests).await;
+
+		for (index, proof) in pending.into_iter().zip(proofs) {
+			let (order, result) = &mut reconciled[index];
+			match proof {
+				Ok(proof) => {
+					if let Err(e) = self
+						.state_machine
+						.set_fill_proof(&order.id, proof.clone())
+						.await
+					{
+						tracing::error!("Failed to store fill proof for order {}: {}", order.id, e);
+					}
+					order.fill_proof = Some(proof.clone());
+					*result = ReconcileResult::NeedsClaim {
+						fill_proof: Some(proof),
+					};
+				},
+				Err(e) => {
+					tracing::warn!("Failed to get attestation for order {}: {}", order.id, e);
+				},
+			}
+		}
+	}
+
 	/// Publishes appropriate event based on reconciliation result.
 	///
 	/// This method converts the reconciliation result into the appropriate
@@ -367,6 +494,15 @@ impl RecoveryService {
 					self.event_bus
 						.publish(SolverEvent::Order(OrderEvent::Executing { order, params }))
 						.ok();
+				} else if order.status == OrderStatus::Created {
+					// Deferred by the strategy before the restart, evaluate it again
+					tracing::info!("Re-evaluating deferred order {}", order.id);
+					self.event_bus
+						.publish(SolverEvent::Order(OrderEvent::Deferred {
+							order_id: order.id,
+							retry_after: std::time::Duration::ZERO,
+						}))
+						.ok();
 				} else {
 					tracing::error!("Order {} missing execution params, cannot resume", order.id);
 				}
@@ -394,8 +530,28 @@ impl RecoveryService {
 			ReconcileResult::NeedsClaim { fill_proof } => {
 				// Fill confirmed, check if ready to claim
 				if let Some(proof) = fill_proof {
-					// We have the proof, check if we can claim
-					if self.settlement.can_claim(&order, &proof).await {
+					// We have the proof, check if we can claim. Fills without an
+					// audit record are left to the monitor, which retries it.
+					let audited = match audit::record_proven_fill(
+						&self.storage,
+						&self.delivery,
+						&order,
+						&proof,
+						current_timestamp(),
+					)
+					.await
+					{
+						Ok(_) => true,
+						Err(e) => {
+							tracing::warn!(
+								"Failed to record fill audit for order {}: {}",
+								order.id,
+								e
+							);
+							false
+						},
+					};
+					if audited && self.settlement.can_claim(&order, &proof).await {
 						tracing::info!("Order {} ready for claiming", order.id);
 						self.event_bus
 							.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
@@ -414,6 +570,7 @@ impl RecoveryService {
 
 			ReconcileResult::Failed(tx_type) => {
 				tracing::warn!("Order {} failed at {:?} stage", order.id, tx_type);
+				self.settlement.invalidate_proof(&order.id);
 				// Update order status to failed
 				if let Err(e) = self
 					.state_machine
@@ -426,6 +583,7 @@ impl RecoveryService {
 
 			ReconcileResult::Finalized => {
 				tracing::info!("Order {} already finalized", order.id);
+				self.settlement.invalidate_proof(&order.id);
 				// Ensure proper state transitions to reach Finalized
 				if order.status != OrderStatus::Finalized {
 					// Transition through the proper sequence based on current state
@@ -483,9 +641,9 @@ impl RecoveryService {
 						OrderStatus::Finalized => {
 							// Already finalized, nothing to do
 						},
-						OrderStatus::Failed(_) => {
+						OrderStatus::Failed(_) | OrderStatus::Cancelled => {
 							// Order is failed, don't transition to finalized
-							tracing::warn!("Order {} is in failed state but blockchain shows finalized - data inconsistency", order.id);
+							tracing::warn!("Order {} is in {} state but blockchain shows finalized - data inconsistency", order.id, order.status);
 						},
 					}
 				}
@@ -536,6 +694,8 @@ impl RecoveryService {
 
 			let settlement_monitor = SettlementMonitor::new(
 				self.settlement.clone(),
+				self.delivery.clone(),
+				self.storage.clone(),
 				self.state_machine.clone(),
 				self.event_bus.clone(),
 				self.monitoring_timeout_minutes,
@@ -549,3 +709,229 @@ impl RecoveryService {
 		}
 	}
 }
+
+/// Stores a recovery report and prunes the oldest reports beyond the
+/// [`MAX_RECOVERY_REPORTS`] most recent ones.
+pub async fn store_recovery_report(
+	storage: &StorageService,
+	report: &RecoveryReport,
+) -> Result<(), StorageError> {
+	// Zero-padded millisecond timestamps sort in storage order
+	let millis = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_millis())
+		.unwrap_or_default();
+	storage
+		.store(
+			StorageKey::RecoveryReports.as_str(),
+			&format!("{:020}", millis),
+			report,
+			Some(StorageIndexes::new().with_numeric("started_at", report.started_at)),
+		)
+		.await?;
+
+	let mut ids: Vec<String> = storage
+		.retrieve_all::<RecoveryReport>(StorageKey::RecoveryReports.as_str())
+		.await?
+		.into_iter()
+		.map(|(id, _)| id)
+		.collect();
+	ids.sort_unstable();
+	let excess = ids.len().saturating_sub(MAX_RECOVERY_REPORTS);
+	for id in &ids[..excess] {
+		storage
+			.remove(StorageKey::RecoveryReports.as_str(), id)
+			.await?;
+	}
+	Ok(())
+}
+
+/// Returns the most recent stored recovery report, if any.
+pub async fn last_recovery_report(
+	storage: &StorageService,
+) -> Result<Option<RecoveryReport>, StorageError> {
+	Ok(storage
+		.retrieve_all::<RecoveryReport>(StorageKey::RecoveryReports.as_str())
+		.await?
+		.into_iter()
+		.max_by(|(a, _), (b, _)| a.cmp(b))
+		.map(|(_, report)| report))
+}
+
+/// Queries all active (non-terminal) orders from storage.
+///
+/// This scans the orders namespace for all orders that are not in terminal
+/// states (Finalized or Failed variants).
+async fn query_active_orders(storage: &StorageService) -> Result<Vec<Order>, StorageError> {
+	// Terminal statuses as indexed by the order state machine, which
+	// indexes every failed order under the same value
+	let terminal_statuses = vec![
+		serde_json::json!(OrderStatus::Finalized.to_string()),
+		serde_json::json!(OrderStatus::Failed(TransactionType::Fill).to_string()),
+		serde_json::json!(OrderStatus::Cancelled.to_string()),
+	];
+
+	// Query for all non-terminal orders
+	let active_orders = storage
+		.query_or_tombstone::<Order>(
+			StorageKey::Orders.as_str(),
+			QueryFilter::NotIn("status".to_string(), terminal_statuses),
+		)
+		.await?;
+
+	// Extract just the orders from the (id, order) tuples
+	Ok(active_orders.into_iter().map(|(_, order)| order).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+	use solver_storage::implementations::file::create_storage;
+	use solver_types::{Address, ExecutionParams};
+	use std::collections::HashMap;
+
+	fn order(id: &str, status: OrderStatus) -> Order {
+		Order {
+			id: id.to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 100,
+			updated_at: 100,
+			status,
+			data: serde_json::json!({}),
+			solver_address: Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: Some(ExecutionParams {
+				gas_price: U256::from(1),
+				priority_fee: None,
+				gas_limit_override: None,
+				output_source: Default::default(),
+			}),
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_recovery_reports_each_order() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let storage = Arc::new(StorageService::new(
+			create_storage(&toml::Value::Table(config)).unwrap(),
+			None,
+		));
+		let state_machine = Arc::new(OrderStateMachine::new(storage.clone()));
+
+		let mut filling = order("filling", OrderStatus::Executed);
+		filling.fill_tx_hash = Some(TransactionHash(vec![1; 32]));
+		let mut claiming = order("claiming", OrderStatus::Settled);
+		claiming.claim_tx_hash = Some(TransactionHash(vec![2; 32]));
+		// Deferred by the strategy, so without execution params
+		let mut deferred = order("deferred", OrderStatus::Created);
+		deferred.execution_params = None;
+		for order in [
+			order("pending", OrderStatus::Pending),
+			deferred,
+			filling,
+			claiming,
+			order("finalized", OrderStatus::Finalized),
+		] {
+			state_machine.store_order(&order).await.unwrap();
+		}
+
+		// Without delivery implementations, sent transactions cannot be
+		// confirmed and are reconciled as failed
+		let event_bus = EventBus::new(16);
+		let mut events = event_bus.subscribe();
+		let recovery = RecoveryService::new(
+			storage.clone(),
+			state_machine,
+			Arc::new(DeliveryService::new(HashMap::new(), 1)),
+			Arc::new(SettlementService::new(HashMap::new())),
+			event_bus,
+			1,
+		);
+		let (report, _) = recovery.recover_state().await.unwrap();
+		assert_eq!(report.total_orders, 4);
+		assert_eq!(report.reconciled_orders, 4);
+
+		let stored = last_recovery_report(&storage).await.unwrap().unwrap();
+		let mut actions: Vec<_> = stored
+			.orders
+			.iter()
+			.map(|summary| {
+				(
+					summary.order_id.as_str(),
+					summary.previous_status.clone(),
+					summary.recovered_action.clone(),
+				)
+			})
+			.collect();
+		actions.sort_by(|a, b| a.0.cmp(b.0));
+		assert_eq!(
+			actions,
+			vec![
+				(
+					"claiming",
+					OrderStatus::Settled,
+					RecoveredAction::Failed(TransactionType::Claim)
+				),
+				(
+					"deferred",
+					OrderStatus::Created,
+					RecoveredAction::NeedsExecution
+				),
+				(
+					"filling",
+					OrderStatus::Executed,
+					RecoveredAction::Failed(TransactionType::Fill)
+				),
+				(
+					"pending",
+					OrderStatus::Pending,
+					RecoveredAction::NeedsExecution
+				),
+			]
+		);
+		let mut executing = Vec::new();
+		let mut deferred = Vec::new();
+		while let Ok(event) = events.try_recv() {
+			match event {
+				SolverEvent::Order(OrderEvent::Executing { order, .. }) => executing.push(order.id),
+				SolverEvent::Order(OrderEvent::Deferred {
+					order_id,
+					retry_after,
+				}) => {
+					assert_eq!(retry_after, std::time::Duration::ZERO);
+					deferred.push(order_id);
+				},
+				_ => {},
+			}
+		}
+		assert_eq!(executing, vec!["pending".to_string()]);
+		assert_eq!(deferred, vec!["deferred".to_string()]);
+
+		// Only the most recent reports are kept
+		for _ in 0..MAX_RECOVERY_REPORTS {
+			tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+			store_recovery_report(&storage, &RecoveryReport::default())
+				.await
+				.unwrap();
+		}
+		let reports = storage
+			.retrieve_all::<RecoveryReport>(StorageKey::RecoveryReports.as_str())
+			.await
+			.unwrap();
+		assert_eq!(reports.len(), MAX_RECOVERY_REPORTS);
+		assert!(reports.iter().all(|(_, report)| report.orders.is_empty()));
+	}
+}
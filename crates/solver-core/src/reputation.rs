@@ -0,0 +1,296 @@
+//! Reputation scoring of solver replicas.
+//!
+//! Each replica scores its own fills and shares the score with its peers over
+//! the gossip layer. When gossiping a discovered intent, the replica assigns it
+//! to the live peer with the best reputation, or keeps it when none scores
+//! better. Scores of peers that miss a broadcast decay, halving every broadcast
+//! interval, and peers missing two broadcasts are no longer preferred.
+//!
+//! Scores are only ranked once a replica has made `MIN_RANKED_FILLS` fills, so
+//! a fresh replica keeps its intents until it has a track record, and fresh
+//! peers are not preferred over it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Identifier of a solver replica, as used by the gossip layer.
+pub type SolverId = String;
+
+/// Number of fills a replica needs before its score is ranked.
+const MIN_RANKED_FILLS: u64 = 10;
+
+/// Fill performance of a solver replica.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReputationScore {
+	/// Share of fills that succeeded, between 0 and 1.
+	pub fill_rate: f64,
+	/// Average time from discovery to confirmed fill, in milliseconds.
+	pub avg_fill_time_ms: u64,
+	/// Timestamp at which the score was last reported.
+	pub last_seen: u64,
+	/// Number of fills the score is based on.
+	#[serde(default)]
+	pub fills: u64,
+}
+
+impl ReputationScore {
+	/// Returns whether this score ranks above another one: a higher fill
+	/// rate wins, with faster fills breaking ties.
+	fn ranks_above(&self, other: &ReputationScore) -> bool {
+		match self.fill_rate.partial_cmp(&other.fill_rate) {
+			Some(std::cmp::Ordering::Greater) => true,
+			Some(std::cmp::Ordering::Equal) => self.avg_fill_time_ms < other.avg_fill_time_ms,
+			_ => false,
+		}
+	}
+}
+
+/// Reputation of a solver replica as reported by the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerReputation {
+	pub solver_id: SolverId,
+	/// Whether this is the local replica.
+	pub local: bool,
+	/// Score after decay.
+	pub score: ReputationScore,
+}
+
+/// Fills made by the local replica.
+#[derive(Debug, Default)]
+struct LocalStats {
+	fills: u64,
+	successful_fills: u64,
+	total_fill_time_ms: u64,
+}
+
+/// Tracks the reputation of the local replica and its peers.
+pub struct ReputationService {
+	/// Seconds between score broadcasts, after which a silent peer's score
+	/// starts decaying and over which it halves.
+	interval_seconds: u64,
+	/// Seconds a peer may stay silent before it is no longer preferred.
+	grace_seconds: u64,
+	local: Mutex<LocalStats>,
+	peers: Mutex<HashMap<SolverId, ReputationScore>>,
+}
+
+impl ReputationService {
+	/// Creates a new ReputationService for scores broadcast at the given
+	/// interval.
+	pub fn new(broadcast_interval_seconds: u64) -> Self {
+		Self {
+			interval_seconds: broadcast_interval_seconds.max(1),
+			grace_seconds: broadcast_interval_seconds.saturating_mul(2),
+			local: Mutex::new(LocalStats::default()),
+			peers: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Records the outcome of a fill of the local replica, with the time from
+	/// discovery to confirmation of successful fills.
+	pub fn record_fill(&self, success: bool, fill_time_ms: u64) {
+		let mut local = self.local.lock().unwrap_or_else(|e| e.into_inner());
+		local.fills += 1;
+		if success {
+			local.successful_fills += 1;
+			local.total_fill_time_ms = local.total_fill_time_ms.saturating_add(fill_time_ms);
+		}
+	}
+
+	/// Returns the current score of the local replica.
+	pub fn local_score(&self, now: u64) -> ReputationScore {
+		let local = self.local.lock().unwrap_or_else(|e| e.into_inner());
+		ReputationScore {
+			fill_rate: if local.fills == 0 {
+				0.0
+			} else {
+				local.successful_fills as f64 / local.fills as f64
+			},
+			avg_fill_time_ms: local
+				.total_fill_time_ms
+				.checked_div(local.successful_fills)
+				.unwrap_or_default(),
+			last_seen: now,
+			fills: local.fills,
+		}
+	}
+
+	/// Records the score reported by a peer at `now`.
+	///
+	/// Fill rates outside of `[0, 1]` are clamped.
+	pub fn update_peer(&self, solver_id: SolverId, score: ReputationScore, now: u64) {
+		let fill_rate = if score.fill_rate.is_nan() {
+			0.0
+		} else {
+			score.fill_rate.clamp(0.0, 1.0)
+		};
+		self.peers.lock().unwrap_or_else(|e| e.into_inner()).insert(
+			solver_id,
+			ReputationScore {
+				fill_rate,
+				last_seen: now,
+				..score
+			},
+		);
+	}
+
+	/// Returns the score of a peer decayed for the broadcasts it missed.
+	fn decayed(&self, score: &ReputationScore, now: u64) -> ReputationScore {
+		let silent = now
+			.saturating_sub(score.last_seen)
+			.saturating_sub(self.interval_seconds);
+		ReputationScore {
+			fill_rate: score.fill_rate * 0.5f64.powf(silent as f64 / self.interval_seconds as f64),
+			..score.clone()
+		}
+	}
+
+	/// Returns the peer among `candidates` whose decayed score ranks above the
+	/// local replica, preferring the best one. Returns None if the local
+	/// replica ranks best, has too few fills to be ranked, or no ranked
+	/// candidate reported a score recently.
+	pub fn preferred_peer<'a>(
+		&self,
+		candidates: impl IntoIterator<Item = &'a SolverId>,
+		now: u64,
+	) -> Option<SolverId> {
+		let local = self.local_score(now);
+		if local.fills < MIN_RANKED_FILLS {
+			return None;
+		}
+		let mut best = (None, local);
+		let peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+		for candidate in candidates {
+			let Some(score) = peers.get(candidate) else {
+				continue;
+			};
+			if score.fills < MIN_RANKED_FILLS
+				|| now.saturating_sub(score.last_seen) > self.grace_seconds
+			{
+				continue;
+			}
+			let score = self.decayed(score, now);
+			if score.ranks_above(&best.1) {
+				best = (Some(candidate.clone()), score);
+			}
+		}
+		best.0
+	}
+
+	/// Lists the local replica and all known peers with their decayed scores,
+	/// best first.
+	pub fn snapshot(&self, local_id: &str, now: u64) -> Vec<PeerReputation> {
+		let mut reputations = vec![PeerReputation {
+			solver_id: local_id.to_string(),
+			local: true,
+			score: self.local_score(now),
+		}];
+		let peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+		reputations.extend(peers.iter().map(|(solver_id, score)| PeerReputation {
+			solver_id: solver_id.clone(),
+			local: false,
+			score: self.decayed(score, now),
+		}));
+		reputations.sort_by(|a, b| {
+			b.score
+				.fill_rate
+				.total_cmp(&a.score.fill_rate)
+				.then(a.score.avg_fill_time_ms.cmp(&b.score.avg_fill_time_ms))
+		});
+		reputations
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn score(fill_rate: f64, avg_fill_time_ms: u64) -> ReputationScore {
+		ReputationScore {
+			fill_rate,
+			avg_fill_time_ms,
+			last_seen: 0,
+			fills: MIN_RANKED_FILLS,
+		}
+	}
+
+	/// Returns a replica with `MIN_RANKED_FILLS` fills at a fill rate of 0.5.
+	fn ranked_reputation() -> ReputationService {
+		let reputation = ReputationService::new(30);
+		for i in 0..MIN_RANKED_FILLS {
+			reputation.record_fill(i % 2 == 0, 2000);
+		}
+		reputation
+	}
+
+	#[test]
+	fn test_preferred_peer_decays_when_silent() {
+		let reputation = ranked_reputation();
+		let local = reputation.local_score(1000);
+		assert_eq!(local.fill_rate, 0.5);
+		assert_eq!(local.avg_fill_time_ms, 2000);
+
+		let strong = "strong".to_string();
+		let weak = "weak".to_string();
+		let unknown = "unknown".to_string();
+		reputation.update_peer(strong.clone(), score(0.9, 5000), 1000);
+		reputation.update_peer(weak.clone(), score(0.4, 1000), 1000);
+
+		let candidates = [weak.clone(), strong.clone(), unknown];
+		assert_eq!(
+			reputation.preferred_peer(&candidates, 1030),
+			Some(strong.clone())
+		);
+		assert_eq!(reputation.preferred_peer([&weak], 1030), None);
+
+		// A peer that missed a broadcast decays within the grace period,
+		// by a factor of sqrt(2) 15 seconds past the interval
+		assert_eq!(
+			reputation.preferred_peer(&candidates, 1045),
+			Some(strong.clone())
+		);
+		reputation.update_peer(strong.clone(), score(0.6, 5000), 1000);
+		assert_eq!(reputation.preferred_peer(&candidates, 1045), None);
+
+		// Peers silent for longer than the grace period are not preferred
+		reputation.update_peer(strong.clone(), score(0.9, 5000), 1000);
+		assert_eq!(reputation.preferred_peer(&candidates, 1061), None);
+
+		// Their listed score halves every broadcast interval
+		let snapshot = reputation.snapshot("local", 1060);
+		let strong_score = snapshot
+			.iter()
+			.find(|peer| peer.solver_id == strong)
+			.unwrap();
+		assert!((strong_score.score.fill_rate - 0.45).abs() < 1e-9);
+		assert!(snapshot[0].local);
+	}
+
+	#[test]
+	fn test_unranked_replicas_keep_their_intents() {
+		// A fresh replica processes intents itself to build a track record
+		let reputation = ReputationService::new(30);
+		let strong = "strong".to_string();
+		reputation.update_peer(strong.clone(), score(1.0, 1000), 1000);
+		assert_eq!(reputation.preferred_peer([&strong], 1000), None);
+
+		// Fresh peers, or peers reporting few fills, are not preferred
+		let reputation = ranked_reputation();
+		reputation.update_peer(
+			strong.clone(),
+			ReputationScore {
+				fills: 1,
+				..score(1.0, 1000)
+			},
+			1000,
+		);
+		assert_eq!(reputation.preferred_peer([&strong], 1000), None);
+
+		// Reported fill rates are clamped
+		reputation.update_peer(strong.clone(), score(f64::NAN, 1000), 1000);
+		assert_eq!(reputation.snapshot("local", 1000)[1].score.fill_rate, 0.0);
+		reputation.update_peer(strong.clone(), score(5.0, 1000), 1000);
+		assert_eq!(reputation.snapshot("local", 1000)[0].score.fill_rate, 1.0);
+	}
+}
@@ -0,0 +1,110 @@
+//! Dead letter queue of orders set aside after a failure.
+//!
+//! Orders whose transaction would fail are recorded here instead of being
+//! submitted, so that operators can inspect them and retry once the cause has
+//! been addressed through the admin API. Recording an order does not change
+//! its status.
+
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{current_timestamp, StorageKey, TransactionType};
+
+/// Order set aside with the reason its transaction was not submitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+	/// ID of the order.
+	pub order_id: String,
+	/// Type of the transaction that was not submitted.
+	pub tx_type: TransactionType,
+	/// Why the transaction was not submitted.
+	pub reason: String,
+	/// Timestamp at which the order was set aside.
+	pub failed_at: u64,
+}
+
+/// Records an order in the dead letter queue, replacing any previous entry.
+pub async fn push_dead_letter(
+	storage: &StorageService,
+	order_id: &str,
+	tx_type: TransactionType,
+	reason: String,
+) -> Result<(), StorageError> {
+	let dead_letter = DeadLetter {
+		order_id: order_id.to_string(),
+		tx_type,
+		reason,
+		failed_at: current_timestamp(),
+	};
+	storage
+		.store(
+			StorageKey::DeadLetters.as_str(),
+			order_id,
+			&dead_letter,
+			None,
+		)
+		.await
+}
+
+/// Lists the orders in the dead letter queue, the most recent first.
+pub async fn dead_letters(storage: &StorageService) -> Result<Vec<DeadLetter>, StorageError> {
+	let mut dead_letters: Vec<DeadLetter> = storage
+		.retrieve_all(StorageKey::DeadLetters.as_str())
+		.await?
+		.into_iter()
+		.map(|(_, dead_letter)| dead_letter)
+		.collect();
+	dead_letters.sort_by(|a, b| b.failed_at.cmp(&a.failed_at));
+	Ok(dead_letters)
+}
+
+/// Removes an order from the dead letter queue and returns its entry.
+///
+/// Fails with `StorageError::NotFound` if the order is not in the queue.
+pub async fn take_dead_letter(
+	storage: &StorageService,
+	order_id: &str,
+) -> Result<DeadLetter, StorageError> {
+	let dead_letter = storage
+		.retrieve(StorageKey::DeadLetters.as_str(), order_id)
+		.await?;
+	storage
+		.remove(StorageKey::DeadLetters.as_str(), order_id)
+		.await?;
+	Ok(dead_letter)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	#[tokio::test]
+	async fn test_push_dead_letter() {
+		let storage = StorageService::new(Box::new(MemoryStorage::new()), None);
+		push_dead_letter(
+			&storage,
+			"order-1",
+			TransactionType::Claim,
+			"execution reverted".to_string(),
+		)
+		.await
+		.unwrap();
+
+		let dead_letter: DeadLetter = storage
+			.retrieve(StorageKey::DeadLetters.as_str(), "order-1")
+			.await
+			.unwrap();
+		assert_eq!(dead_letter.tx_type, TransactionType::Claim);
+		assert_eq!(dead_letter.reason, "execution reverted");
+
+		// Taking the entry removes it from the queue
+		assert_eq!(dead_letters(&storage).await.unwrap().len(), 1);
+		let taken = take_dead_letter(&storage, "order-1").await.unwrap();
+		assert_eq!(taken.order_id, "order-1");
+		assert!(dead_letters(&storage).await.unwrap().is_empty());
+		assert!(matches!(
+			take_dead_letter(&storage, "order-1").await,
+			Err(StorageError::NotFound)
+		));
+	}
+}
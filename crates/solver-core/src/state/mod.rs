@@ -4,6 +4,8 @@
 //! lifecycle transitions and persistence, ensuring valid state changes and
 //! maintaining data consistency.
 
+pub mod dead_letter;
 pub mod order;
+pub mod quote;
 
 pub use order::{OrderStateError, OrderStateMachine};
@@ -4,13 +4,50 @@
 //! valid lifecycle states: Created -> Pending -> Executed -> Settled -> Finalized.
 //! Also handles failure states and provides utilities for updating order fields.
 
+use alloy_primitives::hex;
 use once_cell::sync::Lazy;
 use solver_storage::{StorageIndexes, StorageService};
-use solver_types::{Order, OrderStatus, StorageKey, TransactionType};
+use solver_types::{
+	with_0x_prefix, Eip7683OrderData, Order, OrderStatus, StorageKey, TransactionType,
+};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
+use tokio::sync::Mutex;
+
+/// Builds the storage indexes of an order.
+///
+/// The status index serves recovery queries and the timestamp indexes serve
+/// range queries. Orders are also indexed by standard, origin chain and first
+/// input token, and by the composite `(chain_id, token, timestamp_hour)` key
+/// used by order analytics.
+pub(crate) fn order_indexes(order: &Order) -> StorageIndexes {
+	let mut indexes = StorageIndexes::new()
+		.with_field("status", order.status.to_string())
+		.with_field("standard", &order.standard)
+		.with_numeric("created_at", order.created_at)
+		.with_numeric("updated_at", order.updated_at);
+
+	let Some(chain_id) = order.input_chain_ids.first() else {
+		return indexes;
+	};
+	indexes = indexes.with_field("chain_id", chain_id);
+
+	let token = serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+		.ok()
+		.and_then(|data| data.inputs.first().map(|[token, _]| *token))
+		.map(|token| with_0x_prefix(&hex::encode(&token.to_be_bytes::<32>()[12..])));
+	if let Some(token) = token {
+		indexes = indexes.with_field("token", &token).with_sorted(
+			"chain_token_hour",
+			format!("{}:{}:{:010}", chain_id, token, order.created_at / 3600),
+		);
+	}
+	indexes
+}
 
 /// Errors that can occur during order state management.
 ///
@@ -28,14 +65,32 @@ pub enum OrderStateError {
 	TimeError(String),
 }
 
+/// Number of locks order updates are striped over.
+const UPDATE_LOCK_STRIPES: usize = 64;
+
 /// Manages order state transitions and persistence
+///
+/// Updates of an order are serialized, so a transition validated against the
+/// stored status cannot be overwritten by a concurrent update.
 pub struct OrderStateMachine {
 	storage: Arc<StorageService>,
+	/// Locks serializing the read-modify-write of orders, by order ID hash.
+	update_locks: Vec<Mutex<()>>,
 }
 
 impl OrderStateMachine {
 	pub fn new(storage: Arc<StorageService>) -> Self {
-		Self { storage }
+		Self {
+			storage,
+			update_locks: (0..UPDATE_LOCK_STRIPES).map(|_| Mutex::new(())).collect(),
+		}
+	}
+
+	/// Returns the lock serializing updates of an order.
+	fn update_lock(&self, order_id: &str) -> &Mutex<()> {
+		let mut hasher = DefaultHasher::new();
+		order_id.hash(&mut hasher);
+		&self.update_locks[hasher.finish() as usize % self.update_locks.len()]
 	}
 
 	/// Updates an order with a closure and persists it
@@ -47,12 +102,48 @@ impl OrderStateMachine {
 	where
 		F: FnOnce(&mut Order),
 	{
-		let mut order: Order = self
-			.storage
+		let _guard = self.update_lock(order_id).lock().await;
+		let order = self.load_order(order_id).await?;
+		self.store_update(order, updater).await
+	}
+
+	/// Updates an order with a closure if it satisfies `condition`, checked
+	/// atomically with the update.
+	///
+	/// Returns None without updating the order if the condition is not met.
+	pub async fn update_order_if<C, F>(
+		&self,
+		order_id: &str,
+		condition: C,
+		updater: F,
+	) -> Result<Option<Order>, OrderStateError>
+	where
+		C: FnOnce(&Order) -> bool,
+		F: FnOnce(&mut Order),
+	{
+		let _guard = self.update_lock(order_id).lock().await;
+		let order = self.load_order(order_id).await?;
+		if !condition(&order) {
+			return Ok(None);
+		}
+		self.store_update(order, updater).await.map(Some)
+	}
+
+	/// Loads an order from storage.
+	async fn load_order(&self, order_id: &str) -> Result<Order, OrderStateError> {
+		self.storage
 			.retrieve(StorageKey::Orders.as_str(), order_id)
 			.await
-			.map_err(|e| OrderStateError::Storage(e.to_string()))?;
+			.map_err(|e| OrderStateError::Storage(e.to_string()))
+	}
 
+	/// Applies an update to a loaded order and persists it.
+	///
+	/// Must be called while holding the order's update lock.
+	async fn store_update<F>(&self, mut order: Order, updater: F) -> Result<Order, OrderStateError>
+	where
+		F: FnOnce(&mut Order),
+	{
 		// Apply the update
 		updater(&mut order);
 
@@ -62,11 +153,13 @@ impl OrderStateMachine {
 			.map_err(|e| OrderStateError::TimeError(e.to_string()))?
 			.as_secs();
 
-		// Update with status index
-		let indexes = StorageIndexes::new().with_field("status", order.status.to_string());
-
 		self.storage
-			.update(StorageKey::Orders.as_str(), order_id, &order, Some(indexes))
+			.update(
+				StorageKey::Orders.as_str(),
+				&order.id,
+				&order,
+				Some(order_indexes(&order)),
+			)
 			.await
 			.map_err(|e| OrderStateError::Storage(e.to_string()))?;
 
@@ -79,11 +172,8 @@ impl OrderStateMachine {
 		order_id: &str,
 		new_status: OrderStatus,
 	) -> Result<Order, OrderStateError> {
-		let order: Order = self
-			.storage
-			.retrieve(StorageKey::Orders.as_str(), order_id)
-			.await
-			.map_err(|e| OrderStateError::Storage(e.to_string()))?;
+		let _guard = self.update_lock(order_id).lock().await;
+		let order = self.load_order(order_id).await?;
 
 		// Validate state transition
 		if !Self::is_valid_transition(&order.status, &new_status) {
@@ -93,7 +183,7 @@ impl OrderStateMachine {
 			});
 		}
 
-		self.update_order_with(order_id, |o| {
+		self.store_update(order, |o| {
 			o.status = new_status;
 		})
 		.await
@@ -109,6 +199,7 @@ impl OrderStateMachine {
 			Settled,
 			Finalized,
 			Failed,
+			Cancelled,
 		}
 
 		// Static transition table - each state maps to allowed next states
@@ -117,8 +208,14 @@ impl OrderStateMachine {
 				let mut m = HashMap::new();
 				m.insert(
 					OrderStatusKind::Created,
-					HashSet::from([OrderStatusKind::Pending, OrderStatusKind::Failed]),
+					HashSet::from([
+						OrderStatusKind::Pending,
+						OrderStatusKind::Failed,
+						OrderStatusKind::Cancelled,
+					]),
 				);
+				// Pending orders have an execution in flight and can no
+				// longer be cancelled
 				m.insert(
 					OrderStatusKind::Pending,
 					HashSet::from([OrderStatusKind::Executed, OrderStatusKind::Failed]),
@@ -133,6 +230,7 @@ impl OrderStateMachine {
 				);
 				m.insert(OrderStatusKind::Failed, HashSet::new()); // terminal
 				m.insert(OrderStatusKind::Finalized, HashSet::new()); // terminal
+				m.insert(OrderStatusKind::Cancelled, HashSet::new()); // terminal
 				m
 			});
 
@@ -145,6 +243,7 @@ impl OrderStateMachine {
 				OrderStatus::Settled => OrderStatusKind::Settled,
 				OrderStatus::Finalized => OrderStatusKind::Finalized,
 				OrderStatus::Failed(_) => OrderStatusKind::Failed,
+				OrderStatus::Cancelled => OrderStatusKind::Cancelled,
 			}
 		};
 
@@ -165,11 +264,29 @@ impl OrderStateMachine {
 
 	/// Stores a new order with indexed status
 	pub async fn store_order(&self, order: &Order) -> Result<(), OrderStateError> {
-		// Store with status index for recovery queries
-		let indexes = StorageIndexes::new().with_field("status", order.status.to_string());
+		self.storage
+			.store(
+				StorageKey::Orders.as_str(),
+				&order.id,
+				order,
+				Some(order_indexes(order)),
+			)
+			.await
+			.map_err(|e| OrderStateError::Storage(e.to_string()))
+	}
 
+	/// Stores a new order unless an order with the same ID already exists.
+	///
+	/// Returns `false` if the order was already stored, e.g. by another
+	/// discovery source racing on the same order.
+	pub async fn store_order_if_absent(&self, order: &Order) -> Result<bool, OrderStateError> {
 		self.storage
-			.store(StorageKey::Orders.as_str(), &order.id, order, Some(indexes))
+			.store_if_absent(
+				StorageKey::Orders.as_str(),
+				&order.id,
+				order,
+				Some(order_indexes(order)),
+			)
 			.await
 			.map_err(|e| OrderStateError::Storage(e.to_string()))
 	}
@@ -183,6 +300,9 @@ impl OrderStateMachine {
 	) -> Result<Order, OrderStateError> {
 		self.update_order_with(order_id, |order| match tx_type {
 			TransactionType::Prepare => order.prepare_tx_hash = Some(tx_hash),
+			TransactionType::Commit => order.commit_tx_hash = Some(tx_hash),
+			// Reveals are tracked with the fill commitment by the strategy
+			TransactionType::Reveal => {},
 			TransactionType::Fill => order.fill_tx_hash = Some(tx_hash),
 			TransactionType::Claim => order.claim_tx_hash = Some(tx_hash),
 		})
@@ -213,3 +333,88 @@ impl OrderStateMachine {
 		.await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+	use solver_types::Address;
+
+	fn order(status: OrderStatus) -> Order {
+		Order {
+			id: "order-1".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status,
+			data: serde_json::json!({}),
+			solver_address: Address(vec![0; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![1],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_order_is_not_started() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		storage
+			.store(
+				StorageKey::Orders.as_str(),
+				"order-1",
+				&order(OrderStatus::Created),
+				None,
+			)
+			.await
+			.unwrap();
+		let state_machine = Arc::new(OrderStateMachine::new(storage));
+
+		// Cancelling and starting the execution race on the same order
+		let cancel = {
+			let state_machine = state_machine.clone();
+			tokio::spawn(async move {
+				state_machine
+					.transition_order_status("order-1", OrderStatus::Cancelled)
+					.await
+			})
+		};
+		let start = {
+			let state_machine = state_machine.clone();
+			tokio::spawn(async move {
+				state_machine
+					.update_order_if(
+						"order-1",
+						|o| o.status == OrderStatus::Created,
+						|o| o.status = OrderStatus::Pending,
+					)
+					.await
+			})
+		};
+		let cancelled = cancel.await.unwrap().is_ok();
+		let started = start.await.unwrap().unwrap().is_some();
+
+		// Exactly one of them wins, and the stored status reflects the winner
+		assert!(cancelled != started);
+		let stored = state_machine.get_order("order-1").await.unwrap();
+		let expected = if cancelled {
+			OrderStatus::Cancelled
+		} else {
+			OrderStatus::Pending
+		};
+		assert_eq!(stored.status, expected);
+
+		// Pending orders can no longer be cancelled
+		if started {
+			assert!(state_machine
+				.transition_order_status("order-1", OrderStatus::Cancelled)
+				.await
+				.is_err());
+		}
+	}
+}
@@ -0,0 +1,261 @@
+//! Quote persistence.
+//!
+//! Quotes are indexed by the chains and tokens of their first input and
+//! output and by their expiry, so that the quotes of a token pair can be
+//! listed. Each order executed against a quote claims one of its fill slots,
+//! which are created atomically so that concurrent orders cannot exceed the
+//! configured number of fills.
+
+use alloy_primitives::hex;
+use solver_storage::{StorageError, StorageIndexes, StorageService};
+use solver_types::{current_timestamp, with_0x_prefix, InteropAddress, Quote, StorageKey};
+use std::time::Duration;
+
+/// Builds the storage indexes of a quote.
+///
+/// Token addresses are indexed in lowercase hex form. Quotes without an
+/// expiry are indexed as never expiring.
+pub fn quote_indexes(quote: &Quote) -> StorageIndexes {
+	let mut indexes =
+		StorageIndexes::new().with_numeric("expires_at", quote.valid_until.unwrap_or(u64::MAX));
+
+	if let Some(input) = quote.details.available_inputs.first() {
+		if let Some((chain_id, token)) = chain_and_token(&input.asset) {
+			indexes = indexes
+				.with_field("input_chain_id", chain_id)
+				.with_field("input_token", token);
+		}
+	}
+	if let Some(output) = quote.details.requested_outputs.first() {
+		if let Some((chain_id, token)) = chain_and_token(&output.asset) {
+			indexes = indexes
+				.with_field("output_chain_id", chain_id)
+				.with_field("output_token", token);
+		}
+	}
+	indexes
+}
+
+/// Stores a quote with its indexes until it expires.
+pub async fn store_quote(
+	storage: &StorageService,
+	quote: &Quote,
+	ttl: Duration,
+) -> Result<(), StorageError> {
+	storage
+		.store_with_ttl(
+			StorageKey::Quotes.as_str(),
+			&quote.quote_id,
+			quote,
+			Some(quote_indexes(quote)),
+			Some(ttl),
+		)
+		.await
+}
+
+/// Claims a fill slot of a quote for an order executed against it.
+///
+/// Returns the number of fills of the quote including this one, or `None`
+/// if all `max_fills` slots are taken. Slots expire with the quote.
+pub async fn record_quote_fill(
+	storage: &StorageService,
+	quote_id: &str,
+	order_id: &str,
+	max_fills: u32,
+) -> Result<Option<u32>, StorageError> {
+	let quote: Quote = storage
+		.retrieve(StorageKey::Quotes.as_str(), quote_id)
+		.await?;
+	let ttl = quote
+		.valid_until
+		.map(|valid_until| Duration::from_secs(valid_until.saturating_sub(current_timestamp())));
+
+	for slot in 0..max_fills {
+		if storage
+			.store_if_absent_with_ttl(
+				StorageKey::QuoteFills.as_str(),
+				&fill_slot_key(quote_id, slot),
+				&order_id,
+				None,
+				ttl,
+			)
+			.await?
+		{
+			return Ok(Some(slot + 1));
+		}
+	}
+	Ok(None)
+}
+
+/// Returns the number of orders executed against a quote, counting at most
+/// `max_fills`.
+///
+/// Slots are claimed in order, so the first free slot is the fill count.
+pub async fn quote_fill_count(
+	storage: &StorageService,
+	quote_id: &str,
+	max_fills: u32,
+) -> Result<u32, StorageError> {
+	for slot in 0..max_fills {
+		if !storage
+			.exists(
+				StorageKey::QuoteFills.as_str(),
+				&fill_slot_key(quote_id, slot),
+			)
+			.await?
+		{
+			return Ok(slot);
+		}
+	}
+	Ok(max_fills)
+}
+
+/// Returns whether the first input and output of a quote are on the given
+/// chains and tokens. Token addresses are compared case-insensitively.
+pub fn quote_matches_pair(quote: &Quote, input: (u64, &str), output: (u64, &str)) -> bool {
+	let matches = |asset: Option<&InteropAddress>, (chain_id, token): (u64, &str)| {
+		asset
+			.and_then(chain_and_token)
+			.is_some_and(|(asset_chain, asset_token)| {
+				asset_chain == chain_id
+					&& asset_token
+						.eq_ignore_ascii_case(&with_0x_prefix(token.trim_start_matches("0x")))
+			})
+	};
+	matches(
+		quote
+			.details
+			.available_inputs
+			.first()
+			.map(|input| &input.asset),
+		input,
+	) && matches(
+		quote
+			.details
+			.requested_outputs
+			.first()
+			.map(|output| &output.asset),
+		output,
+	)
+}
+
+/// Returns the storage ID of a fill slot of a quote.
+fn fill_slot_key(quote_id: &str, slot: u32) -> String {
+	format!("{}:{}", quote_id, slot)
+}
+
+/// Returns the chain ID and lowercase hex address of an EVM asset.
+fn chain_and_token(asset: &InteropAddress) -> Option<(u64, String)> {
+	let chain_id = asset.ethereum_chain_id().ok()?;
+	let token = asset.ethereum_address().ok()?;
+	Some((chain_id, with_0x_prefix(&hex::encode(token))))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{Address, U256};
+	use solver_storage::{implementations::memory::MemoryStorage, IndexType};
+	use solver_types::{AvailableInput, QuoteDetails, RequestedOutput};
+	use std::sync::Arc;
+
+	fn test_quote() -> Quote {
+		let user = InteropAddress::new_ethereum(1, Address::repeat_byte(0x01));
+		Quote {
+			orders: Vec::new(),
+			details: QuoteDetails {
+				requested_outputs: vec![RequestedOutput {
+					receiver: user.clone(),
+					asset: InteropAddress::new_ethereum(10, Address::repeat_byte(0xBB)),
+					amount: U256::from(1),
+					calldata: None,
+				}],
+				available_inputs: vec![AvailableInput {
+					user: user.clone(),
+					asset: InteropAddress::new_ethereum(1, Address::repeat_byte(0xAA)),
+					amount: U256::from(1),
+					lock: None,
+				}],
+			},
+			valid_until: Some(current_timestamp() + 300),
+			eta: None,
+			quote_id: "quote-1".to_string(),
+			provider: "oif-solver".to_string(),
+			fill_count: 0,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_quote_indexes_and_fill_count() {
+		let quote = test_quote();
+		let indexes = quote_indexes(&quote);
+		assert_eq!(
+			indexes.fields.get("input_chain_id"),
+			Some(&IndexType::Exact(serde_json::json!(1)))
+		);
+		assert_eq!(
+			indexes.fields.get("output_token"),
+			Some(&IndexType::Exact(serde_json::json!(format!(
+				"0x{}",
+				"bb".repeat(20)
+			))))
+		);
+		assert_eq!(
+			indexes.fields.get("expires_at"),
+			Some(&IndexType::Numeric(quote.valid_until.unwrap()))
+		);
+
+		let storage = StorageService::new(Box::new(MemoryStorage::new()), None);
+		store_quote(&storage, &quote, Duration::from_secs(300))
+			.await
+			.unwrap();
+		assert_eq!(quote_fill_count(&storage, "quote-1", 2).await.unwrap(), 0);
+		assert_eq!(
+			record_quote_fill(&storage, "quote-1", "order-1", 2)
+				.await
+				.unwrap(),
+			Some(1)
+		);
+		assert_eq!(
+			record_quote_fill(&storage, "quote-1", "order-2", 2)
+				.await
+				.unwrap(),
+			Some(2)
+		);
+		assert_eq!(quote_fill_count(&storage, "quote-1", 2).await.unwrap(), 2);
+		assert!(record_quote_fill(&storage, "missing", "order-3", 2)
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_concurrent_fills_never_exceed_max_fills() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		store_quote(&storage, &test_quote(), Duration::from_secs(300))
+			.await
+			.unwrap();
+
+		let fills = futures::future::join_all((0..10).map(|i| {
+			let storage = storage.clone();
+			async move {
+				record_quote_fill(&storage, "quote-1", &format!("order-{}", i), 3)
+					.await
+					.unwrap()
+			}
+		}))
+		.await;
+		let mut counts: Vec<u32> = fills.into_iter().flatten().collect();
+		counts.sort();
+		assert_eq!(counts, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn test_quote_matches_pair() {
+		let quote = test_quote();
+		let input = format!("0x{}", "AA".repeat(20));
+		let output = format!("0x{}", "bb".repeat(20));
+		assert!(quote_matches_pair(&quote, (1, &input), (10, &output)));
+		assert!(!quote_matches_pair(&quote, (2, &input), (10, &output)));
+		assert!(!quote_matches_pair(&quote, (1, &output), (10, &input)));
+	}
+}
@@ -0,0 +1,375 @@
+//! Telemetry export to external metric sinks.
+//!
+//! The engine reports counters, gauges and histograms about intents, orders,
+//! transactions and settlements to an optional sink such as StatsD or
+//! InfluxDB, for deployments that do not scrape metrics.
+
+use solver_config::TelemetrySinkConfig;
+use solver_types::{DeliveryEvent, OrderEvent, SettlementEvent, SolverEvent, TransactionType};
+use std::net::UdpSocket;
+use thiserror::Error;
+
+/// Errors that can occur when creating a telemetry sink.
+#[derive(Debug, Error)]
+pub enum TelemetryError {
+	#[error("Connection error: {0}")]
+	Connection(String),
+}
+
+/// Trait defining the interface for telemetry sinks.
+///
+/// Recording must not block: sinks send metrics in the background and drop
+/// them on failure rather than slowing down order processing.
+pub trait TelemetrySink: Send + Sync {
+	/// Increments a counter by `value`.
+	fn record_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]);
+
+	/// Sets a gauge to `value`.
+	fn record_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]);
+
+	/// Records a sample of a distribution.
+	fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]);
+}
+
+/// Creates the sink selected in the telemetry configuration, if any.
+pub fn create_sink(
+	config: &TelemetrySinkConfig,
+) -> Result<Option<Box<dyn TelemetrySink>>, TelemetryError> {
+	match config {
+		TelemetrySinkConfig::None => Ok(None),
+		TelemetrySinkConfig::Statsd { address, prefix } => {
+			Ok(Some(Box::new(StatsdSink::new(address, prefix.clone())?)))
+		},
+		TelemetrySinkConfig::Influxdb {
+			url,
+			org,
+			bucket,
+			token,
+			prefix,
+		} => Ok(Some(Box::new(InfluxDbSink::new(
+			url,
+			org,
+			bucket,
+			token.clone(),
+			prefix.clone(),
+		)))),
+	}
+}
+
+/// Sink sending metrics to a StatsD server over UDP.
+///
+/// Labels are sent as DogStatsD-style tags.
+pub struct StatsdSink {
+	socket: UdpSocket,
+	prefix: String,
+}
+
+impl StatsdSink {
+	/// Creates a new StatsdSink sending to `address`.
+	pub fn new(address: &str, prefix: String) -> Result<Self, TelemetryError> {
+		let socket = UdpSocket::bind("0.0.0.0:0")
+			.and_then(|socket| {
+				socket.set_nonblocking(true)?;
+				socket.connect(address)?;
+				Ok(socket)
+			})
+			.map_err(|e| TelemetryError::Connection(format!("{}: {}", address, e)))?;
+
+		Ok(Self { socket, prefix })
+	}
+
+	fn send(&self, name: &str, value: &str, kind: &str, labels: &[(&str, &str)]) {
+		let line = statsd_line(&self.prefix, name, value, kind, labels);
+		if let Err(e) = self.socket.send(line.as_bytes()) {
+			tracing::debug!(metric = name, error = %e, "Failed to send StatsD metric");
+		}
+	}
+}
+
+impl TelemetrySink for StatsdSink {
+	fn record_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+		self.send(name, &value.to_string(), "c", labels);
+	}
+
+	fn record_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+		self.send(name, &value.to_string(), "g", labels);
+	}
+
+	fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+		self.send(name, &value.to_string(), "h", labels);
+	}
+}
+
+/// Sink writing metrics to InfluxDB using the line protocol over HTTP.
+pub struct InfluxDbSink {
+	client: reqwest::Client,
+	write_url: String,
+	token: Option<String>,
+	prefix: String,
+}
+
+impl InfluxDbSink {
+	/// Creates a new InfluxDbSink writing to `bucket` of `org` at `url`.
+	pub fn new(url: &str, org: &str, bucket: &str, token: Option<String>, prefix: String) -> Self {
+		Self {
+			client: reqwest::Client::new(),
+			write_url: format!(
+				"{}/api/v2/write?org={}&bucket={}&precision=ns",
+				url.trim_end_matches('/'),
+				org,
+				bucket
+			),
+			token,
+			prefix,
+		}
+	}
+
+	fn send(&self, name: &str, value: String, labels: &[(&str, &str)]) {
+		let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+			return;
+		};
+
+		let timestamp = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_nanos();
+		let line = influx_line(&self.prefix, name, &value, labels, timestamp);
+
+		let mut request = self.client.post(&self.write_url).body(line);
+		if let Some(token) = &self.token {
+			request = request.header("Authorization", format!("Token {}", token));
+		}
+		let name = name.to_string();
+		runtime.spawn(async move {
+			if let Err(e) = request.send().await.and_then(|r| r.error_for_status()) {
+				tracing::debug!(metric = %name, error = %e, "Failed to write InfluxDB metric");
+			}
+		});
+	}
+}
+
+impl TelemetrySink for InfluxDbSink {
+	fn record_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+		self.send(name, format!("{}i", value), labels);
+	}
+
+	fn record_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+		self.send(name, value.to_string(), labels);
+	}
+
+	fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+		self.send(name, value.to_string(), labels);
+	}
+}
+
+/// Formats a metric in the StatsD protocol with DogStatsD tags.
+fn statsd_line(
+	prefix: &str,
+	name: &str,
+	value: &str,
+	kind: &str,
+	labels: &[(&str, &str)],
+) -> String {
+	let mut line = format!("{}.{}:{}|{}", prefix, name, value, kind);
+	if !labels.is_empty() {
+		let tags: Vec<String> = labels.iter().map(|(k, v)| format!("{}:{}", k, v)).collect();
+		line.push_str("|#");
+		line.push_str(&tags.join(","));
+	}
+	line
+}
+
+/// Formats a metric in the InfluxDB line protocol.
+fn influx_line(
+	prefix: &str,
+	name: &str,
+	value: &str,
+	labels: &[(&str, &str)],
+	timestamp_ns: u128,
+) -> String {
+	let mut line = escape_influx(&format!("{}_{}", prefix, name));
+	for (key, label) in labels {
+		line.push_str(&format!(",{}={}", escape_influx(key), escape_influx(label)));
+	}
+	format!("{} value={} {}", line, value, timestamp_ns)
+}
+
+/// Escapes commas, spaces and equals signs in InfluxDB measurement and tag names.
+fn escape_influx(value: &str) -> String {
+	value
+		.replace(',', "\\,")
+		.replace(' ', "\\ ")
+		.replace('=', "\\=")
+}
+
+/// Records the metrics corresponding to a solver event.
+pub fn record_event(sink: &dyn TelemetrySink, event: &SolverEvent) {
+	match event {
+		SolverEvent::Order(event) => {
+			let status = match event {
+				OrderEvent::Preparing { .. } => "preparing",
+				OrderEvent::Executing { .. } => "executing",
+				OrderEvent::Skipped { .. } => "skipped",
+				OrderEvent::Deferred { .. } => "deferred",
+				OrderEvent::Cancelled { .. } => "cancelled",
+				OrderEvent::ExpiryGracePeriod { .. } => "expiry_grace_period",
+			};
+			sink.record_counter("orders", 1, &[("status", status)]);
+		},
+		SolverEvent::Delivery(event) => match event {
+			DeliveryEvent::TransactionPending { tx_type, .. } => {
+				sink.record_counter(
+					"transactions",
+					1,
+					&[("status", "pending"), ("tx_type", tx_type_label(tx_type))],
+				);
+			},
+			DeliveryEvent::TransactionConfirmed {
+				tx_type, receipt, ..
+			} => {
+				let labels = [("status", "confirmed"), ("tx_type", tx_type_label(tx_type))];
+				sink.record_counter("transactions", 1, &labels);
+				sink.record_histogram(
+					"transaction_gas_used",
+					receipt.gas_used as f64,
+					&labels[1..],
+				);
+			},
+			DeliveryEvent::TransactionFailed { tx_type, .. } => {
+				sink.record_counter(
+					"transactions",
+					1,
+					&[("status", "failed"), ("tx_type", tx_type_label(tx_type))],
+				);
+			},
+		},
+		SolverEvent::Settlement(SettlementEvent::Completed { .. }) => {
+			sink.record_counter("settlements", 1, &[("status", "completed")]);
+		},
+		SolverEvent::Settlement(SettlementEvent::FillChallenged { .. }) => {
+			sink.record_counter("settlements", 1, &[("status", "challenged")]);
+		},
+		SolverEvent::OracleUnhealthy { chain_id, .. } => {
+			sink.record_counter(
+				"oracle_unhealthy",
+				1,
+				&[("chain_id", &chain_id.to_string())],
+			);
+		},
+		SolverEvent::GasBudgetWarning {
+			chain_id,
+			spent_wei,
+			budget_wei,
+		} => {
+			let spent = spent_wei.to_string().parse::<f64>().unwrap_or_default();
+			let budget = budget_wei.to_string().parse::<f64>().unwrap_or_default();
+			if budget > 0.0 {
+				sink.record_gauge(
+					"gas_budget_utilization",
+					spent / budget,
+					&[("chain_id", &chain_id.to_string())],
+				);
+			}
+		},
+		SolverEvent::ArbitrageOpportunity {
+			token, spread_bps, ..
+		} => {
+			sink.record_histogram(
+				"arbitrage_spread_bps",
+				*spread_bps as f64,
+				&[("token", token)],
+			);
+		},
+		SolverEvent::ClaimCircuitOpen {
+			implementation_name,
+		} => {
+			sink.record_counter(
+				"claim_circuit_open",
+				1,
+				&[("implementation", implementation_name)],
+			);
+		},
+		SolverEvent::RebalancingInitiated {
+			from_chain,
+			to_chain,
+			..
+		} => {
+			sink.record_counter(
+				"rebalancing_initiated",
+				1,
+				&[
+					("from_chain", &from_chain.to_string()),
+					("to_chain", &to_chain.to_string()),
+				],
+			);
+		},
+		_ => {},
+	}
+}
+
+fn tx_type_label(tx_type: &TransactionType) -> &'static str {
+	match tx_type {
+		TransactionType::Prepare => "prepare",
+		TransactionType::Fill => "fill",
+		TransactionType::Claim => "claim",
+		TransactionType::Commit => "commit",
+		TransactionType::Reveal => "reveal",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Mutex;
+
+	/// Sink recording counters in StatsD format.
+	#[derive(Default)]
+	struct RecordingSink {
+		counters: Mutex<Vec<String>>,
+	}
+
+	impl TelemetrySink for RecordingSink {
+		fn record_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+			self.counters.lock().unwrap().push(statsd_line(
+				"test",
+				name,
+				&value.to_string(),
+				"c",
+				labels,
+			));
+		}
+
+		fn record_gauge(&self, _name: &str, _value: f64, _labels: &[(&str, &str)]) {}
+
+		fn record_histogram(&self, _name: &str, _value: f64, _labels: &[(&str, &str)]) {}
+	}
+
+	#[test]
+	fn test_line_formats() {
+		assert_eq!(
+			statsd_line("oif", "orders", "1", "c", &[("status", "skipped")]),
+			"oif.orders:1|c|#status:skipped"
+		);
+		assert_eq!(
+			influx_line("oif", "orders", "1i", &[("status", "a b")], 7),
+			"oif_orders,status=a\\ b value=1i 7"
+		);
+	}
+
+	#[test]
+	fn test_record_event_counts_orders() {
+		let sink = RecordingSink::default();
+		record_event(
+			&sink,
+			&SolverEvent::Order(OrderEvent::Skipped {
+				order_id: "order".to_string(),
+				reason: "unprofitable".to_string(),
+			}),
+		);
+
+		assert_eq!(
+			*sink.counters.lock().unwrap(),
+			vec!["test.orders:1|c|#status:skipped".to_string()]
+		);
+	}
+}
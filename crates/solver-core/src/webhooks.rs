@@ -0,0 +1,264 @@
+//! Webhook notifications of order status changes.
+//!
+//! The publisher posts every order event to the configured webhook URLs.
+//! Deliveries that fail are not lost: they are stored in a retry queue and
+//! retried in the background with exponential backoff, until they succeed
+//! or run out of attempts.
+
+use alloy_primitives::hex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solver_config::WebhookConfig;
+use solver_storage::{StorageError, StorageService};
+use solver_types::{current_timestamp, SolverEvent, StorageKey};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Interval at which due webhook retries are processed.
+pub const WEBHOOK_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A webhook delivery waiting to be retried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookRetry {
+	pub url: String,
+	pub payload: serde_json::Value,
+	/// Number of failed deliveries so far.
+	pub attempt_count: u32,
+	/// Timestamp from which the delivery is retried.
+	pub next_retry_at: u64,
+}
+
+impl WebhookRetry {
+	/// Returns the storage ID of the retry, derived from its URL and payload.
+	fn id(&self) -> String {
+		let mut hasher = Sha256::new();
+		hasher.update(self.url.as_bytes());
+		hasher.update(self.payload.to_string().as_bytes());
+		hex::encode(hasher.finalize())
+	}
+}
+
+/// A stored webhook retry with its ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookRetryEntry {
+	pub id: String,
+	#[serde(flatten)]
+	pub retry: WebhookRetry,
+}
+
+/// Sends webhook payloads and retries failed deliveries with exponential backoff.
+pub struct WebhookRetryQueue {
+	storage: Arc<StorageService>,
+	client: reqwest::Client,
+	base_delay_seconds: u64,
+	max_delay_seconds: u64,
+	max_attempts: u32,
+}
+
+impl WebhookRetryQueue {
+	/// Creates a new WebhookRetryQueue with the delivery settings of `config`.
+	pub fn new(storage: Arc<StorageService>, config: &WebhookConfig) -> Self {
+		Self {
+			storage,
+			client: reqwest::Client::builder()
+				.timeout(Duration::from_secs(config.timeout_seconds))
+				.build()
+				.unwrap_or_default(),
+			base_delay_seconds: config.base_delay_seconds,
+			max_delay_seconds: config.max_delay_seconds,
+			max_attempts: config.max_attempts,
+		}
+	}
+
+	/// Returns the delay before the retry following `attempt_count` failures.
+	pub fn backoff(&self, attempt_count: u32) -> u64 {
+		self.base_delay_seconds
+			.saturating_mul(2u64.saturating_pow(attempt_count))
+			.min(self.max_delay_seconds)
+	}
+
+	/// Delivers a payload to a webhook URL, enqueueing it for retry on failure.
+	pub async fn deliver(&self, url: &str, payload: serde_json::Value) {
+		let retry = WebhookRetry {
+			url: url.to_string(),
+			payload,
+			attempt_count: 0,
+			next_retry_at: 0,
+		};
+		self.attempt(retry, current_timestamp()).await;
+	}
+
+	/// Retries every delivery that is due at `now`.
+	pub async fn process_due(&self, now: u64) -> Result<(), StorageError> {
+		for entry in pending_retries(&self.storage).await? {
+			if entry.retry.next_retry_at > now {
+				break;
+			}
+			self.storage
+				.remove(StorageKey::WebhookRetries.as_str(), &entry.id)
+				.await?;
+			self.attempt(entry.retry, now).await;
+		}
+		Ok(())
+	}
+
+	/// Posts a payload, re-enqueueing it on failure while attempts are left.
+	async fn attempt(&self, mut retry: WebhookRetry, now: u64) {
+		let error = match self
+			.client
+			.post(&retry.url)
+			.json(&retry.payload)
+			.send()
+			.await
+		{
+			Ok(response) if response.status().is_success() => return,
+			Ok(response) => format!("status {}", response.status()),
+			Err(e) => e.to_string(),
+		};
+
+		retry.attempt_count += 1;
+		if retry.attempt_count >= self.max_attempts {
+			tracing::warn!(
+				url = %retry.url,
+				attempts = retry.attempt_count,
+				error = %error,
+				"Webhook delivery failed, giving up"
+			);
+			return;
+		}
+		retry.next_retry_at = now + self.backoff(retry.attempt_count - 1);
+		tracing::debug!(
+			url = %retry.url,
+			attempts = retry.attempt_count,
+			next_retry_at = retry.next_retry_at,
+			error = %error,
+			"Webhook delivery failed, queued for retry"
+		);
+		if let Err(e) = self
+			.storage
+			.store(
+				StorageKey::WebhookRetries.as_str(),
+				&retry.id(),
+				&retry,
+				None,
+			)
+			.await
+		{
+			tracing::warn!(url = %retry.url, error = %e, "Failed to queue webhook retry");
+		}
+	}
+
+	/// Spawns a task that retries due deliveries every [`WEBHOOK_RETRY_INTERVAL`].
+	pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(WEBHOOK_RETRY_INTERVAL);
+			loop {
+				ticker.tick().await;
+				if let Err(e) = self.process_due(current_timestamp()).await {
+					tracing::warn!(error = %e, "Failed to process webhook retries");
+				}
+			}
+		})
+	}
+}
+
+/// Returns the queued webhook retries, the earliest due first.
+pub async fn pending_retries(
+	storage: &StorageService,
+) -> Result<Vec<WebhookRetryEntry>, StorageError> {
+	let mut entries: Vec<WebhookRetryEntry> = storage
+		.retrieve_all::<WebhookRetry>(StorageKey::WebhookRetries.as_str())
+		.await?
+		.into_iter()
+		.map(|(id, retry)| WebhookRetryEntry { id, retry })
+		.collect();
+	entries.sort_by_key(|entry| entry.retry.next_retry_at);
+	Ok(entries)
+}
+
+/// Posts order events to the configured webhook URLs.
+pub struct EventWebhookPublisher {
+	urls: Vec<String>,
+	queue: Arc<WebhookRetryQueue>,
+}
+
+impl EventWebhookPublisher {
+	/// Creates a new EventWebhookPublisher delivering through `queue`.
+	pub fn new(urls: Vec<String>, queue: Arc<WebhookRetryQueue>) -> Self {
+		Self { urls, queue }
+	}
+
+	/// Spawns a task posting the order events received on `events`.
+	pub fn spawn(self, mut events: broadcast::Receiver<SolverEvent>) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			loop {
+				let event = match events.recv().await {
+					Ok(SolverEvent::Order(event)) => event,
+					Ok(_) => continue,
+					Err(broadcast::error::RecvError::Lagged(skipped)) => {
+						tracing::warn!(skipped, "Webhook publisher lagged behind events");
+						continue;
+					},
+					Err(broadcast::error::RecvError::Closed) => break,
+				};
+				let payload = serde_json::json!({
+					"event": event,
+					"timestamp": current_timestamp(),
+				});
+				for url in &self.urls {
+					let queue = self.queue.clone();
+					let (url, payload) = (url.clone(), payload.clone());
+					tokio::spawn(async move { queue.deliver(&url, payload).await });
+				}
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	#[tokio::test]
+	async fn test_failed_deliveries_back_off_until_exhausted() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let config = WebhookConfig {
+			urls: vec![],
+			timeout_seconds: 1,
+			base_delay_seconds: 5,
+			max_delay_seconds: 30,
+			max_attempts: 3,
+		};
+		let queue = WebhookRetryQueue::new(storage.clone(), &config);
+		assert_eq!(queue.backoff(0), 5);
+		assert_eq!(queue.backoff(2), 20);
+		assert_eq!(queue.backoff(10), 30);
+
+		// Nothing listens on the discard port
+		let url = "http://127.0.0.1:9/hook";
+		queue.deliver(url, serde_json::json!({ "id": 1 })).await;
+		let pending = pending_retries(&storage).await.unwrap();
+		assert_eq!(pending.len(), 1);
+		assert_eq!(pending[0].retry.attempt_count, 1);
+		let due = pending[0].retry.next_retry_at;
+
+		// Retries only run once due
+		queue.process_due(due - 1).await.unwrap();
+		assert_eq!(
+			pending_retries(&storage).await.unwrap()[0]
+				.retry
+				.attempt_count,
+			1
+		);
+		queue.process_due(due).await.unwrap();
+		let pending = pending_retries(&storage).await.unwrap();
+		assert_eq!(pending[0].retry.attempt_count, 2);
+		assert_eq!(pending[0].retry.next_retry_at, due + 10);
+
+		queue.process_due(due + 10).await.unwrap();
+		assert!(pending_retries(&storage).await.unwrap().is_empty());
+	}
+}
@@ -0,0 +1,227 @@
+//! Local Anvil nodes standing in for the configured networks.
+//!
+//! Depending on the provider mode, one Anvil node is started per network and
+//! every component connects to it instead of the configured RPC endpoints:
+//! fresh chains in `anvil` mode, or forks of the configured networks in
+//! `testnet` mode with a fork block. The nodes are killed when they are
+//! dropped or when the process receives Ctrl+C.
+
+use crate::DeliveryError;
+use solver_types::{networks::RpcEndpoint, NetworksConfig, ProviderMode};
+use std::collections::HashMap;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Maximum time to wait for a node to accept connections.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval between connection attempts while a node starts.
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Anvil nodes serving the networks of the solver.
+pub struct AnvilNodes {
+	/// Running node processes.
+	children: Arc<Mutex<Vec<Child>>>,
+	/// Port of the node of each network.
+	ports: HashMap<u64, u16>,
+}
+
+impl AnvilNodes {
+	/// Starts the nodes required by a provider mode, if any.
+	///
+	/// Returns None when the mode uses the configured RPC endpoints.
+	pub async fn start(
+		mode: ProviderMode,
+		networks: &NetworksConfig,
+	) -> Result<Option<Self>, DeliveryError> {
+		let fork_block = match mode {
+			ProviderMode::Mainnet | ProviderMode::Testnet { fork_block: None } => return Ok(None),
+			ProviderMode::Testnet {
+				fork_block: Some(block),
+			} => Some(block),
+			ProviderMode::Anvil { .. } => None,
+		};
+
+		let mut chain_ids: Vec<u64> = networks.keys().map(|chain_id| chain_id.0).collect();
+		chain_ids.sort_unstable();
+		let nodes = Self {
+			children: Arc::new(Mutex::new(Vec::new())),
+			ports: node_ports(mode, &chain_ids)?,
+		};
+
+		for chain_id in chain_ids {
+			let port = nodes.ports[&chain_id];
+			let mut command = Command::new("anvil");
+			command
+				.args(["--host", "127.0.0.1", "--silent"])
+				.args(["--port", &port.to_string()])
+				.args(["--chain-id", &chain_id.to_string()])
+				.stdout(Stdio::null())
+				.stderr(Stdio::null());
+			if let Some(block) = fork_block {
+				let fork_url = networks[&chain_id].get_http_url().ok_or_else(|| {
+					DeliveryError::Network(format!(
+						"No HTTP RPC URL configured to fork network {}",
+						chain_id
+					))
+				})?;
+				command
+					.args(["--fork-url", fork_url])
+					.args(["--fork-block-number", &block.to_string()]);
+			}
+
+			let child = command.spawn().map_err(|e| {
+				DeliveryError::Network(format!(
+					"Failed to start Anvil for network {}: {}",
+					chain_id, e
+				))
+			})?;
+			nodes.children.lock().unwrap().push(child);
+			nodes.wait_until_ready(chain_id, port).await?;
+			tracing::info!(chain_id, port, "Started Anvil node");
+		}
+
+		// Kill the nodes on Ctrl+C, as the process may exit without dropping them
+		let children = Arc::downgrade(&nodes.children);
+		tokio::spawn(async move {
+			if tokio::signal::ctrl_c().await.is_ok() {
+				if let Some(children) = children.upgrade() {
+					kill_all(&children);
+				}
+			}
+		});
+
+		Ok(Some(nodes))
+	}
+
+	/// Returns the networks with their RPC endpoints replaced by their node.
+	pub fn networks(&self, networks: &NetworksConfig) -> NetworksConfig {
+		networks
+			.iter()
+			.map(|(chain_id, network)| {
+				let mut network = network.clone();
+				if let Some(port) = self.ports.get(&chain_id.0) {
+					network.rpc_urls = vec![RpcEndpoint::both(
+						format!("http://127.0.0.1:{}", port),
+						format!("ws://127.0.0.1:{}", port),
+					)];
+				}
+				(*chain_id, network)
+			})
+			.collect()
+	}
+
+	/// Waits until the last started node accepts connections.
+	async fn wait_until_ready(&self, chain_id: u64, port: u16) -> Result<(), DeliveryError> {
+		let started = Instant::now();
+		loop {
+			if tokio::net::TcpStream::connect(("127.0.0.1", port))
+				.await
+				.is_ok()
+			{
+				return Ok(());
+			}
+			let exited = self
+				.children
+				.lock()
+				.unwrap()
+				.last_mut()
+				.and_then(|child| child.try_wait().ok().flatten());
+			if let Some(status) = exited {
+				return Err(DeliveryError::Network(format!(
+					"Anvil for network {} exited with {}",
+					chain_id, status
+				)));
+			}
+			if started.elapsed() > STARTUP_TIMEOUT {
+				return Err(DeliveryError::Network(format!(
+					"Anvil for network {} did not start listening on port {}",
+					chain_id, port
+				)));
+			}
+			tokio::time::sleep(STARTUP_POLL_INTERVAL).await;
+		}
+	}
+}
+
+impl Drop for AnvilNodes {
+	fn drop(&mut self) {
+		kill_all(&self.children);
+	}
+}
+
+/// Kills and reaps the given node processes.
+fn kill_all(children: &Mutex<Vec<Child>>) {
+	for mut child in children.lock().unwrap().drain(..) {
+		let _ = child.kill();
+		let _ = child.wait();
+	}
+}
+
+/// Assigns the port of each network's node: consecutive ports from the
+/// configured one in `anvil` mode, and free ports otherwise.
+fn node_ports(mode: ProviderMode, chain_ids: &[u64]) -> Result<HashMap<u64, u16>, DeliveryError> {
+	chain_ids
+		.iter()
+		.enumerate()
+		.map(|(index, chain_id)| {
+			let port = match mode {
+				ProviderMode::Anvil { port } => u16::try_from(index)
+					.ok()
+					.and_then(|offset| port.checked_add(offset))
+					.ok_or_else(|| {
+						DeliveryError::Network(format!(
+							"No port left for the Anvil node of network {}",
+							chain_id
+						))
+					})?,
+				_ => std::net::TcpListener::bind(("127.0.0.1", 0))
+					.and_then(|listener| listener.local_addr())
+					.map(|addr| addr.port())
+					.map_err(|e| {
+						DeliveryError::Network(format!("Failed to find a free port: {}", e))
+					})?,
+			};
+			Ok((*chain_id, port))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{Address, NetworkConfig};
+
+	fn network(url: &str) -> NetworkConfig {
+		NetworkConfig {
+			rpc_urls: vec![RpcEndpoint::http_only(url.to_string())],
+			input_settler_address: Address(vec![0; 20]),
+			output_settler_address: Address(vec![0; 20]),
+			tokens: Vec::new(),
+			input_settler_compact_address: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_networks_point_to_consecutive_anvil_ports() {
+		let networks = NetworksConfig::from([
+			(10.into(), network("https://optimism.example")),
+			(1.into(), network("https://ethereum.example")),
+		]);
+		assert!(AnvilNodes::start(ProviderMode::Mainnet, &networks)
+			.await
+			.unwrap()
+			.is_none());
+
+		let nodes = AnvilNodes {
+			children: Arc::new(Mutex::new(Vec::new())),
+			ports: node_ports(ProviderMode::Anvil { port: 8545 }, &[1, 10]).unwrap(),
+		};
+		let local = nodes.networks(&networks);
+		assert_eq!(local[&1u64].get_http_url(), Some("http://127.0.0.1:8545"));
+		assert_eq!(local[&10u64].get_ws_url(), Some("ws://127.0.0.1:8546"));
+
+		assert!(node_ports(ProviderMode::Anvil { port: u16::MAX }, &[1, 10]).is_err());
+	}
+}
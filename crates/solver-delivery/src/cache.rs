@@ -0,0 +1,129 @@
+//! Caching of transaction receipts.
+//!
+//! Monitoring loops poll the status of the same transactions repeatedly. The
+//! cache keeps recent receipts for a short TTL, and final receipts (with at
+//! least the required number of confirmations) until a reorg is detected.
+
+use dashmap::DashMap;
+use solver_types::{TransactionHash, TransactionReceipt};
+use std::time::{Duration, Instant};
+
+/// Name of the counter incremented on each cache hit.
+const CACHE_HITS_COUNTER: &str = "delivery_receipt_cache_hits_total";
+
+/// Cached receipt with the time it was fetched.
+struct CachedReceipt {
+	receipt: TransactionReceipt,
+	cached_at: Instant,
+	/// Whether the receipt had enough confirmations to be considered final.
+	is_final: bool,
+}
+
+/// Cache of transaction receipts keyed by chain and transaction hash.
+#[derive(Default)]
+pub struct ReceiptCache {
+	receipts: DashMap<(u64, TransactionHash), CachedReceipt>,
+	/// Latest block number observed on each chain, used to detect reorgs.
+	heads: DashMap<u64, u64>,
+	/// How long non-final receipts are served from the cache.
+	ttl: Duration,
+}
+
+impl ReceiptCache {
+	/// Creates a new ReceiptCache serving non-final receipts for `ttl`.
+	pub fn new(ttl: Duration) -> Self {
+		Self {
+			ttl,
+			..Self::default()
+		}
+	}
+
+	/// Returns the cached receipt of a transaction, if final or still fresh.
+	pub fn get(&self, chain_id: u64, hash: &TransactionHash) -> Option<TransactionReceipt> {
+		let key = (chain_id, hash.clone());
+		let receipt = {
+			let entry = self.receipts.get(&key)?;
+			(entry.is_final || entry.cached_at.elapsed() < self.ttl).then(|| entry.receipt.clone())
+		};
+
+		match receipt {
+			Some(receipt) => {
+				metrics::counter!(CACHE_HITS_COUNTER, "chain_id" => chain_id.to_string())
+					.increment(1);
+				Some(receipt)
+			},
+			None => {
+				self.receipts.remove(&key);
+				None
+			},
+		}
+	}
+
+	/// Caches a receipt, indefinitely if it is final.
+	pub fn insert(&self, chain_id: u64, receipt: TransactionReceipt, is_final: bool) {
+		self.receipts.insert(
+			(chain_id, receipt.hash.clone()),
+			CachedReceipt {
+				receipt,
+				cached_at: Instant::now(),
+				is_final,
+			},
+		);
+	}
+
+	/// Records the latest block number of a chain.
+	///
+	/// A head lower than the previously observed one indicates a reorg, in
+	/// which case receipts of the blocks above the new head are evicted.
+	pub fn observe_head(&self, chain_id: u64, block_number: u64) {
+		let previous = self.heads.insert(chain_id, block_number);
+		if previous.is_some_and(|previous| block_number < previous) {
+			tracing::warn!(
+				chain_id,
+				previous_head = previous,
+				head = block_number,
+				"Chain reorg detected, evicting cached receipts"
+			);
+			self.evict_above(chain_id, block_number);
+		}
+	}
+
+	/// Evicts the receipts of a chain included after the given block.
+	pub fn evict_above(&self, chain_id: u64, block_number: u64) {
+		self.receipts.retain(|(receipt_chain, _), cached| {
+			*receipt_chain != chain_id || cached.receipt.block_number <= block_number
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn receipt(hash: u8, block_number: u64) -> TransactionReceipt {
+		TransactionReceipt {
+			hash: TransactionHash(vec![hash]),
+			block_number,
+			success: true,
+			gas_used: 0,
+			effective_gas_price: 0,
+		}
+	}
+
+	#[test]
+	fn test_final_receipts_survive_until_reorg() {
+		let cache = ReceiptCache::new(Duration::ZERO);
+		cache.insert(1, receipt(1, 100), true);
+		cache.insert(1, receipt(2, 110), true);
+		cache.insert(1, receipt(3, 105), false);
+
+		assert!(cache.get(1, &TransactionHash(vec![1])).is_some());
+		// Non-final receipts expire immediately with a zero TTL
+		assert!(cache.get(1, &TransactionHash(vec![3])).is_none());
+
+		cache.observe_head(1, 120);
+		cache.observe_head(1, 105);
+		assert!(cache.get(1, &TransactionHash(vec![1])).is_some());
+		assert!(cache.get(1, &TransactionHash(vec![2])).is_none());
+	}
+}
@@ -0,0 +1,148 @@
+//! Fee bidding from the recent fee history.
+//!
+//! Instead of relying on the provider's fee estimate, the bidder looks at the
+//! priority fees paid in recent blocks and bids a configurable percentile of
+//! them. The max fee leaves room for the base fee to rise before inclusion.
+
+use crate::{DeliveryError, DeliveryInterface};
+use solver_types::{FeeHistory, GasBidConfig};
+
+/// Number of recent blocks the fee history covers.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Priority fee percentiles requested from the fee history.
+const REWARD_PERCENTILES: [f64; 3] = [25.0, 50.0, 75.0];
+
+/// Wei per gwei.
+const GWEI: u128 = 1_000_000_000;
+
+/// EIP-1559 fees to submit a transaction with, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasBid {
+	/// Maximum total fee per gas.
+	pub max_fee_per_gas: u128,
+	/// Maximum priority fee per gas.
+	pub max_priority_fee_per_gas: u128,
+}
+
+/// Bids transaction fees from the recent fee history of a chain.
+pub struct SmartGasBidder {
+	config: GasBidConfig,
+}
+
+impl SmartGasBidder {
+	/// Creates a new SmartGasBidder with the given configuration.
+	pub fn new(config: GasBidConfig) -> Self {
+		Self { config }
+	}
+
+	/// Computes the fees of a transaction on a chain from its recent fee history.
+	pub async fn bid(
+		&self,
+		delivery: &dyn DeliveryInterface,
+		chain_id: u64,
+	) -> Result<GasBid, DeliveryError> {
+		let history = delivery
+			.get_fee_history(chain_id, FEE_HISTORY_BLOCKS, &REWARD_PERCENTILES)
+			.await?;
+
+		let bid = self.compute_bid(&history).ok_or_else(|| {
+			DeliveryError::Network(format!("Empty fee history on chain {}", chain_id))
+		})?;
+		tracing::debug!(
+			chain_id,
+			max_fee_per_gas = bid.max_fee_per_gas,
+			max_priority_fee_per_gas = bid.max_priority_fee_per_gas,
+			"Computed gas bid"
+		);
+		Ok(bid)
+	}
+
+	/// Computes the fees from a fee history, or None if it holds no base fee.
+	///
+	/// The priority fee is the median over blocks of the target percentile,
+	/// interpolated between the requested percentiles and clamped to the
+	/// configured bounds. The max fee is the pending base fee times the
+	/// multiplier plus the priority fee.
+	fn compute_bid(&self, history: &FeeHistory) -> Option<GasBid> {
+		let base_fee = *history.base_fee_per_gas.last()?;
+
+		let target = f64::from(self.config.target_percentile);
+		let mut rewards: Vec<u128> = history
+			.reward
+			.iter()
+			.filter_map(|block| interpolate_reward(block, target))
+			.collect();
+		rewards.sort_unstable();
+		let median = rewards.get(rewards.len() / 2).copied().unwrap_or_default();
+
+		let min_priority_fee = u128::from(self.config.min_priority_fee_gwei) * GWEI;
+		let max_priority_fee = u128::from(self.config.max_priority_fee_gwei) * GWEI;
+		let priority_fee = median.clamp(min_priority_fee, max_priority_fee.max(min_priority_fee));
+
+		let scaled_base_fee = (base_fee as f64 * self.config.base_fee_multiplier) as u128;
+		Some(GasBid {
+			max_fee_per_gas: scaled_base_fee + priority_fee,
+			max_priority_fee_per_gas: priority_fee,
+		})
+	}
+}
+
+/// Estimates the reward at a percentile from the rewards of one block.
+///
+/// Percentiles outside the requested range take the nearest reward.
+fn interpolate_reward(rewards: &[u128], percentile: f64) -> Option<u128> {
+	if rewards.len() != REWARD_PERCENTILES.len() {
+		return None;
+	}
+
+	if percentile <= REWARD_PERCENTILES[0] {
+		return Some(rewards[0]);
+	}
+	for i in 1..REWARD_PERCENTILES.len() {
+		let (low, high) = (REWARD_PERCENTILES[i - 1], REWARD_PERCENTILES[i]);
+		if percentile <= high {
+			let weight = (percentile - low) / (high - low);
+			let (low_reward, high_reward) = (rewards[i - 1] as f64, rewards[i] as f64);
+			return Some((low_reward + (high_reward - low_reward) * weight).round() as u128);
+		}
+	}
+	rewards.last().copied()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_compute_bid() {
+		let bidder = SmartGasBidder::new(GasBidConfig::default());
+		let history = FeeHistory {
+			base_fee_per_gas: vec![8 * GWEI, 10 * GWEI],
+			reward: vec![vec![GWEI, 2 * GWEI, 6 * GWEI]],
+		};
+
+		// 60th percentile lies 40% of the way from the 50th to the 75th
+		let bid = bidder.compute_bid(&history).unwrap();
+		assert_eq!(bid.max_priority_fee_per_gas, 3_600_000_000);
+		assert_eq!(bid.max_fee_per_gas, 12_500_000_000 + 3_600_000_000);
+
+		let capped = SmartGasBidder::new(GasBidConfig {
+			max_priority_fee_gwei: 1,
+			..GasBidConfig::default()
+		});
+		assert_eq!(
+			capped
+				.compute_bid(&history)
+				.unwrap()
+				.max_priority_fee_per_gas,
+			GWEI
+		);
+		assert!(bidder
+			.compute_bid(&FeeHistory {
+				base_fee_per_gas: vec![],
+				reward: vec![],
+			})
+			.is_none());
+	}
+}
@@ -0,0 +1,345 @@
+//! Gas price history for timing fills.
+//!
+//! This module samples the gas price of each chain at a fixed interval and
+//! keeps the raw samples of the last 48 hours, grouped into hourly buckets.
+//! Every sample also updates the average of its hour, and the hourly
+//! averages of the last 7 days are used to predict the gas price of the
+//! coming hours by hour of the week.
+
+use crate::DeliveryService;
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::StorageKey;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Number of seconds in an hour.
+const HOUR_SECONDS: u64 = 3_600;
+
+/// Number of hours in a week.
+const HOURS_PER_WEEK: u64 = 168;
+
+/// Number of hours in a day.
+const HOURS_PER_DAY: u64 = 24;
+
+/// How long raw samples are retained.
+const SAMPLE_RETENTION: Duration = Duration::from_secs(48 * HOUR_SECONDS);
+
+/// Number of hours of hourly averages predictions are based on.
+const AVERAGE_WINDOW_HOURS: u64 = 7 * HOURS_PER_DAY;
+
+/// Number of wei in a gwei.
+const WEI_PER_GWEI: f64 = 1e9;
+
+/// Gas price observed at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceSample {
+	/// Time of the sample in seconds since the Unix epoch.
+	pub timestamp: u64,
+	/// Gas price in gwei.
+	pub gas_price_gwei: f64,
+}
+
+/// Average gas price of one hour.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct HourlyGasPrice {
+	/// Hours since the Unix epoch.
+	hour: u64,
+	/// Sum of the sampled gas prices in gwei.
+	total_gwei: f64,
+	/// Number of samples taken in the hour.
+	samples: u64,
+}
+
+impl HourlyGasPrice {
+	/// Returns the average gas price of the hour in gwei.
+	fn average_gwei(&self) -> f64 {
+		self.total_gwei / self.samples.max(1) as f64
+	}
+}
+
+/// Samples gas prices and predicts low gas windows from their history.
+pub struct GasPriceHistoryAnalyzer {
+	/// Storage service used to persist samples.
+	storage: Arc<StorageService>,
+	/// Delivery service the gas prices are sampled from.
+	delivery: Arc<DeliveryService>,
+	/// Chains whose gas prices are sampled.
+	chain_ids: Vec<u64>,
+}
+
+impl GasPriceHistoryAnalyzer {
+	/// Creates a new GasPriceHistoryAnalyzer for the given chains.
+	pub fn new(
+		storage: Arc<StorageService>,
+		delivery: Arc<DeliveryService>,
+		chain_ids: Vec<u64>,
+	) -> Self {
+		Self {
+			storage,
+			delivery,
+			chain_ids,
+		}
+	}
+
+	/// Samples the current gas price of every chain.
+	pub async fn sample(&self) {
+		let now = current_timestamp();
+		for &chain_id in &self.chain_ids {
+			let gas_price = match self.delivery.get_gas_price(chain_id).await {
+				Ok(gas_price) => gas_price,
+				Err(e) => {
+					tracing::warn!(chain_id = chain_id, error = %e, "Failed to sample gas price");
+					continue;
+				},
+			};
+			let Ok(wei) = gas_price.parse::<f64>() else {
+				tracing::warn!(chain_id = chain_id, gas_price = %gas_price, "Invalid gas price");
+				continue;
+			};
+			let sample = GasPriceSample {
+				timestamp: now,
+				gas_price_gwei: wei / WEI_PER_GWEI,
+			};
+			if let Err(e) = self.record(chain_id, &sample).await {
+				tracing::warn!(chain_id = chain_id, error = %e, "Failed to store gas price sample");
+			}
+		}
+	}
+
+	/// Records a gas price sample of a chain.
+	pub async fn record(&self, chain_id: u64, sample: &GasPriceSample) -> Result<(), StorageError> {
+		let hour = sample.timestamp / HOUR_SECONDS;
+
+		let samples_id = samples_id(chain_id, hour);
+		let mut samples = self
+			.load::<Vec<GasPriceSample>>(&samples_id)
+			.await?
+			.unwrap_or_default();
+		samples.push(sample.clone());
+		self.storage
+			.store_with_ttl(
+				StorageKey::GasPriceHistory.as_str(),
+				&samples_id,
+				&samples,
+				None,
+				Some(SAMPLE_RETENTION),
+			)
+			.await?;
+
+		let averages_id = averages_id(chain_id);
+		let mut averages = self
+			.load::<Vec<HourlyGasPrice>>(&averages_id)
+			.await?
+			.unwrap_or_default();
+		averages.retain(|average| average.hour + AVERAGE_WINDOW_HOURS > hour);
+		match averages.iter_mut().find(|average| average.hour == hour) {
+			Some(average) => {
+				average.total_gwei += sample.gas_price_gwei;
+				average.samples += 1;
+			},
+			None => averages.push(HourlyGasPrice {
+				hour,
+				total_gwei: sample.gas_price_gwei,
+				samples: 1,
+			}),
+		}
+		self.storage
+			.store(
+				StorageKey::GasPriceHistory.as_str(),
+				&averages_id,
+				&averages,
+				None,
+			)
+			.await
+	}
+
+	/// Returns the samples of a chain taken in the last 48 hours, oldest first.
+	pub async fn samples(&self, chain_id: u64) -> Result<Vec<GasPriceSample>, StorageError> {
+		let now = current_timestamp();
+		let current_hour = now / HOUR_SECONDS;
+		let mut samples = Vec::new();
+		let retention_hours = SAMPLE_RETENTION.as_secs() / HOUR_SECONDS;
+		for hour in current_hour.saturating_sub(retention_hours)..=current_hour {
+			if let Some(bucket) = self
+				.load::<Vec<GasPriceSample>>(&samples_id(chain_id, hour))
+				.await?
+			{
+				samples.extend(bucket);
+			}
+		}
+		samples.retain(|sample| sample.timestamp + SAMPLE_RETENTION.as_secs() > now);
+		Ok(samples)
+	}
+
+	/// Predicts the gas price of each of the next `lookahead_hours` hours.
+	///
+	/// Returns `(unix_timestamp, predicted_gas_gwei)` pairs in chronological
+	/// order, where the timestamp is the start of the hour. The prediction is
+	/// the average over the last 7 days of the hours at the same hour of the
+	/// week, falling back to the same hour of the day. Hours without any
+	/// history are omitted.
+	pub async fn predict_low_gas_window(
+		&self,
+		chain_id: u64,
+		lookahead_hours: u8,
+	) -> Vec<(u64, u64)> {
+		self.predict_at(chain_id, lookahead_hours, current_timestamp())
+			.await
+	}
+
+	/// Predicts the gas prices of the hours following `now`.
+	async fn predict_at(&self, chain_id: u64, lookahead_hours: u8, now: u64) -> Vec<(u64, u64)> {
+		let averages = match self
+			.load::<Vec<HourlyGasPrice>>(&averages_id(chain_id))
+			.await
+		{
+			Ok(averages) => averages.unwrap_or_default(),
+			Err(e) => {
+				tracing::warn!(chain_id = chain_id, error = %e, "Failed to load gas price history");
+				return Vec::new();
+			},
+		};
+		let current_hour = now / HOUR_SECONDS;
+		let averages: Vec<&HourlyGasPrice> = averages
+			.iter()
+			.filter(|average| average.hour + AVERAGE_WINDOW_HOURS > current_hour)
+			.collect();
+
+		(1..=u64::from(lookahead_hours))
+			.filter_map(|offset| {
+				let hour = current_hour + offset;
+				mean(
+					averages
+						.iter()
+						.filter(|a| a.hour % HOURS_PER_WEEK == hour % HOURS_PER_WEEK),
+				)
+				.or_else(|| {
+					mean(
+						averages
+							.iter()
+							.filter(|a| a.hour % HOURS_PER_DAY == hour % HOURS_PER_DAY),
+					)
+				})
+				.map(|gwei| (hour * HOUR_SECONDS, gwei.round() as u64))
+			})
+			.collect()
+	}
+
+	/// Spawns a task that samples gas prices at the given interval.
+	pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(interval);
+			loop {
+				ticker.tick().await;
+				self.sample().await;
+			}
+		})
+	}
+
+	/// Loads an entry, treating a missing entry as `None`.
+	async fn load<T: serde::de::DeserializeOwned>(
+		&self,
+		id: &str,
+	) -> Result<Option<T>, StorageError> {
+		match self
+			.storage
+			.retrieve::<T>(StorageKey::GasPriceHistory.as_str(), id)
+			.await
+		{
+			Ok(value) => Ok(Some(value)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+/// Returns the average gas price of the given hours, if any.
+fn mean<'a>(averages: impl Iterator<Item = &'a &'a HourlyGasPrice>) -> Option<f64> {
+	let (total, count) = averages.fold((0.0, 0u64), |(total, count), average| {
+		(total + average.average_gwei(), count + 1)
+	});
+	(count > 0).then(|| total / count as f64)
+}
+
+/// Builds the storage id of the samples of a chain taken in an hour.
+fn samples_id(chain_id: u64, hour: u64) -> String {
+	format!("{}_samples_{}", chain_id, hour)
+}
+
+/// Builds the storage id of the hourly averages of a chain.
+fn averages_id(chain_id: u64) -> String {
+	format!("{}_hourly", chain_id)
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+fn current_timestamp() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+	use std::collections::HashMap;
+
+	fn sample(timestamp: u64, gas_price_gwei: f64) -> GasPriceSample {
+		GasPriceSample {
+			timestamp,
+			gas_price_gwei,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_predict_low_gas_window_by_hour_of_week() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let delivery = Arc::new(DeliveryService::new(HashMap::new(), 1));
+		let analyzer = GasPriceHistoryAnalyzer::new(storage, delivery, vec![1]);
+
+		// Two days ago: hour 10 cheap, hour 11 expensive
+		let base = 1_000 * HOURS_PER_WEEK * HOUR_SECONDS;
+		let two_days_ago = base - 2 * HOURS_PER_DAY * HOUR_SECONDS;
+		analyzer
+			.record(1, &sample(two_days_ago + 10 * HOUR_SECONDS, 10.0))
+			.await
+			.unwrap();
+		analyzer
+			.record(1, &sample(two_days_ago + 10 * HOUR_SECONDS + 60, 20.0))
+			.await
+			.unwrap();
+		analyzer
+			.record(1, &sample(two_days_ago + 11 * HOUR_SECONDS, 80.0))
+			.await
+			.unwrap();
+		// Six days ago at hour 11, the same hour of the week as tomorrow's
+		let six_days_ago = base - 6 * HOURS_PER_DAY * HOUR_SECONDS;
+		analyzer
+			.record(1, &sample(six_days_ago + 11 * HOUR_SECONDS, 40.0))
+			.await
+			.unwrap();
+
+		let now = base + 9 * HOUR_SECONDS + 30;
+		let predictions = analyzer.predict_at(1, 3, now).await;
+		assert_eq!(
+			predictions,
+			vec![
+				(base + 10 * HOUR_SECONDS, 15),
+				(base + 11 * HOUR_SECONDS, 60),
+			]
+		);
+
+		let tomorrow = now + HOURS_PER_DAY * HOUR_SECONDS;
+		assert_eq!(
+			analyzer.predict_at(1, 2, tomorrow).await,
+			vec![
+				(base + 34 * HOUR_SECONDS, 15),
+				(base + 35 * HOUR_SECONDS, 40),
+			]
+		);
+		assert!(analyzer.predict_at(2, 3, now).await.is_empty());
+	}
+}
@@ -3,21 +3,36 @@
 //! This module provides concrete implementations of the DeliveryInterface trait,
 //! supporting blockchain transaction submission and monitoring using the Alloy library.
 
-use crate::{DeliveryError, DeliveryInterface};
+use crate::revert::decode_revert;
+use crate::rpc_rotator::{RpcRotationConfig, RpcRotator};
+use crate::{DeliveryError, DeliveryFuture, DeliveryInterface};
 use alloy_network::EthereumWallet;
 use alloy_primitives::{Address, FixedBytes, U256};
 use alloy_provider::{Provider, ProviderBuilder};
-use alloy_rpc_types::TransactionRequest;
+use alloy_rpc_client::BatchRequest;
+use alloy_rpc_types::{BlockNumberOrTag, TransactionRequest};
 use alloy_signer::Signer;
 use alloy_signer_local::PrivateKeySigner;
 use alloy_transport_http::Http;
 use async_trait::async_trait;
 use solver_types::{
-	with_0x_prefix, ConfigSchema, Field, FieldType, NetworksConfig, Schema,
-	Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
+	with_0x_prefix, Call, ConfigSchema, FeeHistory, Field, FieldType, NetworksConfig, Schema,
+	SchemaDefinition, TraceLog, Transaction as SolverTransaction, TransactionHash,
+	TransactionReceipt, TransactionTrace,
 };
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Alloy provider of a network.
+type HttpProvider = Arc<dyn Provider<Http<reqwest::Client>> + Send + Sync>;
+
+/// Providers serving the read-only calls of a network.
+struct ReadProviders {
+	rotator: RpcRotator,
+	/// One provider per read endpoint of the rotator.
+	providers: Vec<HttpProvider>,
+}
 
 /// Alloy-based EVM delivery implementation.
 ///
@@ -25,8 +40,16 @@ use std::sync::Arc;
 /// on EVM-compatible blockchains. It handles transaction signing, submission,
 /// and confirmation tracking. Supports multiple networks with a single instance.
 pub struct AlloyDelivery {
-	/// Alloy providers for each supported network.
-	providers: HashMap<u64, Arc<dyn Provider<Http<reqwest::Client>> + Send + Sync>>,
+	/// Alloy providers for each supported network, connected to its primary endpoint.
+	providers: HashMap<u64, HttpProvider>,
+	/// Providers serving receipt, block number and balance reads of each network.
+	read_providers: HashMap<u64, ReadProviders>,
+	/// Address of the signer used on each network, used as sender for simulations.
+	senders: HashMap<u64, Address>,
+	/// Whether to append the memo tag to transaction calldata.
+	enable_transaction_tagging: bool,
+	/// Networks whose nodes rejected `debug_traceCall`, simulated with `eth_call` instead.
+	trace_unsupported: Mutex<HashSet<u64>>,
 }
 
 impl AlloyDelivery {
@@ -34,12 +57,15 @@ impl AlloyDelivery {
 	///
 	/// Configures Alloy providers for multiple networks with the specified
 	/// RPC URLs and signers for transaction submission. The default_signer is used
-	/// for networks that don't have a specific signer configured.
+	/// for networks that don't have a specific signer configured. Networks
+	/// without an RPC rotation configuration read from all their HTTP URLs in
+	/// turn and submit to the first one.
 	pub async fn new(
 		network_ids: Vec<u64>,
 		networks: &NetworksConfig,
 		signers: HashMap<u64, PrivateKeySigner>,
 		default_signer: PrivateKeySigner,
+		rpc_rotation: &HashMap<u64, RpcRotationConfig>,
 	) -> Result<Self, DeliveryError> {
 		// Validate at least one network
 		if network_ids.is_empty() {
@@ -49,6 +75,8 @@ impl AlloyDelivery {
 		}
 
 		let mut providers = HashMap::new();
+		let mut read_providers = HashMap::new();
+		let mut senders = HashMap::new();
 
 		for network_id in &network_ids {
 			// Get network configuration
@@ -64,14 +92,58 @@ impl AlloyDelivery {
 				))
 			})?;
 
+			// Reads are spread over the rotation endpoints of networks that opt
+			// in, writes go to the primary one
+			let rotation = rpc_rotation.get(network_id).cloned();
+			let read_urls = match &rotation {
+				Some(rotation) if rotation.rpc_urls.is_empty() => network
+					.get_all_http_urls()
+					.into_iter()
+					.map(str::to_string)
+					.collect(),
+				Some(rotation) => rotation.rpc_urls.clone(),
+				None => Vec::new(),
+			};
+			let rotation = rotation.unwrap_or_default();
+			let rotator = RpcRotator::new(
+				rotation.primary_url.unwrap_or_else(|| http_url.to_string()),
+				read_urls,
+				rotation.read_strategy,
+			);
+
 			// Parse RPC URL
-			let url = http_url.parse().map_err(|e| {
-				DeliveryError::Network(format!("Invalid RPC URL for network {}: {}", network_id, e))
-			})?;
+			let parse_url = |url: &str| {
+				url.parse().map_err(|e| {
+					DeliveryError::Network(format!(
+						"Invalid RPC URL for network {}: {}",
+						network_id, e
+					))
+				})
+			};
+			let url = parse_url(rotator.primary_url())?;
+			let read = rotator
+				.read_urls()
+				.iter()
+				.map(|read_url| {
+					Ok(
+						Arc::new(ProviderBuilder::new().on_http(parse_url(read_url)?))
+							as HttpProvider,
+					)
+				})
+				.collect::<Result<Vec<_>, DeliveryError>>()?;
+			read_providers.insert(
+				*network_id,
+				ReadProviders {
+					rotator,
+					providers: read,
+				},
+			);
 
 			// Get the signer for this network, or use the default
 			let signer = signers.get(network_id).unwrap_or(&default_signer);
 
+			senders.insert(*network_id, signer.address());
+
 			// Create signer with chain ID
 			let chain_signer = signer.clone().with_chain_id(Some(*network_id));
 			let wallet = EthereumWallet::from(chain_signer);
@@ -86,24 +158,67 @@ impl AlloyDelivery {
 				.client()
 				.set_poll_interval(std::time::Duration::from_secs(7));
 
-			providers.insert(
-				*network_id,
-				Arc::new(provider) as Arc<dyn Provider<Http<reqwest::Client>> + Send + Sync>,
-			);
+			providers.insert(*network_id, Arc::new(provider) as HttpProvider);
 		}
 
-		Ok(Self { providers })
+		Ok(Self {
+			providers,
+			read_providers,
+			senders,
+			enable_transaction_tagging: false,
+			trace_unsupported: Mutex::new(HashSet::new()),
+		})
+	}
+
+	/// Enables or disables transaction tagging.
+	///
+	/// When enabled, the first 4 bytes of a transaction's memo are appended
+	/// after its calldata so it can be reconciled against the order ID.
+	pub fn with_transaction_tagging(mut self, enabled: bool) -> Self {
+		self.enable_transaction_tagging = enabled;
+		self
+	}
+
+	/// Returns the calldata to submit for a transaction, with the
+	/// reconciliation tag appended if tagging is enabled.
+	fn calldata(&self, tx: &SolverTransaction) -> Vec<u8> {
+		if self.enable_transaction_tagging {
+			tx.tagged_data()
+		} else {
+			tx.data.clone()
+		}
 	}
 
 	/// Gets the provider for a specific chain ID.
-	fn get_provider(
-		&self,
-		chain_id: u64,
-	) -> Result<&Arc<dyn Provider<Http<reqwest::Client>> + Send + Sync>, DeliveryError> {
+	fn get_provider(&self, chain_id: u64) -> Result<&HttpProvider, DeliveryError> {
 		self.providers.get(&chain_id).ok_or_else(|| {
 			DeliveryError::Network(format!("No provider configured for chain ID {}", chain_id))
 		})
 	}
+
+	/// Gets the address sending the transactions of a chain.
+	fn sender(&self, chain_id: u64) -> Result<Address, DeliveryError> {
+		self.senders.get(&chain_id).copied().ok_or_else(|| {
+			DeliveryError::Network(format!("No sender configured for chain ID {}", chain_id))
+		})
+	}
+
+	/// Gets the provider serving the next read-only call on a chain, with its
+	/// index for [`Self::record_read`].
+	fn get_read_provider(&self, chain_id: u64) -> Result<(usize, &HttpProvider), DeliveryError> {
+		let read = self.read_providers.get(&chain_id).ok_or_else(|| {
+			DeliveryError::Network(format!("No provider configured for chain ID {}", chain_id))
+		})?;
+		let index = read.rotator.select_read();
+		Ok((index, &read.providers[index]))
+	}
+
+	/// Records the latency of a read-only call served by a read provider.
+	fn record_read(&self, chain_id: u64, index: usize, started: Instant, success: bool) {
+		if let Some(read) = self.read_providers.get(&chain_id) {
+			read.rotator.record_read(index, started.elapsed(), success);
+		}
+	}
 }
 
 /// Configuration schema for Alloy delivery provider.
@@ -118,11 +233,10 @@ impl AlloyDeliverySchema {
 		let instance = Self;
 		instance.validate(config)
 	}
-}
 
-impl ConfigSchema for AlloyDeliverySchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema = Schema::new(
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
 			// Required fields
 			vec![Field::new(
 				"network_ids",
@@ -131,6 +245,7 @@ impl ConfigSchema for AlloyDeliverySchema {
 					max: None,
 				})),
 			)
+			.with_description("Networks transactions are delivered on")
 			.with_validator(|value| {
 				if let Some(arr) = value.as_array() {
 					if arr.is_empty() {
@@ -142,38 +257,73 @@ impl ConfigSchema for AlloyDeliverySchema {
 				}
 			})],
 			// Optional fields
-			vec![Field::new(
-				"accounts",
-				FieldType::Table(Schema::new(
-					vec![], // No required fields - network IDs are dynamic
-					vec![], // No optional fields - all entries should be account names
-				)),
-			)
-			.with_validator(|value| {
-				if let Some(table) = value.as_table() {
-					// Validate that keys are valid integers (network IDs)
-					// and values are strings (account names)
+			vec![
+				Field::new("enable_transaction_tagging", FieldType::Boolean)
+					.with_description("Append the memo tag of transactions to their calldata")
+					.with_default(false),
+				Field::new(
+					"rpc_rotation",
+					FieldType::Table(Schema::new(vec![], vec![])),
+				)
+				.with_description("RPC endpoint rotation settings by network ID")
+				.with_validator(|value| {
+					let table = value
+						.as_table()
+						.ok_or_else(|| "rpc_rotation must be a table".to_string())?;
 					for (key, val) in table {
-						// Try to parse key as network ID
 						if key.parse::<u64>().is_err() {
-							return Err(format!("Invalid network ID in accounts: {}", key));
-						}
-						// Check value is a string
-						if !val.is_str() {
-							return Err(format!(
-								"Account name for network {} must be a string",
-								key
-							));
+							return Err(format!("Invalid network ID in rpc_rotation: {}", key));
 						}
+						val.clone().try_into::<RpcRotationConfig>().map_err(|e| {
+							format!("Invalid rpc_rotation for network {}: {}", key, e)
+						})?;
 					}
 					Ok(())
-				} else {
-					Err("accounts must be a table".to_string())
-				}
-			})],
-		);
+				}),
+				Field::new(
+					"accounts",
+					FieldType::Table(Schema::new(
+						vec![], // No required fields - network IDs are dynamic
+						vec![], // No optional fields - all entries should be account names
+					)),
+				)
+				.with_description(
+					"Account signing transactions by network ID, defaulting to the primary account",
+				)
+				.with_validator(|value| {
+					if let Some(table) = value.as_table() {
+						// Validate that keys are valid integers (network IDs)
+						// and values are strings (account names)
+						for (key, val) in table {
+							// Try to parse key as network ID
+							if key.parse::<u64>().is_err() {
+								return Err(format!("Invalid network ID in accounts: {}", key));
+							}
+							// Check value is a string
+							if !val.is_str() {
+								return Err(format!(
+									"Account name for network {} must be a string",
+									key
+								));
+							}
+						}
+						Ok(())
+					} else {
+						Err("accounts must be a table".to_string())
+					}
+				}),
+			],
+		)
+	}
+}
+
+impl ConfigSchema for AlloyDeliverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
 
-		schema.validate(config)
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -183,10 +333,12 @@ impl DeliveryInterface for AlloyDelivery {
 		Box::new(AlloyDeliverySchema)
 	}
 
-	async fn submit(&self, tx: SolverTransaction) -> Result<TransactionHash, DeliveryError> {
+	async fn submit(&self, mut tx: SolverTransaction) -> Result<TransactionHash, DeliveryError> {
 		// Get the chain ID from the transaction
 		let chain_id = tx.chain_id;
 
+		tx.data = self.calldata(&tx);
+
 		// Get the appropriate provider for this chain
 		let provider = self.get_provider(chain_id)?;
 
@@ -275,6 +427,8 @@ impl DeliveryInterface for AlloyDelivery {
 					hash: TransactionHash(receipt.transaction_hash.0.to_vec()),
 					block_number: tx_block,
 					success: receipt.status(),
+					gas_used: receipt.gas_used as u64,
+					effective_gas_price: receipt.effective_gas_price,
 				});
 			}
 
@@ -295,15 +449,15 @@ impl DeliveryInterface for AlloyDelivery {
 	) -> Result<TransactionReceipt, DeliveryError> {
 		let tx_hash = FixedBytes::<32>::from_slice(&hash.0);
 
-		// Get the provider for the specified chain
-		let provider = self.get_provider(chain_id)?;
+		// Get a read provider for the specified chain
+		let (index, provider) = self.get_read_provider(chain_id)?;
+
+		let started = Instant::now();
+		let result = provider.get_transaction_receipt(tx_hash).await;
+		self.record_read(chain_id, index, started, result.is_ok());
 
-		match provider.get_transaction_receipt(tx_hash).await {
-			Ok(Some(receipt)) => Ok(TransactionReceipt {
-				hash: TransactionHash(receipt.transaction_hash.0.to_vec()),
-				block_number: receipt.block_number.unwrap_or(0),
-				success: receipt.status(),
-			}),
+		match result {
+			Ok(Some(receipt)) => Ok(to_receipt(&receipt)),
 			Ok(None) => Err(DeliveryError::Network(format!(
 				"Transaction not found on chain {}",
 				chain_id
@@ -315,6 +469,48 @@ impl DeliveryInterface for AlloyDelivery {
 		}
 	}
 
+	async fn batch_get_receipts(
+		&self,
+		hashes: &[TransactionHash],
+		chain_id: u64,
+	) -> Result<Vec<Option<TransactionReceipt>>, DeliveryError> {
+		if hashes.is_empty() {
+			return Ok(Vec::new());
+		}
+		let provider = self.get_provider(chain_id)?;
+
+		// All receipts are requested in a single JSON-RPC batch
+		let mut batch = BatchRequest::new(provider.client());
+		let waiters = hashes
+			.iter()
+			.map(|hash| {
+				batch.add_call::<_, Option<alloy_rpc_types::TransactionReceipt>>(
+					"eth_getTransactionReceipt",
+					&(FixedBytes::<32>::from_slice(&hash.0),),
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|e| DeliveryError::Network(format!("Failed to build receipt batch: {}", e)))?;
+		batch.send().await.map_err(|e| {
+			DeliveryError::Network(format!(
+				"Failed to get receipts on chain {}: {}",
+				chain_id, e
+			))
+		})?;
+
+		let mut receipts = Vec::with_capacity(waiters.len());
+		for waiter in waiters {
+			match waiter.await {
+				Ok(receipt) => receipts.push(receipt.as_ref().map(to_receipt)),
+				Err(e) => {
+					tracing::debug!(chain_id, error = %e, "Failed to get receipt in batch");
+					receipts.push(None);
+				},
+			}
+		}
+		Ok(receipts)
+	}
+
 	async fn get_gas_price(&self, chain_id: u64) -> Result<String, DeliveryError> {
 		let provider = self.get_provider(chain_id)?;
 
@@ -336,14 +532,15 @@ impl DeliveryInterface for AlloyDelivery {
 			.parse()
 			.map_err(|e| DeliveryError::Network(format!("Invalid address: {}", e)))?;
 
-		let provider = self.get_provider(chain_id)?;
+		let (index, provider) = self.get_read_provider(chain_id)?;
+		let started = Instant::now();
 
 		match token {
 			None => {
 				// Get native token balance
-				let balance = provider
-					.get_balance(address)
-					.await
+				let result = provider.get_balance(address).await;
+				self.record_read(chain_id, index, started, result.is_ok());
+				let balance = result
 					.map_err(|e| DeliveryError::Network(format!("Failed to get balance: {}", e)))?;
 
 				Ok(balance.to_string())
@@ -362,16 +559,17 @@ impl DeliveryInterface for AlloyDelivery {
 				call_data.extend_from_slice(&[0; 12]); // Pad to 32 bytes
 				call_data.extend_from_slice(address.as_slice());
 
-				let call_result = provider
+				let result = provider
 					.call(
 						&TransactionRequest::default()
 							.to(token_addr)
 							.input(call_data.into()),
 					)
-					.await
-					.map_err(|e| {
-						DeliveryError::Network(format!("Failed to call balanceOf: {}", e))
-					})?;
+					.await;
+				self.record_read(chain_id, index, started, result.is_ok());
+				let call_result = result.map_err(|e| {
+					DeliveryError::Network(format!("Failed to call balanceOf: {}", e))
+				})?;
 
 				if call_result.len() < 32 {
 					return Err(DeliveryError::Network(
@@ -448,13 +646,306 @@ impl DeliveryInterface for AlloyDelivery {
 			.map_err(|e| DeliveryError::Network(format!("Failed to get nonce: {}", e)))
 	}
 
-	async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError> {
+	async fn get_code(&self, address: &str, chain_id: u64) -> Result<Vec<u8>, DeliveryError> {
+		let address: Address = address
+			.parse()
+			.map_err(|e| DeliveryError::Network(format!("Invalid address: {}", e)))?;
+
 		let provider = self.get_provider(chain_id)?;
 
 		provider
-			.get_block_number()
+			.get_code_at(address)
+			.await
+			.map(|code| code.to_vec())
+			.map_err(|e| DeliveryError::Network(format!("Failed to get code: {}", e)))
+	}
+
+	async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError> {
+		let (index, provider) = self.get_read_provider(chain_id)?;
+
+		let started = Instant::now();
+		let result = provider.get_block_number().await;
+		self.record_read(chain_id, index, started, result.is_ok());
+
+		result.map_err(|e| DeliveryError::Network(format!("Failed to get block number: {}", e)))
+	}
+
+	async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		let provider = self.get_provider(chain_id)?;
+
+		let history = provider
+			.get_fee_history(block_count, BlockNumberOrTag::Latest, reward_percentiles)
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get fee history: {}", e)))?;
+
+		Ok(FeeHistory {
+			base_fee_per_gas: history.base_fee_per_gas,
+			reward: history.reward.unwrap_or_default(),
+		})
+	}
+
+	async fn estimate_gas(
+		&self,
+		tx: &SolverTransaction,
+	) -> Result<TransactionTrace, DeliveryError> {
+		let provider = self.get_provider(tx.chain_id)?;
+
+		let mut request: TransactionRequest = tx.clone().into();
+		if let Some(sender) = self.senders.get(&tx.chain_id) {
+			request = request.from(*sender);
+		}
+
+		match provider.call(&request).await {
+			Ok(return_data) => {
+				let gas_used = provider.estimate_gas(&request).await.map_err(|e| {
+					DeliveryError::Network(format!("Failed to estimate gas: {}", e))
+				})?;
+
+				Ok(TransactionTrace {
+					success: true,
+					gas_used: Some(gas_used),
+					return_data: with_0x_prefix(&hex::encode(&return_data)),
+					revert_reason: None,
+					logs: Vec::new(),
+				})
+			},
+			Err(e) => {
+				// Only execution reverts are reported in the trace
+				let payload = e.as_error_resp().ok_or_else(|| {
+					DeliveryError::Network(format!("Failed to simulate transaction: {}", e))
+				})?;
+				let revert_data = payload
+					.as_revert_data()
+					.map(|data| data.to_vec())
+					.unwrap_or_default();
+				let revert_reason =
+					decode_revert(&revert_data).unwrap_or_else(|| payload.message.to_string());
+
+				Ok(TransactionTrace {
+					success: false,
+					gas_used: None,
+					return_data: with_0x_prefix(&hex::encode(&revert_data)),
+					revert_reason: Some(revert_reason),
+					logs: Vec::new(),
+				})
+			},
+		}
+	}
+
+	async fn simulate_transaction(
+		&self,
+		tx: &SolverTransaction,
+	) -> Result<TransactionTrace, DeliveryError> {
+		let chain_id = tx.chain_id;
+		if self.trace_unsupported.lock().unwrap().contains(&chain_id) {
+			return self.estimate_gas(tx).await;
+		}
+		let provider = self.get_provider(chain_id)?;
+
+		let mut request: TransactionRequest = tx.clone().into();
+		if let Some(sender) = self.senders.get(&chain_id) {
+			request = request.from(*sender);
+		}
+
+		let tracer = serde_json::json!({
+			"tracer": "callTracer",
+			"tracerConfig": { "withLog": true }
+		});
+		let frame: CallFrame = match provider
+			.client()
+			.request("debug_traceCall", (request.clone(), "latest", tracer))
+			.await
+		{
+			Ok(frame) => frame,
+			Err(e) => {
+				// Nodes without the debug namespace answer with an error response
+				if e.as_error_resp().is_some() {
+					tracing::debug!(chain_id, error = %e, "debug_traceCall unsupported, simulating with eth_call");
+					self.trace_unsupported.lock().unwrap().insert(chain_id);
+				}
+				return self.estimate_gas(tx).await;
+			},
+		};
+
+		let return_data = frame.output.clone().unwrap_or_default();
+		if let Some(error) = &frame.error {
+			let revert_data = hex::decode(return_data.trim_start_matches("0x")).unwrap_or_default();
+			let revert_reason = decode_revert(&revert_data)
+				.or_else(|| frame.revert_reason.clone())
+				.unwrap_or_else(|| error.clone());
+			return Ok(TransactionTrace {
+				success: false,
+				gas_used: None,
+				return_data: with_0x_prefix(&return_data),
+				revert_reason: Some(revert_reason),
+				logs: Vec::new(),
+			});
+		}
+
+		let gas_used = provider
+			.estimate_gas(&request)
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to estimate gas: {}", e)))?;
+		let mut logs = Vec::new();
+		frame.collect_logs(&mut logs);
+		Ok(TransactionTrace {
+			success: true,
+			gas_used: Some(gas_used),
+			return_data: with_0x_prefix(&return_data),
+			revert_reason: None,
+			logs,
+		})
+	}
+
+	async fn supports_wallet_batch(&self, chain_id: u64) -> Result<bool, DeliveryError> {
+		let provider = self.get_provider(chain_id)?;
+		let sender = self.sender(chain_id)?;
+		let capabilities: serde_json::Value = provider
+			.client()
+			.request(
+				"wallet_getCapabilities",
+				(sender, [format!("{:#x}", chain_id)]),
+			)
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get capabilities: {}", e)))?;
+		Ok(atomic_batch_supported(&capabilities, chain_id))
+	}
+
+	async fn wallet_send_calls(
+		&self,
+		calls: Vec<Call>,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		let provider = self.get_provider(chain_id)?;
+		let sender = self.sender(chain_id)?;
+		let call_count = calls.len();
+		let calls: Vec<serde_json::Value> = calls
+			.iter()
+			.map(|call| {
+				serde_json::json!({
+					"to": with_0x_prefix(&hex::encode(&call.to.0)),
+					"data": with_0x_prefix(&hex::encode(&call.data)),
+					"value": format!("{:#x}", call.value),
+				})
+			})
+			.collect();
+		let request = serde_json::json!({
+			"version": "2.0.0",
+			"chainId": format!("{:#x}", chain_id),
+			"from": sender,
+			"atomicRequired": true,
+			"calls": calls,
+		});
+
+		let response: serde_json::Value = provider
+			.client()
+			.request("wallet_sendCalls", (request,))
 			.await
-			.map_err(|e| DeliveryError::Network(format!("Failed to get block number: {}", e)))
+			.map_err(|e| DeliveryError::Network(format!("Failed to send calls: {}", e)))?;
+		// Version 1 wallets return the batch ID itself, version 2 wallets an object
+		let batch_id = response
+			.get("id")
+			.unwrap_or(&response)
+			.as_str()
+			.ok_or_else(|| {
+				DeliveryError::Network(format!("Invalid wallet_sendCalls response: {}", response))
+			})?
+			.to_string();
+		tracing::info!(batch_id = %batch_id, chain_id, calls = call_count, "Submitted call batch");
+		Ok(batch_id)
+	}
+
+	async fn wallet_calls_status(
+		&self,
+		batch_id: &str,
+		chain_id: u64,
+	) -> Result<Option<TransactionHash>, DeliveryError> {
+		let provider = self.get_provider(chain_id)?;
+		let status: serde_json::Value = provider
+			.client()
+			.request("wallet_getCallsStatus", (batch_id.to_string(),))
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get calls status: {}", e)))?;
+		batch_transaction_hash(&status)
+	}
+}
+
+/// Returns whether `wallet_getCapabilities` reports atomic call batches for a
+/// chain, in either the version 2 (`atomic.status`) or the version 1
+/// (`atomicBatch.supported`) format.
+fn atomic_batch_supported(capabilities: &serde_json::Value, chain_id: u64) -> bool {
+	let Some(chain) = capabilities.get(format!("{:#x}", chain_id)) else {
+		return false;
+	};
+	matches!(
+		chain["atomic"]["status"].as_str(),
+		Some("supported") | Some("ready")
+	) || chain["atomicBatch"]["supported"].as_bool() == Some(true)
+}
+
+/// Returns the hash of the transaction that included a call batch from its
+/// `wallet_getCallsStatus` response, or None while the batch is pending.
+fn batch_transaction_hash(
+	status: &serde_json::Value,
+) -> Result<Option<TransactionHash>, DeliveryError> {
+	if let Some(receipt) = status["receipts"].as_array().and_then(|r| r.last()) {
+		let hash = receipt["transactionHash"].as_str().ok_or_else(|| {
+			DeliveryError::Network(format!("Call batch receipt without hash: {}", receipt))
+		})?;
+		let hash = hex::decode(hash.trim_start_matches("0x"))
+			.map_err(|e| DeliveryError::Network(format!("Invalid transaction hash: {}", e)))?;
+		return Ok(Some(TransactionHash(hash)));
+	}
+	// Version 2 status codes: 1xx pending, 2xx confirmed, 4xx and 5xx failed
+	match status["status"].as_u64() {
+		Some(code) if code >= 400 => Err(DeliveryError::TransactionFailed(format!(
+			"Call batch failed with status {}",
+			code
+		))),
+		_ => Ok(None),
+	}
+}
+
+/// Call frame returned by the `callTracer` of `debug_traceCall`.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallFrame {
+	/// Data returned by the call (hex with 0x prefix).
+	output: Option<String>,
+	/// Error of a failed call, e.g. "execution reverted".
+	error: Option<String>,
+	/// Revert reason decoded by the node, if any.
+	revert_reason: Option<String>,
+	#[serde(default)]
+	logs: Vec<TraceLog>,
+	#[serde(default)]
+	calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+	/// Collects the logs of this frame and its successful subcalls, in the
+	/// order they were emitted by their frame.
+	fn collect_logs(&self, logs: &mut Vec<TraceLog>) {
+		logs.extend(self.logs.iter().cloned());
+		for call in self.calls.iter().filter(|call| call.error.is_none()) {
+			call.collect_logs(logs);
+		}
+	}
+}
+
+/// Converts an Alloy transaction receipt into the solver's receipt type.
+fn to_receipt(receipt: &alloy_rpc_types::TransactionReceipt) -> TransactionReceipt {
+	TransactionReceipt {
+		hash: TransactionHash(receipt.transaction_hash.0.to_vec()),
+		block_number: receipt.block_number.unwrap_or(0),
+		success: receipt.status(),
+		gas_used: receipt.gas_used as u64,
+		effective_gas_price: receipt.effective_gas_price,
 	}
 }
 
@@ -467,18 +958,58 @@ impl DeliveryInterface for AlloyDelivery {
 /// - `config`: TOML configuration containing:
 ///   - `network_ids` (required): Array of network IDs to support
 ///   - `accounts` (optional): Map of network IDs to account names for per-network signing
+///   - `enable_transaction_tagging` (optional): Append order ID tags to calldata (default: false)
+///   - `rpc_rotation` (optional): Map of network IDs to their read endpoints (`rpc_urls`),
+///     submission endpoint (`primary_url`) and `read_strategy`
 /// - `networks`: Network configuration containing RPC URLs and contract addresses
 /// - `default_private_key`: Default private key for signing transactions
 /// - `network_private_keys`: Map of network IDs to private keys for per-network signing
 ///
 /// # Returns
-/// A boxed implementation of DeliveryInterface configured for the specified networks
+/// A future resolving to a boxed implementation of DeliveryInterface configured
+/// for the specified networks. The configuration is parsed before the future
+/// is returned; the future connects to the networks.
 pub fn create_http_delivery(
 	config: &toml::Value,
 	networks: &NetworksConfig,
 	default_private_key: &solver_types::SecretString,
 	network_private_keys: &HashMap<u64, solver_types::SecretString>,
-) -> Result<Box<dyn DeliveryInterface>, DeliveryError> {
+) -> DeliveryFuture {
+	match http_delivery_params(config, default_private_key, network_private_keys) {
+		Ok(params) => {
+			let networks = networks.clone();
+			Box::pin(async move {
+				let delivery = AlloyDelivery::new(
+					params.network_ids,
+					&networks,
+					params.network_signers,
+					params.default_signer,
+					&params.rpc_rotation,
+				)
+				.await?
+				.with_transaction_tagging(params.enable_transaction_tagging);
+				Ok(Box::new(delivery) as Box<dyn DeliveryInterface>)
+			})
+		},
+		Err(e) => Box::pin(std::future::ready(Err(e))),
+	}
+}
+
+/// Parameters of an HTTP delivery parsed from its configuration.
+struct HttpDeliveryParams {
+	network_ids: Vec<u64>,
+	network_signers: HashMap<u64, PrivateKeySigner>,
+	default_signer: PrivateKeySigner,
+	rpc_rotation: HashMap<u64, RpcRotationConfig>,
+	enable_transaction_tagging: bool,
+}
+
+/// Validates and parses the configuration of an HTTP delivery.
+fn http_delivery_params(
+	config: &toml::Value,
+	default_private_key: &solver_types::SecretString,
+	network_private_keys: &HashMap<u64, solver_types::SecretString>,
+) -> Result<HttpDeliveryParams, DeliveryError> {
 	// Validate configuration first
 	AlloyDeliverySchema::validate_config(config)
 		.map_err(|e| DeliveryError::Network(format!("Invalid configuration: {}", e)))?;
@@ -500,6 +1031,30 @@ pub fn create_http_delivery(
 		));
 	}
 
+	let enable_transaction_tagging = config
+		.get("enable_transaction_tagging")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false);
+
+	let mut rpc_rotation = HashMap::new();
+	if let Some(table) = config.get("rpc_rotation").and_then(|v| v.as_table()) {
+		for (network_id, value) in table {
+			let network_id = network_id.parse::<u64>().map_err(|_| {
+				DeliveryError::Network(format!(
+					"Invalid network ID in rpc_rotation: {}",
+					network_id
+				))
+			})?;
+			let rotation: RpcRotationConfig = value.clone().try_into().map_err(|e| {
+				DeliveryError::Network(format!(
+					"Invalid rpc_rotation for network {}: {}",
+					network_id, e
+				))
+			})?;
+			rpc_rotation.insert(network_id, rotation);
+		}
+	}
+
 	// Parse the default signer
 	let default_signer: PrivateKeySigner = default_private_key.with_exposed(|key| {
 		key.parse()
@@ -520,14 +1075,13 @@ pub fn create_http_delivery(
 		network_signers.insert(*network_id, signer);
 	}
 
-	// Create delivery service synchronously, but the actual connection happens async
-	let delivery = tokio::task::block_in_place(|| {
-		tokio::runtime::Handle::current().block_on(async {
-			AlloyDelivery::new(network_ids, networks, network_signers, default_signer).await
-		})
-	})?;
-
-	Ok(Box::new(delivery))
+	Ok(HttpDeliveryParams {
+		network_ids,
+		network_signers,
+		default_signer,
+		rpc_rotation,
+		enable_transaction_tagging,
+	})
 }
 
 /// Registry for the HTTP/Alloy delivery implementation.
@@ -540,6 +1094,195 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_http_delivery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(AlloyDeliverySchema)
+	}
 }
 
 impl crate::DeliveryRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{networks::RpcEndpoint, NetworkConfig};
+	use tokio::io::{AsyncReadExt, AsyncWriteExt};
+	use tokio::net::TcpListener;
+
+	/// Serves a single HTTP request with `response`, returning the request body.
+	async fn serve_once(listener: TcpListener, response: serde_json::Value) -> serde_json::Value {
+		let (mut stream, _) = listener.accept().await.unwrap();
+		let mut request = Vec::new();
+		let mut buf = [0u8; 4096];
+		let body_start = loop {
+			let n = stream.read(&mut buf).await.unwrap();
+			request.extend_from_slice(&buf[..n]);
+			if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+				break pos + 4;
+			}
+		};
+		let headers = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+		let content_length: usize = headers
+			.lines()
+			.find_map(|line| line.strip_prefix("content-length:"))
+			.unwrap()
+			.trim()
+			.parse()
+			.unwrap();
+		while request.len() < body_start + content_length {
+			let n = stream.read(&mut buf).await.unwrap();
+			request.extend_from_slice(&buf[..n]);
+		}
+
+		let body = response.to_string();
+		let reply = format!(
+			"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+			body.len(),
+			body
+		);
+		stream.write_all(reply.as_bytes()).await.unwrap();
+		serde_json::from_slice(&request[body_start..]).unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_batch_get_receipts_sends_single_batch() {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let mut networks = NetworksConfig::new();
+		networks.insert(
+			1.into(),
+			NetworkConfig {
+				rpc_urls: vec![RpcEndpoint::http_only(url)],
+				input_settler_address: solver_types::Address(vec![0; 20]),
+				output_settler_address: solver_types::Address(vec![0; 20]),
+				tokens: Vec::new(),
+				input_settler_compact_address: None,
+			},
+		);
+		let delivery = AlloyDelivery::new(
+			vec![1],
+			&networks,
+			HashMap::new(),
+			PrivateKeySigner::random(),
+			&HashMap::new(),
+		)
+		.await
+		.unwrap();
+
+		let mined = TransactionHash(vec![0x11; 32]);
+		let pending = TransactionHash(vec![0x22; 32]);
+		let zero_address = format!("0x{}", "00".repeat(20));
+		let response = serde_json::json!([
+			{
+				"jsonrpc": "2.0",
+				"id": 1,
+				"result": null
+			},
+			{
+				"jsonrpc": "2.0",
+				"id": 0,
+				"result": {
+					"transactionHash": format!("0x{}", "11".repeat(32)),
+					"transactionIndex": "0x0",
+					"blockHash": format!("0x{}", "33".repeat(32)),
+					"blockNumber": "0x10",
+					"from": zero_address,
+					"to": zero_address,
+					"contractAddress": null,
+					"cumulativeGasUsed": "0x5208",
+					"gasUsed": "0x5208",
+					"effectiveGasPrice": "0x3b9aca00",
+					"logs": [],
+					"logsBloom": format!("0x{}", "00".repeat(256)),
+					"type": "0x2",
+					"status": "0x1"
+				}
+			}
+		]);
+		let server = tokio::spawn(serve_once(listener, response));
+
+		let receipts = delivery
+			.batch_get_receipts(&[mined.clone(), pending], 1)
+			.await
+			.unwrap();
+		let request = server.await.unwrap();
+
+		let calls = request.as_array().unwrap();
+		assert_eq!(calls.len(), 2);
+		assert!(calls
+			.iter()
+			.all(|call| call["method"] == "eth_getTransactionReceipt"));
+		assert_eq!(receipts.len(), 2);
+		let receipt = receipts[0].as_ref().unwrap();
+		assert_eq!(receipt.hash, mined);
+		assert_eq!(receipt.block_number, 16);
+		assert!(receipt.success);
+		assert_eq!(receipt.gas_used, 21_000);
+		assert!(receipts[1].is_none());
+	}
+
+	#[test]
+	fn test_wallet_batch_capabilities_and_status() {
+		let capabilities = serde_json::json!({
+			"0x1": { "atomic": { "status": "supported" } },
+			"0xa": { "atomicBatch": { "supported": true } },
+			"0x2105": { "atomic": { "status": "unsupported" } }
+		});
+		assert!(atomic_batch_supported(&capabilities, 1));
+		assert!(atomic_batch_supported(&capabilities, 10));
+		assert!(!atomic_batch_supported(&capabilities, 8453));
+		assert!(!atomic_batch_supported(&capabilities, 137));
+
+		let pending = serde_json::json!({ "status": 100 });
+		assert!(batch_transaction_hash(&pending).unwrap().is_none());
+		let confirmed = serde_json::json!({
+			"status": 200,
+			"receipts": [{ "transactionHash": format!("0x{}", "11".repeat(32)) }]
+		});
+		assert_eq!(
+			batch_transaction_hash(&confirmed).unwrap(),
+			Some(TransactionHash(vec![0x11; 32]))
+		);
+		let failed = serde_json::json!({ "status": 400 });
+		assert!(batch_transaction_hash(&failed).is_err());
+	}
+
+	#[tokio::test]
+	async fn test_transaction_tagging() {
+		let mut networks = NetworksConfig::new();
+		networks.insert(
+			1.into(),
+			NetworkConfig {
+				rpc_urls: vec![RpcEndpoint::http_only("http://127.0.0.1:1".to_string())],
+				input_settler_address: solver_types::Address(vec![0; 20]),
+				output_settler_address: solver_types::Address(vec![0; 20]),
+				tokens: Vec::new(),
+				input_settler_compact_address: None,
+			},
+		);
+		let delivery = AlloyDelivery::new(
+			vec![1],
+			&networks,
+			HashMap::new(),
+			PrivateKeySigner::random(),
+			&HashMap::new(),
+		)
+		.await
+		.unwrap();
+
+		let mut tx = SolverTransaction::from(TransactionRequest::default());
+		tx.data = vec![0x12, 0x34, 0x56, 0x78];
+		let tx = tx.with_memo([0xaa, 0xbb, 0xcc, 0xdd, 0xee]);
+
+		// Tagging is disabled by default and leaves the calldata unchanged
+		assert_eq!(delivery.calldata(&tx), tx.data);
+		let delivery = delivery.with_transaction_tagging(false);
+		assert_eq!(delivery.calldata(&tx), tx.data);
+
+		let delivery = delivery.with_transaction_tagging(true);
+		assert_eq!(
+			delivery.calldata(&tx),
+			vec![0x12, 0x34, 0x56, 0x78, 0xaa, 0xbb, 0xcc, 0xdd]
+		);
+	}
+}
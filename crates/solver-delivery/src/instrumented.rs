@@ -0,0 +1,372 @@
+//! RPC call telemetry for delivery implementations.
+//!
+//! The wrapper in this module times every call made through a delivery
+//! implementation and records it both as Prometheus metrics and in shared
+//! per-method statistics, which the admin API reports so that operators can
+//! see which RPC methods are slowest.
+
+use crate::{DeliveryError, DeliveryInterface};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::Serialize;
+use solver_types::{
+	Call, ConfigSchema, FeeHistory, Transaction, TransactionHash, TransactionReceipt,
+	TransactionTrace,
+};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Name of the histogram of RPC call durations in seconds.
+const CALL_DURATION_HISTOGRAM: &str = "delivery_rpc_call_duration_seconds";
+/// Name of the counter of RPC calls by outcome.
+const CALL_COUNTER: &str = "delivery_rpc_call_total";
+/// Name of the counter of transaction submissions by outcome.
+const SUBMIT_COUNTER: &str = "delivery_tx_submit_total";
+
+/// Accumulated calls of a method on a chain.
+#[derive(Default)]
+struct MethodTotals {
+	calls: u64,
+	errors: u64,
+	total_duration: Duration,
+	max_duration: Duration,
+}
+
+/// Statistics of the calls of a method on a chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcMethodStats {
+	/// Name of the delivery method.
+	pub method: String,
+	/// Chain the calls were made on.
+	pub chain_id: u64,
+	/// Number of calls.
+	pub calls: u64,
+	/// Number of calls that returned an error.
+	pub errors: u64,
+	/// Average call duration in milliseconds.
+	pub avg_duration_ms: f64,
+	/// Longest call duration in milliseconds.
+	pub max_duration_ms: f64,
+}
+
+/// Per-method call statistics shared by the instrumented implementations of
+/// a delivery service.
+#[derive(Default)]
+pub struct RpcCallStats {
+	totals: DashMap<(&'static str, u64), MethodTotals>,
+}
+
+impl RpcCallStats {
+	/// Creates empty statistics.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Records a call of a method on a chain.
+	fn record(&self, method: &'static str, chain_id: u64, duration: Duration, success: bool) {
+		let mut totals = self.totals.entry((method, chain_id)).or_default();
+		totals.calls += 1;
+		if !success {
+			totals.errors += 1;
+		}
+		totals.total_duration += duration;
+		totals.max_duration = totals.max_duration.max(duration);
+	}
+
+	/// Returns the statistics of every method and chain, slowest on average
+	/// first.
+	pub fn snapshot(&self) -> Vec<RpcMethodStats> {
+		let mut stats: Vec<RpcMethodStats> = self
+			.totals
+			.iter()
+			.map(|entry| {
+				let (method, chain_id) = *entry.key();
+				let totals = entry.value();
+				RpcMethodStats {
+					method: method.to_string(),
+					chain_id,
+					calls: totals.calls,
+					errors: totals.errors,
+					avg_duration_ms: totals.total_duration.as_secs_f64() * 1000.0
+						/ totals.calls.max(1) as f64,
+					max_duration_ms: totals.max_duration.as_secs_f64() * 1000.0,
+				}
+			})
+			.collect();
+		stats.sort_by(|a, b| b.avg_duration_ms.total_cmp(&a.avg_duration_ms));
+		stats
+	}
+}
+
+/// Delivery implementation recording the duration and outcome of each call
+/// of the wrapped implementation.
+pub struct InstrumentedDelivery {
+	inner: Arc<dyn DeliveryInterface>,
+	stats: Arc<RpcCallStats>,
+}
+
+impl InstrumentedDelivery {
+	/// Wraps a delivery implementation, recording its calls into `stats`.
+	pub fn new(inner: Arc<dyn DeliveryInterface>, stats: Arc<RpcCallStats>) -> Self {
+		Self { inner, stats }
+	}
+
+	/// Times a call and records its outcome.
+	async fn observe<T>(
+		&self,
+		method: &'static str,
+		chain_id: u64,
+		call: impl Future<Output = Result<T, DeliveryError>>,
+	) -> Result<T, DeliveryError> {
+		let started = Instant::now();
+		let result = call.await;
+		let duration = started.elapsed();
+		let status = if result.is_ok() { "success" } else { "error" };
+
+		metrics::histogram!(
+			CALL_DURATION_HISTOGRAM,
+			"method" => method,
+			"chain_id" => chain_id.to_string()
+		)
+		.record(duration.as_secs_f64());
+		metrics::counter!(
+			CALL_COUNTER,
+			"method" => method,
+			"chain_id" => chain_id.to_string(),
+			"status" => status
+		)
+		.increment(1);
+		self.stats
+			.record(method, chain_id, duration, result.is_ok());
+
+		result
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for InstrumentedDelivery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	async fn submit(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+		let chain_id = tx.chain_id;
+		let result = self
+			.observe("submit", chain_id, self.inner.submit(tx))
+			.await;
+		metrics::counter!(
+			SUBMIT_COUNTER,
+			"chain_id" => chain_id.to_string(),
+			"success" => result.is_ok().to_string()
+		)
+		.increment(1);
+		result
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		chain_id: u64,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.observe(
+			"wait_for_confirmation",
+			chain_id,
+			self.inner
+				.wait_for_confirmation(hash, chain_id, confirmations),
+		)
+		.await
+	}
+
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.observe(
+			"get_receipt",
+			chain_id,
+			self.inner.get_receipt(hash, chain_id),
+		)
+		.await
+	}
+
+	async fn batch_get_receipts(
+		&self,
+		hashes: &[TransactionHash],
+		chain_id: u64,
+	) -> Result<Vec<Option<TransactionReceipt>>, DeliveryError> {
+		self.observe(
+			"batch_get_receipts",
+			chain_id,
+			self.inner.batch_get_receipts(hashes, chain_id),
+		)
+		.await
+	}
+
+	async fn get_gas_price(&self, chain_id: u64) -> Result<String, DeliveryError> {
+		self.observe(
+			"get_gas_price",
+			chain_id,
+			self.inner.get_gas_price(chain_id),
+		)
+		.await
+	}
+
+	async fn get_balance(
+		&self,
+		address: &str,
+		token: Option<&str>,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		self.observe(
+			"get_balance",
+			chain_id,
+			self.inner.get_balance(address, token, chain_id),
+		)
+		.await
+	}
+
+	async fn get_allowance(
+		&self,
+		owner: &str,
+		spender: &str,
+		token_address: &str,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		self.observe(
+			"get_allowance",
+			chain_id,
+			self.inner
+				.get_allowance(owner, spender, token_address, chain_id),
+		)
+		.await
+	}
+
+	async fn get_nonce(&self, address: &str, chain_id: u64) -> Result<u64, DeliveryError> {
+		self.observe(
+			"get_nonce",
+			chain_id,
+			self.inner.get_nonce(address, chain_id),
+		)
+		.await
+	}
+
+	async fn get_code(&self, address: &str, chain_id: u64) -> Result<Vec<u8>, DeliveryError> {
+		self.observe("get_code", chain_id, self.inner.get_code(address, chain_id))
+			.await
+	}
+
+	async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError> {
+		self.observe(
+			"get_block_number",
+			chain_id,
+			self.inner.get_block_number(chain_id),
+		)
+		.await
+	}
+
+	async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		self.observe(
+			"get_fee_history",
+			chain_id,
+			self.inner
+				.get_fee_history(chain_id, block_count, reward_percentiles),
+		)
+		.await
+	}
+
+	async fn estimate_gas(&self, tx: &Transaction) -> Result<TransactionTrace, DeliveryError> {
+		self.observe("estimate_gas", tx.chain_id, self.inner.estimate_gas(tx))
+			.await
+	}
+
+	async fn simulate_transaction(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, DeliveryError> {
+		self.observe(
+			"simulate_transaction",
+			tx.chain_id,
+			self.inner.simulate_transaction(tx),
+		)
+		.await
+	}
+
+	async fn supports_wallet_batch(&self, chain_id: u64) -> Result<bool, DeliveryError> {
+		self.observe(
+			"supports_wallet_batch",
+			chain_id,
+			self.inner.supports_wallet_batch(chain_id),
+		)
+		.await
+	}
+
+	async fn wallet_send_calls(
+		&self,
+		calls: Vec<Call>,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		self.observe(
+			"wallet_send_calls",
+			chain_id,
+			self.inner.wallet_send_calls(calls, chain_id),
+		)
+		.await
+	}
+
+	async fn wallet_calls_status(
+		&self,
+		batch_id: &str,
+		chain_id: u64,
+	) -> Result<Option<TransactionHash>, DeliveryError> {
+		self.observe(
+			"wallet_calls_status",
+			chain_id,
+			self.inner.wallet_calls_status(batch_id, chain_id),
+		)
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_snapshot_orders_slowest_methods_first() {
+		let stats = RpcCallStats::new();
+		stats.record("get_nonce", 1, Duration::from_millis(10), true);
+		stats.record("submit", 1, Duration::from_millis(100), true);
+		stats.record("submit", 1, Duration::from_millis(300), false);
+		stats.record("get_nonce", 10, Duration::from_millis(50), true);
+
+		let snapshot = stats.snapshot();
+		assert_eq!(snapshot.len(), 3);
+		assert_eq!(
+			snapshot[0],
+			RpcMethodStats {
+				method: "submit".to_string(),
+				chain_id: 1,
+				calls: 2,
+				errors: 1,
+				avg_duration_ms: 200.0,
+				max_duration_ms: 300.0,
+			}
+		);
+		assert_eq!(
+			(snapshot[1].method.as_str(), snapshot[1].chain_id),
+			("get_nonce", 10)
+		);
+		assert_eq!(
+			(snapshot[2].method.as_str(), snapshot[2].chain_id),
+			("get_nonce", 1)
+		);
+	}
+}
@@ -0,0 +1,151 @@
+//! Lazy initialization of delivery implementations.
+//!
+//! Creating RPC providers for every configured chain at startup is slow and
+//! fails the whole solver when a single chain is unreachable. With lazy
+//! initialization, the implementation of a chain is created on its first use
+//! and cached. A failed initialization is retried on a later use, after a
+//! delay that doubles with each consecutive failure.
+
+use crate::{DeliveryError, DeliveryInterface};
+use dashmap::DashMap;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Creates the delivery implementation of a chain.
+pub type LazyDeliveryFactory = Arc<
+	dyn Fn() -> Pin<
+			Box<dyn Future<Output = Result<Arc<dyn DeliveryInterface>, DeliveryError>> + Send>,
+		> + Send
+		+ Sync,
+>;
+
+/// Delay before retrying the first failed initialization of a chain.
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Maximum delay between initialization attempts of a chain.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Consecutive failed initializations of a chain and the earliest next attempt.
+#[derive(Default)]
+struct Failures {
+	count: u32,
+	retry_at: Option<Instant>,
+}
+
+/// Delivery implementations created on first use, by chain ID.
+pub struct LazyImplementations {
+	factories: HashMap<u64, LazyDeliveryFactory>,
+	initialized: DashMap<u64, Arc<dyn DeliveryInterface>>,
+	/// Failed initializations, by chain ID. Each lock is held while the chain
+	/// initializes, so that chains initialize concurrently but each only once.
+	failures: HashMap<u64, Mutex<Failures>>,
+}
+
+impl LazyImplementations {
+	/// Creates the set of lazily initialized implementations.
+	pub fn new(factories: HashMap<u64, LazyDeliveryFactory>) -> Self {
+		let failures = factories
+			.keys()
+			.map(|chain_id| (*chain_id, Mutex::new(Failures::default())))
+			.collect();
+		Self {
+			factories,
+			initialized: DashMap::new(),
+			failures,
+		}
+	}
+
+	/// Returns whether a factory is configured for the chain.
+	pub fn contains(&self, chain_id: u64) -> bool {
+		self.factories.contains_key(&chain_id)
+	}
+
+	/// Returns the chains with a configured factory.
+	pub fn chain_ids(&self) -> impl Iterator<Item = u64> + '_ {
+		self.factories.keys().copied()
+	}
+
+	/// Returns the implementation of a chain, creating it on first use.
+	///
+	/// Fails with `NoImplementationAvailable` if no factory is configured for
+	/// the chain, if its factory fails, or while waiting to retry a failure.
+	pub async fn get(&self, chain_id: u64) -> Result<Arc<dyn DeliveryInterface>, DeliveryError> {
+		if let Some(implementation) = self.initialized.get(&chain_id) {
+			return Ok(implementation.clone());
+		}
+		let (Some(factory), Some(failures)) =
+			(self.factories.get(&chain_id), self.failures.get(&chain_id))
+		else {
+			return Err(DeliveryError::NoImplementationAvailable);
+		};
+
+		let mut failures = failures.lock().await;
+		if failures
+			.retry_at
+			.is_some_and(|retry_at| Instant::now() < retry_at)
+		{
+			return Err(DeliveryError::NoImplementationAvailable);
+		}
+		// Another caller may have initialized the chain while this one waited
+		if let Some(implementation) = self.initialized.get(&chain_id) {
+			return Ok(implementation.clone());
+		}
+
+		match factory().await {
+			Ok(implementation) => {
+				*failures = Failures::default();
+				self.initialized.insert(chain_id, implementation.clone());
+				tracing::info!("lazily initialized delivery for chain {}", chain_id);
+				Ok(implementation)
+			},
+			Err(e) => {
+				failures.count += 1;
+				let delay = BASE_RETRY_DELAY
+					.saturating_mul(2u32.saturating_pow(failures.count - 1))
+					.min(MAX_RETRY_DELAY);
+				failures.retry_at = Some(Instant::now() + delay);
+				tracing::warn!(
+					chain_id,
+					failures = failures.count,
+					retry_in_seconds = delay.as_secs(),
+					error = %e,
+					"Failed to initialize delivery"
+				);
+				Err(DeliveryError::NoImplementationAvailable)
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	#[tokio::test]
+	async fn test_failed_initialization_backs_off() {
+		let calls = Arc::new(AtomicU32::new(0));
+		let factory_calls = calls.clone();
+		let factory: LazyDeliveryFactory = Arc::new(move || {
+			factory_calls.fetch_add(1, Ordering::SeqCst);
+			Box::pin(async { Err(DeliveryError::Network("unreachable".to_string())) })
+		});
+		let lazy = LazyImplementations::new(HashMap::from([(1, factory)]));
+
+		assert!(matches!(
+			lazy.get(1).await,
+			Err(DeliveryError::NoImplementationAvailable)
+		));
+		// Retried only once the back-off delay has passed
+		assert!(lazy.get(1).await.is_err());
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+		assert!(lazy.get(2).await.is_err());
+		assert!(lazy.contains(1));
+		assert!(!lazy.contains(2));
+	}
+}
@@ -5,12 +5,16 @@
 //! blockchain networks, managing transaction signing, submission, and confirmation.
 
 use async_trait::async_trait;
+use solver_account::AccountService;
 use solver_types::{
-	ChainData, ConfigSchema, ImplementationRegistry, NetworksConfig, Transaction, TransactionHash,
-	TransactionReceipt,
+	delegation_designator, Call, ChainData, ChainId, ConfigSchema, FeeHistory, GasBidConfig,
+	GasStrategy, ImplementationRegistry, NetworksConfig, Transaction, TransactionHash,
+	TransactionReceipt, TransactionTrace,
 };
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
 /// Re-export implementations
@@ -20,6 +24,43 @@ pub mod implementations {
 	}
 }
 
+pub mod anvil;
+pub mod cache;
+pub mod gas_bidder;
+pub mod gas_history;
+pub mod instrumented;
+pub mod lazy;
+pub mod rate_limiter;
+pub mod revert;
+pub mod rpc_rotator;
+pub mod simulation;
+
+pub use anvil::AnvilNodes;
+pub use cache::ReceiptCache;
+pub use gas_bidder::SmartGasBidder;
+pub use gas_history::{GasPriceHistoryAnalyzer, GasPriceSample};
+pub use instrumented::{InstrumentedDelivery, RpcCallStats, RpcMethodStats};
+pub use lazy::{LazyDeliveryFactory, LazyImplementations};
+pub use rate_limiter::RateLimitedProvider;
+pub use rpc_rotator::{ReadStrategy, RpcRotationConfig, RpcRotator};
+pub use simulation::SimulatedTransactions;
+
+/// Interval between call batch status polls while a batch is pending.
+const CALLS_STATUS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Maximum time to wait for a call batch to be included in a transaction.
+const CALLS_STATUS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Submission of transactions delivered together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallsSubmission {
+	/// The last transaction was submitted with the given hash.
+	Transaction(TransactionHash),
+	/// The transactions were submitted as an EIP-5792 call batch, whose
+	/// including transaction is not known yet.
+	Batch { batch_id: String, chain_id: u64 },
+}
+
 /// Errors that can occur during transaction delivery operations.
 #[derive(Debug, Error)]
 pub enum DeliveryError {
@@ -75,6 +116,23 @@ pub trait DeliveryInterface: Send + Sync {
 		chain_id: u64,
 	) -> Result<TransactionReceipt, DeliveryError>;
 
+	/// Retrieves the receipts of several transactions on the same chain.
+	///
+	/// Returns one entry per hash, in order, with None for transactions that
+	/// are not found or not yet mined. The default implementation requests
+	/// each receipt separately; implementations should batch the requests.
+	async fn batch_get_receipts(
+		&self,
+		hashes: &[TransactionHash],
+		chain_id: u64,
+	) -> Result<Vec<Option<TransactionReceipt>>, DeliveryError> {
+		let mut receipts = Vec::with_capacity(hashes.len());
+		for hash in hashes {
+			receipts.push(self.get_receipt(hash, chain_id).await.ok());
+		}
+		Ok(receipts)
+	}
+
 	/// Gets the current gas price for the network.
 	///
 	/// Returns the recommended gas price in wei as a decimal string.
@@ -109,22 +167,100 @@ pub trait DeliveryInterface: Send + Sync {
 	/// Returns the next valid nonce for transaction submission.
 	async fn get_nonce(&self, address: &str, chain_id: u64) -> Result<u64, DeliveryError>;
 
+	/// Gets the code deployed at an address.
+	///
+	/// Returns empty bytes for accounts without code.
+	async fn get_code(&self, address: &str, chain_id: u64) -> Result<Vec<u8>, DeliveryError>;
+
 	/// Gets the current block number.
 	///
 	/// Returns the latest block number on the network.
 	async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError>;
+
+	/// Gets the fee history of the latest `block_count` blocks.
+	///
+	/// Priority fees are reported at each of the given reward percentiles.
+	async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError>;
+
+	/// Executes a transaction with `eth_call` and estimates its gas without submitting it.
+	///
+	/// Reverts are reported in the returned trace rather than as errors, so callers
+	/// can inspect the revert reason.
+	async fn estimate_gas(&self, tx: &Transaction) -> Result<TransactionTrace, DeliveryError>;
+
+	/// Simulates a transaction without submitting it, tracing the call where
+	/// the node supports it.
+	///
+	/// Unlike `estimate_gas`, the trace includes the logs emitted by the call
+	/// when available. The default implementation falls back to `estimate_gas`.
+	async fn simulate_transaction(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, DeliveryError> {
+		self.estimate_gas(tx).await
+	}
+
+	/// Returns whether the wallet behind the network's RPC executes EIP-5792
+	/// call batches atomically, as reported by `wallet_getCapabilities`.
+	///
+	/// The default implementation reports no support.
+	async fn supports_wallet_batch(&self, _chain_id: u64) -> Result<bool, DeliveryError> {
+		Ok(false)
+	}
+
+	/// Submits calls as a single EIP-5792 `wallet_sendCalls` batch.
+	///
+	/// Returns the ID of the batch, which is not a transaction hash. The
+	/// default implementation fails, callers check `supports_wallet_batch`
+	/// first.
+	async fn wallet_send_calls(
+		&self,
+		_calls: Vec<Call>,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(format!(
+			"wallet_sendCalls is not supported on chain {}",
+			chain_id
+		)))
+	}
+
+	/// Returns the hash of the transaction that included a call batch, or
+	/// None while the batch is pending.
+	///
+	/// Fails if the batch failed.
+	async fn wallet_calls_status(
+		&self,
+		_batch_id: &str,
+		chain_id: u64,
+	) -> Result<Option<TransactionHash>, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(format!(
+			"wallet_getCallsStatus is not supported on chain {}",
+			chain_id
+		)))
+	}
 }
 
+/// Future resolving to a delivery implementation created by a factory.
+pub type DeliveryFuture =
+	Pin<Box<dyn Future<Output = Result<Box<dyn DeliveryInterface>, DeliveryError>> + Send>>;
+
 /// Type alias for delivery factory functions.
 ///
 /// This is the function signature that all delivery implementations must provide
-/// to create instances of their delivery interface.
+/// to create instances of their delivery interface. Implementations connect to
+/// their networks asynchronously, so the factory returns a future that does
+/// not borrow its arguments.
 pub type DeliveryFactory = fn(
 	&toml::Value,
 	&NetworksConfig,
 	&solver_types::SecretString,               // Default/primary private key
 	&HashMap<u64, solver_types::SecretString>, // Per-network private keys
-) -> Result<Box<dyn DeliveryInterface>, DeliveryError>;
+) -> DeliveryFuture;
 
 /// Registry trait for delivery implementations.
 ///
@@ -142,15 +278,50 @@ pub fn get_all_implementations() -> Vec<(&'static str, DeliveryFactory)> {
 	vec![(alloy::Registry::NAME, alloy::Registry::factory())]
 }
 
+/// Get the configuration schemas of all registered delivery implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_implementations`].
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::evm::alloy;
+
+	vec![(alloy::Registry::NAME, alloy::Registry::config_schema())]
+}
+
 /// Service that manages transaction delivery across multiple blockchain networks.
 ///
 /// The DeliveryService coordinates between different delivery implementations based on
 /// chain ID and provides methods for transaction submission and confirmation monitoring.
 pub struct DeliveryService {
 	/// Map of chain IDs to their corresponding delivery implementations.
-	implementations: std::collections::HashMap<u64, Arc<dyn DeliveryInterface>>,
+	implementations: std::collections::HashMap<ChainId, Arc<dyn DeliveryInterface>>,
 	/// Default number of confirmations required for transactions.
 	min_confirmations: u64,
+	/// When set, transactions are simulated with `eth_call` instead of submitted.
+	simulation_mode: bool,
+	/// Synthetic receipts of simulated transactions.
+	simulated: SimulatedTransactions,
+	/// Fee bidder used when the gas strategy is `Auto`.
+	gas_bidder: Option<SmartGasBidder>,
+	/// Receipts of recently checked and final transactions.
+	receipt_cache: ReceiptCache,
+	/// EIP-7702 delegating accounts sending transactions, by chain ID.
+	delegating_accounts: HashMap<u64, Arc<AccountService>>,
+	/// Chains on which the delegating account's code is known to be delegated.
+	delegated_chains: Mutex<HashSet<u64>>,
+	/// Implementations created on first use of their chain, if any.
+	lazy: Option<LazyImplementations>,
+	/// Call statistics of instrumented implementations.
+	rpc_stats: Option<Arc<RpcCallStats>>,
+	/// Whether several calls are submitted as one EIP-5792 batch where supported.
+	wallet_batch: bool,
+	/// Detected EIP-5792 batch support by chain ID.
+	wallet_batch_support: Mutex<HashMap<u64, bool>>,
+	/// Local nodes the implementations are connected to, kept running while
+	/// the service is alive.
+	anvil_nodes: Option<Arc<AnvilNodes>>,
+	/// Locks serializing transaction submissions, by chain ID.
+	submission_locks: Mutex<HashMap<u64, Arc<tokio::sync::Mutex<()>>>>,
 }
 
 impl DeliveryService {
@@ -159,31 +330,484 @@ impl DeliveryService {
 	/// The implementations map should contain delivery implementations for each supported
 	/// chain ID.
 	pub fn new(
-		implementations: std::collections::HashMap<u64, Arc<dyn DeliveryInterface>>,
+		implementations: std::collections::HashMap<ChainId, Arc<dyn DeliveryInterface>>,
 		min_confirmations: u64,
 	) -> Self {
 		Self {
 			implementations,
+			lazy: None,
 			min_confirmations,
+			simulation_mode: false,
+			simulated: SimulatedTransactions::default(),
+			gas_bidder: None,
+			receipt_cache: ReceiptCache::default(),
+			delegating_accounts: HashMap::new(),
+			delegated_chains: Mutex::new(HashSet::new()),
+			rpc_stats: None,
+			wallet_batch: false,
+			wallet_batch_support: Mutex::new(HashMap::new()),
+			anvil_nodes: None,
+			submission_locks: Mutex::new(HashMap::new()),
 		}
 	}
 
+	/// Creates the implementations of the given chains on their first use.
+	///
+	/// Chains with an implementation passed to [`DeliveryService::new`] use
+	/// that implementation instead.
+	pub fn with_lazy_implementations(
+		mut self,
+		factories: HashMap<u64, LazyDeliveryFactory>,
+	) -> Self {
+		self.lazy = Some(LazyImplementations::new(factories));
+		self
+	}
+
+	/// Returns the implementation of a chain, creating it if it is lazily initialized.
+	async fn implementation(
+		&self,
+		chain_id: u64,
+	) -> Result<Arc<dyn DeliveryInterface>, DeliveryError> {
+		if let Some(implementation) = self.implementations.get(&chain_id) {
+			return Ok(implementation.clone());
+		}
+		match &self.lazy {
+			Some(lazy) => lazy.get(chain_id).await,
+			None => Err(DeliveryError::NoImplementationAvailable),
+		}
+	}
+
+	/// Returns the IDs of the chains with a delivery implementation.
+	fn chain_ids(&self) -> Vec<u64> {
+		let mut chain_ids: Vec<u64> = self
+			.implementations
+			.keys()
+			.map(|chain_id| chain_id.as_u64())
+			.chain(self.lazy.iter().flat_map(|lazy| lazy.chain_ids()))
+			.collect();
+		chain_ids.sort_unstable();
+		chain_ids.dedup();
+		chain_ids
+	}
+
+	/// Reports the call statistics recorded by the instrumented implementations.
+	pub fn with_rpc_stats(mut self, stats: Arc<RpcCallStats>) -> Self {
+		self.rpc_stats = Some(stats);
+		self
+	}
+
+	/// Returns the RPC call statistics, if implementations are instrumented.
+	pub fn rpc_stats(&self) -> Option<&Arc<RpcCallStats>> {
+		self.rpc_stats.as_ref()
+	}
+
+	/// Sets the accounts sending transactions on each chain.
+	///
+	/// Transactions sent by an account delegating its code through EIP-7702
+	/// carry a signed authorization until the delegation is in effect.
+	/// Accounts that do not delegate are ignored.
+	pub fn with_delegating_accounts(mut self, accounts: HashMap<u64, Arc<AccountService>>) -> Self {
+		self.delegating_accounts = accounts
+			.into_iter()
+			.filter(|(_, account)| account.delegate_address().is_some())
+			.collect();
+		self
+	}
+
+	/// Sets how long receipts of non-final transactions are cached.
+	///
+	/// Final receipts are cached until a reorg is detected regardless of the TTL.
+	pub fn with_receipt_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+		self.receipt_cache = ReceiptCache::new(ttl);
+		self
+	}
+
+	/// Sets how fees of submitted transactions are determined.
+	///
+	/// With `GasStrategy::Auto`, fees are bid from the recent fee history
+	/// according to `bid_config` unless the transaction already sets them.
+	pub fn with_gas_strategy(mut self, strategy: GasStrategy, bid_config: GasBidConfig) -> Self {
+		self.gas_bidder = match strategy {
+			GasStrategy::Provider => None,
+			GasStrategy::Auto => Some(SmartGasBidder::new(bid_config)),
+		};
+		self
+	}
+
+	/// Enables simulation mode (paper trading).
+	///
+	/// In simulation mode transactions are executed with `eth_call` and never
+	/// broadcast. Delivery returns a synthetic hash, unique per chain and
+	/// submission, and confirmation returns a successful synthetic receipt.
+	pub fn with_simulation_mode(mut self, simulation_mode: bool) -> Self {
+		self.simulation_mode = simulation_mode;
+		self
+	}
+
+	/// Enables submitting several calls as one EIP-5792 `wallet_sendCalls`
+	/// batch on chains whose wallet supports it.
+	pub fn with_wallet_batch(mut self, enabled: bool) -> Self {
+		self.wallet_batch = enabled;
+		self
+	}
+
+	/// Keeps the local nodes the implementations are connected to running
+	/// for as long as the service is alive.
+	pub fn with_anvil_nodes(mut self, nodes: Option<Arc<AnvilNodes>>) -> Self {
+		self.anvil_nodes = nodes;
+		self
+	}
+
+	/// Returns true if call batching through EIP-5792 is enabled.
+	pub fn is_wallet_batch_enabled(&self) -> bool {
+		self.wallet_batch
+	}
+
+	/// Returns whether calls on a chain are submitted as one batch.
+	///
+	/// Support is detected once per chain. Chains whose RPC fails the
+	/// detection are treated as unsupported.
+	pub async fn supports_wallet_batch(&self, chain_id: u64) -> bool {
+		if !self.wallet_batch || self.simulation_mode {
+			return false;
+		}
+		if let Some(supported) = self.wallet_batch_support.lock().unwrap().get(&chain_id) {
+			return *supported;
+		}
+		let Ok(implementation) = self.implementation(chain_id).await else {
+			return false;
+		};
+
+		let supported = implementation
+			.supports_wallet_batch(chain_id)
+			.await
+			.unwrap_or_else(|e| {
+				tracing::debug!(chain_id, error = %e, "Failed to detect wallet batch support");
+				false
+			});
+		tracing::info!(
+			chain_id,
+			supported,
+			"Detected EIP-5792 wallet batch support"
+		);
+		self.wallet_batch_support
+			.lock()
+			.unwrap()
+			.insert(chain_id, supported);
+		supported
+	}
+
+	/// Returns the detected EIP-5792 batch support of every chain.
+	///
+	/// Empty if call batching is disabled.
+	pub async fn wallet_batch_support(&self) -> BTreeMap<u64, bool> {
+		if !self.wallet_batch {
+			return BTreeMap::new();
+		}
+		let mut support = BTreeMap::new();
+		for chain_id in self.chain_ids() {
+			support.insert(chain_id, self.supports_wallet_batch(chain_id).await);
+		}
+		support
+	}
+
+	/// Delivers transactions of a single chain that must execute in order.
+	///
+	/// Where EIP-5792 batching is enabled and supported, the transactions are
+	/// submitted as the calls of one batch, whose inclusion is awaited with
+	/// [`DeliveryService::wait_for_call_batch`]. Otherwise they are delivered
+	/// individually with [`DeliveryService::deliver`], each waiting for the
+	/// previous one to confirm so that it is estimated against the resulting
+	/// state, and the hash of the last transaction is returned.
+	///
+	/// Batches are priced and paid by the wallet, so they are not used in
+	/// simulation mode or with fee bidding.
+	pub async fn deliver_calls(
+		&self,
+		mut txs: Vec<Transaction>,
+	) -> Result<CallsSubmission, DeliveryError> {
+		let last = txs.pop().ok_or_else(|| {
+			DeliveryError::TransactionFailed("No transactions to deliver".to_string())
+		})?;
+		if txs.is_empty() {
+			return self.deliver(last).await.map(CallsSubmission::Transaction);
+		}
+
+		let chain_id = last.chain_id;
+		if self.batches_calls(chain_id).await {
+			txs.push(last);
+			let batch_id = self.deliver_call_batch(&txs).await?;
+			return Ok(CallsSubmission::Batch { batch_id, chain_id });
+		}
+
+		for tx in txs {
+			let hash = self.deliver(tx).await?;
+			self.confirm_with_default(&hash, chain_id).await?;
+		}
+		self.deliver(last).await.map(CallsSubmission::Transaction)
+	}
+
+	/// Submits transactions of a single chain as the calls of one EIP-5792
+	/// batch and returns the ID of the batch.
+	///
+	/// Callers check [`DeliveryService::batches_calls`] first; the inclusion
+	/// of the batch is awaited with [`DeliveryService::wait_for_call_batch`].
+	pub async fn deliver_call_batch(&self, txs: &[Transaction]) -> Result<String, DeliveryError> {
+		let chain_id = txs.first().map(|tx| tx.chain_id).ok_or_else(|| {
+			DeliveryError::TransactionFailed("No transactions to deliver".to_string())
+		})?;
+		let implementation = self.implementation(chain_id).await?;
+		let calls = txs.iter().map(Call::from).collect();
+		let _submission = self.submission_lock(chain_id).lock_owned().await;
+		implementation.wallet_send_calls(calls, chain_id).await
+	}
+
+	/// Returns true if calls of a chain are submitted as an EIP-5792 batch.
+	pub async fn batches_calls(&self, chain_id: u64) -> bool {
+		!self.simulation_mode
+			&& self.gas_bidder.is_none()
+			&& self.supports_wallet_batch(chain_id).await
+	}
+
+	/// Waits for a call batch to be included in a transaction and returns
+	/// the hash of that transaction.
+	///
+	/// Fails if the batch failed or was not included in time.
+	pub async fn wait_for_call_batch(
+		&self,
+		batch_id: &str,
+		chain_id: u64,
+	) -> Result<TransactionHash, DeliveryError> {
+		let implementation = self.implementation(chain_id).await?;
+		let started = std::time::Instant::now();
+		loop {
+			if let Some(hash) = implementation
+				.wallet_calls_status(batch_id, chain_id)
+				.await?
+			{
+				tracing::info!(
+					batch_id = %batch_id,
+					tx_hash = %hex::encode(&hash.0),
+					chain_id,
+					"Call batch included"
+				);
+				return Ok(hash);
+			}
+			if started.elapsed() > CALLS_STATUS_TIMEOUT {
+				return Err(DeliveryError::Network(format!(
+					"Timeout waiting for call batch {} after {} seconds",
+					batch_id,
+					CALLS_STATUS_TIMEOUT.as_secs()
+				)));
+			}
+			tokio::time::sleep(CALLS_STATUS_POLL_INTERVAL).await;
+		}
+	}
+
+	/// Returns true if transactions are simulated instead of submitted.
+	pub fn is_simulation_mode(&self) -> bool {
+		self.simulation_mode
+	}
+
 	/// Delivers a transaction to the appropriate blockchain network.
 	///
 	/// This method:
 	/// 1. Selects the appropriate implementation based on the transaction's chain ID
 	/// 2. Submits the transaction through the implementation (which handles signing)
-	pub async fn deliver(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+	///
+	/// Submissions on the same chain are serialized, see
+	/// [`DeliveryService::submission_lock`].
+	pub async fn deliver(&self, mut tx: Transaction) -> Result<TransactionHash, DeliveryError> {
 		// Get the implementation for the transaction's chain ID
-		let implementation = self
-			.implementations
-			.get(&tx.chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(tx.chain_id).await?;
+
+		if self.simulation_mode {
+			return self.simulate_delivery(implementation.as_ref(), &tx).await;
+		}
+
+		let _submission = self.submission_lock(tx.chain_id).lock_owned().await;
+		self.attach_delegation(implementation.as_ref(), &mut tx)
+			.await?;
+
+		// Bid fees from the fee history unless the transaction sets them
+		if let Some(bidder) = &self.gas_bidder {
+			if tx.gas_price.is_none() && tx.max_fee_per_gas.is_none() {
+				match bidder.bid(implementation.as_ref(), tx.chain_id).await {
+					Ok(bid) => {
+						tx.max_fee_per_gas = Some(bid.max_fee_per_gas);
+						tx.max_priority_fee_per_gas = Some(bid.max_priority_fee_per_gas);
+					},
+					Err(e) => {
+						tracing::warn!(
+							chain_id = tx.chain_id,
+							error = %e,
+							"Gas bidding failed, using provider fees"
+						);
+					},
+				}
+			}
+		}
 
 		// Submit using the chain-specific implementation (which handles signing)
 		implementation.submit(tx).await
 	}
 
+	/// Returns the lock serializing the submissions of a chain.
+	///
+	/// Implementations assign the nonce of a transaction from the sending
+	/// account's pending transaction count, so two transactions submitted
+	/// concurrently on the same chain could get the same nonce. The lock is
+	/// held from nonce assignment until the transaction is broadcast, which
+	/// lets fills of one chain run concurrently while their submissions stay
+	/// ordered. Confirmations are awaited without the lock.
+	fn submission_lock(&self, chain_id: u64) -> Arc<tokio::sync::Mutex<()>> {
+		self.submission_locks
+			.lock()
+			.unwrap()
+			.entry(chain_id)
+			.or_default()
+			.clone()
+	}
+
+	/// Attaches an EIP-7702 authorization to a transaction sent by a delegating
+	/// account whose code is not yet delegated on the transaction's chain.
+	async fn attach_delegation(
+		&self,
+		implementation: &dyn DeliveryInterface,
+		tx: &mut Transaction,
+	) -> Result<(), DeliveryError> {
+		let Some(account) = self.delegating_accounts.get(&tx.chain_id) else {
+			return Ok(());
+		};
+		let Some(delegate) = account.delegate_address() else {
+			return Ok(());
+		};
+		if self.delegated_chains.lock().unwrap().contains(&tx.chain_id) {
+			return Ok(());
+		}
+
+		let address = account
+			.get_address()
+			.await
+			.map_err(|e| DeliveryError::TransactionFailed(format!("Failed to get address: {}", e)))?
+			.to_string();
+		let code = implementation.get_code(&address, tx.chain_id).await?;
+		if code == delegation_designator(&delegate) {
+			self.delegated_chains.lock().unwrap().insert(tx.chain_id);
+			return Ok(());
+		}
+
+		// The account sends the transaction itself, so its nonce is incremented
+		// before the authorization is processed
+		let nonce = match tx.nonce {
+			Some(nonce) => nonce,
+			None => implementation.get_nonce(&address, tx.chain_id).await?,
+		};
+		let authorization = account
+			.sign_delegation(delegate, tx.chain_id, nonce + 1)
+			.await
+			.map_err(|e| {
+				DeliveryError::TransactionFailed(format!("Failed to sign delegation: {}", e))
+			})?;
+		tx.nonce = Some(nonce);
+		tx.authorization_list = Some(vec![authorization]);
+
+		tracing::info!(chain_id = tx.chain_id, account = %address, "Attached EIP-7702 delegation");
+		Ok(())
+	}
+
+	/// Simulates a transaction with `eth_call` and returns its synthetic hash.
+	async fn simulate_delivery(
+		&self,
+		implementation: &dyn DeliveryInterface,
+		tx: &Transaction,
+	) -> Result<TransactionHash, DeliveryError> {
+		let trace = implementation.estimate_gas(tx).await?;
+		if !trace.success {
+			tracing::info!(
+				chain_id = tx.chain_id,
+				revert_reason = ?trace.revert_reason,
+				"[SIM] Transaction reverted"
+			);
+			return Err(DeliveryError::TransactionFailed(
+				trace
+					.revert_reason
+					.unwrap_or_else(|| "Simulated transaction reverted".to_string()),
+			));
+		}
+
+		let gas_used = trace.gas_used.unwrap_or_default();
+		let gas_price = implementation
+			.get_gas_price(tx.chain_id)
+			.await
+			.ok()
+			.and_then(|price| price.parse::<u128>().ok())
+			.unwrap_or_default();
+
+		let block_number = implementation
+			.get_block_number(tx.chain_id)
+			.await
+			.unwrap_or_default();
+
+		let hash = self.simulated.record(tx, gas_used, gas_price, block_number);
+
+		tracing::info!(
+			chain_id = tx.chain_id,
+			tx_hash = %hex::encode(&hash.0),
+			gas_used,
+			"[SIM] Transaction simulated"
+		);
+
+		Ok(hash)
+	}
+
+	/// Returns the successful synthetic receipt of a simulated transaction.
+	///
+	/// Fails for transactions that were not simulated by this service, or
+	/// whose receipt has since been evicted.
+	fn simulated_receipt(
+		&self,
+		hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		let receipt = self.simulated.receipt(chain_id, hash).ok_or_else(|| {
+			DeliveryError::TransactionFailed(format!(
+				"Unknown simulated transaction {}",
+				hex::encode(&hash.0)
+			))
+		})?;
+
+		tracing::info!(
+			chain_id,
+			tx_hash = %hex::encode(&hash.0),
+			"[SIM] Transaction confirmed"
+		);
+
+		Ok(receipt)
+	}
+
+	/// Simulates a transaction on the appropriate blockchain network without submitting it.
+	///
+	/// Returns the call trace including estimated gas or the revert reason.
+	pub async fn estimate_gas(&self, tx: &Transaction) -> Result<TransactionTrace, DeliveryError> {
+		let implementation = self.implementation(tx.chain_id).await?;
+
+		implementation.estimate_gas(tx).await
+	}
+
+	/// Simulates a transaction on the appropriate blockchain network, tracing
+	/// the call where the node supports it.
+	///
+	/// Returns the call trace including the logs emitted or the decoded revert reason.
+	pub async fn simulate_transaction(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, DeliveryError> {
+		let implementation = self.implementation(tx.chain_id).await?;
+
+		implementation.simulate_transaction(tx).await
+	}
+
 	/// Waits for a transaction to be confirmed with the specified number of confirmations.
 	///
 	/// This method uses the chain_id to directly route to the correct implementation.
@@ -194,14 +818,19 @@ impl DeliveryService {
 		confirmations: u64,
 	) -> Result<TransactionReceipt, DeliveryError> {
 		// Get the implementation for the specified chain
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
-		implementation
+		if self.simulation_mode {
+			return self.simulated_receipt(hash, chain_id);
+		}
+
+		let receipt = implementation
 			.wait_for_confirmation(hash, chain_id, confirmations)
-			.await
+			.await?;
+		if confirmations >= self.min_confirmations {
+			self.receipt_cache.insert(chain_id, receipt.clone(), true);
+		}
+		Ok(receipt)
 	}
 
 	/// Waits for a transaction to be confirmed with the default number of confirmations.
@@ -224,23 +853,125 @@ impl DeliveryService {
 		hash: &TransactionHash,
 		chain_id: u64,
 	) -> Result<bool, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
+
+		if self.simulation_mode {
+			return self
+				.simulated_receipt(hash, chain_id)
+				.map(|receipt| receipt.success);
+		}
+
+		if let Some(receipt) = self.receipt_cache.get(chain_id, hash) {
+			return Ok(receipt.success);
+		}
 
 		let receipt = implementation.get_receipt(hash, chain_id).await?;
+		match implementation.get_block_number(chain_id).await {
+			Ok(head) => {
+				self.receipt_cache.observe_head(chain_id, head);
+				let depth = (head + 1).saturating_sub(receipt.block_number);
+				let is_final = depth >= self.min_confirmations;
+				self.receipt_cache
+					.insert(chain_id, receipt.clone(), is_final);
+			},
+			Err(e) => {
+				tracing::debug!(chain_id, error = %e, "Failed to get block number, not caching receipt");
+			},
+		}
 		Ok(receipt.success)
 	}
 
+	/// Retrieves the receipt of a mined transaction on a specific chain.
+	///
+	/// Fails if the transaction is not found or not yet mined.
+	pub async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		let implementation = self.implementation(chain_id).await?;
+
+		if self.simulation_mode {
+			return self.simulated_receipt(hash, chain_id);
+		}
+
+		if let Some(receipt) = self.receipt_cache.get(chain_id, hash) {
+			return Ok(receipt);
+		}
+		implementation.get_receipt(hash, chain_id).await
+	}
+
+	/// Retrieves the receipts of several transactions on a specific chain.
+	///
+	/// Cached receipts are served from the cache and the others are requested
+	/// in a single batch. Returns None for transactions not yet mined.
+	pub async fn batch_get_receipts(
+		&self,
+		hashes: &[TransactionHash],
+		chain_id: u64,
+	) -> Result<Vec<Option<TransactionReceipt>>, DeliveryError> {
+		let implementation = self.implementation(chain_id).await?;
+
+		if self.simulation_mode {
+			let mut receipts = Vec::with_capacity(hashes.len());
+			for hash in hashes {
+				receipts.push(self.simulated_receipt(hash, chain_id).ok());
+			}
+			return Ok(receipts);
+		}
+
+		let mut receipts: Vec<Option<TransactionReceipt>> = hashes
+			.iter()
+			.map(|hash| self.receipt_cache.get(chain_id, hash))
+			.collect();
+		let missing: Vec<TransactionHash> = hashes
+			.iter()
+			.zip(&receipts)
+			.filter(|(_, receipt)| receipt.is_none())
+			.map(|(hash, _)| hash.clone())
+			.collect();
+		if missing.is_empty() {
+			return Ok(receipts);
+		}
+
+		let fetched = implementation
+			.batch_get_receipts(&missing, chain_id)
+			.await?;
+		let head = if fetched.iter().any(Option::is_some) {
+			match implementation.get_block_number(chain_id).await {
+				Ok(head) => {
+					self.receipt_cache.observe_head(chain_id, head);
+					Some(head)
+				},
+				Err(e) => {
+					tracing::debug!(chain_id, error = %e, "Failed to get block number, not caching receipts");
+					None
+				},
+			}
+		} else {
+			None
+		};
+
+		let mut fetched = fetched.into_iter();
+		for receipt in receipts.iter_mut().filter(|receipt| receipt.is_none()) {
+			*receipt = fetched.next().flatten();
+			if let (Some(receipt), Some(head)) = (receipt.as_ref(), head) {
+				let depth = (head + 1).saturating_sub(receipt.block_number);
+				self.receipt_cache.insert(
+					chain_id,
+					receipt.clone(),
+					depth >= self.min_confirmations,
+				);
+			}
+		}
+		Ok(receipts)
+	}
+
 	/// Gets chain-specific data for the given chain ID.
 	///
 	/// Returns gas price, block number, and other chain state information.
 	pub async fn get_chain_data(&self, chain_id: u64) -> Result<ChainData, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
 		let gas_price = implementation.get_gas_price(chain_id).await?;
 		let block_number = implementation.get_block_number(chain_id).await?;
@@ -265,10 +996,7 @@ impl DeliveryService {
 		address: &str,
 		token: Option<&str>,
 	) -> Result<String, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
 		implementation.get_balance(address, token, chain_id).await
 	}
@@ -277,14 +1005,20 @@ impl DeliveryService {
 	///
 	/// Convenience method that routes to the appropriate implementation.
 	pub async fn get_nonce(&self, chain_id: u64, address: &str) -> Result<u64, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
 		implementation.get_nonce(address, chain_id).await
 	}
 
+	/// Gets the code deployed at an address on a specific chain.
+	///
+	/// Convenience method that routes to the appropriate implementation.
+	pub async fn get_code(&self, chain_id: u64, address: &str) -> Result<Vec<u8>, DeliveryError> {
+		let implementation = self.implementation(chain_id).await?;
+
+		implementation.get_code(address, chain_id).await
+	}
+
 	/// Gets the ERC-20 token allowance for an owner-spender pair on a specific chain.
 	///
 	/// Convenience method that routes to the appropriate implementation.
@@ -295,10 +1029,7 @@ impl DeliveryService {
 		spender: &str,
 		token_address: &str,
 	) -> Result<String, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
 		implementation
 			.get_allowance(owner, spender, token_address, chain_id)
@@ -309,10 +1040,7 @@ impl DeliveryService {
 	///
 	/// Returns the gas price as a string in wei.
 	pub async fn get_gas_price(&self, chain_id: u64) -> Result<String, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
 		implementation.get_gas_price(chain_id).await
 	}
@@ -321,11 +1049,150 @@ impl DeliveryService {
 	///
 	/// Returns the latest block number.
 	pub async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError> {
-		let implementation = self
-			.implementations
-			.get(&chain_id)
-			.ok_or(DeliveryError::NoImplementationAvailable)?;
+		let implementation = self.implementation(chain_id).await?;
 
 		implementation.get_block_number(chain_id).await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::time::Duration;
+
+	/// Delivery recording how many submissions overlap.
+	#[derive(Default)]
+	struct OverlapDelivery {
+		in_flight: AtomicUsize,
+		max_in_flight: AtomicUsize,
+	}
+
+	#[async_trait]
+	impl DeliveryInterface for OverlapDelivery {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn submit(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+			let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+			self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+			tokio::time::sleep(Duration::from_millis(20)).await;
+			self.in_flight.fetch_sub(1, Ordering::SeqCst);
+			Ok(TransactionHash(vec![tx.chain_id as u8; 32]))
+		}
+
+		async fn wait_for_confirmation(
+			&self,
+			_hash: &TransactionHash,
+			_chain_id: u64,
+			_confirmations: u64,
+		) -> Result<TransactionReceipt, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_receipt(
+			&self,
+			_hash: &TransactionHash,
+			_chain_id: u64,
+		) -> Result<TransactionReceipt, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_gas_price(&self, _chain_id: u64) -> Result<String, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_balance(
+			&self,
+			_address: &str,
+			_token: Option<&str>,
+			_chain_id: u64,
+		) -> Result<String, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_allowance(
+			&self,
+			_owner: &str,
+			_spender: &str,
+			_token_address: &str,
+			_chain_id: u64,
+		) -> Result<String, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_nonce(&self, _address: &str, _chain_id: u64) -> Result<u64, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_code(&self, _address: &str, _chain_id: u64) -> Result<Vec<u8>, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_block_number(&self, _chain_id: u64) -> Result<u64, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn get_fee_history(
+			&self,
+			_chain_id: u64,
+			_block_count: u64,
+			_reward_percentiles: &[f64],
+		) -> Result<FeeHistory, DeliveryError> {
+			unimplemented!()
+		}
+
+		async fn estimate_gas(&self, _tx: &Transaction) -> Result<TransactionTrace, DeliveryError> {
+			unimplemented!()
+		}
+	}
+
+	fn test_tx(chain_id: u64) -> Transaction {
+		Transaction {
+			to: None,
+			data: vec![],
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_submissions_are_serialized_per_chain() {
+		let chain_1 = Arc::new(OverlapDelivery::default());
+		let chain_10 = Arc::new(OverlapDelivery::default());
+		let implementations = HashMap::from([
+			(
+				ChainId::from(1),
+				chain_1.clone() as Arc<dyn DeliveryInterface>,
+			),
+			(
+				ChainId::from(10),
+				chain_10.clone() as Arc<dyn DeliveryInterface>,
+			),
+		]);
+		let service = Arc::new(DeliveryService::new(implementations, 1));
+
+		let submissions: Vec<_> = [1, 1, 1, 10, 10, 10]
+			.into_iter()
+			.map(|chain_id| {
+				let service = service.clone();
+				tokio::spawn(async move { service.deliver(test_tx(chain_id)).await })
+			})
+			.collect();
+		for submission in submissions {
+			submission.await.unwrap().unwrap();
+		}
+
+		assert_eq!(chain_1.max_in_flight.load(Ordering::SeqCst), 1);
+		assert_eq!(chain_10.max_in_flight.load(Ordering::SeqCst), 1);
+	}
+}
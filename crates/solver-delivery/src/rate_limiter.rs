@@ -0,0 +1,271 @@
+//! Per-chain RPC rate limiting for delivery implementations.
+//!
+//! Public RPC endpoints enforce request rate limits. The wrapper in this module
+//! applies a token bucket per chain to every delivery call, waiting for the
+//! next token instead of failing when the bucket is empty. Transaction
+//! submissions take precedence over other calls such as receipt polling.
+
+use crate::{DeliveryError, DeliveryInterface};
+use async_trait::async_trait;
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use solver_types::{
+	Call, ConfigSchema, FeeHistory, Transaction, TransactionHash, TransactionReceipt,
+	TransactionTrace,
+};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Name of the counter incremented each time a call waits for a token.
+const THROTTLE_COUNTER: &str = "delivery_rpc_throttle_total";
+
+/// Token bucket of a single chain.
+struct ChainLimiter {
+	chain_id: u64,
+	limiter: DefaultDirectRateLimiter,
+	/// Number of submissions waiting for a token.
+	waiting_submits: AtomicUsize,
+	/// Notified when no submission is waiting anymore.
+	submits_done: Notify,
+}
+
+impl ChainLimiter {
+	fn new(chain_id: u64, requests_per_second: NonZeroU32) -> Self {
+		Self {
+			chain_id,
+			limiter: RateLimiter::direct(Quota::per_second(requests_per_second)),
+			waiting_submits: AtomicUsize::new(0),
+			submits_done: Notify::new(),
+		}
+	}
+
+	/// Waits for a token for a transaction submission.
+	async fn acquire_submit(&self) {
+		self.waiting_submits.fetch_add(1, Ordering::SeqCst);
+		let _guard = SubmitGuard(self);
+		self.wait_for_token().await;
+	}
+
+	/// Waits for a token for any other call, yielding to waiting submissions.
+	async fn acquire(&self) {
+		loop {
+			let notified = self.submits_done.notified();
+			if self.waiting_submits.load(Ordering::SeqCst) == 0 {
+				break;
+			}
+			notified.await;
+		}
+		self.wait_for_token().await;
+	}
+
+	async fn wait_for_token(&self) {
+		if self.limiter.check().is_err() {
+			metrics::counter!(THROTTLE_COUNTER, "chain_id" => self.chain_id.to_string())
+				.increment(1);
+			self.limiter.until_ready().await;
+		}
+	}
+}
+
+/// Marks a submission as no longer waiting, even if its call is cancelled.
+struct SubmitGuard<'a>(&'a ChainLimiter);
+
+impl Drop for SubmitGuard<'_> {
+	fn drop(&mut self) {
+		if self.0.waiting_submits.fetch_sub(1, Ordering::SeqCst) == 1 {
+			self.0.submits_done.notify_waiters();
+		}
+	}
+}
+
+/// Delivery implementation wrapper applying per-chain rate limits.
+///
+/// Every call of the wrapped implementation consumes one token from the
+/// bucket of its chain. Chains without a configured limit are not throttled.
+pub struct RateLimitedProvider {
+	inner: Arc<dyn DeliveryInterface>,
+	limiters: HashMap<u64, ChainLimiter>,
+}
+
+impl RateLimitedProvider {
+	/// Creates a new RateLimitedProvider with the given requests per second per chain.
+	pub fn new(inner: Arc<dyn DeliveryInterface>, requests_per_second: &HashMap<u64, u32>) -> Self {
+		let limiters = requests_per_second
+			.iter()
+			.filter_map(|(&chain_id, &rps)| {
+				NonZeroU32::new(rps).map(|rps| (chain_id, ChainLimiter::new(chain_id, rps)))
+			})
+			.collect();
+
+		Self { inner, limiters }
+	}
+
+	async fn throttle(&self, chain_id: u64) {
+		if let Some(limiter) = self.limiters.get(&chain_id) {
+			limiter.acquire().await;
+		}
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for RateLimitedProvider {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	async fn submit(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+		if let Some(limiter) = self.limiters.get(&tx.chain_id) {
+			limiter.acquire_submit().await;
+		}
+		self.inner.submit(tx).await
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		chain_id: u64,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner
+			.wait_for_confirmation(hash, chain_id, confirmations)
+			.await
+	}
+
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+		chain_id: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.get_receipt(hash, chain_id).await
+	}
+
+	async fn batch_get_receipts(
+		&self,
+		hashes: &[TransactionHash],
+		chain_id: u64,
+	) -> Result<Vec<Option<TransactionReceipt>>, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.batch_get_receipts(hashes, chain_id).await
+	}
+
+	async fn get_gas_price(&self, chain_id: u64) -> Result<String, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.get_gas_price(chain_id).await
+	}
+
+	async fn get_balance(
+		&self,
+		address: &str,
+		token: Option<&str>,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.get_balance(address, token, chain_id).await
+	}
+
+	async fn get_allowance(
+		&self,
+		owner: &str,
+		spender: &str,
+		token_address: &str,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner
+			.get_allowance(owner, spender, token_address, chain_id)
+			.await
+	}
+
+	async fn get_nonce(&self, address: &str, chain_id: u64) -> Result<u64, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.get_nonce(address, chain_id).await
+	}
+
+	async fn get_code(&self, address: &str, chain_id: u64) -> Result<Vec<u8>, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.get_code(address, chain_id).await
+	}
+
+	async fn get_block_number(&self, chain_id: u64) -> Result<u64, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.get_block_number(chain_id).await
+	}
+
+	async fn get_fee_history(
+		&self,
+		chain_id: u64,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner
+			.get_fee_history(chain_id, block_count, reward_percentiles)
+			.await
+	}
+
+	async fn estimate_gas(&self, tx: &Transaction) -> Result<TransactionTrace, DeliveryError> {
+		self.throttle(tx.chain_id).await;
+		self.inner.estimate_gas(tx).await
+	}
+
+	async fn simulate_transaction(
+		&self,
+		tx: &Transaction,
+	) -> Result<TransactionTrace, DeliveryError> {
+		self.throttle(tx.chain_id).await;
+		self.inner.simulate_transaction(tx).await
+	}
+
+	async fn supports_wallet_batch(&self, chain_id: u64) -> Result<bool, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.supports_wallet_batch(chain_id).await
+	}
+
+	async fn wallet_send_calls(
+		&self,
+		calls: Vec<Call>,
+		chain_id: u64,
+	) -> Result<String, DeliveryError> {
+		if let Some(limiter) = self.limiters.get(&chain_id) {
+			limiter.acquire_submit().await;
+		}
+		self.inner.wallet_send_calls(calls, chain_id).await
+	}
+
+	async fn wallet_calls_status(
+		&self,
+		batch_id: &str,
+		chain_id: u64,
+	) -> Result<Option<TransactionHash>, DeliveryError> {
+		self.throttle(chain_id).await;
+		self.inner.wallet_calls_status(batch_id, chain_id).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn test_other_calls_yield_to_waiting_submits() {
+		let limiter = Arc::new(ChainLimiter::new(1, NonZeroU32::new(1).unwrap()));
+		limiter.acquire().await;
+
+		let submit = tokio::spawn({
+			let limiter = limiter.clone();
+			async move { limiter.acquire_submit().await }
+		});
+		tokio::time::sleep(Duration::from_millis(50)).await;
+		assert_eq!(limiter.waiting_submits.load(Ordering::SeqCst), 1);
+
+		// The poll only gets a token after the waiting submission
+		limiter.acquire().await;
+		assert!(submit.is_finished());
+		assert_eq!(limiter.waiting_submits.load(Ordering::SeqCst), 0);
+	}
+}
@@ -0,0 +1,88 @@
+//! Decoding of revert data into readable reasons.
+//!
+//! Besides the standard `Error(string)` and `Panic(uint256)` reverts, the
+//! contracts a solver interacts with mostly revert with custom errors. The
+//! errors of ERC-20 tokens, Permit2 and the settlement contracts are decoded
+//! by name and arguments, so simulation failures explain themselves.
+
+use alloy_sol_types::{decode_revert_reason, sol, SolInterface};
+
+sol! {
+	/// Custom errors of contracts commonly called by the solver.
+	interface KnownErrors {
+		// ERC-20 (ERC-6093)
+		error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+		error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+		// Permit2
+		error AllowanceExpired(uint256 deadline);
+		error InsufficientAllowance(uint256 amount);
+		error InvalidNonce();
+		error InvalidSigner();
+		error SignatureExpired(uint256 signatureDeadline);
+		// Settlement contracts
+		error AlreadyFilled();
+		error InvalidOrderStatus();
+		error NotProven();
+	}
+}
+
+/// Decodes revert data into a readable reason.
+///
+/// Returns None if the data is neither a known custom error nor a standard
+/// revert.
+pub fn decode_revert(data: &[u8]) -> Option<String> {
+	use KnownErrors::KnownErrorsErrors as Error;
+
+	let Ok(error) = Error::abi_decode(data, true) else {
+		return decode_revert_reason(data);
+	};
+	Some(match error {
+		Error::ERC20InsufficientAllowance(e) => format!(
+			"ERC20InsufficientAllowance(spender: {}, allowance: {}, needed: {})",
+			e.spender, e.allowance, e.needed
+		),
+		Error::ERC20InsufficientBalance(e) => format!(
+			"ERC20InsufficientBalance(sender: {}, balance: {}, needed: {})",
+			e.sender, e.balance, e.needed
+		),
+		Error::AllowanceExpired(e) => format!("AllowanceExpired(deadline: {})", e.deadline),
+		Error::InsufficientAllowance(e) => format!("InsufficientAllowance(amount: {})", e.amount),
+		Error::InvalidNonce(_) => "InvalidNonce()".to_string(),
+		Error::InvalidSigner(_) => "InvalidSigner()".to_string(),
+		Error::SignatureExpired(e) => format!(
+			"SignatureExpired(signatureDeadline: {})",
+			e.signatureDeadline
+		),
+		Error::AlreadyFilled(_) => "AlreadyFilled()".to_string(),
+		Error::InvalidOrderStatus(_) => "InvalidOrderStatus()".to_string(),
+		Error::NotProven(_) => "NotProven()".to_string(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{address, U256};
+	use alloy_sol_types::{Revert, SolError};
+
+	#[test]
+	fn test_decode_known_and_standard_reverts() {
+		let data = KnownErrors::ERC20InsufficientAllowance {
+			spender: address!("000000000022D473030F116dDEE9F6B43aC78BA3"),
+			allowance: U256::from(1),
+			needed: U256::from(2),
+		}
+		.abi_encode();
+		assert_eq!(
+			decode_revert(&data).unwrap(),
+			"ERC20InsufficientAllowance(spender: 0x000000000022D473030F116dDEE9F6B43aC78BA3, allowance: 1, needed: 2)"
+		);
+		assert_eq!(
+			decode_revert(&KnownErrors::AlreadyFilled {}.abi_encode()).unwrap(),
+			"AlreadyFilled()"
+		);
+
+		let data = Revert::from("insufficient output").abi_encode();
+		assert_eq!(decode_revert(&data).unwrap(), "revert: insufficient output");
+	}
+}
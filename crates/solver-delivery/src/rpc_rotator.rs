@@ -0,0 +1,203 @@
+//! Distribution of read-only RPC calls over several endpoints.
+//!
+//! Monitoring polls receipts, block numbers and balances far more often than
+//! transactions are submitted, and a single endpoint serving all of them can
+//! hit its rate limit. The rotator spreads reads over the configured
+//! endpoints, either in turn or preferring the fastest one, while
+//! transactions are always submitted to a designated primary endpoint.
+//! Networks without a rotation configuration read from the primary endpoint.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Weight of the latest sample in the latency moving average.
+const LATENCY_EMA_ALPHA: f64 = 0.2;
+
+/// Latency recorded for a failed read, so failing endpoints lose preference.
+const FAILED_READ_LATENCY: Duration = Duration::from_secs(5);
+
+/// Age of its latest sample after which a latency-weighted endpoint serves a
+/// read again, so that slow or failed endpoints can recover.
+const REPROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How read-only calls are distributed over the endpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadStrategy {
+	/// Each read goes to the next endpoint in turn.
+	#[default]
+	RoundRobin,
+	/// Reads go to the endpoint with the lowest average latency.
+	LatencyWeighted,
+}
+
+/// RPC endpoints of a single network.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RpcRotationConfig {
+	/// Endpoints serving read-only calls. Defaults to the HTTP URLs of the network.
+	#[serde(default)]
+	pub rpc_urls: Vec<String>,
+	/// Endpoint transactions are submitted to. Defaults to the first HTTP URL
+	/// of the network.
+	#[serde(default)]
+	pub primary_url: Option<String>,
+	#[serde(default)]
+	pub read_strategy: ReadStrategy,
+}
+
+/// Selects the endpoint of each RPC call of a network.
+pub struct RpcRotator {
+	primary_url: String,
+	read_urls: Vec<String>,
+	strategy: ReadStrategy,
+	/// Index of the next endpoint for round-robin reads.
+	next: AtomicUsize,
+	/// Moving average latency per read endpoint in milliseconds and the time
+	/// of its latest sample, None until the endpoint served a read.
+	latencies_ms: Mutex<Vec<Option<(f64, Instant)>>>,
+}
+
+impl RpcRotator {
+	/// Creates a new rotator submitting to `primary_url` and reading from
+	/// `read_urls`, or from the primary endpoint when none are given.
+	pub fn new(primary_url: String, read_urls: Vec<String>, strategy: ReadStrategy) -> Self {
+		let read_urls = if read_urls.is_empty() {
+			vec![primary_url.clone()]
+		} else {
+			read_urls
+		};
+		Self {
+			latencies_ms: Mutex::new(vec![None; read_urls.len()]),
+			primary_url,
+			read_urls,
+			strategy,
+			next: AtomicUsize::new(0),
+		}
+	}
+
+	/// Returns the endpoint transactions are submitted to.
+	pub fn primary_url(&self) -> &str {
+		&self.primary_url
+	}
+
+	/// Returns the endpoints serving read-only calls.
+	pub fn read_urls(&self) -> &[String] {
+		&self.read_urls
+	}
+
+	/// Returns the index in [`Self::read_urls`] of the endpoint serving the
+	/// next read.
+	///
+	/// With latency weighting, endpoints that did not serve a read yet are
+	/// tried first so that every endpoint gets measured, then endpoints not
+	/// measured for [`REPROBE_INTERVAL`].
+	pub fn select_read(&self) -> usize {
+		self.select_read_at(Instant::now())
+	}
+
+	fn select_read_at(&self, now: Instant) -> usize {
+		match self.strategy {
+			ReadStrategy::RoundRobin => {
+				self.next.fetch_add(1, Ordering::Relaxed) % self.read_urls.len()
+			},
+			ReadStrategy::LatencyWeighted => {
+				let latencies = self.latencies_ms.lock().unwrap();
+				let measured = || {
+					latencies
+						.iter()
+						.enumerate()
+						.filter_map(|(index, latency)| latency.map(|latency| (index, latency)))
+				};
+				latencies
+					.iter()
+					.position(Option::is_none)
+					.or_else(|| {
+						measured()
+							.filter(|(_, (_, at))| {
+								now.saturating_duration_since(*at) >= REPROBE_INTERVAL
+							})
+							.min_by_key(|(_, (_, at))| *at)
+							.map(|(index, _)| index)
+					})
+					.or_else(|| {
+						measured()
+							.min_by(|a, b| a.1 .0.total_cmp(&b.1 .0))
+							.map(|(index, _)| index)
+					})
+					.unwrap_or_default()
+			},
+		}
+	}
+
+	/// Records the outcome of a read served by the endpoint at `index`.
+	///
+	/// Failed reads count as slow reads.
+	pub fn record_read(&self, index: usize, elapsed: Duration, success: bool) {
+		let elapsed = if success {
+			elapsed
+		} else {
+			elapsed.max(FAILED_READ_LATENCY)
+		};
+		let sample = elapsed.as_secs_f64() * 1000.0;
+		let mut latencies = self.latencies_ms.lock().unwrap();
+		if let Some(latency) = latencies.get_mut(index) {
+			let average = match latency {
+				Some((average, _)) => {
+					LATENCY_EMA_ALPHA * sample + (1.0 - LATENCY_EMA_ALPHA) * *average
+				},
+				None => sample,
+			};
+			*latency = Some((average, Instant::now()));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn urls() -> Vec<String> {
+		vec!["http://a".to_string(), "http://b".to_string()]
+	}
+
+	#[test]
+	fn test_reads_rotate_and_prefer_fastest() {
+		let rotator = RpcRotator::new(
+			"http://primary".to_string(),
+			urls(),
+			ReadStrategy::RoundRobin,
+		);
+		assert_eq!(rotator.primary_url(), "http://primary");
+		let picks: Vec<usize> = (0..4).map(|_| rotator.select_read()).collect();
+		assert_eq!(picks, vec![0, 1, 0, 1]);
+
+		let rotator = RpcRotator::new(
+			"http://a".to_string(),
+			urls(),
+			ReadStrategy::LatencyWeighted,
+		);
+		// Unmeasured endpoints are tried first
+		assert_eq!(rotator.select_read(), 0);
+		rotator.record_read(0, Duration::from_millis(100), true);
+		assert_eq!(rotator.select_read(), 1);
+		rotator.record_read(1, Duration::from_millis(50), true);
+		assert_eq!(rotator.select_read(), 1);
+
+		// Failures make the endpoint lose preference
+		rotator.record_read(1, Duration::from_millis(50), false);
+		assert_eq!(rotator.select_read(), 0);
+
+		// Endpoints not measured for a while are probed again
+		rotator.record_read(0, Duration::from_millis(100), true);
+		let later = Instant::now() + REPROBE_INTERVAL;
+		assert_eq!(rotator.select_read_at(later), 1);
+		rotator.record_read(1, Duration::from_millis(50), true);
+		assert_eq!(rotator.select_read_at(later), 0);
+
+		// Without read endpoints, reads go to the primary endpoint
+		let rotator = RpcRotator::new("http://a".to_string(), Vec::new(), ReadStrategy::RoundRobin);
+		assert_eq!(rotator.read_urls(), ["http://a".to_string()]);
+	}
+}
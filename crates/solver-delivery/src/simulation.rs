@@ -0,0 +1,154 @@
+//! Bookkeeping of transactions simulated instead of submitted.
+//!
+//! In simulation mode transactions are executed with `eth_call` and never
+//! broadcast. Each simulated transaction gets a synthetic hash, unique per
+//! chain and submission, under which its synthetic receipt is kept. Only the
+//! most recent simulations are kept, so the bookkeeping stays bounded.
+
+use alloy_primitives::keccak256;
+use solver_types::{Transaction, TransactionHash, TransactionReceipt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Default number of simulated transactions whose receipts are kept.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Recorded simulations, in the order they were made.
+#[derive(Default)]
+struct Simulations {
+	/// Number of transactions simulated so far, mixed into each hash.
+	sequence: u64,
+	/// Synthetic receipts keyed by chain and synthetic hash.
+	receipts: HashMap<(u64, TransactionHash), TransactionReceipt>,
+	/// Keys of the receipts, oldest first.
+	order: VecDeque<(u64, TransactionHash)>,
+}
+
+/// Synthetic receipts of simulated transactions.
+pub struct SimulatedTransactions {
+	simulations: Mutex<Simulations>,
+	/// Maximum number of receipts kept.
+	capacity: usize,
+}
+
+impl Default for SimulatedTransactions {
+	fn default() -> Self {
+		Self::new(DEFAULT_CAPACITY)
+	}
+}
+
+impl SimulatedTransactions {
+	/// Creates a new SimulatedTransactions keeping at most `capacity` receipts.
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			simulations: Mutex::new(Simulations::default()),
+			capacity: capacity.max(1),
+		}
+	}
+
+	/// Records a successful simulation and returns its synthetic hash.
+	///
+	/// The hash commits to the chain, the calldata and the position of the
+	/// simulation, so identical calls never share a hash.
+	pub fn record(
+		&self,
+		tx: &Transaction,
+		gas_used: u64,
+		effective_gas_price: u128,
+		block_number: u64,
+	) -> TransactionHash {
+		let mut simulations = self.simulations.lock().unwrap();
+		simulations.sequence += 1;
+
+		let mut preimage = Vec::with_capacity(16 + tx.data.len());
+		preimage.extend_from_slice(&tx.chain_id.to_be_bytes());
+		preimage.extend_from_slice(&simulations.sequence.to_be_bytes());
+		preimage.extend_from_slice(&tx.data);
+		let hash = TransactionHash(keccak256(&preimage).to_vec());
+
+		let key = (tx.chain_id, hash.clone());
+		simulations.receipts.insert(
+			key.clone(),
+			TransactionReceipt {
+				hash: hash.clone(),
+				block_number,
+				success: true,
+				gas_used,
+				effective_gas_price,
+			},
+		);
+		simulations.order.push_back(key);
+		while simulations.order.len() > self.capacity {
+			if let Some(oldest) = simulations.order.pop_front() {
+				simulations.receipts.remove(&oldest);
+			}
+		}
+
+		hash
+	}
+
+	/// Returns the synthetic receipt of a simulated transaction, if still kept.
+	pub fn receipt(&self, chain_id: u64, hash: &TransactionHash) -> Option<TransactionReceipt> {
+		self.simulations
+			.lock()
+			.unwrap()
+			.receipts
+			.get(&(chain_id, hash.clone()))
+			.cloned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+
+	fn test_tx(chain_id: u64) -> Transaction {
+		Transaction {
+			to: None,
+			data: vec![0xab; 36],
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		}
+	}
+
+	#[test]
+	fn test_identical_calls_get_distinct_hashes() {
+		let simulated = SimulatedTransactions::default();
+		let first = simulated.record(&test_tx(1), 21_000, 5, 100);
+		let second = simulated.record(&test_tx(1), 30_000, 6, 101);
+		let other_chain = simulated.record(&test_tx(10), 40_000, 7, 102);
+		assert_ne!(first, second);
+		assert_ne!(first, other_chain);
+
+		let receipt = simulated.receipt(1, &first).unwrap();
+		assert!(receipt.success);
+		assert_eq!(receipt.gas_used, 21_000);
+		assert_eq!(receipt.effective_gas_price, 5);
+		assert_eq!(receipt.block_number, 100);
+		assert_eq!(simulated.receipt(1, &second).unwrap().gas_used, 30_000);
+
+		// Receipts are only found on the chain they were simulated on
+		assert!(simulated.receipt(10, &first).is_none());
+		assert!(simulated.receipt(10, &other_chain).is_some());
+	}
+
+	#[test]
+	fn test_oldest_receipts_are_evicted() {
+		let simulated = SimulatedTransactions::new(2);
+		let first = simulated.record(&test_tx(1), 1, 1, 1);
+		let second = simulated.record(&test_tx(1), 2, 1, 1);
+		let third = simulated.record(&test_tx(1), 3, 1, 1);
+
+		assert!(simulated.receipt(1, &first).is_none());
+		assert!(simulated.receipt(1, &second).is_some());
+		assert!(simulated.receipt(1, &third).is_some());
+	}
+}
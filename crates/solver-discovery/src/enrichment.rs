@@ -0,0 +1,247 @@
+//! Intent metadata enrichment.
+//!
+//! Raw intents only carry the data emitted by their source. Enrichers add
+//! context useful for strategy decisions, such as token symbols and prices,
+//! to `intent.metadata` before the intent is handed to the solver.
+
+use crate::DiscoveryError;
+use alloy_primitives::Address as AlloyAddress;
+use async_trait::async_trait;
+use solver_price::PriceService;
+use solver_types::{Eip7683OrderData, Intent, NetworksConfig};
+use std::sync::Arc;
+
+/// Name of the enricher adding token symbols and decimals.
+pub const TOKEN_METADATA_ENRICHER: &str = "token_metadata";
+
+/// Name of the enricher adding token USD prices.
+pub const PRICE_ENRICHER: &str = "price";
+
+/// Trait defining the interface for intent enrichers.
+///
+/// Enrichers run in order on every discovered intent. Each enricher may add
+/// information to the intent metadata; a failing enricher does not prevent
+/// the intent from being processed.
+#[async_trait]
+pub trait IntentEnricher: Send + Sync {
+	/// Returns the name of this enricher, used for logging.
+	fn name(&self) -> &'static str;
+
+	/// Adds information to the intent's metadata.
+	async fn enrich(&self, intent: &mut Intent) -> Result<(), DiscoveryError>;
+}
+
+/// Runs all enrichers on an intent, logging and skipping failures.
+pub async fn run_pipeline(enrichers: &[Box<dyn IntentEnricher>], intent: &mut Intent) {
+	for enricher in enrichers {
+		if let Err(e) = enricher.enrich(intent).await {
+			tracing::warn!(
+				enricher = enricher.name(),
+				intent_id = %solver_types::truncate_id(&intent.id),
+				error = %e,
+				"Failed to enrich intent"
+			);
+		}
+	}
+}
+
+/// Returns the (chain ID, token) pairs of the intent's inputs and outputs.
+///
+/// Intents of standards without known token data yield no tokens.
+fn intent_tokens(intent: &Intent) -> Result<Vec<(u64, AlloyAddress)>, DiscoveryError> {
+	if intent.standard != "eip7683" {
+		return Ok(Vec::new());
+	}
+
+	let order_data = serde_json::from_value::<Eip7683OrderData>(intent.data.clone())
+		.map_err(|e| DiscoveryError::ParseError(format!("Invalid order data: {}", e)))?;
+	let origin_chain = u64::try_from(order_data.origin_chain_id)
+		.map_err(|e| DiscoveryError::ParseError(format!("Invalid origin chain: {}", e)))?;
+
+	let mut tokens = Vec::new();
+	for [token, _] in &order_data.inputs {
+		tokens.push((
+			origin_chain,
+			AlloyAddress::from_slice(&token.to_be_bytes::<32>()[12..]),
+		));
+	}
+	for output in &order_data.outputs {
+		let chain_id = u64::try_from(output.chain_id)
+			.map_err(|e| DiscoveryError::ParseError(format!("Invalid output chain: {}", e)))?;
+		tokens.push((chain_id, AlloyAddress::from_slice(&output.token[12..])));
+	}
+
+	Ok(tokens)
+}
+
+/// Enricher adding token symbols and decimals from the networks configuration.
+pub struct TokenMetadataEnricher {
+	networks: NetworksConfig,
+}
+
+impl TokenMetadataEnricher {
+	/// Creates a new TokenMetadataEnricher for the given networks.
+	pub fn new(networks: NetworksConfig) -> Self {
+		Self { networks }
+	}
+}
+
+#[async_trait]
+impl IntentEnricher for TokenMetadataEnricher {
+	fn name(&self) -> &'static str {
+		TOKEN_METADATA_ENRICHER
+	}
+
+	async fn enrich(&self, intent: &mut Intent) -> Result<(), DiscoveryError> {
+		for (chain_id, token) in intent_tokens(intent)? {
+			let entry = intent.metadata.token_mut(chain_id, &token.to_string());
+			let config = self.networks.get(&chain_id).and_then(|network| {
+				network
+					.tokens
+					.iter()
+					.find(|t| t.address.0.as_slice() == token.as_slice())
+			});
+			if let Some(config) = config {
+				entry.symbol = Some(config.symbol.clone());
+				entry.decimals = Some(config.decimals);
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Enricher adding token USD prices from the price service.
+pub struct PriceEnricher {
+	price: Arc<PriceService>,
+}
+
+impl PriceEnricher {
+	/// Creates a new PriceEnricher using the given price service.
+	pub fn new(price: Arc<PriceService>) -> Self {
+		Self { price }
+	}
+}
+
+#[async_trait]
+impl IntentEnricher for PriceEnricher {
+	fn name(&self) -> &'static str {
+		PRICE_ENRICHER
+	}
+
+	async fn enrich(&self, intent: &mut Intent) -> Result<(), DiscoveryError> {
+		for (chain_id, token) in intent_tokens(intent)? {
+			let address = token.to_string();
+			match self.price.get_token_price(chain_id, &address).await {
+				Ok(price) => {
+					intent.metadata.token_mut(chain_id, &address).price_usd = Some(price.price_usd);
+				},
+				Err(e) => {
+					tracing::debug!(
+						chain_id = chain_id,
+						token = %address,
+						error = %e,
+						"No price available for intent token"
+					);
+				},
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+	use solver_types::networks::RpcEndpoint;
+	use solver_types::standards::eip7683::{GasLimitOverrides, MandateOutput};
+	use solver_types::{Address, ChainId, IntentMetadata, NetworkConfig, TokenConfig};
+	use std::collections::HashMap;
+
+	const TOKEN: &str = "0x1111111111111111111111111111111111111111";
+
+	fn intent() -> Intent {
+		let token = TOKEN.parse::<AlloyAddress>().unwrap();
+		let mut output_token = [0u8; 32];
+		output_token[12..].copy_from_slice(token.as_slice());
+
+		let order_data = Eip7683OrderData {
+			user: "0x0000000000000000000000000000000000000001".to_string(),
+			nonce: U256::ZERO,
+			origin_chain_id: U256::from(1),
+			expires: 0,
+			fill_deadline: 0,
+			input_oracle: "0x0000000000000000000000000000000000000002".to_string(),
+			inputs: vec![[U256::from_be_slice(token.as_slice()), U256::from(100)]],
+			order_id: [0u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![MandateOutput {
+				oracle: [0u8; 32],
+				settler: [0u8; 32],
+				chain_id: U256::from(10),
+				token: output_token,
+				amount: U256::from(100),
+				recipient: [0u8; 32],
+				call: vec![],
+				context: vec![],
+			}],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		};
+
+		Intent {
+			id: "intent".to_string(),
+			source: "on-chain".to_string(),
+			standard: "eip7683".to_string(),
+			metadata: IntentMetadata {
+				requires_auction: false,
+				exclusive_until: None,
+				discovered_at: 0,
+				tokens: Vec::new(),
+				origin_block_number: None,
+				origin_block_timestamp: None,
+			},
+			data: serde_json::to_value(order_data).unwrap(),
+			quote_id: None,
+		}
+	}
+
+	fn network() -> NetworkConfig {
+		NetworkConfig {
+			rpc_urls: vec![RpcEndpoint {
+				http: Some("http://localhost:8545".to_string()),
+				ws: None,
+			}],
+			input_settler_address: Address(vec![0u8; 20]),
+			output_settler_address: Address(vec![0u8; 20]),
+			tokens: vec![TokenConfig {
+				address: Address(TOKEN.parse::<AlloyAddress>().unwrap().to_vec()),
+				symbol: "USDC".to_string(),
+				decimals: 6,
+			}],
+			input_settler_compact_address: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_token_metadata_enricher_adds_configured_tokens() {
+		let networks: NetworksConfig = HashMap::from([(ChainId::MAINNET, network())]);
+		let mut intent = intent();
+
+		TokenMetadataEnricher::new(networks)
+			.enrich(&mut intent)
+			.await
+			.unwrap();
+
+		let tokens = &intent.metadata.tokens;
+		assert_eq!(tokens.len(), 2);
+		assert_eq!(tokens[0].chain_id, 1);
+		assert_eq!(tokens[0].symbol.as_deref(), Some("USDC"));
+		assert_eq!(tokens[0].decimals, Some(6));
+		// Chain 10 is not configured, so only the entry is recorded
+		assert_eq!(tokens[1].chain_id, 10);
+		assert_eq!(tokens[1].symbol, None);
+	}
+}
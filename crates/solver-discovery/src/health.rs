@@ -0,0 +1,240 @@
+//! Health monitoring for discovery sources.
+//!
+//! Subscriptions to public RPC nodes can drop silently, leaving a discovery
+//! source running but no longer delivering intents. The monitor tracks when
+//! each source last produced an intent and restarts sources that have been
+//! inactive for longer than the configured threshold.
+
+use crate::DiscoveryService;
+use solver_types::Intent;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Name of the gauge reporting per-source health (1 healthy, 0 unhealthy).
+const HEALTH_GAUGE: &str = "discovery_source_health";
+
+/// Activity and restart state for a single discovery source.
+#[derive(Debug)]
+struct SourceState {
+	/// When the source last delivered an intent or was (re)started.
+	last_seen: Instant,
+	/// Restarts attempted since the source was last active.
+	restart_attempts: u32,
+}
+
+/// Monitors discovery sources for inactivity and restarts them.
+pub struct DiscoveryHealthMonitor {
+	/// Duration without intents after which a source is considered unhealthy.
+	inactivity_threshold: Duration,
+	/// Maximum consecutive restarts before giving up on a source.
+	max_restart_attempts: u32,
+	/// State of each monitored source.
+	sources: Mutex<HashMap<String, SourceState>>,
+}
+
+impl DiscoveryHealthMonitor {
+	/// Creates a monitor for the given discovery sources.
+	pub fn new(
+		sources: impl IntoIterator<Item = String>,
+		inactivity_threshold: Duration,
+		max_restart_attempts: u32,
+	) -> Self {
+		let now = Instant::now();
+		let sources = sources
+			.into_iter()
+			.map(|name| {
+				metrics::gauge!(HEALTH_GAUGE, "source" => name.clone()).set(1.0);
+				(
+					name,
+					SourceState {
+						last_seen: now,
+						restart_attempts: 0,
+					},
+				)
+			})
+			.collect();
+
+		Self {
+			inactivity_threshold,
+			max_restart_attempts,
+			sources: Mutex::new(sources),
+		}
+	}
+
+	/// Returns true if the given source is monitored.
+	pub fn is_monitored(&self, source: &str) -> bool {
+		self.lock().contains_key(source)
+	}
+
+	/// Records that the given source delivered an intent.
+	pub fn record_activity(&self, source: &str) {
+		if let Some(state) = self.lock().get_mut(source) {
+			state.last_seen = Instant::now();
+			state.restart_attempts = 0;
+			metrics::gauge!(HEALTH_GAUGE, "source" => source.to_string()).set(1.0);
+		}
+	}
+
+	/// Wraps a sender so intents from the given source are recorded as activity.
+	///
+	/// Intents are forwarded unchanged to the original sender.
+	pub fn track(
+		self: &Arc<Self>,
+		source: &str,
+		sender: mpsc::UnboundedSender<Intent>,
+	) -> mpsc::UnboundedSender<Intent> {
+		let (tracked_tx, mut tracked_rx) = mpsc::unbounded_channel();
+		let monitor = self.clone();
+		let source = source.to_string();
+
+		tokio::spawn(async move {
+			while let Some(intent) = tracked_rx.recv().await {
+				monitor.record_activity(&source);
+				if sender.send(intent).is_err() {
+					break;
+				}
+			}
+		});
+
+		tracked_tx
+	}
+
+	/// Checks all sources and returns those that should be restarted.
+	///
+	/// Sources returned here are counted as having a restart attempted. Once a
+	/// source reaches the maximum number of attempts it is left unhealthy
+	/// until it delivers an intent again.
+	pub fn check(&self) -> Vec<String> {
+		let mut to_restart = Vec::new();
+		let now = Instant::now();
+
+		for (source, state) in self.lock().iter_mut() {
+			let inactive_for = now.duration_since(state.last_seen);
+			if inactive_for < self.inactivity_threshold {
+				metrics::gauge!(HEALTH_GAUGE, "source" => source.clone()).set(1.0);
+				continue;
+			}
+
+			metrics::gauge!(HEALTH_GAUGE, "source" => source.clone()).set(0.0);
+
+			if state.restart_attempts >= self.max_restart_attempts {
+				tracing::error!(
+					source = %source,
+					attempts = state.restart_attempts,
+					"Discovery source still inactive after maximum restart attempts"
+				);
+				continue;
+			}
+
+			tracing::warn!(
+				source = %source,
+				inactive_seconds = inactive_for.as_secs(),
+				"No intents received from discovery source, restarting"
+			);
+			state.restart_attempts += 1;
+			// Give the restarted source a full threshold before checking again
+			state.last_seen = now;
+			to_restart.push(source.clone());
+		}
+
+		to_restart
+	}
+
+	/// Spawns a task that periodically checks sources and restarts inactive ones.
+	///
+	/// Restarted sources are started with a tracked sender forwarding to `sender`.
+	pub fn spawn(
+		self: Arc<Self>,
+		discovery: Arc<DiscoveryService>,
+		sender: mpsc::UnboundedSender<Intent>,
+		interval: Duration,
+	) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut ticker = tokio::time::interval(interval);
+			// The first tick completes immediately
+			ticker.tick().await;
+			loop {
+				ticker.tick().await;
+				for source in self.check() {
+					let tracked = self.track(&source, sender.clone());
+					if let Err(e) = discovery.restart(&source, tracked).await {
+						tracing::error!(
+							source = %source,
+							error = %e,
+							"Failed to restart discovery source"
+						);
+					}
+				}
+			}
+		})
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, SourceState>> {
+		self.sources.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_inactive_source_is_restarted_up_to_limit() {
+		let monitor = DiscoveryHealthMonitor::new(vec!["onchain".to_string()], Duration::ZERO, 2);
+
+		assert_eq!(monitor.check(), vec!["onchain".to_string()]);
+		assert_eq!(monitor.check(), vec!["onchain".to_string()]);
+		// Maximum restart attempts reached
+		assert!(monitor.check().is_empty());
+
+		// Activity resets the restart counter
+		monitor.record_activity("onchain");
+		assert_eq!(monitor.check(), vec!["onchain".to_string()]);
+	}
+
+	#[test]
+	fn test_active_source_is_not_restarted() {
+		let monitor =
+			DiscoveryHealthMonitor::new(vec!["onchain".to_string()], Duration::from_secs(60), 3);
+
+		assert!(monitor.check().is_empty());
+		assert!(monitor.is_monitored("onchain"));
+		assert!(!monitor.is_monitored("offchain"));
+	}
+
+	#[tokio::test]
+	async fn test_tracked_sender_records_activity() {
+		let monitor = Arc::new(DiscoveryHealthMonitor::new(
+			vec!["onchain".to_string()],
+			Duration::from_millis(50),
+			3,
+		));
+		let (sender, mut receiver) = mpsc::unbounded_channel();
+		let tracked = monitor.track("onchain", sender);
+
+		tokio::time::sleep(Duration::from_millis(60)).await;
+		tracked
+			.send(Intent {
+				id: "intent".to_string(),
+				source: "on-chain".to_string(),
+				standard: "eip7683".to_string(),
+				metadata: solver_types::IntentMetadata {
+					requires_auction: false,
+					exclusive_until: None,
+					discovered_at: 0,
+					tokens: Vec::new(),
+					origin_block_number: None,
+					origin_block_timestamp: None,
+				},
+				data: serde_json::json!({}),
+				quote_id: None,
+			})
+			.unwrap();
+
+		assert_eq!(receiver.recv().await.unwrap().id, "intent");
+		assert!(monitor.check().is_empty());
+	}
+}
@@ -25,6 +25,7 @@
 //! ## API Endpoint
 //!
 //! - `POST /intent` - Submit a new cross-chain order
+//! - `POST /intents/batch` - Submit up to 100 cross-chain orders at once
 //!
 //! ## Configuration
 //!
@@ -32,7 +33,9 @@
 //! - `api_host` - The host address to bind the API server (default: "0.0.0.0")
 //! - `api_port` - The port to listen on (default: 8080)
 //! - `rpc_url` - Ethereum RPC URL for calling settler contracts
-//! - `auth_token` - Optional authentication token for API access
+//! - `auth_token` - Optional API key required as a bearer token
+//! - `auth_tokens` - Optional list of API keys, any of which is accepted
+//! - `rate_limit` - Optional maximum number of intents accepted per minute
 //!
 //! ## Order Flow
 //!
@@ -43,31 +46,40 @@
 //! 5. The order is converted to an Intent and broadcast to solvers
 
 use crate::{DiscoveryError, DiscoveryInterface};
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use alloy_provider::RootProvider;
 use alloy_sol_types::sol;
 use alloy_transport_http::Http;
 use async_trait::async_trait;
 use axum::{
-	extract::State,
-	http::StatusCode,
-	response::{IntoResponse, Json},
+	extract::{ConnectInfo, Request, State},
+	http::{header::AUTHORIZATION, StatusCode},
+	middleware::{self, Next},
+	response::{IntoResponse, Json, Response},
 	routing::post,
-	Router,
+	Extension, Router,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use solver_types::{
 	current_timestamp,
-	standards::eip7683::{GasLimitOverrides, MandateOutput},
+	protocols::PROTOCOL_REGISTRY,
+	standards::eip7683::{GasLimitOverrides, LockType, MandateOutput},
+	utils::{
+		check_bytes32_address, compute_domain_hash, compute_final_digest, Eip712AbiEncoder,
+		MANDATE_OUTPUT_TYPE, NAME_PERMIT2, PERMIT2_WITNESS_TYPE, PERMIT_BATCH_WITNESS_TYPE,
+		TOKEN_PERMISSIONS_TYPE,
+	},
 	with_0x_prefix, ConfigSchema, Eip7683OrderData, Field, FieldType, ImplementationRegistry,
-	Intent, IntentMetadata, NetworksConfig, Schema,
+	Intent, IntentMetadata, NetworksConfig, Schema, SchemaDefinition,
 };
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, Mutex};
-use tower_http::cors::CorsLayer;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
 
 // Import the Solidity types for the OIF contracts
 sol! {
@@ -77,6 +89,11 @@ sol! {
 		function openFor(bytes calldata order, address sponsor, bytes calldata signature) external;
 	}
 
+	#[sol(rpc)]
+	interface IInputSettlerCompact {
+		function orderIdentifier(StandardOrder calldata order) external view returns (bytes32);
+	}
+
 	struct StandardOrder {
 		address user;
 		uint256 nonce;
@@ -188,11 +205,18 @@ where
 /// * `order` - The StandardOrder encoded as hex bytes
 /// * `sponsor` - The address sponsoring the order (usually the user)
 /// * `signature` - The Permit2Witness signature
+/// * `lock_type` - How the order's inputs are secured (defaults to escrow)
+/// * `requires_auction` - Whether solvers must bid for the order before
+///   filling it (defaults to false)
 #[derive(Debug, Deserialize)]
 struct IntentRequest {
 	order: Bytes,
 	sponsor: Address,
 	signature: Bytes,
+	#[serde(default, rename = "lockType")]
+	lock_type: LockType,
+	#[serde(default, rename = "requiresAuction")]
+	requires_auction: bool,
 }
 
 /// API response for intent submission.
@@ -212,6 +236,291 @@ struct IntentResponse {
 	message: Option<String>,
 }
 
+/// Maximum number of intents accepted in a single batch submission.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// Name of the counter of orders rejected for right-padded addresses.
+const RIGHT_PADDED_ADDRESSES_COUNTER: &str = "discovery_right_padded_addresses_total";
+
+/// Successfully ingested intent in a batch submission.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchIntentSuccess {
+	order_id: String,
+	status: String,
+}
+
+/// Rejected intent in a batch submission.
+///
+/// `index` is the position of the intent in the submitted batch.
+#[derive(Debug, Serialize)]
+struct BatchIntentFailure {
+	index: usize,
+	error: String,
+}
+
+/// API response for batch intent submission.
+///
+/// Returned by the POST /intents/batch endpoint with a 207 Multi-Status code.
+#[derive(Debug, Serialize)]
+struct BatchIntentResponse {
+	successes: Vec<BatchIntentSuccess>,
+	failures: Vec<BatchIntentFailure>,
+}
+
+/// Fixed-window rate limiter counting submitted intents rather than requests.
+///
+/// A batch of N intents consumes N units of the per-minute allowance.
+struct IntentRateLimiter {
+	/// Maximum number of intents per window.
+	limit: u32,
+	/// Start of the current window and intents accepted in it.
+	window: std::sync::Mutex<(Instant, u32)>,
+}
+
+impl IntentRateLimiter {
+	/// Length of a rate limiting window.
+	const WINDOW: Duration = Duration::from_secs(60);
+
+	fn new(limit: u32) -> Self {
+		Self {
+			limit,
+			window: std::sync::Mutex::new((Instant::now(), 0)),
+		}
+	}
+
+	/// Reserves capacity for `count` intents.
+	///
+	/// Returns false without reserving anything if the limit would be exceeded.
+	fn try_acquire(&self, count: u32) -> bool {
+		let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+		if window.0.elapsed() >= Self::WINDOW {
+			*window = (Instant::now(), 0);
+		}
+		if window.1.saturating_add(count) > self.limit {
+			return false;
+		}
+		window.1 += count;
+		true
+	}
+}
+
+/// Intent rate limiters of the API.
+///
+/// Each API key has its own allowance, independent of the other keys and of
+/// the allowance shared by unauthenticated callers.
+struct RateLimiters {
+	/// Limiter of requests without an API key.
+	unauthenticated: IntentRateLimiter,
+	/// Limiters of the API keys, by key index.
+	keys: Vec<IntentRateLimiter>,
+}
+
+impl RateLimiters {
+	fn new(limit: u32, key_count: usize) -> Self {
+		Self {
+			unauthenticated: IntentRateLimiter::new(limit),
+			keys: (0..key_count)
+				.map(|_| IntentRateLimiter::new(limit))
+				.collect(),
+		}
+	}
+
+	/// Returns the limiter of the caller of a request.
+	fn for_caller(&self, key: Option<AuthenticatedKey>) -> &IntentRateLimiter {
+		key.and_then(|AuthenticatedKey(index)| self.keys.get(index))
+			.unwrap_or(&self.unauthenticated)
+	}
+}
+
+/// API keys accepted by the discovery API.
+///
+/// Only the SHA-256 hashes of the keys are kept. Any configured key is
+/// accepted, so a key is rotated by adding the new key first and removing
+/// the old one once clients have switched.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+	hashes: Vec<[u8; 32]>,
+}
+
+impl ApiKeys {
+	/// Creates the set of accepted keys.
+	pub fn new<I, S>(keys: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		Self {
+			hashes: keys.into_iter().map(|key| hash_key(key.as_ref())).collect(),
+		}
+	}
+
+	/// Reads the keys from the `auth_token` and `auth_tokens` fields.
+	fn from_config(config: &toml::Value) -> Self {
+		let single = config.get("auth_token").and_then(|v| v.as_str());
+		let list = config
+			.get("auth_tokens")
+			.and_then(|v| v.as_array())
+			.into_iter()
+			.flatten()
+			.filter_map(|v| v.as_str());
+		Self::new(single.into_iter().chain(list))
+	}
+
+	/// Whether no key is configured, leaving the API open.
+	pub fn is_empty(&self) -> bool {
+		self.hashes.is_empty()
+	}
+
+	/// Number of configured keys.
+	pub fn len(&self) -> usize {
+		self.hashes.len()
+	}
+
+	/// Returns the index of the key matching `token`, if any.
+	fn find(&self, token: &str) -> Option<usize> {
+		let hash = hash_key(token);
+		self.hashes.iter().position(|candidate| *candidate == hash)
+	}
+}
+
+fn hash_key(key: &str) -> [u8; 32] {
+	Sha256::digest(key.as_bytes()).into()
+}
+
+/// Index of the API key a request was authenticated with.
+#[derive(Debug, Clone, Copy)]
+struct AuthenticatedKey(usize);
+
+/// CORS policy of the discovery API.
+///
+/// Only the listed origins, methods and headers are allowed. A value of `"*"`
+/// allows any, but must be configured explicitly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsPolicy {
+	/// Origins allowed to submit intents, e.g. `https://app.example.com`.
+	pub allowed_origins: Vec<String>,
+	/// HTTP methods allowed in cross-origin requests.
+	pub allowed_methods: Vec<String>,
+	/// Request headers allowed in cross-origin requests.
+	pub allowed_headers: Vec<String>,
+	/// Whether credentials (cookies, authorization headers) may be sent.
+	pub credentials: bool,
+	/// How long browsers may cache preflight responses.
+	pub max_age_seconds: Option<u64>,
+}
+
+impl Default for CorsPolicy {
+	fn default() -> Self {
+		Self {
+			allowed_origins: Vec::new(),
+			allowed_methods: vec!["POST".to_string()],
+			allowed_headers: vec!["content-type".to_string(), "authorization".to_string()],
+			credentials: false,
+			max_age_seconds: None,
+		}
+	}
+}
+
+impl CorsPolicy {
+	/// Reads the policy from a `cors` configuration table.
+	///
+	/// Missing fields keep their defaults, so no origin is allowed unless listed.
+	pub fn from_config(config: &toml::Value) -> Self {
+		let strings = |field: &str| {
+			config.get(field).and_then(|v| v.as_array()).map(|arr| {
+				arr.iter()
+					.filter_map(|v| v.as_str().map(String::from))
+					.collect::<Vec<_>>()
+			})
+		};
+
+		let defaults = Self::default();
+		Self {
+			allowed_origins: strings("allowed_origins").unwrap_or(defaults.allowed_origins),
+			allowed_methods: strings("allowed_methods").unwrap_or(defaults.allowed_methods),
+			allowed_headers: strings("allowed_headers").unwrap_or(defaults.allowed_headers),
+			credentials: config
+				.get("credentials")
+				.and_then(|v| v.as_bool())
+				.unwrap_or(defaults.credentials),
+			max_age_seconds: config
+				.get("max_age_seconds")
+				.and_then(|v| v.as_integer())
+				.map(|v| v as u64),
+		}
+	}
+
+	/// Builds the CORS layer enforcing this policy.
+	///
+	/// # Errors
+	///
+	/// Returns `DiscoveryError::ValidationError` if a value is invalid, or if
+	/// credentials are allowed together with a wildcard, which browsers reject.
+	pub fn layer(&self) -> Result<CorsLayer, DiscoveryError> {
+		let is_wildcard = |values: &[String]| values.iter().any(|v| v == "*");
+		let invalid = |kind: &str, value: &str| {
+			DiscoveryError::ValidationError(format!("Invalid CORS {}: {}", kind, value))
+		};
+
+		if self.credentials
+			&& (is_wildcard(&self.allowed_origins)
+				|| is_wildcard(&self.allowed_methods)
+				|| is_wildcard(&self.allowed_headers))
+		{
+			return Err(DiscoveryError::ValidationError(
+				"CORS credentials cannot be combined with a \"*\" wildcard".to_string(),
+			));
+		}
+
+		let origins = if is_wildcard(&self.allowed_origins) {
+			AllowOrigin::any()
+		} else {
+			AllowOrigin::list(
+				self.allowed_origins
+					.iter()
+					.map(|origin| origin.parse().map_err(|_| invalid("origin", origin)))
+					.collect::<Result<Vec<_>, _>>()?,
+			)
+		};
+		let methods = if is_wildcard(&self.allowed_methods) {
+			AllowMethods::any()
+		} else {
+			AllowMethods::list(
+				self.allowed_methods
+					.iter()
+					.map(|method| {
+						method
+							.to_uppercase()
+							.parse()
+							.map_err(|_| invalid("method", method))
+					})
+					.collect::<Result<Vec<_>, _>>()?,
+			)
+		};
+		let headers = if is_wildcard(&self.allowed_headers) {
+			AllowHeaders::any()
+		} else {
+			AllowHeaders::list(
+				self.allowed_headers
+					.iter()
+					.map(|header| header.parse().map_err(|_| invalid("header", header)))
+					.collect::<Result<Vec<_>, _>>()?,
+			)
+		};
+
+		let mut layer = CorsLayer::new()
+			.allow_origin(origins)
+			.allow_methods(methods)
+			.allow_headers(headers)
+			.allow_credentials(self.credentials);
+		if let Some(max_age) = self.max_age_seconds {
+			layer = layer.max_age(Duration::from_secs(max_age));
+		}
+		Ok(layer)
+	}
+}
+
 /// Shared state for the API server.
 ///
 /// Contains all the dependencies needed by API request handlers.
@@ -220,20 +529,19 @@ struct IntentResponse {
 /// # Fields
 ///
 /// * `intent_sender` - Channel to broadcast discovered intents to the solver system
-/// * `auth_token` - Optional authentication token for API access control
 /// * `provider` - RPC provider for interacting with on-chain contracts
 /// * `networks` - Networks configuration for settler lookups
+/// * `rate_limiters` - Optional limiters on the number of intents accepted per minute
 #[derive(Clone)]
 struct ApiState {
 	/// Channel to send discovered intents
 	intent_sender: mpsc::UnboundedSender<Intent>,
-	/// Optional authentication token
-	#[allow(dead_code)]
-	auth_token: Option<String>,
 	/// RPC providers for each supported network
 	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
 	/// Networks configuration for settler lookups
 	networks: NetworksConfig,
+	/// Optional intent rate limiters
+	rate_limiters: Option<Arc<RateLimiters>>,
 }
 
 /// EIP-7683 offchain discovery implementation.
@@ -246,11 +554,16 @@ pub struct Eip7683OffchainDiscovery {
 	/// API server configuration
 	api_host: String,
 	api_port: u16,
-	auth_token: Option<String>,
+	/// API keys required to submit intents, if any
+	api_keys: Arc<ApiKeys>,
 	/// RPC providers for each supported network
 	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
 	/// Networks configuration for settler lookups
 	networks: NetworksConfig,
+	/// Maximum number of intents accepted per minute
+	rate_limit: Option<u32>,
+	/// CORS layer applied to the API
+	cors: CorsLayer,
 	/// Flag indicating if the server is running
 	is_running: Arc<AtomicBool>,
 	/// Channel for signaling server shutdown
@@ -264,9 +577,11 @@ impl Eip7683OffchainDiscovery {
 	///
 	/// * `api_host` - The host address to bind the API server
 	/// * `api_port` - The port number to listen on
-	/// * `auth_token` - Optional authentication token for API access
+	/// * `api_keys` - API keys required to submit intents, none leaving the API open
 	/// * `network_ids` - List of network IDs this discovery source supports
 	/// * `networks` - Networks configuration with RPC URLs
+	/// * `rate_limit` - Optional maximum number of intents accepted per minute
+	/// * `cors` - CORS policy of the API
 	///
 	/// # Returns
 	///
@@ -275,13 +590,15 @@ impl Eip7683OffchainDiscovery {
 	/// # Errors
 	///
 	/// Returns `DiscoveryError::Connection` if any RPC URL cannot be parsed.
-	/// Returns `DiscoveryError::ValidationError` if networks config is invalid.
+	/// Returns `DiscoveryError::ValidationError` if networks config or CORS policy is invalid.
 	pub fn new(
 		api_host: String,
 		api_port: u16,
-		auth_token: Option<String>,
+		api_keys: ApiKeys,
 		network_ids: Vec<u64>,
 		networks: &NetworksConfig,
+		rate_limit: Option<u32>,
+		cors: CorsPolicy,
 	) -> Result<Self, DiscoveryError> {
 		// Validate networks config has at least one network
 		if networks.is_empty() {
@@ -324,9 +641,11 @@ impl Eip7683OffchainDiscovery {
 		Ok(Self {
 			api_host,
 			api_port,
-			auth_token,
+			api_keys: Arc::new(api_keys),
 			providers,
 			networks: networks.clone(),
+			rate_limit,
+			cors: cors.layer()?,
 			is_running: Arc::new(AtomicBool::new(false)),
 			shutdown_signal: Arc::new(Mutex::new(None)),
 		})
@@ -362,13 +681,15 @@ impl Eip7683OffchainDiscovery {
 	/// Validates the incoming StandardOrder.
 	///
 	/// Performs validation checks on order deadlines to ensure
-	/// the order is still valid and can be processed.
+	/// the order is still valid and can be processed. The Permit2 signature
+	/// of escrow orders is verified against the sponsor, using the Permit2
+	/// deployment of the origin chain as the EIP-712 domain.
 	///
 	/// # Arguments
 	///
 	/// * `order` - The StandardOrder to validate
-	/// * `sponsor` - The sponsor address
-	/// * `signature` - The Permit2Witness signature
+	/// * `request` - The submitted request with sponsor and signature
+	/// * `state` - Shared API state with the networks configuration
 	///
 	/// # Returns
 	///
@@ -379,14 +700,12 @@ impl Eip7683OffchainDiscovery {
 	/// Returns `DiscoveryError::ValidationError` if:
 	/// - The expiry has passed
 	/// - The fill deadline has passed
-	///
-	/// # TODO
-	///
-	/// - Implement Permit2Witness signature validation
+	/// - The origin chain is unknown or has no Permit2 deployment
+	/// - The signature does not recover to the sponsor
 	async fn validate_order(
 		order: &StandardOrder,
-		_sponsor: &Address,
-		_signature: &Bytes,
+		request: &IntentRequest,
+		state: &ApiState,
 	) -> Result<(), DiscoveryError> {
 		// Check if deadlines are still valid
 		let current_time = current_timestamp() as u32;
@@ -403,8 +722,41 @@ impl Eip7683OffchainDiscovery {
 			));
 		}
 
-		// TODO: Implement Permit2Witness signature validation
-		// The signature should be validated against the Permit2 contract
+		// Resource-lock orders are authorized through TheCompact instead
+		if request.lock_type == LockType::Permit2Escrow {
+			let origin_chain_id = u64::try_from(order.originChainId).map_err(|_| {
+				DiscoveryError::ValidationError(format!(
+					"Invalid origin chain ID {}",
+					order.originChainId
+				))
+			})?;
+			let spender = state
+				.networks
+				.get(&origin_chain_id)
+				.map(|network| Address::from_slice(&network.input_settler_address.0))
+				.ok_or_else(|| {
+					DiscoveryError::ValidationError(format!(
+						"Chain ID {} not found in networks configuration",
+						origin_chain_id
+					))
+				})?;
+			let permit2 = PROTOCOL_REGISTRY
+				.get_permit2_address(origin_chain_id)
+				.ok_or_else(|| {
+					DiscoveryError::ValidationError(format!(
+						"Permit2 not deployed on chain {}",
+						origin_chain_id
+					))
+				})?;
+			verify_sponsor_signature(
+				order,
+				&request.sponsor,
+				&request.signature,
+				&spender,
+				&permit2,
+				origin_chain_id,
+			)?;
+		}
 
 		Ok(())
 	}
@@ -434,10 +786,12 @@ impl Eip7683OffchainDiscovery {
 	/// - Order ID computation fails
 	/// - Order data parsing fails
 	/// - No outputs are present in the order
+	/// - An output settler or oracle address is right-padded
 	async fn order_to_intent(
 		order_bytes: &Bytes,
 		sponsor: &Address,
 		signature: &Bytes,
+		lock_type: LockType,
 		providers: &HashMap<u64, RootProvider<Http<reqwest::Client>>>,
 		networks: &NetworksConfig,
 	) -> Result<Intent, DiscoveryError> {
@@ -445,7 +799,12 @@ impl Eip7683OffchainDiscovery {
 		let order = Self::parse_standard_order(order_bytes)?;
 
 		// Get the input settler address for the order's origin chain
-		let origin_chain_id = order.originChainId.to::<u64>();
+		let origin_chain_id = u64::try_from(order.originChainId).map_err(|_| {
+			DiscoveryError::ValidationError(format!(
+				"Invalid origin chain ID {}",
+				order.originChainId
+			))
+		})?;
 		let network = networks.get(&origin_chain_id).ok_or_else(|| {
 			DiscoveryError::ValidationError(format!(
 				"Chain ID {} not found in networks configuration",
@@ -453,12 +812,26 @@ impl Eip7683OffchainDiscovery {
 			))
 		})?;
 
-		if network.input_settler_address.0.len() != 20 {
+		// Resource-lock orders are identified by the compact input settler
+		let settler =
+			match lock_type {
+				LockType::Permit2Escrow => &network.input_settler_address,
+				LockType::ResourceLock => network
+					.input_settler_compact_address
+					.as_ref()
+					.ok_or_else(|| {
+						DiscoveryError::ValidationError(format!(
+							"No compact input settler configured for chain ID {}",
+							origin_chain_id
+						))
+					})?,
+			};
+		if settler.0.len() != 20 {
 			return Err(DiscoveryError::ValidationError(
 				"Invalid settler address length".to_string(),
 			));
 		}
-		let settler_address = Address::from_slice(&network.input_settler_address.0);
+		let settler_address = Address::from_slice(&settler.0);
 
 		// Get provider for the origin chain
 		let provider = providers.get(&origin_chain_id).ok_or_else(|| {
@@ -469,7 +842,8 @@ impl Eip7683OffchainDiscovery {
 		})?;
 
 		// Generate order ID from order data
-		let order_id = Self::compute_order_id(order_bytes, provider, settler_address).await?;
+		let order_id =
+			Self::compute_order_id(order_bytes, lock_type, provider, settler_address).await?;
 
 		// Validate that order has outputs
 		if order.outputs.is_empty() {
@@ -478,6 +852,17 @@ impl Eip7683OffchainDiscovery {
 			));
 		}
 
+		// Signed addresses are used as they are, so right-padded ones would
+		// point the fill at the wrong contracts
+		for output in &order.outputs {
+			for address in [&output.oracle.0, &output.settler.0] {
+				check_bytes32_address(address).map_err(|e| {
+					metrics::counter!(RIGHT_PADDED_ADDRESSES_COUNTER).increment(1);
+					DiscoveryError::ValidationError(e)
+				})?;
+			}
+		}
+
 		// Convert to intent format
 		let order_data = Eip7683OrderData {
 			user: with_0x_prefix(&hex::encode(order.user)),
@@ -508,6 +893,7 @@ impl Eip7683OffchainDiscovery {
 			// Include signature and sponsor
 			signature: Some(with_0x_prefix(&hex::encode(signature))),
 			sponsor: Some(sponsor.to_string()),
+			lock_type,
 		};
 
 		Ok(Intent {
@@ -518,6 +904,9 @@ impl Eip7683OffchainDiscovery {
 				requires_auction: false,
 				exclusive_until: None,
 				discovered_at: current_timestamp(),
+				tokens: Vec::new(),
+				origin_block_number: None,
+				origin_block_timestamp: None,
 			},
 			data: serde_json::to_value(&order_data).map_err(|e| {
 				DiscoveryError::ParseError(format!("Failed to serialize order data: {}", e))
@@ -529,11 +918,14 @@ impl Eip7683OffchainDiscovery {
 	/// Computes order ID from order data.
 	///
 	/// Calls the `orderIdentifier` function on the origin settler contract
-	/// to compute the canonical order ID for the given order.
+	/// to compute the canonical order ID for the given order. The escrow
+	/// settler takes the encoded order while the compact settler takes the
+	/// decoded `StandardOrder`.
 	///
 	/// # Arguments
 	///
 	/// * `order` - The order to compute ID for
+	/// * `lock_type` - How the order's inputs are secured
 	/// * `provider` - RPC provider for calling the settler contract
 	///
 	/// # Returns
@@ -545,20 +937,50 @@ impl Eip7683OffchainDiscovery {
 	/// Returns `DiscoveryError::Connection` if the contract call fails.
 	async fn compute_order_id(
 		order_bytes: &Bytes,
+		lock_type: LockType,
 		provider: &RootProvider<Http<reqwest::Client>>,
 		settler_address: Address,
 	) -> Result<[u8; 32], DiscoveryError> {
-		let settler = IInputSettlerEscrow::new(settler_address, provider);
+		let order_id = match lock_type {
+			LockType::Permit2Escrow => IInputSettlerEscrow::new(settler_address, provider)
+				.orderIdentifier(order_bytes.clone())
+				.call()
+				.await
+				.map(|id| id._0),
+			LockType::ResourceLock => {
+				let order = Self::parse_standard_order(order_bytes)?;
+				IInputSettlerCompact::new(settler_address, provider)
+					.orderIdentifier(order)
+					.call()
+					.await
+					.map(|id| id._0)
+			},
+		}
+		.map_err(|e| {
+			DiscoveryError::Connection(format!("Failed to get order ID from contract: {}", e))
+		})?;
 
-		let order_id = settler
-			.orderIdentifier(order_bytes.clone())
-			.call()
-			.await
-			.map_err(|e| {
-				DiscoveryError::Connection(format!("Failed to get order ID from contract: {}", e))
-			})?;
+		Ok(order_id.0)
+	}
 
-		Ok(order_id._0.0)
+	/// Parses, validates and converts a submitted request into an intent.
+	async fn request_to_intent(
+		request: &IntentRequest,
+		state: &ApiState,
+	) -> Result<Intent, DiscoveryError> {
+		let order = Self::parse_standard_order(&request.order)?;
+		Self::validate_order(&order, request, state).await?;
+		let mut intent = Self::order_to_intent(
+			&request.order,
+			&request.sponsor,
+			&request.signature,
+			request.lock_type,
+			&state.providers,
+			&state.networks,
+		)
+		.await?;
+		intent.metadata.requires_auction = request.requires_auction;
+		Ok(intent)
 	}
 
 	/// Main API server task.
@@ -571,9 +993,11 @@ impl Eip7683OffchainDiscovery {
 	/// * `api_host` - Host address to bind to
 	/// * `api_port` - Port number to listen on
 	/// * `intent_sender` - Channel to send discovered intents
-	/// * `auth_token` - Optional authentication token
+	/// * `api_keys` - API keys required to submit intents
 	/// * `provider` - RPC provider for contract calls
 	/// * `networks` - Networks configuration for settler lookups
+	/// * `rate_limit` - Optional maximum number of intents accepted per minute
+	/// * `cors` - CORS layer applied to all routes
 	/// * `shutdown_rx` - Channel to receive shutdown signal
 	///
 	/// # Errors
@@ -582,26 +1006,33 @@ impl Eip7683OffchainDiscovery {
 	/// - The address cannot be parsed
 	/// - The TCP listener cannot bind to the address
 	/// - The server encounters a fatal error
+	#[allow(clippy::too_many_arguments)]
 	async fn run_server(
 		api_host: String,
 		api_port: u16,
 		intent_sender: mpsc::UnboundedSender<Intent>,
-		auth_token: Option<String>,
+		api_keys: Arc<ApiKeys>,
 		providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
 		networks: NetworksConfig,
+		rate_limit: Option<u32>,
+		cors: CorsLayer,
 		mut shutdown_rx: mpsc::Receiver<()>,
 	) -> Result<(), String> {
 		let state = ApiState {
 			intent_sender,
-			auth_token,
 			providers,
 			networks,
+			rate_limiters: rate_limit
+				.map(|limit| Arc::new(RateLimiters::new(limit, api_keys.len()))),
 		};
 
-		let app = Router::new()
+		let mut app = Router::new()
 			.route("/intent", post(handle_intent_submission))
-			.layer(CorsLayer::permissive())
-			.with_state(state);
+			.route("/intents/batch", post(handle_batch_intent_submission));
+		if !api_keys.is_empty() {
+			app = app.route_layer(middleware::from_fn_with_state(api_keys, authenticate));
+		}
+		let app = app.layer(cors).with_state(state);
 
 		let addr = format!("{}:{}", api_host, api_port)
 			.parse::<SocketAddr>()
@@ -613,18 +1044,65 @@ impl Eip7683OffchainDiscovery {
 
 		tracing::info!("EIP-7683 offchain discovery API listening on {}", addr);
 
-		axum::serve(listener, app)
-			.with_graceful_shutdown(async move {
-				let _ = shutdown_rx.recv().await;
-				tracing::info!("Shutting down API server");
-			})
-			.await
-			.map_err(|e| format!("Server error: {}", e))?;
+		axum::serve(
+			listener,
+			app.into_make_service_with_connect_info::<SocketAddr>(),
+		)
+		.with_graceful_shutdown(async move {
+			let _ = shutdown_rx.recv().await;
+			tracing::info!("Shutting down API server");
+		})
+		.await
+		.map_err(|e| format!("Server error: {}", e))?;
 
 		Ok(())
 	}
 }
 
+/// Rejects requests without a valid API key.
+///
+/// Expects an `Authorization: Bearer <key>` header matching one of the
+/// configured keys, and records the matched key for rate limiting.
+///
+/// # Returns
+///
+/// Returns 401 Unauthorized if the header is missing or the key is invalid.
+async fn authenticate(
+	State(api_keys): State<Arc<ApiKeys>>,
+	ConnectInfo(peer): ConnectInfo<SocketAddr>,
+	mut request: Request,
+	next: Next,
+) -> Response {
+	let token = request
+		.headers()
+		.get(AUTHORIZATION)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.strip_prefix("Bearer "));
+	let Some(token) = token else {
+		tracing::warn!(source_ip = %peer.ip(), "Rejected request without API key");
+		return unauthorized("Missing bearer token");
+	};
+
+	match api_keys.find(token.trim()) {
+		Some(index) => {
+			request.extensions_mut().insert(AuthenticatedKey(index));
+			next.run(request).await
+		},
+		None => {
+			tracing::warn!(source_ip = %peer.ip(), "Rejected request with invalid API key");
+			unauthorized("Invalid API key")
+		},
+	}
+}
+
+fn unauthorized(message: &str) -> Response {
+	(
+		StatusCode::UNAUTHORIZED,
+		Json(serde_json::json!({ "error": message })),
+	)
+		.into_response()
+}
+
 /// Handles intent submission requests.
 ///
 /// This is the main request handler for the POST /intent endpoint.
@@ -634,6 +1112,7 @@ impl Eip7683OffchainDiscovery {
 /// # Arguments
 ///
 /// * `state` - Shared API state containing dependencies
+/// * `key` - API key the request was authenticated with, if any
 /// * `request` - The intent submission request
 ///
 /// # Returns
@@ -641,6 +1120,8 @@ impl Eip7683OffchainDiscovery {
 /// Returns an HTTP response with:
 /// - 200 OK with order_id on success
 /// - 400 Bad Request if validation fails
+/// - 401 Unauthorized if API keys are configured and none was given
+/// - 429 Too Many Requests if the intent rate limit is exceeded
 /// - 500 Internal Server Error if processing fails
 ///
 /// # Response Format
@@ -654,12 +1135,25 @@ impl Eip7683OffchainDiscovery {
 /// ```
 async fn handle_intent_submission(
 	State(state): State<ApiState>,
+	key: Option<Extension<AuthenticatedKey>>,
 	Json(request): Json<IntentRequest>,
 ) -> impl IntoResponse {
-	// TODO: Implement authentication
-	// if let Some(token) = &state.auth_token {
-	//     // Check Authorization header
-	// }
+	if let Some(limiters) = &state.rate_limiters {
+		if !limiters
+			.for_caller(key.map(|Extension(key)| key))
+			.try_acquire(1)
+		{
+			return (
+				StatusCode::TOO_MANY_REQUESTS,
+				Json(IntentResponse {
+					order_id: String::new(),
+					status: "error".to_string(),
+					message: Some("Intent rate limit exceeded".to_string()),
+				}),
+			)
+				.into_response();
+		}
+	}
 
 	// Parse the StandardOrder from bytes
 	let order = match Eip7683OffchainDiscovery::parse_standard_order(&request.order) {
@@ -678,9 +1172,7 @@ async fn handle_intent_submission(
 	};
 
 	// Validate order
-	if let Err(e) =
-		Eip7683OffchainDiscovery::validate_order(&order, &request.sponsor, &request.signature).await
-	{
+	if let Err(e) = Eip7683OffchainDiscovery::validate_order(&order, &request, &state).await {
 		return (
 			StatusCode::BAD_REQUEST,
 			Json(IntentResponse {
@@ -697,13 +1189,15 @@ async fn handle_intent_submission(
 		&request.order,
 		&request.sponsor,
 		&request.signature,
+		request.lock_type,
 		&state.providers,
 		&state.networks,
 	)
 	.await
 	{
-		Ok(intent) => {
+		Ok(mut intent) => {
 			let order_id = intent.id.clone();
+			intent.metadata.requires_auction = request.requires_auction;
 
 			// Send intent through channel
 			if let Err(e) = state.intent_sender.send(intent) {
@@ -740,6 +1234,209 @@ async fn handle_intent_submission(
 	}
 }
 
+/// Handles batch intent submission requests.
+///
+/// This is the request handler for the POST /intents/batch endpoint. All
+/// intents are validated and converted before any is sent to the solver, and
+/// each intent succeeds or fails independently of the others.
+///
+/// # Returns
+///
+/// Returns an HTTP response with:
+/// - 207 Multi-Status with per-intent results
+/// - 400 Bad Request if the batch exceeds the maximum size
+/// - 401 Unauthorized if API keys are configured and none was given
+/// - 429 Too Many Requests if the batch would exceed the intent rate limit
+///
+/// # Response Format
+///
+/// ```json
+/// {
+///   "successes": [{ "orderId": "0x...", "status": "success" }],
+///   "failures": [{ "index": 1, "error": "error message" }]
+/// }
+/// ```
+async fn handle_batch_intent_submission(
+	State(state): State<ApiState>,
+	key: Option<Extension<AuthenticatedKey>>,
+	Json(requests): Json<Vec<IntentRequest>>,
+) -> impl IntoResponse {
+	if requests.len() > MAX_BATCH_SIZE {
+		return (
+			StatusCode::BAD_REQUEST,
+			Json(serde_json::json!({
+				"error": format!("Batch size {} exceeds maximum of {}", requests.len(), MAX_BATCH_SIZE)
+			})),
+		)
+			.into_response();
+	}
+
+	if let Some(limiters) = &state.rate_limiters {
+		let limiter = limiters.for_caller(key.map(|Extension(key)| key));
+		if !limiter.try_acquire(requests.len() as u32) {
+			return (
+				StatusCode::TOO_MANY_REQUESTS,
+				Json(serde_json::json!({
+					"error": "Intent rate limit exceeded"
+				})),
+			)
+				.into_response();
+		}
+	}
+
+	// Validate every intent before sending any to the solver
+	let mut results = Vec::with_capacity(requests.len());
+	for request in &requests {
+		results.push(Eip7683OffchainDiscovery::request_to_intent(request, &state).await);
+	}
+
+	let (intents, failures) = partition_batch_results(results);
+	let response = dispatch_batch(intents, failures, &state.intent_sender);
+
+	(StatusCode::MULTI_STATUS, Json(response)).into_response()
+}
+
+/// Splits per-intent results into valid intents and indexed failures.
+fn partition_batch_results(
+	results: Vec<Result<Intent, DiscoveryError>>,
+) -> (Vec<(usize, Intent)>, Vec<BatchIntentFailure>) {
+	let mut intents = Vec::new();
+	let mut failures = Vec::new();
+	for (index, result) in results.into_iter().enumerate() {
+		match result {
+			Ok(intent) => intents.push((index, intent)),
+			Err(e) => failures.push(BatchIntentFailure {
+				index,
+				error: e.to_string(),
+			}),
+		}
+	}
+	(intents, failures)
+}
+
+/// Sends validated intents to the solver and builds the batch response.
+fn dispatch_batch(
+	intents: Vec<(usize, Intent)>,
+	mut failures: Vec<BatchIntentFailure>,
+	sender: &mpsc::UnboundedSender<Intent>,
+) -> BatchIntentResponse {
+	let mut successes = Vec::with_capacity(intents.len());
+	for (index, intent) in intents {
+		let order_id = intent.id.clone();
+		match sender.send(intent) {
+			Ok(()) => successes.push(BatchIntentSuccess {
+				order_id,
+				status: "success".to_string(),
+			}),
+			Err(e) => failures.push(BatchIntentFailure {
+				index,
+				error: format!("Failed to process intent: {}", e),
+			}),
+		}
+	}
+	failures.sort_by_key(|failure| failure.index);
+
+	BatchIntentResponse {
+		successes,
+		failures,
+	}
+}
+
+/// Computes the EIP-712 digest of the Permit2 batch-witness transfer
+/// authorizing an escrow order.
+///
+/// The input settler is the spender, and the order's nonce and fill deadline
+/// are the Permit2 nonce and deadline. The domain is the Permit2 contract on
+/// the origin chain.
+fn permit2_digest(
+	order: &StandardOrder,
+	spender: &Address,
+	permit2: &Address,
+	chain_id: u64,
+) -> B256 {
+	let mandate_output_type_hash = keccak256(MANDATE_OUTPUT_TYPE.as_bytes());
+	let mut output_hashes = Vec::with_capacity(order.outputs.len() * 32);
+	for output in &order.outputs {
+		let mut enc = Eip712AbiEncoder::new();
+		enc.push_b256(&mandate_output_type_hash);
+		enc.push_b256(&output.oracle);
+		enc.push_b256(&output.settler);
+		enc.push_u256(output.chainId);
+		enc.push_b256(&output.token);
+		enc.push_u256(output.amount);
+		enc.push_b256(&output.recipient);
+		enc.push_b256(&keccak256(&output.call));
+		enc.push_b256(&keccak256(&output.context));
+		output_hashes.extend_from_slice(keccak256(enc.finish()).as_slice());
+	}
+
+	let mut enc = Eip712AbiEncoder::new();
+	enc.push_b256(&keccak256(
+		format!("{}{}", PERMIT2_WITNESS_TYPE, MANDATE_OUTPUT_TYPE).as_bytes(),
+	));
+	enc.push_u32(order.expires);
+	enc.push_address(&order.inputOracle);
+	enc.push_b256(&keccak256(output_hashes));
+	let witness_hash = keccak256(enc.finish());
+
+	let token_permissions_type_hash = keccak256(TOKEN_PERMISSIONS_TYPE.as_bytes());
+	let mut permission_hashes = Vec::with_capacity(order.inputs.len() * 32);
+	for [token, amount] in &order.inputs {
+		let mut enc = Eip712AbiEncoder::new();
+		enc.push_b256(&token_permissions_type_hash);
+		enc.push_u256(*token);
+		enc.push_u256(*amount);
+		permission_hashes.extend_from_slice(keccak256(enc.finish()).as_slice());
+	}
+
+	let mut enc = Eip712AbiEncoder::new();
+	enc.push_b256(&keccak256(
+		format!(
+			"{}{}{}{}",
+			PERMIT_BATCH_WITNESS_TYPE,
+			MANDATE_OUTPUT_TYPE,
+			PERMIT2_WITNESS_TYPE,
+			TOKEN_PERMISSIONS_TYPE
+		)
+		.as_bytes(),
+	));
+	enc.push_b256(&keccak256(permission_hashes));
+	enc.push_address(spender);
+	enc.push_u256(order.nonce);
+	enc.push_u256(U256::from(order.fillDeadline));
+	enc.push_b256(&witness_hash);
+	let struct_hash = keccak256(enc.finish());
+
+	let domain_hash = compute_domain_hash(NAME_PERMIT2, chain_id, permit2);
+	compute_final_digest(&domain_hash, &struct_hash)
+}
+
+/// Verifies that the Permit2 signature of an escrow order was made by the
+/// sponsor.
+///
+/// # Errors
+///
+/// Returns `DiscoveryError::ValidationError("invalid sponsor signature")` if
+/// the signature is malformed or recovers to another address.
+fn verify_sponsor_signature(
+	order: &StandardOrder,
+	sponsor: &Address,
+	signature: &Bytes,
+	spender: &Address,
+	permit2: &Address,
+	chain_id: u64,
+) -> Result<(), DiscoveryError> {
+	let invalid = || DiscoveryError::ValidationError("invalid sponsor signature".to_string());
+	let digest = permit2_digest(order, spender, permit2, chain_id);
+	let signer = alloy_signer::Signature::try_from(signature.as_ref())
+		.and_then(|signature| signature.recover_address_from_prehash(&digest))
+		.map_err(|_| invalid())?;
+	if signer != *sponsor {
+		return Err(invalid());
+	}
+	Ok(())
+}
+
 /// Configuration schema for EIP-7683 off-chain discovery service.
 ///
 /// This schema validates the configuration for the off-chain discovery API,
@@ -753,7 +1450,10 @@ async fn handle_intent_submission(
 ///
 /// # Optional Fields
 ///
-/// - `auth_token` - Authentication token string for API access
+/// - `auth_token` - API key required as a bearer token
+/// - `auth_tokens` - List of API keys, any of which is accepted
+/// - `rate_limit` - Maximum number of intents accepted per minute
+/// - `signature_domain` - EIP-712 domain of Permit2 sponsor signatures
 pub struct Eip7683OffchainDiscoverySchema;
 
 impl Eip7683OffchainDiscoverySchema {
@@ -762,43 +1462,94 @@ impl Eip7683OffchainDiscoverySchema {
 		let instance = Self;
 		instance.validate(config)
 	}
-}
 
-impl ConfigSchema for Eip7683OffchainDiscoverySchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema = Schema::new(
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
 			// Required fields
 			vec![
-				Field::new("api_host", FieldType::String),
+				Field::new("api_host", FieldType::String)
+					.with_description("Address the intent API listens on"),
 				Field::new(
 					"api_port",
 					FieldType::Integer {
 						min: Some(1),
 						max: Some(65535),
 					},
-				),
+				)
+				.with_description("Port the intent API listens on"),
 				Field::new(
 					"network_ids",
 					FieldType::Array(Box::new(FieldType::Integer {
 						min: Some(1),
 						max: None,
 					})),
-				),
+				)
+				.with_description("Networks intents are accepted for"),
 			],
 			// Optional fields
 			vec![
-				Field::new("auth_token", FieldType::String),
+				Field::new("auth_token", FieldType::String)
+					.with_description("API key required to submit intents"),
+				Field::new("auth_tokens", FieldType::Array(Box::new(FieldType::String)))
+					.with_description("API keys accepted in addition to auth_token"),
 				Field::new(
 					"rate_limit",
 					FieldType::Integer {
 						min: Some(1),
 						max: Some(10000),
 					},
-				),
+				)
+				.with_description("Maximum number of intents accepted per minute"),
+				Field::new(
+					"cors",
+					FieldType::Table(Schema::new(
+						vec![],
+						vec![
+							Field::new(
+								"allowed_origins",
+								FieldType::Array(Box::new(FieldType::String)),
+							)
+							.with_description("Origins allowed to call the API, none by default"),
+							Field::new(
+								"allowed_methods",
+								FieldType::Array(Box::new(FieldType::String)),
+							)
+							.with_description("HTTP methods allowed in cross-origin requests")
+							.with_default(vec!["POST"]),
+							Field::new(
+								"allowed_headers",
+								FieldType::Array(Box::new(FieldType::String)),
+							)
+							.with_description("Headers allowed in cross-origin requests")
+							.with_default(vec!["content-type", "authorization"]),
+							Field::new("credentials", FieldType::Boolean)
+								.with_description("Allow cross-origin requests with credentials")
+								.with_default(false),
+							Field::new(
+								"max_age_seconds",
+								FieldType::Integer {
+									min: Some(0),
+									max: None,
+								},
+							)
+							.with_description("Seconds browsers may cache preflight responses"),
+						],
+					)),
+				)
+				.with_description("CORS policy of the intent API"),
 			],
-		);
+		)
+	}
+}
+
+impl ConfigSchema for Eip7683OffchainDiscoverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
 
-		schema.validate(config)
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -822,18 +1573,22 @@ impl DiscoveryInterface for Eip7683OffchainDiscovery {
 		// Spawn API server task
 		let api_host = self.api_host.clone();
 		let api_port = self.api_port;
-		let auth_token = self.auth_token.clone();
+		let api_keys = self.api_keys.clone();
 		let providers = self.providers.clone();
 		let networks = self.networks.clone();
+		let rate_limit = self.rate_limit;
+		let cors = self.cors.clone();
 
 		tokio::spawn(async move {
 			if let Err(e) = Self::run_server(
 				api_host,
 				api_port,
 				sender,
-				auth_token,
+				api_keys,
 				providers,
 				networks,
+				rate_limit,
+				cors,
 				shutdown_rx,
 			)
 			.await
@@ -885,8 +1640,17 @@ impl DiscoveryInterface for Eip7683OffchainDiscovery {
 /// ```toml
 /// api_host = "0.0.0.0"         # optional, defaults to "0.0.0.0"
 /// api_port = 8081              # optional, defaults to 8081
-/// auth_token = "secret"        # optional
+/// auth_token = "secret"        # optional, required bearer token
+/// auth_tokens = ["k1", "k2"]   # optional, any listed key is accepted
 /// network_ids = [1, 10, 137]  # optional, defaults to all networks
+/// rate_limit = 600             # optional, intents per minute
+///
+/// [cors]                       # optional, no cross-origin requests by default
+/// allowed_origins = ["https://app.example.com"]
+/// allowed_methods = ["POST"]   # optional, defaults to ["POST"]
+/// allowed_headers = ["content-type", "authorization"]
+/// credentials = false          # optional
+/// max_age_seconds = 3600       # optional
 /// ```
 ///
 /// # Errors
@@ -913,10 +1677,7 @@ pub fn create_discovery(
 		.and_then(|v| v.as_integer())
 		.unwrap_or(8081) as u16;
 
-	let auth_token = config
-		.get("auth_token")
-		.and_then(|v| v.as_str())
-		.map(String::from);
+	let api_keys = ApiKeys::from_config(config);
 
 	// Get network_ids from config, or default to all networks
 	let network_ids = config
@@ -927,16 +1688,33 @@ pub fn create_discovery(
 				.filter_map(|v| v.as_integer().map(|i| i as u64))
 				.collect::<Vec<_>>()
 		})
-		.unwrap_or_else(|| networks.keys().cloned().collect());
-
-	let discovery =
-		Eip7683OffchainDiscovery::new(api_host, api_port, auth_token, network_ids, networks)
-			.map_err(|e| {
-				DiscoveryError::Connection(format!(
-					"Failed to create offchain discovery service: {}",
-					e
-				))
-			})?;
+		.unwrap_or_else(|| networks.keys().map(|chain_id| chain_id.as_u64()).collect());
+
+	let rate_limit = config
+		.get("rate_limit")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u32);
+
+	let cors = config
+		.get("cors")
+		.map(CorsPolicy::from_config)
+		.unwrap_or_default();
+
+	let discovery = Eip7683OffchainDiscovery::new(
+		api_host,
+		api_port,
+		api_keys,
+		network_ids,
+		networks,
+		rate_limit,
+		cors,
+	)
+	.map_err(|e| {
+		DiscoveryError::Connection(format!(
+			"Failed to create offchain discovery service: {}",
+			e
+		))
+	})?;
 
 	Ok(Box::new(discovery))
 }
@@ -951,6 +1729,383 @@ impl ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_discovery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683OffchainDiscoverySchema)
+	}
 }
 
 impl crate::DiscoveryRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn test_intent(id: &str) -> Intent {
+		Intent {
+			id: id.to_string(),
+			source: "off-chain".to_string(),
+			standard: "eip7683".to_string(),
+			metadata: IntentMetadata {
+				requires_auction: false,
+				exclusive_until: None,
+				discovered_at: 0,
+				tokens: Vec::new(),
+				origin_block_number: None,
+				origin_block_timestamp: None,
+			},
+			data: serde_json::json!({}),
+			quote_id: None,
+		}
+	}
+
+	#[test]
+	fn test_invalid_intent_does_not_block_batch() {
+		let results = vec![
+			Ok(test_intent("first")),
+			Err(DiscoveryError::ValidationError(
+				"Order has expired".to_string(),
+			)),
+			Ok(test_intent("third")),
+		];
+
+		let (intents, failures) = partition_batch_results(results);
+		let (sender, mut receiver) = mpsc::unbounded_channel();
+		let response = dispatch_batch(intents, failures, &sender);
+
+		let order_ids: Vec<_> = response
+			.successes
+			.iter()
+			.map(|s| s.order_id.as_str())
+			.collect();
+		assert_eq!(order_ids, vec!["first", "third"]);
+		assert_eq!(response.failures.len(), 1);
+		assert_eq!(response.failures[0].index, 1);
+
+		assert_eq!(receiver.try_recv().unwrap().id, "first");
+		assert_eq!(receiver.try_recv().unwrap().id, "third");
+		assert!(receiver.try_recv().is_err());
+	}
+
+	#[test]
+	fn test_closed_channel_reports_failures() {
+		let (sender, receiver) = mpsc::unbounded_channel();
+		drop(receiver);
+
+		let (intents, failures) = partition_batch_results(vec![Ok(test_intent("first"))]);
+		let response = dispatch_batch(intents, failures, &sender);
+
+		assert!(response.successes.is_empty());
+		assert_eq!(response.failures[0].index, 0);
+	}
+
+	#[test]
+	fn test_lock_type_defaults_to_escrow() {
+		let request: IntentRequest = serde_json::from_value(serde_json::json!({
+			"order": "0x00",
+			"sponsor": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+			"signature": "0x00",
+		}))
+		.unwrap();
+		assert_eq!(request.lock_type, LockType::Permit2Escrow);
+
+		let request: IntentRequest = serde_json::from_value(serde_json::json!({
+			"order": "0x00",
+			"sponsor": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+			"signature": "0x00",
+			"lockType": "resource-lock",
+		}))
+		.unwrap();
+		assert_eq!(request.lock_type, LockType::ResourceLock);
+	}
+
+	#[test]
+	fn test_requires_auction_defaults_to_false() {
+		let request: IntentRequest = serde_json::from_value(serde_json::json!({
+			"order": "0x00",
+			"sponsor": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+			"signature": "0x00",
+		}))
+		.unwrap();
+		assert!(!request.requires_auction);
+
+		let request: IntentRequest = serde_json::from_value(serde_json::json!({
+			"order": "0x00",
+			"sponsor": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+			"signature": "0x00",
+			"requiresAuction": true,
+		}))
+		.unwrap();
+		assert!(request.requires_auction);
+	}
+
+	#[test]
+	fn test_rate_limiter_counts_intents() {
+		let limiter = IntentRateLimiter::new(10);
+		assert!(limiter.try_acquire(8));
+		// A batch that would exceed the limit is rejected as a whole
+		assert!(!limiter.try_acquire(3));
+		assert!(limiter.try_acquire(2));
+		assert!(!limiter.try_acquire(1));
+	}
+
+	#[test]
+	fn test_api_keys_rate_limited_independently() {
+		let limiters = RateLimiters::new(2, 2);
+		assert!(limiters.for_caller(None).try_acquire(2));
+		assert!(!limiters.for_caller(None).try_acquire(1));
+		assert!(limiters
+			.for_caller(Some(AuthenticatedKey(0)))
+			.try_acquire(2));
+		assert!(limiters
+			.for_caller(Some(AuthenticatedKey(1)))
+			.try_acquire(2));
+		assert!(!limiters
+			.for_caller(Some(AuthenticatedKey(1)))
+			.try_acquire(1));
+	}
+
+	#[tokio::test]
+	async fn test_authentication_accepts_any_configured_key() {
+		use axum::{body::Body, extract::connect_info::MockConnectInfo, http::Request};
+		use tower::ServiceExt;
+
+		let config: toml::Value = toml::from_str(
+			r#"
+			auth_token = "old-key"
+			auth_tokens = ["new-key"]
+			"#,
+		)
+		.unwrap();
+		let api_keys = Arc::new(ApiKeys::from_config(&config));
+		let app = Router::new()
+			.route("/intent", post(|| async { StatusCode::OK }))
+			.route_layer(middleware::from_fn_with_state(api_keys, authenticate))
+			.layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 1234))));
+
+		let status = |authorization: Option<&'static str>| {
+			let app = app.clone();
+			async move {
+				let mut request = Request::post("/intent");
+				if let Some(authorization) = authorization {
+					request = request.header(AUTHORIZATION, authorization);
+				}
+				app.oneshot(request.body(Body::empty()).unwrap())
+					.await
+					.unwrap()
+					.status()
+			}
+		};
+		assert_eq!(status(Some("Bearer old-key")).await, StatusCode::OK);
+		assert_eq!(status(Some("Bearer new-key")).await, StatusCode::OK);
+		assert_eq!(
+			status(Some("Bearer other-key")).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_eq!(status(Some("new-key")).await, StatusCode::UNAUTHORIZED);
+		assert_eq!(status(None).await, StatusCode::UNAUTHORIZED);
+	}
+
+	#[test]
+	fn test_cors_policy_wildcard_must_be_explicit() {
+		let config: toml::Value = toml::from_str(
+			r#"
+			allowed_origins = ["https://app.example.com"]
+			credentials = true
+			max_age_seconds = 600
+			"#,
+		)
+		.unwrap();
+		let policy = CorsPolicy::from_config(&config);
+		assert_eq!(policy.allowed_origins, vec!["https://app.example.com"]);
+		assert_eq!(policy.allowed_methods, vec!["POST"]);
+		assert!(policy.credentials);
+		assert_eq!(policy.max_age_seconds, Some(600));
+		assert!(policy.layer().is_ok());
+
+		let wildcard = CorsPolicy {
+			allowed_origins: vec!["*".to_string()],
+			..policy
+		};
+		assert!(wildcard.layer().is_err());
+		assert!(CorsPolicy {
+			credentials: false,
+			..wildcard
+		}
+		.layer()
+		.is_ok());
+
+		let invalid = CorsPolicy {
+			allowed_methods: vec!["NOT A METHOD".to_string()],
+			..CorsPolicy::default()
+		};
+		assert!(invalid.layer().is_err());
+	}
+
+	/// Order signed in the Permit2 test vectors.
+	fn vector_order(sponsor: Address) -> StandardOrder {
+		StandardOrder {
+			user: sponsor,
+			nonce: U256::from(1),
+			originChainId: U256::from(31337),
+			expires: u32::MAX,
+			fillDeadline: u32::MAX,
+			inputOracle: Address::repeat_byte(0x11),
+			inputs: vec![[U256::from(0x22), U256::from(1_000_000)]],
+			outputs: vec![SolMandateOutput {
+				oracle: B256::repeat_byte(0x33),
+				settler: B256::repeat_byte(0x44),
+				chainId: U256::from(31338),
+				token: B256::repeat_byte(0x55),
+				amount: U256::from(990_000),
+				recipient: B256::left_padding_from(sponsor.as_slice()),
+				call: Bytes::new(),
+				context: Bytes::new(),
+			}],
+		}
+	}
+
+	/// First default Foundry/Anvil account.
+	const SPONSOR: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+	const SPENDER: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+	const PERMIT2: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+	#[test]
+	fn test_permit2_digest_matches_eip712() {
+		use alloy_sol_types::{eip712_domain, SolStruct};
+
+		sol! {
+			struct TokenPermissions {
+				address token;
+				uint256 amount;
+			}
+
+			struct MandateOutput {
+				bytes32 oracle;
+				bytes32 settler;
+				uint256 chainId;
+				bytes32 token;
+				uint256 amount;
+				bytes32 recipient;
+				bytes call;
+				bytes context;
+			}
+
+			struct Permit2Witness {
+				uint32 expires;
+				address inputOracle;
+				MandateOutput[] outputs;
+			}
+
+			struct PermitBatchWitnessTransferFrom {
+				TokenPermissions[] permitted;
+				address spender;
+				uint256 nonce;
+				uint256 deadline;
+				Permit2Witness witness;
+			}
+		}
+
+		let sponsor: Address = SPONSOR.parse().unwrap();
+		let spender: Address = SPENDER.parse().unwrap();
+		let permit2: Address = PERMIT2.parse().unwrap();
+		let order = vector_order(sponsor);
+
+		let permit = PermitBatchWitnessTransferFrom {
+			permitted: order
+				.inputs
+				.iter()
+				.map(|[token, amount]| TokenPermissions {
+					token: Address::from_word(B256::from(*token)),
+					amount: *amount,
+				})
+				.collect(),
+			spender,
+			nonce: order.nonce,
+			deadline: U256::from(order.fillDeadline),
+			witness: Permit2Witness {
+				expires: order.expires,
+				inputOracle: order.inputOracle,
+				outputs: order
+					.outputs
+					.iter()
+					.map(|output| MandateOutput {
+						oracle: output.oracle,
+						settler: output.settler,
+						chainId: output.chainId,
+						token: output.token,
+						amount: output.amount,
+						recipient: output.recipient,
+						call: output.call.clone(),
+						context: output.context.clone(),
+					})
+					.collect(),
+			},
+		};
+		let domain = eip712_domain! {
+			name: "Permit2",
+			chain_id: 31337,
+			verifying_contract: permit2,
+		};
+
+		assert_eq!(
+			permit2_digest(&order, &spender, &permit2, 31337),
+			permit.eip712_signing_hash(&domain)
+		);
+	}
+
+	#[test]
+	fn test_verify_sponsor_signature() {
+		let sponsor: Address = SPONSOR.parse().unwrap();
+		let spender: Address = SPENDER.parse().unwrap();
+		let permit2: Address = PERMIT2.parse().unwrap();
+		let mut order = vector_order(sponsor);
+
+		// Signature of the first Anvil key over the digest, as produced by
+		// `cast wallet sign --no-hash <digest>`
+		let digest = permit2_digest(&order, &spender, &permit2, 31337);
+		assert_eq!(
+			digest,
+			"0x8ae03e161630d56094639493b30538fc434229d336bfd4c946a553bee98d1ce7"
+				.parse::<B256>()
+				.unwrap()
+		);
+		let signature: Bytes = "0xde9b5de98cabe3ef331f1d83ea7d678acce72930f554b5128bd7a3e2749b3a5178a1dd8c91765103b7d9540fdcc90aef161f7f22c7fc6faa185bae92fecb66dc1c"
+			.parse()
+			.unwrap();
+		assert!(
+			verify_sponsor_signature(&order, &sponsor, &signature, &spender, &permit2, 31337)
+				.is_ok()
+		);
+
+		let expect_invalid = |result: Result<(), DiscoveryError>| match result {
+			Err(DiscoveryError::ValidationError(msg)) => {
+				assert_eq!(msg, "invalid sponsor signature")
+			},
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		};
+		let other = Address::repeat_byte(0x66);
+		expect_invalid(verify_sponsor_signature(
+			&order, &other, &signature, &spender, &permit2, 31337,
+		));
+		expect_invalid(verify_sponsor_signature(
+			&order,
+			&sponsor,
+			&Bytes::from_static(&[0u8; 3]),
+			&spender,
+			&permit2,
+			31337,
+		));
+		// Signed for another chain's Permit2 domain
+		expect_invalid(verify_sponsor_signature(
+			&order, &sponsor, &signature, &spender, &permit2, 1,
+		));
+
+		order.outputs[0].amount = U256::from(1);
+		expect_invalid(verify_sponsor_signature(
+			&order, &sponsor, &signature, &spender, &permit2, 31337,
+		));
+	}
+}
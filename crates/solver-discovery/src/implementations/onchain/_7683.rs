@@ -4,24 +4,26 @@
 //! currently supporting on-chain EIP-7683 event monitoring using the Alloy library.
 
 use crate::{DiscoveryError, DiscoveryInterface};
-use alloy_primitives::{Address as AlloyAddress, Log as PrimLog, LogData};
+use alloy_primitives::Address as AlloyAddress;
 use alloy_provider::{Provider, ProviderBuilder, RootProvider};
 use alloy_pubsub::PubSubFrontend;
-use alloy_rpc_types::{Filter, Log};
+use alloy_rpc_types::{BlockNumberOrTag, BlockTransactionsKind, Filter, Log};
 use alloy_sol_types::{sol, SolEvent, SolValue};
+use alloy_transport::Transport;
 use alloy_transport_http::Http;
 use alloy_transport_ws::WsConnect;
 use async_trait::async_trait;
 use futures::StreamExt;
+use solver_settlement::utils::log_parser::LogParser;
 use solver_types::current_timestamp;
 use solver_types::{
 	standards::eip7683::{GasLimitOverrides, MandateOutput},
 	with_0x_prefix, ConfigSchema, Eip7683OrderData, Field, FieldType, Intent, IntentMetadata,
-	NetworksConfig, Schema,
+	NetworksConfig, Schema, SchemaDefinition,
 };
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use tokio::sync::{broadcast, mpsc, Mutex};
 use tokio::task::JoinHandle;
 
@@ -58,6 +60,9 @@ sol! {
 	event Open(bytes32 indexed orderId, bytes order);
 }
 
+/// Parser for the events monitored by this discovery source.
+static LOG_PARSER: LazyLock<LogParser> = LazyLock::new(|| LogParser::new().register::<Open>());
+
 const DEFAULT_POLLING_INTERVAL_SECS: u64 = 3;
 const MAX_POLLING_INTERVAL_SECS: u64 = 300;
 
@@ -201,16 +206,11 @@ impl Eip7683Discovery {
 	/// Decodes the EIP-7683 event data and converts it into the internal
 	/// Intent format used by the solver.
 	fn parse_open_event(log: &Log) -> Result<Intent, DiscoveryError> {
-		// Convert RPC log to primitives log for decoding
-		let prim_log = PrimLog {
-			address: log.address(),
-			data: LogData::new_unchecked(log.topics().to_vec(), log.data().data.clone()),
-		};
-
 		// Decode the Open event
-		let open_event = Open::decode_log(&prim_log, true).map_err(|e| {
-			DiscoveryError::ParseError(format!("Failed to decode Open event: {}", e))
-		})?;
+		let open_event = LOG_PARSER
+			.parse_log(log)
+			.and_then(|parsed| parsed.into_event::<Open>())
+			.ok_or_else(|| DiscoveryError::ParseError("Failed to decode Open event".to_string()))?;
 
 		let order_id = open_event.orderId;
 		let order_bytes = &open_event.order;
@@ -257,6 +257,7 @@ impl Eip7683Discovery {
 			raw_order_data: Some(with_0x_prefix(&hex::encode(order_bytes))),
 			signature: None,
 			sponsor: None,
+			lock_type: Default::default(),
 		};
 
 		Ok(Intent {
@@ -267,6 +268,9 @@ impl Eip7683Discovery {
 				requires_auction: false,
 				exclusive_until: None,
 				discovered_at: current_timestamp(),
+				tokens: Vec::new(),
+				origin_block_number: log.block_number,
+				origin_block_timestamp: log.block_timestamp,
 			},
 			data: serde_json::to_value(&order_data).map_err(|e| {
 				DiscoveryError::ParseError(format!("Failed to serialize order data: {}", e))
@@ -277,20 +281,62 @@ impl Eip7683Discovery {
 
 	/// Process discovered logs into intents and send them.
 	///
-	/// Common logic for both polling and subscription modes.
-	fn process_discovered_logs(
+	/// Common logic for both polling and subscription modes. Nodes that do not
+	/// include the block timestamp in logs have it fetched from the block header,
+	/// once per block.
+	async fn process_discovered_logs<T, P>(
+		provider: &P,
 		logs: Vec<Log>,
 		sender: &mpsc::UnboundedSender<Intent>,
 		chain_id: u64,
-	) {
+	) where
+		T: Transport + Clone,
+		P: Provider<T>,
+	{
+		let mut block_timestamps: HashMap<u64, Option<u64>> = HashMap::new();
 		for log in logs {
-			if let Ok(intent) = Self::parse_open_event(&log) {
+			if let Ok(mut intent) = Self::parse_open_event(&log) {
+				let metadata = &mut intent.metadata;
+				if let (Some(block_number), None) = (
+					metadata.origin_block_number,
+					metadata.origin_block_timestamp,
+				) {
+					metadata.origin_block_timestamp = match block_timestamps.get(&block_number) {
+						Some(timestamp) => *timestamp,
+						None => {
+							let timestamp = Self::block_timestamp(provider, block_number).await;
+							block_timestamps.insert(block_number, timestamp);
+							timestamp
+						},
+					};
+				}
 				tracing::info!(chain = chain_id, "Discovered intent: {}", intent.id);
 				let _ = sender.send(intent);
 			}
 		}
 	}
 
+	/// Fetches the timestamp of a block, or `None` if it cannot be retrieved.
+	async fn block_timestamp<T, P>(provider: &P, block_number: u64) -> Option<u64>
+	where
+		T: Transport + Clone,
+		P: Provider<T>,
+	{
+		match provider
+			.get_block_by_number(
+				BlockNumberOrTag::Number(block_number),
+				BlockTransactionsKind::Hashes,
+			)
+			.await
+		{
+			Ok(block) => block.map(|block| block.header.timestamp),
+			Err(e) => {
+				tracing::warn!(block = block_number, "Failed to get block timestamp: {}", e);
+				None
+			},
+		}
+	}
+
 	/// Polling-based monitoring for a single chain.
 	///
 	/// Periodically polls the blockchain for new Open events and sends
@@ -368,7 +414,7 @@ impl Eip7683Discovery {
 					};
 
 					// Process discovered logs
-					Self::process_discovered_logs(logs, &sender, chain_id);
+					Self::process_discovered_logs(&provider, logs, &sender, chain_id).await;
 
 					// Update last block for this chain
 					last_blocks.lock().await.insert(chain_id, current_block);
@@ -433,7 +479,7 @@ impl Eip7683Discovery {
 			tokio::select! {
 				Some(log) = stream.next() => {
 					// Process single log as it arrives
-					Self::process_discovered_logs(vec![log], &sender, chain_id);
+					Self::process_discovered_logs(&provider, vec![log], &sender, chain_id).await;
 				}
 				_ = stop_rx.recv() => {
 					tracing::info!(chain = chain_id, "Stopping WebSocket monitor");
@@ -457,11 +503,10 @@ impl Eip7683DiscoverySchema {
 		let instance = Self;
 		instance.validate(config)
 	}
-}
 
-impl ConfigSchema for Eip7683DiscoverySchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema = Schema::new(
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
 			// Required fields
 			vec![Field::new(
 				"network_ids",
@@ -470,6 +515,7 @@ impl ConfigSchema for Eip7683DiscoverySchema {
 					max: None,
 				})),
 			)
+			.with_description("Networks whose input settlers are monitored for new orders")
 			.with_validator(|value| {
 				if let Some(arr) = value.as_array() {
 					if arr.is_empty() {
@@ -487,10 +533,20 @@ impl ConfigSchema for Eip7683DiscoverySchema {
 					min: Some(0),                                // 0 = WebSocket mode
 					max: Some(MAX_POLLING_INTERVAL_SECS as i64), // Maximum 5 minutes
 				},
-			)],
-		);
+			)
+			.with_description("Seconds between log polls, or 0 to subscribe over WebSocket")
+			.with_default(DEFAULT_POLLING_INTERVAL_SECS as i64)],
+		)
+	}
+}
 
-		schema.validate(config)
+impl ConfigSchema for Eip7683DiscoverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -650,6 +706,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_discovery
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683DiscoverySchema)
+	}
 }
 
 impl crate::DiscoveryRegistry for Registry {}
@@ -5,12 +5,19 @@
 //! on-chain event monitoring, off-chain APIs, or other intent implementations.
 
 use async_trait::async_trait;
+use enrichment::IntentEnricher;
+use futures::future::join_all;
+use health::DiscoveryHealthMonitor;
 use solver_types::{ConfigSchema, ImplementationRegistry, Intent, NetworksConfig};
 use std::collections::HashMap;
+use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::mpsc;
 
 /// Re-export implementations
+pub mod enrichment;
+pub mod health;
+
 pub mod implementations {
 	pub mod onchain {
 		pub mod _7683;
@@ -111,6 +118,34 @@ pub fn get_all_implementations() -> Vec<(&'static str, DiscoveryFactory)> {
 	]
 }
 
+/// Get the configuration schemas of all registered discovery implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_implementations`].
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{offchain, onchain};
+
+	vec![
+		(
+			onchain::_7683::Registry::NAME,
+			onchain::_7683::Registry::config_schema(),
+		),
+		(
+			offchain::_7683::Registry::NAME,
+			offchain::_7683::Registry::config_schema(),
+		),
+	]
+}
+
+/// Outcome of starting the discovery implementations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryStartReport {
+	/// Number of implementations that started monitoring.
+	pub started_count: usize,
+	/// Names of the implementations that failed to start, sorted.
+	pub failed_sources: Vec<String>,
+}
+
 /// Service that manages multiple intent discovery implementations.
 ///
 /// The DiscoveryService coordinates multiple discovery implementations, allowing
@@ -118,6 +153,8 @@ pub fn get_all_implementations() -> Vec<(&'static str, DiscoveryFactory)> {
 pub struct DiscoveryService {
 	/// Map of implementation names to their interfaces.
 	implementations: HashMap<String, Box<dyn DiscoveryInterface>>,
+	/// Enrichers run in order on every discovered intent.
+	enrichers: Arc<Vec<Box<dyn IntentEnricher>>>,
 }
 
 impl DiscoveryService {
@@ -125,7 +162,42 @@ impl DiscoveryService {
 	///
 	/// Each implementation will be monitored independently when monitoring is started.
 	pub fn new(implementations: HashMap<String, Box<dyn DiscoveryInterface>>) -> Self {
-		Self { implementations }
+		Self {
+			implementations,
+			enrichers: Arc::new(Vec::new()),
+		}
+	}
+
+	/// Sets the enrichers run on every intent received from the implementations.
+	pub fn with_enrichers(mut self, enrichers: Vec<Box<dyn IntentEnricher>>) -> Self {
+		self.enrichers = Arc::new(enrichers);
+		self
+	}
+
+	/// Wraps a sender so intents are enriched before being forwarded to it.
+	///
+	/// Returns the sender unchanged when no enrichers are configured.
+	fn enriching_sender(
+		&self,
+		sender: mpsc::UnboundedSender<Intent>,
+	) -> mpsc::UnboundedSender<Intent> {
+		if self.enrichers.is_empty() {
+			return sender;
+		}
+
+		let (enriching_tx, mut enriching_rx) = mpsc::unbounded_channel();
+		let enrichers = self.enrichers.clone();
+
+		tokio::spawn(async move {
+			while let Some(mut intent) = enriching_rx.recv().await {
+				enrichment::run_pipeline(&enrichers, &mut intent).await;
+				if sender.send(intent).is_err() {
+					break;
+				}
+			}
+		});
+
+		enriching_tx
 	}
 
 	/// Gets a specific discovery implementation by name.
@@ -147,16 +219,91 @@ impl DiscoveryService {
 	/// Starts monitoring on all configured discovery implementations.
 	///
 	/// All discovered intents from any implementation will be sent through the
-	/// provided channel. If any implementation fails to start, the entire operation
-	/// fails and no implementations will be monitoring.
+	/// provided channel. Implementations are started concurrently; those that
+	/// fail to start are logged and reported, and the operation only fails if
+	/// every implementation failed.
 	pub async fn start_all(
 		&self,
 		sender: mpsc::UnboundedSender<Intent>,
-	) -> Result<(), DiscoveryError> {
-		for implementation in self.implementations.values() {
-			implementation.start_monitoring(sender.clone()).await?;
+	) -> Result<DiscoveryStartReport, DiscoveryError> {
+		let sender = self.enriching_sender(sender);
+		self.start_concurrently(|_| sender.clone()).await
+	}
+
+	/// Starts monitoring on all implementations, tracking activity of monitored sources.
+	///
+	/// Sources covered by the health monitor receive a tracked sender so that
+	/// the intents they deliver are recorded as activity. Failures are handled
+	/// as in [`Self::start_all`].
+	pub async fn start_all_with_health(
+		&self,
+		sender: mpsc::UnboundedSender<Intent>,
+		monitor: &Arc<DiscoveryHealthMonitor>,
+	) -> Result<DiscoveryStartReport, DiscoveryError> {
+		let sender = self.enriching_sender(sender);
+		self.start_concurrently(|name| {
+			if monitor.is_monitored(name) {
+				monitor.track(name, sender.clone())
+			} else {
+				sender.clone()
+			}
+		})
+		.await
+	}
+
+	/// Starts every implementation concurrently with the sender returned for
+	/// its name, collecting which ones failed.
+	async fn start_concurrently(
+		&self,
+		sender_for: impl Fn(&str) -> mpsc::UnboundedSender<Intent>,
+	) -> Result<DiscoveryStartReport, DiscoveryError> {
+		let results = join_all(self.implementations.iter().map(|(name, implementation)| {
+			let sender = sender_for(name);
+			async move { (name, implementation.start_monitoring(sender).await) }
+		}))
+		.await;
+
+		let mut report = DiscoveryStartReport::default();
+		for (name, result) in results {
+			match result {
+				Ok(()) => report.started_count += 1,
+				Err(e) => {
+					tracing::warn!(source = %name, error = %e, "Failed to start discovery source");
+					report.failed_sources.push(name.clone());
+				},
+			}
 		}
-		Ok(())
+		report.failed_sources.sort();
+
+		if report.started_count == 0 && !report.failed_sources.is_empty() {
+			return Err(DiscoveryError::Connection("all sources failed".to_string()));
+		}
+		Ok(report)
+	}
+
+	/// Restarts a single discovery implementation with the given sender.
+	///
+	/// Stops the implementation before starting it again, which lets sources
+	/// re-establish dropped connections or subscriptions.
+	pub async fn restart(
+		&self,
+		implementation_name: &str,
+		sender: mpsc::UnboundedSender<Intent>,
+	) -> Result<(), DiscoveryError> {
+		let implementation = self
+			.implementations
+			.get(implementation_name)
+			.ok_or_else(|| {
+				DiscoveryError::ValidationError(format!(
+					"Unknown discovery implementation: {}",
+					implementation_name
+				))
+			})?;
+
+		implementation.stop_monitoring().await?;
+		implementation
+			.start_monitoring(self.enriching_sender(sender))
+			.await
 	}
 
 	/// Stops monitoring on all active discovery implementations.
@@ -171,3 +318,72 @@ impl DiscoveryService {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Discovery source whose start either succeeds or fails.
+	struct StubDiscovery {
+		fails: bool,
+	}
+
+	#[async_trait]
+	impl DiscoveryInterface for StubDiscovery {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn start_monitoring(
+			&self,
+			_sender: mpsc::UnboundedSender<Intent>,
+		) -> Result<(), DiscoveryError> {
+			if self.fails {
+				Err(DiscoveryError::Connection("unreachable".to_string()))
+			} else {
+				Ok(())
+			}
+		}
+
+		async fn stop_monitoring(&self) -> Result<(), DiscoveryError> {
+			Ok(())
+		}
+	}
+
+	fn service(sources: &[(&str, bool)]) -> DiscoveryService {
+		DiscoveryService::new(
+			sources
+				.iter()
+				.map(|(name, fails)| {
+					(
+						name.to_string(),
+						Box::new(StubDiscovery { fails: *fails }) as Box<dyn DiscoveryInterface>,
+					)
+				})
+				.collect(),
+		)
+	}
+
+	#[tokio::test]
+	async fn test_start_all_tolerates_partial_failure() {
+		let (tx, _rx) = mpsc::unbounded_channel();
+
+		let report = service(&[("a", false), ("b", true), ("c", false)])
+			.start_all(tx.clone())
+			.await
+			.unwrap();
+		assert_eq!(
+			report,
+			DiscoveryStartReport {
+				started_count: 2,
+				failed_sources: vec!["b".to_string()],
+			}
+		);
+
+		let err = service(&[("a", true), ("b", true)])
+			.start_all(tx)
+			.await
+			.unwrap_err();
+		assert!(matches!(err, DiscoveryError::Connection(_)));
+	}
+}
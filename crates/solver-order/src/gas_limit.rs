@@ -0,0 +1,175 @@
+//! Gas limit selection for fill transactions.
+//!
+//! Fill transactions without a gas limit are estimated by the delivery
+//! service on submission. Complex fills may exceed that estimate, so each
+//! order implementation can configure how the gas limit of its fills is set,
+//! and execution parameters can override it per order.
+
+use crate::OrderError;
+use serde::{Deserialize, Serialize};
+use solver_types::{ExecutionParams, Transaction, TransactionTrace};
+
+/// Strategy for setting the gas limit of fill transactions.
+///
+/// Configured per order implementation as `gas_limit_strategy`:
+///
+/// ```toml
+/// gas_limit_strategy = "estimate"
+/// gas_limit_strategy = { static = 500000 }
+/// gas_limit_strategy = { estimate_with_buffer = 1.2 }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasLimitStrategy {
+	/// Use the gas estimate of the delivery service.
+	#[default]
+	Estimate,
+	/// Use a fixed gas limit.
+	Static(u64),
+	/// Multiply the gas estimate by a factor, e.g. 1.2 for a 20% buffer.
+	EstimateWithBuffer(f64),
+}
+
+impl GasLimitStrategy {
+	/// Parses the `gas_limit_strategy` of an order implementation config,
+	/// defaulting to [`GasLimitStrategy::Estimate`].
+	pub fn from_config(config: &toml::Value) -> Result<Self, OrderError> {
+		let Some(value) = config.get("gas_limit_strategy") else {
+			return Ok(Self::default());
+		};
+		let strategy: Self = value
+			.clone()
+			.try_into()
+			.map_err(|e| OrderError::InvalidOrder(format!("Invalid gas_limit_strategy: {}", e)))?;
+		if let Self::EstimateWithBuffer(factor) = strategy {
+			if !(factor >= 1.0 && factor.is_finite()) {
+				return Err(OrderError::InvalidOrder(
+					"gas_limit_strategy buffer must be at least 1.0".to_string(),
+				));
+			}
+		}
+		Ok(strategy)
+	}
+}
+
+/// Sets the gas limit of a fill transaction.
+///
+/// The `gas_limit_override` of the execution parameters takes precedence,
+/// followed by a gas limit already set by the order itself. Otherwise the
+/// strategy applies, buffering the estimate of the fill simulation, if any.
+/// Buffered estimates that cannot be computed leave the gas limit to the
+/// delivery service.
+pub fn apply_gas_limit(
+	tx: &mut Transaction,
+	params: &ExecutionParams,
+	strategy: GasLimitStrategy,
+	simulation: Option<&TransactionTrace>,
+) {
+	if let Some(gas_limit) = params.gas_limit_override {
+		tracing::debug!(gas_limit, "Using gas limit override");
+		tx.gas_limit = Some(gas_limit);
+		return;
+	}
+	if let Some(gas_limit) = tx.gas_limit {
+		tracing::debug!(gas_limit, "Using gas limit set by order");
+		return;
+	}
+
+	match strategy {
+		GasLimitStrategy::Estimate => {
+			tracing::debug!("Using gas estimate");
+		},
+		GasLimitStrategy::Static(gas_limit) => {
+			tracing::debug!(gas_limit, "Using static gas limit");
+			tx.gas_limit = Some(gas_limit);
+		},
+		GasLimitStrategy::EstimateWithBuffer(factor) => match simulation {
+			Some(trace) => match trace.gas_used {
+				Some(estimate) => {
+					let gas_limit = (estimate as f64 * factor).ceil() as u64;
+					tracing::debug!(estimate, gas_limit, "Using buffered gas estimate");
+					tx.gas_limit = Some(gas_limit);
+				},
+				None => {
+					tracing::debug!(
+						revert_reason = ?trace.revert_reason,
+						"Fill estimate reverted, using gas estimate"
+					);
+				},
+			},
+			None => {
+				tracing::debug!("Fill was not simulated, using gas estimate");
+			},
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tx() -> Transaction {
+		Transaction {
+			to: None,
+			data: Vec::new(),
+			value: Default::default(),
+			chain_id: 1,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		}
+	}
+
+	#[test]
+	fn test_gas_limit_strategies() {
+		let config: toml::Value =
+			toml::from_str("gas_limit_strategy = { estimate_with_buffer = 1.2 }").unwrap();
+		let strategy = GasLimitStrategy::from_config(&config).unwrap();
+		assert_eq!(strategy, GasLimitStrategy::EstimateWithBuffer(1.2));
+		let config: toml::Value = toml::from_str("gas_limit_strategy = \"estimate\"").unwrap();
+		assert_eq!(
+			GasLimitStrategy::from_config(&config).unwrap(),
+			GasLimitStrategy::Estimate
+		);
+
+		let mut params = ExecutionParams {
+			gas_price: Default::default(),
+			priority_fee: None,
+			gas_limit_override: None,
+			output_source: Default::default(),
+		};
+		let simulation = TransactionTrace {
+			success: true,
+			gas_used: Some(100_000),
+			return_data: "0x".to_string(),
+			revert_reason: None,
+			logs: Vec::new(),
+		};
+
+		let mut buffered = tx();
+		apply_gas_limit(&mut buffered, &params, strategy, Some(&simulation));
+		assert_eq!(buffered.gas_limit, Some(120_000));
+
+		let mut unsimulated = tx();
+		apply_gas_limit(&mut unsimulated, &params, strategy, None);
+		assert_eq!(unsimulated.gas_limit, None);
+
+		let mut estimated = tx();
+		apply_gas_limit(
+			&mut estimated,
+			&params,
+			GasLimitStrategy::Estimate,
+			Some(&simulation),
+		);
+		assert_eq!(estimated.gas_limit, None);
+
+		params.gas_limit_override = Some(500_000);
+		let mut overridden = tx();
+		apply_gas_limit(&mut overridden, &params, strategy, Some(&simulation));
+		assert_eq!(overridden.gas_limit, Some(500_000));
+	}
+}
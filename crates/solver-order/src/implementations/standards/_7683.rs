@@ -4,14 +4,19 @@
 //! for EIP-7683 cross-chain orders, including transaction generation for
 //! filling and claiming orders.
 
-use crate::{OrderError, OrderInterface};
+use crate::swap::{self, OutputSwapper};
+use crate::version_registry::{intent_version, DEFAULT_STANDARD_VERSION};
+use crate::{GasLimitStrategy, OrderError, OrderInterface, SwapQuote, TransactionSimulator};
 use alloy_primitives::{Address as AlloyAddress, FixedBytes, U256};
 use alloy_sol_types::{sol, SolCall, SolValue};
 use async_trait::async_trait;
 use solver_types::{
-	oracle::OracleRoutes, Address, ConfigSchema, Eip7683OrderData, ExecutionParams, FillProof,
-	Intent, NetworksConfig, Order, OrderStatus, Schema, Transaction,
+	bytes32_to_address, oracle::OracleRouteSource, standards::eip7683::LockType, with_0x_prefix,
+	Address, ConfigSchema, Eip7683OrderData, ExecutionParams, FillProof, Intent, NetworksConfig,
+	Order, OrderStatus, OutputTokenSource, Schema, SchemaDefinition, SimulatedOutput,
+	SimulationResult, Transaction,
 };
+use std::sync::Arc;
 
 // Solidity type definitions for EIP-7683 contract interactions.
 sol! {
@@ -63,6 +68,11 @@ sol! {
 		function open(bytes calldata order) external;
 		function openFor(bytes calldata order, address sponsor, bytes calldata signature) external;
 	}
+
+	/// IInputSettlerCompact interface for orders locked in TheCompact.
+	interface IInputSettlerCompact {
+		function finalise(OrderStruct order, bytes signatures, uint32[] timestamps, bytes32[] solvers, bytes32 destination, bytes call) external;
+	}
 }
 
 /// EIP-7683 order implementation.
@@ -86,8 +96,13 @@ sol! {
 pub struct Eip7683OrderImpl {
 	/// Networks configuration for dynamic settler address lookups.
 	networks: NetworksConfig,
-	/// Oracle routes for validation of input/output oracle compatibility.
-	oracle_routes: OracleRoutes,
+	/// Source of the current oracle routes for validation of input/output
+	/// oracle compatibility.
+	oracle_routes: Arc<dyn OracleRouteSource>,
+	/// Strategy for setting the gas limit of fill transactions.
+	gas_limit_strategy: GasLimitStrategy,
+	/// Buys output tokens for fills sourced from a DEX, if configured.
+	output_swapper: Option<OutputSwapper>,
 }
 
 impl Eip7683OrderImpl {
@@ -96,8 +111,11 @@ impl Eip7683OrderImpl {
 	/// # Arguments
 	///
 	/// * `networks` - Networks configuration with settler addresses
-	/// * `oracle_routes` - Oracle routes for validation
-	pub fn new(networks: NetworksConfig, oracle_routes: OracleRoutes) -> Result<Self, OrderError> {
+	/// * `oracle_routes` - Source of the oracle routes for validation
+	pub fn new(
+		networks: NetworksConfig,
+		oracle_routes: Arc<dyn OracleRouteSource>,
+	) -> Result<Self, OrderError> {
 		// Validate that networks config has at least 2 networks
 		if networks.len() < 2 {
 			return Err(OrderError::ValidationFailed(
@@ -108,8 +126,22 @@ impl Eip7683OrderImpl {
 		Ok(Self {
 			networks,
 			oracle_routes,
+			gas_limit_strategy: GasLimitStrategy::default(),
+			output_swapper: None,
 		})
 	}
+
+	/// Sets the strategy for setting the gas limit of fill transactions.
+	pub fn with_gas_limit_strategy(mut self, gas_limit_strategy: GasLimitStrategy) -> Self {
+		self.gas_limit_strategy = gas_limit_strategy;
+		self
+	}
+
+	/// Sets the swapper buying output tokens for fills sourced from a DEX.
+	pub fn with_output_swapper(mut self, output_swapper: Option<OutputSwapper>) -> Self {
+		self.output_swapper = output_swapper;
+		self
+	}
 }
 
 /// Configuration schema for EIP-7683 order implementation.
@@ -127,6 +159,8 @@ impl Eip7683OrderImpl {
 /// ```toml
 /// output_settler_address = "0x..."  # 42-char hex address
 /// input_settler_address = "0x..."   # 42-char hex address
+/// gas_limit_strategy = { estimate_with_buffer = 1.2 }  # optional
+/// output_swap = { funding_tokens = { 10 = "0x..." } }  # optional
 /// ```
 pub struct Eip7683OrderSchema;
 
@@ -136,18 +170,25 @@ impl Eip7683OrderSchema {
 		let instance = Self;
 		instance.validate(config)
 	}
-}
 
-impl ConfigSchema for Eip7683OrderSchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema = Schema::new(
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
 			// Required fields
 			vec![],
 			// Optional fields
-			vec![],
-		);
+			vec![swap::config_field()],
+		)
+	}
+}
+
+impl ConfigSchema for Eip7683OrderSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
 
-		schema.validate(config)
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -157,6 +198,15 @@ impl OrderInterface for Eip7683OrderImpl {
 		Box::new(Eip7683OrderSchema)
 	}
 
+	fn gas_limit_strategy(&self) -> GasLimitStrategy {
+		self.gas_limit_strategy
+	}
+
+	fn supports_standard(&self, standard: &str, version: u8) -> bool {
+		standard == <Registry as solver_types::ImplementationRegistry>::NAME
+			&& version == DEFAULT_STANDARD_VERSION
+	}
+
 	/// Validates an EIP-7683 intent and converts it to an order.
 	///
 	/// Performs validation checks to ensure the intent is a valid EIP-7683 order
@@ -182,7 +232,7 @@ impl OrderInterface for Eip7683OrderImpl {
 		intent: &Intent,
 		solver_address: &Address,
 	) -> Result<Order, OrderError> {
-		if intent.standard != "eip7683" {
+		if !self.supports_standard(&intent.standard, intent_version(intent)) {
 			return Err(OrderError::ValidationFailed(
 				"Not an EIP-7683 order".to_string(),
 			));
@@ -205,6 +255,7 @@ impl OrderInterface for Eip7683OrderImpl {
 		}
 
 		// Validate oracle routes
+		let oracle_routes = self.oracle_routes.oracle_routes();
 		let origin_chain = order_data.origin_chain_id.to::<u64>();
 		let input_oracle = solver_types::utils::parse_address(&order_data.input_oracle)
 			.map_err(|e| OrderError::ValidationFailed(format!("Invalid input oracle: {}", e)))?;
@@ -215,11 +266,7 @@ impl OrderInterface for Eip7683OrderImpl {
 		};
 
 		// Check if the input oracle is supported
-		if !self
-			.oracle_routes
-			.supported_routes
-			.contains_key(&input_info)
-		{
+		if !oracle_routes.supported_routes.contains_key(&input_info) {
 			return Err(OrderError::ValidationFailed(format!(
 				"Input oracle {} on chain {} is not supported",
 				order_data.input_oracle, origin_chain
@@ -227,16 +274,16 @@ impl OrderInterface for Eip7683OrderImpl {
 		}
 
 		// Get supported output oracles for this input oracle
-		let supported_outputs = self
-			.oracle_routes
-			.supported_routes
-			.get(&input_info)
-			.ok_or_else(|| {
-				OrderError::ValidationFailed(format!(
-					"No routes configured for input oracle {} on chain {}",
-					order_data.input_oracle, origin_chain
-				))
-			})?;
+		let supported_outputs =
+			oracle_routes
+				.supported_routes
+				.get(&input_info)
+				.ok_or_else(|| {
+					OrderError::ValidationFailed(format!(
+						"No routes configured for input oracle {} on chain {}",
+						order_data.input_oracle, origin_chain
+					))
+				})?;
 
 		// Early validation: Check if the specific routes exist
 		let supported_destinations: std::collections::HashSet<u64> =
@@ -323,6 +370,7 @@ impl OrderInterface for Eip7683OrderImpl {
 			status: OrderStatus::Created,
 			execution_params: None,
 			prepare_tx_hash: None,
+			commit_tx_hash: None,
 			fill_tx_hash: None,
 			claim_tx_hash: None,
 			fill_proof: None,
@@ -367,6 +415,11 @@ impl OrderInterface for Eip7683OrderImpl {
 				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
 			})?;
 
+		// Resource-locked inputs are already deposited in TheCompact
+		if order_data.lock_type == LockType::ResourceLock {
+			return Ok(None);
+		}
+
 		let raw_order_data = order_data.raw_order_data.as_ref().ok_or_else(|| {
 			OrderError::ValidationFailed("Missing raw order data for off-chain order".to_string())
 		})?;
@@ -426,6 +479,8 @@ impl OrderInterface for Eip7683OrderImpl {
 			gas_price: None,
 			max_fee_per_gas: None,
 			max_priority_fee_per_gas: None,
+			memo: Some(order_data.order_id.to_vec()),
+			authorization_list: None,
 		}))
 	}
 
@@ -453,7 +508,8 @@ impl OrderInterface for Eip7683OrderImpl {
 	async fn generate_fill_transaction(
 		&self,
 		order: &Order,
-		_params: &ExecutionParams,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
 	) -> Result<Transaction, OrderError> {
 		let order_data: Eip7683OrderData =
 			serde_json::from_value(order.data.clone()).map_err(|e| {
@@ -514,6 +570,47 @@ impl OrderInterface for Eip7683OrderImpl {
 		}
 		.abi_encode();
 
+		if let OutputTokenSource::AmmSwap {
+			dex,
+			max_slippage_bps,
+		} = params.output_source
+		{
+			let swapper = self.output_swapper.as_ref().ok_or_else(|| {
+				OrderError::ValidationFailed("Output swaps are not configured".to_string())
+			})?;
+			let solver = AlloyAddress::from_slice(&order.solver_address.0);
+			let mut calls = swapper
+				.swap_calls(
+					dex,
+					max_slippage_bps,
+					dest_chain_id,
+					AlloyAddress::from_slice(&output.token[12..]),
+					output.amount,
+					solver,
+					simulator,
+				)
+				.await?;
+			calls.push(swap::call(
+				AlloyAddress::from_slice(&output_settler_address.0),
+				fill_data,
+			));
+
+			// The gas limit of the order only covers the fill itself
+			return Ok(Transaction {
+				to: Some(order.solver_address.clone()),
+				data: swap::execute(calls),
+				value: U256::ZERO,
+				chain_id: dest_chain_id,
+				nonce: None,
+				gas_limit: None,
+				gas_price: None,
+				max_fee_per_gas: None,
+				max_priority_fee_per_gas: None,
+				memo: Some(order_data.order_id.to_vec()),
+				authorization_list: None,
+			});
+		}
+
 		Ok(Transaction {
 			to: Some(output_settler_address),
 			data: fill_data,
@@ -524,9 +621,58 @@ impl OrderInterface for Eip7683OrderImpl {
 			gas_price: None,
 			max_fee_per_gas: None,
 			max_priority_fee_per_gas: None,
+			memo: Some(order_data.order_id.to_vec()),
+			authorization_list: None,
 		})
 	}
 
+	/// Quotes the funding tokens sold to buy the filled output of an order
+	/// sourced from a DEX.
+	async fn quote_output_swap(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<Option<SwapQuote>, OrderError> {
+		let OutputTokenSource::AmmSwap {
+			dex,
+			max_slippage_bps,
+		} = params.output_source
+		else {
+			return Ok(None);
+		};
+		let swapper = self.output_swapper.as_ref().ok_or_else(|| {
+			OrderError::ValidationFailed("Output swaps are not configured".to_string())
+		})?;
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+		let output = order_data
+			.outputs
+			.iter()
+			.find(|o| o.chain_id != order_data.origin_chain_id)
+			.ok_or_else(|| {
+				OrderError::ValidationFailed("No cross-chain output found".to_string())
+			})?;
+
+		let chain_id = u64::try_from(output.chain_id).map_err(|_| {
+			OrderError::ValidationFailed(format!("Invalid output chain ID {}", output.chain_id))
+		})?;
+
+		swapper
+			.quote(
+				dex,
+				max_slippage_bps,
+				chain_id,
+				AlloyAddress::from_slice(&output.token[12..]),
+				output.amount,
+				simulator,
+			)
+			.await
+			.map(Some)
+	}
+
 	/// Generates a transaction to claim rewards for a filled order on the origin chain.
 	///
 	/// Creates a transaction that calls the origin settler's `finaliseSelf()` function
@@ -547,11 +693,18 @@ impl OrderInterface for Eip7683OrderImpl {
 	/// - Order data cannot be parsed
 	/// - Order is a same-chain order (not supported)
 	/// - Address parsing fails
+	/// - The fill proof is not an oracle attested proof
 	async fn generate_claim_transaction(
 		&self,
 		order: &Order,
 		fill_proof: &FillProof,
 	) -> Result<Transaction, OrderError> {
+		let FillProof::Standard(fill_proof) = fill_proof else {
+			return Err(OrderError::ValidationFailed(
+				"EIP-7683 claims require an oracle attested fill proof".to_string(),
+			));
+		};
+
 		let order_data: Eip7683OrderData =
 			serde_json::from_value(order.data.clone()).map_err(|e| {
 				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
@@ -658,32 +811,73 @@ impl OrderInterface for Eip7683OrderImpl {
 		// Empty call data for simple finalisation
 		let call = vec![];
 
-		// Encode the finalise call
-		let call_data = IInputSettlerEscrow::finaliseCall {
-			order: order_struct,
-			timestamps,
-			solvers,
-			destination,
-			call: call.into(),
-		}
-		.abi_encode();
-
-		// Get the input settler address for the order's origin chain
+		// Get the input settler of the order's origin chain
 		let origin_chain_id = *order
 			.input_chain_ids
 			.first()
 			.ok_or_else(|| OrderError::ValidationFailed("No input chains in order".into()))?;
-		let input_settler_address = self
-			.networks
-			.get(&origin_chain_id)
-			.ok_or_else(|| {
-				OrderError::ValidationFailed(format!(
-					"Chain ID {} not found in networks configuration",
-					order_data.origin_chain_id
-				))
-			})?
-			.input_settler_address
-			.clone();
+		let network = self.networks.get(&origin_chain_id).ok_or_else(|| {
+			OrderError::ValidationFailed(format!(
+				"Chain ID {} not found in networks configuration",
+				order_data.origin_chain_id
+			))
+		})?;
+
+		// Resource locks are claimed through the compact input settler with
+		// the sponsor's and allocator's signatures
+		let (input_settler_address, call_data) = match order_data.lock_type {
+			LockType::Permit2Escrow => (
+				network.input_settler_address.clone(),
+				IInputSettlerEscrow::finaliseCall {
+					order: order_struct,
+					timestamps,
+					solvers,
+					destination,
+					call: call.into(),
+				}
+				.abi_encode(),
+			),
+			LockType::ResourceLock => {
+				let settler = network
+					.input_settler_compact_address
+					.clone()
+					.ok_or_else(|| {
+						OrderError::ValidationFailed(format!(
+							"No compact input settler configured for chain {}",
+							origin_chain_id
+						))
+					})?;
+				let sponsor_signature = order_data
+					.signature
+					.as_deref()
+					.map(|signature| hex::decode(signature.trim_start_matches("0x")))
+					.transpose()
+					.map_err(|e| OrderError::ValidationFailed(format!("Invalid signature: {}", e)))?
+					.unwrap_or_default();
+				let allocator_data = fill_proof.attestation_data.clone().ok_or_else(|| {
+					OrderError::ValidationFailed(
+						"Resource-lock claims require the allocator attestation".to_string(),
+					)
+				})?;
+				let signatures = (
+					alloy_primitives::Bytes::from(sponsor_signature),
+					alloy_primitives::Bytes::from(allocator_data),
+				)
+					.abi_encode_params();
+				(
+					settler,
+					IInputSettlerCompact::finaliseCall {
+						order: order_struct,
+						signatures: signatures.into(),
+						timestamps,
+						solvers,
+						destination,
+						call: call.into(),
+					}
+					.abi_encode(),
+				)
+			},
+		};
 
 		Ok(Transaction {
 			to: Some(input_settler_address),
@@ -695,6 +889,50 @@ impl OrderInterface for Eip7683OrderImpl {
 			gas_price: None,
 			max_fee_per_gas: None,
 			max_priority_fee_per_gas: None,
+			memo: Some(order_data.order_id.to_vec()),
+			authorization_list: None,
+		})
+	}
+
+	/// Simulates the fill transaction for an EIP-7683 order.
+	///
+	/// Executes the fill with `eth_call` through the given simulator and reports
+	/// the cross-chain output the fill delivers.
+	async fn simulate_fill_transaction(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<SimulationResult, OrderError> {
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		let tx = self
+			.generate_fill_transaction(order, params, simulator)
+			.await?;
+		let trace = simulator.simulate(&tx).await?;
+
+		// The fill transaction delivers the first cross-chain output
+		let expected_outputs = order_data
+			.outputs
+			.iter()
+			.find(|o| o.chain_id != order_data.origin_chain_id)
+			.map(|output| SimulatedOutput {
+				chain_id: output.chain_id.to::<u64>(),
+				token: with_0x_prefix(&bytes32_to_address(&output.token)),
+				recipient: with_0x_prefix(&bytes32_to_address(&output.recipient)),
+				amount: output.amount.to_string(),
+			})
+			.into_iter()
+			.collect();
+
+		Ok(SimulationResult {
+			chain_id: tx.chain_id,
+			estimated_gas: trace.gas_used,
+			expected_outputs,
+			revert_reason: trace.revert_reason,
 		})
 	}
 }
@@ -723,13 +961,15 @@ impl OrderInterface for Eip7683OrderImpl {
 pub fn create_order_impl(
 	config: &toml::Value,
 	networks: &NetworksConfig,
-	oracle_routes: &solver_types::oracle::OracleRoutes,
+	oracle_routes: &Arc<dyn OracleRouteSource>,
 ) -> Result<Box<dyn OrderInterface>, OrderError> {
 	// Validate configuration first
 	Eip7683OrderSchema::validate_config(config)
 		.map_err(|e| OrderError::InvalidOrder(format!("Invalid configuration: {}", e)))?;
 
-	let order_impl = Eip7683OrderImpl::new(networks.clone(), oracle_routes.clone())?;
+	let order_impl = Eip7683OrderImpl::new(networks.clone(), oracle_routes.clone())?
+		.with_gas_limit_strategy(GasLimitStrategy::from_config(config)?)
+		.with_output_swapper(OutputSwapper::from_config(config)?);
 	Ok(Box::new(order_impl))
 }
 
@@ -743,6 +983,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_order_impl
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683OrderSchema)
+	}
 }
 
 impl crate::OrderRegistry for Registry {}
@@ -0,0 +1,678 @@
+//! Commit-reveal execution strategy.
+//!
+//! Fill transactions sitting in the mempool reveal the order being filled, so
+//! competitors can copy them with a higher priority fee. This strategy first
+//! commits to the fill with a hash binding the order to the solver, and once
+//! the commitment has been mined for a configured number of blocks reveals it
+//! to the commitment contract, which verifies it against the recorded hash.
+//! The fill is only submitted after the reveal succeeded, so the earlier
+//! commitment wins any race on the fill.
+
+use alloy_primitives::{keccak256, Address as AlloyAddress, FixedBytes, B256, U256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_types::{
+	ConfigSchema, ExecutionContext, ExecutionDecision, Field, FieldType, ImplementationRegistry,
+	Order, Schema, SchemaDefinition, Transaction, TransactionHash, TransactionType,
+};
+use std::time::Duration;
+
+use super::simple::SimpleStrategy;
+use crate::{ExecutionStrategy, StrategyError, TransactionSimulator};
+
+/// Delay before checking a pending commitment again.
+const COMMIT_DEFER: Duration = Duration::from_secs(15);
+
+/// Number of failed submissions after which an order is given up.
+const MAX_SUBMIT_ATTEMPTS: u32 = 3;
+
+sol! {
+	/// Registry recording fill commitments and verifying their reveal.
+	interface IFillCommitments {
+		function commitFill(bytes32 commitment) external;
+		function revealFill(bytes32 orderId, bytes32 salt) external;
+	}
+}
+
+/// Fill commitment of an order, persisted between its commit and reveal.
+///
+/// The salt is needed to reveal the commitment, so it is stored before the
+/// commitment is submitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillCommitment {
+	/// Salt hashed into the commitment.
+	pub salt: B256,
+	/// Transaction revealing the commitment, once submitted.
+	pub reveal_tx_hash: Option<TransactionHash>,
+	/// Number of commit or reveal submissions that failed.
+	pub failed_attempts: u32,
+}
+
+impl FillCommitment {
+	/// Creates a commitment with a fresh random salt.
+	fn new() -> Self {
+		Self {
+			salt: keccak256(uuid::Uuid::new_v4().as_bytes()),
+			reveal_tx_hash: None,
+			failed_attempts: 0,
+		}
+	}
+}
+
+/// Execution strategy committing to fills before submitting them.
+///
+/// Orders first go through the simple strategy, so gas price and balance
+/// checks still apply. An order that passes them gets a commitment submitted
+/// on its fill chain and is deferred. Once the commitment has been mined for
+/// `commit_block_delay` blocks it is revealed, and the order is executed once
+/// the reveal has been mined.
+pub struct CommitRevealStrategy {
+	/// Contract receiving and verifying the commitments on every fill chain.
+	commit_contract: AlloyAddress,
+	/// Number of blocks to wait between the commitment and the reveal.
+	commit_block_delay: u64,
+	/// Strategy deciding whether an order should be filled at all.
+	inner: SimpleStrategy,
+}
+
+impl CommitRevealStrategy {
+	/// Creates a new CommitRevealStrategy with the given commitment contract,
+	/// block delay and maximum gas price in gwei.
+	pub fn new(
+		commit_contract: AlloyAddress,
+		commit_block_delay: u64,
+		max_gas_price_gwei: u64,
+	) -> Self {
+		Self {
+			commit_contract,
+			commit_block_delay,
+			inner: SimpleStrategy::new(max_gas_price_gwei),
+		}
+	}
+
+	/// Builds a call to the commitment contract on an order's fill chain.
+	fn contract_call(&self, data: Vec<u8>, chain_id: u64) -> Transaction {
+		Transaction {
+			to: Some(solver_types::Address(self.commit_contract.to_vec())),
+			data,
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		}
+	}
+
+	/// Builds the commitment transaction for an order on its fill chain.
+	fn commit_transaction(
+		&self,
+		order: &Order,
+		salt: B256,
+		chain_id: u64,
+	) -> Result<Transaction, String> {
+		let order_id = parse_order_id(order)?;
+		if order.solver_address.0.len() != 20 {
+			return Err("Invalid solver address length".to_string());
+		}
+		let solver = AlloyAddress::from_slice(&order.solver_address.0);
+
+		// The salt keeps others from matching the commitment to the order
+		let commitment = keccak256((order_id, solver, salt).abi_encode());
+		let data = IFillCommitments::commitFillCall { commitment }.abi_encode();
+		Ok(self.contract_call(data, chain_id))
+	}
+
+	/// Builds the transaction revealing an order's commitment on its fill chain.
+	fn reveal_transaction(
+		&self,
+		order: &Order,
+		salt: B256,
+		chain_id: u64,
+	) -> Result<Transaction, String> {
+		let order_id = parse_order_id(order)?;
+		let data = IFillCommitments::revealFillCall {
+			orderId: order_id,
+			salt,
+		}
+		.abi_encode();
+		Ok(self.contract_call(data, chain_id))
+	}
+
+	/// Submits a commit or reveal transaction, counting failed submissions.
+	///
+	/// Returns the submitted transaction hash, or the decision to return once
+	/// the submission failed.
+	async fn submit(
+		&self,
+		order: &Order,
+		commitment: &mut FillCommitment,
+		tx: Result<Transaction, String>,
+		tx_type: TransactionType,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<TransactionHash, ExecutionDecision> {
+		if commitment.failed_attempts >= MAX_SUBMIT_ATTEMPTS {
+			return Err(ExecutionDecision::Skip(format!(
+				"Fill commitment failed {} times",
+				commitment.failed_attempts
+			)));
+		}
+		let tx = tx.map_err(ExecutionDecision::Skip)?;
+		match simulator.submit(&order.id, tx, tx_type).await {
+			Ok(tx_hash) => Ok(tx_hash),
+			Err(e) => {
+				tracing::warn!(order_id = %order.id, ?tx_type, error = %e, "Failed to submit fill commitment transaction");
+				commitment.failed_attempts += 1;
+				if let Err(e) = simulator.store_commitment(&order.id, commitment).await {
+					tracing::warn!(order_id = %order.id, error = %e, "Failed to persist fill commitment");
+				}
+				Err(ExecutionDecision::Defer(COMMIT_DEFER))
+			},
+		}
+	}
+}
+
+/// Parses the ID of an order as the bytes32 value committed to.
+fn parse_order_id(order: &Order) -> Result<FixedBytes<32>, String> {
+	order
+		.id
+		.parse::<FixedBytes<32>>()
+		.map_err(|_| format!("Order ID {} is not a bytes32 value", order.id))
+}
+
+/// Configuration schema for CommitRevealStrategy.
+///
+/// Validates the commitment contract and block delay, and the optional
+/// maximum gas price forwarded to the simple strategy.
+pub struct CommitRevealStrategySchema;
+
+impl CommitRevealStrategySchema {
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
+			// Required fields
+			vec![
+				Field::new("commit_contract", FieldType::String)
+					.with_description("Address of the contract receiving and verifying commitments")
+					.with_validator(|value| {
+						let address = value.as_str().unwrap_or_default();
+						address
+							.parse::<AlloyAddress>()
+							.map(|_| ())
+							.map_err(|_| format!("Invalid commit contract address: {}", address))
+					}),
+				Field::new(
+					"commit_block_delay",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				)
+				.with_description("Blocks to wait between commitment and reveal"),
+			],
+			// Optional fields
+			vec![Field::new(
+				"max_gas_price_gwei",
+				FieldType::Integer {
+					min: Some(1),
+					max: None,
+				},
+			)
+			.with_description(
+				"Orders are deferred while any chain's gas price exceeds this, in gwei",
+			)
+			.with_default(100)],
+		)
+	}
+}
+
+impl ConfigSchema for CommitRevealStrategySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for CommitRevealStrategy {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(CommitRevealStrategySchema)
+	}
+
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
+		let decision = self.inner.should_execute(order, context, simulator).await;
+		if !matches!(decision, ExecutionDecision::Execute(_)) {
+			return decision;
+		}
+
+		let Some(&chain_id) = order.output_chain_ids.first() else {
+			return ExecutionDecision::Skip("Order has no fill chain to commit on".to_string());
+		};
+
+		// The salt is persisted before committing, so the commitment can be
+		// revealed after a restart
+		let mut commitment = match simulator.load_commitment(&order.id).await {
+			Ok(Some(commitment)) => commitment,
+			Ok(None) => {
+				let commitment = FillCommitment::new();
+				if let Err(e) = simulator.store_commitment(&order.id, &commitment).await {
+					tracing::warn!(order_id = %order.id, error = %e, "Failed to persist fill commitment");
+					return ExecutionDecision::Defer(COMMIT_DEFER);
+				}
+				commitment
+			},
+			Err(e) => {
+				tracing::warn!(order_id = %order.id, error = %e, "Failed to load fill commitment");
+				return ExecutionDecision::Defer(COMMIT_DEFER);
+			},
+		};
+
+		// Phase 1: commit to the fill and wait for the commitment to be mined
+		let Some(commit_tx_hash) = &order.commit_tx_hash else {
+			let tx = self.commit_transaction(order, commitment.salt, chain_id);
+			return match self
+				.submit(
+					order,
+					&mut commitment,
+					tx,
+					TransactionType::Commit,
+					simulator,
+				)
+				.await
+			{
+				Ok(_) => {
+					tracing::info!(order_id = %order.id, chain_id, "Committed to fill");
+					ExecutionDecision::Defer(COMMIT_DEFER)
+				},
+				Err(decision) => decision,
+			};
+		};
+		let receipt = match simulator.get_receipt(commit_tx_hash, chain_id).await {
+			Ok(receipt) => receipt,
+			Err(e) => {
+				tracing::debug!(order_id = %order.id, error = %e, "Fill commitment not mined yet");
+				return ExecutionDecision::Defer(COMMIT_DEFER);
+			},
+		};
+		if !receipt.success {
+			return ExecutionDecision::Skip("Fill commitment reverted".to_string());
+		}
+
+		let head = context
+			.chain_data
+			.get(&chain_id)
+			.map(|chain_data| chain_data.block_number)
+			.unwrap_or_default();
+		if head < receipt.block_number.saturating_add(self.commit_block_delay) {
+			return ExecutionDecision::Defer(COMMIT_DEFER);
+		}
+
+		// Phase 2: reveal the commitment to the contract verifying it
+		let Some(reveal_tx_hash) = &commitment.reveal_tx_hash else {
+			let tx = self.reveal_transaction(order, commitment.salt, chain_id);
+			return match self
+				.submit(
+					order,
+					&mut commitment,
+					tx,
+					TransactionType::Reveal,
+					simulator,
+				)
+				.await
+			{
+				Ok(tx_hash) => {
+					tracing::info!(order_id = %order.id, chain_id, "Revealed fill commitment");
+					commitment.reveal_tx_hash = Some(tx_hash);
+					if let Err(e) = simulator.store_commitment(&order.id, &commitment).await {
+						tracing::warn!(order_id = %order.id, error = %e, "Failed to persist fill commitment");
+					}
+					ExecutionDecision::Defer(COMMIT_DEFER)
+				},
+				Err(decision) => decision,
+			};
+		};
+		match simulator.get_receipt(reveal_tx_hash, chain_id).await {
+			Ok(receipt) if receipt.success => decision,
+			Ok(_) => ExecutionDecision::Skip("Fill commitment was rejected on reveal".to_string()),
+			Err(e) => {
+				tracing::debug!(order_id = %order.id, error = %e, "Fill reveal not mined yet");
+				ExecutionDecision::Defer(COMMIT_DEFER)
+			},
+		}
+	}
+}
+
+/// Factory function to create a commit-reveal strategy from configuration.
+///
+/// Configuration parameters:
+/// - `commit_contract`: Address of the contract receiving and verifying commitments
+/// - `commit_block_delay`: Blocks to wait between commitment and reveal
+/// - `max_gas_price_gwei`: Maximum gas price in gwei (default: 100)
+pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError> {
+	// Validate configuration using the schema
+	let schema = CommitRevealStrategySchema;
+	schema
+		.validate(config)
+		.map_err(|e| StrategyError::InvalidConfig(e.to_string()))?;
+
+	let commit_contract = config
+		.get("commit_contract")
+		.and_then(|v| v.as_str())
+		.unwrap_or_default()
+		.parse::<AlloyAddress>()
+		.map_err(|e| StrategyError::InvalidConfig(format!("Invalid commit contract: {}", e)))?;
+	let commit_block_delay = config
+		.get("commit_block_delay")
+		.and_then(|v| v.as_integer())
+		.unwrap_or_default() as u64;
+	let max_gas_price = config
+		.get("max_gas_price_gwei")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(100) as u64;
+
+	Ok(Box::new(CommitRevealStrategy::new(
+		commit_contract,
+		commit_block_delay,
+		max_gas_price,
+	)))
+}
+
+/// Registry for the commit-reveal strategy implementation.
+pub struct Registry;
+
+impl ImplementationRegistry for Registry {
+	const NAME: &'static str = "commit_reveal";
+	type Factory = crate::StrategyFactory;
+
+	fn factory() -> Self::Factory {
+		create_strategy
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(CommitRevealStrategySchema)
+	}
+}
+
+impl crate::StrategyRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::OrderError;
+	use solver_types::{ChainData, OrderStatus, TransactionReceipt, TransactionTrace};
+	use std::collections::HashMap;
+	use std::sync::Mutex;
+
+	/// Simulator recording submissions and commitments, returning canned receipts.
+	#[derive(Default)]
+	struct MockSimulator {
+		submitted: Mutex<Vec<(Transaction, TransactionType)>>,
+		commitments: Mutex<HashMap<String, FillCommitment>>,
+		receipts: HashMap<TransactionHash, TransactionReceipt>,
+		fail_submissions: bool,
+	}
+
+	#[async_trait]
+	impl TransactionSimulator for MockSimulator {
+		async fn simulate(&self, _tx: &Transaction) -> Result<TransactionTrace, OrderError> {
+			Err(OrderError::ValidationFailed("Not mocked".to_string()))
+		}
+
+		async fn submit(
+			&self,
+			_order_id: &str,
+			tx: Transaction,
+			tx_type: TransactionType,
+		) -> Result<TransactionHash, OrderError> {
+			if self.fail_submissions {
+				return Err(OrderError::ValidationFailed(
+					"Submission failed".to_string(),
+				));
+			}
+			let tx_hash = match tx_type {
+				TransactionType::Commit => commit_hash(),
+				_ => reveal_hash(),
+			};
+			self.submitted.lock().unwrap().push((tx, tx_type));
+			Ok(tx_hash)
+		}
+
+		async fn get_receipt(
+			&self,
+			tx_hash: &TransactionHash,
+			_chain_id: u64,
+		) -> Result<TransactionReceipt, OrderError> {
+			self.receipts
+				.get(tx_hash)
+				.cloned()
+				.ok_or_else(|| OrderError::ValidationFailed("Not mined".to_string()))
+		}
+
+		async fn load_commitment(
+			&self,
+			order_id: &str,
+		) -> Result<Option<FillCommitment>, OrderError> {
+			Ok(self.commitments.lock().unwrap().get(order_id).cloned())
+		}
+
+		async fn store_commitment(
+			&self,
+			order_id: &str,
+			commitment: &FillCommitment,
+		) -> Result<(), OrderError> {
+			self.commitments
+				.lock()
+				.unwrap()
+				.insert(order_id.to_string(), commitment.clone());
+			Ok(())
+		}
+	}
+
+	fn commit_hash() -> TransactionHash {
+		TransactionHash(vec![0xcc; 32])
+	}
+
+	fn reveal_hash() -> TransactionHash {
+		TransactionHash(vec![0xdd; 32])
+	}
+
+	fn receipt(hash: TransactionHash, block_number: u64, success: bool) -> TransactionReceipt {
+		TransactionReceipt {
+			hash,
+			block_number,
+			success,
+			gas_used: 0,
+			effective_gas_price: 0,
+		}
+	}
+
+	fn test_order(commit_tx_hash: Option<TransactionHash>) -> Order {
+		Order {
+			id: format!("0x{}", "ab".repeat(32)),
+			standard: "test".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::json!({}),
+			solver_address: solver_types::Address(vec![0x22; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn test_context(block_number: u64) -> ExecutionContext {
+		ExecutionContext {
+			chain_data: HashMap::from([(
+				10,
+				ChainData {
+					chain_id: 10,
+					gas_price: "1".to_string(),
+					block_number,
+					timestamp: 0,
+				},
+			)]),
+			solver_balances: HashMap::new(),
+			timestamp: 0,
+			gas_budget_remaining: HashMap::new(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_commits_before_revealing() {
+		let strategy = CommitRevealStrategy::new(AlloyAddress::repeat_byte(0x11), 2, 100);
+
+		// Without a commitment, one is submitted on the fill chain
+		let mut simulator = MockSimulator::default();
+		let decision = strategy
+			.should_execute(&test_order(None), &test_context(100), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(d) if d == COMMIT_DEFER));
+		let salt = {
+			let submitted = simulator.submitted.lock().unwrap();
+			assert_eq!(submitted.len(), 1);
+			assert_eq!(submitted[0].0.chain_id, 10);
+			assert_eq!(submitted[0].1, TransactionType::Commit);
+			let call =
+				IFillCommitments::commitFillCall::abi_decode(&submitted[0].0.data, true).unwrap();
+
+			// The persisted salt opens the submitted commitment
+			let commitment = simulator.commitments.lock().unwrap()[&test_order(None).id].clone();
+			let order_id = test_order(None).id.parse::<FixedBytes<32>>().unwrap();
+			let solver = AlloyAddress::repeat_byte(0x22);
+			assert_eq!(
+				call.commitment,
+				keccak256((order_id, solver, commitment.salt).abi_encode())
+			);
+			commitment.salt
+		};
+
+		// Pending commitments keep the order deferred
+		let committed = test_order(Some(commit_hash()));
+		let decision = strategy
+			.should_execute(&committed, &test_context(100), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(_)));
+
+		// Mined commitments are revealed with the persisted salt after the block delay
+		simulator
+			.receipts
+			.insert(commit_hash(), receipt(commit_hash(), 100, true));
+		let decision = strategy
+			.should_execute(&committed, &test_context(101), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(_)));
+		let decision = strategy
+			.should_execute(&committed, &test_context(102), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(_)));
+		{
+			let submitted = simulator.submitted.lock().unwrap();
+			assert_eq!(submitted.len(), 2);
+			assert_eq!(submitted[1].1, TransactionType::Reveal);
+			let call =
+				IFillCommitments::revealFillCall::abi_decode(&submitted[1].0.data, true).unwrap();
+			assert_eq!(call.salt, salt);
+		}
+
+		// The order is filled once the reveal is mined
+		simulator
+			.receipts
+			.insert(reveal_hash(), receipt(reveal_hash(), 103, true));
+		let decision = strategy
+			.should_execute(&committed, &test_context(103), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+		assert_eq!(simulator.submitted.lock().unwrap().len(), 2);
+	}
+
+	#[tokio::test]
+	async fn test_rejected_reveal_skips_order() {
+		let strategy = CommitRevealStrategy::new(AlloyAddress::repeat_byte(0x11), 1, 100);
+		let order = test_order(Some(commit_hash()));
+		let simulator = MockSimulator {
+			receipts: HashMap::from([
+				(commit_hash(), receipt(commit_hash(), 100, true)),
+				(reveal_hash(), receipt(reveal_hash(), 101, false)),
+			]),
+			..Default::default()
+		};
+		simulator.commitments.lock().unwrap().insert(
+			order.id.clone(),
+			FillCommitment {
+				salt: B256::repeat_byte(0x33),
+				reveal_tx_hash: Some(reveal_hash()),
+				failed_attempts: 0,
+			},
+		);
+
+		let decision = strategy
+			.should_execute(&order, &test_context(110), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Skip(_)));
+	}
+
+	#[tokio::test]
+	async fn test_failed_submissions_are_bounded() {
+		let strategy = CommitRevealStrategy::new(AlloyAddress::repeat_byte(0x11), 1, 100);
+		let simulator = MockSimulator {
+			fail_submissions: true,
+			..Default::default()
+		};
+		let order = test_order(None);
+
+		for _ in 0..MAX_SUBMIT_ATTEMPTS {
+			let decision = strategy
+				.should_execute(&order, &test_context(100), &simulator)
+				.await;
+			assert!(matches!(decision, ExecutionDecision::Defer(_)));
+		}
+		let decision = strategy
+			.should_execute(&order, &test_context(100), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Skip(_)));
+
+		// The salt is kept across attempts
+		let commitments = simulator.commitments.lock().unwrap();
+		assert_eq!(commitments[&order.id].failed_attempts, MAX_SUBMIT_ATTEMPTS);
+	}
+
+	#[test]
+	fn test_create_strategy_requires_block_delay() {
+		let config: toml::Value = toml::from_str(
+			r#"
+			commit_contract = "0x1111111111111111111111111111111111111111"
+			commit_block_delay = 3
+			"#,
+		)
+		.unwrap();
+		assert!(create_strategy(&config).is_ok());
+
+		let invalid: toml::Value = toml::from_str(
+			r#"
+			commit_contract = "0x1111111111111111111111111111111111111111"
+			commit_block_delay = 0
+			"#,
+		)
+		.unwrap();
+		assert!(create_strategy(&invalid).is_err());
+	}
+}
@@ -0,0 +1,439 @@
+//! Conditional execution strategy.
+//!
+//! This module provides an ExecutionStrategy that only executes orders once a set
+//! of on-chain conditions hold, checked with static calls against configured contracts.
+
+use alloy_primitives::{Address as AlloyAddress, U256};
+use async_trait::async_trait;
+use solver_types::{
+	without_0x_prefix, ConfigSchema, ExecutionContext, ExecutionDecision, Field, FieldType,
+	ImplementationRegistry, Order, Schema, SchemaDefinition, Transaction,
+};
+use std::time::Duration;
+
+use super::simple::SimpleStrategy;
+use crate::{ExecutionStrategy, StrategyError, TransactionSimulator};
+
+/// Delay before re-evaluating an order whose conditions are not met.
+const CONDITION_DEFER: Duration = Duration::from_secs(60);
+
+/// An on-chain condition that must hold before an order is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+	/// Chain on which the static call is made.
+	pub chain_id: u64,
+	/// Contract receiving the static call.
+	pub contract_address: AlloyAddress,
+	/// ABI-encoded calldata for the static call.
+	pub call_data: Vec<u8>,
+	/// Data the call must return for the condition to hold.
+	pub expected_return: Vec<u8>,
+}
+
+/// Execution strategy gated on on-chain state.
+///
+/// Each configured condition is evaluated with a static call. If any call reverts
+/// or returns something other than the expected data, execution is deferred.
+/// Once all conditions hold, the decision is delegated to the simple strategy
+/// so gas price and balance checks still apply.
+pub struct ConditionalStrategy {
+	/// Conditions that must all hold before execution.
+	conditions: Vec<Condition>,
+	/// Strategy used once all conditions are met.
+	inner: SimpleStrategy,
+}
+
+impl ConditionalStrategy {
+	/// Creates a new ConditionalStrategy with the given conditions and maximum gas price in gwei.
+	pub fn new(conditions: Vec<Condition>, max_gas_price_gwei: u64) -> Self {
+		Self {
+			conditions,
+			inner: SimpleStrategy::new(max_gas_price_gwei),
+		}
+	}
+
+	/// Checks a single condition, returning false if the call fails or returns unexpected data.
+	async fn check_condition(
+		&self,
+		condition: &Condition,
+		simulator: &dyn TransactionSimulator,
+	) -> bool {
+		let tx = Transaction {
+			to: Some(solver_types::Address(condition.contract_address.to_vec())),
+			data: condition.call_data.clone(),
+			value: U256::ZERO,
+			chain_id: condition.chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		};
+
+		match simulator.simulate(&tx).await {
+			Ok(trace) if trace.success => {
+				hex::decode(without_0x_prefix(&trace.return_data))
+					.ok()
+					.as_ref() == Some(&condition.expected_return)
+			},
+			Ok(trace) => {
+				tracing::debug!(
+					chain_id = condition.chain_id,
+					contract = %condition.contract_address,
+					revert_reason = ?trace.revert_reason,
+					"Condition call reverted"
+				);
+				false
+			},
+			Err(e) => {
+				tracing::warn!(
+					chain_id = condition.chain_id,
+					contract = %condition.contract_address,
+					error = %e,
+					"Failed to evaluate condition"
+				);
+				false
+			},
+		}
+	}
+}
+
+/// Configuration schema for ConditionalStrategy.
+///
+/// Validates the list of conditions and the optional maximum gas price
+/// forwarded to the simple strategy.
+pub struct ConditionalStrategySchema;
+
+/// Validates that a configuration value is a hex string.
+fn validate_hex(value: &toml::Value) -> Result<(), String> {
+	let data = value.as_str().unwrap_or_default();
+	hex::decode(without_0x_prefix(data))
+		.map(|_| ())
+		.map_err(|_| format!("Invalid hex data: {}", data))
+}
+
+impl ConditionalStrategySchema {
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		let condition_schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new(
+					"chain_id",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				)
+				.with_description("Chain on which the static call is made"),
+				Field::new("contract_address", FieldType::String)
+					.with_description("Contract receiving the static call")
+					.with_validator(|value| {
+						let address = value.as_str().unwrap_or_default();
+						address
+							.parse::<AlloyAddress>()
+							.map(|_| ())
+							.map_err(|_| format!("Invalid contract address: {}", address))
+					}),
+				Field::new("call_data", FieldType::String)
+					.with_description("Hex-encoded calldata of the static call")
+					.with_validator(validate_hex),
+				Field::new("expected_return", FieldType::String)
+					.with_description("Hex-encoded data the call must return")
+					.with_validator(validate_hex),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		Schema::new(
+			// Required fields
+			vec![Field::new(
+				"conditions",
+				FieldType::Array(Box::new(FieldType::Table(condition_schema))),
+			)
+			.with_description("On-chain conditions that must all hold before orders are executed")],
+			// Optional fields
+			vec![Field::new(
+				"max_gas_price_gwei",
+				FieldType::Integer {
+					min: Some(1),
+					max: None,
+				},
+			)
+			.with_description(
+				"Orders are deferred while any chain's gas price exceeds this, in gwei",
+			)
+			.with_default(100)],
+		)
+	}
+}
+
+impl ConfigSchema for ConditionalStrategySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for ConditionalStrategy {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(ConditionalStrategySchema)
+	}
+
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
+		for condition in &self.conditions {
+			if !self.check_condition(condition, simulator).await {
+				tracing::debug!(
+					order_id = %order.id,
+					chain_id = condition.chain_id,
+					contract = %condition.contract_address,
+					"Execution condition not met, deferring order"
+				);
+				return ExecutionDecision::Defer(CONDITION_DEFER);
+			}
+		}
+
+		self.inner.should_execute(order, context, simulator).await
+	}
+}
+
+/// Factory function to create a conditional strategy from configuration.
+///
+/// Configuration parameters:
+/// - `conditions`: Array of `{chain_id, contract_address, call_data, expected_return}`
+/// - `max_gas_price_gwei`: Maximum gas price in gwei (default: 100)
+pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError> {
+	// Validate configuration using the schema
+	let schema = ConditionalStrategySchema;
+	schema
+		.validate(config)
+		.map_err(|e| StrategyError::InvalidConfig(e.to_string()))?;
+
+	let hex_field = |entry: &toml::Value, name: &str| -> Result<Vec<u8>, StrategyError> {
+		let data = entry.get(name).and_then(|v| v.as_str()).unwrap_or_default();
+		hex::decode(without_0x_prefix(data))
+			.map_err(|e| StrategyError::InvalidConfig(format!("Invalid {}: {}", name, e)))
+	};
+
+	let mut conditions = Vec::new();
+	for entry in config
+		.get("conditions")
+		.and_then(|v| v.as_array())
+		.into_iter()
+		.flatten()
+	{
+		let chain_id = entry
+			.get("chain_id")
+			.and_then(|v| v.as_integer())
+			.unwrap_or_default() as u64;
+		let contract_address = entry
+			.get("contract_address")
+			.and_then(|v| v.as_str())
+			.unwrap_or_default()
+			.parse::<AlloyAddress>()
+			.map_err(|e| {
+				StrategyError::InvalidConfig(format!("Invalid contract address: {}", e))
+			})?;
+
+		conditions.push(Condition {
+			chain_id,
+			contract_address,
+			call_data: hex_field(entry, "call_data")?,
+			expected_return: hex_field(entry, "expected_return")?,
+		});
+	}
+
+	let max_gas_price = config
+		.get("max_gas_price_gwei")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(100) as u64;
+
+	Ok(Box::new(ConditionalStrategy::new(
+		conditions,
+		max_gas_price,
+	)))
+}
+
+/// Registry for the conditional strategy implementation.
+pub struct Registry;
+
+impl ImplementationRegistry for Registry {
+	const NAME: &'static str = "conditional";
+	type Factory = crate::StrategyFactory;
+
+	fn factory() -> Self::Factory {
+		create_strategy
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(ConditionalStrategySchema)
+	}
+}
+
+impl crate::StrategyRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::OrderError;
+	use solver_types::{OrderStatus, TransactionTrace};
+	use std::collections::HashMap;
+
+	/// Simulator returning canned responses keyed by calldata.
+	struct MockSimulator {
+		responses: HashMap<Vec<u8>, TransactionTrace>,
+	}
+
+	#[async_trait]
+	impl TransactionSimulator for MockSimulator {
+		async fn simulate(&self, tx: &Transaction) -> Result<TransactionTrace, OrderError> {
+			self.responses
+				.get(&tx.data)
+				.cloned()
+				.ok_or_else(|| OrderError::ValidationFailed("No mocked response".to_string()))
+		}
+	}
+
+	fn trace(success: bool, return_data: &str) -> TransactionTrace {
+		TransactionTrace {
+			success,
+			gas_used: None,
+			return_data: return_data.to_string(),
+			revert_reason: (!success).then(|| "reverted".to_string()),
+			logs: Vec::new(),
+		}
+	}
+
+	fn condition(call_data: u8, expected_return: Vec<u8>) -> Condition {
+		Condition {
+			chain_id: 1,
+			contract_address: AlloyAddress::repeat_byte(0x11),
+			call_data: vec![call_data],
+			expected_return,
+		}
+	}
+
+	fn test_order() -> Order {
+		Order {
+			id: "order-1".to_string(),
+			standard: "test".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::json!({}),
+			solver_address: solver_types::Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![],
+			output_chain_ids: vec![],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn test_context() -> ExecutionContext {
+		ExecutionContext {
+			chain_data: HashMap::new(),
+			solver_balances: HashMap::new(),
+			timestamp: 0,
+			gas_budget_remaining: HashMap::new(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_executes_when_all_conditions_hold() {
+		let strategy =
+			ConditionalStrategy::new(vec![condition(1, vec![0x01]), condition(2, vec![])], 100);
+		let simulator = MockSimulator {
+			responses: HashMap::from([
+				(vec![1], trace(true, "0x01")),
+				(vec![2], trace(true, "0x")),
+			]),
+		};
+
+		let decision = strategy
+			.should_execute(&test_order(), &test_context(), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+	}
+
+	#[tokio::test]
+	async fn test_defers_on_unexpected_return() {
+		let strategy = ConditionalStrategy::new(vec![condition(1, vec![0x01])], 100);
+		let simulator = MockSimulator {
+			responses: HashMap::from([(vec![1], trace(true, "0x00"))]),
+		};
+
+		let decision = strategy
+			.should_execute(&test_order(), &test_context(), &simulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(d) if d == CONDITION_DEFER));
+	}
+
+	#[tokio::test]
+	async fn test_defers_on_revert_or_error() {
+		let strategy =
+			ConditionalStrategy::new(vec![condition(1, vec![0x01]), condition(2, vec![])], 100);
+
+		let reverting = MockSimulator {
+			responses: HashMap::from([(vec![1], trace(false, "0x01"))]),
+		};
+		let decision = strategy
+			.should_execute(&test_order(), &test_context(), &reverting)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(_)));
+
+		// Second condition has no mocked response, so the call fails
+		let failing = MockSimulator {
+			responses: HashMap::from([(vec![1], trace(true, "0x01"))]),
+		};
+		let decision = strategy
+			.should_execute(&test_order(), &test_context(), &failing)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(_)));
+	}
+
+	#[test]
+	fn test_create_strategy_parses_conditions() {
+		let config: toml::Value = toml::from_str(
+			r#"
+			max_gas_price_gwei = 50
+
+			[[conditions]]
+			chain_id = 1
+			contract_address = "0x1111111111111111111111111111111111111111"
+			call_data = "0x50d25bcd"
+			expected_return = "0x01"
+			"#,
+		)
+		.unwrap();
+		assert!(create_strategy(&config).is_ok());
+
+		let invalid: toml::Value = toml::from_str(
+			r#"
+			[[conditions]]
+			chain_id = 1
+			contract_address = "0x1111111111111111111111111111111111111111"
+			call_data = "not-hex"
+			expected_return = "0x01"
+			"#,
+		)
+		.unwrap();
+		assert!(create_strategy(&invalid).is_err());
+	}
+}
@@ -0,0 +1,57 @@
+//! Expiry-based order filtering.
+//!
+//! This module provides a wrapper around any ExecutionStrategy that skips
+//! orders expiring before a fill could be confirmed.
+
+use async_trait::async_trait;
+use solver_types::{ConfigSchema, Eip7683OrderData, ExecutionContext, ExecutionDecision, Order};
+
+use crate::{ExecutionStrategy, TransactionSimulator};
+
+/// Execution strategy wrapper that skips orders expiring too soon.
+///
+/// An order expiring within `buffer_seconds` of the execution context
+/// timestamp is skipped, since the fill would likely only be confirmed after
+/// the order expired. Orders without a parsable expiry are passed through.
+pub struct ExpiryFilter {
+	/// Strategy that decides on orders that do not expire too soon.
+	inner: Box<dyn ExecutionStrategy>,
+	/// Minimum remaining lifetime of an order in seconds.
+	buffer_seconds: u64,
+}
+
+impl ExpiryFilter {
+	/// Creates a new ExpiryFilter wrapping the given strategy.
+	pub fn new(inner: Box<dyn ExecutionStrategy>, buffer_seconds: u64) -> Self {
+		Self {
+			inner,
+			buffer_seconds,
+		}
+	}
+}
+
+/// Returns true if the order expires within `buffer_seconds` of `now`.
+fn expires_within(order: &Order, now: u64, buffer_seconds: u64) -> bool {
+	serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+		.is_ok_and(|order_data| u64::from(order_data.expires) <= now + buffer_seconds)
+}
+
+#[async_trait]
+impl ExecutionStrategy for ExpiryFilter {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
+		if expires_within(order, context.timestamp, self.buffer_seconds) {
+			return ExecutionDecision::Skip("expires too soon".to_string());
+		}
+
+		self.inner.should_execute(order, context, simulator).await
+	}
+}
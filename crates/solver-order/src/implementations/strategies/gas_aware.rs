@@ -0,0 +1,301 @@
+//! Gas-aware execution timing.
+//!
+//! This module provides a wrapper around any ExecutionStrategy that defers
+//! orders while gas is expensive. Instead of retrying after a fixed delay,
+//! orders are deferred until the next hour in which gas is predicted to be
+//! below the limit on every chain of the order, but never past the time
+//! the order must be filled by.
+
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::{ConfigSchema, Eip7683OrderData, ExecutionContext, ExecutionDecision, Order};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{ExecutionStrategy, GasWindowPredictor, TransactionSimulator};
+
+/// Delay used when no low gas window is predicted within the lookahead.
+pub const GAS_DEFER: Duration = Duration::from_secs(60);
+
+/// Execution strategy wrapper deferring orders to predicted low gas windows.
+///
+/// Orders are passed to the inner strategy while the gas price of every
+/// chain in the execution context is at or below `max_gas_price_gwei`.
+/// Deferrals end at the latest `min_fill_window_seconds` before the fill
+/// deadline of the order, and orders past that point are skipped.
+pub struct GasAwareStrategy {
+	/// Strategy that decides on orders while gas is cheap.
+	inner: Box<dyn ExecutionStrategy>,
+	/// Source of gas price predictions.
+	predictor: Arc<dyn GasWindowPredictor>,
+	/// Maximum gas price in gwei at which orders are passed through.
+	max_gas_price_gwei: u64,
+	/// Number of hours searched for a low gas window.
+	lookahead_hours: u8,
+	/// Minimum seconds left before the fill deadline to fill an order.
+	min_fill_window_seconds: u64,
+}
+
+impl GasAwareStrategy {
+	/// Creates a new GasAwareStrategy wrapping the given strategy.
+	pub fn new(
+		inner: Box<dyn ExecutionStrategy>,
+		predictor: Arc<dyn GasWindowPredictor>,
+		max_gas_price_gwei: u64,
+		lookahead_hours: u8,
+		min_fill_window_seconds: u64,
+	) -> Self {
+		Self {
+			inner,
+			predictor,
+			max_gas_price_gwei,
+			lookahead_hours,
+			min_fill_window_seconds,
+		}
+	}
+
+	/// Returns the latest time an order can be deferred to, if it has an
+	/// EIP-7683 fill deadline.
+	fn latest_fill_time(&self, order: &Order) -> Option<u64> {
+		serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+			.ok()
+			.map(|order_data| {
+				u64::from(order_data.fill_deadline).saturating_sub(self.min_fill_window_seconds)
+			})
+	}
+
+	/// Returns how long to wait until gas is predicted to be cheap on all
+	/// of the given chains, or `None` if no such window is predicted.
+	async fn next_low_gas_window(&self, chain_ids: &[u64], now: u64) -> Option<Duration> {
+		let mut window_start = now;
+		for &chain_id in chain_ids {
+			let (timestamp, _) = self
+				.predictor
+				.predict_low_gas_window(chain_id, self.lookahead_hours)
+				.await
+				.into_iter()
+				.find(|&(timestamp, gwei)| timestamp > now && gwei <= self.max_gas_price_gwei)?;
+			window_start = window_start.max(timestamp);
+		}
+		Some(Duration::from_secs(window_start - now))
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for GasAwareStrategy {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
+		let max_gas_price = U256::from(self.max_gas_price_gwei) * U256::from(10u64.pow(9));
+		let mut expensive: Vec<u64> = context
+			.chain_data
+			.values()
+			.filter(|chain_data| {
+				chain_data.gas_price.parse::<U256>().unwrap_or(U256::ZERO) > max_gas_price
+			})
+			.map(|chain_data| chain_data.chain_id)
+			.collect();
+		if expensive.is_empty() {
+			return self.inner.should_execute(order, context, simulator).await;
+		}
+		expensive.sort_unstable();
+
+		let mut delay = self
+			.next_low_gas_window(&expensive, context.timestamp)
+			.await
+			.unwrap_or(GAS_DEFER);
+		if let Some(latest) = self.latest_fill_time(order) {
+			if latest <= context.timestamp {
+				return ExecutionDecision::Skip(
+					"gas too expensive before fill deadline".to_string(),
+				);
+			}
+			delay = delay.min(Duration::from_secs(latest - context.timestamp));
+		}
+		tracing::debug!(
+			order_id = %order.id,
+			chains = ?expensive,
+			delay_seconds = delay.as_secs(),
+			"Deferring order until gas is cheaper"
+		);
+		ExecutionDecision::Defer(delay)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::OrderError;
+	use solver_types::{ChainData, ExecutionParams, OrderStatus, Transaction, TransactionTrace};
+	use std::collections::HashMap;
+
+	struct AlwaysExecute;
+
+	#[async_trait]
+	impl ExecutionStrategy for AlwaysExecute {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn should_execute(
+			&self,
+			_order: &Order,
+			_context: &ExecutionContext,
+			_simulator: &dyn TransactionSimulator,
+		) -> ExecutionDecision {
+			ExecutionDecision::Execute(ExecutionParams {
+				gas_price: U256::ZERO,
+				priority_fee: None,
+				gas_limit_override: None,
+				output_source: Default::default(),
+			})
+		}
+	}
+
+	struct NoSimulator;
+
+	#[async_trait]
+	impl TransactionSimulator for NoSimulator {
+		async fn simulate(&self, _tx: &Transaction) -> Result<TransactionTrace, OrderError> {
+			Err(OrderError::ValidationFailed("No simulator".to_string()))
+		}
+	}
+
+	/// Predicts 80 gwei for the next hour and 20 gwei for the one after.
+	struct FixedPredictor;
+
+	#[async_trait]
+	impl GasWindowPredictor for FixedPredictor {
+		async fn predict_low_gas_window(
+			&self,
+			chain_id: u64,
+			_lookahead_hours: u8,
+		) -> Vec<(u64, u64)> {
+			match chain_id {
+				1 => vec![(3_600, 80), (7_200, 20)],
+				_ => Vec::new(),
+			}
+		}
+	}
+
+	fn order(data: serde_json::Value) -> Order {
+		Order {
+			id: "order-1".to_string(),
+			standard: "test".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data,
+			solver_address: solver_types::Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![1],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn order_data(fill_deadline: u32) -> serde_json::Value {
+		serde_json::to_value(Eip7683OrderData {
+			user: format!("0x{}", "11".repeat(20)),
+			nonce: U256::from(1),
+			origin_chain_id: U256::from(1),
+			expires: fill_deadline,
+			fill_deadline,
+			input_oracle: format!("0x{}", "00".repeat(20)),
+			inputs: Vec::new(),
+			order_id: [0u8; 32],
+			gas_limit_overrides: Default::default(),
+			outputs: Vec::new(),
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		})
+		.unwrap()
+	}
+
+	fn context(chain_id: u64, gas_price_gwei: u64) -> ExecutionContext {
+		ExecutionContext {
+			chain_data: HashMap::from([(
+				chain_id,
+				ChainData {
+					chain_id,
+					gas_price: (gas_price_gwei * 10u64.pow(9)).to_string(),
+					block_number: 1,
+					timestamp: 1_000,
+				},
+			)]),
+			solver_balances: HashMap::new(),
+			timestamp: 1_000,
+			gas_budget_remaining: HashMap::new(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_defers_to_predicted_low_gas_window() {
+		let strategy = GasAwareStrategy::new(
+			Box::new(AlwaysExecute),
+			Arc::new(FixedPredictor),
+			50,
+			24,
+			60,
+		);
+
+		let decision = strategy
+			.should_execute(&order(serde_json::json!({})), &context(1, 30), &NoSimulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+
+		let decision = strategy
+			.should_execute(
+				&order(serde_json::json!({})),
+				&context(1, 100),
+				&NoSimulator,
+			)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(d) if d == Duration::from_secs(6_200)));
+
+		// Without a predicted window the order is retried after a fixed delay
+		let decision = strategy
+			.should_execute(
+				&order(serde_json::json!({})),
+				&context(2, 100),
+				&NoSimulator,
+			)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(d) if d == GAS_DEFER));
+	}
+
+	#[tokio::test]
+	async fn test_defer_is_capped_at_fill_deadline() {
+		let strategy = GasAwareStrategy::new(
+			Box::new(AlwaysExecute),
+			Arc::new(FixedPredictor),
+			50,
+			24,
+			60,
+		);
+
+		let decision = strategy
+			.should_execute(&order(order_data(2_060)), &context(1, 100), &NoSimulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(d) if d == Duration::from_secs(1_000)));
+
+		let decision = strategy
+			.should_execute(&order(order_data(1_060)), &context(1, 100), &NoSimulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Skip(_)));
+	}
+}
@@ -6,18 +6,23 @@ use alloy_primitives::U256;
 use async_trait::async_trait;
 use solver_types::{
 	bytes32_to_address, with_0x_prefix, ConfigSchema, Eip7683OrderData, ExecutionContext,
-	ExecutionDecision, ExecutionParams, Field, FieldType, Order, Schema,
+	ExecutionDecision, ExecutionParams, Field, FieldType, Order, OutputTokenSource, Schema,
+	SchemaDefinition, TokenAmount,
 };
 
-use crate::{ExecutionStrategy, StrategyError};
+use crate::{ExecutionStrategy, StrategyError, TransactionSimulator};
 
 /// Simple execution strategy that considers gas price limits.
 ///
 /// This strategy executes orders when gas prices are below a configured
-/// maximum, deferring execution when prices are too high.
+/// maximum, deferring execution when prices are too high. Orders whose
+/// outputs exceed the solver's balance are skipped, unless an AMM swap is
+/// configured to buy the missing output tokens in the fill transaction.
 pub struct SimpleStrategy {
 	/// Maximum gas price the solver is willing to pay.
 	max_gas_price: U256,
+	/// Source of output tokens the solver does not hold enough of.
+	shortfall_source: Option<OutputTokenSource>,
 }
 
 impl SimpleStrategy {
@@ -25,8 +30,16 @@ impl SimpleStrategy {
 	pub fn new(max_gas_price_gwei: u64) -> Self {
 		Self {
 			max_gas_price: U256::from(max_gas_price_gwei) * U256::from(10u64.pow(9)),
+			shortfall_source: None,
 		}
 	}
+
+	/// Buys output tokens the solver does not hold enough of from the given
+	/// source instead of skipping the order.
+	pub fn with_shortfall_source(mut self, source: Option<OutputTokenSource>) -> Self {
+		self.shortfall_source = source;
+		self
+	}
 }
 
 /// Configuration schema for SimpleStrategy.
@@ -35,22 +48,59 @@ impl SimpleStrategy {
 /// ensuring the optional maximum gas price parameter is valid if provided.
 pub struct SimpleStrategySchema;
 
-impl ConfigSchema for SimpleStrategySchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema = Schema::new(
+impl SimpleStrategySchema {
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
 			// Required fields
 			vec![],
 			// Optional fields
-			vec![Field::new(
-				"max_gas_price_gwei",
-				FieldType::Integer {
-					min: Some(1),
-					max: None,
-				},
-			)],
-		);
-
-		schema.validate(config)
+			vec![
+				Field::new(
+					"max_gas_price_gwei",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				)
+				.with_description(
+					"Orders are deferred while any chain's gas price exceeds this, in gwei",
+				)
+				.with_default(100),
+				Field::new(
+					"amm_swap",
+					FieldType::Table(Schema::new(
+						vec![
+							Field::new("dex", FieldType::String).with_description(
+								"DEX the tokens are bought on: uniswap_v3 or 1inch",
+							),
+							Field::new(
+								"max_slippage_bps",
+								FieldType::Integer {
+									min: Some(0),
+									max: Some(10_000),
+								},
+							)
+							.with_description("Maximum slippage of the swap in basis points"),
+						],
+						vec![],
+					)),
+				)
+				.with_description(
+					"Buy missing output tokens on a DEX instead of skipping the order",
+				),
+			],
+		)
+	}
+}
+
+impl ConfigSchema for SimpleStrategySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -60,7 +110,12 @@ impl ExecutionStrategy for SimpleStrategy {
 		Box::new(SimpleStrategySchema)
 	}
 
-	async fn should_execute(&self, order: &Order, context: &ExecutionContext) -> ExecutionDecision {
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		_simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
 		// Find the maximum gas price across all chains in the context
 		let max_gas_price = context
 			.chain_data
@@ -75,6 +130,7 @@ impl ExecutionStrategy for SimpleStrategy {
 		}
 
 		// Check token balances based on order standard
+		let mut output_source = OutputTokenSource::SolverInventory;
 		match order.standard.as_str() {
 			"eip7683" => {
 				if let Ok(order_data) =
@@ -91,17 +147,24 @@ impl ExecutionStrategy for SimpleStrategy {
 
 						// Check if we have the balance for this token
 						if let Some(balance_str) = context.solver_balances.get(&balance_key) {
-							// Parse balance and required amount
-							let balance = balance_str.parse::<U256>().unwrap_or(U256::ZERO);
-							let required = output.amount;
+							// Parse balance and required amount in raw units of the same token
+							let balance = TokenAmount::new(
+								balance_str.parse::<U256>().unwrap_or(U256::ZERO),
+								0,
+							);
+							let required = TokenAmount::new(output.amount, 0);
 
 							if balance < required {
+								if let Some(source) = self.shortfall_source {
+									output_source = source;
+									continue;
+								}
 								tracing::warn!(
 									order_id = %order.id,
 									chain_id = chain_id,
 									token = %with_0x_prefix(&token_address),
-									balance = ?balance,
-									required = ?required,
+									balance = %balance,
+									required = %required,
 									"Insufficient token balance for order"
 								);
 								return ExecutionDecision::Skip(format!(
@@ -112,6 +175,8 @@ impl ExecutionStrategy for SimpleStrategy {
 									with_0x_prefix(&token_address)
 								));
 							}
+						} else if let Some(source) = self.shortfall_source {
+							output_source = source;
 						} else {
 							// No balance info available for this token
 							tracing::warn!(
@@ -148,6 +213,8 @@ impl ExecutionStrategy for SimpleStrategy {
 		ExecutionDecision::Execute(ExecutionParams {
 			gas_price: max_gas_price,
 			priority_fee: Some(U256::from(2) * U256::from(10u64.pow(9))), // 2 gwei priority
+			gas_limit_override: None,
+			output_source,
 		})
 	}
 }
@@ -156,6 +223,8 @@ impl ExecutionStrategy for SimpleStrategy {
 ///
 /// Configuration parameters:
 /// - `max_gas_price_gwei`: Maximum gas price in gwei (default: 100)
+/// - `amm_swap`: Buy missing output tokens on a DEX, as
+///   `{ dex = "uniswap_v3" | "1inch", max_slippage_bps = 50 }` (default: skip)
 pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError> {
 	// Validate configuration using the schema
 	let schema = SimpleStrategySchema;
@@ -168,7 +237,20 @@ pub fn create_strategy(config: &toml::Value) -> Result<Box<dyn ExecutionStrategy
 		.and_then(|v| v.as_integer())
 		.unwrap_or(100) as u64;
 
-	Ok(Box::new(SimpleStrategy::new(max_gas_price)))
+	let shortfall_source = config
+		.get("amm_swap")
+		.map(|value| {
+			let mut table = value.as_table().cloned().unwrap_or_default();
+			table.insert("type".to_string(), toml::Value::from("amm_swap"));
+			toml::Value::Table(table)
+				.try_into::<OutputTokenSource>()
+				.map_err(|e| StrategyError::InvalidConfig(format!("Invalid amm_swap: {}", e)))
+		})
+		.transpose()?;
+
+	Ok(Box::new(
+		SimpleStrategy::new(max_gas_price).with_shortfall_source(shortfall_source),
+	))
 }
 
 /// Registry for the simple strategy implementation.
@@ -181,6 +263,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_strategy
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(SimpleStrategySchema)
+	}
 }
 
 impl crate::StrategyRegistry for Registry {}
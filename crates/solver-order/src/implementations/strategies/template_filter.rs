@@ -0,0 +1,344 @@
+//! Template-based order filtering.
+//!
+//! This module provides a wrapper around any ExecutionStrategy that only lets
+//! through orders matching one of the configured order templates.
+
+use alloy_primitives::{Address as AlloyAddress, U256};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use solver_types::{
+	ConfigSchema, Eip7683OrderData, ExecutionContext, ExecutionDecision, NetworksConfig, Order,
+	OrderTemplate, TokenAmount,
+};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use crate::{ExecutionStrategy, TransactionSimulator};
+
+/// Shared, replaceable set of order templates.
+pub type TemplateStore = Arc<RwLock<Vec<OrderTemplate>>>;
+
+/// Execution strategy wrapper that skips orders not matching any template.
+///
+/// An order matches a template when one of its inputs and one of its outputs
+/// use the template's tokens and chains, the input amount is within the
+/// template's range and the output value does not exceed the input value at
+/// the template's rate by more than `max_slippage_bps`. Templates whose
+/// tokens are not configured for their networks never match, as their
+/// amounts cannot be compared. With no templates configured every order is
+/// passed through to the wrapped strategy.
+pub struct TemplateFilter {
+	/// Strategy that decides on orders matching a template.
+	inner: Box<dyn ExecutionStrategy>,
+	/// Templates currently in effect.
+	templates: TemplateStore,
+	/// Networks providing the decimals of template tokens.
+	networks: NetworksConfig,
+}
+
+impl TemplateFilter {
+	/// Creates a new TemplateFilter wrapping the given strategy.
+	pub fn new(
+		inner: Box<dyn ExecutionStrategy>,
+		templates: TemplateStore,
+		networks: NetworksConfig,
+	) -> Self {
+		Self {
+			inner,
+			templates,
+			networks,
+		}
+	}
+
+	/// Returns true if the order matches at least one template.
+	fn matches_any(&self, order: &Order, templates: &[OrderTemplate]) -> bool {
+		let order_data = match order.standard.as_str() {
+			"eip7683" => match serde_json::from_value::<Eip7683OrderData>(order.data.clone()) {
+				Ok(order_data) => order_data,
+				Err(_) => return false,
+			},
+			_ => return false,
+		};
+
+		templates
+			.iter()
+			.any(|template| template_matches(template, &order_data, &self.networks))
+	}
+}
+
+/// Returns the decimals of a token configured on a chain.
+fn token_decimals(networks: &NetworksConfig, chain_id: u64, token: &AlloyAddress) -> Option<u8> {
+	networks
+		.get(&chain_id)?
+		.tokens
+		.iter()
+		.find(|configured| configured.address.0 == token.as_slice())
+		.map(|configured| configured.decimals)
+}
+
+/// Returns a raw token amount in whole tokens.
+fn whole_tokens(amount: U256, decimals: u8) -> Option<Decimal> {
+	Decimal::from_str(&TokenAmount::new(amount, decimals).to_string()).ok()
+}
+
+/// Returns the largest output, in whole output tokens, a template accepts for
+/// an input of `input` whole tokens.
+fn max_output(template: &OrderTemplate, input: Decimal) -> Option<Decimal> {
+	let slippage = Decimal::from(10_000 + u64::from(template.max_slippage_bps))
+		.checked_div(Decimal::from(10_000))?;
+	input.checked_mul(template.rate)?.checked_mul(slippage)
+}
+
+/// Checks whether an EIP-7683 order satisfies a single template.
+fn template_matches(
+	template: &OrderTemplate,
+	order_data: &Eip7683OrderData,
+	networks: &NetworksConfig,
+) -> bool {
+	let (Ok(input_token), Ok(output_token)) = (
+		template.input_token.parse::<AlloyAddress>(),
+		template.output_token.parse::<AlloyAddress>(),
+	) else {
+		return false;
+	};
+	let (Some(input_decimals), Some(output_decimals)) = (
+		token_decimals(networks, template.input_chain, &input_token),
+		token_decimals(networks, template.output_chain, &output_token),
+	) else {
+		return false;
+	};
+
+	if order_data.origin_chain_id != U256::from(template.input_chain) {
+		return false;
+	}
+
+	order_data.inputs.iter().any(|[token, amount]| {
+		let token = AlloyAddress::from_slice(&token.to_be_bytes::<32>()[12..]);
+		if token != input_token || *amount < template.min_amount || *amount > template.max_amount {
+			return false;
+		}
+
+		let Some(max_output) =
+			whole_tokens(*amount, input_decimals).and_then(|input| max_output(template, input))
+		else {
+			return false;
+		};
+		order_data.outputs.iter().any(|output| {
+			output.chain_id == U256::from(template.output_chain)
+				&& AlloyAddress::from_slice(&output.token[12..]) == output_token
+				&& whole_tokens(output.amount, output_decimals)
+					.is_some_and(|output| output <= max_output)
+		})
+	})
+}
+
+#[async_trait]
+impl ExecutionStrategy for TemplateFilter {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
+		let matched = {
+			let templates = self.templates.read().unwrap_or_else(|e| e.into_inner());
+			templates.is_empty() || self.matches_any(order, &templates)
+		};
+
+		if !matched {
+			return ExecutionDecision::Skip("Order does not match any order template".to_string());
+		}
+
+		self.inner.should_execute(order, context, simulator).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::standards::eip7683::{GasLimitOverrides, MandateOutput};
+	use solver_types::{Address, ChainId, NetworkConfig, TokenConfig};
+
+	const INPUT_TOKEN: &str = "0x1111111111111111111111111111111111111111";
+	const OUTPUT_TOKEN: &str = "0x2222222222222222222222222222222222222222";
+
+	fn template() -> OrderTemplate {
+		OrderTemplate {
+			input_token: INPUT_TOKEN.to_string(),
+			output_token: OUTPUT_TOKEN.to_string(),
+			input_chain: 1,
+			output_chain: 10,
+			min_amount: U256::from(100),
+			max_amount: U256::from(1_000),
+			rate: Decimal::ONE,
+			max_slippage_bps: 50,
+		}
+	}
+
+	fn network(token: &str, decimals: u8) -> NetworkConfig {
+		NetworkConfig {
+			rpc_urls: Vec::new(),
+			input_settler_address: Address(vec![0; 20]),
+			output_settler_address: Address(vec![0; 20]),
+			tokens: vec![TokenConfig {
+				address: Address(token.parse::<AlloyAddress>().unwrap().to_vec()),
+				symbol: "TKN".to_string(),
+				decimals,
+			}],
+			input_settler_compact_address: None,
+		}
+	}
+
+	/// Networks with the input token on chain 1 and the output token on
+	/// chain 10.
+	fn networks(input_decimals: u8, output_decimals: u8) -> NetworksConfig {
+		NetworksConfig::from([
+			(ChainId(1), network(INPUT_TOKEN, input_decimals)),
+			(ChainId(10), network(OUTPUT_TOKEN, output_decimals)),
+		])
+	}
+
+	fn order_data(input_amount: u64, output_amount: u64, output_chain: u64) -> Eip7683OrderData {
+		let input_token =
+			U256::from_be_slice(INPUT_TOKEN.parse::<AlloyAddress>().unwrap().as_slice());
+		let mut output_token = [0u8; 32];
+		output_token[12..]
+			.copy_from_slice(OUTPUT_TOKEN.parse::<AlloyAddress>().unwrap().as_slice());
+
+		Eip7683OrderData {
+			user: "0x0000000000000000000000000000000000000001".to_string(),
+			nonce: U256::ZERO,
+			origin_chain_id: U256::from(1),
+			expires: 0,
+			fill_deadline: 0,
+			input_oracle: "0x0000000000000000000000000000000000000002".to_string(),
+			inputs: vec![[input_token, U256::from(input_amount)]],
+			order_id: [0u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![MandateOutput {
+				oracle: [0u8; 32],
+				settler: [0u8; 32],
+				chain_id: U256::from(output_chain),
+				token: output_token,
+				amount: U256::from(output_amount),
+				recipient: [0u8; 32],
+				call: vec![],
+				context: vec![],
+			}],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		}
+	}
+
+	#[test]
+	fn test_template_matches_pair_and_range() {
+		assert!(template_matches(
+			&template(),
+			&order_data(500, 500, 10),
+			&networks(6, 6)
+		));
+		// Output may exceed the input by up to 50 bps
+		assert!(template_matches(
+			&template(),
+			&order_data(1_000, 1_005, 10),
+			&networks(6, 6)
+		));
+	}
+
+	#[test]
+	fn test_template_rejects_mismatches() {
+		// Amount outside the configured range
+		assert!(!template_matches(
+			&template(),
+			&order_data(50, 50, 10),
+			&networks(6, 6)
+		));
+		assert!(!template_matches(
+			&template(),
+			&order_data(2_000, 2_000, 10),
+			&networks(6, 6)
+		));
+		// Output chain differs
+		assert!(!template_matches(
+			&template(),
+			&order_data(500, 500, 8453),
+			&networks(6, 6)
+		));
+		// Output exceeds the slippage tolerance
+		assert!(!template_matches(
+			&template(),
+			&order_data(1_000, 1_006, 10),
+			&networks(6, 6)
+		));
+	}
+
+	#[test]
+	fn test_template_compares_whole_tokens() {
+		// 1 token with 6 decimals for 1 token with 18 decimals
+		let networks = networks(6, 18);
+		assert!(template_matches(
+			&template(),
+			&order_data(1_000, 10u64.pow(15), 10),
+			&networks
+		));
+		assert!(!template_matches(
+			&template(),
+			&order_data(1_000, 10u64.pow(15) + 10u64.pow(13), 10),
+			&networks
+		));
+
+		// Outputs are priced at the template rate
+		let template = OrderTemplate {
+			rate: Decimal::from(2),
+			..template()
+		};
+		assert!(template_matches(
+			&template,
+			&order_data(1_000, 2 * 10u64.pow(15), 10),
+			&networks
+		));
+
+		// Tokens without configured decimals never match
+		assert!(!template_matches(
+			&template,
+			&order_data(500, 500, 10),
+			&NetworksConfig::default()
+		));
+	}
+
+	#[test]
+	fn test_template_slippage_does_not_overflow() {
+		let template = OrderTemplate {
+			max_slippage_bps: u32::MAX,
+			..template()
+		};
+		assert!(template_matches(
+			&template,
+			&order_data(1_000, 400_000, 10),
+			&networks(6, 6)
+		));
+	}
+
+	#[test]
+	fn test_template_deserializes_from_toml() {
+		let parsed: OrderTemplate = toml::from_str(&format!(
+			r#"
+			input_token = "{INPUT_TOKEN}"
+			output_token = "{OUTPUT_TOKEN}"
+			input_chain = 1
+			output_chain = 10
+			min_amount = "100"
+			max_amount = "1000"
+			max_slippage_bps = 50
+			"#
+		))
+		.unwrap();
+		assert_eq!(parsed, template());
+	}
+}
@@ -0,0 +1,265 @@
+//! Time-weighted average execution strategy.
+//!
+//! This module provides a wrapper around any ExecutionStrategy that spreads
+//! fills over time. At most `max_fills_per_window` orders are executed per
+//! rolling window of `window_seconds`, regardless of gas prices; orders over
+//! the limit are deferred until the oldest fill of the window expires.
+
+use alloy_primitives::hex;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{
+	ConfigSchema, Eip7683OrderData, ExecutionContext, ExecutionDecision, Order, StorageKey,
+	TwapGranularity,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::{ExecutionStrategy, TransactionSimulator};
+
+/// Fill timestamps of one rate-limited scope.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TwapWindow {
+	/// Timestamps of the fills executed within the window, oldest first.
+	fills: Vec<u64>,
+}
+
+/// Execution strategy wrapper limiting the frequency of fills.
+///
+/// Orders the inner strategy decides to execute are counted against the
+/// rolling window of their scope, persisted so that the limit holds across
+/// restarts.
+pub struct TwapStrategy {
+	/// Strategy that decides on orders within the rate limit.
+	inner: Box<dyn ExecutionStrategy>,
+	storage: Arc<StorageService>,
+	/// Maximum number of fills per window.
+	max_fills_per_window: u32,
+	/// Length of the rolling window in seconds.
+	window_seconds: u64,
+	/// Scope within which fills are counted.
+	granularity: TwapGranularity,
+	/// Serializes the read-modify-write of windows between orders.
+	lock: Mutex<()>,
+}
+
+impl TwapStrategy {
+	/// Creates a new TwapStrategy wrapping the given strategy.
+	pub fn new(
+		inner: Box<dyn ExecutionStrategy>,
+		storage: Arc<StorageService>,
+		max_fills_per_window: u32,
+		window_seconds: u64,
+		granularity: TwapGranularity,
+	) -> Self {
+		Self {
+			inner,
+			storage,
+			max_fills_per_window,
+			window_seconds,
+			granularity,
+			lock: Mutex::new(()),
+		}
+	}
+
+	/// Loads the window of a scope, dropping fills that left it.
+	async fn load_window(&self, key: &str, now: u64) -> Result<TwapWindow, StorageError> {
+		let mut window = match self
+			.storage
+			.retrieve::<TwapWindow>(StorageKey::TwapState.as_str(), key)
+			.await
+		{
+			Ok(window) => window,
+			Err(StorageError::NotFound) => TwapWindow::default(),
+			Err(e) => return Err(e),
+		};
+		let window_start = now.saturating_sub(self.window_seconds);
+		window.fills.retain(|&filled_at| filled_at > window_start);
+		Ok(window)
+	}
+}
+
+/// Returns the key of the scope an order's fills are counted in.
+fn scope_key(order: &Order, granularity: TwapGranularity) -> String {
+	let output_chain = order.output_chain_ids.first().copied().unwrap_or_default();
+	match granularity {
+		TwapGranularity::Global => "global".to_string(),
+		TwapGranularity::Chain => format!("chain:{}", output_chain),
+		TwapGranularity::TokenPair => {
+			let input_chain = order.input_chain_ids.first().copied().unwrap_or_default();
+			let (input_token, output_token) =
+				serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+					.ok()
+					.and_then(|order_data| {
+						let [input_token, _] = order_data.inputs.first()?;
+						let output = order_data.outputs.first()?;
+						Some((
+							hex::encode(&input_token.to_be_bytes::<32>()[12..]),
+							hex::encode(&output.token[12..]),
+						))
+					})
+					.unwrap_or_default();
+			format!(
+				"pair:{}:{}:{}:{}",
+				input_chain, input_token, output_chain, output_token
+			)
+		},
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for TwapStrategy {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision {
+		let key = scope_key(order, self.granularity);
+		let now = context.timestamp;
+		let _guard = self.lock.lock().await;
+
+		let mut window = match self.load_window(&key, now).await {
+			Ok(window) => window,
+			Err(e) => {
+				tracing::warn!(scope = %key, error = %e, "Failed to load TWAP window, not limiting fills");
+				return self.inner.should_execute(order, context, simulator).await;
+			},
+		};
+		if window.fills.len() >= self.max_fills_per_window as usize {
+			let oldest = window.fills.first().copied().unwrap_or(now);
+			let remaining = (oldest + self.window_seconds).saturating_sub(now).max(1);
+			return ExecutionDecision::Defer(Duration::from_secs(remaining));
+		}
+
+		let decision = self.inner.should_execute(order, context, simulator).await;
+		if matches!(decision, ExecutionDecision::Execute(_)) {
+			window.fills.push(now);
+			if let Err(e) = self
+				.storage
+				.store_with_ttl(
+					StorageKey::TwapState.as_str(),
+					&key,
+					&window,
+					None,
+					Some(Duration::from_secs(self.window_seconds)),
+				)
+				.await
+			{
+				tracing::warn!(scope = %key, error = %e, "Failed to record TWAP fill");
+			}
+		}
+		decision
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::OrderError;
+	use alloy_primitives::U256;
+	use solver_storage::implementations::memory::MemoryStorage;
+	use solver_types::{ExecutionParams, OrderStatus, Transaction, TransactionTrace};
+	use std::collections::HashMap;
+
+	struct AlwaysExecute;
+
+	#[async_trait]
+	impl ExecutionStrategy for AlwaysExecute {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn should_execute(
+			&self,
+			_order: &Order,
+			_context: &ExecutionContext,
+			_simulator: &dyn TransactionSimulator,
+		) -> ExecutionDecision {
+			ExecutionDecision::Execute(ExecutionParams {
+				gas_price: U256::ZERO,
+				priority_fee: None,
+				gas_limit_override: None,
+				output_source: Default::default(),
+			})
+		}
+	}
+
+	struct NoSimulator;
+
+	#[async_trait]
+	impl TransactionSimulator for NoSimulator {
+		async fn simulate(&self, _tx: &Transaction) -> Result<TransactionTrace, OrderError> {
+			Err(OrderError::ValidationFailed("No simulator".to_string()))
+		}
+	}
+
+	fn order(output_chain: u64) -> Order {
+		Order {
+			id: "order-1".to_string(),
+			standard: "test".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::json!({}),
+			solver_address: solver_types::Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![output_chain],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn context(timestamp: u64) -> ExecutionContext {
+		ExecutionContext {
+			chain_data: HashMap::new(),
+			solver_balances: HashMap::new(),
+			timestamp,
+			gas_budget_remaining: HashMap::new(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_defers_fills_over_window_limit() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let strategy = TwapStrategy::new(
+			Box::new(AlwaysExecute),
+			storage,
+			2,
+			60,
+			TwapGranularity::Chain,
+		);
+
+		for now in [100, 110] {
+			let decision = strategy
+				.should_execute(&order(10), &context(now), &NoSimulator)
+				.await;
+			assert!(matches!(decision, ExecutionDecision::Execute(_)));
+		}
+		let decision = strategy
+			.should_execute(&order(10), &context(120), &NoSimulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Defer(d) if d == Duration::from_secs(40)));
+
+		// Other chains have their own window, and the first fill leaves the window
+		let decision = strategy
+			.should_execute(&order(20), &context(120), &NoSimulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+		let decision = strategy
+			.should_execute(&order(10), &context(161), &NoSimulator)
+			.await;
+		assert!(matches!(decision, ExecutionDecision::Execute(_)));
+	}
+}
@@ -5,11 +5,13 @@
 //! standards and pluggable execution strategies.
 
 use async_trait::async_trait;
+use implementations::strategies::template_filter::{TemplateFilter, TemplateStore};
 use solver_types::{
 	Address, ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, FillProof,
-	ImplementationRegistry, Intent, NetworksConfig, Order, Transaction,
+	ImplementationRegistry, Intent, NetworksConfig, Order, OrderTemplate, SimulationResult,
+	Transaction, TransactionHash, TransactionReceipt, TransactionTrace, TransactionType,
 };
-use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 /// Re-export implementations
@@ -18,9 +20,26 @@ pub mod implementations {
 		pub mod _7683;
 	}
 	pub mod strategies {
+		pub mod commit_reveal;
+		pub mod conditional;
+		pub mod expiry_filter;
+		pub mod gas_aware;
 		pub mod simple;
+		pub mod template_filter;
+		pub mod twap;
 	}
 }
+pub mod gas_limit;
+pub mod nonce_coordinator;
+pub mod prediction;
+pub mod swap;
+pub mod version_registry;
+
+pub use gas_limit::GasLimitStrategy;
+pub use implementations::strategies::commit_reveal::FillCommitment;
+pub use prediction::{FillPredictor, GasWindowPredictor};
+pub use swap::SwapQuote;
+pub use version_registry::VersionRegistry;
 
 /// Errors that can occur during order processing operations.
 #[derive(Debug, Error)]
@@ -37,6 +56,9 @@ pub enum OrderError {
 	/// Error that occurs when the order configuration is invalid.
 	#[error("Invalid order: {0}")]
 	InvalidOrder(String),
+	/// Error that occurs when persisted order state cannot be accessed.
+	#[error("Storage error: {0}")]
+	Storage(String),
 }
 
 /// Errors that can occur during strategy creation and execution.
@@ -56,6 +78,81 @@ pub enum StrategyError {
 	ImplementationNotAvailable(String),
 }
 
+/// Trait for executing transactions without submitting them.
+///
+/// Implemented on top of the delivery service so that order implementations
+/// can simulate the transactions they generate. While an order is evaluated
+/// for execution, strategies may also submit transactions ahead of its fill.
+#[async_trait]
+pub trait TransactionSimulator: Send + Sync {
+	/// Executes the transaction against the current chain state and returns its trace.
+	async fn simulate(&self, tx: &Transaction) -> Result<TransactionTrace, OrderError>;
+
+	/// Submits a transaction on behalf of an order, tracking it as `tx_type`.
+	///
+	/// The default implementation rejects the submission, as simulators used
+	/// outside of order evaluation must not send transactions.
+	async fn submit(
+		&self,
+		_order_id: &str,
+		_tx: Transaction,
+		_tx_type: TransactionType,
+	) -> Result<TransactionHash, OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Transaction submission is not supported".to_string(),
+		))
+	}
+
+	/// Returns the receipt of a mined transaction.
+	///
+	/// Fails if the transaction is not found or not yet mined.
+	async fn get_receipt(
+		&self,
+		_tx_hash: &TransactionHash,
+		_chain_id: u64,
+	) -> Result<TransactionReceipt, OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Transaction receipts are not supported".to_string(),
+		))
+	}
+
+	/// Returns the number of transactions sent from `address` on a chain.
+	async fn get_transaction_count(
+		&self,
+		_chain_id: u64,
+		_address: &str,
+	) -> Result<u64, OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Transaction counts are not supported".to_string(),
+		))
+	}
+
+	/// Returns the code deployed at `address` on a chain.
+	async fn get_code(&self, _chain_id: u64, _address: &str) -> Result<Vec<u8>, OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Code lookups are not supported".to_string(),
+		))
+	}
+
+	/// Returns the fill commitment persisted for an order, if any.
+	async fn load_commitment(&self, _order_id: &str) -> Result<Option<FillCommitment>, OrderError> {
+		Err(OrderError::Storage(
+			"Fill commitments are not supported".to_string(),
+		))
+	}
+
+	/// Persists the fill commitment of an order so it survives restarts.
+	async fn store_commitment(
+		&self,
+		_order_id: &str,
+		_commitment: &FillCommitment,
+	) -> Result<(), OrderError> {
+		Err(OrderError::Storage(
+			"Fill commitments are not supported".to_string(),
+		))
+	}
+}
+
 /// Trait defining the interface for order standard implementations.
 ///
 /// This trait must be implemented for each order standard (e.g., EIP-7683)
@@ -70,6 +167,19 @@ pub trait OrderInterface: Send + Sync {
 	/// before initializing the order processor.
 	fn config_schema(&self) -> Box<dyn ConfigSchema>;
 
+	/// Returns the strategy for setting the gas limit of fill transactions.
+	fn gas_limit_strategy(&self) -> GasLimitStrategy {
+		GasLimitStrategy::default()
+	}
+
+	/// Returns whether this implementation handles intents and orders of the
+	/// given standard and version.
+	///
+	/// Orders are routed to the implementation registered for their standard
+	/// and version, or otherwise to the first implementation supporting them,
+	/// so one implementation can handle several variants of a standard.
+	fn supports_standard(&self, standard: &str, version: u8) -> bool;
+
 	/// Validates an intent and converts it to a standard order format.
 	///
 	/// This method performs standard-specific validation to ensure the intent
@@ -103,13 +213,28 @@ pub trait OrderInterface: Send + Sync {
 	/// Generates a transaction to fill the given order.
 	///
 	/// Creates a blockchain transaction that will execute the order fill
-	/// according to the standard's requirements.
+	/// according to the standard's requirements. Fills depending on on-chain
+	/// state, such as DEX quotes, read it through the simulator.
 	async fn generate_fill_transaction(
 		&self,
 		order: &Order,
 		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
 	) -> Result<Transaction, OrderError>;
 
+	/// Quotes the funding tokens sold to buy the outputs of a fill sourced
+	/// from a DEX.
+	///
+	/// Returns `None` if the fill is paid from the solver's balance.
+	async fn quote_output_swap(
+		&self,
+		_order: &Order,
+		_params: &ExecutionParams,
+		_simulator: &dyn TransactionSimulator,
+	) -> Result<Option<SwapQuote>, OrderError> {
+		Ok(None)
+	}
+
 	/// Generates a transaction to claim rewards for a filled order.
 	///
 	/// Creates a blockchain transaction that will claim any rewards or fees
@@ -119,6 +244,17 @@ pub trait OrderInterface: Send + Sync {
 		order: &Order,
 		fill_proof: &FillProof,
 	) -> Result<Transaction, OrderError>;
+
+	/// Simulates the fill transaction for the given order without submitting it.
+	///
+	/// Returns the estimated gas, the outputs the fill is expected to deliver
+	/// and the revert reason if the fill would fail.
+	async fn simulate_fill_transaction(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<SimulationResult, OrderError>;
 }
 
 /// Trait defining the interface for execution strategies.
@@ -137,8 +273,14 @@ pub trait ExecutionStrategy: Send + Sync {
 	/// Determines whether an order should be executed given the current context.
 	///
 	/// Returns an ExecutionDecision indicating whether to execute now,
-	/// skip the order, or defer execution to a later time.
-	async fn should_execute(&self, order: &Order, context: &ExecutionContext) -> ExecutionDecision;
+	/// skip the order, or defer execution to a later time. Strategies that
+	/// depend on on-chain state can issue static calls through the simulator.
+	async fn should_execute(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
+	) -> ExecutionDecision;
 }
 
 /// Type alias for order factory functions.
@@ -148,7 +290,7 @@ pub trait ExecutionStrategy: Send + Sync {
 pub type OrderFactory = fn(
 	&toml::Value,
 	&NetworksConfig,
-	&solver_types::oracle::OracleRoutes,
+	&std::sync::Arc<dyn solver_types::oracle::OracleRouteSource>,
 ) -> Result<Box<dyn OrderInterface>, OrderError>;
 
 /// Type alias for strategy factory functions.
@@ -179,14 +321,54 @@ pub fn get_all_order_implementations() -> Vec<(&'static str, OrderFactory)> {
 	vec![(_7683::Registry::NAME, _7683::Registry::factory())]
 }
 
+/// Get the configuration schemas of all registered order implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_order_implementations`].
+pub fn get_all_order_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::standards::_7683;
+
+	vec![(_7683::Registry::NAME, _7683::Registry::config_schema())]
+}
+
 /// Get all registered strategy implementations.
 ///
 /// Returns a vector of (name, factory) tuples for all available strategy implementations.
 /// This is used by the factory registry to automatically register all implementations.
 pub fn get_all_strategy_implementations() -> Vec<(&'static str, StrategyFactory)> {
-	use implementations::strategies::simple;
+	use implementations::strategies::{commit_reveal, conditional, simple};
 
-	vec![(simple::Registry::NAME, simple::Registry::factory())]
+	vec![
+		(simple::Registry::NAME, simple::Registry::factory()),
+		(
+			conditional::Registry::NAME,
+			conditional::Registry::factory(),
+		),
+		(
+			commit_reveal::Registry::NAME,
+			commit_reveal::Registry::factory(),
+		),
+	]
+}
+
+/// Get the configuration schemas of all registered strategy implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_strategy_implementations`].
+pub fn get_all_strategy_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::strategies::{commit_reveal, conditional, simple};
+
+	vec![
+		(simple::Registry::NAME, simple::Registry::config_schema()),
+		(
+			conditional::Registry::NAME,
+			conditional::Registry::config_schema(),
+		),
+		(
+			commit_reveal::Registry::NAME,
+			commit_reveal::Registry::config_schema(),
+		),
+	]
 }
 
 /// Service that manages order processing with multiple implementations and strategies.
@@ -194,53 +376,123 @@ pub fn get_all_strategy_implementations() -> Vec<(&'static str, StrategyFactory)
 /// The OrderService coordinates between different order standard implementations
 /// and applies the configured execution strategy to make filling decisions.
 pub struct OrderService {
-	/// Map of standard names to their implementations.
-	implementations: HashMap<String, Box<dyn OrderInterface>>,
+	/// Implementations by standard name and version.
+	implementations: VersionRegistry,
 	/// The execution strategy to use for making filling decisions.
 	strategy: Box<dyn ExecutionStrategy>,
+	/// Order templates restricting which orders reach the strategy.
+	templates: TemplateStore,
+	/// Fill time predictor used to skip orders likely to expire before filling.
+	predictor: Option<Arc<dyn FillPredictor>>,
 }
 
 impl OrderService {
 	/// Creates a new OrderService with the specified implementations and strategy.
+	///
+	/// Implementations given by standard name are registered as the default
+	/// version of their standard.
 	pub fn new(
-		implementations: HashMap<String, Box<dyn OrderInterface>>,
+		implementations: impl Into<VersionRegistry>,
 		strategy: Box<dyn ExecutionStrategy>,
 	) -> Self {
-		Self {
+		Self::with_templates(
 			implementations,
 			strategy,
+			Vec::new(),
+			NetworksConfig::default(),
+		)
+	}
+
+	/// Creates a new OrderService whose strategy only sees orders matching the given templates.
+	///
+	/// An empty template list lets every order through to the strategy. The
+	/// decimals of template tokens are taken from the tokens configured in
+	/// `networks`.
+	pub fn with_templates(
+		implementations: impl Into<VersionRegistry>,
+		strategy: Box<dyn ExecutionStrategy>,
+		templates: Vec<OrderTemplate>,
+		networks: NetworksConfig,
+	) -> Self {
+		let templates: TemplateStore = Arc::new(RwLock::new(templates));
+		Self {
+			implementations: implementations.into(),
+			strategy: Box::new(TemplateFilter::new(strategy, templates.clone(), networks)),
+			templates,
+			predictor: None,
 		}
 	}
 
-	/// Validates an intent using the appropriate standard implementation.
+	/// Sets the fill time predictor consulted before the strategy.
+	pub fn with_predictor(mut self, predictor: Arc<dyn FillPredictor>) -> Self {
+		self.predictor = Some(predictor);
+		self
+	}
+
+	/// Returns the fill time predictor, if one is configured.
+	pub fn predictor(&self) -> Option<&dyn FillPredictor> {
+		self.predictor.as_deref()
+	}
+
+	/// Replaces the order templates used to filter orders.
 	///
-	/// Selects the implementation based on the intent's standard field
-	/// and delegates validation to that implementation. The solver address
-	/// is included in the resulting order for reward attribution.
+	/// Takes effect for all subsequent execution decisions.
+	pub fn update_templates(&self, templates: Vec<OrderTemplate>) {
+		*self.templates.write().unwrap_or_else(|e| e.into_inner()) = templates;
+	}
+
+	/// Validates an intent using the implementations supporting its standard.
+	///
+	/// Each implementation supporting the intent's standard and the version
+	/// declared by its data is tried in turn, starting with the one registered
+	/// for them, until one validates the intent. The solver address is
+	/// included in the resulting order for reward attribution.
 	///
 	/// # Arguments
 	///
 	/// * `intent` - The intent to validate
 	/// * `solver_address` - The solver's address for reward attribution
+	///
+	/// # Errors
+	///
+	/// Returns the error of the last implementation tried, or
+	/// `OrderError::ValidationFailed` if no implementation supports the intent.
 	pub async fn validate_intent(
 		&self,
 		intent: &Intent,
 		solver_address: &Address,
 	) -> Result<Order, OrderError> {
-		let implementation = self.implementations.get(&intent.standard).ok_or_else(|| {
-			OrderError::ValidationFailed(format!("Unknown standard: {}", intent.standard))
-		})?;
+		let version = version_registry::intent_version(intent);
+		let mut last_error = None;
+		for implementation in self.implementations.supporting(&intent.standard, version) {
+			match implementation.validate_intent(intent, solver_address).await {
+				Ok(order) => return Ok(order),
+				Err(e) => last_error = Some(e),
+			}
+		}
 
-		implementation.validate_intent(intent, solver_address).await
+		Err(last_error.unwrap_or_else(|| self.unsupported(&intent.standard, &intent.data)))
 	}
 
 	/// Determines whether an order should be executed using the configured strategy.
+	///
+	/// When a fill predictor is configured, orders predicted to be filled only
+	/// after their fill deadline are skipped without consulting the strategy.
 	pub async fn should_execute(
 		&self,
 		order: &Order,
 		context: &ExecutionContext,
+		simulator: &dyn TransactionSimulator,
 	) -> ExecutionDecision {
-		self.strategy.should_execute(order, context).await
+		if let Some(predictor) = self.predictor() {
+			if !prediction::should_execute_by_deadline(order, predictor, context).await {
+				return ExecutionDecision::Skip("likely to expire before filling".to_string());
+			}
+		}
+
+		self.strategy
+			.should_execute(order, context, simulator)
+			.await
 	}
 
 	/// Generates a prepare transaction for the given order if needed.
@@ -252,10 +504,7 @@ impl OrderService {
 		order: &Order,
 		params: &ExecutionParams,
 	) -> Result<Option<Transaction>, OrderError> {
-		let implementation = self
-			.implementations
-			.get(&order.standard)
-			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
+		let implementation = self.implementation(&order.standard, &order.data)?;
 
 		implementation
 			.generate_prepare_transaction(intent, order, params)
@@ -264,19 +513,80 @@ impl OrderService {
 
 	/// Generates a fill transaction for the given order.
 	///
-	/// Uses the appropriate standard implementation to create the transaction.
+	/// Uses the appropriate standard implementation to create the transaction
+	/// and simulates it, then sets its gas limit from the execution parameters
+	/// or the gas limit strategy of the implementation.
+	///
+	/// # Errors
+	///
+	/// Returns `OrderError::ValidationFailed` with the decoded revert reason if
+	/// the simulated fill reverts. Fills that cannot be simulated are still
+	/// generated.
 	pub async fn generate_fill_transaction(
 		&self,
 		order: &Order,
 		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
 	) -> Result<Transaction, OrderError> {
-		let implementation = self
-			.implementations
-			.get(&order.standard)
-			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
+		let implementation = self.implementation(&order.standard, &order.data)?;
+
+		let mut tx = implementation
+			.generate_fill_transaction(order, params, simulator)
+			.await?;
+		let simulation = match simulator.simulate(&tx).await {
+			Ok(trace) if !trace.success => {
+				return Err(OrderError::ValidationFailed(format!(
+					"Fill for order {} would revert: {}",
+					order.id,
+					trace
+						.revert_reason
+						.unwrap_or_else(|| "unknown reason".to_string())
+				)));
+			},
+			Ok(trace) => Some(trace),
+			Err(e) => {
+				tracing::debug!(order_id = %order.id, error = %e, "Failed to simulate fill");
+				None
+			},
+		};
+		gas_limit::apply_gas_limit(
+			&mut tx,
+			params,
+			implementation.gas_limit_strategy(),
+			simulation.as_ref(),
+		);
+		Ok(tx)
+	}
+
+	/// Quotes the funding tokens sold by the fill of an order sourced from a DEX.
+	///
+	/// Uses the appropriate standard implementation to quote the swap.
+	pub async fn quote_output_swap(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<Option<SwapQuote>, OrderError> {
+		let implementation = self.implementation(&order.standard, &order.data)?;
+
+		implementation
+			.quote_output_swap(order, params, simulator)
+			.await
+	}
+
+	/// Simulates the fill transaction for the given order without submitting it.
+	///
+	/// Uses the appropriate standard implementation to build and simulate the transaction.
+	pub async fn simulate_fill_transaction(
+		&self,
+		order: &Order,
+		params: &ExecutionParams,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<SimulationResult, OrderError> {
+		let implementation = self.implementation(&order.standard, &order.data)?;
 
 		implementation
-			.generate_fill_transaction(order, params)
+			.simulate_fill_transaction(order, params, simulator)
 			.await
 	}
 
@@ -288,13 +598,39 @@ impl OrderService {
 		order: &Order,
 		proof: &FillProof,
 	) -> Result<Transaction, OrderError> {
-		let implementation = self
-			.implementations
-			.get(&order.standard)
-			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
+		let implementation = self.implementation(&order.standard, &order.data)?;
 
 		implementation
 			.generate_claim_transaction(order, proof)
 			.await
 	}
+
+	/// Returns the implementation of a standard at the version declared by
+	/// intent or order data.
+	fn implementation(
+		&self,
+		standard: &str,
+		data: &serde_json::Value,
+	) -> Result<&dyn OrderInterface, OrderError> {
+		let version = version_registry::standard_version(data)
+			.unwrap_or(version_registry::DEFAULT_STANDARD_VERSION);
+		self.implementations
+			.supporting(standard, version)
+			.into_iter()
+			.next()
+			.ok_or_else(|| self.unsupported(standard, data))
+	}
+
+	/// Returns the error for a standard, or version of it, without implementation.
+	fn unsupported(&self, standard: &str, data: &serde_json::Value) -> OrderError {
+		match version_registry::standard_version(data) {
+			Some(version) if !self.implementations.versions(standard).is_empty() => {
+				OrderError::ValidationFailed(format!(
+					"Unsupported version {} of standard {}",
+					version, standard
+				))
+			},
+			_ => OrderError::ValidationFailed(format!("Unknown standard: {}", standard)),
+		}
+	}
 }
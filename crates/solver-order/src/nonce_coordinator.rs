@@ -0,0 +1,369 @@
+//! Nonce coordination for off-chain orders.
+//!
+//! Opening an off-chain order through `openFor` fails once the order's nonce
+//! has been consumed in Permit2, by another order signed with the same nonce
+//! or by the user invalidating it. The coordinator locks the nonce for the
+//! order the solver commits to until it is finalized or fails, so that orders
+//! of a user reusing a nonce are processed one at a time, and checks before
+//! opening that the nonce is still unused.
+
+use crate::{OrderError, TransactionSimulator};
+use alloy_primitives::U256;
+use alloy_sol_types::{sol, SolCall};
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{
+	current_timestamp, protocols::PROTOCOL_REGISTRY, standards::eip7683::LockType,
+	without_0x_prefix, Address, Eip7683OrderData, Order, StorageKey, Transaction,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+sol! {
+	interface IPermit2 {
+		function nonceBitmap(address owner, uint256 wordPos) external view returns (uint256);
+	}
+}
+
+/// Order holding the lock of a nonce.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockedNonce {
+	order_id: String,
+}
+
+/// Outcome of locking an order's nonce for the order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonceLock {
+	/// The order holds the lock.
+	Acquired,
+	/// Another order holds the lock; contains its order ID.
+	Held(String),
+	/// The nonce has already been consumed on-chain.
+	Consumed,
+}
+
+/// Serializes off-chain orders sharing a user nonce through locked nonces.
+pub struct NonceCoordinator {
+	storage: Arc<StorageService>,
+	/// Serializes read-modify-write updates of locks.
+	update_lock: Mutex<()>,
+}
+
+impl NonceCoordinator {
+	/// Creates a new NonceCoordinator persisting locks to `storage`.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self {
+			storage,
+			update_lock: Mutex::new(()),
+		}
+	}
+
+	/// Locks the order's nonce for the order if it has not been consumed yet.
+	///
+	/// Locking again for the order holding the lock succeeds. The lock
+	/// expires with the order.
+	pub async fn lock(
+		&self,
+		order: &Order,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<NonceLock, OrderError> {
+		let owner = NonceOwner::from_order(order)?;
+
+		let _guard = self.update_lock.lock().await;
+		if let Some(locked) = self.load(&owner.key()).await? {
+			if locked.order_id != order.id {
+				return Ok(NonceLock::Held(locked.order_id));
+			}
+		}
+		if owner.is_consumed(simulator).await? {
+			return Ok(NonceLock::Consumed);
+		}
+
+		let ttl = owner.expires.saturating_sub(current_timestamp());
+		self.storage
+			.store_with_ttl(
+				StorageKey::LockedNonces.as_str(),
+				&owner.key(),
+				&LockedNonce {
+					order_id: order.id.clone(),
+				},
+				None,
+				Some(Duration::from_secs(ttl)),
+			)
+			.await
+			.map_err(|e| OrderError::Storage(e.to_string()))?;
+
+		Ok(NonceLock::Acquired)
+	}
+
+	/// Returns true if the order holds the lock of its nonce and the nonce
+	/// has not been consumed on-chain.
+	pub async fn is_current(
+		&self,
+		order: &Order,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<bool, OrderError> {
+		let owner = NonceOwner::from_order(order)?;
+		let Some(locked) = self.load(&owner.key()).await? else {
+			return Ok(false);
+		};
+		if locked.order_id != order.id {
+			return Ok(false);
+		}
+
+		Ok(!owner.is_consumed(simulator).await?)
+	}
+
+	/// Removes the lock held by the order, if any.
+	pub async fn unlock(&self, order: &Order) -> Result<(), OrderError> {
+		let Ok(owner) = NonceOwner::from_order(order) else {
+			return Ok(());
+		};
+
+		let _guard = self.update_lock.lock().await;
+		match self.load(&owner.key()).await? {
+			Some(locked) if locked.order_id == order.id => self
+				.storage
+				.remove(StorageKey::LockedNonces.as_str(), &owner.key())
+				.await
+				.map_err(|e| OrderError::Storage(e.to_string())),
+			_ => Ok(()),
+		}
+	}
+
+	/// Loads the lock stored under `key`, if any.
+	async fn load(&self, key: &str) -> Result<Option<LockedNonce>, OrderError> {
+		match self
+			.storage
+			.retrieve(StorageKey::LockedNonces.as_str(), key)
+			.await
+		{
+			Ok(locked) => Ok(Some(locked)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(OrderError::Storage(e.to_string())),
+		}
+	}
+}
+
+/// User nonce an order consumes when it is opened.
+struct NonceOwner {
+	chain_id: u64,
+	user: String,
+	nonce: U256,
+	lock_type: LockType,
+	/// Unix timestamp at which the order expires.
+	expires: u64,
+}
+
+impl NonceOwner {
+	/// Extracts the sponsor (or user), nonce and origin chain of an order.
+	fn from_order(order: &Order) -> Result<Self, OrderError> {
+		let data: Eip7683OrderData = serde_json::from_value(order.data.clone()).map_err(|e| {
+			OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
+		})?;
+		let chain_id = u64::try_from(data.origin_chain_id).map_err(|_| {
+			OrderError::ValidationFailed(format!(
+				"Invalid origin chain ID: {}",
+				data.origin_chain_id
+			))
+		})?;
+
+		Ok(Self {
+			chain_id,
+			user: data.sponsor.unwrap_or(data.user).to_lowercase(),
+			nonce: data.nonce,
+			lock_type: data.lock_type,
+			expires: data.expires as u64,
+		})
+	}
+
+	/// Returns the storage key of the nonce's lock.
+	fn key(&self) -> String {
+		format!("{}_{}_{:x}", self.chain_id, self.user, self.nonce)
+	}
+
+	/// Returns true if the nonce has been consumed in Permit2.
+	///
+	/// Nonces of resource locks are consumed by TheCompact only when the
+	/// order is claimed, so they are never reported as consumed.
+	async fn is_consumed(&self, simulator: &dyn TransactionSimulator) -> Result<bool, OrderError> {
+		if self.lock_type != LockType::Permit2Escrow {
+			return Ok(false);
+		}
+		let Some(permit2) = PROTOCOL_REGISTRY.get_permit2_address(self.chain_id) else {
+			return Ok(false);
+		};
+		let owner = self.user.parse().map_err(|e| {
+			OrderError::ValidationFailed(format!("Invalid user address {}: {}", self.user, e))
+		})?;
+
+		// Permit2 tracks unordered nonces in bitmaps of 256 nonces each
+		let data = IPermit2::nonceBitmapCall {
+			owner,
+			wordPos: self.nonce >> 8,
+		}
+		.abi_encode();
+		let trace = simulator
+			.simulate(&Transaction {
+				to: Some(Address(permit2.to_vec())),
+				data,
+				value: U256::ZERO,
+				chain_id: self.chain_id,
+				nonce: None,
+				gas_limit: None,
+				gas_price: None,
+				max_fee_per_gas: None,
+				max_priority_fee_per_gas: None,
+				memo: None,
+				authorization_list: None,
+			})
+			.await?;
+		if !trace.success {
+			return Err(OrderError::ValidationFailed(format!(
+				"Permit2 nonce lookup failed: {}",
+				trace
+					.revert_reason
+					.unwrap_or_else(|| "unknown reason".to_string())
+			)));
+		}
+		let data = hex::decode(without_0x_prefix(&trace.return_data)).map_err(|e| {
+			OrderError::ValidationFailed(format!("Invalid Permit2 nonce bitmap: {}", e))
+		})?;
+		let bitmap = IPermit2::nonceBitmapCall::abi_decode_returns(&data, true)
+			.map_err(|e| {
+				OrderError::ValidationFailed(format!("Invalid Permit2 nonce bitmap: {}", e))
+			})?
+			._0;
+		let bit = self.nonce & U256::from(0xff);
+		Ok(bitmap.bit(bit.to::<usize>()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_trait::async_trait;
+	use solver_storage::implementations::memory::MemoryStorage;
+	use solver_types::{OrderStatus, TransactionTrace};
+	use std::sync::Mutex as StdMutex;
+
+	/// Simulator answering Permit2 nonce bitmap lookups with a fixed bitmap.
+	#[derive(Default)]
+	struct MockSimulator {
+		bitmap: StdMutex<U256>,
+		lookups: StdMutex<Vec<U256>>,
+	}
+
+	#[async_trait]
+	impl TransactionSimulator for MockSimulator {
+		async fn simulate(&self, tx: &Transaction) -> Result<TransactionTrace, OrderError> {
+			let call = IPermit2::nonceBitmapCall::abi_decode(&tx.data, true)
+				.map_err(|e| OrderError::ValidationFailed(e.to_string()))?;
+			self.lookups.lock().unwrap().push(call.wordPos);
+			Ok(TransactionTrace {
+				success: true,
+				gas_used: None,
+				return_data: hex::encode(self.bitmap.lock().unwrap().to_be_bytes::<32>()),
+				revert_reason: None,
+				logs: Vec::new(),
+			})
+		}
+	}
+
+	fn test_order(id: &str, nonce: u64) -> Order {
+		Order {
+			id: id.to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Created,
+			data: serde_json::json!({
+				"user": "0xAAaa000000000000000000000000000000000001",
+				"nonce": format!("{:#x}", nonce),
+				"origin_chain_id": "0x1",
+				"expires": u32::MAX,
+				"fill_deadline": u32::MAX,
+				"input_oracle": "0x0000000000000000000000000000000000000000",
+				"inputs": [],
+				"order_id": vec![0u8; 32],
+				"gas_limit_overrides": {},
+				"outputs": [],
+			}),
+			solver_address: solver_types::Address(vec![0x22; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn coordinator() -> NonceCoordinator {
+		NonceCoordinator::new(Arc::new(StorageService::new(
+			Box::new(MemoryStorage::new()),
+			None,
+		)))
+	}
+
+	#[tokio::test]
+	async fn test_orders_sharing_a_nonce_serialize() {
+		let coordinator = coordinator();
+		let simulator = MockSimulator::default();
+
+		let first = test_order("order-1", 7);
+		let second = test_order("order-2", 7);
+		let other_nonce = test_order("order-3", 8);
+		assert_eq!(
+			coordinator.lock(&first, &simulator).await.unwrap(),
+			NonceLock::Acquired
+		);
+		assert_eq!(
+			coordinator.lock(&second, &simulator).await.unwrap(),
+			NonceLock::Held("order-1".to_string())
+		);
+		// Orders of the same user with other nonces are not held back
+		assert_eq!(
+			coordinator.lock(&other_nonce, &simulator).await.unwrap(),
+			NonceLock::Acquired
+		);
+		assert!(coordinator.is_current(&first, &simulator).await.unwrap());
+		assert!(!coordinator.is_current(&second, &simulator).await.unwrap());
+
+		// Only the holder can release the lock
+		coordinator.unlock(&second).await.unwrap();
+		assert!(coordinator.is_current(&first, &simulator).await.unwrap());
+		coordinator.unlock(&first).await.unwrap();
+		assert_eq!(
+			coordinator.lock(&second, &simulator).await.unwrap(),
+			NonceLock::Acquired
+		);
+	}
+
+	#[tokio::test]
+	async fn test_consumed_nonce_is_detected() {
+		let coordinator = coordinator();
+		let simulator = MockSimulator::default();
+
+		// Nonce 0x1ff is bit 255 of word 1
+		let order = test_order("order-1", 0x1ff);
+		assert_eq!(
+			coordinator.lock(&order, &simulator).await.unwrap(),
+			NonceLock::Acquired
+		);
+		assert_eq!(simulator.lookups.lock().unwrap()[0], U256::from(1));
+
+		*simulator.bitmap.lock().unwrap() = U256::from(1) << 255;
+		assert!(!coordinator.is_current(&order, &simulator).await.unwrap());
+
+		coordinator.unlock(&order).await.unwrap();
+		assert_eq!(
+			coordinator.lock(&order, &simulator).await.unwrap(),
+			NonceLock::Consumed
+		);
+	}
+}
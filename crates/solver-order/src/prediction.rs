@@ -0,0 +1,316 @@
+//! Fill time prediction for execution decisions.
+//!
+//! A fill predictor estimates how long an order takes from its creation to a
+//! confirmed fill, so that orders likely to pass their fill deadline before
+//! being filled can be skipped. Fill times are recorded per token pair when
+//! fills are confirmed and the historical predictor serves their median.
+//! A gas window predictor forecasts gas prices, so that orders can be
+//! deferred until gas is expected to be cheap.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_storage::{QueryFilter, StorageError, StorageIndexes, StorageService};
+use solver_types::{
+	bytes32_to_address, with_0x_prefix, Eip7683OrderData, ExecutionContext, Order, StorageKey,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Trait for estimating the fill time of orders.
+#[async_trait]
+pub trait FillPredictor: Send + Sync {
+	/// Returns the expected time from the creation of the order to its fill.
+	async fn predict_fill_time(&self, order: &Order, context: &ExecutionContext) -> Duration;
+}
+
+/// Trait for predicting the gas prices of the coming hours.
+#[async_trait]
+pub trait GasWindowPredictor: Send + Sync {
+	/// Returns `(unix_timestamp, predicted_gas_gwei)` pairs for the next
+	/// `lookahead_hours` hours of a chain, in chronological order. Hours
+	/// without a prediction are omitted.
+	async fn predict_low_gas_window(&self, chain_id: u64, lookahead_hours: u8) -> Vec<(u64, u64)>;
+}
+
+/// Fill time of an order, recorded once its fill is confirmed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillTimeRecord {
+	/// ID of the order.
+	pub order_id: String,
+	/// Token pair of the order, as returned by [`token_pair`].
+	pub pair: String,
+	/// Seconds between the creation of the order and its fill.
+	pub fill_time_seconds: u64,
+	/// Timestamp of the fill.
+	pub filled_at: u64,
+}
+
+/// Returns the token pair of an order as
+/// `origin_chain:input_token:destination_chain:output_token`.
+///
+/// Only the first input and output are considered. Returns None for orders
+/// whose data is not EIP-7683 order data.
+pub fn token_pair(order: &Order) -> Option<String> {
+	let data = serde_json::from_value::<Eip7683OrderData>(order.data.clone()).ok()?;
+	let [input_token, _] = data.inputs.first()?;
+	let output = data.outputs.first()?;
+	Some(format!(
+		"{}:{}:{}:{}",
+		data.origin_chain_id,
+		with_0x_prefix(&bytes32_to_address(&input_token.to_be_bytes::<32>())),
+		output.chain_id,
+		with_0x_prefix(&bytes32_to_address(&output.token)),
+	))
+}
+
+/// Records the fill time of an order filled at `filled_at`.
+///
+/// Orders without a token pair are not recorded.
+pub async fn record_fill_time(
+	storage: &StorageService,
+	order: &Order,
+	filled_at: u64,
+) -> Result<(), StorageError> {
+	let Some(pair) = token_pair(order) else {
+		return Ok(());
+	};
+	let record = FillTimeRecord {
+		order_id: order.id.clone(),
+		pair,
+		fill_time_seconds: filled_at.saturating_sub(order.created_at),
+		filled_at,
+	};
+	storage
+		.store(
+			StorageKey::Analytics.as_str(),
+			&order.id,
+			&record,
+			Some(
+				StorageIndexes::new()
+					.with_field("pair", &record.pair)
+					.with_numeric("filled_at", filled_at),
+			),
+		)
+		.await
+}
+
+/// Predictor returning the same fill time for every order.
+pub struct StaticPredictor {
+	fill_time: Duration,
+}
+
+impl StaticPredictor {
+	/// Creates a predictor always returning `fill_time`.
+	pub fn new(fill_time: Duration) -> Self {
+		Self { fill_time }
+	}
+}
+
+#[async_trait]
+impl FillPredictor for StaticPredictor {
+	async fn predict_fill_time(&self, _order: &Order, _context: &ExecutionContext) -> Duration {
+		self.fill_time
+	}
+}
+
+/// Predictor returning the median recorded fill time of the token pair of
+/// the order.
+///
+/// Only the most recent `max_samples` fills are considered. Orders of pairs
+/// without recorded fills get the default fill time.
+pub struct HistoricalFillPredictor {
+	storage: Arc<StorageService>,
+	default_fill_time: Duration,
+	max_samples: usize,
+}
+
+impl HistoricalFillPredictor {
+	/// Creates a predictor reading fill times from `storage`.
+	pub fn new(
+		storage: Arc<StorageService>,
+		default_fill_time: Duration,
+		max_samples: usize,
+	) -> Self {
+		Self {
+			storage,
+			default_fill_time,
+			max_samples,
+		}
+	}
+
+	/// Returns the median fill time of the most recent fills of a pair.
+	async fn median_fill_time(&self, pair: &str) -> Result<Option<Duration>, StorageError> {
+		let mut records: Vec<FillTimeRecord> = self
+			.storage
+			.query(
+				StorageKey::Analytics.as_str(),
+				QueryFilter::Equals("pair".to_string(), serde_json::json!(pair)),
+			)
+			.await?
+			.into_iter()
+			.map(|(_, record)| record)
+			.collect();
+		records.sort_unstable_by(|a: &FillTimeRecord, b| b.filled_at.cmp(&a.filled_at));
+		records.truncate(self.max_samples);
+
+		let fill_times: Vec<u64> = records
+			.iter()
+			.map(|record| record.fill_time_seconds)
+			.collect();
+		Ok(median(fill_times).map(Duration::from_secs))
+	}
+}
+
+#[async_trait]
+impl FillPredictor for HistoricalFillPredictor {
+	async fn predict_fill_time(&self, order: &Order, _context: &ExecutionContext) -> Duration {
+		let Some(pair) = token_pair(order) else {
+			return self.default_fill_time;
+		};
+		match self.median_fill_time(&pair).await {
+			Ok(fill_time) => fill_time.unwrap_or(self.default_fill_time),
+			Err(e) => {
+				tracing::debug!(order_id = %order.id, error = %e, "Failed to load fill times");
+				self.default_fill_time
+			},
+		}
+	}
+}
+
+/// Returns the median of a set of values, rounding down between the two
+/// middle values of an even set.
+fn median(mut values: Vec<u64>) -> Option<u64> {
+	if values.is_empty() {
+		return None;
+	}
+	values.sort_unstable();
+	let mid = values.len() / 2;
+	Some(if values.len() % 2 == 0 {
+		(values[mid - 1] + values[mid]) / 2
+	} else {
+		values[mid]
+	})
+}
+
+/// Returns false if the order is expected to be filled only after its fill
+/// deadline.
+///
+/// The predicted fill time is counted from the execution context timestamp.
+/// Orders without a parsable fill deadline are always executed.
+pub async fn should_execute_by_deadline(
+	order: &Order,
+	predictor: &dyn FillPredictor,
+	context: &ExecutionContext,
+) -> bool {
+	let Ok(data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+		return true;
+	};
+	let fill_time = predictor.predict_fill_time(order, context).await;
+	context.timestamp.saturating_add(fill_time.as_secs()) <= u64::from(data.fill_deadline)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::U256;
+	use solver_storage::implementations::file::create_storage;
+	use solver_types::{
+		standards::eip7683::{GasLimitOverrides, MandateOutput},
+		Address, OrderStatus,
+	};
+	use std::collections::HashMap;
+
+	fn test_order(id: &str, created_at: u64, fill_deadline: u32) -> Order {
+		let mut input_token = [0u8; 32];
+		input_token[12..].copy_from_slice(&[0xAA; 20]);
+		let mut output_token = [0u8; 32];
+		output_token[12..].copy_from_slice(&[0xBB; 20]);
+		let data = Eip7683OrderData {
+			user: with_0x_prefix(&"11".repeat(20)),
+			nonce: U256::from(1),
+			origin_chain_id: U256::from(1),
+			expires: fill_deadline,
+			fill_deadline,
+			input_oracle: with_0x_prefix(&"00".repeat(20)),
+			inputs: vec![[U256::from_be_bytes(input_token), U256::from(100)]],
+			order_id: [0u8; 32],
+			gas_limit_overrides: GasLimitOverrides::default(),
+			outputs: vec![MandateOutput {
+				oracle: [0u8; 32],
+				settler: [0u8; 32],
+				chain_id: U256::from(10),
+				token: output_token,
+				amount: U256::from(100),
+				recipient: [0u8; 32],
+				call: Vec::new(),
+				context: Vec::new(),
+			}],
+			raw_order_data: None,
+			signature: None,
+			sponsor: None,
+			lock_type: Default::default(),
+		};
+		Order {
+			id: id.to_string(),
+			standard: "eip7683".to_string(),
+			created_at,
+			updated_at: created_at,
+			status: OrderStatus::Created,
+			data: serde_json::to_value(&data).unwrap(),
+			solver_address: Address(vec![0; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![10],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		}
+	}
+
+	fn test_context(timestamp: u64) -> ExecutionContext {
+		ExecutionContext {
+			chain_data: HashMap::new(),
+			solver_balances: HashMap::new(),
+			timestamp,
+			gas_budget_remaining: HashMap::new(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_historical_prediction_and_deadline() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let backend = create_storage(&toml::Value::Table(config)).unwrap();
+		let storage = Arc::new(StorageService::new(backend, None));
+		let order = test_order("new", 1_000, 1_100);
+		assert_eq!(
+			token_pair(&order).unwrap(),
+			format!("1:0x{}:10:0x{}", "aa".repeat(20), "bb".repeat(20))
+		);
+
+		for (id, fill_time) in [("a", 30), ("b", 90), ("c", 60)] {
+			record_fill_time(&storage, &test_order(id, 0, 0), fill_time)
+				.await
+				.unwrap();
+		}
+
+		let predictor = HistoricalFillPredictor::new(storage, Duration::from_secs(600), 10);
+		let context = test_context(1_000);
+		assert_eq!(
+			predictor.predict_fill_time(&order, &context).await,
+			Duration::from_secs(60)
+		);
+		assert!(should_execute_by_deadline(&order, &predictor, &context).await);
+		assert!(!should_execute_by_deadline(&order, &predictor, &test_context(1_050)).await);
+
+		let predictor = StaticPredictor::new(Duration::from_secs(200));
+		assert!(!should_execute_by_deadline(&order, &predictor, &context).await);
+	}
+}
@@ -0,0 +1,725 @@
+//! Just-in-time sourcing of output tokens from DEXes.
+//!
+//! When the execution parameters select an AMM swap, the fill transaction
+//! first buys the output tokens with the chain's funding token and then
+//! fills the order. The approval, the swap and the fill are batched in one
+//! `execute` call on the solver's own account, which must delegate through
+//! EIP-7702 to the configured executor of the chain, so that every call is
+//! still executed with the solver as sender. The executor must only accept
+//! calls from the account itself (`msg.sender == address(this)`), and the
+//! delegation is checked before every swap.
+//!
+//! The funding tokens sold are quoted on the DEX before the fill: through
+//! the Uniswap V3 quoter, or through a 1inch quote of the output tokens in
+//! funding tokens. A swap sells at most the quoted amount plus the maximum
+//! slippage.
+
+use crate::{OrderError, TransactionSimulator};
+use alloy_primitives::{hex, Address as AlloyAddress, Bytes, U256};
+use alloy_sol_types::{sol, SolCall};
+use serde::Deserialize;
+use solver_types::{
+	delegation_designator, with_0x_prefix, without_0x_prefix, Address, Field, FieldType, Schema,
+	SwapDex, Transaction,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default 1inch API.
+const DEFAULT_ONE_INCH_API_URL: &str = "https://api.1inch.dev";
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// Default Uniswap V3 pool fee tier, in hundredths of a basis point.
+const DEFAULT_UNISWAP_V3_FEE: u32 = 500;
+
+sol! {
+	/// Batch executor the solver's account delegates to, reverting unless
+	/// called by the account itself.
+	interface IBatchExecutor {
+		struct Call {
+			address target;
+			uint256 value;
+			bytes data;
+		}
+
+		function execute(Call[] calldata calls) external payable;
+	}
+
+	/// ERC-20 approval of the DEX spending funding tokens.
+	interface IFundingToken {
+		function approve(address spender, uint256 amount) external returns (bool);
+	}
+
+	/// Uniswap V3 SwapRouter02 subset used to buy output tokens.
+	interface IUniswapV3Router {
+		struct ExactOutputParams {
+			bytes path;
+			address recipient;
+			uint256 amountOut;
+			uint256 amountInMaximum;
+		}
+
+		function exactOutput(ExactOutputParams calldata params) external payable returns (uint256 amountIn);
+	}
+
+	/// Uniswap V3 QuoterV2 subset used to price output tokens.
+	interface IUniswapV3Quoter {
+		function quoteExactOutput(bytes memory path, uint256 amountOut) external returns (uint256 amountIn, uint160[] memory sqrtPriceX96AfterList, uint32[] memory initializedTicksCrossedList, uint256 gasEstimate);
+	}
+}
+
+/// The `output_swap` table of an order implementation configuration.
+#[derive(Debug, Deserialize)]
+struct OutputSwapConfig {
+	/// Executor the solver's account delegates to, by chain ID.
+	executors: HashMap<String, String>,
+	/// Token sold for output tokens, by chain ID.
+	funding_tokens: HashMap<String, String>,
+	#[serde(default)]
+	uniswap_v3: Option<UniswapV3Config>,
+	#[serde(default, rename = "1inch")]
+	one_inch: Option<OneInchConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UniswapV3Config {
+	/// SwapRouter02 address, by chain ID.
+	routers: HashMap<String, String>,
+	/// QuoterV2 address, by chain ID.
+	quoters: HashMap<String, String>,
+	/// Fee tier of the pools swapped through.
+	#[serde(default = "default_uniswap_v3_fee")]
+	fee: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct OneInchConfig {
+	#[serde(default)]
+	api_url: Option<String>,
+	#[serde(default)]
+	api_key: Option<String>,
+}
+
+fn default_uniswap_v3_fee() -> u32 {
+	DEFAULT_UNISWAP_V3_FEE
+}
+
+/// Returns the schema field of the `output_swap` table.
+pub(crate) fn config_field() -> Field {
+	let by_chain = || FieldType::Table(Schema::new(vec![], vec![]));
+	Field::new(
+		"output_swap",
+		FieldType::Table(Schema::new(
+			vec![
+				Field::new("executors", by_chain()).with_description(
+					"Batch executor the solver's account delegates to, by chain ID",
+				),
+				Field::new("funding_tokens", by_chain())
+					.with_description("Token sold for output tokens, by chain ID"),
+			],
+			vec![
+				Field::new(
+					"uniswap_v3",
+					FieldType::Table(Schema::new(
+						vec![
+							Field::new("routers", by_chain())
+								.with_description("SwapRouter02 address, by chain ID"),
+							Field::new("quoters", by_chain())
+								.with_description("QuoterV2 address, by chain ID"),
+						],
+						vec![Field::new(
+							"fee",
+							FieldType::Integer {
+								min: Some(0),
+								max: None,
+							},
+						)
+						.with_description("Fee tier of the pools swapped through")
+						.with_default(DEFAULT_UNISWAP_V3_FEE)],
+					)),
+				)
+				.with_description("Swaps through Uniswap V3 routers"),
+				Field::new(
+					"1inch",
+					FieldType::Table(Schema::new(
+						vec![],
+						vec![
+							Field::new("api_url", FieldType::String)
+								.with_description("URL of the 1inch API")
+								.with_default(DEFAULT_ONE_INCH_API_URL),
+							Field::new("api_key", FieldType::String)
+								.with_description("Key of the 1inch API"),
+						],
+					)),
+				)
+				.with_description("Swaps through the 1inch API"),
+			],
+		)),
+	)
+	.with_description("Buys output tokens the solver lacks on a DEX")
+}
+
+/// Quote of a 1inch swap.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OneInchQuote {
+	dst_amount: String,
+}
+
+/// Swap transaction returned by the 1inch API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OneInchSwap {
+	dst_amount: String,
+	tx: OneInchTx,
+}
+
+#[derive(Debug, Deserialize)]
+struct OneInchTx {
+	to: String,
+	data: String,
+}
+
+/// 1inch API client.
+struct OneInch {
+	client: reqwest::Client,
+	api_url: String,
+	api_key: Option<String>,
+}
+
+/// Funding tokens sold by a swap buying the outputs of a fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapQuote {
+	/// Chain the swap is executed on.
+	pub chain_id: u64,
+	/// Token sold for the output tokens.
+	pub funding_token: AlloyAddress,
+	/// Maximum amount of funding tokens sold, slippage included.
+	pub amount_in_max: U256,
+}
+
+/// Builds the calls buying output tokens on a DEX.
+pub struct OutputSwapper {
+	executors: HashMap<u64, AlloyAddress>,
+	funding_tokens: HashMap<u64, AlloyAddress>,
+	uniswap_routers: HashMap<u64, AlloyAddress>,
+	uniswap_quoters: HashMap<u64, AlloyAddress>,
+	uniswap_fee: u32,
+	one_inch: Option<OneInch>,
+}
+
+impl OutputSwapper {
+	/// Creates the swapper from the `output_swap` table of an order
+	/// implementation configuration, if present.
+	pub fn from_config(config: &toml::Value) -> Result<Option<Self>, OrderError> {
+		let Some(value) = config.get("output_swap") else {
+			return Ok(None);
+		};
+		let config: OutputSwapConfig = value
+			.clone()
+			.try_into()
+			.map_err(|e| OrderError::InvalidOrder(format!("Invalid output_swap: {}", e)))?;
+
+		let (uniswap_routers, uniswap_quoters, uniswap_fee) = match &config.uniswap_v3 {
+			Some(uniswap) => (
+				parse_addresses(&uniswap.routers)?,
+				parse_addresses(&uniswap.quoters)?,
+				uniswap.fee,
+			),
+			None => (HashMap::new(), HashMap::new(), DEFAULT_UNISWAP_V3_FEE),
+		};
+		let one_inch = config
+			.one_inch
+			.map(|one_inch| {
+				let client = reqwest::Client::builder()
+					.timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+					.build()
+					.map_err(|e| {
+						OrderError::InvalidOrder(format!("Failed to build 1inch client: {}", e))
+					})?;
+				Ok::<_, OrderError>(OneInch {
+					client,
+					api_url: one_inch
+						.api_url
+						.unwrap_or_else(|| DEFAULT_ONE_INCH_API_URL.to_string()),
+					api_key: one_inch.api_key,
+				})
+			})
+			.transpose()?;
+
+		Ok(Some(Self {
+			executors: parse_addresses(&config.executors)?,
+			funding_tokens: parse_addresses(&config.funding_tokens)?,
+			uniswap_routers,
+			uniswap_quoters,
+			uniswap_fee,
+			one_inch,
+		}))
+	}
+
+	/// Quotes the funding tokens sold to buy `amount_out` of `output_token`
+	/// on a chain, slippage included.
+	pub async fn quote(
+		&self,
+		dex: SwapDex,
+		max_slippage_bps: u16,
+		chain_id: u64,
+		output_token: AlloyAddress,
+		amount_out: U256,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<SwapQuote, OrderError> {
+		let funding_token = self.funding_token(chain_id)?;
+		let amount_in = match dex {
+			SwapDex::UniswapV3 => {
+				let quoter = *self.uniswap_quoters.get(&chain_id).ok_or_else(|| {
+					OrderError::ValidationFailed(format!(
+						"No Uniswap V3 quoter configured for chain {}",
+						chain_id
+					))
+				})?;
+				let data = IUniswapV3Quoter::quoteExactOutputCall {
+					path: exact_output_path(output_token, self.uniswap_fee, funding_token).into(),
+					amountOut: amount_out,
+				}
+				.abi_encode();
+				let trace = simulator
+					.simulate(&Transaction {
+						to: Some(Address(quoter.to_vec())),
+						data,
+						value: U256::ZERO,
+						chain_id,
+						nonce: None,
+						gas_limit: None,
+						gas_price: None,
+						max_fee_per_gas: None,
+						max_priority_fee_per_gas: None,
+						memo: None,
+						authorization_list: None,
+					})
+					.await?;
+				if !trace.success {
+					return Err(OrderError::ValidationFailed(format!(
+						"Uniswap V3 quote failed: {}",
+						trace
+							.revert_reason
+							.unwrap_or_else(|| "unknown reason".to_string())
+					)));
+				}
+				let data = hex::decode(without_0x_prefix(&trace.return_data)).map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid Uniswap V3 quote: {}", e))
+				})?;
+				IUniswapV3Quoter::quoteExactOutputCall::abi_decode_returns(&data, true)
+					.map_err(|e| {
+						OrderError::ValidationFailed(format!("Invalid Uniswap V3 quote: {}", e))
+					})?
+					.amountIn
+			},
+			SwapDex::OneInch => {
+				// 1inch only quotes exact inputs, so the output tokens are
+				// priced in funding tokens
+				let quote = self
+					.one_inch()?
+					.quote(chain_id, output_token, funding_token, amount_out)
+					.await?;
+				quote.dst_amount.parse::<U256>().map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid 1inch amount: {}", e))
+				})?
+			},
+		};
+
+		Ok(SwapQuote {
+			chain_id,
+			funding_token,
+			amount_in_max: amount_in * U256::from(10_000 + u64::from(max_slippage_bps))
+				/ U256::from(10_000),
+		})
+	}
+
+	/// Returns the calls approving the DEX and buying `amount_out` of
+	/// `output_token` for `solver` on a chain.
+	///
+	/// Fails unless the solver's account delegates to the executor of the
+	/// chain, as the calls would otherwise not be executed by the account.
+	#[allow(clippy::too_many_arguments)]
+	pub async fn swap_calls(
+		&self,
+		dex: SwapDex,
+		max_slippage_bps: u16,
+		chain_id: u64,
+		output_token: AlloyAddress,
+		amount_out: U256,
+		solver: AlloyAddress,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<Vec<IBatchExecutor::Call>, OrderError> {
+		self.verify_delegation(chain_id, solver, simulator).await?;
+		let quote = self
+			.quote(
+				dex,
+				max_slippage_bps,
+				chain_id,
+				output_token,
+				amount_out,
+				simulator,
+			)
+			.await?;
+		let amount_in_max = quote.amount_in_max;
+
+		let (spender, swap_data) = match dex {
+			SwapDex::UniswapV3 => {
+				let router = *self.uniswap_routers.get(&chain_id).ok_or_else(|| {
+					OrderError::ValidationFailed(format!(
+						"No Uniswap V3 router configured for chain {}",
+						chain_id
+					))
+				})?;
+				let data = IUniswapV3Router::exactOutputCall {
+					params: IUniswapV3Router::ExactOutputParams {
+						path: exact_output_path(
+							output_token,
+							self.uniswap_fee,
+							quote.funding_token,
+						)
+						.into(),
+						recipient: solver,
+						amountOut: amount_out,
+						amountInMaximum: amount_in_max,
+					},
+				}
+				.abi_encode();
+				(router, data)
+			},
+			SwapDex::OneInch => {
+				let swap = self
+					.one_inch()?
+					.swap(
+						chain_id,
+						quote.funding_token,
+						output_token,
+						amount_in_max,
+						solver,
+						max_slippage_bps,
+					)
+					.await?;
+				let dst_amount = swap.dst_amount.parse::<U256>().map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid 1inch amount: {}", e))
+				})?;
+				if dst_amount < amount_out {
+					return Err(OrderError::ValidationFailed(format!(
+						"1inch quote {} is below the output amount {}",
+						dst_amount, amount_out
+					)));
+				}
+				let router = swap.tx.to.parse::<AlloyAddress>().map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid 1inch router: {}", e))
+				})?;
+				let data = hex::decode(&swap.tx.data).map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid 1inch calldata: {}", e))
+				})?;
+				(router, data)
+			},
+		};
+
+		Ok(vec![
+			call(
+				quote.funding_token,
+				IFundingToken::approveCall {
+					spender,
+					amount: amount_in_max,
+				}
+				.abi_encode(),
+			),
+			call(spender, swap_data),
+		])
+	}
+
+	/// Checks that the solver's account delegates to the executor of a chain.
+	async fn verify_delegation(
+		&self,
+		chain_id: u64,
+		solver: AlloyAddress,
+		simulator: &dyn TransactionSimulator,
+	) -> Result<(), OrderError> {
+		let executor = self.executors.get(&chain_id).ok_or_else(|| {
+			OrderError::ValidationFailed(format!(
+				"No swap executor configured for chain {}",
+				chain_id
+			))
+		})?;
+		let code = simulator
+			.get_code(chain_id, &with_0x_prefix(&hex::encode(solver)))
+			.await?;
+		if code != delegation_designator(&Address(executor.to_vec())) {
+			return Err(OrderError::ValidationFailed(format!(
+				"Solver account does not delegate to swap executor {} on chain {}",
+				executor, chain_id
+			)));
+		}
+		Ok(())
+	}
+
+	/// Returns the funding token of a chain.
+	fn funding_token(&self, chain_id: u64) -> Result<AlloyAddress, OrderError> {
+		self.funding_tokens.get(&chain_id).copied().ok_or_else(|| {
+			OrderError::ValidationFailed(format!(
+				"No funding token configured for chain {}",
+				chain_id
+			))
+		})
+	}
+
+	/// Returns the 1inch client.
+	fn one_inch(&self) -> Result<&OneInch, OrderError> {
+		self.one_inch.as_ref().ok_or_else(|| {
+			OrderError::ValidationFailed("1inch swaps are not configured".to_string())
+		})
+	}
+}
+
+impl OneInch {
+	/// Fetches a quote of selling `amount` of `src` for `dst`.
+	async fn quote(
+		&self,
+		chain_id: u64,
+		src: AlloyAddress,
+		dst: AlloyAddress,
+		amount: U256,
+	) -> Result<OneInchQuote, OrderError> {
+		let mut request = self
+			.client
+			.get(format!("{}/swap/v6.0/{}/quote", self.api_url, chain_id))
+			.query(&[
+				("src", src.to_string()),
+				("dst", dst.to_string()),
+				("amount", amount.to_string()),
+			]);
+		if let Some(api_key) = &self.api_key {
+			request = request.bearer_auth(api_key);
+		}
+		request
+			.send()
+			.await
+			.and_then(|response| response.error_for_status())
+			.map_err(|e| {
+				OrderError::ValidationFailed(format!("1inch quote request failed: {}", e))
+			})?
+			.json()
+			.await
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid 1inch response: {}", e)))
+	}
+
+	/// Fetches a swap selling `amount` of `src` for `dst`.
+	async fn swap(
+		&self,
+		chain_id: u64,
+		src: AlloyAddress,
+		dst: AlloyAddress,
+		amount: U256,
+		from: AlloyAddress,
+		max_slippage_bps: u16,
+	) -> Result<OneInchSwap, OrderError> {
+		let slippage_pct = (f64::from(max_slippage_bps) / 100.0).to_string();
+		let mut request = self
+			.client
+			.get(format!("{}/swap/v6.0/{}/swap", self.api_url, chain_id))
+			.query(&[
+				("src", src.to_string()),
+				("dst", dst.to_string()),
+				("amount", amount.to_string()),
+				("from", from.to_string()),
+				("origin", from.to_string()),
+				("slippage", slippage_pct),
+				("disableEstimate", "true".to_string()),
+			]);
+		if let Some(api_key) = &self.api_key {
+			request = request.bearer_auth(api_key);
+		}
+		request
+			.send()
+			.await
+			.and_then(|response| response.error_for_status())
+			.map_err(|e| OrderError::ValidationFailed(format!("1inch swap request failed: {}", e)))?
+			.json()
+			.await
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid 1inch response: {}", e)))
+	}
+}
+
+/// Returns a call of the batch, which reverts the whole batch on failure.
+pub fn call(target: AlloyAddress, data: Vec<u8>) -> IBatchExecutor::Call {
+	IBatchExecutor::Call {
+		target,
+		value: U256::ZERO,
+		data: Bytes::from(data),
+	}
+}
+
+/// Encodes the `execute` call executing the calls in order.
+pub fn execute(calls: Vec<IBatchExecutor::Call>) -> Vec<u8> {
+	IBatchExecutor::executeCall { calls }.abi_encode()
+}
+
+/// Encodes the Uniswap V3 path of an exact output swap, which lists the
+/// output token first.
+fn exact_output_path(token_out: AlloyAddress, fee: u32, token_in: AlloyAddress) -> Vec<u8> {
+	let mut path = Vec::with_capacity(43);
+	path.extend_from_slice(token_out.as_slice());
+	path.extend_from_slice(&fee.to_be_bytes()[1..]);
+	path.extend_from_slice(token_in.as_slice());
+	path
+}
+
+/// Parses addresses keyed by chain ID.
+fn parse_addresses(
+	addresses: &HashMap<String, String>,
+) -> Result<HashMap<u64, AlloyAddress>, OrderError> {
+	addresses
+		.iter()
+		.map(|(chain_id, address)| {
+			let chain = chain_id.parse::<u64>().map_err(|e| {
+				OrderError::InvalidOrder(format!("Invalid chain ID {}: {}", chain_id, e))
+			})?;
+			let address = address.parse::<AlloyAddress>().map_err(|e| {
+				OrderError::InvalidOrder(format!(
+					"Invalid address {} for chain {}: {}",
+					address, chain_id, e
+				))
+			})?;
+			Ok((chain, address))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_sol_types::SolValue;
+	use async_trait::async_trait;
+	use solver_types::TransactionTrace;
+
+	/// Quotes 9_900 funding tokens for every swap, with the solver's account
+	/// delegating to `executor`.
+	struct QuoteSimulator {
+		executor: AlloyAddress,
+	}
+
+	#[async_trait]
+	impl TransactionSimulator for QuoteSimulator {
+		async fn simulate(&self, tx: &Transaction) -> Result<TransactionTrace, OrderError> {
+			assert_eq!(tx.to, Some(Address(vec![0x55; 20])));
+			let quote = (
+				U256::from(9_900),
+				Vec::<U256>::new(),
+				Vec::<u32>::new(),
+				U256::ZERO,
+			)
+				.abi_encode_params();
+			Ok(TransactionTrace {
+				success: true,
+				gas_used: None,
+				return_data: with_0x_prefix(&hex::encode(quote)),
+				revert_reason: None,
+				logs: Vec::new(),
+			})
+		}
+
+		async fn get_code(&self, _chain_id: u64, _address: &str) -> Result<Vec<u8>, OrderError> {
+			Ok(delegation_designator(&Address(self.executor.to_vec())))
+		}
+	}
+
+	#[tokio::test]
+	async fn test_uniswap_swap_calls() {
+		let config: toml::Value = toml::from_str(
+			r#"
+			[output_swap.executors]
+			10 = "0x6666666666666666666666666666666666666666"
+
+			[output_swap.funding_tokens]
+			10 = "0x1111111111111111111111111111111111111111"
+
+			[output_swap.uniswap_v3.routers]
+			10 = "0x2222222222222222222222222222222222222222"
+
+			[output_swap.uniswap_v3.quoters]
+			10 = "0x5555555555555555555555555555555555555555"
+			"#,
+		)
+		.unwrap();
+		let swapper = OutputSwapper::from_config(&config).unwrap().unwrap();
+		let simulator = QuoteSimulator {
+			executor: AlloyAddress::repeat_byte(0x66),
+		};
+
+		let calls = swapper
+			.swap_calls(
+				SwapDex::UniswapV3,
+				50,
+				10,
+				AlloyAddress::repeat_byte(0x33),
+				U256::from(10_000),
+				AlloyAddress::repeat_byte(0x44),
+				&simulator,
+			)
+			.await
+			.unwrap();
+		assert_eq!(calls[0].target, AlloyAddress::repeat_byte(0x11));
+		let approve = IFundingToken::approveCall::abi_decode(&calls[0].data, true).unwrap();
+		// The quoted 9_900 funding tokens plus 0.5% slippage
+		assert_eq!(approve.amount, U256::from(9_949));
+
+		assert_eq!(calls[1].target, AlloyAddress::repeat_byte(0x22));
+		let swap = IUniswapV3Router::exactOutputCall::abi_decode(&calls[1].data, true).unwrap();
+		assert_eq!(swap.params.path.len(), 43);
+		assert_eq!(&swap.params.path[..20], &[0x33; 20]);
+		assert_eq!(swap.params.recipient, AlloyAddress::repeat_byte(0x44));
+		assert_eq!(swap.params.amountInMaximum, U256::from(9_949));
+
+		assert!(swapper
+			.swap_calls(
+				SwapDex::OneInch,
+				50,
+				10,
+				AlloyAddress::repeat_byte(0x33),
+				U256::from(10_000),
+				AlloyAddress::repeat_byte(0x44),
+				&simulator,
+			)
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_swap_requires_delegation_to_executor() {
+		let config: toml::Value = toml::from_str(
+			r#"
+			[output_swap.executors]
+			10 = "0x6666666666666666666666666666666666666666"
+
+			[output_swap.funding_tokens]
+			10 = "0x1111111111111111111111111111111111111111"
+
+			[output_swap.uniswap_v3]
+			routers = { 10 = "0x2222222222222222222222222222222222222222" }
+			quoters = { 10 = "0x5555555555555555555555555555555555555555" }
+			"#,
+		)
+		.unwrap();
+		let swapper = OutputSwapper::from_config(&config).unwrap().unwrap();
+		let simulator = QuoteSimulator {
+			executor: AlloyAddress::repeat_byte(0x77),
+		};
+
+		let result = swapper
+			.swap_calls(
+				SwapDex::UniswapV3,
+				50,
+				10,
+				AlloyAddress::repeat_byte(0x33),
+				U256::from(10_000),
+				AlloyAddress::repeat_byte(0x44),
+				&simulator,
+			)
+			.await;
+		assert!(result.is_err());
+	}
+}
@@ -0,0 +1,252 @@
+//! Registry of order implementations by standard and version.
+//!
+//! Order standards evolve, and a solver may see orders of several versions of
+//! the same standard at once. Intents and orders carry their version as an
+//! optional `version` field of their data; data without it is treated as the
+//! first version of its standard.
+
+use crate::OrderInterface;
+use solver_types::Intent;
+use std::collections::HashMap;
+
+/// Version assumed for intents and orders that do not carry one.
+pub const DEFAULT_STANDARD_VERSION: u8 = 1;
+
+/// Returns the standard version declared by intent or order data, if any.
+pub fn standard_version(data: &serde_json::Value) -> Option<u8> {
+	data.get("version")
+		.and_then(|version| version.as_u64())
+		.and_then(|version| u8::try_from(version).ok())
+}
+
+/// Returns the standard version of an intent, defaulting to the first version.
+pub fn intent_version(intent: &Intent) -> u8 {
+	standard_version(&intent.data).unwrap_or(DEFAULT_STANDARD_VERSION)
+}
+
+/// Order implementations keyed by standard name and version.
+#[derive(Default)]
+pub struct VersionRegistry {
+	implementations: HashMap<(String, u8), Box<dyn OrderInterface>>,
+}
+
+impl VersionRegistry {
+	/// Creates an empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers the implementation of a version of a standard, replacing any
+	/// implementation previously registered for it.
+	pub fn register(
+		&mut self,
+		standard: impl Into<String>,
+		version: u8,
+		implementation: Box<dyn OrderInterface>,
+	) {
+		self.implementations
+			.insert((standard.into(), version), implementation);
+	}
+
+	/// Returns the implementation of a standard at the given version, or at
+	/// the default version when none is given.
+	pub fn get(&self, standard: &str, version: Option<u8>) -> Option<&dyn OrderInterface> {
+		self.implementations
+			.get(&(
+				standard.to_string(),
+				version.unwrap_or(DEFAULT_STANDARD_VERSION),
+			))
+			.map(|implementation| implementation.as_ref())
+	}
+
+	/// Returns the implementations supporting a version of a standard.
+	///
+	/// The implementation registered for the standard and version comes
+	/// first, followed by the other implementations supporting it in order
+	/// of their registration key.
+	pub fn supporting(&self, standard: &str, version: u8) -> Vec<&dyn OrderInterface> {
+		let mut others: Vec<_> = self
+			.implementations
+			.iter()
+			.filter(|((name, registered_version), implementation)| {
+				(name != standard || *registered_version != version)
+					&& implementation.supports_standard(standard, version)
+			})
+			.collect();
+		others.sort_by(|(a, _), (b, _)| a.cmp(b));
+		self.get(standard, Some(version))
+			.into_iter()
+			.chain(
+				others
+					.into_iter()
+					.map(|(_, implementation)| implementation.as_ref()),
+			)
+			.collect()
+	}
+
+	/// Returns the registered versions of a standard in ascending order.
+	pub fn versions(&self, standard: &str) -> Vec<u8> {
+		let mut versions: Vec<u8> = self
+			.implementations
+			.keys()
+			.filter(|(name, _)| name == standard)
+			.map(|(_, version)| *version)
+			.collect();
+		versions.sort_unstable();
+		versions
+	}
+}
+
+impl From<HashMap<String, Box<dyn OrderInterface>>> for VersionRegistry {
+	/// Registers each implementation as the default version of its standard.
+	fn from(implementations: HashMap<String, Box<dyn OrderInterface>>) -> Self {
+		let mut registry = Self::new();
+		for (standard, implementation) in implementations {
+			registry.register(standard, DEFAULT_STANDARD_VERSION, implementation);
+		}
+		registry
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{OrderError, TransactionSimulator};
+	use async_trait::async_trait;
+	use solver_types::{
+		Address, ConfigSchema, ExecutionParams, FillProof, Intent, Order, SimulationResult,
+		Transaction,
+	};
+
+	/// Implementation identified by the version it was registered for.
+	struct VersionedImpl(u8);
+
+	#[async_trait]
+	impl OrderInterface for VersionedImpl {
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		fn supports_standard(&self, standard: &str, version: u8) -> bool {
+			standard == "eip7683" && version == self.0
+		}
+
+		async fn validate_intent(
+			&self,
+			_intent: &Intent,
+			_solver_address: &Address,
+		) -> Result<Order, OrderError> {
+			Err(OrderError::ValidationFailed(format!("v{}", self.0)))
+		}
+
+		async fn generate_prepare_transaction(
+			&self,
+			_intent: &Intent,
+			_order: &Order,
+			_params: &ExecutionParams,
+		) -> Result<Option<Transaction>, OrderError> {
+			unimplemented!()
+		}
+
+		async fn generate_fill_transaction(
+			&self,
+			_order: &Order,
+			_params: &ExecutionParams,
+			_simulator: &dyn TransactionSimulator,
+		) -> Result<Transaction, OrderError> {
+			unimplemented!()
+		}
+
+		async fn generate_claim_transaction(
+			&self,
+			_order: &Order,
+			_fill_proof: &FillProof,
+		) -> Result<Transaction, OrderError> {
+			unimplemented!()
+		}
+
+		async fn simulate_fill_transaction(
+			&self,
+			_order: &Order,
+			_params: &ExecutionParams,
+			_simulator: &dyn TransactionSimulator,
+		) -> Result<SimulationResult, OrderError> {
+			unimplemented!()
+		}
+	}
+
+	fn intent(data: serde_json::Value) -> Intent {
+		serde_json::from_value(serde_json::json!({
+			"id": "intent",
+			"source": "test",
+			"standard": "eip7683",
+			"metadata": {
+				"requires_auction": false,
+				"exclusive_until": null,
+				"discovered_at": 0
+			},
+			"data": data,
+			"quote_id": null
+		}))
+		.unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_dispatch_by_version() {
+		let mut registry = VersionRegistry::from(HashMap::from([(
+			"eip7683".to_string(),
+			Box::new(VersionedImpl(1)) as Box<dyn OrderInterface>,
+		)]));
+		registry.register("eip7683", 2, Box::new(VersionedImpl(2)));
+		assert_eq!(registry.versions("eip7683"), vec![1, 2]);
+		assert!(registry.get("eip7683", Some(3)).is_none());
+
+		let solver = Address(vec![0; 20]);
+		for (data, expected) in [
+			(serde_json::json!({}), "v1"),
+			(serde_json::json!({ "version": 1 }), "v1"),
+			(serde_json::json!({ "version": 2 }), "v2"),
+		] {
+			let intent = intent(data);
+			let implementation = registry
+				.get(&intent.standard, standard_version(&intent.data))
+				.unwrap();
+			let err = implementation
+				.validate_intent(&intent, &solver)
+				.await
+				.unwrap_err();
+			assert_eq!(err.to_string(), format!("Validation failed: {}", expected));
+		}
+	}
+
+	#[tokio::test]
+	async fn test_routes_to_supporting_implementation() {
+		let mut registry = VersionRegistry::new();
+		registry.register("eip7683", 1, Box::new(VersionedImpl(1)));
+		// Registered under another standard, but supports eip7683 version 2
+		registry.register("other", 1, Box::new(VersionedImpl(2)));
+		assert_eq!(registry.supporting("eip7683", 2).len(), 1);
+		assert!(registry.supporting("eip7683", 3).is_empty());
+
+		let strategy = crate::implementations::strategies::simple::create_strategy(
+			&toml::Value::Table(toml::map::Map::new()),
+		)
+		.unwrap();
+		let service = crate::OrderService::new(registry, strategy);
+		let solver = Address(vec![0; 20]);
+		for (data, expected) in [
+			(serde_json::json!({ "version": 1 }), "v1"),
+			(serde_json::json!({ "version": 2 }), "v2"),
+			(
+				serde_json::json!({ "version": 3 }),
+				"Unsupported version 3 of standard eip7683",
+			),
+		] {
+			let err = service
+				.validate_intent(&intent(data), &solver)
+				.await
+				.unwrap_err();
+			assert_eq!(err.to_string(), format!("Validation failed: {}", expected));
+		}
+	}
+}
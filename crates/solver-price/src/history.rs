@@ -0,0 +1,243 @@
+//! Historical token price storage.
+//!
+//! This module persists price snapshots so that fills can be valued at the
+//! price observed when they happened rather than the current price. Lookups
+//! are sampled to at most one snapshot per token and minute. The snapshots of
+//! a token are stored in one bucket per hour, so a lookup reads a handful of
+//! buckets rather than every minute around its timestamp. Buckets have a TTL
+//! equal to the retention period, so the regular storage cleanup prunes old
+//! history.
+
+use crate::TokenPrice;
+use solver_storage::{StorageError, StorageService};
+use solver_types::StorageKey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Number of seconds between two snapshots of the same token.
+const SAMPLE_SECONDS: u64 = 60;
+
+/// Number of seconds covered by a bucket of snapshots.
+const BUCKET_SECONDS: u64 = 3_600;
+
+/// Number of seconds in a day.
+const DAY_SECONDS: u64 = 86_400;
+
+/// Distance in seconds searched first for the closest snapshot.
+const NEAR_SEARCH_SECONDS: u64 = 3_600;
+
+/// Maximum distance in seconds between a timestamp and its closest snapshot.
+const MAX_SEARCH_SECONDS: u64 = DAY_SECONDS;
+
+/// Persists and queries historical token price snapshots.
+pub struct HistoricalPriceStore {
+	/// Storage service used to persist snapshots.
+	storage: Arc<StorageService>,
+	/// How long snapshots are retained.
+	retention: Duration,
+	/// Last sampled minute per (chain_id, lowercase token address).
+	last_sampled: Mutex<HashMap<(u64, String), u64>>,
+}
+
+impl HistoricalPriceStore {
+	/// Creates a new HistoricalPriceStore retaining snapshots for the given number of days.
+	pub fn new(storage: Arc<StorageService>, retention_days: u64) -> Self {
+		Self {
+			storage,
+			retention: Duration::from_secs(retention_days.saturating_mul(DAY_SECONDS)),
+			last_sampled: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns whether a price should be recorded, claiming its minute if so.
+	///
+	/// Only the first price of each token per minute is sampled, which bounds
+	/// the snapshots of a token to one per minute.
+	pub fn should_record(&self, price: &TokenPrice) -> bool {
+		let minute = price.timestamp / SAMPLE_SECONDS;
+		let mut last_sampled = self.last_sampled.lock().unwrap();
+		let last = last_sampled
+			.entry((price.chain_id, price.token.to_lowercase()))
+			.or_insert(u64::MAX);
+		if *last == minute {
+			return false;
+		}
+		*last = minute;
+		true
+	}
+
+	/// Records a price snapshot at its observation timestamp.
+	///
+	/// A snapshot replaces any other snapshot of the token in the same minute.
+	pub async fn record(&self, price: &TokenPrice) -> Result<(), StorageError> {
+		let id = bucket_id(
+			price.chain_id,
+			&price.token,
+			price.timestamp / BUCKET_SECONDS,
+		);
+		let mut bucket = match self
+			.storage
+			.retrieve::<Vec<TokenPrice>>(StorageKey::PriceHistory.as_str(), &id)
+			.await
+		{
+			Ok(bucket) => bucket,
+			Err(StorageError::NotFound) => Vec::new(),
+			Err(e) => return Err(e),
+		};
+		let minute = price.timestamp / SAMPLE_SECONDS;
+		bucket.retain(|snapshot| snapshot.timestamp / SAMPLE_SECONDS != minute);
+		bucket.push(price.clone());
+
+		self.storage
+			.store_with_ttl(
+				StorageKey::PriceHistory.as_str(),
+				&id,
+				&bucket,
+				None,
+				Some(self.retention),
+			)
+			.await
+	}
+
+	/// Returns the stored snapshot closest to the given timestamp.
+	///
+	/// Searches the hour around the timestamp first and widens the search to
+	/// a day if it holds no snapshot. Returns `None` if no snapshot is
+	/// available in that range.
+	pub async fn get_price_at_timestamp(
+		&self,
+		token: &str,
+		chain_id: u64,
+		ts: u64,
+	) -> Option<TokenPrice> {
+		let token = token.to_lowercase();
+
+		for distance in [NEAR_SEARCH_SECONDS, MAX_SEARCH_SECONDS] {
+			let first_hour = ts.saturating_sub(distance) / BUCKET_SECONDS;
+			let last_hour = ts.saturating_add(distance) / BUCKET_SECONDS;
+			let ids: Vec<String> = (first_hour..=last_hour)
+				.map(|hour| bucket_id(chain_id, &token, hour))
+				.collect();
+			let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+			let mut closest: Option<TokenPrice> = None;
+			for (_, result) in self
+				.storage
+				.retrieve_batch::<Vec<TokenPrice>>(StorageKey::PriceHistory.as_str(), &ids)
+				.await
+			{
+				let bucket = match result {
+					Ok(bucket) => bucket,
+					Err(StorageError::NotFound) => continue,
+					Err(e) => {
+						tracing::warn!(
+							chain_id = chain_id,
+							token = %token,
+							error = %e,
+							"Failed to load price history"
+						);
+						continue;
+					},
+				};
+				for snapshot in bucket {
+					if snapshot.timestamp.abs_diff(ts) > distance {
+						continue;
+					}
+					let is_closer = closest
+						.as_ref()
+						.is_none_or(|c| snapshot.timestamp.abs_diff(ts) < c.timestamp.abs_diff(ts));
+					if is_closer {
+						closest = Some(snapshot);
+					}
+				}
+			}
+			if closest.is_some() {
+				return closest;
+			}
+		}
+
+		None
+	}
+}
+
+/// Builds the storage id for a token's bucket of snapshots of the given hour.
+fn bucket_id(chain_id: u64, token: &str, hour: u64) -> String {
+	format!("{}_{}_h{}", chain_id, token.to_lowercase(), hour)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	fn price(ts: u64, value: f64) -> TokenPrice {
+		TokenPrice {
+			chain_id: 1,
+			token: "0xAbCd".to_string(),
+			price_usd: value,
+			timestamp: ts,
+			source: "test".to_string(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_get_price_at_timestamp_returns_closest() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let store = HistoricalPriceStore::new(storage, 30);
+
+		let base = 10 * DAY_SECONDS;
+		store.record(&price(base - 100, 1.0)).await.unwrap();
+		store.record(&price(base + 50, 2.0)).await.unwrap();
+		store.record(&price(base + 5_000, 3.0)).await.unwrap();
+
+		let found = store
+			.get_price_at_timestamp("0xabcd", 1, base)
+			.await
+			.unwrap();
+		assert_eq!(found.price_usd, 2.0);
+
+		// A later snapshot of the same minute replaces the earlier one
+		store.record(&price(base + 55, 2.5)).await.unwrap();
+		let found = store
+			.get_price_at_timestamp("0xabcd", 1, base)
+			.await
+			.unwrap();
+		assert_eq!(found.price_usd, 2.5);
+
+		let found = store
+			.get_price_at_timestamp("0xABCD", 1, base + 4_000)
+			.await
+			.unwrap();
+		assert_eq!(found.price_usd, 3.0);
+
+		let found = store
+			.get_price_at_timestamp("0xabcd", 1, base + 20_000)
+			.await
+			.unwrap();
+		assert_eq!(found.price_usd, 3.0);
+
+		assert!(store
+			.get_price_at_timestamp("0xabcd", 2, base)
+			.await
+			.is_none());
+		assert!(store
+			.get_price_at_timestamp("0xabcd", 1, base + 30 * DAY_SECONDS)
+			.await
+			.is_none());
+	}
+
+	#[test]
+	fn test_should_record_samples_once_per_minute() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+		let store = HistoricalPriceStore::new(storage, 30);
+
+		assert!(store.should_record(&price(120, 1.0)));
+		assert!(!store.should_record(&price(150, 1.0)));
+		assert!(store.should_record(&price(180, 1.0)));
+
+		let mut other = price(180, 1.0);
+		other.chain_id = 2;
+		assert!(store.should_record(&other));
+	}
+}
@@ -0,0 +1,567 @@
+//! Uniswap V3 TWAP price feed implementation.
+//!
+//! This module provides a price feed that derives token prices from the
+//! time-weighted average tick of configured Uniswap V3 pools. TWAP prices
+//! cannot be moved within a single block, which makes them costly to manipulate.
+
+use crate::{PriceFeedError, PriceFeedInterface, TokenPrice};
+use alloy_primitives::{hex, Address as AlloyAddress};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::TransactionRequest;
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use solver_types::{
+	current_timestamp, with_0x_prefix, ConfigSchema, Field, FieldType, ImplementationRegistry,
+	NetworksConfig, Schema, SchemaDefinition,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Solidity type definitions for Uniswap V3 pool interactions.
+sol! {
+	/// Subset of the Uniswap V3 pool interface used for TWAP queries.
+	interface IUniswapV3Pool {
+		function observe(uint32[] secondsAgos) external view returns (int56[] tickCumulatives, uint160[] secondsPerLiquidityCumulativeX128s);
+		function liquidity() external view returns (uint128);
+		function token0() external view returns (address);
+		function token1() external view returns (address);
+		function fee() external view returns (uint24);
+	}
+
+	/// Subset of the ERC-20 interface used to read token decimals.
+	interface IERC20Metadata {
+		function decimals() external view returns (uint8);
+	}
+}
+
+/// Default TWAP window in seconds.
+const DEFAULT_TWAP_WINDOW_SECONDS: u32 = 300;
+
+/// Pool configuration for a single token.
+#[derive(Debug, Clone)]
+struct PoolConfig {
+	/// Address of the Uniswap V3 pool pairing the token with its quote token.
+	pool_address: AlloyAddress,
+	/// Decimals of the token being priced, read from the token contract if
+	/// neither configured nor found in the networks config.
+	token_decimals: Option<u8>,
+	/// Decimals of the pool's quote token (e.g. 6 for USDC, 18 for WETH).
+	quote_token_decimals: u8,
+	/// Whether the pool's quote token is a USD stablecoin.
+	quote_is_usd: bool,
+	/// TWAP window in seconds.
+	twap_window_seconds: u32,
+}
+
+/// Uniswap V3 TWAP price feed.
+///
+/// Prices each configured token against the other token of its pool. Pools
+/// flagged with `quote_is_usd` are quoted in a USD stablecoin. Other prices are
+/// converted to USD through the pool of the quote token (e.g. a token/WETH pool
+/// alongside a WETH/USDC pool on the same chain), which must itself be quoted
+/// in USD.
+pub struct UniswapV3TwapFeed {
+	/// RPC providers for each chain that has pools configured.
+	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
+	/// Pool configuration keyed by (chain_id, token address).
+	pools: HashMap<(u64, AlloyAddress), PoolConfig>,
+	/// Decimals read from token contracts, keyed by (chain_id, token address).
+	token_decimals: Mutex<HashMap<(u64, AlloyAddress), u8>>,
+}
+
+impl UniswapV3TwapFeed {
+	/// Creates a new UniswapV3TwapFeed for the given pools.
+	fn new(
+		networks: &NetworksConfig,
+		pools: HashMap<(u64, AlloyAddress), PoolConfig>,
+	) -> Result<Self, PriceFeedError> {
+		let mut providers = HashMap::new();
+
+		for (chain_id, _) in pools.keys() {
+			if providers.contains_key(chain_id) {
+				continue;
+			}
+
+			let network = networks.get(chain_id).ok_or_else(|| {
+				PriceFeedError::InvalidConfig(format!(
+					"Network {} not found in configuration",
+					chain_id
+				))
+			})?;
+
+			let http_url = network.get_http_url().ok_or_else(|| {
+				PriceFeedError::InvalidConfig(format!(
+					"No HTTP RPC URL configured for network {}",
+					chain_id
+				))
+			})?;
+
+			let provider = RootProvider::new_http(http_url.parse().map_err(|e| {
+				PriceFeedError::InvalidConfig(format!(
+					"Invalid RPC URL for network {}: {}",
+					chain_id, e
+				))
+			})?);
+
+			providers.insert(*chain_id, provider);
+		}
+
+		Ok(Self {
+			providers,
+			pools,
+			token_decimals: Mutex::new(HashMap::new()),
+		})
+	}
+
+	/// Executes a read-only call against a contract and decodes the result.
+	async fn call_contract<C: SolCall>(
+		&self,
+		chain_id: u64,
+		contract: AlloyAddress,
+		call: C,
+	) -> Result<C::Return, PriceFeedError> {
+		let provider = self.providers.get(&chain_id).ok_or_else(|| {
+			PriceFeedError::Network(format!("No provider configured for chain {}", chain_id))
+		})?;
+
+		let result = provider
+			.call(
+				&TransactionRequest::default()
+					.to(contract)
+					.input(call.abi_encode().into()),
+			)
+			.await
+			.map_err(|e| PriceFeedError::Network(format!("Contract call failed: {}", e)))?;
+
+		C::abi_decode_returns(&result, true).map_err(|e| {
+			PriceFeedError::Network(format!("Failed to decode contract response: {}", e))
+		})
+	}
+
+	/// Returns the decimals of a token, reading them from the token contract
+	/// once if they are not configured.
+	async fn token_decimals(
+		&self,
+		chain_id: u64,
+		token: AlloyAddress,
+		pool: &PoolConfig,
+	) -> Result<u8, PriceFeedError> {
+		if let Some(decimals) = pool.token_decimals {
+			return Ok(decimals);
+		}
+		if let Some(decimals) = self.token_decimals.lock().unwrap().get(&(chain_id, token)) {
+			return Ok(*decimals);
+		}
+
+		let decimals = self
+			.call_contract(chain_id, token, IERC20Metadata::decimalsCall {})
+			.await
+			.map_err(|e| {
+				PriceFeedError::PriceUnavailable(format!(
+					"Failed to read decimals of token {} on chain {}: {}",
+					token, chain_id, e
+				))
+			})?
+			._0;
+		self.token_decimals
+			.lock()
+			.unwrap()
+			.insert((chain_id, token), decimals);
+		Ok(decimals)
+	}
+
+	/// Returns the TWAP price of a token denominated in its pool's quote token,
+	/// along with the quote token address.
+	async fn price_in_quote(
+		&self,
+		chain_id: u64,
+		token: AlloyAddress,
+		pool: &PoolConfig,
+	) -> Result<(f64, AlloyAddress), PriceFeedError> {
+		let liquidity = self
+			.call_contract(
+				chain_id,
+				pool.pool_address,
+				IUniswapV3Pool::liquidityCall {},
+			)
+			.await?
+			._0;
+		if liquidity == 0 {
+			return Err(PriceFeedError::PriceUnavailable(format!(
+				"Pool {} on chain {} has no liquidity",
+				pool.pool_address, chain_id
+			)));
+		}
+
+		let token0 = self
+			.call_contract(chain_id, pool.pool_address, IUniswapV3Pool::token0Call {})
+			.await?
+			._0;
+		let token1 = self
+			.call_contract(chain_id, pool.pool_address, IUniswapV3Pool::token1Call {})
+			.await?
+			._0;
+
+		let (token_is_token0, quote_token) = if token == token0 {
+			(true, token1)
+		} else if token == token1 {
+			(false, token0)
+		} else {
+			return Err(PriceFeedError::InvalidConfig(format!(
+				"Token {} is not part of pool {}",
+				token, pool.pool_address
+			)));
+		};
+
+		let observation = self
+			.call_contract(
+				chain_id,
+				pool.pool_address,
+				IUniswapV3Pool::observeCall {
+					secondsAgos: vec![pool.twap_window_seconds, 0],
+				},
+			)
+			.await
+			.map_err(|e| {
+				PriceFeedError::PriceUnavailable(format!(
+					"Failed to observe pool {} over {}s: {}",
+					pool.pool_address, pool.twap_window_seconds, e
+				))
+			})?;
+
+		let [start, end] = observation.tickCumulatives.as_slice() else {
+			return Err(PriceFeedError::PriceUnavailable(
+				"Unexpected number of tick observations".to_string(),
+			));
+		};
+
+		let tick = average_tick(
+			start.as_i64(),
+			end.as_i64(),
+			pool.twap_window_seconds as i64,
+		);
+
+		let price = tick_to_price(
+			tick,
+			token_is_token0,
+			self.token_decimals(chain_id, token, pool).await?,
+			pool.quote_token_decimals,
+		);
+
+		Ok((price, quote_token))
+	}
+}
+
+/// Computes the time-weighted average tick from two tick cumulatives.
+///
+/// Rounds towards negative infinity, matching Uniswap's `OracleLibrary.consult`.
+pub fn average_tick(start_cumulative: i64, end_cumulative: i64, window_seconds: i64) -> i64 {
+	let delta = end_cumulative - start_cumulative;
+	let mut tick = delta / window_seconds;
+	if delta < 0 && delta % window_seconds != 0 {
+		tick -= 1;
+	}
+	tick
+}
+
+/// Converts a pool tick into a human-readable price of the token in quote units.
+///
+/// A tick corresponds to `sqrtPriceX96 = sqrt(1.0001^tick) * 2^96`, which encodes
+/// the raw token1/token0 price. The result is adjusted for token decimals and
+/// inverted when the priced token is token1.
+pub fn tick_to_price(
+	tick: i64,
+	token_is_token0: bool,
+	token_decimals: u8,
+	quote_decimals: u8,
+) -> f64 {
+	let raw = 1.0001f64.powi(tick as i32);
+	let decimal_adjustment = 10f64.powi(token_decimals as i32 - quote_decimals as i32);
+
+	if token_is_token0 {
+		raw * decimal_adjustment
+	} else {
+		decimal_adjustment / raw
+	}
+}
+
+/// Configuration schema for UniswapV3TwapFeed.
+pub struct UniswapV3TwapFeedSchema;
+
+impl UniswapV3TwapFeedSchema {
+	/// Static validation method for use before instance creation
+	pub fn validate_config(config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let instance = Self;
+		instance.validate(config)
+	}
+
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		let twap_window = || FieldType::Integer {
+			min: Some(1),
+			max: Some(u32::MAX as i64),
+		};
+		let decimals = || FieldType::Integer {
+			min: Some(0),
+			max: Some(255),
+		};
+
+		let pool_schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new(
+					"chain_id",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				)
+				.with_description("Chain where the pool is deployed"),
+				Field::new("token", FieldType::String)
+					.with_description("Address of the token to price"),
+				Field::new("pool_address", FieldType::String)
+					.with_description("Address of the Uniswap V3 pool"),
+				Field::new("quote_token_decimals", decimals())
+					.with_description("Decimals of the pool's quote token"),
+			],
+			// Optional fields
+			vec![
+				Field::new("token_decimals", decimals()).with_description(
+					"Token decimals, defaulting to the networks config or the token contract",
+				),
+				Field::new("quote_is_usd", FieldType::Boolean)
+					.with_description(
+						"Whether the quote token is a USD stablecoin; otherwise the quote token needs a pool quoted in USD",
+					)
+					.with_default(false),
+				Field::new("twap_window_seconds", twap_window())
+					.with_description("TWAP window of this pool, overriding the default window"),
+			],
+		);
+
+		Schema::new(
+			// Required fields
+			vec![Field::new(
+				"pools",
+				FieldType::Array(Box::new(FieldType::Table(pool_schema))),
+			)
+			.with_description("Pools tokens are priced against")],
+			// Optional fields
+			vec![Field::new("twap_window_seconds", twap_window())
+				.with_description("Default TWAP window of all pools")
+				.with_default(DEFAULT_TWAP_WINDOW_SECONDS)],
+		)
+	}
+}
+
+impl ConfigSchema for UniswapV3TwapFeedSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
+	}
+}
+
+#[async_trait]
+impl PriceFeedInterface for UniswapV3TwapFeed {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(UniswapV3TwapFeedSchema)
+	}
+
+	async fn get_token_price(
+		&self,
+		chain_id: u64,
+		token: &str,
+	) -> Result<TokenPrice, PriceFeedError> {
+		let token_address: AlloyAddress = token
+			.parse()
+			.map_err(|e| PriceFeedError::UnsupportedToken(format!("{}: {}", token, e)))?;
+
+		let pool = self.pools.get(&(chain_id, token_address)).ok_or_else(|| {
+			PriceFeedError::UnsupportedToken(format!(
+				"No pool configured for token {} on chain {}",
+				token, chain_id
+			))
+		})?;
+
+		let (mut price, quote_token) = self.price_in_quote(chain_id, token_address, pool).await?;
+
+		// Convert through the quote token's own pool unless it is quoted in USD
+		if !pool.quote_is_usd {
+			let quote_pool = self
+				.pools
+				.get(&(chain_id, quote_token))
+				.filter(|quote_pool| quote_pool.quote_is_usd)
+				.ok_or_else(|| {
+					PriceFeedError::InvalidConfig(format!(
+						"Quote token {} of pool {} on chain {} has no pool quoted in USD; configure one or set quote_is_usd",
+						quote_token, pool.pool_address, chain_id
+					))
+				})?;
+			let (quote_price, _) = self
+				.price_in_quote(chain_id, quote_token, quote_pool)
+				.await?;
+			price *= quote_price;
+		}
+
+		if !price.is_finite() || price <= 0.0 {
+			return Err(PriceFeedError::PriceUnavailable(format!(
+				"Invalid TWAP price for token {} on chain {}",
+				token, chain_id
+			)));
+		}
+
+		Ok(TokenPrice {
+			chain_id,
+			token: with_0x_prefix(&hex::encode(token_address)),
+			price_usd: price,
+			timestamp: current_timestamp(),
+			source: Registry::NAME.to_string(),
+		})
+	}
+}
+
+/// Factory function to create a Uniswap V3 TWAP price feed from configuration.
+///
+/// Configuration parameters:
+/// - `twap_window_seconds`: Default TWAP window for all pools (default: 300)
+/// - `pools`: Array of pool entries, each with:
+///   - `chain_id`: Chain where the pool is deployed
+///   - `token`: Address of the token to price
+///   - `pool_address`: Address of the Uniswap V3 pool
+///   - `quote_token_decimals`: Decimals of the pool's quote token
+///   - `token_decimals` (optional): Token decimals, defaults to the networks config or
+///     the token contract's `decimals()`
+///   - `quote_is_usd` (optional): Whether the quote token is a USD stablecoin
+///     (default: false). Otherwise the quote token needs its own pool quoted in USD.
+///   - `twap_window_seconds` (optional): Per-pool TWAP window override
+pub fn create_price_feed(
+	config: &toml::Value,
+	networks: &NetworksConfig,
+) -> Result<Box<dyn PriceFeedInterface>, PriceFeedError> {
+	// Validate configuration first
+	UniswapV3TwapFeedSchema::validate_config(config)
+		.map_err(|e| PriceFeedError::InvalidConfig(e.to_string()))?;
+
+	let default_window = config
+		.get("twap_window_seconds")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u32)
+		.unwrap_or(DEFAULT_TWAP_WINDOW_SECONDS);
+
+	let mut pools = HashMap::new();
+	let entries = config
+		.get("pools")
+		.and_then(|v| v.as_array())
+		.expect("pools already validated");
+
+	for entry in entries {
+		let chain_id = entry
+			.get("chain_id")
+			.and_then(|v| v.as_integer())
+			.expect("chain_id already validated") as u64;
+
+		let parse_address = |field: &str| -> Result<AlloyAddress, PriceFeedError> {
+			let value = entry
+				.get(field)
+				.and_then(|v| v.as_str())
+				.expect("address fields already validated");
+			value.parse().map_err(|e| {
+				PriceFeedError::InvalidConfig(format!("Invalid {} '{}': {}", field, value, e))
+			})
+		};
+		let token = parse_address("token")?;
+		let pool_address = parse_address("pool_address")?;
+
+		let token_decimals = entry
+			.get("token_decimals")
+			.and_then(|v| v.as_integer())
+			.map(|v| v as u8)
+			.or_else(|| {
+				networks.get(&chain_id).and_then(|network| {
+					network
+						.tokens
+						.iter()
+						.find(|t| t.address.0 == token.as_slice())
+						.map(|t| t.decimals)
+				})
+			});
+
+		let pool = PoolConfig {
+			pool_address,
+			token_decimals,
+			quote_token_decimals: entry
+				.get("quote_token_decimals")
+				.and_then(|v| v.as_integer())
+				.expect("quote_token_decimals already validated") as u8,
+			quote_is_usd: entry
+				.get("quote_is_usd")
+				.and_then(|v| v.as_bool())
+				.unwrap_or(false),
+			twap_window_seconds: entry
+				.get("twap_window_seconds")
+				.and_then(|v| v.as_integer())
+				.map(|v| v as u32)
+				.unwrap_or(default_window),
+		};
+
+		if pools.insert((chain_id, token), pool).is_some() {
+			return Err(PriceFeedError::InvalidConfig(format!(
+				"Duplicate pool configuration for token {} on chain {}",
+				token, chain_id
+			)));
+		}
+	}
+
+	Ok(Box::new(UniswapV3TwapFeed::new(networks, pools)?))
+}
+
+/// Registry for the Uniswap V3 TWAP price feed implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "uniswap_v3";
+	type Factory = crate::PriceFeedFactory;
+
+	fn factory() -> Self::Factory {
+		create_price_feed
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(UniswapV3TwapFeedSchema)
+	}
+}
+
+impl crate::PriceFeedRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_average_tick_rounds_down() {
+		assert_eq!(average_tick(0, 3000, 300), 10);
+		assert_eq!(average_tick(0, -3000, 300), -10);
+		assert_eq!(average_tick(0, -3001, 300), -11);
+		assert_eq!(average_tick(100, 100, 300), 0);
+	}
+
+	#[test]
+	fn test_tick_to_price() {
+		// Tick zero with equal decimals is parity in both directions
+		assert!((tick_to_price(0, true, 18, 18) - 1.0).abs() < 1e-12);
+		assert!((tick_to_price(0, false, 18, 18) - 1.0).abs() < 1e-12);
+
+		// WETH (token1, 18 decimals) priced in USDC (token0, 6 decimals)
+		// around tick 200000 is roughly 2000 USD
+		let price = tick_to_price(200_000, false, 18, 6);
+		assert!(price > 1_900.0 && price < 2_200.0, "price = {}", price);
+
+		// Inverting the pool orientation inverts the price
+		let inverse = tick_to_price(-200_000, true, 18, 6);
+		assert!((price - inverse).abs() / price < 1e-9);
+	}
+}
@@ -0,0 +1,183 @@
+//! Price feed module for the OIF solver system.
+//!
+//! This module provides token price lookups used for profitability checks,
+//! PnL tracking and analytics. It supports pluggable price sources such as
+//! on-chain DEX oracles.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_types::{ConfigSchema, ImplementationRegistry, NetworksConfig};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Re-export implementations
+pub mod implementations {
+	pub mod uniswap_v3;
+}
+
+/// Historical price snapshot storage
+pub mod history;
+
+pub use history::HistoricalPriceStore;
+
+/// Errors that can occur during price feed operations.
+#[derive(Debug, Error)]
+pub enum PriceFeedError {
+	/// Error that occurs when no reliable price can be determined for a token.
+	#[error("Price unavailable: {0}")]
+	PriceUnavailable(String),
+	/// Error that occurs when the requested token is not configured.
+	#[error("Unsupported token: {0}")]
+	UnsupportedToken(String),
+	/// Error that occurs when communicating with the price source fails.
+	#[error("Network error: {0}")]
+	Network(String),
+	/// Error that occurs when the price feed configuration is invalid.
+	#[error("Invalid configuration: {0}")]
+	InvalidConfig(String),
+}
+
+/// Price of a token at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPrice {
+	/// Chain ID where the token lives.
+	pub chain_id: u64,
+	/// Token address (hex with 0x prefix).
+	pub token: String,
+	/// Price of one whole token in USD.
+	pub price_usd: f64,
+	/// Unix timestamp at which the price was observed.
+	pub timestamp: u64,
+	/// Name of the price source that produced this price.
+	pub source: String,
+}
+
+/// Trait defining the interface for price feed implementations.
+///
+/// This trait must be implemented by any price source that wants to integrate
+/// with the solver system.
+#[async_trait]
+pub trait PriceFeedInterface: Send + Sync {
+	/// Returns the configuration schema for this price feed implementation.
+	///
+	/// This allows each implementation to define its own configuration requirements
+	/// with specific validation rules. The schema is used to validate TOML configuration
+	/// before initializing the price feed.
+	fn config_schema(&self) -> Box<dyn ConfigSchema>;
+
+	/// Returns the current USD price of a token on the given chain.
+	async fn get_token_price(
+		&self,
+		chain_id: u64,
+		token: &str,
+	) -> Result<TokenPrice, PriceFeedError>;
+}
+
+/// Type alias for price feed factory functions.
+///
+/// This is the function signature that all price feed implementations must provide
+/// to create instances of their price feed interface.
+pub type PriceFeedFactory =
+	fn(&toml::Value, &NetworksConfig) -> Result<Box<dyn PriceFeedInterface>, PriceFeedError>;
+
+/// Registry trait for price feed implementations.
+///
+/// This trait extends the base ImplementationRegistry to specify that
+/// price feed implementations must provide a PriceFeedFactory.
+pub trait PriceFeedRegistry: ImplementationRegistry<Factory = PriceFeedFactory> {}
+
+/// Get all registered price feed implementations.
+///
+/// Returns a vector of (name, factory) tuples for all available price feed implementations.
+/// This is used by the factory registry to automatically register all implementations.
+pub fn get_all_implementations() -> Vec<(&'static str, PriceFeedFactory)> {
+	use implementations::uniswap_v3;
+
+	vec![(uniswap_v3::Registry::NAME, uniswap_v3::Registry::factory())]
+}
+
+/// Get the configuration schemas of all registered price feed implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_implementations`].
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::uniswap_v3;
+
+	vec![(
+		uniswap_v3::Registry::NAME,
+		uniswap_v3::Registry::config_schema(),
+	)]
+}
+
+/// Service that manages token price lookups.
+///
+/// This struct provides a high-level interface for price queries,
+/// wrapping an underlying price feed implementation and optionally
+/// sampling successful lookups into a historical price store.
+pub struct PriceService {
+	/// The underlying price feed implementation.
+	implementation: Box<dyn PriceFeedInterface>,
+	/// Optional store that records price snapshots for later lookups.
+	history: Option<Arc<HistoricalPriceStore>>,
+}
+
+impl PriceService {
+	/// Creates a new PriceService with the specified implementation.
+	pub fn new(implementation: Box<dyn PriceFeedInterface>) -> Self {
+		Self {
+			implementation,
+			history: None,
+		}
+	}
+
+	/// Enables recording of price snapshots in the given history store.
+	pub fn with_history(mut self, history: HistoricalPriceStore) -> Self {
+		self.history = Some(Arc::new(history));
+		self
+	}
+
+	/// Returns the current USD price of a token on the given chain.
+	///
+	/// Successful lookups are sampled into the history store if one is
+	/// configured. Snapshots are written in the background, and failures to
+	/// record them are logged without affecting the lookup.
+	pub async fn get_token_price(
+		&self,
+		chain_id: u64,
+		token: &str,
+	) -> Result<TokenPrice, PriceFeedError> {
+		let price = self.implementation.get_token_price(chain_id, token).await?;
+
+		if let Some(history) = self.history.as_ref().filter(|h| h.should_record(&price)) {
+			let history = history.clone();
+			let snapshot = price.clone();
+			tokio::spawn(async move {
+				if let Err(e) = history.record(&snapshot).await {
+					tracing::warn!(
+						chain_id = snapshot.chain_id,
+						token = %snapshot.token,
+						error = %e,
+						"Failed to record price snapshot"
+					);
+				}
+			});
+		}
+
+		Ok(price)
+	}
+
+	/// Returns the recorded price closest to the given timestamp.
+	///
+	/// Returns `None` if history is disabled or no snapshot is available.
+	pub async fn get_price_at_timestamp(
+		&self,
+		token: &str,
+		chain_id: u64,
+		ts: u64,
+	) -> Option<TokenPrice> {
+		match &self.history {
+			Some(history) => history.get_price_at_timestamp(token, chain_id, ts).await,
+			None => None,
+		}
+	}
+}
@@ -0,0 +1,273 @@
+//! Benchmarks of the solver's hot paths.
+//!
+//! Run with `cargo bench -p solver-service --bench solver`. CI runs them on the
+//! base branch and the pull request on the same runner and reports, without
+//! failing the build, benchmarks more than 20% slower than on the base branch
+//! with `scripts/bench/check_regression.sh`.
+
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, Criterion};
+use solver_config::Config;
+use solver_core::EventBus;
+use solver_order::implementations::{standards::_7683, strategies::simple};
+use solver_order::OrderService;
+use solver_service::apis::quote::generation::QuoteGenerator;
+use solver_service::init::{render_config, InitAnswers};
+use solver_settlement::{
+	utils::parse_oracle_config, OracleConfig, SettlementError, SettlementInterface,
+	SettlementService,
+};
+use solver_storage::{implementations::memory::MemoryStorage, StorageService};
+use solver_types::oracle::OracleRouteSource;
+use solver_types::standards::eip7683::{Eip7683OrderData, GasLimitOverrides, MandateOutput};
+use solver_types::{
+	Address, AvailableInput, ConfigSchema, DeliveryEvent, FillProof, GetQuoteRequest, Intent,
+	IntentMetadata, InteropAddress, Order, QuotePreference, RequestedOutput, SolverEvent,
+	TransactionHash, TransactionType,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+const ORIGIN_CHAIN: u64 = 31337;
+const DESTINATION_CHAIN: u64 = 31338;
+const ORACLE: &str = "0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9";
+const TOKEN: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+/// Settlement accepting the configured oracles without any chain access.
+struct MockSettlement {
+	oracle_config: OracleConfig,
+}
+
+#[async_trait]
+impl SettlementInterface for MockSettlement {
+	fn oracle_config(&self) -> &OracleConfig {
+		&self.oracle_config
+	}
+
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		unimplemented!()
+	}
+
+	async fn get_attestation(
+		&self,
+		_order: &Order,
+		_tx_hash: &TransactionHash,
+	) -> Result<FillProof, SettlementError> {
+		unimplemented!()
+	}
+
+	async fn can_claim(&self, _order: &Order, _fill_proof: &FillProof) -> bool {
+		false
+	}
+}
+
+/// Configuration of two local chains settled directly, as generated by `solver init`.
+fn config() -> Config {
+	let chain = |chain_id: u64| {
+		serde_json::json!({
+			"chain_id": chain_id,
+			"rpc_url": "http://localhost:8545",
+			"input_settler_address": "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+			"output_settler_address": "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+			"oracle_address": ORACLE,
+		})
+	};
+	let answers: InitAnswers = serde_json::from_value(serde_json::json!({
+		"solver_id": "bench",
+		"chains": [chain(ORIGIN_CHAIN), chain(DESTINATION_CHAIN)],
+		"private_key": {
+			"source": "inline",
+			"private_key": "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+		},
+		"tokens": [{
+			"symbol": "TOKA",
+			"decimals": 18,
+			"addresses": {
+				"31337": TOKEN,
+				"31338": TOKEN,
+			},
+		}],
+		"settlement": { "mechanism": "direct" },
+	}))
+	.unwrap();
+	Config::from_str(&render_config(&answers).unwrap()).unwrap()
+}
+
+fn settlement_service(config: &Config) -> SettlementService {
+	let oracle_config = parse_oracle_config(&config.settlement.implementations["direct"]).unwrap();
+	let implementations: HashMap<String, Box<dyn SettlementInterface>> = HashMap::from([(
+		"direct".to_string(),
+		Box::new(MockSettlement { oracle_config }) as Box<dyn SettlementInterface>,
+	)]);
+	SettlementService::new(implementations)
+}
+
+fn bytes32(address: &str) -> [u8; 32] {
+	let mut bytes = [0u8; 32];
+	bytes[12..].copy_from_slice(&solver_types::utils::parse_address(address).unwrap().0);
+	bytes
+}
+
+/// On-chain EIP-7683 intent from the origin to the destination chain.
+fn intent() -> Intent {
+	let expires = (chrono::Utc::now().timestamp() + 86_400) as u32;
+	let order_data = Eip7683OrderData {
+		user: "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_string(),
+		nonce: U256::from(1),
+		origin_chain_id: U256::from(ORIGIN_CHAIN),
+		expires,
+		fill_deadline: expires,
+		input_oracle: ORACLE.to_string(),
+		inputs: vec![[U256::from_be_bytes(bytes32(TOKEN)), U256::from(1_000)]],
+		order_id: [1u8; 32],
+		gas_limit_overrides: GasLimitOverrides::default(),
+		outputs: vec![MandateOutput {
+			oracle: [0u8; 32],
+			settler: bytes32("0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9"),
+			chain_id: U256::from(DESTINATION_CHAIN),
+			token: bytes32(TOKEN),
+			amount: U256::from(1_000),
+			recipient: bytes32("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+			call: vec![],
+			context: vec![],
+		}],
+		raw_order_data: None,
+		signature: None,
+		sponsor: None,
+		lock_type: Default::default(),
+	};
+	Intent {
+		id: "bench".to_string(),
+		source: "on-chain".to_string(),
+		standard: "eip7683".to_string(),
+		metadata: IntentMetadata {
+			requires_auction: false,
+			exclusive_until: None,
+			discovered_at: 0,
+			tokens: vec![],
+			origin_block_number: None,
+			origin_block_timestamp: None,
+		},
+		data: serde_json::to_value(order_data).unwrap(),
+		quote_id: None,
+	}
+}
+
+fn quote_request() -> GetQuoteRequest {
+	let address = |chain_id: u64, address: &str| {
+		InteropAddress::new_ethereum(chain_id, address.parse().unwrap())
+	};
+	let user = address(ORIGIN_CHAIN, "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+	GetQuoteRequest {
+		user: user.clone(),
+		available_inputs: vec![AvailableInput {
+			user,
+			asset: address(ORIGIN_CHAIN, TOKEN),
+			amount: U256::from(1_000),
+			lock: None,
+		}],
+		requested_outputs: vec![RequestedOutput {
+			receiver: address(
+				DESTINATION_CHAIN,
+				"0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+			),
+			asset: address(DESTINATION_CHAIN, TOKEN),
+			amount: U256::from(1_000),
+			calldata: None,
+		}],
+		min_valid_until: None,
+		preference: Some(QuotePreference::Price),
+	}
+}
+
+fn bench_storage(c: &mut Criterion) {
+	let runtime = Runtime::new().unwrap();
+	let storage = StorageService::new(Box::new(MemoryStorage::new()), None);
+	let value = serde_json::json!({ "id": "order", "status": "created", "amount": "1000" });
+
+	c.bench_function("storage_store_retrieve", |b| {
+		b.to_async(&runtime).iter(|| async {
+			storage
+				.store("orders", "order", &value, None)
+				.await
+				.unwrap();
+			storage
+				.retrieve::<serde_json::Value>("orders", "order")
+				.await
+				.unwrap()
+		})
+	});
+}
+
+fn bench_validate_intent(c: &mut Criterion) {
+	let runtime = Runtime::new().unwrap();
+	let config = config();
+	let oracle_routes: Arc<dyn OracleRouteSource> =
+		Arc::new(settlement_service(&config).build_oracle_routes());
+	let implementation = _7683::create_order_impl(
+		&config.order.implementations["eip7683"],
+		&config.networks,
+		&oracle_routes,
+	)
+	.unwrap();
+	let order = OrderService::new(
+		HashMap::from([("eip7683".to_string(), implementation)]),
+		simple::create_strategy(&toml::Value::Table(Default::default())).unwrap(),
+	);
+	let intent = intent();
+	let solver = Address(vec![0xf3; 20]);
+	runtime
+		.block_on(order.validate_intent(&intent, &solver))
+		.unwrap();
+
+	c.bench_function("order_validate_intent", |b| {
+		b.to_async(&runtime)
+			.iter(|| async { order.validate_intent(&intent, &solver).await.unwrap() })
+	});
+}
+
+fn bench_generate_quotes(c: &mut Criterion) {
+	let runtime = Runtime::new().unwrap();
+	let config = config();
+	let generator = QuoteGenerator::new(Arc::new(settlement_service(&config)));
+	let request = quote_request();
+	runtime
+		.block_on(generator.generate_quotes(&request, &config))
+		.unwrap();
+
+	c.bench_function("quote_generate_quotes", |b| {
+		b.to_async(&runtime)
+			.iter(|| async { generator.generate_quotes(&request, &config).await.unwrap() })
+	});
+}
+
+fn bench_event_bus(c: &mut Criterion) {
+	let runtime = Runtime::new().unwrap();
+	let bus = EventBus::new(1_000);
+	let mut receiver = bus.subscribe();
+	let event = SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+		order_id: "order".to_string(),
+		tx_hash: TransactionHash(vec![0xab; 32]),
+		tx_type: TransactionType::Fill,
+		tx_chain_id: DESTINATION_CHAIN,
+	});
+
+	c.bench_function("event_bus_publish_recv", |b| {
+		b.iter(|| {
+			bus.publish(event.clone()).unwrap();
+			runtime.block_on(receiver.recv()).unwrap()
+		})
+	});
+}
+
+criterion_group!(
+	benches,
+	bench_storage,
+	bench_validate_intent,
+	bench_generate_quotes,
+	bench_event_bus
+);
+criterion_main!(benches);
@@ -0,0 +1,5 @@
+//! Captures build information, such as the git commit, for the `build_info` module.
+
+fn main() {
+	built::write_built_file().expect("Failed to acquire build-time information");
+}
@@ -0,0 +1,330 @@
+//! API key management.
+//!
+//! API keys grant clients a set of permissions on the solver API. Only a hash
+//! of each key is stored; the key itself is returned once, when it is created.
+//! Keys are managed through admin endpoints gated by the master admin token,
+//! which is itself only configurable through the environment or configuration.
+
+use alloy_primitives::{hex, keccak256};
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{current_timestamp, StorageKey};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Storage ID under which all keys are persisted.
+const KEYS_STORAGE_ID: &str = "keys";
+
+/// Prefix of generated API keys, making them recognizable in logs and configs.
+const KEY_PREFIX: &str = "oif_";
+
+/// Number of hex characters of the key hash used as the key ID.
+const KEY_ID_LENGTH: usize = 16;
+
+/// Errors that can occur when managing API keys.
+#[derive(Debug, Error)]
+pub enum ApiKeyError {
+	#[error("API key not found: {0}")]
+	NotFound(String),
+	#[error("Invalid API key request: {0}")]
+	InvalidRequest(String),
+	#[error("Storage error: {0}")]
+	Storage(#[from] StorageError),
+}
+
+/// Permission granted to an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+	/// Read orders and stream their status updates.
+	ReadOrders,
+	/// Submit intents.
+	WriteIntents,
+	/// Use admin endpoints such as fill simulation and order preview.
+	Admin,
+}
+
+/// Stored API key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+	/// Hex-encoded keccak256 hash of the key.
+	pub key_hash: String,
+	/// Permissions granted to the key.
+	pub permissions: Vec<Permission>,
+	/// Unix timestamp at which the key was created.
+	pub created_at: u64,
+	/// Unix timestamp after which the key is no longer accepted, if any.
+	pub expires_at: Option<u64>,
+}
+
+impl ApiKey {
+	/// Returns whether the key is expired at `now`.
+	fn is_expired(&self, now: u64) -> bool {
+		self.expires_at.is_some_and(|expires_at| expires_at <= now)
+	}
+}
+
+/// Request body for POST /api/admin/api-keys.
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+	/// Permissions granted to the new key.
+	pub permissions: Vec<Permission>,
+	/// Expires the key after this many seconds.
+	#[serde(default)]
+	pub expires_in_seconds: Option<u64>,
+}
+
+/// Response to POST /api/admin/api-keys.
+///
+/// This is the only time the key itself is returned.
+#[derive(Debug, Serialize)]
+pub struct CreateApiKeyResponse {
+	/// ID used to revoke the key.
+	pub id: String,
+	/// The API key, sent as `Authorization: Bearer <key>`.
+	pub key: String,
+	/// Permissions granted to the key.
+	pub permissions: Vec<Permission>,
+	/// Unix timestamp at which the key expires, if any.
+	pub expires_at: Option<u64>,
+}
+
+/// API key as listed by GET /api/admin/api-keys, without its hash.
+#[derive(Debug, Serialize)]
+pub struct ApiKeyInfo {
+	/// ID of the key.
+	pub id: String,
+	/// Permissions granted to the key.
+	pub permissions: Vec<Permission>,
+	/// Unix timestamp at which the key was created.
+	pub created_at: u64,
+	/// Unix timestamp at which the key expires, if any.
+	pub expires_at: Option<u64>,
+}
+
+/// Store of hashed API keys, persisted as a single map of keys by ID.
+pub struct ApiKeyStore {
+	storage: Arc<StorageService>,
+	/// Serializes modifications of the persisted map.
+	write_lock: Mutex<()>,
+}
+
+impl ApiKeyStore {
+	/// Creates a new ApiKeyStore persisting keys to `storage`.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self {
+			storage,
+			write_lock: Mutex::new(()),
+		}
+	}
+
+	/// Generates and stores a new key with the requested permissions.
+	pub async fn create(
+		&self,
+		request: CreateApiKeyRequest,
+	) -> Result<CreateApiKeyResponse, ApiKeyError> {
+		if request.permissions.is_empty() {
+			return Err(ApiKeyError::InvalidRequest(
+				"at least one permission is required".to_string(),
+			));
+		}
+
+		let key = format!(
+			"{}{}{}",
+			KEY_PREFIX,
+			Uuid::new_v4().simple(),
+			Uuid::new_v4().simple()
+		);
+		let key_hash = hash_key(&key);
+		let id = key_id(&key_hash);
+		let created_at = current_timestamp();
+		let expires_at = match request.expires_in_seconds {
+			Some(seconds) => Some(created_at.checked_add(seconds).ok_or_else(|| {
+				ApiKeyError::InvalidRequest("expires_in_seconds is too large".to_string())
+			})?),
+			None => None,
+		};
+
+		let mut permissions = Vec::new();
+		for permission in request.permissions {
+			if !permissions.contains(&permission) {
+				permissions.push(permission);
+			}
+		}
+		let entry = ApiKey {
+			key_hash,
+			permissions: permissions.clone(),
+			created_at,
+			expires_at,
+		};
+
+		let _guard = self.write_lock.lock().await;
+		let mut keys = self.load().await?;
+		keys.insert(id.clone(), entry);
+		self.persist(&keys).await?;
+
+		tracing::info!(key_id = %id, permissions = ?permissions, "Created API key");
+		Ok(CreateApiKeyResponse {
+			id,
+			key,
+			permissions,
+			expires_at,
+		})
+	}
+
+	/// Revokes the key with the given ID.
+	pub async fn revoke(&self, id: &str) -> Result<(), ApiKeyError> {
+		let _guard = self.write_lock.lock().await;
+		let mut keys = self.load().await?;
+		if keys.remove(id).is_none() {
+			return Err(ApiKeyError::NotFound(id.to_string()));
+		}
+		self.persist(&keys).await?;
+
+		tracing::info!(key_id = %id, "Revoked API key");
+		Ok(())
+	}
+
+	/// Lists all stored keys.
+	pub async fn list(&self) -> Result<Vec<ApiKeyInfo>, ApiKeyError> {
+		let mut keys: Vec<ApiKeyInfo> = self
+			.load()
+			.await?
+			.into_iter()
+			.map(|(id, entry)| ApiKeyInfo {
+				id,
+				permissions: entry.permissions,
+				created_at: entry.created_at,
+				expires_at: entry.expires_at,
+			})
+			.collect();
+		keys.sort_by_key(|key| key.created_at);
+		Ok(keys)
+	}
+
+	/// Returns whether `key` is a valid, unexpired key granted `permission`.
+	pub async fn authorize(&self, key: &str, permission: Permission) -> bool {
+		let key_hash = hash_key(key);
+		let keys = match self.load().await {
+			Ok(keys) => keys,
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to load API keys");
+				return false;
+			},
+		};
+
+		keys.get(&key_id(&key_hash)).is_some_and(|entry| {
+			entry.key_hash == key_hash
+				&& !entry.is_expired(current_timestamp())
+				&& entry.permissions.contains(&permission)
+		})
+	}
+
+	/// Loads all keys, treating a missing map as empty.
+	async fn load(&self) -> Result<BTreeMap<String, ApiKey>, ApiKeyError> {
+		match self
+			.storage
+			.retrieve(StorageKey::ApiKeys.as_str(), KEYS_STORAGE_ID)
+			.await
+		{
+			Ok(keys) => Ok(keys),
+			Err(StorageError::NotFound) => Ok(BTreeMap::new()),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	async fn persist(&self, keys: &BTreeMap<String, ApiKey>) -> Result<(), ApiKeyError> {
+		self.storage
+			.store(StorageKey::ApiKeys.as_str(), KEYS_STORAGE_ID, keys, None)
+			.await?;
+		Ok(())
+	}
+}
+
+/// Hashes an API key for storage.
+fn hash_key(key: &str) -> String {
+	hex::encode(keccak256(key.as_bytes()))
+}
+
+/// Returns whether a provided token equals the expected one, in time
+/// independent of where they differ.
+///
+/// The tokens are hashed first so that the comparison does not leak the
+/// expected token's length either.
+pub fn tokens_match(expected: &str, provided: &str) -> bool {
+	keccak256(expected.as_bytes())
+		.as_slice()
+		.ct_eq(keccak256(provided.as_bytes()).as_slice())
+		.into()
+}
+
+/// Derives the ID of a key from its hash, so keys can be looked up by hash.
+fn key_id(key_hash: &str) -> String {
+	key_hash[..KEY_ID_LENGTH].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	#[tokio::test]
+	async fn test_api_key_lifecycle() {
+		let store = ApiKeyStore::new(Arc::new(StorageService::new(
+			Box::new(MemoryStorage::new()),
+			None,
+		)));
+
+		let created = store
+			.create(CreateApiKeyRequest {
+				permissions: vec![Permission::ReadOrders],
+				expires_in_seconds: None,
+			})
+			.await
+			.unwrap();
+		assert!(created.key.starts_with(KEY_PREFIX));
+		assert!(store.authorize(&created.key, Permission::ReadOrders).await);
+		assert!(!store.authorize(&created.key, Permission::Admin).await);
+		assert!(!store.authorize("oif_unknown", Permission::ReadOrders).await);
+
+		let listed = store.list().await.unwrap();
+		assert_eq!(listed.len(), 1);
+		assert_eq!(listed[0].id, created.id);
+
+		store.revoke(&created.id).await.unwrap();
+		assert!(!store.authorize(&created.key, Permission::ReadOrders).await);
+		assert!(matches!(
+			store.revoke(&created.id).await,
+			Err(ApiKeyError::NotFound(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_expiry_overflow_is_rejected() {
+		let store = ApiKeyStore::new(Arc::new(StorageService::new(
+			Box::new(MemoryStorage::new()),
+			None,
+		)));
+
+		let result = store
+			.create(CreateApiKeyRequest {
+				permissions: vec![Permission::ReadOrders],
+				expires_in_seconds: Some(u64::MAX),
+			})
+			.await;
+		assert!(matches!(result, Err(ApiKeyError::InvalidRequest(_))));
+		assert!(store.list().await.unwrap().is_empty());
+	}
+
+	#[test]
+	fn test_tokens_match() {
+		assert!(tokens_match("secret", "secret"));
+		assert!(!tokens_match("secret", "secreT"));
+		assert!(!tokens_match("secret", "secret-longer"));
+		assert!(!tokens_match("secret", ""));
+	}
+}
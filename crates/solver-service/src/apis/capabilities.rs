@@ -0,0 +1,181 @@
+//! Capability advertisement API for the OIF Solver.
+//!
+//! This module provides the endpoint client integrators query before
+//! submitting intents, describing the chains, tokens, order standards and
+//! routes the solver supports.
+
+use crate::apis::tokens::TokenInfo;
+use alloy_primitives::hex;
+use axum::{extract::State, Json};
+use serde::Serialize;
+use solver_core::SolverEngine;
+use solver_order::version_registry::DEFAULT_STANDARD_VERSION;
+use solver_types::{oracle::OracleRoutes, with_0x_prefix, NetworksConfig};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Everything the solver supports.
+#[derive(Debug, Serialize)]
+pub struct SolverCapabilities {
+	/// Configured chains and their tokens, ordered by chain ID.
+	pub supported_chains: Vec<ChainCapabilities>,
+	/// Enabled order standards.
+	pub supported_standards: Vec<StandardCapability>,
+	/// Chain pairs between which orders can be settled.
+	pub supported_routes: Vec<RouteCapability>,
+	/// Output oracles accepted for each input oracle.
+	pub oracle_routes: OracleRoutes,
+}
+
+/// Tokens supported on a chain.
+#[derive(Debug, Serialize)]
+pub struct ChainCapabilities {
+	/// The blockchain network ID.
+	pub chain_id: u64,
+	/// List of supported tokens on this network.
+	pub tokens: Vec<TokenInfo>,
+}
+
+/// An order standard and its version.
+#[derive(Debug, Serialize)]
+pub struct StandardCapability {
+	/// Name of the standard (e.g., "eip7683").
+	pub name: String,
+	/// Version of the standard.
+	pub version: u8,
+}
+
+/// A pair of chains between which orders can be settled.
+#[derive(Debug, Serialize)]
+pub struct RouteCapability {
+	/// Chain the inputs are locked on.
+	pub input_chain: u64,
+	/// Chain the outputs are filled on.
+	pub output_chain: u64,
+}
+
+/// Handles GET /api/capabilities requests.
+///
+/// Returns the capabilities of the solver.
+pub async fn get_capabilities(State(solver): State<Arc<SolverEngine>>) -> Json<SolverCapabilities> {
+	Json(build_capabilities(
+		solver.token_manager().get_networks(),
+		&solver.config().order.implementations,
+		solver.settlement().build_oracle_routes(),
+	))
+}
+
+/// Builds the capabilities from the networks, the configured order
+/// implementations and the oracle routes of the settlements.
+fn build_capabilities(
+	networks: &NetworksConfig,
+	order_implementations: &HashMap<String, toml::Value>,
+	oracle_routes: OracleRoutes,
+) -> SolverCapabilities {
+	let mut supported_chains: Vec<ChainCapabilities> = networks
+		.iter()
+		.map(|(chain_id, network)| ChainCapabilities {
+			chain_id: chain_id.as_u64(),
+			tokens: network
+				.tokens
+				.iter()
+				.map(|t| TokenInfo {
+					address: with_0x_prefix(&hex::encode(&t.address.0)),
+					symbol: t.symbol.clone(),
+					decimals: t.decimals,
+				})
+				.collect(),
+		})
+		.collect();
+	supported_chains.sort_by_key(|chain| chain.chain_id);
+
+	let supported_standards = solver_order::get_all_order_implementations()
+		.into_iter()
+		.filter(|(name, _)| order_implementations.contains_key(*name))
+		.map(|(name, _)| StandardCapability {
+			name: name.to_string(),
+			version: DEFAULT_STANDARD_VERSION,
+		})
+		.collect();
+
+	let supported_routes = oracle_routes
+		.supported_routes
+		.iter()
+		.flat_map(|(input, outputs)| {
+			outputs
+				.iter()
+				.map(move |output| (input.chain_id, output.chain_id))
+		})
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.map(|(input_chain, output_chain)| RouteCapability {
+			input_chain,
+			output_chain,
+		})
+		.collect();
+
+	SolverCapabilities {
+		supported_chains,
+		supported_standards,
+		supported_routes,
+		oracle_routes,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{oracle::OracleInfo, Address, NetworkConfig, TokenConfig};
+
+	fn oracle(chain_id: u64) -> OracleInfo {
+		OracleInfo {
+			chain_id,
+			oracle: Address(vec![chain_id as u8; 20]),
+		}
+	}
+
+	#[test]
+	fn test_capabilities_list_configured_standards_and_routes() {
+		let network = NetworkConfig {
+			rpc_urls: Vec::new(),
+			input_settler_address: Address(vec![0; 20]),
+			output_settler_address: Address(vec![0; 20]),
+			tokens: vec![TokenConfig {
+				address: Address(vec![0xaa; 20]),
+				symbol: "USDC".to_string(),
+				decimals: 6,
+			}],
+			input_settler_compact_address: None,
+		};
+		let networks = NetworksConfig::from([(2.into(), network.clone()), (1.into(), network)]);
+		let implementations = HashMap::from([(
+			"eip7683".to_string(),
+			toml::Value::Table(Default::default()),
+		)]);
+		let oracle_routes = OracleRoutes {
+			supported_routes: HashMap::from([
+				(oracle(1), vec![oracle(2)]),
+				(oracle(2), vec![oracle(1)]),
+			]),
+		};
+
+		let capabilities = build_capabilities(&networks, &implementations, oracle_routes);
+		let json = serde_json::to_value(&capabilities).unwrap();
+
+		assert_eq!(json["supported_chains"][0]["chain_id"], 1);
+		assert_eq!(json["supported_chains"][1]["tokens"][0]["symbol"], "USDC");
+		assert_eq!(
+			json["supported_standards"],
+			serde_json::json!([{ "name": "eip7683", "version": 1 }])
+		);
+		assert_eq!(
+			json["supported_routes"],
+			serde_json::json!([
+				{ "input_chain": 1, "output_chain": 2 },
+				{ "input_chain": 2, "output_chain": 1 },
+			])
+		);
+		assert_eq!(json["oracle_routes"][0]["input"]["chain_id"], 1);
+		assert_eq!(json["oracle_routes"][0]["outputs"][0]["chain_id"], 2);
+	}
+}
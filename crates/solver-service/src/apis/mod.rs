@@ -3,6 +3,9 @@
 //! This module contains the implementation of the HTTP API endpoints
 //! for the OIF Solver.
 
+pub mod capabilities;
 pub mod order;
+pub mod order_events;
 pub mod quote;
+pub mod sign_payload;
 pub mod tokens;
@@ -7,8 +7,8 @@
 use axum::extract::Path;
 use solver_core::SolverEngine;
 use solver_types::{
-	bytes32_to_address, with_0x_prefix, AssetAmount, GetOrderError, GetOrderResponse, Order,
-	OrderResponse, OrderStatus, Settlement, SettlementType, TransactionType,
+	bytes32_to_address, with_0x_prefix, AssetAmount, GetOrderError, GetOrderResponse, Intent,
+	Order, OrderResponse, OrderStatus, Settlement, SettlementType, StorageKey, TransactionType,
 };
 use tracing::info;
 
@@ -43,7 +43,18 @@ async fn process_order_request(
 	{
 		Ok(order) => {
 			// Order found in storage, convert to OrderResponse
-			convert_order_to_response(order).await
+			let mut response = convert_order_to_response(order).await?;
+
+			// Add the origin block recorded when the intent was discovered
+			if let Ok(intent) = solver
+				.storage()
+				.retrieve::<Intent>(StorageKey::Intents.as_str(), order_id)
+				.await
+			{
+				response.origin_block_number = intent.metadata.origin_block_number;
+				response.origin_block_timestamp = intent.metadata.origin_block_timestamp;
+			}
+			Ok(response)
 		},
 		Err(solver_storage::StorageError::NotFound) => {
 			// Order not found in storage
@@ -206,16 +217,26 @@ async fn convert_eip7683_order_to_response(
 			},
 			// Fill transaction failed
 			OrderStatus::Failed(TransactionType::Fill) => "failed",
-			// Prepare failed - shouldn't have fill_tx_hash
-			OrderStatus::Failed(TransactionType::Prepare) => {
+			// Prepare, commit or reveal failed - shouldn't have fill_tx_hash
+			OrderStatus::Failed(
+				TransactionType::Prepare | TransactionType::Commit | TransactionType::Reveal,
+			) => {
 				tracing::warn!(
 					order_id = %order.id,
-					"Unexpected fill_tx_hash when prepare transaction failed"
+					"Unexpected fill_tx_hash when a transaction before the fill failed"
 				);
 				"failed"
 			},
 			// Fill succeeded but claim failed
 			OrderStatus::Failed(TransactionType::Claim) => "executed",
+			// Only unfilled orders can be cancelled
+			OrderStatus::Cancelled => {
+				tracing::warn!(
+					order_id = %order.id,
+					"Unexpected fill_tx_hash in cancelled order"
+				);
+				"failed"
+			},
 		};
 
 		serde_json::json!({
@@ -238,6 +259,8 @@ async fn convert_eip7683_order_to_response(
 			data: settlement_data,
 		},
 		fill_transaction,
+		origin_block_number: None,
+		origin_block_timestamp: None,
 	};
 
 	Ok(response)
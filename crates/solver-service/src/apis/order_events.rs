@@ -0,0 +1,208 @@
+//! Server-Sent Events stream of order status updates.
+//!
+//! Clients following an order receive its current state followed by every
+//! solver event concerning the order, until the order reaches a terminal
+//! state. The stream then ends with a `terminal` sentinel event.
+
+use axum::extract::Path;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use solver_core::SolverEngine;
+use solver_types::{
+	current_timestamp, DeliveryEvent, GetOrderError, OrderEvent, OrderStatus, SettlementEvent,
+	SolverEvent,
+};
+use std::convert::Infallible;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Stream of SSE events for a single order.
+pub type OrderEventStream = Sse<BoxStream<'static, Result<Event, Infallible>>>;
+
+/// Payload of each order event.
+#[derive(Debug, Serialize)]
+pub struct OrderEventPayload {
+	/// Kind of update, e.g. `order_state` or `transaction_confirmed`.
+	pub event_type: &'static str,
+	/// Unix timestamp at which the event was sent.
+	pub timestamp: u64,
+	/// ID of the order the event concerns.
+	pub order_id: String,
+	/// Event details.
+	pub data: Value,
+}
+
+impl OrderEventPayload {
+	fn into_event(self) -> Event {
+		Event::default().data(serde_json::to_string(&self).unwrap_or_default())
+	}
+}
+
+/// An order event together with whether it ends the order's lifecycle.
+struct OrderUpdate {
+	payload: OrderEventPayload,
+	terminal: bool,
+}
+
+/// Handles GET /orders/{id}/events requests.
+///
+/// Sends the current order state as the first event, then forwards solver
+/// events concerning the order until it reaches a terminal state.
+pub async fn stream_order_events(
+	Path(id): Path<String>,
+	solver: &SolverEngine,
+) -> Result<OrderEventStream, GetOrderError> {
+	// Subscribe before reading the state so that no update is missed
+	let receiver = solver.event_bus().subscribe();
+	let order = solver
+		.storage()
+		.retrieve::<solver_types::Order>("orders", &id)
+		.await
+		.map_err(|e| match e {
+			solver_storage::StorageError::NotFound => {
+				GetOrderError::NotFound(format!("Order not found: {}", id))
+			},
+			e => GetOrderError::Internal(format!("Storage error: {}", e)),
+		})?;
+	let already_terminal = matches!(
+		order.status,
+		OrderStatus::Finalized | OrderStatus::Failed(_) | OrderStatus::Cancelled
+	);
+	let current = crate::apis::order::get_order_by_id(Path(id.clone()), solver).await?;
+
+	let initial = OrderEventPayload {
+		event_type: "order_state",
+		timestamp: current_timestamp(),
+		order_id: id.clone(),
+		data: serde_json::to_value(current.order).unwrap_or_default(),
+	};
+
+	// Forward events concerning the order until it reaches a terminal state
+	let updates = stream::unfold((!already_terminal).then_some(receiver), move |receiver| {
+		let order_id = id.clone();
+		async move {
+			let mut receiver = receiver?;
+			loop {
+				match receiver.recv().await {
+					Ok(event) => {
+						if let Some(update) = order_update(&event, &order_id) {
+							let next = (!update.terminal).then_some(receiver);
+							return Some((update.payload.into_event(), next));
+						}
+					},
+					Err(RecvError::Lagged(skipped)) => {
+						tracing::warn!(skipped, "Order event stream lagged behind");
+					},
+					Err(RecvError::Closed) => return None,
+				}
+			}
+		}
+	});
+
+	let sentinel = Event::default().data(json!({ "event_type": "terminal" }).to_string());
+	let events = stream::once(async move { initial.into_event() })
+		.chain(updates)
+		.chain(stream::once(async move { sentinel }))
+		.map(Ok)
+		.boxed();
+
+	Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Maps a solver event to an update of the given order, if it concerns the order.
+fn order_update(event: &SolverEvent, order_id: &str) -> Option<OrderUpdate> {
+	let (event_type, event_order_id, terminal, data) = match event {
+		SolverEvent::Order(order_event) => {
+			let (event_type, event_order_id, terminal) = match order_event {
+				OrderEvent::Preparing { order, .. } => ("preparing", order.id.as_str(), false),
+				OrderEvent::Executing { order, .. } => ("executing", order.id.as_str(), false),
+				OrderEvent::Skipped { order_id, .. } => ("skipped", order_id.as_str(), true),
+				OrderEvent::Deferred { order_id, .. } => ("deferred", order_id.as_str(), false),
+				OrderEvent::Cancelled { order_id, .. } => ("cancelled", order_id.as_str(), true),
+				OrderEvent::ExpiryGracePeriod { order_id, .. } => {
+					("expiry_grace_period", order_id.as_str(), false)
+				},
+			};
+			(
+				event_type,
+				event_order_id,
+				terminal,
+				serde_json::to_value(order_event),
+			)
+		},
+		SolverEvent::Delivery(delivery_event) => {
+			let (event_type, event_order_id, terminal) = match delivery_event {
+				DeliveryEvent::TransactionPending { order_id, .. } => {
+					("transaction_pending", order_id, false)
+				},
+				DeliveryEvent::TransactionConfirmed { order_id, .. } => {
+					("transaction_confirmed", order_id, false)
+				},
+				DeliveryEvent::TransactionFailed { order_id, .. } => {
+					("transaction_failed", order_id, true)
+				},
+			};
+			(
+				event_type,
+				event_order_id.as_str(),
+				terminal,
+				serde_json::to_value(delivery_event),
+			)
+		},
+		SolverEvent::Settlement(settlement_event) => {
+			let (event_type, event_order_id, terminal) = match settlement_event {
+				SettlementEvent::FillDetected { order_id, .. } => {
+					("fill_detected", order_id, false)
+				},
+				SettlementEvent::ProofReady { order_id, .. } => ("proof_ready", order_id, false),
+				SettlementEvent::ClaimReady { order_id } => ("claim_ready", order_id, false),
+				SettlementEvent::FillChallenged { order_id } => ("fill_challenged", order_id, true),
+				SettlementEvent::Completed { order_id } => ("completed", order_id, true),
+			};
+			(
+				event_type,
+				event_order_id.as_str(),
+				terminal,
+				serde_json::to_value(settlement_event),
+			)
+		},
+		_ => return None,
+	};
+
+	if event_order_id != order_id {
+		return None;
+	}
+
+	Some(OrderUpdate {
+		payload: OrderEventPayload {
+			event_type,
+			timestamp: current_timestamp(),
+			order_id: order_id.to_string(),
+			data: data.unwrap_or_default(),
+		},
+		terminal,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_order_update_filters_by_order() {
+		let event = SolverEvent::Settlement(SettlementEvent::Completed {
+			order_id: "order-1".to_string(),
+		});
+
+		let update = order_update(&event, "order-1").unwrap();
+		assert_eq!(update.payload.event_type, "completed");
+		assert!(update.terminal);
+		assert!(order_update(&event, "order-2").is_none());
+
+		let claim_ready = SolverEvent::Settlement(SettlementEvent::ClaimReady {
+			order_id: "order-1".to_string(),
+		});
+		assert!(!order_update(&claim_ready, "order-1").unwrap().terminal);
+	}
+}
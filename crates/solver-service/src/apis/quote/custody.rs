@@ -39,7 +39,7 @@
 
 use solver_types::{AvailableInput, LockKind as ApiLockKind, QuoteError};
 
-use super::registry::PROTOCOL_REGISTRY;
+use solver_types::protocols::PROTOCOL_REGISTRY;
 
 /// Types of resource locks supported
 #[derive(Debug, Clone)]
@@ -52,11 +52,12 @@ use super::custody::{CustodyDecision, CustodyStrategy, EscrowKind, LockKind};
 use crate::apis::quote::permit2::{
 	build_permit2_batch_witness_digest, permit2_domain_address_from_config,
 };
+use futures::stream::{FuturesUnordered, StreamExt};
 use solver_config::Config;
 use solver_settlement::{SettlementInterface, SettlementService};
 use solver_types::{
-	with_0x_prefix, GetQuoteRequest, InteropAddress, Quote, QuoteDetails, QuoteError, QuoteOrder,
-	QuotePreference, SignatureType,
+	with_0x_prefix, GetQuoteRequest, GetQuoteResponse, InteropAddress, Quote, QuoteDetails,
+	QuoteError, QuoteOrder, QuotePreference, SignatureType,
 };
 use std::sync::Arc;
 use uuid::Uuid;
@@ -102,6 +103,51 @@ impl QuoteGenerator {
 		Ok(quotes)
 	}
 
+	/// Generates the quotes of several requests concurrently.
+	///
+	/// At most `api.batch_concurrency` requests are processed at once. Results
+	/// are returned in the order of the requests.
+	pub async fn generate_batch_quotes(
+		&self,
+		requests: Vec<GetQuoteRequest>,
+		config: &Config,
+	) -> Vec<Result<GetQuoteResponse, QuoteError>> {
+		let concurrency = config
+			.api
+			.as_ref()
+			.map_or(8, |api| api.batch_concurrency)
+			.max(1);
+		let mut results: Vec<Option<Result<GetQuoteResponse, QuoteError>>> =
+			(0..requests.len()).map(|_| None).collect();
+
+		let mut pending = requests.iter().enumerate();
+		let mut in_flight = FuturesUnordered::new();
+		loop {
+			while in_flight.len() < concurrency {
+				let Some((index, request)) = pending.next() else {
+					break;
+				};
+				in_flight.push(async move {
+					let quotes = self.generate_quotes(request, config).await;
+					(index, quotes.map(|quotes| GetQuoteResponse { quotes }))
+				});
+			}
+			let Some((index, result)) = in_flight.next().await else {
+				break;
+			};
+			results[index] = Some(result);
+		}
+
+		results
+			.into_iter()
+			.map(|result| {
+				result.unwrap_or(Err(QuoteError::Internal(
+					"Request not processed".to_string(),
+				)))
+			})
+			.collect()
+	}
+
 	async fn generate_quote_for_settlement(
 		&self,
 		request: &GetQuoteRequest,
@@ -129,6 +175,7 @@ impl QuoteGenerator {
 			eta: Some(eta),
 			quote_id,
 			provider: "oif-solver".to_string(),
+			fill_count: 0,
 		})
 	}
 
@@ -138,7 +185,17 @@ impl QuoteGenerator {
 		config: &Config,
 		lock_kind: &LockKind,
 	) -> Result<QuoteOrder, QuoteError> {
-		let domain_address = self.get_lock_domain_address(config, lock_kind)?;
+		// Resource locks are signed against the domain of the input chain
+		let chain_id = request
+			.available_inputs
+			.first()
+			.ok_or_else(|| QuoteError::InvalidRequest("No available inputs".to_string()))?
+			.asset
+			.ethereum_chain_id()
+			.map_err(|e| {
+				QuoteError::InvalidRequest(format!("Invalid chain ID in asset address: {}", e))
+			})?;
+		let domain_address = self.get_lock_domain_address(config, lock_kind, chain_id)?;
 		let (primary_type, message) = match lock_kind {
 			LockKind::TheCompact { params } => (
 				"CompactLock".to_string(),
@@ -226,9 +283,12 @@ impl QuoteGenerator {
 	) -> Result<QuoteOrder, QuoteError> {
 		let input = &request.available_inputs[0];
 		let domain_address = input.asset.clone();
+		let chain_id = input.asset.ethereum_chain_id().map_err(|e| {
+			QuoteError::InvalidRequest(format!("Invalid chain ID in asset address: {}", e))
+		})?;
 		let message = serde_json::json!({
 			"from": input.user.ethereum_address().map_err(|e| QuoteError::InvalidRequest(format!("Invalid Ethereum address: {}", e)))?,
-			"to": self.get_escrow_address(config)?,
+			"to": self.get_escrow_address(config, chain_id)?,
 			"value": input.amount.to_string(),
 			"validAfter": 0,
 			"validBefore": chrono::Utc::now().timestamp() + 300,
@@ -260,8 +320,9 @@ impl QuoteGenerator {
 		&self,
 		config: &Config,
 		lock_kind: &LockKind,
+		chain_id: u64,
 	) -> Result<InteropAddress, QuoteError> {
-		match &config.settlement.domain {
+		match config.settlement.domain_for_chain(chain_id) {
 			Some(domain_config) => {
 				let address = domain_config.address.parse().map_err(|e| {
 					QuoteError::InvalidRequest(format!("Invalid domain address in config: {}", e))
@@ -272,20 +333,25 @@ impl QuoteGenerator {
 				))
 			},
 			None => Err(QuoteError::InvalidRequest(format!(
-				"Domain configuration required for lock type: {:?}",
-				lock_kind
+				"Domain configuration for chain {} required for lock type: {:?}",
+				chain_id, lock_kind
 			))),
 		}
 	}
 
-	fn get_escrow_address(&self, config: &Config) -> Result<alloy_primitives::Address, QuoteError> {
-		match &config.settlement.domain {
+	fn get_escrow_address(
+		&self,
+		config: &Config,
+		chain_id: u64,
+	) -> Result<alloy_primitives::Address, QuoteError> {
+		match config.settlement.domain_for_chain(chain_id) {
 			Some(domain_config) => domain_config.address.parse().map_err(|e| {
 				QuoteError::InvalidRequest(format!("Invalid escrow address in config: {}", e))
 			}),
-			None => Err(QuoteError::InvalidRequest(
-				"Escrow address configuration required".to_string(),
-			)),
+			None => Err(QuoteError::InvalidRequest(format!(
+				"Escrow address configuration required for chain {}",
+				chain_id
+			))),
 		}
 	}
 
@@ -43,8 +43,10 @@
 //!
 //! ## API Integration
 //!
-//! The module exposes three main functions:
+//! The module exposes five main functions:
 //! - `process_quote_request`: Main entry point for quote generation
+//! - `process_batch_quote_request`: Quote generation for up to 50 requests at once
+//! - `list_quotes`: List the open quotes of a token pair
 //! - `get_quote_by_id`: Retrieve stored quotes
 //! - `quote_exists`: Check quote validity
 //!
@@ -52,6 +54,7 @@
 //!
 //! Quotes are stored with:
 //! - TTL-based expiry (default 5 minutes)
+//! - Indexes on the chains and tokens of their first input and output, and on expiry
 //! - Unique IDs for retrieval
 //! - Complete execution details
 //!
@@ -65,7 +68,6 @@
 
 pub mod custody;
 pub mod generation;
-pub mod registry;
 pub mod signing;
 pub mod validation;
 
@@ -74,9 +76,18 @@ pub use generation::QuoteGenerator;
 pub use signing::payloads::permit2;
 pub use validation::QuoteValidator;
 
+/// Maximum number of requests accepted in one batch quote request.
+pub const MAX_BATCH_QUOTE_REQUESTS: usize = 50;
+
 use solver_config::Config;
+use solver_core::state::quote::{quote_fill_count, quote_matches_pair, store_quote};
 use solver_core::SolverEngine;
-use solver_types::{GetQuoteRequest, GetQuoteResponse, Quote, QuoteError, StorageKey};
+use solver_storage::{QueryFilter, StorageService};
+use solver_types::{
+	current_timestamp, BatchQuoteResult, GetQuoteRequest, GetQuoteResponse, Quote, QuoteError,
+	QuoteListQuery, StorageKey,
+};
+use validation::SupportedAsset;
 
 use std::time::Duration;
 use tracing::info;
@@ -95,17 +106,8 @@ pub async fn process_quote_request(
 		request.available_inputs.len()
 	);
 
-	// 1. Validate the request
-	QuoteValidator::validate_request(&request)?;
-
-	// 2. Check solver capabilities: networks only (token support is enforced during collection below)
-	QuoteValidator::validate_supported_networks(&request, solver)?;
-
-	// 3. Collect supported assets for this request (for later use: balances/custody/pricing)
-	let (_supported_inputs, supported_outputs) = (
-		QuoteValidator::collect_supported_available_inputs(&request, solver)?,
-		QuoteValidator::validate_and_collect_requested_outputs(&request, solver)?,
-	);
+	// 1-3. Validate the request and collect its supported outputs
+	let supported_outputs = validate_quote_request(&request, solver)?;
 
 	// Check destination balances for required outputs
 	QuoteValidator::ensure_destination_balances(solver, &supported_outputs).await?;
@@ -124,6 +126,98 @@ pub async fn process_quote_request(
 	Ok(GetQuoteResponse { quotes })
 }
 
+/// Processes a batch of quote requests and returns the outcome of each.
+///
+/// Requests are validated one by one, while the destination balance of each
+/// token is fetched once for the whole batch. Quotes of the valid requests are
+/// then generated concurrently. A failing request does not fail the batch.
+pub async fn process_batch_quote_request(
+	requests: Vec<GetQuoteRequest>,
+	solver: &SolverEngine,
+	config: &Config,
+) -> Result<Vec<BatchQuoteResult>, QuoteError> {
+	if requests.is_empty() || requests.len() > MAX_BATCH_QUOTE_REQUESTS {
+		return Err(QuoteError::InvalidRequest(format!(
+			"Batch must contain between 1 and {} requests",
+			MAX_BATCH_QUOTE_REQUESTS
+		)));
+	}
+	info!("Processing batch of {} quote requests", requests.len());
+
+	let validated: Vec<Result<Vec<SupportedAsset>, QuoteError>> = requests
+		.iter()
+		.map(|request| validate_quote_request(request, solver))
+		.collect();
+	let batch_outputs: Vec<SupportedAsset> = validated
+		.iter()
+		.filter_map(|outputs| outputs.as_ref().ok())
+		.flatten()
+		.cloned()
+		.collect();
+	let balances = QuoteValidator::fetch_destination_balances(solver, &batch_outputs).await;
+
+	let mut results: Vec<Result<Vec<Quote>, QuoteError>> = Vec::with_capacity(requests.len());
+	let mut ready = Vec::new();
+	for (index, (request, outputs)) in requests.into_iter().zip(validated).enumerate() {
+		match outputs
+			.and_then(|outputs| QuoteValidator::check_destination_balances(&outputs, &balances))
+		{
+			Ok(()) => {
+				ready.push((index, request));
+				results.push(Ok(Vec::new()));
+			},
+			Err(e) => results.push(Err(e)),
+		}
+	}
+
+	let (indices, ready): (Vec<usize>, Vec<GetQuoteRequest>) = ready.into_iter().unzip();
+	let quote_generator = QuoteGenerator::new(solver.settlement().clone());
+	let responses = quote_generator.generate_batch_quotes(ready, config).await;
+
+	let quote_ttl = Duration::from_secs(300);
+	for (index, response) in indices.into_iter().zip(responses) {
+		if let Ok(response) = &response {
+			store_quotes(solver, &response.quotes, quote_ttl).await;
+		}
+		results[index] = response.map(|response| response.quotes);
+	}
+
+	Ok(results
+		.into_iter()
+		.enumerate()
+		.map(|(request_index, result)| match result {
+			Ok(quotes) => BatchQuoteResult {
+				request_index,
+				quotes,
+				error: None,
+			},
+			Err(e) => BatchQuoteResult {
+				request_index,
+				quotes: Vec::new(),
+				error: Some(e.to_string()),
+			},
+		})
+		.collect())
+}
+
+/// Validates a quote request against the solver's capabilities.
+///
+/// Returns the requested outputs, all of them supported by the solver.
+fn validate_quote_request(
+	request: &GetQuoteRequest,
+	solver: &SolverEngine,
+) -> Result<Vec<SupportedAsset>, QuoteError> {
+	// 1. Validate the request
+	QuoteValidator::validate_request(request)?;
+
+	// 2. Check solver capabilities: networks only (token support is enforced during collection below)
+	QuoteValidator::validate_supported_networks(request, solver)?;
+
+	// 3. Collect supported assets for this request (for later use: balances/custody/pricing)
+	QuoteValidator::collect_supported_available_inputs(request, solver)?;
+	QuoteValidator::validate_and_collect_requested_outputs(request, solver)
+}
+
 /// Stores generated quotes with a given TTL.
 ///
 /// Storage errors are logged but do not fail the request.
@@ -131,16 +225,7 @@ async fn store_quotes(solver: &SolverEngine, quotes: &[Quote], ttl: Duration) {
 	let storage = solver.storage();
 
 	for quote in quotes {
-		if let Err(e) = storage
-			.store_with_ttl(
-				StorageKey::Quotes.as_str(),
-				&quote.quote_id,
-				quote,
-				None, // No indexes needed for quotes
-				Some(ttl),
-			)
-			.await
-		{
+		if let Err(e) = store_quote(storage, quote, ttl).await {
 			tracing::warn!("Failed to store quote {}: {}", quote.quote_id, e);
 		} else {
 			tracing::debug!("Stored quote {} with TTL {:?}", quote.quote_id, ttl);
@@ -148,6 +233,64 @@ async fn store_quotes(solver: &SolverEngine, quotes: &[Quote], ttl: Duration) {
 	}
 }
 
+/// Lists the unexpired quotes of the token pair of `query`.
+///
+/// All four filters are required, so that quotes can only be listed by
+/// those who know the pair they were requested for. Quotes that have reached
+/// `max_fills` are left out. Results are sorted by expiry, latest first.
+pub async fn list_quotes(
+	query: &QuoteListQuery,
+	storage: &StorageService,
+	max_fills: u32,
+) -> Result<Vec<Quote>, QuoteError> {
+	let (Some(input_chain), Some(output_chain), Some(input_token), Some(output_token)) = (
+		query.input_chain,
+		query.output_chain,
+		query.input_token.as_deref(),
+		query.output_token.as_deref(),
+	) else {
+		return Err(QuoteError::InvalidRequest(
+			"input_chain, output_chain, input_token and output_token are required".to_string(),
+		));
+	};
+	let now = current_timestamp();
+
+	// The input token is the most selective index, the rest of the pair is
+	// matched on the quotes it returns
+	let candidates: Vec<(String, Quote)> = storage
+		.query(
+			StorageKey::Quotes.as_str(),
+			QueryFilter::Equals("input_token".to_string(), input_token.to_lowercase().into()),
+		)
+		.await
+		.map_err(|e| QuoteError::Internal(format!("Storage error: {}", e)))?;
+
+	let mut quotes = Vec::new();
+	for (_, mut quote) in candidates {
+		if quote
+			.valid_until
+			.is_some_and(|valid_until| valid_until <= now)
+			|| !quote_matches_pair(
+				&quote,
+				(input_chain, input_token),
+				(output_chain, output_token),
+			) {
+			continue;
+		}
+		quote.fill_count = quote_fill_count(storage, &quote.quote_id, max_fills)
+			.await
+			.map_err(|e| QuoteError::Internal(format!("Storage error: {}", e)))?;
+		if quote.fill_count < max_fills {
+			quotes.push(quote);
+		}
+	}
+	quotes.sort_by(|a, b| {
+		let expiry = |quote: &Quote| quote.valid_until.unwrap_or(u64::MAX);
+		expiry(b).cmp(&expiry(a))
+	});
+	Ok(quotes)
+}
+
 #[allow(dead_code)]
 /// Retrieves a stored quote by its ID.
 ///
@@ -192,3 +335,100 @@ pub async fn quote_exists(quote_id: &str, solver: &SolverEngine) -> Result<bool,
 		},
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{Address, U256};
+	use solver_core::state::quote::record_quote_fill;
+	use solver_storage::implementations::{file::create_storage, memory::MemoryStorage};
+	use solver_types::{AvailableInput, InteropAddress, QuoteDetails, RequestedOutput};
+
+	fn quote(id: &str, output_chain: u64, valid_for: u64) -> Quote {
+		let user = InteropAddress::new_ethereum(1, Address::repeat_byte(0x01));
+		Quote {
+			orders: Vec::new(),
+			details: QuoteDetails {
+				requested_outputs: vec![RequestedOutput {
+					receiver: user.clone(),
+					asset: InteropAddress::new_ethereum(output_chain, Address::repeat_byte(0xBB)),
+					amount: U256::from(1),
+					calldata: None,
+				}],
+				available_inputs: vec![AvailableInput {
+					user,
+					asset: InteropAddress::new_ethereum(1, Address::repeat_byte(0xAA)),
+					amount: U256::from(1),
+					lock: None,
+				}],
+			},
+			valid_until: Some(current_timestamp() + valid_for),
+			eta: None,
+			quote_id: id.to_string(),
+			provider: "oif-solver".to_string(),
+			fill_count: 0,
+		}
+	}
+
+	fn pair_query() -> QuoteListQuery {
+		QuoteListQuery {
+			input_chain: Some(1),
+			output_chain: Some(10),
+			input_token: Some(format!("0x{}", "AA".repeat(20))),
+			output_token: Some(format!("0x{}", "bb".repeat(20))),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_list_quotes_requires_token_pair() {
+		let storage = StorageService::new(Box::new(MemoryStorage::new()), None);
+		let query = QuoteListQuery {
+			output_token: None,
+			..pair_query()
+		};
+		assert!(matches!(
+			list_quotes(&QuoteListQuery::default(), &storage, 1).await,
+			Err(QuoteError::InvalidRequest(_))
+		));
+		assert!(matches!(
+			list_quotes(&query, &storage, 1).await,
+			Err(QuoteError::InvalidRequest(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_list_quotes_of_pair_below_max_fills() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut config = toml::map::Map::new();
+		config.insert(
+			"storage_path".to_string(),
+			toml::Value::String(dir.path().to_string_lossy().into_owned()),
+		);
+		let storage =
+			StorageService::new(create_storage(&toml::Value::Table(config)).unwrap(), None);
+		for quote in [
+			quote("short", 10, 100),
+			quote("long", 10, 300),
+			quote("filled", 10, 200),
+			quote("other-pair", 8453, 200),
+		] {
+			store_quote(&storage, &quote, Duration::from_secs(300))
+				.await
+				.unwrap();
+		}
+		record_quote_fill(&storage, "long", "order-1", 2)
+			.await
+			.unwrap();
+		for order_id in ["order-2", "order-3"] {
+			record_quote_fill(&storage, "filled", order_id, 2)
+				.await
+				.unwrap();
+		}
+
+		let quotes = list_quotes(&pair_query(), &storage, 2).await.unwrap();
+		let ids: Vec<&str> = quotes.iter().map(|quote| quote.quote_id.as_str()).collect();
+		assert_eq!(ids, vec!["long", "short"]);
+		assert_eq!(quotes[0].fill_count, 1);
+		assert_eq!(quotes[1].fill_count, 0);
+	}
+}
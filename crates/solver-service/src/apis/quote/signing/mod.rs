@@ -19,7 +19,7 @@
 //! - Batch transfers with witness data
 //! - Cross-chain intent specification
 //!
-//! ### ERC-3009 (Future)
+//! ### ERC-3009
 //! Transfer with authorization for USDC and similar tokens:
 //! - Native gasless transfers
 //! - Built-in authorization mechanics
@@ -33,5 +33,6 @@
 //! - **Amount Authorization**: Explicit token amounts and recipients
 
 pub mod payloads {
+	pub mod erc3009;
 	pub mod permit2;
 }
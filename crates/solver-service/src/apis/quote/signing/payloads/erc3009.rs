@@ -0,0 +1,186 @@
+//! ERC-3009 signature payload generation.
+//!
+//! Builds the EIP-712 typed data for `ReceiveWithAuthorization` messages,
+//! which authorize a token transfer to the settlement contract without a
+//! prior approval. ERC-3009 tokens verify these messages against their own
+//! domain, made of the token's name, version, chain and address.
+
+use alloy_primitives::{keccak256, Address as AlloyAddress, B256, U256};
+use serde_json::{json, Value};
+use solver_core::SolverEngine;
+use solver_types::utils::{
+	compute_final_digest, compute_versioned_domain_hash, typed_data_types, Eip712AbiEncoder,
+	RECEIVE_WITH_AUTHORIZATION_TYPE, VERSIONED_DOMAIN_TYPE,
+};
+use solver_types::{with_0x_prefix, Address, QuoteOrder, SignPayloadError, SignPayloadResponse};
+
+/// Builds `eth_signTypedData_v4` typed data for a stored ERC-3009 quote order.
+///
+/// The domain name and version are read from the token contract, which is
+/// the domain of the quote order.
+pub async fn build_erc3009_typed_data(
+	order: &QuoteOrder,
+	solver: &SolverEngine,
+) -> Result<SignPayloadResponse, SignPayloadError> {
+	let chain_id = order
+		.domain
+		.ethereum_chain_id()
+		.map_err(|e| SignPayloadError::Internal(format!("Invalid quote domain chain ID: {}", e)))?;
+	let token = order
+		.domain
+		.ethereum_address()
+		.map_err(|e| SignPayloadError::Internal(format!("Invalid quote domain address: {}", e)))?;
+	let (name, version) = solver
+		.token_discovery()
+		.eip712_domain(chain_id, &Address(token.to_vec()))
+		.await
+		.map_err(|e| {
+			SignPayloadError::InvalidRequest(format!(
+				"Token 0x{:x} on chain {} has no EIP-712 domain: {}",
+				token, chain_id, e
+			))
+		})?;
+	erc3009_typed_data(order, chain_id, &token, &name, &version)
+}
+
+/// Builds the typed data of an ERC-3009 quote order signed against the
+/// domain of the given token.
+pub(crate) fn erc3009_typed_data(
+	order: &QuoteOrder,
+	chain_id: u64,
+	token: &AlloyAddress,
+	name: &str,
+	version: &str,
+) -> Result<SignPayloadResponse, SignPayloadError> {
+	let message = &order.message;
+	let mut enc = Eip712AbiEncoder::new();
+	enc.push_b256(&keccak256(RECEIVE_WITH_AUTHORIZATION_TYPE.as_bytes()));
+	enc.push_address(&message_address(message, "from")?);
+	enc.push_address(&message_address(message, "to")?);
+	enc.push_u256(message_u256(message, "value")?);
+	enc.push_u256(message_u256(message, "validAfter")?);
+	enc.push_u256(message_u256(message, "validBefore")?);
+	enc.push_b256(&B256::from(message_u256(message, "nonce")?));
+	let struct_hash = keccak256(enc.finish());
+
+	let domain_hash = compute_versioned_domain_hash(name, version, chain_id, token);
+	let digest = compute_final_digest(&domain_hash, &struct_hash);
+
+	Ok(SignPayloadResponse {
+		domain: json!({
+			"name": name,
+			"version": version,
+			"chainId": chain_id,
+			"verifyingContract": format!("0x{:x}", token),
+		}),
+		types: typed_data_types(&[VERSIONED_DOMAIN_TYPE, RECEIVE_WITH_AUTHORIZATION_TYPE]),
+		primary_type: order.primary_type.clone(),
+		message: message.clone(),
+		digest: with_0x_prefix(&alloy_primitives::hex::encode(digest)),
+	})
+}
+
+/// Reads an address field of the quote message.
+fn message_address(message: &Value, field: &str) -> Result<AlloyAddress, SignPayloadError> {
+	message[field]
+		.as_str()
+		.and_then(|value| value.parse().ok())
+		.ok_or_else(|| SignPayloadError::Internal(format!("Invalid '{}' in quote message", field)))
+}
+
+/// Reads a numeric field of the quote message, given as a number, decimal or hex string.
+fn message_u256(message: &Value, field: &str) -> Result<U256, SignPayloadError> {
+	let value = match &message[field] {
+		Value::Number(number) => number.as_u64().map(U256::from),
+		Value::String(value) => value.parse::<U256>().ok(),
+		_ => None,
+	};
+	value.ok_or_else(|| SignPayloadError::Internal(format!("Invalid '{}' in quote message", field)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_message_u256_formats() {
+		let message = json!({
+			"value": "1000",
+			"validBefore": 1700000000u64,
+			"nonce": format!("0x{:064x}", 42),
+		});
+
+		assert_eq!(message_u256(&message, "value").unwrap(), U256::from(1000));
+		assert_eq!(
+			message_u256(&message, "validBefore").unwrap(),
+			U256::from(1700000000u64)
+		);
+		assert_eq!(message_u256(&message, "nonce").unwrap(), U256::from(42));
+		assert!(message_u256(&message, "validAfter").is_err());
+	}
+
+	#[test]
+	fn test_typed_data_uses_token_domain() {
+		use alloy_sol_types::{eip712_domain, sol, SolStruct};
+		use solver_types::{InteropAddress, SignatureType};
+
+		sol! {
+			struct ReceiveWithAuthorization {
+				address from;
+				address to;
+				uint256 value;
+				uint256 validAfter;
+				uint256 validBefore;
+				bytes32 nonce;
+			}
+		}
+
+		// Mainnet USDC
+		let token: AlloyAddress = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+			.parse()
+			.unwrap();
+		let from = AlloyAddress::repeat_byte(0x11);
+		let to = AlloyAddress::repeat_byte(0x22);
+		let order = QuoteOrder {
+			signature_type: SignatureType::Erc3009,
+			domain: InteropAddress::new_ethereum(1, token),
+			primary_type: "ReceiveWithAuthorization".to_string(),
+			message: json!({
+				"from": format!("0x{:x}", from),
+				"to": format!("0x{:x}", to),
+				"value": "1000000",
+				"validAfter": 0,
+				"validBefore": 1700000000u64,
+				"nonce": format!("0x{:064x}", 42),
+			}),
+		};
+
+		let response = erc3009_typed_data(&order, 1, &token, "USD Coin", "2").unwrap();
+		assert_eq!(response.domain["name"], "USD Coin");
+		assert_eq!(response.domain["version"], "2");
+		assert_eq!(response.domain["chainId"], 1);
+		assert_eq!(
+			response.domain["verifyingContract"],
+			format!("0x{:x}", token)
+		);
+
+		let expected = ReceiveWithAuthorization {
+			from,
+			to,
+			value: U256::from(1_000_000),
+			validAfter: U256::ZERO,
+			validBefore: U256::from(1700000000u64),
+			nonce: B256::from(U256::from(42)),
+		}
+		.eip712_signing_hash(&eip712_domain! {
+			name: "USD Coin",
+			version: "2",
+			chain_id: 1,
+			verifying_contract: token,
+		});
+		assert_eq!(
+			response.digest,
+			with_0x_prefix(&alloy_primitives::hex::encode(expected))
+		);
+	}
+}
@@ -18,18 +18,18 @@
 //! - **Domain Separation**: EIP-712 domain binding to prevent signature replay attacks
 //! - **Oracle Integration**: Embeds oracle addresses for settlement verification
 
-use crate::apis::quote::registry::PROTOCOL_REGISTRY;
 use alloy_primitives::{keccak256, B256, U256};
 use serde_json::json;
 use solver_config::Config;
 use solver_settlement::SettlementInterface;
+use solver_types::protocols::PROTOCOL_REGISTRY;
 use solver_types::utils::{
-	bytes20_to_alloy_address, DOMAIN_TYPE, MANDATE_OUTPUT_TYPE, NAME_PERMIT2, PERMIT2_WITNESS_TYPE,
-	PERMIT_BATCH_WITNESS_TYPE, TOKEN_PERMISSIONS_TYPE,
+	bytes20_to_alloy_address, typed_data_types, DOMAIN_TYPE, MANDATE_OUTPUT_TYPE, NAME_PERMIT2,
+	PERMIT2_WITNESS_TYPE, PERMIT_BATCH_WITNESS_TYPE, TOKEN_PERMISSIONS_TYPE,
 };
 use solver_types::{
 	utils::{compute_final_digest, Eip712AbiEncoder},
-	GetQuoteRequest, InteropAddress, QuoteError,
+	GetQuoteRequest, InteropAddress, QuoteError, QuoteOrder, SignPayloadError, SignPayloadResponse,
 };
 
 pub fn build_permit2_batch_witness_digest(
@@ -225,3 +225,53 @@ pub fn permit2_domain_address_from_config(
 		})?;
 	Ok(InteropAddress::new_ethereum(chain_id, permit2))
 }
+
+/// Builds `eth_signTypedData_v4` typed data for a stored Permit2 quote order.
+///
+/// The message and digest are taken from the quote so that the signature
+/// commits to the nonce and deadline the quote was issued with.
+pub fn build_permit2_typed_data(
+	order: &QuoteOrder,
+	config: &Config,
+) -> Result<SignPayloadResponse, SignPayloadError> {
+	let chain_id = order
+		.domain
+		.ethereum_chain_id()
+		.map_err(|e| SignPayloadError::Internal(format!("Invalid quote domain chain ID: {}", e)))?;
+	let permit2 = permit2_domain_address_from_config(config, chain_id)
+		.and_then(|domain| {
+			domain
+				.ethereum_address()
+				.map_err(|e| QuoteError::Internal(e.to_string()))
+		})
+		.map_err(|e| SignPayloadError::InvalidRequest(e.to_string()))?;
+
+	let digest = order.message["digest"]
+		.as_str()
+		.ok_or_else(|| SignPayloadError::Internal("Quote order has no digest".to_string()))?
+		.to_string();
+	let mut message = order.message["eip712"].clone();
+	let fields = message.as_object_mut().ok_or_else(|| {
+		SignPayloadError::Internal("Quote order has no EIP-712 message".to_string())
+	})?;
+	fields.remove("digest");
+	fields.remove("signing");
+
+	Ok(SignPayloadResponse {
+		domain: json!({
+			"name": NAME_PERMIT2,
+			"chainId": chain_id,
+			"verifyingContract": format!("0x{:x}", permit2),
+		}),
+		types: typed_data_types(&[
+			DOMAIN_TYPE,
+			PERMIT_BATCH_WITNESS_TYPE,
+			TOKEN_PERMISSIONS_TYPE,
+			PERMIT2_WITNESS_TYPE,
+			MANDATE_OUTPUT_TYPE,
+		]),
+		primary_type: order.primary_type.clone(),
+		message,
+		digest,
+	})
+}
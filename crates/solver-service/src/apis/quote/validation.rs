@@ -14,9 +14,14 @@
 //! 5. **Balance Checks** - Ensures solver has sufficient liquidity
 
 use alloy_primitives::{Address as AlloyAddress, U256};
-use futures::future::try_join_all;
+use futures::future::join_all;
 use solver_core::SolverEngine;
 use solver_types::{GetQuoteRequest, InteropAddress, QuoteError};
+use std::collections::{HashMap, HashSet};
+
+/// Balances of the solver per chain and token, or the reason they could not
+/// be fetched.
+pub type DestinationBalances = HashMap<(u64, AlloyAddress), Result<U256, String>>;
 
 /// Main validator for quote requests.
 ///
@@ -356,11 +361,6 @@ impl QuoteValidator {
 	/// has enough liquidity to fulfill the quote. This is a critical pre-flight
 	/// check to prevent quote generation for unfulfillable requests.
 	///
-	/// # Performance
-	///
-	/// Balance checks are executed in parallel using `futures::try_join_all` for
-	/// optimal performance when checking multiple outputs.
-	///
 	/// # Arguments
 	///
 	/// * `solver` - The solver engine with token manager
@@ -374,49 +374,85 @@ impl QuoteValidator {
 		solver: &SolverEngine,
 		outputs: &[SupportedAsset],
 	) -> Result<(), QuoteError> {
+		let balances = Self::fetch_destination_balances(solver, outputs).await;
+		Self::check_destination_balances(outputs, &balances)
+	}
+
+	/// Fetches the solver's balance of each distinct output token.
+	///
+	/// # Performance
+	///
+	/// Each token is queried once, however many outputs request it, and the
+	/// queries are executed in parallel. Outputs with invalid addresses are
+	/// skipped; [`Self::check_destination_balances`] reports them.
+	pub async fn fetch_destination_balances(
+		solver: &SolverEngine,
+		outputs: &[SupportedAsset],
+	) -> DestinationBalances {
 		let token_manager = solver.token_manager();
+		let tokens: HashSet<(u64, AlloyAddress)> = outputs
+			.iter()
+			.filter_map(|output| Self::extract_chain_and_address(&output.asset).ok())
+			.collect();
+
+		let balance_checks = tokens.into_iter().map(|(chain_id, evm_addr)| async move {
+			let token_addr = solver_types::Address(evm_addr.as_slice().to_vec());
+			let balance = token_manager
+				.check_balance(chain_id, &token_addr)
+				.await
+				.map_err(|e| format!("Balance check failed: {}", e))
+				.and_then(|balance_str| {
+					U256::from_str_radix(&balance_str, 10)
+						.map_err(|e| format!("Failed to parse balance: {}", e))
+				});
+			((chain_id, evm_addr), balance)
+		});
+		join_all(balance_checks).await.into_iter().collect()
+	}
 
-		// Create futures for parallel balance checks
-		let balance_checks = outputs.iter().map(|output| {
-			let output = output.clone();
-			async move {
-				let (chain_id, evm_addr) = Self::extract_chain_and_address(&output.asset)?;
-				let token_addr = solver_types::Address(evm_addr.as_slice().to_vec());
-
-				let balance_str = token_manager
-					.check_balance(chain_id, &token_addr)
-					.await
-					.map_err(|e| QuoteError::Internal(format!("Balance check failed: {}", e)))?;
-
-				let balance = U256::from_str_radix(&balance_str, 10)
-					.map_err(|e| QuoteError::Internal(format!("Failed to parse balance: {}", e)))?;
-
-				if balance < output.amount {
-					let token_hex = alloy_primitives::hex::encode(evm_addr.as_slice());
-					tracing::error!(
-						chain_id = chain_id,
-						required = %output.amount,
-						available = %balance,
-						token = %token_hex,
-						"Insufficient destination balance",
-					);
-					return Err(QuoteError::InsufficientLiquidity);
-				} else {
-					tracing::debug!(
-						chain_id = chain_id,
-						required = %output.amount,
-						available = %balance,
-						token = %alloy_primitives::hex::encode(evm_addr.as_slice()),
-						"Sufficient destination balance"
-					);
-				}
-
-				Ok::<(), QuoteError>(())
+	/// Checks fetched balances against the amounts of the requested outputs.
+	///
+	/// # Errors
+	///
+	/// Returns `QuoteError::InsufficientLiquidity` if any balance is insufficient.
+	/// Returns `QuoteError::Internal` if the balance of an output could not be fetched.
+	pub fn check_destination_balances(
+		outputs: &[SupportedAsset],
+		balances: &DestinationBalances,
+	) -> Result<(), QuoteError> {
+		for output in outputs {
+			let (chain_id, evm_addr) = Self::extract_chain_and_address(&output.asset)?;
+			let balance = match balances.get(&(chain_id, evm_addr)) {
+				Some(Ok(balance)) => *balance,
+				Some(Err(e)) => return Err(QuoteError::Internal(e.clone())),
+				None => {
+					return Err(QuoteError::Internal(
+						"Balance check failed: balance not fetched".to_string(),
+					))
+				},
+			};
+
+			if balance < output.amount {
+				let token_hex = alloy_primitives::hex::encode(evm_addr.as_slice());
+				tracing::error!(
+					chain_id = chain_id,
+					required = %output.amount,
+					available = %balance,
+					token = %token_hex,
+					"Insufficient destination balance",
+				);
+				return Err(QuoteError::InsufficientLiquidity);
+			} else {
+				tracing::debug!(
+					chain_id = chain_id,
+					required = %output.amount,
+					available = %balance,
+					token = %alloy_primitives::hex::encode(evm_addr.as_slice()),
+					"Sufficient destination balance"
+				);
 			}
-		});
+		}
 
-		// Execute all balance checks in parallel
-		try_join_all(balance_checks).await?;
 		Ok(())
 	}
 }
@@ -0,0 +1,225 @@
+//! Signing payload endpoint for the OIF Solver API.
+//!
+//! Returns the EIP-712 typed data a user must sign to accept a stored quote,
+//! in the `eth_signTypedData_v4` format understood by browser wallets, plus
+//! the pre-hashed digest for hardware wallets that sign raw hashes.
+
+use crate::apis::quote::signing::payloads::{erc3009, permit2};
+use solver_config::Config;
+use solver_core::SolverEngine;
+use solver_storage::StorageError;
+use solver_types::{
+	current_timestamp, Quote, QuoteOrder, SignPayloadError, SignPayloadRequest,
+	SignPayloadResponse, SignatureType, SigningScheme, StorageKey,
+};
+use tracing::info;
+
+/// Primary type of Permit2 quote orders.
+const PERMIT2_PRIMARY_TYPE: &str = "PermitBatchWitnessTransferFrom";
+
+/// Handles POST /sign-payload requests.
+///
+/// Looks up the quote, selects its order for the requested signing scheme and
+/// builds the typed data for it. Expired quotes are reported as not found.
+pub async fn process_sign_payload_request(
+	request: SignPayloadRequest,
+	solver: &SolverEngine,
+	config: &Config,
+) -> Result<SignPayloadResponse, SignPayloadError> {
+	info!(
+		"Building {:?} signing payload for quote {}",
+		request.signing_scheme, request.quote_id
+	);
+
+	let quote = match solver
+		.storage()
+		.retrieve::<Quote>(StorageKey::Quotes.as_str(), &request.quote_id)
+		.await
+	{
+		Ok(quote) => quote,
+		Err(StorageError::NotFound) => {
+			return Err(SignPayloadError::QuoteNotFound(request.quote_id));
+		},
+		Err(e) => return Err(SignPayloadError::Internal(format!("Storage error: {}", e))),
+	};
+	if quote
+		.valid_until
+		.is_some_and(|valid_until| valid_until < current_timestamp())
+	{
+		return Err(SignPayloadError::QuoteNotFound(request.quote_id));
+	}
+
+	let order = select_order(&quote, &request.signing_scheme).ok_or_else(|| {
+		SignPayloadError::InvalidRequest(format!(
+			"Quote {} has no {:?} order",
+			request.quote_id, request.signing_scheme
+		))
+	})?;
+
+	match request.signing_scheme {
+		SigningScheme::Permit2 => permit2::build_permit2_typed_data(order, config),
+		SigningScheme::Erc3009 => erc3009::build_erc3009_typed_data(order, solver).await,
+	}
+}
+
+/// Returns the order of a quote signed with the given scheme.
+fn select_order<'a>(quote: &'a Quote, scheme: &SigningScheme) -> Option<&'a QuoteOrder> {
+	quote.orders.iter().find(|order| match scheme {
+		SigningScheme::Permit2 => {
+			matches!(order.signature_type, SignatureType::Eip712)
+				&& order.primary_type == PERMIT2_PRIMARY_TYPE
+		},
+		SigningScheme::Erc3009 => matches!(order.signature_type, SignatureType::Erc3009),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::apis::quote::generation::QuoteGenerator;
+	use crate::init::{render_config, InitAnswers};
+	use alloy_primitives::U256;
+	use async_trait::async_trait;
+	use solver_config::DomainConfig;
+	use solver_settlement::{
+		utils::parse_oracle_config, OracleConfig, SettlementError, SettlementInterface,
+		SettlementService,
+	};
+	use solver_types::{
+		AvailableInput, ConfigSchema, FillProof, GetQuoteRequest, InteropAddress, Order,
+		RequestedOutput, TransactionHash,
+	};
+	use std::collections::HashMap;
+	use std::str::FromStr;
+	use std::sync::Arc;
+
+	const USDC_MAINNET: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+	const USDC_OPTIMISM: &str = "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85";
+	const ESCROW: &str = "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0";
+	const USER: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+	/// Settlement accepting the configured oracles without any chain access.
+	struct MockSettlement {
+		oracle_config: OracleConfig,
+	}
+
+	#[async_trait]
+	impl SettlementInterface for MockSettlement {
+		fn oracle_config(&self) -> &OracleConfig {
+			&self.oracle_config
+		}
+
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn get_attestation(
+			&self,
+			_order: &Order,
+			_tx_hash: &TransactionHash,
+		) -> Result<FillProof, SettlementError> {
+			unimplemented!()
+		}
+
+		async fn can_claim(&self, _order: &Order, _fill_proof: &FillProof) -> bool {
+			false
+		}
+	}
+
+	/// Configuration settling USDC from mainnet to Optimism through an escrow.
+	fn config() -> Config {
+		let chain = |chain_id: u64| {
+			serde_json::json!({
+				"chain_id": chain_id,
+				"rpc_url": "http://localhost:8545",
+				"input_settler_address": ESCROW,
+				"output_settler_address": "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+				"oracle_address": "0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9",
+			})
+		};
+		let answers: InitAnswers = serde_json::from_value(serde_json::json!({
+			"solver_id": "test",
+			"chains": [chain(1), chain(10)],
+			"private_key": {
+				"source": "inline",
+				"private_key": "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+			},
+			"tokens": [{
+				"symbol": "USDC",
+				"decimals": 6,
+				"addresses": { "1": USDC_MAINNET, "10": USDC_OPTIMISM },
+			}],
+			"settlement": { "mechanism": "direct" },
+		}))
+		.unwrap();
+		let mut config = Config::from_str(&render_config(&answers).unwrap()).unwrap();
+		config.settlement.domain = [1, 10]
+			.into_iter()
+			.map(|chain_id| DomainConfig {
+				chain_id,
+				address: ESCROW.to_string(),
+				name: String::new(),
+			})
+			.collect();
+		config
+	}
+
+	/// Quotes moving mainnet USDC, which supports ERC-3009, to Optimism.
+	async fn erc3009_quote(config: &Config) -> Quote {
+		let oracle_config =
+			parse_oracle_config(&config.settlement.implementations["direct"]).unwrap();
+		let settlement = SettlementService::new(HashMap::from([(
+			"direct".to_string(),
+			Box::new(MockSettlement { oracle_config }) as Box<dyn SettlementInterface>,
+		)]));
+		let address = |chain_id: u64, address: &str| {
+			InteropAddress::new_ethereum(chain_id, address.parse().unwrap())
+		};
+		let request = GetQuoteRequest {
+			user: address(1, USER),
+			available_inputs: vec![AvailableInput {
+				user: address(1, USER),
+				asset: address(1, USDC_MAINNET),
+				amount: U256::from(1_000_000),
+				lock: None,
+			}],
+			requested_outputs: vec![RequestedOutput {
+				receiver: address(10, USER),
+				asset: address(10, USDC_OPTIMISM),
+				amount: U256::from(1_000_000),
+				calldata: None,
+			}],
+			min_valid_until: None,
+			preference: None,
+		};
+		QuoteGenerator::new(Arc::new(settlement))
+			.generate_quotes(&request, config)
+			.await
+			.unwrap()
+			.remove(0)
+	}
+
+	#[tokio::test]
+	async fn test_erc3009_scheme_signs_erc3009_quote() {
+		let config = config();
+		let quote = erc3009_quote(&config).await;
+
+		assert!(select_order(&quote, &SigningScheme::Permit2).is_none());
+		let order = select_order(&quote, &SigningScheme::Erc3009).unwrap();
+		let token: alloy_primitives::Address = USDC_MAINNET.parse().unwrap();
+		let payload = erc3009::erc3009_typed_data(order, 1, &token, "USD Coin", "2").unwrap();
+
+		assert_eq!(payload.primary_type, "ReceiveWithAuthorization");
+		assert_eq!(payload.domain["name"], "USD Coin");
+		assert_eq!(payload.domain["chainId"], 1);
+		assert_eq!(
+			payload.domain["verifyingContract"],
+			format!("0x{:x}", token)
+		);
+		assert_eq!(payload.message["value"], "1000000");
+		// Funds are authorized to the escrow of the input chain
+		let to: alloy_primitives::Address =
+			payload.message["to"].as_str().unwrap().parse().unwrap();
+		assert_eq!(to, ESCROW.parse::<alloy_primitives::Address>().unwrap());
+	}
+}
@@ -60,7 +60,7 @@ pub async fn get_tokens(State(solver): State<Arc<SolverEngine>>) -> Json<TokensR
 		response.networks.insert(
 			chain_id.to_string(),
 			NetworkTokens {
-				chain_id: *chain_id,
+				chain_id: chain_id.as_u64(),
 				input_settler: with_0x_prefix(&hex::encode(&network.input_settler_address.0)),
 				output_settler: with_0x_prefix(&hex::encode(&network.output_settler_address.0)),
 				tokens: network
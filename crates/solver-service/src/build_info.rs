@@ -0,0 +1,36 @@
+//! Build information captured at compile time.
+//!
+//! Identifies the exact code version that is running, for startup logs and
+//! the admin status endpoint.
+
+use serde::Serialize;
+
+/// Constants generated by the build script.
+#[allow(dead_code)]
+mod built_info {
+	include!(concat!(env!("OUT_DIR"), "/built.rs"));
+}
+
+/// Version, commit and build time of the running binary.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+	/// Crate version.
+	pub version: &'static str,
+	/// Git commit the binary was built from, `unknown` outside a git checkout.
+	pub commit: &'static str,
+	/// Whether the working tree had uncommitted changes when built.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub dirty: Option<bool>,
+	/// Time the binary was built, in RFC 2822 format.
+	pub build_time: &'static str,
+}
+
+/// Returns the build information of the running binary.
+pub fn build_info() -> BuildInfo {
+	BuildInfo {
+		version: built_info::PKG_VERSION,
+		commit: built_info::GIT_COMMIT_HASH.unwrap_or("unknown"),
+		dirty: built_info::GIT_DIRTY,
+		build_time: built_info::BUILT_TIME_UTC,
+	}
+}
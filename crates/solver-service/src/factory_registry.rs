@@ -6,13 +6,14 @@
 use solver_account::{AccountError, AccountInterface};
 use solver_config::Config;
 use solver_core::{SolverBuilder, SolverEngine, SolverFactories};
-use solver_delivery::{DeliveryError, DeliveryInterface};
 use solver_discovery::{DiscoveryError, DiscoveryInterface};
 use solver_order::{ExecutionStrategy, OrderError, OrderInterface, StrategyError};
+use solver_price::{PriceFeedError, PriceFeedInterface};
 use solver_settlement::{SettlementError, SettlementInterface};
 use solver_storage::{StorageError, StorageInterface};
-use solver_types::NetworksConfig;
-use std::collections::HashMap;
+use solver_types::{ConfigSchema, NetworksConfig, SchemaDefinition};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::OnceLock;
 
 // Type aliases for factory functions
@@ -23,17 +24,19 @@ pub type DeliveryFactory = fn(
 	&NetworksConfig,
 	&solver_types::SecretString,
 	&std::collections::HashMap<u64, solver_types::SecretString>,
-) -> Result<Box<dyn DeliveryInterface>, DeliveryError>;
+) -> solver_delivery::DeliveryFuture;
 pub type DiscoveryFactory =
 	fn(&toml::Value, &NetworksConfig) -> Result<Box<dyn DiscoveryInterface>, DiscoveryError>;
 pub type OrderFactory = fn(
 	&toml::Value,
 	&NetworksConfig,
-	&solver_types::oracle::OracleRoutes,
+	&std::sync::Arc<dyn solver_types::oracle::OracleRouteSource>,
 ) -> Result<Box<dyn OrderInterface>, OrderError>;
 pub type SettlementFactory =
 	fn(&toml::Value, &NetworksConfig) -> Result<Box<dyn SettlementInterface>, SettlementError>;
 pub type StrategyFactory = fn(&toml::Value) -> Result<Box<dyn ExecutionStrategy>, StrategyError>;
+pub type PriceFactory =
+	fn(&toml::Value, &NetworksConfig) -> Result<Box<dyn PriceFeedInterface>, PriceFeedError>;
 
 /// Global registry for all implementation factories
 pub struct FactoryRegistry {
@@ -44,6 +47,15 @@ pub struct FactoryRegistry {
 	pub order: HashMap<String, OrderFactory>,
 	pub settlement: HashMap<String, SettlementFactory>,
 	pub strategy: HashMap<String, StrategyFactory>,
+	pub price: HashMap<String, PriceFactory>,
+	/// Configuration schemas keyed by `<component>.<implementation>`.
+	pub schemas: BTreeMap<String, Box<dyn ConfigSchema>>,
+}
+
+impl Default for FactoryRegistry {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl FactoryRegistry {
@@ -57,6 +69,8 @@ impl FactoryRegistry {
 			order: HashMap::new(),
 			settlement: HashMap::new(),
 			strategy: HashMap::new(),
+			price: HashMap::new(),
+			schemas: BTreeMap::new(),
 		}
 	}
 
@@ -94,6 +108,36 @@ impl FactoryRegistry {
 	pub fn register_strategy(&mut self, name: impl Into<String>, factory: StrategyFactory) {
 		self.strategy.insert(name.into(), factory);
 	}
+
+	/// Register a price feed implementation
+	pub fn register_price(&mut self, name: impl Into<String>, factory: PriceFactory) {
+		self.price.insert(name.into(), factory);
+	}
+
+	/// Register the configuration schema of an implementation of a component
+	pub fn register_schema(&mut self, component: &str, name: &str, schema: Box<dyn ConfigSchema>) {
+		self.schemas
+			.insert(format!("{}.{}", component, name), schema);
+	}
+
+	/// Get the configuration schema of an implementation by its
+	/// `<component>.<implementation>` name, e.g. `settlement.direct`
+	pub fn schema(&self, component_name: &str) -> Option<SchemaDefinition> {
+		self.schemas
+			.get(component_name)
+			.map(|schema| schema.schema())
+	}
+
+	/// Write the configuration schema of every implementation to
+	/// `<dir>/<component>.<implementation>.json`, returning the number written
+	pub fn export_schemas(&self, dir: &Path) -> std::io::Result<usize> {
+		std::fs::create_dir_all(dir)?;
+		for (component_name, schema) in &self.schemas {
+			let json = serde_json::to_string_pretty(&schema.schema())?;
+			std::fs::write(dir.join(format!("{}.json", component_name)), json + "\n")?;
+		}
+		Ok(self.schemas.len())
+	}
 }
 
 // Global registry instance
@@ -146,6 +190,29 @@ pub fn initialize_registry() -> &'static FactoryRegistry {
 			registry.register_strategy(name, factory);
 		}
 
+		// Auto-register all price feed implementations
+		for (name, factory) in solver_price::get_all_implementations() {
+			tracing::debug!("Registering price implementation: {}", name);
+			registry.register_price(name, factory);
+		}
+
+		// Register the configuration schemas of all implementations
+		let schemas = [
+			("storage", solver_storage::get_all_schemas()),
+			("account", solver_account::get_all_schemas()),
+			("delivery", solver_delivery::get_all_schemas()),
+			("discovery", solver_discovery::get_all_schemas()),
+			("order", solver_order::get_all_order_schemas()),
+			("settlement", solver_settlement::get_all_schemas()),
+			("strategy", solver_order::get_all_strategy_schemas()),
+			("price", solver_price::get_all_schemas()),
+		];
+		for (component, implementations) in schemas {
+			for (name, schema) in implementations {
+				registry.register_schema(component, name, schema);
+			}
+		}
+
 		registry
 	})
 }
@@ -176,12 +243,21 @@ macro_rules! build_factories {
 	}};
 }
 
-/// Build solver using registry and config
-pub async fn build_solver_from_config(
+/// Build one solver per solver identity using registry and config
+pub async fn build_solvers_from_config(
 	config: Config,
-) -> Result<SolverEngine, Box<dyn std::error::Error>> {
+) -> Result<Vec<SolverEngine>, Box<dyn std::error::Error>> {
 	let registry = get_registry();
-	let builder = SolverBuilder::new(config.clone());
+	let tenants = config.tenants();
+	let account_implementations: HashMap<_, _> = tenants
+		.iter()
+		.flat_map(|tenant| tenant.account.implementations.clone())
+		.collect();
+	let strategy_implementations: HashMap<_, _> = tenants
+		.iter()
+		.flat_map(|tenant| tenant.order.strategy.implementations.clone())
+		.collect();
+	let builder = SolverBuilder::with_configs(tenants);
 
 	// Build factories for each component type using the macro
 	let storage_factories =
@@ -205,14 +281,13 @@ pub async fn build_solver_from_config(
 		settlement,
 		"settlement"
 	);
-	let account_factories =
-		build_factories!(registry, config.account.implementations, account, "account");
-	let strategy_factories = build_factories!(
-		registry,
-		config.order.strategy.implementations,
-		strategy,
-		"strategy"
-	);
+	let account_factories = build_factories!(registry, account_implementations, account, "account");
+	let strategy_factories =
+		build_factories!(registry, strategy_implementations, strategy, "strategy");
+	let price_factories = match &config.price {
+		Some(price) => build_factories!(registry, price.implementations, price, "price"),
+		None => HashMap::new(),
+	};
 
 	let factories = SolverFactories {
 		storage_factories,
@@ -222,7 +297,8 @@ pub async fn build_solver_from_config(
 		order_factories,
 		settlement_factories,
 		strategy_factories,
+		price_factories,
 	};
 
-	Ok(builder.build(factories).await?)
+	Ok(builder.build_all(factories).await?)
 }
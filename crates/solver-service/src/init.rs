@@ -0,0 +1,598 @@
+//! Configuration wizard for the `init` subcommand.
+//!
+//! Asks for the settings needed by a first configuration, either through
+//! interactive prompts or as JSON answers read from stdin, renders them as a
+//! configuration file and validates it like the `config validate` subcommand.
+//!
+//! Validation runs on a copy of the configuration holding a placeholder key,
+//! so that it neither requires the key's environment variable to be set nor
+//! prints an inline key in its diagnostics.
+
+use crate::validate::{self, ValidationReport};
+use dialoguer::{Confirm, Input, MultiSelect, Password, Select};
+use serde::Deserialize;
+use solver_config::Config;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use toml::{Table, Value};
+
+/// Well-known chains offered by the wizard, as (chain ID, name, public RPC URL).
+const WELL_KNOWN_CHAINS: &[(u64, &str, &str)] = &[
+	(1, "Ethereum", "https://ethereum-rpc.publicnode.com"),
+	(10, "Optimism", "https://mainnet.optimism.io"),
+	(137, "Polygon", "https://polygon-rpc.com"),
+	(8453, "Base", "https://mainnet.base.org"),
+	(42161, "Arbitrum One", "https://arb1.arbitrum.io/rpc"),
+	(
+		11155111,
+		"Sepolia",
+		"https://ethereum-sepolia-rpc.publicnode.com",
+	),
+	(31337, "Local (Anvil)", "http://localhost:8545"),
+	(
+		31338,
+		"Local (Anvil, second chain)",
+		"http://localhost:8546",
+	),
+];
+
+/// Private key validated in place of the solver's key.
+const PLACEHOLDER_PRIVATE_KEY: &str =
+	"0x0000000000000000000000000000000000000000000000000000000000000001";
+
+/// Errors that can occur while generating a configuration.
+#[derive(Debug, Error)]
+pub enum InitError {
+	#[error("Prompt failed: {0}")]
+	Prompt(#[from] dialoguer::Error),
+	#[error("Invalid answers: {0}")]
+	Answers(String),
+	#[error("{0} already exists, pass --force to overwrite it")]
+	Exists(PathBuf),
+	#[error("IO error: {0}")]
+	Io(#[from] std::io::Error),
+}
+
+/// Answers to the wizard's questions.
+#[derive(Debug, Deserialize)]
+pub struct InitAnswers {
+	/// Unique identifier of the solver instance.
+	pub solver_id: String,
+	/// Chains the solver operates on.
+	pub chains: Vec<ChainAnswers>,
+	/// Where the solver's private key is read from.
+	pub private_key: PrivateKeySource,
+	/// Tokens supported across the chains.
+	#[serde(default)]
+	pub tokens: Vec<TokenAnswers>,
+	/// Settlement mechanism used to claim filled orders.
+	pub settlement: SettlementMechanism,
+}
+
+/// Settings of a single chain.
+#[derive(Debug, Deserialize)]
+pub struct ChainAnswers {
+	pub chain_id: u64,
+	pub rpc_url: String,
+	pub input_settler_address: String,
+	pub output_settler_address: String,
+	/// Oracle attesting fills on this chain, used as input and output oracle.
+	pub oracle_address: String,
+}
+
+/// Source of the solver's private key.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum PrivateKeySource {
+	/// Read from an environment variable when the configuration is loaded.
+	Env { variable: String },
+	/// Written into the configuration file.
+	Inline { private_key: String },
+}
+
+/// A token supported on several chains, forming the pairs the solver fills.
+#[derive(Debug, Deserialize)]
+pub struct TokenAnswers {
+	pub symbol: String,
+	pub decimals: u8,
+	/// Token address per chain ID.
+	pub addresses: BTreeMap<u64, String>,
+}
+
+/// Settlement mechanism and its dispute window.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "mechanism", rename_all = "snake_case")]
+pub enum SettlementMechanism {
+	/// Claims after a fixed dispute period.
+	Direct {
+		#[serde(default = "default_period_seconds")]
+		dispute_period_seconds: u64,
+	},
+	/// Claims after an optimistic challenge period.
+	Optimistic {
+		#[serde(default = "default_period_seconds")]
+		challenge_period_seconds: u64,
+	},
+}
+
+/// Returns the default dispute or challenge period in seconds.
+///
+/// This provides a default value of 300 seconds (5 minutes).
+fn default_period_seconds() -> u64 {
+	300
+}
+
+/// Generates a configuration at `path` and validates it.
+///
+/// Answers are prompted for interactively, or read from stdin as JSON when
+/// `non_interactive` is set. An existing file is only overwritten with `force`.
+pub async fn run(
+	path: &Path,
+	non_interactive: bool,
+	force: bool,
+) -> Result<ValidationReport, InitError> {
+	if path.exists() && !force {
+		let overwrite = !non_interactive
+			&& Confirm::new()
+				.with_prompt(format!("{} already exists. Overwrite it?", path.display()))
+				.default(false)
+				.interact()?;
+		if !overwrite {
+			return Err(InitError::Exists(path.to_path_buf()));
+		}
+	}
+
+	let answers = if non_interactive {
+		let mut input = String::new();
+		std::io::stdin().read_to_string(&mut input)?;
+		serde_json::from_str(&input).map_err(|e| InitError::Answers(e.to_string()))?
+	} else {
+		prompt_answers()?
+	};
+
+	if let PrivateKeySource::Inline { private_key } = &answers.private_key {
+		let hex = private_key.strip_prefix("0x").unwrap_or(private_key);
+		if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+			return Err(InitError::Answers(
+				"the private key must be a 32-byte hex string".to_string(),
+			));
+		}
+	}
+
+	let config = match Config::from_str(&render_config_with_key(&answers, PLACEHOLDER_PRIVATE_KEY)?)
+	{
+		Ok(config) => config,
+		Err(e) => {
+			// Render the diagnostic with the offending configuration snippet
+			eprintln!("{:?}", miette::Report::new(e));
+			return Err(InitError::Answers(
+				"the answers do not form a valid configuration".to_string(),
+			));
+		},
+	};
+	write_config(path, &render_config(&answers)?)?;
+	println!("Wrote {}", path.display());
+
+	Ok(validate::validate_config(&config).await)
+}
+
+/// Writes a configuration file readable by its owner only, as it may hold
+/// the solver's private key.
+fn write_config(path: &Path, toml: &str) -> std::io::Result<()> {
+	let mut options = std::fs::OpenOptions::new();
+	options.write(true).create(true).truncate(true);
+	#[cfg(unix)]
+	std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+	let mut file = options.open(path)?;
+	// The mode only applies to new files
+	#[cfg(unix)]
+	file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+	file.write_all(toml.as_bytes())
+}
+
+/// Renders the answers as a configuration file.
+pub fn render_config(answers: &InitAnswers) -> Result<String, InitError> {
+	let private_key = match &answers.private_key {
+		PrivateKeySource::Env { variable } => format!("${{{}}}", variable),
+		PrivateKeySource::Inline { private_key } => private_key.clone(),
+	};
+	render_config_with_key(answers, &private_key)
+}
+
+/// Renders the answers as a configuration file with the given private key.
+fn render_config_with_key(answers: &InitAnswers, private_key: &str) -> Result<String, InitError> {
+	if answers.chains.len() < 2 {
+		return Err(InitError::Answers(
+			"at least 2 chains must be selected".to_string(),
+		));
+	}
+	let chain_ids: Vec<u64> = answers.chains.iter().map(|c| c.chain_id).collect();
+	let network_ids = || Value::Array(chain_ids.iter().map(|id| int(*id)).collect());
+
+	let mut networks = Table::new();
+	let mut oracles = Table::new();
+	let mut routes = Table::new();
+	for chain in &answers.chains {
+		let tokens = answers
+			.tokens
+			.iter()
+			.filter_map(|token| {
+				let address = token.addresses.get(&chain.chain_id)?;
+				Some(Value::Table(Table::from_iter([
+					("address".to_string(), str(address)),
+					("symbol".to_string(), str(&token.symbol)),
+					("decimals".to_string(), int(token.decimals as u64)),
+				])))
+			})
+			.collect();
+		let rpc_url = Table::from_iter([("http".to_string(), str(&chain.rpc_url))]);
+		networks.insert(
+			chain.chain_id.to_string(),
+			Value::Table(Table::from_iter([
+				(
+					"input_settler_address".to_string(),
+					str(&chain.input_settler_address),
+				),
+				(
+					"output_settler_address".to_string(),
+					str(&chain.output_settler_address),
+				),
+				(
+					"rpc_urls".to_string(),
+					Value::Array(vec![Value::Table(rpc_url)]),
+				),
+				("tokens".to_string(), Value::Array(tokens)),
+			])),
+		);
+		oracles.insert(
+			chain.chain_id.to_string(),
+			Value::Array(vec![str(&chain.oracle_address)]),
+		);
+		routes.insert(
+			chain.chain_id.to_string(),
+			Value::Array(
+				chain_ids
+					.iter()
+					.filter(|id| **id != chain.chain_id)
+					.map(|id| int(*id))
+					.collect(),
+			),
+		);
+	}
+
+	let (settlement_name, period) = match answers.settlement {
+		SettlementMechanism::Direct {
+			dispute_period_seconds,
+		} => ("direct", ("dispute_period_seconds", dispute_period_seconds)),
+		SettlementMechanism::Optimistic {
+			challenge_period_seconds,
+		} => (
+			"optimistic",
+			("challenge_period_seconds", challenge_period_seconds),
+		),
+	};
+
+	let config = Table::from_iter([
+		(
+			"solver".to_string(),
+			table([
+				("id", str(&answers.solver_id)),
+				("monitoring_timeout_minutes", int(480)),
+			]),
+		),
+		("networks".to_string(), Value::Table(networks)),
+		(
+			"storage".to_string(),
+			table([
+				("primary", str("file")),
+				("cleanup_interval_seconds", int(3600)),
+				(
+					"implementations",
+					table([("file", table([("storage_path", str("./data/storage"))]))]),
+				),
+			]),
+		),
+		(
+			"account".to_string(),
+			table([
+				("primary", str("local")),
+				(
+					"implementations",
+					table([("local", table([("private_key", str(private_key))]))]),
+				),
+			]),
+		),
+		(
+			"delivery".to_string(),
+			table([(
+				"implementations",
+				table([("evm_alloy", table([("network_ids", network_ids())]))]),
+			)]),
+		),
+		(
+			"discovery".to_string(),
+			table([(
+				"implementations",
+				table([("onchain_eip7683", table([("network_ids", network_ids())]))]),
+			)]),
+		),
+		(
+			"order".to_string(),
+			table([
+				("implementations", table([("eip7683", table([]))])),
+				(
+					"strategy",
+					table([
+						("primary", str("simple")),
+						("implementations", table([("simple", table([]))])),
+					]),
+				),
+			]),
+		),
+		(
+			"settlement".to_string(),
+			table([(
+				"implementations",
+				table([(
+					settlement_name,
+					table([
+						("order", str("eip7683")),
+						("network_ids", network_ids()),
+						(period.0, int(period.1)),
+						(
+							"oracles",
+							table([
+								("input", Value::Table(oracles.clone())),
+								("output", Value::Table(oracles)),
+							]),
+						),
+						("routes", Value::Table(routes)),
+					]),
+				)]),
+			)]),
+		),
+	]);
+
+	toml::to_string_pretty(&config).map_err(|e| InitError::Answers(e.to_string()))
+}
+
+/// Prompts for the answers interactively.
+fn prompt_answers() -> Result<InitAnswers, InitError> {
+	let solver_id: String = Input::new()
+		.with_prompt("Solver ID")
+		.default("oif-solver".to_string())
+		.interact_text()?;
+
+	let mut items: Vec<String> = WELL_KNOWN_CHAINS
+		.iter()
+		.map(|(chain_id, name, _)| format!("{} ({})", name, chain_id))
+		.collect();
+	items.push("Other chain".to_string());
+	let selected = MultiSelect::new()
+		.with_prompt("Chains to operate on (space to select, at least 2)")
+		.items(&items)
+		.interact()?;
+
+	let mut chains = Vec::new();
+	for index in selected {
+		let (chain_id, name, rpc_url) = match WELL_KNOWN_CHAINS.get(index) {
+			Some((chain_id, name, rpc_url)) => (*chain_id, name.to_string(), rpc_url.to_string()),
+			None => {
+				let chain_id: u64 = Input::new().with_prompt("Chain ID").interact_text()?;
+				(chain_id, format!("chain {}", chain_id), String::new())
+			},
+		};
+		println!("Settings of {}:", name);
+		let mut rpc_prompt = Input::<String>::new().with_prompt("  RPC URL");
+		if !rpc_url.is_empty() {
+			rpc_prompt = rpc_prompt.default(rpc_url);
+		}
+		chains.push(ChainAnswers {
+			chain_id,
+			rpc_url: rpc_prompt.interact_text()?,
+			input_settler_address: prompt_address("  Input settler address")?,
+			output_settler_address: prompt_address("  Output settler address")?,
+			oracle_address: prompt_address("  Oracle address")?,
+		});
+	}
+
+	let private_key = match Select::new()
+		.with_prompt("Private key source")
+		.items(&["Environment variable", "Stored in the configuration file"])
+		.default(0)
+		.interact()?
+	{
+		0 => PrivateKeySource::Env {
+			variable: Input::new()
+				.with_prompt("Environment variable")
+				.default("SOLVER_PRIVATE_KEY".to_string())
+				.interact_text()?,
+		},
+		_ => PrivateKeySource::Inline {
+			private_key: Password::new()
+				.with_prompt("Private key (hex)")
+				.interact()?,
+		},
+	};
+
+	let mut tokens = Vec::new();
+	while Confirm::new()
+		.with_prompt("Add a supported token?")
+		.default(tokens.is_empty())
+		.interact()?
+	{
+		let symbol: String = Input::new().with_prompt("  Symbol").interact_text()?;
+		let decimals: u8 = Input::new()
+			.with_prompt("  Decimals")
+			.default(18)
+			.interact_text()?;
+		let mut addresses = BTreeMap::new();
+		for chain in &chains {
+			let address: String = Input::new()
+				.with_prompt(format!(
+					"  Address on chain {} (empty if unsupported)",
+					chain.chain_id
+				))
+				.allow_empty(true)
+				.interact_text()?;
+			if !address.is_empty() {
+				addresses.insert(chain.chain_id, address);
+			}
+		}
+		tokens.push(TokenAnswers {
+			symbol,
+			decimals,
+			addresses,
+		});
+	}
+
+	let settlement = match Select::new()
+		.with_prompt("Settlement mechanism")
+		.items(&["Direct", "Optimistic"])
+		.default(0)
+		.interact()?
+	{
+		0 => SettlementMechanism::Direct {
+			dispute_period_seconds: Input::new()
+				.with_prompt("Dispute period in seconds")
+				.default(default_period_seconds())
+				.interact_text()?,
+		},
+		_ => SettlementMechanism::Optimistic {
+			challenge_period_seconds: Input::new()
+				.with_prompt("Challenge period in seconds")
+				.default(default_period_seconds())
+				.interact_text()?,
+		},
+	};
+
+	Ok(InitAnswers {
+		solver_id,
+		chains,
+		private_key,
+		tokens,
+		settlement,
+	})
+}
+
+/// Prompts for a 20-byte hex address.
+fn prompt_address(prompt: &str) -> Result<String, InitError> {
+	Ok(Input::<String>::new()
+		.with_prompt(prompt)
+		.validate_with(|input: &String| -> Result<(), &str> {
+			let hex = input.strip_prefix("0x").unwrap_or(input);
+			if hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+				Ok(())
+			} else {
+				Err("expected a 0x-prefixed 20-byte hex address")
+			}
+		})
+		.interact_text()?)
+}
+
+fn str(value: &str) -> Value {
+	Value::String(value.to_string())
+}
+
+fn int(value: u64) -> Value {
+	Value::Integer(value as i64)
+}
+
+fn table<const N: usize>(entries: [(&str, Value); N]) -> Value {
+	Value::Table(
+		entries
+			.into_iter()
+			.map(|(key, value)| (key.to_string(), value))
+			.collect(),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn answers(private_key: serde_json::Value) -> InitAnswers {
+		let chain = |chain_id: u64| {
+			serde_json::json!({
+				"chain_id": chain_id,
+				"rpc_url": "http://localhost:8545",
+				"input_settler_address": "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+				"output_settler_address": "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+				"oracle_address": "0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9",
+			})
+		};
+		serde_json::from_value(serde_json::json!({
+			"solver_id": "wizard",
+			"chains": [chain(31337), chain(31338)],
+			"private_key": private_key,
+			"tokens": [{
+				"symbol": "TOKA",
+				"decimals": 18,
+				"addresses": {
+					"31337": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+					"31338": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+				},
+			}],
+			"settlement": { "mechanism": "direct" },
+		}))
+		.unwrap()
+	}
+
+	#[test]
+	fn test_rendered_answers_are_valid_config() {
+		let answers = answers(serde_json::json!({
+			"source": "inline",
+			"private_key": "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+		}));
+
+		let config = Config::from_str(&render_config(&answers).unwrap()).unwrap();
+		assert_eq!(config.solver.id, "wizard");
+		assert_eq!(config.networks[&31338].tokens[0].symbol, "TOKA");
+		let direct = &config.settlement.implementations["direct"];
+		assert_eq!(direct["dispute_period_seconds"].as_integer(), Some(300));
+		assert_eq!(
+			direct["routes"]["31337"].as_array().unwrap(),
+			&vec![Value::Integer(31338)]
+		);
+
+		// Validation never sees the inline key
+		let placeholder = render_config_with_key(&answers, PLACEHOLDER_PRIVATE_KEY).unwrap();
+		assert!(!placeholder
+			.contains("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"));
+	}
+
+	#[test]
+	fn test_env_key_source_validates_without_the_variable() {
+		let answers = answers(serde_json::json!({
+			"source": "env",
+			"variable": "INIT_TEST_UNSET_PRIVATE_KEY",
+		}));
+
+		let toml = render_config(&answers).unwrap();
+		assert!(toml.contains("private_key = \"${INIT_TEST_UNSET_PRIVATE_KEY}\""));
+		assert!(Config::from_str(&toml).is_err());
+		// The configuration is validated with a placeholder key instead
+		let placeholder = render_config_with_key(&answers, PLACEHOLDER_PRIVATE_KEY).unwrap();
+		assert!(Config::from_str(&placeholder).is_ok());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_config_file_is_private() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("config.toml");
+		// Overwritten files are restricted as well
+		std::fs::write(&path, "").unwrap();
+		std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+		write_config(&path, "[solver]").unwrap();
+		assert_eq!(std::fs::read_to_string(&path).unwrap(), "[solver]");
+		let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+		assert_eq!(mode & 0o777, 0o600);
+	}
+}
@@ -0,0 +1,15 @@
+//! Components of the OIF solver service binary.
+//!
+//! Exposes the API server, configuration tooling and implementation
+//! registries used by the `solver` binary, so that they can also be
+//! exercised from benchmarks.
+
+pub mod api_keys;
+pub mod apis;
+pub mod build_info;
+pub mod factory_registry;
+pub mod init;
+pub mod log_level;
+pub mod server;
+pub mod uptime;
+pub mod validate;
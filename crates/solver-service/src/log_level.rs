@@ -0,0 +1,261 @@
+//! Runtime log level overrides.
+//!
+//! Allows raising or lowering the log level of individual components (tracing
+//! targets such as `solver_delivery`) without restarting the solver. Overrides
+//! are persisted to storage so they survive restarts, and may expire after a
+//! given duration, reverting the component to the configured default.
+
+use serde::{Deserialize, Serialize};
+use solver_storage::StorageService;
+use solver_types::{current_timestamp, StorageKey};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Handle used to swap the active log filter at runtime.
+pub type ReloadHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Storage ID under which all overrides are persisted.
+const OVERRIDES_STORAGE_ID: &str = "overrides";
+
+/// Errors that can occur when changing log levels.
+#[derive(Debug, Error)]
+pub enum LogLevelError {
+	#[error("Invalid log level: {0}")]
+	InvalidLevel(String),
+	#[error("Invalid component: {0}")]
+	InvalidComponent(String),
+	#[error("Failed to apply log filter: {0}")]
+	Reload(String),
+}
+
+/// Log level override of a single component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLevelOverride {
+	/// Level applied to the component.
+	pub level: String,
+	/// Unix timestamp at which the override reverts, if temporary.
+	pub expires_at: Option<u64>,
+}
+
+/// Request body for POST /api/admin/log-level.
+#[derive(Debug, Deserialize)]
+pub struct SetLogLevelRequest {
+	/// Tracing target to override, e.g. `solver_delivery`.
+	pub component: String,
+	/// Level to apply (trace, debug, info, warn, error, off).
+	pub level: String,
+	/// Reverts the override to the default after this many seconds.
+	#[serde(default)]
+	pub duration_seconds: Option<u64>,
+}
+
+/// Current log filter configuration.
+#[derive(Debug, Serialize)]
+pub struct LogLevelsResponse {
+	/// Default filter directives from the command line or `RUST_LOG`.
+	pub default: String,
+	/// Active overrides by component.
+	pub overrides: BTreeMap<String, LogLevelOverride>,
+}
+
+/// Manages runtime log level overrides on top of the default filter.
+pub struct LogLevelController {
+	handle: ReloadHandle,
+	default_directives: String,
+	storage: Arc<StorageService>,
+	overrides: Mutex<BTreeMap<String, LogLevelOverride>>,
+}
+
+impl LogLevelController {
+	/// Creates a new LogLevelController without any overrides.
+	pub fn new(
+		handle: ReloadHandle,
+		default_directives: String,
+		storage: Arc<StorageService>,
+	) -> Arc<Self> {
+		Arc::new(Self {
+			handle,
+			default_directives,
+			storage,
+			overrides: Mutex::new(BTreeMap::new()),
+		})
+	}
+
+	/// Restores persisted overrides, dropping those that have expired.
+	pub async fn restore(self: &Arc<Self>) {
+		let stored: BTreeMap<String, LogLevelOverride> = match self
+			.storage
+			.retrieve(StorageKey::LogLevels.as_str(), OVERRIDES_STORAGE_ID)
+			.await
+		{
+			Ok(stored) => stored,
+			Err(_) => return,
+		};
+
+		let now = current_timestamp();
+		let mut overrides = self.overrides.lock().await;
+		for (component, entry) in stored {
+			if let Some(expires_at) = entry.expires_at {
+				if expires_at <= now {
+					continue;
+				}
+				self.schedule_revert(component.clone(), expires_at, expires_at - now);
+			}
+			overrides.insert(component, entry);
+		}
+
+		if !overrides.is_empty() {
+			tracing::info!(overrides = overrides.len(), "Restored log level overrides");
+		}
+		if let Err(e) = self.apply(&overrides) {
+			tracing::warn!(error = %e, "Failed to restore log level overrides");
+		}
+	}
+
+	/// Sets the log level of a component, optionally reverting after `duration_seconds`.
+	pub async fn set(
+		self: &Arc<Self>,
+		request: SetLogLevelRequest,
+	) -> Result<LogLevelsResponse, LogLevelError> {
+		validate_component(&request.component)?;
+		let level = LevelFilter::from_str(&request.level)
+			.map_err(|_| LogLevelError::InvalidLevel(request.level.clone()))?
+			.to_string()
+			.to_lowercase();
+
+		let expires_at = request
+			.duration_seconds
+			.map(|seconds| current_timestamp() + seconds);
+
+		let mut overrides = self.overrides.lock().await;
+		let previous = overrides.insert(
+			request.component.clone(),
+			LogLevelOverride { level, expires_at },
+		);
+		if let Err(e) = self.apply(&overrides) {
+			match previous {
+				Some(previous) => overrides.insert(request.component, previous),
+				None => overrides.remove(&request.component),
+			};
+			return Err(e);
+		}
+		self.persist(&overrides).await;
+
+		if let (Some(expires_at), Some(seconds)) = (expires_at, request.duration_seconds) {
+			self.schedule_revert(request.component, expires_at, seconds);
+		}
+
+		Ok(self.snapshot(&overrides))
+	}
+
+	/// Returns the default filter and the active overrides.
+	pub async fn current(&self) -> LogLevelsResponse {
+		self.snapshot(&*self.overrides.lock().await)
+	}
+
+	/// Removes an override once it expires, unless it has been replaced since.
+	fn schedule_revert(self: &Arc<Self>, component: String, expires_at: u64, seconds: u64) {
+		let controller = Arc::clone(self);
+		tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_secs(seconds)).await;
+
+			let mut overrides = controller.overrides.lock().await;
+			if overrides
+				.get(&component)
+				.is_some_and(|entry| entry.expires_at == Some(expires_at))
+			{
+				overrides.remove(&component);
+				if let Err(e) = controller.apply(&overrides) {
+					tracing::warn!(error = %e, "Failed to revert log level override");
+				}
+				controller.persist(&overrides).await;
+				tracing::info!(component = %component, "Log level override expired");
+			}
+		});
+	}
+
+	/// Applies the default filter combined with the given overrides.
+	fn apply(&self, overrides: &BTreeMap<String, LogLevelOverride>) -> Result<(), LogLevelError> {
+		let filter = EnvFilter::try_new(build_directives(&self.default_directives, overrides))
+			.map_err(|e| LogLevelError::Reload(e.to_string()))?;
+		self.handle
+			.reload(filter)
+			.map_err(|e| LogLevelError::Reload(e.to_string()))
+	}
+
+	/// Persists the overrides, logging failures.
+	async fn persist(&self, overrides: &BTreeMap<String, LogLevelOverride>) {
+		if let Err(e) = self
+			.storage
+			.store(
+				StorageKey::LogLevels.as_str(),
+				OVERRIDES_STORAGE_ID,
+				overrides,
+				None,
+			)
+			.await
+		{
+			tracing::warn!(error = %e, "Failed to persist log level overrides");
+		}
+	}
+
+	fn snapshot(&self, overrides: &BTreeMap<String, LogLevelOverride>) -> LogLevelsResponse {
+		LogLevelsResponse {
+			default: self.default_directives.clone(),
+			overrides: overrides.clone(),
+		}
+	}
+}
+
+/// Ensures a component is a plain tracing target such as `solver_delivery::alloy`.
+fn validate_component(component: &str) -> Result<(), LogLevelError> {
+	let valid = !component.is_empty()
+		&& component
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':');
+	if valid {
+		Ok(())
+	} else {
+		Err(LogLevelError::InvalidComponent(component.to_string()))
+	}
+}
+
+/// Combines the default directives with per-component overrides.
+fn build_directives(default: &str, overrides: &BTreeMap<String, LogLevelOverride>) -> String {
+	let mut directives = vec![default.to_string()];
+	directives.extend(
+		overrides
+			.iter()
+			.map(|(component, entry)| format!("{}={}", component, entry.level)),
+	);
+	directives.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_build_directives_appends_overrides() {
+		let overrides = BTreeMap::from([(
+			"solver_delivery".to_string(),
+			LogLevelOverride {
+				level: "warn".to_string(),
+				expires_at: None,
+			},
+		)]);
+
+		assert_eq!(
+			build_directives("info", &overrides),
+			"info,solver_delivery=warn"
+		);
+		assert!(validate_component("solver_delivery::alloy").is_ok());
+		assert!(validate_component("solver=debug").is_err());
+	}
+}
@@ -4,16 +4,16 @@
 //! validates, executes, and settles cross-chain orders. It uses a modular
 //! architecture with pluggable implementations for different components.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use metrics_exporter_prometheus::PrometheusBuilder;
 use solver_config::Config;
+use solver_core::SolverEngine;
 use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::task::JoinSet;
 
-mod apis;
-mod factory_registry;
-mod server;
-
-use factory_registry::build_solver_from_config;
+use solver_service::factory_registry::{build_solvers_from_config, get_registry};
+use solver_service::{build_info, init, log_level, server, uptime, validate};
 
 /// Command-line arguments for the solver service.
 #[derive(Parser, Debug)]
@@ -26,6 +26,57 @@ struct Args {
 	/// Log level (trace, debug, info, warn, error)
 	#[arg(short, long, default_value = "info")]
 	log_level: String,
+
+	#[command(subcommand)]
+	command: Option<Command>,
+}
+
+/// Subcommands of the solver service. Runs the solver when none is given.
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Generate a configuration file by answering a few questions, then
+	/// validate it. Writes to the `--config` path.
+	Init {
+		/// Read the answers from stdin as JSON instead of prompting
+		#[arg(long)]
+		non_interactive: bool,
+		/// Overwrite an existing configuration file
+		#[arg(long)]
+		force: bool,
+	},
+	/// Configuration management commands
+	Config {
+		#[command(subcommand)]
+		command: ConfigCommand,
+	},
+	/// Replay a stored intent as if it had been freshly discovered, then run
+	/// the solver to process it. Requires `solver.debug_mode`.
+	Replay {
+		/// ID of the stored intent to replay
+		#[arg(long)]
+		intent_id: String,
+		/// Solver the intent belongs to. Defaults to the first solver.
+		#[arg(long)]
+		solver_id: Option<String>,
+	},
+}
+
+/// Configuration management subcommands.
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+	/// Validate the configuration file and all configured implementations
+	/// without starting the solver. Exits with code 1 on any error.
+	Validate {
+		/// Also fail on warnings about missing recommended settings
+		#[arg(long)]
+		strict: bool,
+	},
+	/// Export the configuration schema of every implementation as JSON
+	Schemas {
+		/// Directory the schemas are written to
+		#[arg(long, default_value = "schemas")]
+		out_dir: PathBuf,
+	},
 }
 
 /// Main entry point for the solver service.
@@ -34,64 +85,203 @@ struct Args {
 /// 1. Parses command-line arguments
 /// 2. Initializes logging infrastructure
 /// 3. Loads configuration from file
-/// 4. Builds the solver engine with all implementations
-/// 5. Runs the solver until interrupted
+/// 4. Builds a solver engine per solver identity with all implementations
+/// 5. Runs the solvers until interrupted
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let args = Args::parse();
 
-	// Initialize tracing with env filter
-	use tracing_subscriber::{fmt, EnvFilter};
+	// Initialize tracing with a reloadable env filter
+	use tracing_subscriber::{fmt, prelude::*, reload, EnvFilter};
 
 	// Create env filter with default from args
-	let default_directive = args.log_level.to_string();
-	let env_filter =
-		EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_directive));
-
-	fmt()
-		.with_env_filter(env_filter)
-		.with_thread_ids(true)
-		.with_target(true)
+	let default_directives = std::env::var(EnvFilter::DEFAULT_ENV)
+		.ok()
+		.filter(|directives| EnvFilter::try_new(directives).is_ok())
+		.unwrap_or_else(|| args.log_level.to_string());
+	let (env_filter, log_reload_handle) = reload::Layer::new(EnvFilter::new(&default_directives));
+
+	tracing_subscriber::registry()
+		.with(env_filter)
+		.with(fmt::layer().with_thread_ids(true).with_target(true))
 		.init();
 
-	tracing::info!("Started solver");
+	if let Some(Command::Init {
+		non_interactive,
+		force,
+	}) = args.command
+	{
+		match init::run(&args.config, non_interactive, force).await {
+			Ok(report) => {
+				validate::print_report(&args.config, &report);
+				std::process::exit(if report.is_success(false) { 0 } else { 1 });
+			},
+			Err(e) => {
+				eprintln!("error: {}", e);
+				std::process::exit(1);
+			},
+		}
+	}
+
+	if let Some(Command::Config {
+		command: ConfigCommand::Validate { strict },
+	}) = args.command
+	{
+		let report = validate::validate_config_file(&args.config).await;
+		validate::print_report(&args.config, &report);
+		std::process::exit(if report.is_success(strict) { 0 } else { 1 });
+	}
+
+	if let Some(Command::Config {
+		command: ConfigCommand::Schemas { out_dir },
+	}) = &args.command
+	{
+		match get_registry().export_schemas(out_dir) {
+			Ok(count) => {
+				println!("Wrote {} schemas to {}", count, out_dir.display());
+				std::process::exit(0);
+			},
+			Err(e) => {
+				eprintln!("error: {}", e);
+				std::process::exit(1);
+			},
+		}
+	}
+
+	let build = build_info::build_info();
+	tracing::info!(
+		version = build.version,
+		commit = build.commit,
+		build_time = build.build_time,
+		"Started solver"
+	);
+
+	// Record metrics for export in the Prometheus format by the API server
+	let prometheus = PrometheusBuilder::new().install_recorder()?;
 
 	// Load configuration
-	let config = Config::from_file(args.config.to_str().unwrap()).await?;
+	let config = match Config::from_file(args.config.to_str().unwrap()).await {
+		Ok(config) => config,
+		Err(e) => {
+			// Render the diagnostic with the offending configuration snippet
+			eprintln!("{:?}", miette::Report::new(e));
+			std::process::exit(1);
+		},
+	};
 	tracing::info!("Loaded configuration [{}]", config.solver.id);
 
-	// Build solver engine with implementations using the factory registry
-	let solver = build_solver_from_config(config.clone()).await?;
-	let solver = Arc::new(solver);
+	// Build solver engines with implementations using the factory registry
+	let solvers: Vec<Arc<SolverEngine>> = build_solvers_from_config(config.clone())
+		.await?
+		.into_iter()
+		.map(Arc::new)
+		.collect();
+	let solver = solvers.first().cloned().ok_or("No solver configured")?;
+
+	// Log the address of each solver's key so operators can verify it before any fill
+	for solver in &solvers {
+		match solver.account().get_address().await {
+			Ok(address) => tracing::info!(
+				solver_id = %solver.solver_id(),
+				address = %address,
+				commit = build.commit,
+				"Loaded solver account"
+			),
+			Err(e) => tracing::error!(
+				solver_id = %solver.solver_id(),
+				error = %e,
+				"Failed to get solver address"
+			),
+		}
+	}
+
+	// Restore runtime log level overrides from previous runs
+	let log_levels = log_level::LogLevelController::new(
+		log_reload_handle,
+		default_directives,
+		solver.storage().clone(),
+	);
+	log_levels.restore().await;
+
+	// Track uptime and report why the previous run stopped
+	let uptime = uptime::UptimeMonitor::new(solver.storage().clone());
+	let uptime_task = uptime.start().await;
+
+	// Queue the replayed intent so it is processed once the solver runs
+	if let Some(Command::Replay {
+		intent_id,
+		solver_id,
+	}) = &args.command
+	{
+		let solver = match solver_id {
+			Some(solver_id) => solvers
+				.iter()
+				.find(|solver| solver.solver_id() == solver_id)
+				.ok_or_else(|| format!("Unknown solver '{}'", solver_id))?,
+			None => &solver,
+		};
+		solver.replay_intent(intent_id).await?;
+		tracing::info!("Queued intent {} for replay", intent_id);
+	}
 
 	// Check if API server should be started
 	let api_enabled = config.api.as_ref().is_some_and(|api| api.enabled);
 
-	if api_enabled {
+	let result = if api_enabled {
 		let api_config = config.api.as_ref().unwrap().clone();
-		let api_solver = Arc::clone(&solver);
+		let api_solvers = solvers.clone();
 
-		// Start both the solver and the API server concurrently
-		let solver_task = solver.run();
-		let api_task = server::start_server(api_config, api_solver);
+		// Start both the solvers and the API server concurrently
+		let solver_task = run_solvers(&solvers);
+		let api_task = server::start_server(api_config, api_solvers, log_levels, prometheus);
 
 		// Run both tasks concurrently
 		tokio::select! {
 			result = solver_task => {
 				tracing::info!("Solver finished");
-				result?;
+				result
 			}
 			result = api_task => {
 				tracing::info!("API server finished");
-				result?;
+				result
 			}
 		}
 	} else {
-		// Run only the solver
+		// Run only the solvers
 		tracing::info!("Starting solver only");
-		solver.run().await?;
-	}
+		run_solvers(&solvers).await
+	};
+
+	uptime_task.abort();
+	let exit_reason = match result {
+		Ok(()) => uptime::ExitReason::Graceful,
+		Err(_) => uptime::ExitReason::Panic,
+	};
+	uptime.record_exit(exit_reason).await;
+	result?;
 
 	tracing::info!("Stopped solver");
 	Ok(())
 }
+
+/// Runs each solver engine in its own task until all of them stop.
+///
+/// Returns on the first solver failing, aborting the others.
+async fn run_solvers(solvers: &[Arc<SolverEngine>]) -> Result<(), Box<dyn std::error::Error>> {
+	let mut tasks = JoinSet::new();
+	for solver in solvers {
+		let solver = Arc::clone(solver);
+		tasks.spawn(async move {
+			let result = solver.run().await;
+			if let Err(e) = &result {
+				tracing::error!(solver_id = %solver.solver_id(), error = %e, "Solver failed");
+			}
+			result
+		});
+	}
+
+	while let Some(result) = tasks.join_next().await {
+		result??;
+	}
+	Ok(())
+}
@@ -3,44 +3,126 @@
 //! This module provides a minimal HTTP server infrastructure
 //! for the OIF Solver API.
 
+use crate::api_keys::{
+	tokens_match, ApiKeyError, ApiKeyInfo, ApiKeyStore, CreateApiKeyRequest, CreateApiKeyResponse,
+	Permission,
+};
+use crate::build_info::{build_info, BuildInfo};
+use crate::factory_registry::get_registry;
+use crate::log_level::{LogLevelController, LogLevelError, LogLevelsResponse, SetLogLevelRequest};
 use axum::{
-	extract::{Path, State},
-	http::StatusCode,
-	response::{IntoResponse, Json},
-	routing::{get, post},
+	extract::{FromRequestParts, MatchedPath, Path, Query, Request, State},
+	http::{header, request::Parts, HeaderMap, Method, StatusCode},
+	middleware::{self, Next},
+	response::{IntoResponse, Json, Response},
+	routing::{delete, get, post},
 	Router, ServiceExt,
 };
+use metrics_exporter_prometheus::PrometheusHandle;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use solver_config::{ApiConfig, Config};
+use solver_core::audit::{AuditEntry, AuditPage};
+use solver_core::cancellation::{CancellationError, OrderCancellation};
+use solver_core::engine::token_manager::TokenGasEstimate;
+use solver_core::event_persister::EventLogEntry;
+use solver_core::inventory::InventorySummary;
+use solver_core::recovery::RecoveryReport;
+use solver_core::reputation::PeerReputation;
+use solver_core::state::dead_letter::DeadLetter;
+use solver_core::webhooks::WebhookRetryEntry;
 use solver_core::SolverEngine;
-use solver_types::{APIError, GetOrderResponse, GetQuoteRequest, GetQuoteResponse};
+use solver_delivery::RpcMethodStats;
+use solver_storage::{StorageError, StorageHealthReport};
+use solver_types::{
+	APIError, AccountInfo, AuditQuery, BatchQuoteResult, EventLogQuery, GetOrderResponse,
+	GetQuoteRequest, GetQuoteResponse, Intent, OrderAnalytics, OrderAnalyticsQuery, PreviewResult,
+	Quote, QuoteListQuery, ReportFormat, ReportQuery, SchemaDefinition, SignPayloadRequest,
+	SignPayloadResponse, SimulationResult,
+};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tower_http::normalize_path::NormalizePath;
 
+/// Header selecting the solver a request is routed to.
+const SOLVER_ID_HEADER: &str = "x-solver-id";
+
+/// Environment variable overriding the configured master admin token.
+///
+/// The master token can only be rotated by changing this variable (or the
+/// configuration) and restarting; no endpoint modifies it.
+const ADMIN_TOKEN_ENV: &str = "SOLVER_ADMIN_TOKEN";
+
 /// Shared application state for the API server.
 #[derive(Clone)]
 pub struct AppState {
-	/// Reference to the solver engine for processing requests.
+	/// Solver engine handling requests without an `X-Solver-Id` header.
 	pub solver: Arc<SolverEngine>,
-	/// Complete configuration.
+	/// All solver engines by solver ID.
+	pub solvers: Arc<HashMap<String, Arc<SolverEngine>>>,
+	/// Complete configuration of the default solver.
 	pub config: Config,
 	/// HTTP client for forwarding requests.
 	pub http_client: reqwest::Client,
 	/// Discovery service URL for forwarding orders (if configured).
 	pub discovery_url: Option<String>,
+	/// Runtime log level overrides.
+	pub log_levels: Arc<LogLevelController>,
+	/// Master token required by admin endpoints, if admin endpoints are enabled.
+	pub admin_token: Option<String>,
+	/// Store of API keys checked when API keys are required.
+	pub api_keys: Arc<ApiKeyStore>,
+}
+
+/// Solver engine selected by the `X-Solver-Id` header of a request.
+///
+/// Requests without the header are handled by the default solver.
+pub struct SelectedSolver(pub Arc<SolverEngine>);
+
+impl FromRequestParts<AppState> for SelectedSolver {
+	type Rejection = APIError;
+
+	async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, APIError> {
+		let Some(value) = parts.headers.get(SOLVER_ID_HEADER) else {
+			return Ok(Self(state.solver.clone()));
+		};
+
+		value
+			.to_str()
+			.ok()
+			.and_then(|solver_id| state.solvers.get(solver_id))
+			.map(|solver| Self(solver.clone()))
+			.ok_or_else(|| APIError::NotFound {
+				error_type: "SOLVER_NOT_FOUND".to_string(),
+				message: format!("Unknown solver {:?}", value),
+				details: None,
+			})
+	}
 }
 
 /// Starts the HTTP server for the API.
 ///
 /// This function creates and configures the HTTP server with routing,
-/// middleware, and error handling for the endpoint.
+/// middleware, and error handling for the endpoint. Requests are routed to
+/// one of `solvers` by the `X-Solver-Id` header, defaulting to the first.
 pub async fn start_server(
 	api_config: ApiConfig,
-	solver: Arc<SolverEngine>,
+	solvers: Vec<Arc<SolverEngine>>,
+	log_levels: Arc<LogLevelController>,
+	prometheus: PrometheusHandle,
 ) -> Result<(), Box<dyn std::error::Error>> {
+	let solver = solvers
+		.first()
+		.cloned()
+		.ok_or("API server requires at least one solver")?;
+	let solvers = solvers
+		.into_iter()
+		.map(|solver| (solver.solver_id().to_string(), solver))
+		.collect();
+
 	// Get the full config from the solver engine
 	let config = solver.config().clone();
 
@@ -69,26 +151,23 @@ pub async fn start_server(
 		tracing::warn!("No offchain_eip7683 discovery source configured - /orders endpoint will not be available");
 	}
 
+	let admin_token = std::env::var(ADMIN_TOKEN_ENV)
+		.ok()
+		.filter(|token| !token.is_empty())
+		.or_else(|| api_config.admin_token.clone());
+	let api_keys = Arc::new(ApiKeyStore::new(solver.storage().clone()));
+
 	let app_state = AppState {
 		solver,
+		solvers: Arc::new(solvers),
 		config,
 		http_client,
 		discovery_url,
+		log_levels,
+		admin_token,
+		api_keys,
 	};
-
-	// Build the router with /api base path and quote endpoint
-	let app = Router::new()
-		.nest(
-			"/api",
-			Router::new()
-				.route("/quotes", post(handle_quote))
-				.route("/orders", post(handle_order))
-				.route("/orders/{id}", get(handle_get_order_by_id))
-				.route("/tokens", get(handle_get_tokens))
-				.route("/tokens/{chain_id}", get(handle_get_tokens_for_chain)),
-		)
-		.layer(ServiceBuilder::new().layer(CorsLayer::permissive()))
-		.with_state(app_state);
+	let app = build_router(&api_config, app_state, prometheus);
 
 	let bind_address = format!("{}:{}", api_config.host, api_config.port);
 	let listener = TcpListener::bind(&bind_address).await?;
@@ -104,15 +183,134 @@ pub async fn start_server(
 	Ok(())
 }
 
+/// Builds the router of the API.
+///
+/// Admin, debug and API key protected endpoints are enabled according to the
+/// API configuration and the admin token of the application state.
+fn build_router(
+	api_config: &ApiConfig,
+	app_state: AppState,
+	prometheus: PrometheusHandle,
+) -> Router {
+	let mut api_router = Router::new()
+		.route("/quotes", post(handle_quote).get(handle_list_quotes))
+		.route("/quotes/batch", post(handle_batch_quote))
+		.route("/sign-payload", post(handle_sign_payload))
+		.route("/orders", post(handle_order))
+		.route("/orders/{id}", get(handle_get_order_by_id))
+		.route("/intents/{id}/cancel", post(handle_cancel_intent))
+		.route("/analytics/orders", get(handle_order_analytics))
+		.route("/capabilities", get(handle_get_capabilities))
+		.route("/tokens", get(handle_get_tokens))
+		.route("/tokens/{chain_id}", get(handle_get_tokens_for_chain));
+
+	// Admin endpoints expose inventory, accounts and audit data
+	let mut admin_router = Router::new()
+		.route("/admin/simulate-fill", post(handle_simulate_fill))
+		.route("/admin/order/preview", post(handle_preview_order))
+		.route("/admin/accounts", get(handle_list_accounts))
+		.route("/admin/status", get(handle_admin_status))
+		.route("/admin/inventory", get(handle_inventory))
+		.route("/admin/report", get(handle_report))
+		.route("/admin/reputation", get(handle_reputation))
+		.route(
+			"/admin/recovery/last-report",
+			get(handle_last_recovery_report),
+		)
+		.route("/admin/audit", get(handle_audit_log))
+		.route("/admin/audit/{order_id}", get(handle_audit_entry))
+		.route("/admin/events/log", get(handle_event_log))
+		.route("/admin/webhook-retries", get(handle_webhook_retries))
+		.route("/admin/dead-letters", get(handle_dead_letters))
+		.route("/admin/token-gas-costs", get(handle_token_gas_costs))
+		.route("/admin/schemas/{component_name}", get(handle_get_schema));
+	if api_config.public_admin_endpoints {
+		tracing::warn!("Admin endpoints are served without authentication");
+	} else {
+		if app_state.admin_token.is_none() {
+			tracing::warn!(
+				"No admin token is set - admin endpoints only accept API keys with the admin permission"
+			);
+		}
+		admin_router = admin_router.route_layer(middleware::from_fn_with_state(
+			app_state.clone(),
+			require_admin,
+		));
+	}
+	api_router = api_router.merge(admin_router);
+
+	// Streaming order updates keeps a connection open per client
+	if api_config.sse_enabled {
+		api_router = api_router.route("/orders/{id}/events", get(handle_order_events));
+	}
+
+	// Endpoints changing solver behavior require the admin token
+	if app_state.admin_token.is_some() {
+		api_router = api_router
+			.route(
+				"/admin/log-level",
+				get(handle_get_log_levels).post(handle_set_log_level),
+			)
+			.route(
+				"/admin/api-keys",
+				get(handle_list_api_keys).post(handle_create_api_key),
+			)
+			.route("/admin/api-keys/{id}", delete(handle_revoke_api_key))
+			.route(
+				"/admin/settlement/{name}/reset-circuit",
+				post(handle_reset_claim_circuit),
+			)
+			.route(
+				"/admin/dead-letters/{order_id}/retry",
+				post(handle_retry_dead_letter),
+			);
+	}
+
+	// Endpoints modifying solver state are only exposed in debug mode, and
+	// require the admin token
+	if app_state.config.solver.debug_mode && app_state.admin_token.is_some() {
+		tracing::warn!("Debug mode enabled - /admin/replay-intent endpoint is available");
+		api_router = api_router.route(
+			"/admin/replay-intent/{intent_id}",
+			post(handle_replay_intent),
+		);
+	}
+
+	// Order and admin endpoints require an API key with the matching permission
+	if api_config.require_api_keys {
+		if app_state.admin_token.is_none() {
+			tracing::warn!(
+				"API keys are required but no admin token is set - keys cannot be managed"
+			);
+		}
+		api_router = api_router.route_layer(middleware::from_fn_with_state(
+			app_state.clone(),
+			require_api_key,
+		));
+	}
+
+	// Build the router with /api base path and quote endpoint
+	Router::new()
+		.route(
+			"/metrics",
+			get(move || std::future::ready(prometheus.render())),
+		)
+		.route("/health/storage", get(handle_storage_health))
+		.route("/ready", get(handle_ready))
+		.nest("/api", api_router)
+		.layer(ServiceBuilder::new().layer(CorsLayer::permissive()))
+		.with_state(app_state)
+}
+
 /// Handles POST /api/quotes requests.
 ///
 /// This endpoint processes quote requests and returns price estimates
 /// for cross-chain intents following the ERC-7683 standard.
 async fn handle_quote(
-	State(state): State<AppState>,
+	SelectedSolver(solver): SelectedSolver,
 	Json(request): Json<GetQuoteRequest>,
 ) -> Result<Json<GetQuoteResponse>, APIError> {
-	match crate::apis::quote::process_quote_request(request, &state.solver, &state.config).await {
+	match crate::apis::quote::process_quote_request(request, &solver, solver.config()).await {
 		Ok(response) => Ok(Json(response)),
 		Err(e) => {
 			tracing::warn!("Quote request failed: {}", e);
@@ -121,15 +319,52 @@ async fn handle_quote(
 	}
 }
 
+/// Handles POST /api/quotes/batch requests.
+///
+/// Processes up to 50 quote requests and returns the quotes or the error of
+/// each, tagged with its position in the batch.
+async fn handle_batch_quote(
+	SelectedSolver(solver): SelectedSolver,
+	Json(requests): Json<Vec<GetQuoteRequest>>,
+) -> Result<Json<Vec<BatchQuoteResult>>, APIError> {
+	match crate::apis::quote::process_batch_quote_request(requests, &solver, solver.config()).await
+	{
+		Ok(results) => Ok(Json(results)),
+		Err(e) => {
+			tracing::warn!("Batch quote request failed: {}", e);
+			Err(APIError::from(e))
+		},
+	}
+}
+
+/// Handles POST /api/sign-payload requests.
+///
+/// Returns the EIP-712 typed data and digest a user must sign to accept a
+/// stored quote with the requested signing scheme.
+async fn handle_sign_payload(
+	SelectedSolver(solver): SelectedSolver,
+	Json(request): Json<SignPayloadRequest>,
+) -> Result<Json<SignPayloadResponse>, APIError> {
+	match crate::apis::sign_payload::process_sign_payload_request(request, &solver, solver.config())
+		.await
+	{
+		Ok(response) => Ok(Json(response)),
+		Err(e) => {
+			tracing::warn!("Sign payload request failed: {}", e);
+			Err(APIError::from(e))
+		},
+	}
+}
+
 /// Handles GET /api/orders/{id} requests.
 ///
 /// This endpoint retrieves order details by ID, providing status information
 /// and execution details for cross-chain intent orders.
 async fn handle_get_order_by_id(
 	Path(id): Path<String>,
-	State(state): State<AppState>,
+	SelectedSolver(solver): SelectedSolver,
 ) -> Result<Json<GetOrderResponse>, APIError> {
-	match crate::apis::order::get_order_by_id(Path(id), &state.solver).await {
+	match crate::apis::order::get_order_by_id(Path(id), &solver).await {
 		Ok(response) => Ok(Json(response)),
 		Err(e) => {
 			tracing::warn!("Order retrieval failed: {}", e);
@@ -138,13 +373,345 @@ async fn handle_get_order_by_id(
 	}
 }
 
+/// Handles GET /api/orders/{id}/events requests.
+///
+/// Streams the order's current state and subsequent status updates as
+/// Server-Sent Events until the order reaches a terminal state.
+async fn handle_order_events(
+	Path(id): Path<String>,
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<crate::apis::order_events::OrderEventStream, APIError> {
+	crate::apis::order_events::stream_order_events(Path(id), &solver)
+		.await
+		.map_err(|e| {
+			tracing::warn!("Order event stream failed: {}", e);
+			APIError::from(e)
+		})
+}
+
+/// Handles GET /api/analytics/orders requests.
+///
+/// Returns throughput statistics over the orders matching the query.
+async fn handle_order_analytics(
+	SelectedSolver(solver): SelectedSolver,
+	Query(query): Query<OrderAnalyticsQuery>,
+) -> Result<Json<OrderAnalytics>, APIError> {
+	match solver.order_analytics(&query).await {
+		Ok(analytics) => Ok(Json(analytics)),
+		Err(e) => Err(APIError::InternalServerError {
+			error_type: "ANALYTICS_FAILED".to_string(),
+			message: e.to_string(),
+		}),
+	}
+}
+
+/// Handles GET /api/quotes requests.
+///
+/// Returns the unexpired quotes of a token pair that can still be filled,
+/// latest expiry first. The chains and tokens of the pair are required.
+async fn handle_list_quotes(
+	SelectedSolver(solver): SelectedSolver,
+	Query(query): Query<QuoteListQuery>,
+) -> Result<Json<Vec<Quote>>, APIError> {
+	let max_fills = solver
+		.config()
+		.api
+		.as_ref()
+		.map_or(1, |api| api.quote_max_fills);
+	match crate::apis::quote::list_quotes(&query, solver.storage(), max_fills).await {
+		Ok(quotes) => Ok(Json(quotes)),
+		Err(e) => Err(APIError::from(e)),
+	}
+}
+
+/// Status of a solver reported by the admin API.
+#[derive(Debug, Serialize)]
+struct AdminStatus {
+	solver_id: String,
+	simulation_mode: bool,
+	/// Version, commit and build time of the running binary.
+	build: BuildInfo,
+	/// Delivery RPC calls by method and chain, slowest on average first.
+	rpc_methods: Vec<RpcMethodStats>,
+	/// Detected EIP-5792 call batch support by chain, empty unless
+	/// `use_wallet_batch` is enabled.
+	wallet_batch_support: BTreeMap<u64, bool>,
+}
+
+/// Handles GET /api/admin/status requests.
+///
+/// Returns the solver's status and build information, with a per-method
+/// breakdown of delivery RPC call latencies and the detected EIP-5792 call
+/// batch support.
+async fn handle_admin_status(SelectedSolver(solver): SelectedSolver) -> Json<AdminStatus> {
+	let delivery = solver.delivery();
+	Json(AdminStatus {
+		solver_id: solver.solver_id().to_string(),
+		simulation_mode: delivery.is_simulation_mode(),
+		build: build_info(),
+		rpc_methods: delivery
+			.rpc_stats()
+			.map(|stats| stats.snapshot())
+			.unwrap_or_default(),
+		wallet_batch_support: delivery.wallet_batch_support().await,
+	})
+}
+
+/// Handles GET /api/admin/inventory requests.
+///
+/// Returns the reserved and available balances of every configured token by chain.
+async fn handle_inventory(
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<Json<InventorySummary>, APIError> {
+	match solver.inventory().summary().await {
+		Ok(summary) => Ok(Json(summary)),
+		Err(e) => Err(APIError::InternalServerError {
+			error_type: "INVENTORY_FAILED".to_string(),
+			message: e.to_string(),
+		}),
+	}
+}
+
+/// Handles GET /api/admin/reputation requests.
+///
+/// Lists this solver and its known gossip peers with their reputation
+/// scores, best first.
+async fn handle_reputation(SelectedSolver(solver): SelectedSolver) -> Json<Vec<PeerReputation>> {
+	Json(solver.reputation())
+}
+
+/// Handles GET /api/admin/recovery/last-report requests.
+///
+/// Returns the report of the most recent state recovery, listing the action
+/// taken on each recovered order.
+async fn handle_last_recovery_report(
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<Json<RecoveryReport>, APIError> {
+	match solver.last_recovery_report().await {
+		Ok(Some(report)) => Ok(Json(report)),
+		Ok(None) => Err(APIError::NotFound {
+			error_type: "RECOVERY_REPORT_NOT_FOUND".to_string(),
+			message: "No recovery has been run yet".to_string(),
+			details: None,
+		}),
+		Err(e) => Err(APIError::InternalServerError {
+			error_type: "RECOVERY_REPORT_FAILED".to_string(),
+			message: e.to_string(),
+		}),
+	}
+}
+
+/// Handles GET /api/admin/audit/{order_id} requests.
+///
+/// Returns the audit record of the order's fill with its hash.
+async fn handle_audit_entry(
+	SelectedSolver(solver): SelectedSolver,
+	Path(order_id): Path<String>,
+) -> Result<Json<AuditEntry>, APIError> {
+	match solver.audit_entry(&order_id).await {
+		Ok(Some(entry)) => Ok(Json(entry)),
+		Ok(None) => Err(APIError::NotFound {
+			error_type: "AUDIT_RECORD_NOT_FOUND".to_string(),
+			message: format!("No fill audit record for order {}", order_id),
+			details: None,
+		}),
+		Err(e) => Err(APIError::InternalServerError {
+			error_type: "AUDIT_LOG_FAILED".to_string(),
+			message: e.to_string(),
+		}),
+	}
+}
+
+/// Handles GET /api/admin/audit requests.
+///
+/// Returns a page of the fill audit records written between `from_ts` and
+/// `to_ts`, oldest first.
+async fn handle_audit_log(
+	SelectedSolver(solver): SelectedSolver,
+	Query(query): Query<AuditQuery>,
+) -> Result<Json<AuditPage>, APIError> {
+	solver
+		.audit_log(&query)
+		.await
+		.map(Json)
+		.map_err(|e| APIError::InternalServerError {
+			error_type: "AUDIT_LOG_FAILED".to_string(),
+			message: e.to_string(),
+		})
+}
+
+/// Handles GET /api/admin/events/log requests.
+///
+/// Returns up to `limit` logged solver events from sequence number
+/// `from_seq` on, in order, for replaying the event log.
+async fn handle_event_log(
+	SelectedSolver(solver): SelectedSolver,
+	Query(query): Query<EventLogQuery>,
+) -> Result<Json<Vec<EventLogEntry>>, APIError> {
+	solver
+		.event_log(&query)
+		.await
+		.map(Json)
+		.map_err(|e| APIError::InternalServerError {
+			error_type: "EVENT_LOG_FAILED".to_string(),
+			message: e.to_string(),
+		})
+}
+
+/// Handles GET /api/admin/webhook-retries requests.
+///
+/// Returns the webhook deliveries waiting to be retried, the earliest due first.
+async fn handle_webhook_retries(
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<Json<Vec<WebhookRetryEntry>>, APIError> {
+	solver
+		.webhook_retries()
+		.await
+		.map(Json)
+		.map_err(|e| APIError::InternalServerError {
+			error_type: "WEBHOOK_RETRIES_FAILED".to_string(),
+			message: e.to_string(),
+		})
+}
+
+/// Handles GET /api/admin/dead-letters requests.
+///
+/// Returns the orders whose transactions could not be submitted, the most
+/// recent first.
+async fn handle_dead_letters(
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<Json<Vec<DeadLetter>>, APIError> {
+	solver
+		.dead_letters()
+		.await
+		.map(Json)
+		.map_err(|e| APIError::InternalServerError {
+			error_type: "DEAD_LETTERS_FAILED".to_string(),
+			message: e.to_string(),
+		})
+}
+
+/// Handles GET /health/storage requests.
+///
+/// Writes, reads back and deletes a probe entry in the default solver's
+/// storage backend, returning how long that took.
+async fn handle_storage_health(
+	State(state): State<AppState>,
+) -> Result<Json<StorageHealthReport>, APIError> {
+	state
+		.solver
+		.storage()
+		.health_check()
+		.await
+		.map(Json)
+		.map_err(storage_unavailable)
+}
+
+/// Handles GET /ready requests.
+///
+/// The service is ready once its storage backend is reachable and writeable.
+async fn handle_ready(State(state): State<AppState>) -> Result<Json<Value>, APIError> {
+	let storage = state
+		.solver
+		.storage()
+		.health_check()
+		.await
+		.map_err(storage_unavailable)?;
+	Ok(Json(serde_json::json!({
+		"status": "ready",
+		"storage": storage,
+	})))
+}
+
+/// Maps a failed storage health check to a 503 response.
+fn storage_unavailable(error: StorageError) -> APIError {
+	tracing::warn!(error = %error, "Storage health check failed");
+	APIError::ServiceUnavailable {
+		error_type: "STORAGE_UNAVAILABLE".to_string(),
+		message: error.to_string(),
+		retry_after: Some(1),
+	}
+}
+
+/// Handles GET /api/admin/token-gas-costs requests.
+///
+/// Returns the cached gas estimates of token approvals by chain and token.
+async fn handle_token_gas_costs(
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<Json<Vec<TokenGasEstimate>>, APIError> {
+	solver
+		.token_gas_costs()
+		.await
+		.map(Json)
+		.map_err(|e| APIError::InternalServerError {
+			error_type: "TOKEN_GAS_COSTS_FAILED".to_string(),
+			message: e.to_string(),
+		})
+}
+
+/// Handles GET /api/admin/schemas/{component_name} requests.
+///
+/// Returns the configuration schema of an implementation named as
+/// `<component>.<implementation>`, e.g. `settlement.direct`.
+async fn handle_get_schema(
+	Path(component_name): Path<String>,
+) -> Result<Json<SchemaDefinition>, APIError> {
+	get_registry()
+		.schema(&component_name)
+		.map(Json)
+		.ok_or_else(|| APIError::NotFound {
+			error_type: "SCHEMA_NOT_FOUND".to_string(),
+			message: format!("Unknown implementation {}", component_name),
+			details: None,
+		})
+}
+
+/// Handles GET /api/admin/report requests.
+///
+/// Returns the daily report of the requested UTC day as JSON, or as CSV
+/// with `format=csv`.
+async fn handle_report(
+	SelectedSolver(solver): SelectedSolver,
+	Query(query): Query<ReportQuery>,
+) -> Result<Response, APIError> {
+	let day = chrono::NaiveDate::parse_from_str(&query.date, "%Y-%m-%d").map_err(|e| {
+		APIError::BadRequest {
+			error_type: "INVALID_DATE".to_string(),
+			message: format!("Invalid report date '{}': {}", query.date, e),
+			details: None,
+		}
+	})?;
+	let report = solver
+		.daily_report(day)
+		.await
+		.map_err(|e| APIError::InternalServerError {
+			error_type: "REPORT_FAILED".to_string(),
+			message: e.to_string(),
+		})?;
+	Ok(match query.format {
+		ReportFormat::Json => Json(report).into_response(),
+		ReportFormat::Csv => {
+			([(header::CONTENT_TYPE, "text/csv")], report.to_csv()).into_response()
+		},
+	})
+}
+
+/// Handles GET /api/capabilities requests.
+///
+/// Returns the chains, tokens, order standards and routes the solver supports.
+async fn handle_get_capabilities(
+	SelectedSolver(solver): SelectedSolver,
+) -> Json<crate::apis::capabilities::SolverCapabilities> {
+	crate::apis::capabilities::get_capabilities(State(solver)).await
+}
+
 /// Handles GET /api/tokens requests.
 ///
 /// Returns all supported tokens across all configured networks.
 async fn handle_get_tokens(
-	State(state): State<AppState>,
+	SelectedSolver(solver): SelectedSolver,
 ) -> Json<crate::apis::tokens::TokensResponse> {
-	crate::apis::tokens::get_tokens(State(state.solver)).await
+	crate::apis::tokens::get_tokens(State(solver)).await
 }
 
 /// Handles GET /api/tokens/{chain_id} requests.
@@ -152,9 +719,401 @@ async fn handle_get_tokens(
 /// Returns supported tokens for a specific chain.
 async fn handle_get_tokens_for_chain(
 	Path(chain_id): Path<u64>,
-	State(state): State<AppState>,
+	SelectedSolver(solver): SelectedSolver,
 ) -> Result<Json<crate::apis::tokens::NetworkTokens>, StatusCode> {
-	crate::apis::tokens::get_tokens_for_chain(Path(chain_id), State(state.solver)).await
+	crate::apis::tokens::get_tokens_for_chain(Path(chain_id), State(solver)).await
+}
+
+/// Handles GET /api/admin/accounts requests.
+///
+/// Lists the accounts loaded by the solver with their addresses and capabilities.
+async fn handle_list_accounts(SelectedSolver(solver): SelectedSolver) -> Json<Vec<AccountInfo>> {
+	Json(solver.account().list_accounts().await)
+}
+
+/// Request body for POST /api/intents/{id}/cancel.
+#[derive(Debug, Deserialize)]
+struct CancelIntentRequest {
+	/// `personal_sign` signature of `cancel:{order_id}:{timestamp}` by the
+	/// order's sponsor.
+	signature: String,
+	/// Unix timestamp included in the signed message.
+	timestamp: u64,
+}
+
+/// Handles POST /api/intents/{id}/cancel requests.
+///
+/// Cancels an order whose execution has not started yet on behalf of its
+/// sponsor, who authorizes the request by signing it.
+async fn handle_cancel_intent(
+	SelectedSolver(solver): SelectedSolver,
+	Path(order_id): Path<String>,
+	Json(request): Json<CancelIntentRequest>,
+) -> Result<Json<Value>, APIError> {
+	let cancellation = OrderCancellation {
+		order_id: order_id.clone(),
+		signature: request.signature,
+		timestamp: request.timestamp,
+	};
+	match solver.cancel_order(cancellation).await {
+		Ok(()) => Ok(Json(serde_json::json!({
+			"order_id": order_id,
+			"status": "cancelled",
+		}))),
+		Err(e @ CancellationError::NotFound(_)) => Err(APIError::NotFound {
+			error_type: "ORDER_NOT_FOUND".to_string(),
+			message: e.to_string(),
+			details: None,
+		}),
+		Err(e @ CancellationError::NotCancellable(_)) => Err(APIError::UnprocessableEntity {
+			error_type: "ORDER_NOT_CANCELLABLE".to_string(),
+			message: e.to_string(),
+			details: None,
+		}),
+		Err(e @ (CancellationError::Expired(_) | CancellationError::InvalidSignature(_))) => {
+			Err(APIError::BadRequest {
+				error_type: "INVALID_CANCELLATION".to_string(),
+				message: e.to_string(),
+				details: None,
+			})
+		},
+		Err(e @ CancellationError::Unauthorized(_)) => Err(APIError::Unauthorized {
+			error_type: "UNAUTHORIZED_CANCELLATION".to_string(),
+			message: e.to_string(),
+		}),
+		Err(e @ CancellationError::Storage(_)) => Err(APIError::InternalServerError {
+			error_type: "CANCELLATION_FAILED".to_string(),
+			message: e.to_string(),
+		}),
+	}
+}
+
+/// Request body for POST /api/admin/simulate-fill.
+#[derive(Debug, Deserialize)]
+struct SimulateFillRequest {
+	/// ID of the stored order to simulate.
+	order_id: String,
+}
+
+/// Handles POST /api/admin/simulate-fill requests.
+///
+/// Simulates the fill transaction for a stored order without submitting it,
+/// returning the estimated gas, expected outputs and any revert reason.
+async fn handle_simulate_fill(
+	SelectedSolver(solver): SelectedSolver,
+	Json(request): Json<SimulateFillRequest>,
+) -> Result<Json<SimulationResult>, APIError> {
+	match solver.simulate_fill(&request.order_id).await {
+		Ok(result) => Ok(Json(result)),
+		Err(e) => {
+			tracing::warn!("Fill simulation failed: {}", e);
+			Err(APIError::UnprocessableEntity {
+				error_type: "SIMULATION_FAILED".to_string(),
+				message: e.to_string(),
+				details: None,
+			})
+		},
+	}
+}
+
+/// Handles POST /api/admin/order/preview requests.
+///
+/// Estimates whether an intent would be profitable to fill without storing
+/// it or submitting anything on-chain. Returns 200 whether or not the order
+/// is profitable.
+async fn handle_preview_order(
+	SelectedSolver(solver): SelectedSolver,
+	Json(intent): Json<Intent>,
+) -> Result<Json<PreviewResult>, APIError> {
+	match solver.preview_order(&intent).await {
+		Ok(result) => Ok(Json(result)),
+		Err(e) => {
+			tracing::warn!("Order preview failed: {}", e);
+			Err(APIError::UnprocessableEntity {
+				error_type: "PREVIEW_FAILED".to_string(),
+				message: e.to_string(),
+				details: None,
+			})
+		},
+	}
+}
+
+/// Handles POST /api/admin/replay-intent/{intent_id} requests.
+///
+/// Removes the stored order for the intent and re-emits the intent as if it
+/// had been freshly discovered. Only routed in `solver.debug_mode`.
+async fn handle_replay_intent(
+	State(state): State<AppState>,
+	headers: HeaderMap,
+	Path(intent_id): Path<String>,
+	SelectedSolver(solver): SelectedSolver,
+) -> Result<Json<Intent>, APIError> {
+	authorize_admin(&state, &headers)?;
+	match solver.replay_intent(&intent_id).await {
+		Ok(intent) => Ok(Json(intent)),
+		Err(e) => {
+			tracing::warn!("Intent replay failed: {}", e);
+			Err(APIError::UnprocessableEntity {
+				error_type: "REPLAY_FAILED".to_string(),
+				message: e.to_string(),
+				details: None,
+			})
+		},
+	}
+}
+
+/// Extracts the token of an `Authorization: Bearer` header.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+	headers
+		.get(header::AUTHORIZATION)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Checks the bearer token of an admin request against the master admin token.
+fn authorize_admin(state: &AppState, headers: &HeaderMap) -> Result<(), APIError> {
+	match bearer_token(headers) {
+		Some(token) if is_admin_token(state, token) => Ok(()),
+		_ => Err(APIError::Unauthorized {
+			error_type: "UNAUTHORIZED".to_string(),
+			message: "Missing or invalid admin token".to_string(),
+		}),
+	}
+}
+
+/// Returns whether a token is the master admin token.
+fn is_admin_token(state: &AppState, token: &str) -> bool {
+	state
+		.admin_token
+		.as_deref()
+		.is_some_and(|expected| tokens_match(expected, token))
+}
+
+/// Returns the permission an API key needs to call an endpoint.
+///
+/// Endpoints returning None are either public or authorize requests themselves.
+fn required_permission(method: &Method, route: &str) -> Option<Permission> {
+	let route = route.strip_prefix("/api").unwrap_or(route);
+	match route {
+		"/orders" if method == Method::POST => Some(Permission::WriteIntents),
+		"/orders/{id}" | "/orders/{id}/events" | "/analytics/orders" => {
+			Some(Permission::ReadOrders)
+		},
+		"/admin/log-level"
+		| "/admin/api-keys"
+		| "/admin/api-keys/{id}"
+		| "/admin/settlement/{name}/reset-circuit"
+		| "/admin/dead-letters/{order_id}/retry"
+		| "/admin/replay-intent/{intent_id}" => None,
+		_ if route.starts_with("/admin/") => Some(Permission::Admin),
+		_ => None,
+	}
+}
+
+/// Middleware rejecting requests without an API key granted the permission
+/// required by the matched endpoint. The master admin token is always accepted.
+async fn require_api_key(
+	State(state): State<AppState>,
+	request: Request,
+	next: Next,
+) -> Result<Response, APIError> {
+	let permission = request
+		.extensions()
+		.get::<MatchedPath>()
+		.and_then(|route| required_permission(request.method(), route.as_str()));
+	let Some(permission) = permission else {
+		return Ok(next.run(request).await);
+	};
+
+	let authorized = match bearer_token(request.headers()) {
+		Some(token) if is_admin_token(&state, token) => true,
+		Some(token) => state.api_keys.authorize(token, permission).await,
+		None => false,
+	};
+	if !authorized {
+		return Err(APIError::Unauthorized {
+			error_type: "UNAUTHORIZED".to_string(),
+			message: format!("Missing or invalid API key for {:?} permission", permission),
+		});
+	}
+
+	Ok(next.run(request).await)
+}
+
+/// Middleware rejecting requests authorized by neither the master admin
+/// token nor an API key with the admin permission.
+async fn require_admin(
+	State(state): State<AppState>,
+	request: Request,
+	next: Next,
+) -> Result<Response, APIError> {
+	let authorized = match bearer_token(request.headers()) {
+		Some(token) if is_admin_token(&state, token) => true,
+		Some(token) => state.api_keys.authorize(token, Permission::Admin).await,
+		None => false,
+	};
+	if !authorized {
+		return Err(APIError::Unauthorized {
+			error_type: "UNAUTHORIZED".to_string(),
+			message: "Missing or invalid admin token or API key".to_string(),
+		});
+	}
+
+	Ok(next.run(request).await)
+}
+
+/// Handles GET /api/admin/api-keys requests.
+///
+/// Lists all API keys without their hashes.
+async fn handle_list_api_keys(
+	State(state): State<AppState>,
+	headers: HeaderMap,
+) -> Result<Json<Vec<ApiKeyInfo>>, APIError> {
+	authorize_admin(&state, &headers)?;
+	state.api_keys.list().await.map(Json).map_err(api_key_error)
+}
+
+/// Handles POST /api/admin/api-keys requests.
+///
+/// Creates an API key with the requested permissions. The key is only
+/// returned in this response.
+async fn handle_create_api_key(
+	State(state): State<AppState>,
+	headers: HeaderMap,
+	Json(request): Json<CreateApiKeyRequest>,
+) -> Result<Json<CreateApiKeyResponse>, APIError> {
+	authorize_admin(&state, &headers)?;
+	state
+		.api_keys
+		.create(request)
+		.await
+		.map(Json)
+		.map_err(api_key_error)
+}
+
+/// Handles DELETE /api/admin/api-keys/{id} requests.
+async fn handle_revoke_api_key(
+	Path(id): Path<String>,
+	State(state): State<AppState>,
+	headers: HeaderMap,
+) -> Result<StatusCode, APIError> {
+	authorize_admin(&state, &headers)?;
+	state
+		.api_keys
+		.revoke(&id)
+		.await
+		.map(|_| StatusCode::NO_CONTENT)
+		.map_err(api_key_error)
+}
+
+/// Maps an API key error to its API error response.
+fn api_key_error(e: ApiKeyError) -> APIError {
+	match e {
+		ApiKeyError::NotFound(_) => APIError::NotFound {
+			error_type: "API_KEY_NOT_FOUND".to_string(),
+			message: e.to_string(),
+			details: None,
+		},
+		ApiKeyError::InvalidRequest(_) => APIError::BadRequest {
+			error_type: "INVALID_API_KEY_REQUEST".to_string(),
+			message: e.to_string(),
+			details: None,
+		},
+		ApiKeyError::Storage(_) => APIError::InternalServerError {
+			error_type: "API_KEY_STORAGE_FAILED".to_string(),
+			message: e.to_string(),
+		},
+	}
+}
+
+/// Handles GET /api/admin/log-level requests.
+///
+/// Returns the default log filter and the active per-component overrides.
+async fn handle_get_log_levels(
+	State(state): State<AppState>,
+	headers: HeaderMap,
+) -> Result<Json<LogLevelsResponse>, APIError> {
+	authorize_admin(&state, &headers)?;
+	Ok(Json(state.log_levels.current().await))
+}
+
+/// Handles POST /api/admin/log-level requests.
+///
+/// Overrides the log level of a component at runtime, optionally reverting
+/// to the default after `duration_seconds`.
+async fn handle_set_log_level(
+	State(state): State<AppState>,
+	headers: HeaderMap,
+	Json(request): Json<SetLogLevelRequest>,
+) -> Result<Json<LogLevelsResponse>, APIError> {
+	authorize_admin(&state, &headers)?;
+	match state.log_levels.set(request).await {
+		Ok(response) => Ok(Json(response)),
+		Err(e @ (LogLevelError::InvalidLevel(_) | LogLevelError::InvalidComponent(_))) => {
+			Err(APIError::BadRequest {
+				error_type: "INVALID_LOG_LEVEL".to_string(),
+				message: e.to_string(),
+				details: None,
+			})
+		},
+		Err(e) => Err(APIError::InternalServerError {
+			error_type: "LOG_LEVEL_FAILED".to_string(),
+			message: e.to_string(),
+		}),
+	}
+}
+
+/// Result of resetting the claim circuit of a settlement implementation.
+#[derive(Debug, Serialize)]
+struct ResetCircuitResponse {
+	implementation: String,
+	/// Claims held back while the circuit was open, queued again.
+	requeued_claims: usize,
+}
+
+/// Handles POST /api/admin/settlement/{name}/reset-circuit requests.
+///
+/// Re-enables auto-claiming for a settlement implementation disabled by the
+/// claim success rate circuit breaker.
+async fn handle_reset_claim_circuit(
+	Path(name): Path<String>,
+	State(state): State<AppState>,
+	SelectedSolver(solver): SelectedSolver,
+	headers: HeaderMap,
+) -> Result<Json<ResetCircuitResponse>, APIError> {
+	authorize_admin(&state, &headers)?;
+	match solver.reset_claim_circuit(&name) {
+		Ok(requeued_claims) => Ok(Json(ResetCircuitResponse {
+			implementation: name,
+			requeued_claims,
+		})),
+		Err(e) => Err(APIError::NotFound {
+			error_type: "SETTLEMENT_NOT_FOUND".to_string(),
+			message: e.to_string(),
+			details: None,
+		}),
+	}
+}
+
+/// Handles POST /api/admin/dead-letters/{order_id}/retry requests.
+///
+/// Queues the claim of an order in the dead letter queue again.
+async fn handle_retry_dead_letter(
+	Path(order_id): Path<String>,
+	State(state): State<AppState>,
+	SelectedSolver(solver): SelectedSolver,
+	headers: HeaderMap,
+) -> Result<StatusCode, APIError> {
+	authorize_admin(&state, &headers)?;
+	solver
+		.retry_dead_letter(&order_id)
+		.await
+		.map(|()| StatusCode::ACCEPTED)
+		.map_err(|e| APIError::BadRequest {
+			error_type: "DEAD_LETTER_RETRY_FAILED".to_string(),
+			message: e.to_string(),
+			details: None,
+		})
 }
 
 /// Handles POST /api/orders requests.
@@ -225,3 +1184,378 @@ async fn handle_order(
 		},
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::factory_registry::build_solvers_from_config;
+	use crate::init::{render_config, InitAnswers};
+	use axum::body::Body;
+	use solver_config::TenantConfig;
+	use solver_core::state::dead_letter::push_dead_letter;
+	use solver_types::TransactionType;
+	use std::str::FromStr;
+	use tower::ServiceExt as _;
+	use tracing_subscriber::{reload, EnvFilter, Registry};
+
+	const ADMIN_TOKEN: &str = "admin-secret";
+
+	/// Builds the engines of two solvers, `alpha` and `beta`, sharing a
+	/// memory storage backend, without any chain access.
+	async fn solvers() -> Vec<Arc<SolverEngine>> {
+		let chain = |chain_id: u64| {
+			serde_json::json!({
+				"chain_id": chain_id,
+				"rpc_url": "http://localhost:8545",
+				"input_settler_address": "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+				"output_settler_address": "0xCf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9",
+				"oracle_address": "0xDc64a140Aa3E981100a9becA4E685f962f0cF6C9",
+			})
+		};
+		let answers: InitAnswers = serde_json::from_value(serde_json::json!({
+			"solver_id": "alpha",
+			"chains": [chain(31337), chain(31338)],
+			"private_key": {
+				"source": "inline",
+				"private_key": "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+			},
+			"tokens": [{
+				"symbol": "TOKA",
+				"decimals": 18,
+				"addresses": {
+					"31337": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+					"31338": "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+				},
+			}],
+			"settlement": { "mechanism": "direct" },
+		}))
+		.unwrap();
+		let mut config = Config::from_str(&render_config(&answers).unwrap()).unwrap();
+		config.storage.primary = "memory".to_string();
+		config.storage.implementations =
+			HashMap::from([("memory".to_string(), toml::Value::Table(Default::default()))]);
+		// Without tokens, no approvals or balances are checked on chain
+		for network in config.networks.values_mut() {
+			network.tokens.clear();
+		}
+		// Intents are discovered off-chain, as on-chain discovery needs an RPC node
+		config.discovery.implementations = HashMap::from([(
+			"offchain_eip7683".to_string(),
+			toml::Value::Table(toml::toml! {
+				api_host = "127.0.0.1"
+				api_port = 8081
+				network_ids = [31337]
+			}),
+		)]);
+		config.solvers = ["alpha", "beta"]
+			.into_iter()
+			.map(|id| {
+				let mut solver = config.solver.clone();
+				solver.id = id.to_string();
+				TenantConfig {
+					solver,
+					account: None,
+					strategy: None,
+				}
+			})
+			.collect();
+
+		build_solvers_from_config(config)
+			.await
+			.unwrap()
+			.into_iter()
+			.map(Arc::new)
+			.collect()
+	}
+
+	/// Builds the router serving the given solvers, the first being the default.
+	fn app(
+		solvers: &[Arc<SolverEngine>],
+		api: serde_json::Value,
+		admin_token: Option<&str>,
+		debug_mode: bool,
+	) -> Router {
+		let api_config: ApiConfig = serde_json::from_value(api).unwrap();
+		let solver = solvers[0].clone();
+		let mut config = solver.config().clone();
+		config.solver.debug_mode = debug_mode;
+		let (_, handle) = reload::Layer::<EnvFilter, Registry>::new(EnvFilter::new("info"));
+		let app_state = AppState {
+			solver: solver.clone(),
+			solvers: Arc::new(
+				solvers
+					.iter()
+					.map(|solver| (solver.solver_id().to_string(), solver.clone()))
+					.collect(),
+			),
+			config,
+			http_client: reqwest::Client::new(),
+			discovery_url: None,
+			log_levels: LogLevelController::new(
+				handle,
+				"info".to_string(),
+				solver.storage().clone(),
+			),
+			admin_token: admin_token.map(str::to_string),
+			api_keys: Arc::new(ApiKeyStore::new(solver.storage().clone())),
+		};
+		let prometheus = metrics_exporter_prometheus::PrometheusBuilder::new()
+			.build_recorder()
+			.handle();
+		build_router(&api_config, app_state, prometheus)
+	}
+
+	/// Sends a request to the router, returning the response status and body.
+	async fn send(
+		router: &Router,
+		method: Method,
+		uri: &str,
+		headers: &[(&str, &str)],
+	) -> (StatusCode, Value) {
+		let mut request = axum::http::Request::builder().method(method).uri(uri);
+		for (name, value) in headers {
+			request = request.header(*name, *value);
+		}
+		let body = if uri == "/api/orders" {
+			Body::from("{}")
+		} else {
+			Body::empty()
+		};
+		let request = request
+			.header(header::CONTENT_TYPE, "application/json")
+			.body(body)
+			.unwrap();
+		let response = router.clone().oneshot(request).await.unwrap();
+		let status = response.status();
+		let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+			.await
+			.unwrap();
+		(
+			status,
+			serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+		)
+	}
+
+	fn bearer(token: &str) -> String {
+		format!("Bearer {}", token)
+	}
+
+	/// Creates an API key granted the given permission.
+	async fn api_key(solver: &SolverEngine, permission: Permission) -> String {
+		ApiKeyStore::new(solver.storage().clone())
+			.create(CreateApiKeyRequest {
+				permissions: vec![permission],
+				expires_in_seconds: None,
+			})
+			.await
+			.unwrap()
+			.key
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_solver_id_header_selects_solver() {
+		let solvers = solvers().await;
+		push_dead_letter(
+			solvers[0].storage(),
+			"order-1",
+			TransactionType::Claim,
+			"failed".to_string(),
+		)
+		.await
+		.unwrap();
+		let router = app(&solvers, serde_json::json!({}), Some(ADMIN_TOKEN), false);
+		let auth = bearer(ADMIN_TOKEN);
+		let dead_letters = |solver_id: Option<&'static str>| {
+			let router = router.clone();
+			let auth = auth.clone();
+			async move {
+				let mut headers = vec![("authorization", auth.as_str())];
+				headers.extend(solver_id.map(|id| (SOLVER_ID_HEADER, id)));
+				send(&router, Method::GET, "/api/admin/dead-letters", &headers).await
+			}
+		};
+
+		// Requests without the header go to the first solver
+		let (status, body) = dead_letters(None).await;
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(body.as_array().unwrap().len(), 1);
+		let (status, body) = dead_letters(Some("alpha")).await;
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(body.as_array().unwrap().len(), 1);
+		let (status, body) = dead_letters(Some("beta")).await;
+		assert_eq!(status, StatusCode::OK);
+		assert!(body.as_array().unwrap().is_empty());
+
+		let (status, body) = dead_letters(Some("gamma")).await;
+		assert_eq!(status, StatusCode::NOT_FOUND);
+		assert_eq!(body["error"], "SOLVER_NOT_FOUND");
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_admin_endpoints_require_admin_token() {
+		let solvers = solvers().await;
+		let router = app(&solvers, serde_json::json!({}), Some(ADMIN_TOKEN), false);
+		let admin_key = api_key(&solvers[0], Permission::Admin).await;
+		let read_key = api_key(&solvers[0], Permission::ReadOrders).await;
+		let status = |token: Option<String>| {
+			let router = router.clone();
+			async move {
+				let auth = token.map(|token| bearer(&token));
+				let headers: Vec<_> = auth
+					.iter()
+					.map(|auth| ("authorization", auth.as_str()))
+					.collect();
+				send(&router, Method::GET, "/api/admin/dead-letters", &headers)
+					.await
+					.0
+			}
+		};
+
+		assert_eq!(status(None).await, StatusCode::UNAUTHORIZED);
+		assert_eq!(
+			status(Some("wrong".to_string())).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_eq!(status(Some(read_key)).await, StatusCode::UNAUTHORIZED);
+		assert_eq!(status(Some(admin_key)).await, StatusCode::OK);
+		assert_eq!(status(Some(ADMIN_TOKEN.to_string())).await, StatusCode::OK);
+
+		// Public admin endpoints skip the check
+		let router = app(
+			&solvers,
+			serde_json::json!({ "public_admin_endpoints": true }),
+			Some(ADMIN_TOKEN),
+			false,
+		);
+		let (status, _) = send(&router, Method::GET, "/api/admin/dead-letters", &[]).await;
+		assert_eq!(status, StatusCode::OK);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_api_key_permissions() {
+		let solvers = solvers().await;
+		let api = serde_json::json!({ "require_api_keys": true });
+		let router = app(&solvers, api.clone(), Some(ADMIN_TOKEN), false);
+		let read_key = bearer(&api_key(&solvers[0], Permission::ReadOrders).await);
+		let write_key = bearer(&api_key(&solvers[0], Permission::WriteIntents).await);
+		let admin_key = bearer(&api_key(&solvers[0], Permission::Admin).await);
+		let admin_token = bearer(ADMIN_TOKEN);
+		let status = |method: Method, uri: &'static str, auth: Option<String>| {
+			let router = router.clone();
+			async move {
+				let headers: Vec<_> = auth
+					.iter()
+					.map(|auth| ("authorization", auth.as_str()))
+					.collect();
+				send(&router, method, uri, &headers).await.0
+			}
+		};
+		let order =
+			"/api/orders/0x0000000000000000000000000000000000000000000000000000000000000001";
+
+		// Reading orders requires the read permission
+		assert_eq!(
+			status(Method::GET, order, None).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_eq!(
+			status(Method::GET, order, Some(write_key.clone())).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_ne!(
+			status(Method::GET, order, Some(read_key.clone())).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_ne!(
+			status(Method::GET, order, Some(admin_token.clone())).await,
+			StatusCode::UNAUTHORIZED
+		);
+
+		// Submitting orders requires the write permission
+		assert_eq!(
+			status(Method::POST, "/api/orders", Some(read_key.clone())).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_ne!(
+			status(Method::POST, "/api/orders", Some(write_key.clone())).await,
+			StatusCode::UNAUTHORIZED
+		);
+
+		// Admin endpoints require the admin permission
+		assert_eq!(
+			status(
+				Method::GET,
+				"/api/admin/dead-letters",
+				Some(read_key.clone())
+			)
+			.await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_eq!(
+			status(
+				Method::GET,
+				"/api/admin/dead-letters",
+				Some(admin_key.clone())
+			)
+			.await,
+			StatusCode::OK
+		);
+
+		// Endpoints authorizing requests themselves only accept the admin token
+		assert_eq!(
+			status(Method::GET, "/api/admin/log-level", Some(admin_key.clone())).await,
+			StatusCode::UNAUTHORIZED
+		);
+		assert_eq!(
+			status(
+				Method::GET,
+				"/api/admin/log-level",
+				Some(admin_token.clone())
+			)
+			.await,
+			StatusCode::OK
+		);
+		assert_ne!(
+			status(
+				Method::POST,
+				"/api/admin/dead-letters/order-1/retry",
+				Some(admin_token.clone())
+			)
+			.await,
+			StatusCode::UNAUTHORIZED
+		);
+
+		// Public endpoints need no key
+		assert_eq!(
+			status(Method::GET, "/api/tokens", None).await,
+			StatusCode::OK
+		);
+
+		// Replaying intents is only served in debug mode
+		assert_eq!(
+			status(
+				Method::POST,
+				"/api/admin/replay-intent/intent-1",
+				Some(admin_token.clone())
+			)
+			.await,
+			StatusCode::NOT_FOUND
+		);
+		let debug_router = app(&solvers, api.clone(), Some(ADMIN_TOKEN), true);
+		let (status, _) = send(
+			&debug_router,
+			Method::POST,
+			"/api/admin/replay-intent/intent-1",
+			&[],
+		)
+		.await;
+		assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+		// Without an admin token, token-only endpoints are not served
+		let router = app(&solvers, api, None, true);
+		for uri in ["/api/admin/log-level", "/api/admin/replay-intent/intent-1"] {
+			let (status, _) = send(&router, Method::GET, uri, &[]).await;
+			assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+		}
+	}
+}
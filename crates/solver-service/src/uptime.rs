@@ -0,0 +1,216 @@
+//! Uptime and restart tracking.
+//!
+//! Exports when the solver started, how long it has been running and why it
+//! last restarted. A graceful shutdown records its exit reason to storage; a
+//! previous run that left a heartbeat but no exit record was killed without a
+//! chance to clean up, which in practice means the OOM killer.
+//!
+//! The heartbeat is written from the same task that counts uptime, so monitors
+//! can detect a stuck event loop even while the metrics endpoint still responds.
+
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{current_timestamp, StorageKey};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Gauge set to the Unix timestamp at which the solver started.
+const START_TIME_GAUGE: &str = "solver_start_time_unix";
+
+/// Counter of seconds the solver has been running.
+const UPTIME_COUNTER: &str = "solver_uptime_seconds";
+
+/// Gauge set to 1 for the reason of the last restart, labelled by `reason`.
+const RESTART_REASON_GAUGE: &str = "solver_last_restart_reason";
+
+/// Storage ID of the single exit record and heartbeat entries.
+const STORAGE_ID: &str = "solver";
+
+/// Interval between heartbeat writes.
+const HEARTBEAT_INTERVAL_SECONDS: u64 = 10;
+
+/// Reason the solver process exited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+	/// Shut down on request, after stopping all services.
+	Graceful,
+	/// Stopped by a panic or fatal error in a solver task.
+	Panic,
+	/// Killed without recording an exit, e.g. by the OOM killer.
+	Oom,
+}
+
+impl ExitReason {
+	const ALL: [ExitReason; 3] = [ExitReason::Graceful, ExitReason::Panic, ExitReason::Oom];
+
+	/// Returns the metric label of the reason.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ExitReason::Graceful => "graceful",
+			ExitReason::Panic => "panic",
+			ExitReason::Oom => "oom",
+		}
+	}
+}
+
+/// Exit record written when the solver stops.
+#[derive(Debug, Serialize, Deserialize)]
+struct LastExit {
+	reason: ExitReason,
+	timestamp: u64,
+}
+
+/// Liveness heartbeat written periodically while the solver runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Heartbeat {
+	/// Unix timestamp of the heartbeat.
+	pub timestamp: u64,
+	/// Unix timestamp at which the solver started.
+	pub started_at: u64,
+}
+
+/// Tracks uptime, restarts and liveness of the solver process.
+pub struct UptimeMonitor {
+	storage: Arc<StorageService>,
+	started_at: u64,
+}
+
+impl UptimeMonitor {
+	/// Creates a new UptimeMonitor for a process started now.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self {
+			storage,
+			started_at: current_timestamp(),
+		}
+	}
+
+	/// Exports the start time and restart reason, then spawns the task counting
+	/// uptime and writing heartbeats.
+	pub async fn start(&self) -> JoinHandle<()> {
+		metrics::gauge!(START_TIME_GAUGE).set(self.started_at as f64);
+
+		let restart_reason = self.restart_reason().await;
+		if let Some(reason) = restart_reason {
+			tracing::info!(reason = reason.as_str(), "Solver restarted");
+		}
+		for reason in ExitReason::ALL {
+			let value = if restart_reason == Some(reason) {
+				1.0
+			} else {
+				0.0
+			};
+			metrics::gauge!(RESTART_REASON_GAUGE, "reason" => reason.as_str()).set(value);
+		}
+
+		let storage = self.storage.clone();
+		let started_at = self.started_at;
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(Duration::from_secs(1));
+			interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+			let mut ticks: u64 = 0;
+			loop {
+				interval.tick().await;
+				if ticks > 0 {
+					metrics::counter!(UPTIME_COUNTER).increment(1);
+				}
+				if ticks % HEARTBEAT_INTERVAL_SECONDS == 0 {
+					write_heartbeat(&storage, started_at).await;
+				}
+				ticks += 1;
+			}
+		})
+	}
+
+	/// Records how the solver exited, for the next start to report.
+	pub async fn record_exit(&self, reason: ExitReason) {
+		let exit = LastExit {
+			reason,
+			timestamp: current_timestamp(),
+		};
+		if let Err(e) = self
+			.storage
+			.store(StorageKey::LastExit.as_str(), STORAGE_ID, &exit, None)
+			.await
+		{
+			tracing::warn!(error = %e, "Failed to record solver exit");
+		}
+	}
+
+	/// Determines why the previous run stopped, consuming its exit record.
+	///
+	/// Returns None on the first start, when no previous run left any trace.
+	async fn restart_reason(&self) -> Option<ExitReason> {
+		match self
+			.storage
+			.retrieve::<LastExit>(StorageKey::LastExit.as_str(), STORAGE_ID)
+			.await
+		{
+			Ok(exit) => {
+				if let Err(e) = self
+					.storage
+					.remove(StorageKey::LastExit.as_str(), STORAGE_ID)
+					.await
+				{
+					tracing::warn!(error = %e, "Failed to clear solver exit record");
+				}
+				return Some(exit.reason);
+			},
+			Err(StorageError::NotFound) => {},
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to load solver exit record");
+				return None;
+			},
+		}
+
+		match self
+			.storage
+			.exists(StorageKey::Heartbeat.as_str(), STORAGE_ID)
+			.await
+		{
+			Ok(true) => Some(ExitReason::Oom),
+			Ok(false) => None,
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to load solver heartbeat");
+				None
+			},
+		}
+	}
+}
+
+async fn write_heartbeat(storage: &StorageService, started_at: u64) {
+	let heartbeat = Heartbeat {
+		timestamp: current_timestamp(),
+		started_at,
+	};
+	if let Err(e) = storage
+		.store(StorageKey::Heartbeat.as_str(), STORAGE_ID, &heartbeat, None)
+		.await
+	{
+		tracing::warn!(error = %e, "Failed to write solver heartbeat");
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_storage::implementations::memory::MemoryStorage;
+
+	#[tokio::test]
+	async fn test_restart_reason_detection() {
+		let storage = Arc::new(StorageService::new(Box::new(MemoryStorage::new()), None));
+
+		// First start leaves no trace to report
+		let monitor = UptimeMonitor::new(storage.clone());
+		assert_eq!(monitor.restart_reason().await, None);
+
+		// A graceful exit is reported once
+		monitor.record_exit(ExitReason::Graceful).await;
+		assert_eq!(monitor.restart_reason().await, Some(ExitReason::Graceful));
+
+		// A heartbeat without exit record means the process was killed
+		write_heartbeat(&storage, monitor.started_at).await;
+		assert_eq!(monitor.restart_reason().await, Some(ExitReason::Oom));
+	}
+}
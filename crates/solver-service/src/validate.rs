@@ -0,0 +1,270 @@
+//! Configuration validation for the `config validate` subcommand.
+//!
+//! Loads a configuration file, runs its validation checks and instantiates
+//! (without starting) every configured implementation through the factory
+//! registry, so that misconfigurations are caught before deployment.
+
+use crate::factory_registry::get_registry;
+use solver_account::AccountService;
+use solver_config::Config;
+use solver_settlement::SettlementService;
+use solver_types::{oracle::OracleRouteSource, SecretString};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Errors and warnings collected while validating a configuration.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+	/// Problems that prevent the solver from starting, as (section, message).
+	pub errors: Vec<(String, String)>,
+	/// Recommended settings that are missing, as (section, message).
+	pub warnings: Vec<(String, String)>,
+}
+
+impl ValidationReport {
+	fn error(&mut self, section: impl Into<String>, message: impl Into<String>) {
+		self.errors.push((section.into(), message.into()));
+	}
+
+	fn warning(&mut self, section: impl Into<String>, message: impl Into<String>) {
+		self.warnings.push((section.into(), message.into()));
+	}
+
+	/// Returns true if validation passed, treating warnings as errors when `strict`.
+	pub fn is_success(&self, strict: bool) -> bool {
+		self.errors.is_empty() && (!strict || self.warnings.is_empty())
+	}
+}
+
+/// Instantiates each configured implementation of a component and validates
+/// its configuration against the implementation's schema.
+///
+/// Evaluates to a map of the successfully created implementations.
+macro_rules! check_implementations {
+	($report:expr, $section:expr, $implementations:expr, $factories:expr, |$factory:ident, $config:ident| $create:expr) => {{
+		let mut created = HashMap::new();
+		for (name, $config) in $implementations {
+			let section = format!("{}.implementations.{}", $section, name);
+			let Some($factory) = $factories.get(name) else {
+				let mut available: Vec<_> = $factories.keys().cloned().collect();
+				available.sort();
+				$report.error(
+					section,
+					format!(
+						"Unknown implementation '{}'. Available: [{}]",
+						name,
+						available.join(", ")
+					),
+				);
+				continue;
+			};
+			match $create {
+				Ok(implementation) => {
+					if let Err(e) = implementation.config_schema().validate($config) {
+						$report.error(section, e.to_string());
+						continue;
+					}
+					created.insert(name.clone(), implementation);
+				},
+				Err(e) => $report.error(section, e.to_string()),
+			}
+		}
+		created
+	}};
+}
+
+/// Validates the configuration file at `path`.
+pub async fn validate_config_file(path: &Path) -> ValidationReport {
+	match Config::from_file(&path.to_string_lossy()).await {
+		Ok(config) => validate_config(&config).await,
+		Err(e) => {
+			let mut report = ValidationReport::default();
+			report.error("config", e.to_string());
+			report
+		},
+	}
+}
+
+/// Validates a loaded configuration.
+pub async fn validate_config(config: &Config) -> ValidationReport {
+	let mut report = ValidationReport::default();
+	instantiate_implementations(config, &mut report).await;
+	check_recommended(config, &mut report);
+	report
+}
+
+/// Instantiates all configured implementations without starting them.
+async fn instantiate_implementations(config: &Config, report: &mut ValidationReport) {
+	let registry = get_registry();
+
+	check_implementations!(
+		report,
+		"storage",
+		&config.storage.implementations,
+		registry.storage,
+		|factory, impl_config| factory(impl_config)
+	);
+
+	let mut accounts = check_implementations!(
+		report,
+		"account",
+		&config.account.implementations,
+		registry.account,
+		|factory, impl_config| factory(impl_config)
+	);
+	let private_key = match accounts.remove(&config.account.primary) {
+		Some(account) => AccountService::new(account).get_private_key(),
+		None => {
+			report.error(
+				"account",
+				format!(
+					"Primary account '{}' could not be created",
+					config.account.primary
+				),
+			);
+			SecretString::from("")
+		},
+	};
+
+	check_implementations!(
+		report,
+		"delivery",
+		&config.delivery.implementations,
+		registry.delivery,
+		|factory, impl_config| factory(
+			impl_config,
+			&config.networks,
+			&private_key,
+			&HashMap::new()
+		)
+		.await
+	);
+
+	check_implementations!(
+		report,
+		"discovery",
+		&config.discovery.implementations,
+		registry.discovery,
+		|factory, impl_config| factory(impl_config, &config.networks)
+	);
+
+	let settlements = check_implementations!(
+		report,
+		"settlement",
+		&config.settlement.implementations,
+		registry.settlement,
+		|factory, impl_config| factory(impl_config, &config.networks)
+	);
+	let oracle_routes: Arc<dyn OracleRouteSource> =
+		Arc::new(SettlementService::new(settlements).build_oracle_routes());
+
+	check_implementations!(
+		report,
+		"order",
+		&config.order.implementations,
+		registry.order,
+		|factory, impl_config| factory(impl_config, &config.networks, &oracle_routes)
+	);
+
+	check_implementations!(
+		report,
+		"order.strategy",
+		&config.order.strategy.implementations,
+		registry.strategy,
+		|factory, impl_config| factory(impl_config)
+	);
+
+	if let Some(price) = &config.price {
+		check_implementations!(
+			report,
+			"price",
+			&price.implementations,
+			registry.price,
+			|factory, impl_config| factory(impl_config, &config.networks)
+		);
+	}
+
+	for tenant in &config.solvers {
+		if let Some(account) = &tenant.account {
+			check_implementations!(
+				report,
+				format!("solvers.{}.account", tenant.solver.id),
+				&account.implementations,
+				registry.account,
+				|factory, impl_config| factory(impl_config)
+			);
+		}
+		if let Some(strategy) = &tenant.strategy {
+			check_implementations!(
+				report,
+				format!("solvers.{}.strategy", tenant.solver.id),
+				&strategy.implementations,
+				registry.strategy,
+				|factory, impl_config| factory(impl_config)
+			);
+		}
+	}
+}
+
+/// Reports optional settings that are recommended for production deployments.
+fn check_recommended(config: &Config, report: &mut ValidationReport) {
+	let mut chain_ids: Vec<_> = config.networks.keys().copied().collect();
+	chain_ids.sort();
+
+	for chain_id in chain_ids {
+		let network = &config.networks[&chain_id];
+		if network.rpc_urls.len() < 2 {
+			report.warning(
+				format!("networks.{}", chain_id),
+				"Only one RPC endpoint configured; add a fallback endpoint",
+			);
+		}
+		if network.tokens.is_empty() {
+			report.warning(format!("networks.{}", chain_id), "No tokens configured");
+		}
+	}
+
+	if config.discovery.health.is_none() {
+		report.warning(
+			"discovery.health",
+			"Not configured; stalled discovery sources will not be restarted",
+		);
+	}
+}
+
+/// Prints a validation report for the configuration file at `path`.
+pub fn print_report(path: &Path, report: &ValidationReport) {
+	let file = path.display();
+	for (section, message) in &report.errors {
+		eprintln!("error: {} [{}]: {}", file, section, message);
+	}
+	for (section, message) in &report.warnings {
+		eprintln!("warning: {} [{}]: {}", file, section, message);
+	}
+
+	println!(
+		"{}: {} error(s), {} warning(s)",
+		file,
+		report.errors.len(),
+		report.warnings.len()
+	);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_strict_fails_on_warnings() {
+		let mut report = ValidationReport::default();
+		assert!(report.is_success(true));
+
+		report.warning("discovery.health", "Not configured");
+		assert!(report.is_success(false));
+		assert!(!report.is_success(true));
+
+		report.error("storage", "Invalid");
+		assert!(!report.is_success(false));
+	}
+}
@@ -0,0 +1,203 @@
+//! Circuit breaker on the success rate of claims.
+//!
+//! Claims reverting repeatedly, e.g. after an upgrade of the settlement
+//! contract, keep spending gas without paying out. The breaker tracks the
+//! claim outcomes of each settlement implementation over a rolling window and
+//! opens the circuit when the success rate drops below a threshold. While the
+//! circuit is open, claims of that implementation are held back until it is
+//! reset manually.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Length of the rolling window of claim outcomes, in seconds.
+pub const CLAIM_SUCCESS_WINDOW_SECONDS: u64 = 3600;
+
+/// Claim outcomes and circuit state of one settlement implementation.
+#[derive(Debug, Default)]
+struct ImplementationCircuit {
+	/// Timestamps and outcomes of the claims in the window, oldest first.
+	outcomes: VecDeque<(u64, bool)>,
+	/// Whether auto-claiming is disabled.
+	open: bool,
+	/// Orders whose claims were held back while the circuit was open.
+	held: Vec<String>,
+}
+
+impl ImplementationCircuit {
+	/// Drops outcomes that fell out of the window.
+	fn prune(&mut self, now: u64) {
+		let cutoff = now.saturating_sub(CLAIM_SUCCESS_WINDOW_SECONDS);
+		while self
+			.outcomes
+			.front()
+			.is_some_and(|(timestamp, _)| *timestamp < cutoff)
+		{
+			self.outcomes.pop_front();
+		}
+	}
+
+	/// Returns the success rate in percent, if any claims were recorded.
+	fn success_rate_pct(&self) -> Option<f64> {
+		if self.outcomes.is_empty() {
+			return None;
+		}
+		let successes = self.outcomes.iter().filter(|(_, success)| *success).count();
+		Some(successes as f64 * 100.0 / self.outcomes.len() as f64)
+	}
+}
+
+/// Circuit state of a settlement implementation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimCircuitStatus {
+	/// Whether auto-claiming is disabled.
+	pub open: bool,
+	/// Success rate of the claims in the window in percent, if any.
+	pub success_rate_pct: Option<f64>,
+	/// Number of claims in the window.
+	pub claims: usize,
+	/// Number of claims held back while the circuit is open.
+	pub held: usize,
+}
+
+/// Tracks the rolling claim success rate per settlement implementation.
+pub struct ClaimSuccessRateCircuitBreaker {
+	/// Success rate in percent below which the circuit opens.
+	min_success_rate_pct: f64,
+	/// Number of claims in the window required before the circuit can open.
+	min_claims: usize,
+	circuits: Mutex<HashMap<String, ImplementationCircuit>>,
+}
+
+impl Default for ClaimSuccessRateCircuitBreaker {
+	fn default() -> Self {
+		Self::new(50.0, 5)
+	}
+}
+
+impl ClaimSuccessRateCircuitBreaker {
+	/// Creates a new breaker opening below the given success rate once at
+	/// least `min_claims` claims were made within the window.
+	pub fn new(min_success_rate_pct: f64, min_claims: usize) -> Self {
+		Self {
+			min_success_rate_pct,
+			min_claims: min_claims.max(1),
+			circuits: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Records the outcome of a claim of an implementation.
+	///
+	/// Returns `true` if this outcome opened the circuit.
+	pub fn record(&self, implementation: &str, success: bool, now: u64) -> bool {
+		let mut circuits = self.circuits.lock().unwrap();
+		let circuit = circuits.entry(implementation.to_string()).or_default();
+		circuit.outcomes.push_back((now, success));
+		circuit.prune(now);
+
+		if circuit.open || circuit.outcomes.len() < self.min_claims {
+			return false;
+		}
+		let below_threshold = circuit
+			.success_rate_pct()
+			.is_some_and(|rate| rate < self.min_success_rate_pct);
+		if below_threshold {
+			circuit.open = true;
+		}
+		below_threshold
+	}
+
+	/// Returns whether auto-claiming is disabled for an implementation.
+	pub fn is_open(&self, implementation: &str) -> bool {
+		self.circuits
+			.lock()
+			.unwrap()
+			.get(implementation)
+			.is_some_and(|circuit| circuit.open)
+	}
+
+	/// Holds back the claim of an order while the circuit is open.
+	pub fn hold(&self, implementation: &str, order_id: String) {
+		let mut circuits = self.circuits.lock().unwrap();
+		let held = &mut circuits.entry(implementation.to_string()).or_default().held;
+		if !held.contains(&order_id) {
+			held.push(order_id);
+		}
+	}
+
+	/// Closes the circuit of an implementation and clears its outcomes.
+	///
+	/// Returns the orders whose claims were held back, so they can be
+	/// claimed again.
+	pub fn reset(&self, implementation: &str) -> Vec<String> {
+		let mut circuits = self.circuits.lock().unwrap();
+		circuits
+			.remove(implementation)
+			.map(|circuit| circuit.held)
+			.unwrap_or_default()
+	}
+
+	/// Returns the circuit state of an implementation at the given time.
+	pub fn status(&self, implementation: &str, now: u64) -> ClaimCircuitStatus {
+		let mut circuits = self.circuits.lock().unwrap();
+		match circuits.get_mut(implementation) {
+			Some(circuit) => {
+				circuit.prune(now);
+				ClaimCircuitStatus {
+					open: circuit.open,
+					success_rate_pct: circuit.success_rate_pct(),
+					claims: circuit.outcomes.len(),
+					held: circuit.held.len(),
+				}
+			},
+			None => ClaimCircuitStatus {
+				open: false,
+				success_rate_pct: None,
+				claims: 0,
+				held: 0,
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_circuit_opens_below_success_rate() {
+		let breaker = ClaimSuccessRateCircuitBreaker::new(50.0, 4);
+
+		// Too few claims to judge the rate
+		assert!(!breaker.record("direct", false, 1000));
+		assert!(!breaker.record("direct", false, 1000));
+		assert!(!breaker.record("direct", true, 1000));
+		assert!(!breaker.is_open("direct"));
+
+		// Outcomes older than the window are ignored
+		assert!(!breaker.record("direct", true, 1000 + CLAIM_SUCCESS_WINDOW_SECONDS + 1));
+		assert_eq!(
+			breaker
+				.status("direct", 1000 + CLAIM_SUCCESS_WINDOW_SECONDS + 1)
+				.claims,
+			1
+		);
+
+		let now = 10_000;
+		assert!(!breaker.record("optimistic", true, now));
+		assert!(!breaker.record("optimistic", false, now));
+		assert!(!breaker.record("optimistic", false, now));
+		assert!(breaker.record("optimistic", false, now));
+		assert!(breaker.is_open("optimistic"));
+		assert!(!breaker.is_open("direct"));
+		// Reported only once
+		assert!(!breaker.record("optimistic", false, now));
+
+		breaker.hold("optimistic", "order-1".to_string());
+		breaker.hold("optimistic", "order-1".to_string());
+		assert_eq!(breaker.status("optimistic", now).held, 1);
+		assert_eq!(breaker.reset("optimistic"), vec!["order-1".to_string()]);
+		assert!(!breaker.is_open("optimistic"));
+		assert_eq!(breaker.status("optimistic", now).claims, 0);
+	}
+}
@@ -0,0 +1,175 @@
+//! Oracle health monitoring.
+//!
+//! Periodically probes every configured oracle with a lightweight
+//! `eth_getCode` call. Probes run concurrently and time out after
+//! [`PROBE_TIMEOUT`]. Oracles whose probe fails or times out, or that have no
+//! code deployed, are marked unhealthy so oracle selection can skip them.
+
+use crate::SettlementError;
+use alloy_primitives::{hex, Address as AlloyAddress};
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport_http::Http;
+use dashmap::DashMap;
+use solver_types::{with_0x_prefix, Address, NetworksConfig, SolverEvent};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Time after which an oracle probe is considered failed.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Health state of an oracle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleHealth {
+	/// The oracle responded to its last probe.
+	Healthy,
+	/// The oracle failed its last probe.
+	Unhealthy,
+}
+
+/// Shared map of oracle health states, keyed by chain ID and oracle address.
+///
+/// Oracles are keyed by chain since the same address may be deployed on some
+/// chains only. Oracles without an entry have not been probed yet and are
+/// treated as healthy.
+pub type OracleHealthMap = Arc<DashMap<(u64, Address), OracleHealth>>;
+
+/// Background checker that probes oracle contracts and records their health.
+pub struct OracleHealthChecker {
+	/// RPC providers for each chain with configured oracles.
+	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
+	/// Oracles to probe, with the health map each result is recorded in.
+	targets: Vec<(u64, Address, OracleHealthMap)>,
+}
+
+impl OracleHealthChecker {
+	/// Creates a new OracleHealthChecker for the given oracles.
+	///
+	/// Each target is a (chain ID, oracle address, health map) tuple.
+	pub fn new(
+		networks: &NetworksConfig,
+		targets: Vec<(u64, Address, OracleHealthMap)>,
+	) -> Result<Self, SettlementError> {
+		let mut providers = HashMap::new();
+
+		for (chain_id, _, _) in &targets {
+			if providers.contains_key(chain_id) {
+				continue;
+			}
+
+			let network = networks.get(chain_id).ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"Network {} not found in configuration",
+					chain_id
+				))
+			})?;
+			let http_url = network.get_http_url().ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No HTTP RPC URL configured for network {}",
+					chain_id
+				))
+			})?;
+			let provider = RootProvider::new_http(http_url.parse().map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Invalid RPC URL for network {}: {}",
+					chain_id, e
+				))
+			})?);
+
+			providers.insert(*chain_id, provider);
+		}
+
+		Ok(Self { providers, targets })
+	}
+
+	/// Probes all oracles once, concurrently, and records their health.
+	///
+	/// Returns the oracles that failed their probe as (chain ID, address) pairs.
+	pub async fn check_all(&self) -> Vec<(u64, Address)> {
+		let probes =
+			self.targets
+				.iter()
+				.map(|(chain_id, oracle, health)| async move {
+					let status =
+						match tokio::time::timeout(PROBE_TIMEOUT, self.probe(*chain_id, oracle))
+							.await
+						{
+							Ok(status) => status,
+							Err(_) => {
+								tracing::warn!(
+									chain_id = chain_id,
+									oracle = %with_0x_prefix(&hex::encode(&oracle.0)),
+									"Oracle health probe timed out"
+								);
+								OracleHealth::Unhealthy
+							},
+						};
+					health.insert((*chain_id, oracle.clone()), status);
+					(status == OracleHealth::Unhealthy).then(|| (*chain_id, oracle.clone()))
+				});
+
+		futures::future::join_all(probes)
+			.await
+			.into_iter()
+			.flatten()
+			.collect()
+	}
+
+	/// Spawns a task that probes all oracles at the given interval.
+	///
+	/// `on_event` is called with `SolverEvent::OracleUnhealthy` for every
+	/// oracle that fails a probe. Intervals shorter than a second are raised
+	/// to one second.
+	pub fn spawn<F>(self, interval: Duration, on_event: F) -> JoinHandle<()>
+	where
+		F: Fn(SolverEvent) + Send + Sync + 'static,
+	{
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(interval.max(Duration::from_secs(1)));
+			loop {
+				interval.tick().await;
+				for (chain_id, oracle_address) in self.check_all().await {
+					on_event(SolverEvent::OracleUnhealthy {
+						chain_id,
+						oracle_address,
+					});
+				}
+			}
+		})
+	}
+
+	/// Probes a single oracle with `eth_getCode`.
+	async fn probe(&self, chain_id: u64, oracle: &Address) -> OracleHealth {
+		let Some(provider) = self.providers.get(&chain_id) else {
+			return OracleHealth::Unhealthy;
+		};
+		if oracle.0.len() != 20 {
+			return OracleHealth::Unhealthy;
+		}
+
+		match provider
+			.get_code_at(AlloyAddress::from_slice(&oracle.0))
+			.await
+		{
+			Ok(code) if !code.is_empty() => OracleHealth::Healthy,
+			Ok(_) => {
+				tracing::warn!(
+					chain_id = chain_id,
+					oracle = %with_0x_prefix(&hex::encode(&oracle.0)),
+					"Oracle has no code deployed"
+				);
+				OracleHealth::Unhealthy
+			},
+			Err(e) => {
+				tracing::warn!(
+					chain_id = chain_id,
+					oracle = %with_0x_prefix(&hex::encode(&oracle.0)),
+					error = %e,
+					"Oracle health probe failed"
+				);
+				OracleHealth::Unhealthy
+			},
+		}
+	}
+}
@@ -4,19 +4,74 @@
 //! intended for testing and development. It handles fill validation and claim
 //! readiness checks using simple transaction receipt verification without
 //! complex attestation mechanisms.
-
-use crate::{utils::parse_oracle_config, OracleConfig, SettlementError, SettlementInterface};
-use alloy_primitives::{hex, FixedBytes};
+//!
+//! When TheCompact is configured, fills of resource-locked orders are proven
+//! with the allocator's attestation, and orders whose allocator nonce has
+//! already been consumed are not claimed.
+
+use crate::{
+	utils::{
+		log_parser::LogParser, merkle::MerkleProofBuilder, parse_oracle_config,
+		proof_registry::PROOF_VERSION_1,
+	},
+	OracleConfig, SettlementError, SettlementInterface,
+};
+use alloy_primitives::{hex, Address as AlloyAddress, FixedBytes, U256};
 use alloy_provider::{Provider, RootProvider};
-use alloy_rpc_types::BlockTransactionsKind;
+use alloy_rpc_client::BatchRequest;
+use alloy_rpc_types::{BlockTransactionsKind, Filter, TransactionReceipt, TransactionRequest};
+use alloy_sol_types::{sol, SolCall, SolEvent};
 use alloy_transport_http::Http;
 use async_trait::async_trait;
 use solver_types::{
-	with_0x_prefix, ConfigSchema, Eip7683OrderData, Field, FieldType, FillProof, NetworksConfig,
-	Order, Schema, TransactionHash,
+	standards::eip7683::LockType, with_0x_prefix, ConfigSchema, Eip7683OrderData, Field, FieldType,
+	FillProof, NetworksConfig, Order, Schema, SchemaDefinition, StandardFillProof, Transaction,
+	TransactionHash,
 };
 use std::collections::HashMap;
 
+/// Maximum number of receipts requested in one JSON-RPC batch.
+const RECEIPT_BATCH_SIZE: usize = 100;
+
+sol! {
+	/// Input settler entry point for claiming a batch of fills.
+	interface IInputSettlerBatch {
+		function finaliseBatch(bytes32 root, bytes32[] orderIds, bytes32[][] proofs, uint32[] timestamps, bytes32 solver) external;
+	}
+
+	/// MandateOutput specification as emitted by the output settler.
+	struct FilledMandateOutput {
+		bytes32 oracle;
+		bytes32 settler;
+		uint256 chainId;
+		bytes32 token;
+		uint256 amount;
+		bytes32 recipient;
+		bytes call;
+		bytes context;
+	}
+
+	/// Event emitted by the output settler when an output is filled.
+	event OutputFilled(bytes32 indexed orderId, bytes32 solver, uint32 timestamp, FilledMandateOutput output, uint256 finalAmount);
+
+	/// Event emitted by a TheCompact allocator from `attest(orderId, filler)`.
+	event Attested(bytes32 indexed orderId, address indexed filler, bytes signature);
+
+	/// TheCompact nonce lookups.
+	interface ITheCompact {
+		function hasConsumedAllocatorNonce(uint256 nonce, address allocator) external view returns (bool);
+	}
+}
+
+/// TheCompact contracts of resource-locked orders.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactConfig {
+	/// Allocator attesting fills of resource-locked inputs.
+	pub allocator: AlloyAddress,
+	/// TheCompact contract holding the resource locks.
+	pub compact: AlloyAddress,
+}
+
 /// Direct settlement implementation.
 ///
 /// This implementation validates fills by checking transaction receipts
@@ -28,27 +83,37 @@ pub struct DirectSettlement {
 	oracle_config: OracleConfig,
 	/// Dispute period duration in seconds.
 	dispute_period_seconds: u64,
+	/// Input settler addresses for each network, used for batch claims.
+	input_settlers: HashMap<u64, solver_types::Address>,
+	/// Parser for the output settler events of fill transactions.
+	log_parser: LogParser,
+	/// TheCompact contracts, if resource-locked orders are settled.
+	compact: Option<CompactConfig>,
+	/// Parser for allocator attestation events.
+	attestation_parser: LogParser,
 }
 
 impl DirectSettlement {
 	/// Creates a new DirectSettlement instance.
 	///
-	/// Configures settlement validation with oracle configuration
-	/// and dispute period.
+	/// Configures settlement validation with oracle configuration,
+	/// dispute period and optional TheCompact contracts.
 	pub async fn new(
 		networks: &NetworksConfig,
 		oracle_config: OracleConfig,
 		dispute_period_seconds: u64,
+		compact: Option<CompactConfig>,
 	) -> Result<Self, SettlementError> {
 		// Create RPC providers for each network that has oracles configured
 		let mut providers = HashMap::new();
+		let mut input_settlers = HashMap::new();
 
 		// Collect unique network IDs from input and output oracles
 		let mut all_network_ids: Vec<u64> = oracle_config
 			.input_oracles
 			.keys()
 			.chain(oracle_config.output_oracles.keys())
-			.copied()
+			.map(|chain_id| chain_id.as_u64())
 			.collect();
 		all_network_ids.sort_unstable();
 		all_network_ids.dedup();
@@ -75,14 +140,284 @@ impl DirectSettlement {
 			})?);
 
 			providers.insert(network_id, provider);
+			input_settlers.insert(network_id, network.input_settler_address.clone());
 		}
 
 		Ok(Self {
 			providers,
 			oracle_config,
 			dispute_period_seconds,
+			input_settlers,
+			log_parser: LogParser::new().register::<OutputFilled>(),
+			compact,
+			attestation_parser: LogParser::new().register::<Attested>(),
+		})
+	}
+
+	/// Returns the provider of the chain an order originates on.
+	fn origin_provider(
+		&self,
+		order: &Order,
+	) -> Result<&RootProvider<Http<reqwest::Client>>, SettlementError> {
+		let origin_chain_id = *order.input_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No input chains in order".to_string())
+		})?;
+
+		self.providers.get(&origin_chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No provider configured for chain {}",
+				origin_chain_id
+			))
+		})
+	}
+
+	/// Returns the signature the allocator attested the order's fill with.
+	///
+	/// Reads the `Attested` event the allocator emits for the order and the
+	/// solver on the origin chain. The attestation follows the fill, so logs
+	/// are searched from the origin chain block at the fill timestamp.
+	async fn allocator_signature(
+		&self,
+		order: &Order,
+		order_id: [u8; 32],
+		compact: &CompactConfig,
+		filled_timestamp: u64,
+	) -> Result<Vec<u8>, SettlementError> {
+		if order.solver_address.0.len() != 20 {
+			return Err(SettlementError::ValidationFailed(
+				"Invalid solver address length".to_string(),
+			));
+		}
+		let filler = AlloyAddress::from_slice(&order.solver_address.0);
+
+		let provider = self.origin_provider(order)?;
+		let from_block = block_at_or_before(provider, filled_timestamp).await?;
+		let filter = Filter::new()
+			.address(vec![compact.allocator])
+			.event_signature(vec![Attested::SIGNATURE_HASH])
+			.topic1(FixedBytes::<32>::from(order_id))
+			.from_block(from_block);
+		let logs = provider
+			.get_logs(&filter)
+			.await
+			.map_err(|e| SettlementError::ValidationFailed(format!("Failed to get logs: {}", e)))?;
+
+		self.attestation_parser
+			.parse_logs(&logs)
+			.into_iter()
+			.filter_map(|log| log.into_event::<Attested>())
+			.find(|event| event.filler == filler)
+			.map(|event| event.signature.to_vec())
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed("Fill not attested by allocator".to_string())
+			})
+	}
+
+	/// Returns whether TheCompact has consumed the order's allocator nonce,
+	/// meaning the resource lock has already been claimed.
+	async fn allocator_nonce_consumed(
+		&self,
+		order: &Order,
+		nonce: U256,
+		compact: &CompactConfig,
+	) -> Result<bool, SettlementError> {
+		let result = self
+			.origin_provider(order)?
+			.call(
+				&TransactionRequest::default().to(compact.compact).input(
+					ITheCompact::hasConsumedAllocatorNonceCall {
+						nonce,
+						allocator: compact.allocator,
+					}
+					.abi_encode()
+					.into(),
+				),
+			)
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to query TheCompact: {}", e))
+			})?;
+
+		ITheCompact::hasConsumedAllocatorNonceCall::abi_decode_returns(&result, true)
+			.map(|consumed| consumed._0)
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Invalid TheCompact response: {}", e))
+			})
+	}
+
+	/// Returns the provider of the chain an order is filled on.
+	fn destination_provider(
+		&self,
+		order: &Order,
+	) -> Result<&RootProvider<Http<reqwest::Client>>, SettlementError> {
+		// Note: For now we assume all outputs are on the same chain
+		let destination_chain_id = *order.output_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No output chains in order".to_string())
+		})?;
+
+		self.providers.get(&destination_chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No provider configured for chain {}",
+				destination_chain_id
+			))
 		})
 	}
+
+	/// Fetches the receipts of several transactions on a chain in one batch request.
+	///
+	/// Returns one result per transaction hash, in the same order.
+	async fn fetch_receipts(
+		&self,
+		chain_id: u64,
+		hashes: &[&TransactionHash],
+	) -> Vec<Result<TransactionReceipt, SettlementError>> {
+		let failed = |message: String| {
+			hashes
+				.iter()
+				.map(|_| Err(SettlementError::ValidationFailed(message.clone())))
+				.collect()
+		};
+
+		let Some(provider) = self.providers.get(&chain_id) else {
+			return failed(format!("No provider configured for chain {}", chain_id));
+		};
+
+		let mut batch = BatchRequest::new(provider.client());
+		let mut waiters = Vec::with_capacity(hashes.len());
+		for hash in hashes {
+			let hash = FixedBytes::<32>::from_slice(&hash.0);
+			match batch
+				.add_call::<_, Option<TransactionReceipt>>("eth_getTransactionReceipt", &(hash,))
+			{
+				Ok(waiter) => waiters.push(waiter),
+				Err(e) => return failed(format!("Failed to build receipt request: {}", e)),
+			}
+		}
+		if let Err(e) = batch.send().await {
+			return failed(format!("Failed to get receipts: {}", e));
+		}
+
+		let mut receipts = Vec::with_capacity(waiters.len());
+		for waiter in waiters {
+			receipts.push(match waiter.await {
+				Ok(Some(receipt)) => Ok(receipt),
+				Ok(None) => Err(SettlementError::ValidationFailed(
+					"Transaction not found".to_string(),
+				)),
+				Err(e) => Err(SettlementError::ValidationFailed(format!(
+					"Failed to get receipt: {}",
+					e
+				))),
+			});
+		}
+		receipts
+	}
+
+	/// Builds the fill proof of an order from the receipt of its fill transaction.
+	async fn build_fill_proof(
+		&self,
+		order: &Order,
+		tx_hash: &TransactionHash,
+		receipt: TransactionReceipt,
+	) -> Result<FillProof, SettlementError> {
+		// Get the origin chain ID from the order
+		// Note: For now we assume all inputs are on the same chain
+		let origin_chain_id = *order.input_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No input chains in order".to_string())
+		})?;
+
+		// Parse order data for other fields we need
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		let provider = self.destination_provider(order)?;
+
+		// Get the oracle address for this chain using the selection strategy
+		let oracle_addresses = self.get_input_oracles(origin_chain_id);
+		if oracle_addresses.is_empty() {
+			return Err(SettlementError::ValidationFailed(format!(
+				"No input oracle configured for chain {}",
+				origin_chain_id
+			)));
+		}
+
+		// Use selection strategy with order nonce as context for deterministic selection
+		let selection_context = order_data.nonce.to::<u64>();
+		let oracle_address = self
+			.select_oracle(origin_chain_id, &oracle_addresses, Some(selection_context))
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"Failed to select oracle for chain {}",
+					origin_chain_id
+				))
+			})?;
+
+		// Check if transaction was successful
+		if !receipt.status() {
+			return Err(SettlementError::ValidationFailed(
+				"Transaction failed".to_string(),
+			));
+		}
+
+		let tx_block = receipt.block_number.unwrap_or(0);
+
+		// Prefer the fill timestamp emitted by the output settler
+		let fill_timestamp = self
+			.log_parser
+			.parse(&receipt)
+			.iter()
+			.filter_map(|log| log.event::<OutputFilled>())
+			.find(|event| event.orderId.0 == order_data.order_id)
+			.map(|event| u64::from(event.timestamp));
+
+		let block_timestamp = match fill_timestamp {
+			Some(timestamp) => timestamp,
+			None => {
+				// Fall back to the block timestamp
+				let block = provider
+					.get_block_by_number(
+						alloy_rpc_types::BlockNumberOrTag::Number(tx_block),
+						BlockTransactionsKind::Hashes,
+					)
+					.await
+					.map_err(|e| {
+						SettlementError::ValidationFailed(format!("Failed to get block: {}", e))
+					})?;
+
+				block
+					.ok_or_else(|| {
+						SettlementError::ValidationFailed("Block not found".to_string())
+					})?
+					.header
+					.timestamp
+			},
+		};
+
+		// Resource-locked fills are proven by the allocator's attestation
+		let attestation_data = match order_data.lock_type {
+			LockType::ResourceLock => {
+				let compact = self.compact.as_ref().ok_or_else(|| {
+					SettlementError::ValidationFailed(
+						"TheCompact is not configured for resource-lock orders".to_string(),
+					)
+				})?;
+				self.allocator_signature(order, order_data.order_id, compact, block_timestamp)
+					.await?
+			},
+			LockType::Permit2Escrow => order_data.order_id.to_vec(),
+		};
+
+		Ok(FillProof::Standard(StandardFillProof {
+			tx_hash: tx_hash.clone(),
+			block_number: tx_block,
+			oracle_address: with_0x_prefix(&hex::encode(&oracle_address.0)),
+			attestation_data: Some(attestation_data),
+			filled_timestamp: block_timestamp,
+			proof_version: PROOF_VERSION_1,
+		}))
+	}
 }
 
 /// Configuration schema for DirectSettlement.
@@ -94,11 +429,10 @@ impl DirectSettlementSchema {
 		let instance = Self;
 		instance.validate(config)
 	}
-}
 
-impl ConfigSchema for DirectSettlementSchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
-		let schema = Schema::new(
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
 			// Required fields
 			vec![
 				Field::new(
@@ -107,24 +441,57 @@ impl ConfigSchema for DirectSettlementSchema {
 						min: Some(0),
 						max: Some(86400),
 					},
-				),
+				)
+				.with_description("Seconds after a fill before it can be claimed"),
 				Field::new(
 					"oracles",
 					FieldType::Table(Schema::new(
 						vec![
-							Field::new("input", FieldType::Table(Schema::new(vec![], vec![]))),
-							Field::new("output", FieldType::Table(Schema::new(vec![], vec![]))),
+							Field::new("input", FieldType::Table(Schema::new(vec![], vec![])))
+								.with_description("Oracle addresses on origin chains, by chain ID"),
+							Field::new("output", FieldType::Table(Schema::new(vec![], vec![])))
+								.with_description(
+									"Oracle addresses on destination chains, by chain ID",
+								),
 						],
 						vec![],
 					)),
-				),
-				Field::new("routes", FieldType::Table(Schema::new(vec![], vec![]))),
+				)
+				.with_description("Input and output oracle addresses per chain"),
+				Field::new("routes", FieldType::Table(Schema::new(vec![], vec![])))
+					.with_description(
+						"Destination chains reachable from each origin chain, by chain ID",
+					),
 			],
 			// Optional fields
-			vec![Field::new("oracle_selection_strategy", FieldType::String)],
-		);
+			vec![
+				Field::new("oracle_selection_strategy", FieldType::String)
+					.with_description(
+						"Oracle used when several are configured: First, RoundRobin or Random",
+					)
+					.with_default("First"),
+				Field::new("allocator_address", FieldType::String)
+					.with_description(
+						"TheCompact allocator attesting fills, requires compact_address",
+					)
+					.with_validator(validate_address),
+				Field::new("compact_address", FieldType::String)
+					.with_description(
+						"TheCompact contract on origin chains, requires allocator_address",
+					)
+					.with_validator(validate_address),
+			],
+		)
+	}
+}
+
+impl ConfigSchema for DirectSettlementSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
 
-		schema.validate(config)
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -147,57 +514,11 @@ impl SettlementInterface for DirectSettlement {
 		order: &Order,
 		tx_hash: &TransactionHash,
 	) -> Result<FillProof, SettlementError> {
-		// Get the origin chain ID from the order
-		// Note: For now we assume all inputs are on the same chain
-		let origin_chain_id = *order.input_chain_ids.first().ok_or_else(|| {
-			SettlementError::ValidationFailed("No input chains in order".to_string())
-		})?;
-		// Get the destination chain ID from the order
-		// Note: For now we assume all outputs are on the same chain
-		let destination_chain_id = *order.output_chain_ids.first().ok_or_else(|| {
-			SettlementError::ValidationFailed("No output chains in order".to_string())
-		})?;
-
-		// Parse order data for other fields we need
-		let order_data: Eip7683OrderData =
-			serde_json::from_value(order.data.clone()).map_err(|e| {
-				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
-			})?;
-
-		// Get the appropriate provider for this chain
-		let provider = self.providers.get(&destination_chain_id).ok_or_else(|| {
-			SettlementError::ValidationFailed(format!(
-				"No provider configured for chain {}",
-				destination_chain_id
-			))
-		})?;
-
-		// Get the oracle address for this chain using the selection strategy
-		let oracle_addresses = self.get_input_oracles(origin_chain_id);
-		if oracle_addresses.is_empty() {
-			return Err(SettlementError::ValidationFailed(format!(
-				"No input oracle configured for chain {}",
-				origin_chain_id
-			)));
-		}
-
-		// Use selection strategy with order nonce as context for deterministic selection
-		let selection_context = order_data.nonce.to::<u64>();
-		let oracle_address = self
-			.select_oracle(&oracle_addresses, Some(selection_context))
-			.ok_or_else(|| {
-				SettlementError::ValidationFailed(format!(
-					"Failed to select oracle for chain {}",
-					origin_chain_id
-				))
-			})?;
-
-		// Convert tx hash
-		let hash = FixedBytes::<32>::from_slice(&tx_hash.0);
+		let provider = self.destination_provider(order)?;
 
 		// Get transaction receipt
 		let receipt = provider
-			.get_transaction_receipt(hash)
+			.get_transaction_receipt(FixedBytes::<32>::from_slice(&tx_hash.0))
 			.await
 			.map_err(|e| {
 				SettlementError::ValidationFailed(format!("Failed to get receipt: {}", e))
@@ -206,38 +527,59 @@ impl SettlementInterface for DirectSettlement {
 				SettlementError::ValidationFailed("Transaction not found".to_string())
 			})?;
 
-		// Check if transaction was successful
-		if !receipt.status() {
-			return Err(SettlementError::ValidationFailed(
-				"Transaction failed".to_string(),
-			));
-		}
+		self.build_fill_proof(order, tx_hash, receipt).await
+	}
 
-		let tx_block = receipt.block_number.unwrap_or(0);
+	/// Gets attestation data for several filled orders.
+	///
+	/// Receipts are fetched with one JSON-RPC batch request per destination
+	/// chain and chunk of orders instead of one request per order.
+	async fn get_attestations_batch(
+		&self,
+		orders: &[(&Order, &TransactionHash)],
+	) -> Vec<Result<FillProof, SettlementError>> {
+		let mut receipts: Vec<Result<TransactionReceipt, SettlementError>> = orders
+			.iter()
+			.map(|_| {
+				Err(SettlementError::ValidationFailed(
+					"Receipt not fetched".to_string(),
+				))
+			})
+			.collect();
 
-		// Get the block timestamp
-		let block = provider
-			.get_block_by_number(
-				alloy_rpc_types::BlockNumberOrTag::Number(tx_block),
-				BlockTransactionsKind::Hashes,
-			)
-			.await
-			.map_err(|e| {
-				SettlementError::ValidationFailed(format!("Failed to get block: {}", e))
-			})?;
+		// Group orders by destination chain
+		let mut by_chain: HashMap<u64, Vec<usize>> = HashMap::new();
+		for (index, (order, _)) in orders.iter().enumerate() {
+			match order.output_chain_ids.first() {
+				Some(&chain_id) => by_chain.entry(chain_id).or_default().push(index),
+				None => {
+					receipts[index] = Err(SettlementError::ValidationFailed(
+						"No output chains in order".to_string(),
+					))
+				},
+			}
+		}
 
-		let block_timestamp = block
-			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?
-			.header
-			.timestamp;
+		for (chain_id, indices) in by_chain {
+			for chunk in indices.chunks(RECEIPT_BATCH_SIZE) {
+				let hashes: Vec<&TransactionHash> = chunk.iter().map(|&i| orders[i].1).collect();
+				for (&index, receipt) in chunk
+					.iter()
+					.zip(self.fetch_receipts(chain_id, &hashes).await)
+				{
+					receipts[index] = receipt;
+				}
+			}
+		}
 
-		Ok(FillProof {
-			tx_hash: tx_hash.clone(),
-			block_number: tx_block,
-			oracle_address: with_0x_prefix(&hex::encode(&oracle_address.0)),
-			attestation_data: Some(order_data.order_id.to_vec()),
-			filled_timestamp: block_timestamp,
-		})
+		let mut proofs = Vec::with_capacity(orders.len());
+		for ((order, tx_hash), receipt) in orders.iter().zip(receipts) {
+			proofs.push(match receipt {
+				Ok(receipt) => self.build_fill_proof(order, tx_hash, receipt).await,
+				Err(e) => Err(e),
+			});
+		}
+		proofs
 	}
 
 	/// Checks if an order is ready to be claimed.
@@ -278,12 +620,33 @@ impl SettlementInterface for DirectSettlement {
 
 		// Check if dispute period has passed using timestamps
 		let current_timestamp = current_block.header.timestamp;
-		let dispute_end_timestamp = fill_proof.filled_timestamp + self.dispute_period_seconds;
+		let dispute_end_timestamp = fill_proof.filled_timestamp() + self.dispute_period_seconds;
 
 		if current_timestamp < dispute_end_timestamp {
 			return false; // Still in dispute period
 		}
 
+		// Resource locks can only be claimed while the allocator nonce is unused
+		let Ok(order_data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+			return false;
+		};
+		if order_data.lock_type == LockType::ResourceLock {
+			let Some(compact) = &self.compact else {
+				return false;
+			};
+			match self
+				.allocator_nonce_consumed(order, order_data.nonce, compact)
+				.await
+			{
+				Ok(false) => {},
+				Ok(true) => return false,
+				Err(e) => {
+					tracing::warn!(order_id = %order.id, error = %e, "Failed to check allocator nonce");
+					return false;
+				},
+			}
+		}
+
 		// TODO check:
 		// 1. Oracle attestation exists
 		// 2. No disputes were raised
@@ -293,6 +656,84 @@ impl SettlementInterface for DirectSettlement {
 		// For now, return true if dispute period passed
 		true
 	}
+
+	/// Generates a batch claim committing to all order IDs with a Merkle root.
+	///
+	/// All orders must originate on the same chain. The calldata carries the
+	/// root, the order IDs with their Merkle proofs and the fill timestamps.
+	async fn generate_batch_claim_transaction(
+		&self,
+		orders: &[Order],
+		proofs: &[FillProof],
+	) -> Result<Transaction, SettlementError> {
+		if orders.is_empty() || orders.len() != proofs.len() {
+			return Err(SettlementError::ValidationFailed(format!(
+				"Batch claim requires one proof per order, got {} orders and {} proofs",
+				orders.len(),
+				proofs.len()
+			)));
+		}
+
+		let origin_chain_id = *orders[0].input_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No input chains in order".to_string())
+		})?;
+
+		let mut order_ids = Vec::with_capacity(orders.len());
+		for order in orders {
+			if order.input_chain_ids.first() != Some(&origin_chain_id) {
+				return Err(SettlementError::ValidationFailed(
+					"All orders in a batch claim must share the origin chain".to_string(),
+				));
+			}
+			let order_data: Eip7683OrderData =
+				serde_json::from_value(order.data.clone()).map_err(|e| {
+					SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+				})?;
+			order_ids.push(order_data.order_id);
+		}
+
+		let settler = self.input_settlers.get(&origin_chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No input settler configured for chain {}",
+				origin_chain_id
+			))
+		})?;
+
+		let tree = MerkleProofBuilder::new(order_ids.clone()).build()?;
+
+		let mut solver = [0u8; 32];
+		solver[12..].copy_from_slice(&orders[0].solver_address.0);
+
+		let call_data = IInputSettlerBatch::finaliseBatchCall {
+			root: FixedBytes::from(tree.root),
+			orderIds: order_ids.into_iter().map(FixedBytes::from).collect(),
+			proofs: tree
+				.proofs
+				.into_iter()
+				.map(|proof| proof.into_iter().map(FixedBytes::from).collect())
+				.collect(),
+			timestamps: proofs
+				.iter()
+				.map(|proof| proof.filled_timestamp() as u32)
+				.collect(),
+			solver: FixedBytes::from(solver),
+		}
+		.abi_encode();
+
+		Ok(Transaction {
+			to: Some(settler.clone()),
+			data: call_data,
+			value: U256::ZERO,
+			chain_id: origin_chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		})
+	}
 }
 
 /// Factory function to create a settlement provider from configuration.
@@ -304,6 +745,8 @@ impl SettlementInterface for DirectSettlement {
 ///
 /// Optional configuration parameters:
 /// - `dispute_period_seconds`: Dispute period duration (default: 300)
+/// - `allocator_address`: TheCompact allocator attesting fills (requires `compact_address`)
+/// - `compact_address`: TheCompact contract on origin chains (requires `allocator_address`)
 pub fn create_settlement(
 	config: &toml::Value,
 	networks: &NetworksConfig,
@@ -320,16 +763,69 @@ pub fn create_settlement(
 		.and_then(|v| v.as_integer())
 		.unwrap_or(300) as u64; // 5 minutes default
 
+	let address = |key: &str| {
+		config
+			.get(key)
+			.and_then(|v| v.as_str())
+			.and_then(|s| s.parse::<AlloyAddress>().ok())
+	};
+	let compact = match (address("allocator_address"), address("compact_address")) {
+		(Some(allocator), Some(compact)) => Some(CompactConfig { allocator, compact }),
+		(None, None) => None,
+		_ => {
+			return Err(SettlementError::ValidationFailed(
+				"allocator_address and compact_address must be configured together".to_string(),
+			))
+		},
+	};
+
 	// Create settlement service synchronously
 	let settlement = tokio::task::block_in_place(|| {
 		tokio::runtime::Handle::current().block_on(async {
-			DirectSettlement::new(networks, oracle_config, dispute_period_seconds).await
+			DirectSettlement::new(networks, oracle_config, dispute_period_seconds, compact).await
 		})
 	})?;
 
 	Ok(Box::new(settlement))
 }
 
+/// Returns a block of a chain mined at or before the given timestamp.
+///
+/// Steps back from the latest block in doubling strides, so the block is
+/// found in a logarithmic number of requests and lies at most twice as far
+/// back as needed.
+async fn block_at_or_before(
+	provider: &RootProvider<Http<reqwest::Client>>,
+	timestamp: u64,
+) -> Result<u64, SettlementError> {
+	let latest = provider.get_block_number().await.map_err(|e| {
+		SettlementError::ValidationFailed(format!("Failed to get block number: {}", e))
+	})?;
+	let mut block_number = latest;
+	let mut stride = 1u64;
+	loop {
+		let block = provider
+			.get_block_by_number(block_number.into(), BlockTransactionsKind::Hashes)
+			.await
+			.map_err(|e| SettlementError::ValidationFailed(format!("Failed to get block: {}", e)))?
+			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?;
+		if block.header.timestamp <= timestamp || block_number == 0 {
+			return Ok(block_number);
+		}
+		block_number = latest.saturating_sub(stride);
+		stride = stride.saturating_mul(2);
+	}
+}
+
+/// Validates that a configuration value is an address.
+fn validate_address(value: &toml::Value) -> Result<(), String> {
+	let address = value.as_str().unwrap_or_default();
+	address
+		.parse::<AlloyAddress>()
+		.map(|_| ())
+		.map_err(|_| format!("Invalid address: {}", address))
+}
+
 /// Registry for the direct settlement implementation.
 pub struct Registry;
 
@@ -340,6 +836,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_settlement
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(DirectSettlementSchema)
+	}
 }
 
 impl crate::SettlementRegistry for Registry {}
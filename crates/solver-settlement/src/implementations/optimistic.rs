@@ -0,0 +1,630 @@
+//! Optimistic settlement implementation with challenge monitoring.
+//!
+//! This module provides a settlement implementation where fills are assumed
+//! valid unless challenged within a challenge period. A `ChallengeMonitor`
+//! watches the origin settlement contract for `Challenged` events so that
+//! challenged fills are never claimed.
+
+use crate::{
+	utils::{
+		blocks::{block_ranges, block_timestamp, first_block_at},
+		log_parser::LogParser,
+		parse_oracle_config,
+		proof_registry::PROOF_VERSION_1,
+	},
+	OracleConfig, SettlementError, SettlementInterface,
+};
+use alloy_primitives::{hex, Address as AlloyAddress, FixedBytes};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{BlockTransactionsKind, Filter};
+use alloy_sol_types::{sol, SolEvent};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use solver_types::{
+	truncate_id, with_0x_prefix, ConfigSchema, Eip7683OrderData, Field, FieldType, FillProof,
+	NetworksConfig, Order, Schema, SchemaDefinition, StandardFillProof, TransactionHash,
+};
+use std::collections::{HashMap, HashSet};
+use tokio::sync::RwLock;
+
+// Solidity type definitions for challenge events.
+sol! {
+	/// Emitted by the settlement contract when a fill is challenged.
+	event Challenged(bytes32 orderId);
+}
+
+/// Seconds scanned before the fill timestamp when the origin chain block of
+/// a fill has to be looked up, tolerating clock differences between chains.
+const FILL_BLOCK_MARGIN_SECONDS: u64 = 60;
+
+/// Maximum number of blocks covered by a single log query, within the limits
+/// of common RPC providers.
+const MAX_LOG_QUERY_BLOCKS: u64 = 10_000;
+
+/// Watches settlement contracts for challenges against filled orders.
+///
+/// Orders are registered when their attestation is produced, together with
+/// the origin chain block to start scanning from. Orders that are not
+/// registered, such as after a restart, are scanned from the first origin
+/// chain block of their fill proof's timestamp. Logs are queried in ranges of
+/// at most [`MAX_LOG_QUERY_BLOCKS`] blocks. Once a challenge is seen for an
+/// order it is remembered permanently.
+pub struct ChallengeMonitor {
+	/// Next block to scan per order ID.
+	watched: RwLock<HashMap<String, u64>>,
+	/// Orders with a detected challenge.
+	challenged: RwLock<HashSet<String>>,
+	/// Parser for challenge events.
+	log_parser: LogParser,
+}
+
+impl ChallengeMonitor {
+	/// Creates a new, empty ChallengeMonitor.
+	pub fn new() -> Self {
+		Self {
+			watched: RwLock::new(HashMap::new()),
+			challenged: RwLock::new(HashSet::new()),
+			log_parser: LogParser::new().register::<Challenged>(),
+		}
+	}
+
+	/// Starts watching an order for challenges from the given block.
+	pub async fn watch(&self, order_id: &str, from_block: u64) {
+		self.watched
+			.write()
+			.await
+			.entry(order_id.to_string())
+			.or_insert(from_block);
+	}
+
+	/// Returns whether a challenge has already been recorded for the order.
+	pub async fn is_challenged(&self, order_id: &str) -> bool {
+		self.challenged.read().await.contains(order_id)
+	}
+
+	/// Scans new logs of the settlement contract for a challenge against the order.
+	///
+	/// Returns true if the order has been challenged, either now or previously.
+	/// Orders that are not being watched are scanned from the origin chain
+	/// block at which they were filled or, if their fill proof is not known,
+	/// from the first block of the challenge period preceding the current
+	/// block, as older challenges cannot affect a claim.
+	pub async fn poll(
+		&self,
+		provider: &RootProvider<Http<reqwest::Client>>,
+		settlement_contract: AlloyAddress,
+		order: &Order,
+		fill_proof: Option<&FillProof>,
+		order_id_bytes: [u8; 32],
+		challenge_period_seconds: u64,
+	) -> Result<bool, SettlementError> {
+		if self.is_challenged(&order.id).await {
+			return Ok(true);
+		}
+
+		let current_block = provider.get_block_number().await.map_err(|e| {
+			SettlementError::ValidationFailed(format!("Failed to get block number: {}", e))
+		})?;
+
+		let watched = self.watched.read().await.get(&order.id).copied();
+		let from_block = match (watched, fill_proof) {
+			(Some(from_block), _) => from_block,
+			// Fills on the origin chain are included in a known block
+			(None, Some(fill_proof)) if order.output_chain_ids == order.input_chain_ids => {
+				let from_block = fill_proof.block_number();
+				self.watch(&order.id, from_block).await;
+				from_block
+			},
+			(None, Some(fill_proof)) => {
+				let filled_at = fill_proof
+					.filled_timestamp()
+					.saturating_sub(FILL_BLOCK_MARGIN_SECONDS);
+				let from_block = first_block_at(current_block, filled_at, |number| async move {
+					block_timestamp(provider, number).await
+				})
+				.await?;
+				self.watch(&order.id, from_block).await;
+				from_block
+			},
+			(None, None) => {
+				let window_start = block_timestamp(provider, current_block)
+					.await?
+					.saturating_sub(challenge_period_seconds + FILL_BLOCK_MARGIN_SECONDS);
+				let from_block = first_block_at(current_block, window_start, |number| async move {
+					block_timestamp(provider, number).await
+				})
+				.await?;
+				self.watch(&order.id, from_block).await;
+				from_block
+			},
+		};
+
+		let target = FixedBytes::<32>::from(order_id_bytes);
+		for (from_block, to_block) in block_ranges(from_block, current_block, MAX_LOG_QUERY_BLOCKS)
+		{
+			let filter = Filter::new()
+				.address(vec![settlement_contract])
+				.event_signature(vec![Challenged::SIGNATURE_HASH])
+				.from_block(from_block)
+				.to_block(to_block);
+
+			let logs = provider.get_logs(&filter).await.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to get logs: {}", e))
+			})?;
+
+			let challenged = self
+				.log_parser
+				.parse_logs(&logs)
+				.iter()
+				.filter_map(|log| log.event::<Challenged>())
+				.any(|event| event.orderId == target);
+
+			if challenged {
+				tracing::warn!(
+					order_id = %truncate_id(&order.id),
+					"Fill challenged during challenge period"
+				);
+				self.challenged.write().await.insert(order.id.clone());
+				self.watched.write().await.remove(&order.id);
+				return Ok(true);
+			}
+
+			// Scanned ranges are not queried again, even if a later range fails
+			self.watched
+				.write()
+				.await
+				.insert(order.id.clone(), to_block + 1);
+		}
+
+		Ok(false)
+	}
+}
+
+impl Default for ChallengeMonitor {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Optimistic settlement implementation.
+///
+/// Fills become claimable once the challenge period has elapsed without a
+/// `Challenged` event for the order on the origin chain settlement contract.
+pub struct OptimisticSettlement {
+	/// RPC providers for each supported network.
+	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
+	/// Oracle configuration including addresses and routes.
+	oracle_config: OracleConfig,
+	/// Settlement contract emitting challenge events, per origin chain.
+	settlement_contracts: HashMap<u64, AlloyAddress>,
+	/// Challenge period duration in seconds.
+	challenge_period_seconds: u64,
+	/// Monitor tracking challenges against filled orders.
+	challenge_monitor: ChallengeMonitor,
+}
+
+impl OptimisticSettlement {
+	/// Creates a new OptimisticSettlement instance.
+	///
+	/// Challenges are watched on the input settler of each origin chain
+	/// unless a settlement contract override is provided for that chain.
+	pub fn new(
+		networks: &NetworksConfig,
+		oracle_config: OracleConfig,
+		settlement_overrides: HashMap<u64, AlloyAddress>,
+		challenge_period_seconds: u64,
+	) -> Result<Self, SettlementError> {
+		let mut providers = HashMap::new();
+		let mut settlement_contracts = HashMap::new();
+
+		// Collect unique network IDs from input and output oracles
+		let mut all_network_ids: Vec<u64> = oracle_config
+			.input_oracles
+			.keys()
+			.chain(oracle_config.output_oracles.keys())
+			.map(|chain_id| chain_id.as_u64())
+			.collect();
+		all_network_ids.sort_unstable();
+		all_network_ids.dedup();
+
+		for network_id in all_network_ids {
+			let network = networks.get(&network_id).ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"Network {} not found in configuration",
+					network_id
+				))
+			})?;
+
+			let http_url = network.get_http_url().ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No HTTP RPC URL configured for network {}",
+					network_id
+				))
+			})?;
+			let provider = RootProvider::new_http(http_url.parse().map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Invalid RPC URL for network {}: {}",
+					network_id, e
+				))
+			})?);
+
+			providers.insert(network_id, provider);
+
+			let contract = settlement_overrides
+				.get(&network_id)
+				.copied()
+				.unwrap_or_else(|| AlloyAddress::from_slice(&network.input_settler_address.0));
+			settlement_contracts.insert(network_id, contract);
+		}
+
+		Ok(Self {
+			providers,
+			oracle_config,
+			settlement_contracts,
+			challenge_period_seconds,
+			challenge_monitor: ChallengeMonitor::new(),
+		})
+	}
+
+	/// Gets the provider for a chain.
+	fn provider(
+		&self,
+		chain_id: u64,
+	) -> Result<&RootProvider<Http<reqwest::Client>>, SettlementError> {
+		self.providers.get(&chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No provider configured for chain {}",
+				chain_id
+			))
+		})
+	}
+
+	/// Checks the origin chain settlement contract for a challenge against the order.
+	async fn check_challenge(
+		&self,
+		order: &Order,
+		fill_proof: Option<&FillProof>,
+	) -> Result<bool, SettlementError> {
+		let origin_chain_id = *order.input_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No input chains in order".to_string())
+		})?;
+
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		let contract = *self
+			.settlement_contracts
+			.get(&origin_chain_id)
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No settlement contract configured for chain {}",
+					origin_chain_id
+				))
+			})?;
+
+		self.challenge_monitor
+			.poll(
+				self.provider(origin_chain_id)?,
+				contract,
+				order,
+				fill_proof,
+				order_data.order_id,
+				self.challenge_period_seconds,
+			)
+			.await
+	}
+}
+
+/// Configuration schema for OptimisticSettlement.
+pub struct OptimisticSettlementSchema;
+
+impl OptimisticSettlementSchema {
+	/// Static validation method for use before instance creation
+	pub fn validate_config(config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let instance = Self;
+		instance.validate(config)
+	}
+
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
+			// Required fields
+			vec![
+				Field::new(
+					"challenge_period_seconds",
+					FieldType::Integer {
+						min: Some(0),
+						max: Some(604800),
+					},
+				).with_description("Duration of the challenge window"),
+				Field::new(
+					"oracles",
+					FieldType::Table(Schema::new(
+						vec![
+							Field::new("input", FieldType::Table(Schema::new(vec![], vec![]))).with_description("Oracle addresses on origin chains, by chain ID"),
+							Field::new("output", FieldType::Table(Schema::new(vec![], vec![]))).with_description("Oracle addresses on destination chains, by chain ID"),
+						],
+						vec![],
+					)),
+				).with_description("Input and output oracle addresses per chain"),
+				Field::new("routes", FieldType::Table(Schema::new(vec![], vec![]))).with_description("Destination chains reachable from each origin chain, by chain ID"),
+			],
+			// Optional fields
+			vec![
+				Field::new("oracle_selection_strategy", FieldType::String).with_description("Oracle used when several are configured: First, RoundRobin or Random").with_default("First"),
+				Field::new(
+					"settlement_contracts",
+					FieldType::Table(Schema::new(vec![], vec![])),
+				).with_description("Contract emitting challenge events, by chain ID, defaulting to the input settler")
+				.with_validator(|value| {
+					let table = value
+						.as_table()
+						.ok_or_else(|| "settlement_contracts must be a table".to_string())?;
+					for (chain_id, address) in table {
+						if chain_id.parse::<u64>().is_err() {
+							return Err(format!(
+								"Invalid chain ID in settlement_contracts: {}",
+								chain_id
+							));
+						}
+						let valid = address
+							.as_str()
+							.is_some_and(|s| s.parse::<AlloyAddress>().is_ok());
+						if !valid {
+							return Err(format!(
+								"Invalid settlement contract address for chain {}",
+								chain_id
+							));
+						}
+					}
+					Ok(())
+				}),
+			],
+		)
+	}
+}
+
+impl ConfigSchema for OptimisticSettlementSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
+	}
+}
+
+#[async_trait]
+impl SettlementInterface for OptimisticSettlement {
+	fn oracle_config(&self) -> &OracleConfig {
+		&self.oracle_config
+	}
+
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(OptimisticSettlementSchema)
+	}
+
+	/// Gets attestation data for a filled order and starts challenge monitoring.
+	///
+	/// Extracts the fill block and timestamp from the fill receipt and registers
+	/// the order with the challenge monitor from the current origin chain block.
+	async fn get_attestation(
+		&self,
+		order: &Order,
+		tx_hash: &TransactionHash,
+	) -> Result<FillProof, SettlementError> {
+		let origin_chain_id = *order.input_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No input chains in order".to_string())
+		})?;
+		let destination_chain_id = *order.output_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No output chains in order".to_string())
+		})?;
+
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		// Use selection strategy with order nonce as context for deterministic selection
+		let oracle_addresses = self.get_input_oracles(origin_chain_id);
+		let oracle_address = self
+			.select_oracle(
+				origin_chain_id,
+				&oracle_addresses,
+				Some(order_data.nonce.to::<u64>()),
+			)
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No input oracle configured for chain {}",
+					origin_chain_id
+				))
+			})?;
+
+		let provider = self.provider(destination_chain_id)?;
+		let receipt = provider
+			.get_transaction_receipt(FixedBytes::<32>::from_slice(&tx_hash.0))
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to get receipt: {}", e))
+			})?
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed("Transaction not found".to_string())
+			})?;
+
+		if !receipt.status() {
+			return Err(SettlementError::ValidationFailed(
+				"Transaction failed".to_string(),
+			));
+		}
+
+		let tx_block = receipt.block_number.unwrap_or(0);
+		let block_timestamp = provider
+			.get_block_by_number(
+				alloy_rpc_types::BlockNumberOrTag::Number(tx_block),
+				BlockTransactionsKind::Hashes,
+			)
+			.await
+			.map_err(|e| SettlementError::ValidationFailed(format!("Failed to get block: {}", e)))?
+			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?
+			.header
+			.timestamp;
+
+		// Start watching for challenges from the current origin chain block
+		let origin_block = self
+			.provider(origin_chain_id)?
+			.get_block_number()
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to get block number: {}", e))
+			})?;
+		self.challenge_monitor.watch(&order.id, origin_block).await;
+
+		Ok(FillProof::Standard(StandardFillProof {
+			tx_hash: tx_hash.clone(),
+			block_number: tx_block,
+			oracle_address: with_0x_prefix(&hex::encode(&oracle_address.0)),
+			attestation_data: Some(order_data.order_id.to_vec()),
+			filled_timestamp: block_timestamp,
+			proof_version: PROOF_VERSION_1,
+		}))
+	}
+
+	/// Checks if an order is ready to be claimed.
+	///
+	/// Returns false permanently once a challenge has been detected. Otherwise
+	/// the order becomes claimable once the challenge period has elapsed on
+	/// the origin chain.
+	async fn can_claim(&self, order: &Order, fill_proof: &FillProof) -> bool {
+		match self.check_challenge(order, Some(fill_proof)).await {
+			Ok(false) => {},
+			Ok(true) => return false,
+			Err(e) => {
+				tracing::debug!(
+					order_id = %truncate_id(&order.id),
+					error = %e,
+					"Failed to check for challenges"
+				);
+				return false;
+			},
+		}
+
+		let origin_chain_id = match order.input_chain_ids.first() {
+			Some(&chain_id) => chain_id,
+			None => return false,
+		};
+		let provider = match self.provider(origin_chain_id) {
+			Ok(provider) => provider,
+			Err(_) => return false,
+		};
+
+		let current_timestamp = match provider
+			.get_block_by_number(
+				alloy_rpc_types::BlockNumberOrTag::Latest,
+				BlockTransactionsKind::Hashes,
+			)
+			.await
+		{
+			Ok(Some(block)) => block.header.timestamp,
+			_ => return false,
+		};
+
+		current_timestamp >= fill_proof.filled_timestamp() + self.challenge_period_seconds
+	}
+
+	/// Checks whether the fill for this order has been challenged.
+	async fn is_challenged(&self, order: &Order) -> bool {
+		if self.challenge_monitor.is_challenged(&order.id).await {
+			return true;
+		}
+		self.check_challenge(order, order.fill_proof.as_ref())
+			.await
+			.unwrap_or(false)
+	}
+}
+
+/// Factory function to create an optimistic settlement from configuration.
+///
+/// Required configuration parameters:
+/// - `challenge_period_seconds`: Duration of the challenge window
+/// - `oracles`: Input and output oracle addresses per chain
+/// - `routes`: Supported routes between chains
+///
+/// Optional configuration parameters:
+/// - `settlement_contracts`: Table mapping chain ID -> contract emitting `Challenged`
+///   events (defaults to the network's input settler)
+/// - `oracle_selection_strategy`: Oracle selection strategy
+pub fn create_settlement(
+	config: &toml::Value,
+	networks: &NetworksConfig,
+) -> Result<Box<dyn SettlementInterface>, SettlementError> {
+	// Validate configuration first
+	OptimisticSettlementSchema::validate_config(config)
+		.map_err(|e| SettlementError::ValidationFailed(format!("Invalid configuration: {}", e)))?;
+
+	// Parse oracle configuration using common utilities
+	let oracle_config = parse_oracle_config(config)?;
+
+	let challenge_period_seconds = config
+		.get("challenge_period_seconds")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(3600) as u64;
+
+	let settlement_overrides = config
+		.get("settlement_contracts")
+		.and_then(|v| v.as_table())
+		.map(|table| {
+			table
+				.iter()
+				.filter_map(|(chain_id, address)| {
+					Some((
+						chain_id.parse::<u64>().ok()?,
+						address.as_str()?.parse::<AlloyAddress>().ok()?,
+					))
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+
+	let settlement = OptimisticSettlement::new(
+		networks,
+		oracle_config,
+		settlement_overrides,
+		challenge_period_seconds,
+	)?;
+
+	Ok(Box::new(settlement))
+}
+
+/// Registry for the optimistic settlement implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "optimistic";
+	type Factory = crate::SettlementFactory;
+
+	fn factory() -> Self::Factory {
+		create_settlement
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(OptimisticSettlementSchema)
+	}
+}
+
+impl crate::SettlementRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_watch_keeps_first_block() {
+		let monitor = ChallengeMonitor::new();
+		monitor.watch("order-1", 100).await;
+		monitor.watch("order-1", 200).await;
+		assert_eq!(monitor.watched.read().await.get("order-1"), Some(&100));
+		assert!(!monitor.is_challenged("order-1").await);
+	}
+}
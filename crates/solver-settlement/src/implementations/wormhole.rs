@@ -0,0 +1,1234 @@
+//! Wormhole-based settlement implementation.
+//!
+//! This module provides a settlement implementation that relies on Wormhole
+//! guardians for cross-chain fill attestation. After the fill, the output
+//! oracle of the destination chain publishes the fill through the Wormhole
+//! core bridge, and the VAA (Verified Action Approval) signed by the guardians
+//! for that message is fetched from the Wormhole API and carried in the fill
+//! proof. The VAA must be emitted by the output oracle from the destination
+//! chain and carry the fill of the order by the solver. Claims are only
+//! allowed once the core bridge of the origin chain accepts the VAA as signed
+//! by a quorum of guardians.
+
+use crate::{
+	utils::{parse_oracle_config, proof_registry::PROOF_VERSION_1},
+	OracleConfig, SettlementError, SettlementInterface,
+};
+use alloy_primitives::{hex, keccak256, Address as AlloyAddress, FixedBytes, B256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{BlockTransactionsKind, Filter, TransactionRequest};
+use alloy_sol_types::{sol, SolCall, SolEvent};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use base64::Engine;
+use serde::Deserialize;
+use solver_types::oracle::DEFAULT_WORMHOLE_API_URL;
+use solver_types::{
+	truncate_id, with_0x_prefix, ConfigSchema, Eip7683OrderData, Field, FieldType, FillProof,
+	NetworksConfig, Order, Schema, SchemaDefinition, StandardFillProof, TransactionHash,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// Length of a guardian signature: guardian index, r, s and v.
+const SIGNATURE_LEN: usize = 66;
+
+/// Number of blocks after the fill searched for the message publishing it.
+const MAX_MESSAGE_SEARCH_BLOCKS: u64 = 10_000;
+
+// Solidity type definitions for the Wormhole core bridge.
+sol! {
+	/// Emitted by the core bridge when a message is published.
+	event LogMessagePublished(address indexed sender, uint64 sequence, uint32 nonce, bytes payload, uint8 consistencyLevel);
+
+	/// Wormhole core bridge subset used for VAA verification.
+	interface IWormhole {
+		struct Signature {
+			bytes32 r;
+			bytes32 s;
+			uint8 v;
+			uint8 guardianIndex;
+		}
+
+		struct VM {
+			uint8 version;
+			uint32 timestamp;
+			uint32 nonce;
+			uint16 emitterChainId;
+			bytes32 emitterAddress;
+			uint64 sequence;
+			uint8 consistencyLevel;
+			bytes payload;
+			uint32 guardianSetIndex;
+			Signature[] signatures;
+			bytes32 hash;
+		}
+
+		struct GuardianSet {
+			address[] keys;
+			uint32 expirationTime;
+		}
+
+		function parseAndVerifyVM(bytes calldata encodedVM) external view returns (VM memory vm, bool valid, string memory reason);
+		function verifyVM(VM memory vm) external view returns (bool valid, string memory reason);
+		function verifySignatures(bytes32 hash, Signature[] memory signatures, GuardianSet memory guardianSet) external pure returns (bool valid, string memory reason);
+		function getGuardianSet(uint32 index) external view returns (GuardianSet memory);
+	}
+}
+
+/// Signed content of a VAA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VaaBody {
+	/// Version 1: a single observation.
+	Observation {
+		timestamp: u32,
+		nonce: u32,
+		emitter_chain_id: u16,
+		emitter_address: B256,
+		sequence: u64,
+		consistency_level: u8,
+		payload: Vec<u8>,
+	},
+	/// Version 2: a batch of observation hashes.
+	Batch { hashes: Vec<B256> },
+}
+
+/// VAA decoded from its wire format.
+#[derive(Clone)]
+struct Vaa {
+	/// Index of the guardian set that signed the VAA.
+	guardian_set_index: u32,
+	/// Guardian signatures.
+	signatures: Vec<IWormhole::Signature>,
+	/// Signed content.
+	body: VaaBody,
+	/// Digest signed by the guardians.
+	digest: B256,
+}
+
+impl Vaa {
+	/// Decodes a version 1 or version 2 VAA.
+	///
+	/// Both versions share the header carrying the guardian set index and the
+	/// signatures. Version 1 signs the double keccak256 of a single observation
+	/// body; version 2 signs the double keccak256 of a list of observation
+	/// hashes.
+	fn parse(bytes: &[u8]) -> Result<Self, SettlementError> {
+		let mut reader = Reader(bytes);
+		let version = reader.u8()?;
+		let guardian_set_index = reader.u32()?;
+		let signature_count = reader.u8()? as usize;
+
+		let mut signatures = Vec::with_capacity(signature_count);
+		for _ in 0..signature_count {
+			let signature = reader.take(SIGNATURE_LEN)?;
+			signatures.push(IWormhole::Signature {
+				guardianIndex: signature[0],
+				r: B256::from_slice(&signature[1..33]),
+				s: B256::from_slice(&signature[33..65]),
+				// Guardians sign with recovery IDs 0 and 1
+				v: signature[65].wrapping_add(27),
+			});
+		}
+
+		let signed = reader.0;
+		let body = match version {
+			1 => VaaBody::Observation {
+				timestamp: reader.u32()?,
+				nonce: reader.u32()?,
+				emitter_chain_id: u16::from_be_bytes(reader.array()?),
+				emitter_address: B256::from(reader.array::<32>()?),
+				sequence: u64::from_be_bytes(reader.array()?),
+				consistency_level: reader.u8()?,
+				payload: reader.0.to_vec(),
+			},
+			2 => {
+				let hash_count = reader.u8()? as usize;
+				let hashes = (0..hash_count)
+					.map(|_| reader.array::<32>().map(B256::from))
+					.collect::<Result<Vec<_>, _>>()?;
+				if !reader.0.is_empty() {
+					return Err(invalid_vaa("trailing bytes after observation hashes"));
+				}
+				VaaBody::Batch { hashes }
+			},
+			version => return Err(invalid_vaa(&format!("unsupported version {}", version))),
+		};
+
+		Ok(Self {
+			guardian_set_index,
+			signatures,
+			body,
+			digest: keccak256(keccak256(signed)),
+		})
+	}
+
+	/// Returns the VAA in the form accepted by `IWormhole::verifyVM`, if it
+	/// carries a single observation.
+	fn to_vm(&self) -> Option<IWormhole::VM> {
+		let VaaBody::Observation {
+			timestamp,
+			nonce,
+			emitter_chain_id,
+			emitter_address,
+			sequence,
+			consistency_level,
+			payload,
+		} = &self.body
+		else {
+			return None;
+		};
+
+		Some(IWormhole::VM {
+			version: 1,
+			timestamp: *timestamp,
+			nonce: *nonce,
+			emitterChainId: *emitter_chain_id,
+			emitterAddress: *emitter_address,
+			sequence: *sequence,
+			consistencyLevel: *consistency_level,
+			payload: payload.clone().into(),
+			guardianSetIndex: self.guardian_set_index,
+			signatures: self.signatures.clone(),
+			hash: self.digest,
+		})
+	}
+}
+
+/// Cursor over the bytes of a VAA.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, len: usize) -> Result<&'a [u8], SettlementError> {
+		if self.0.len() < len {
+			return Err(invalid_vaa("unexpected end of data"));
+		}
+		let (head, tail) = self.0.split_at(len);
+		self.0 = tail;
+		Ok(head)
+	}
+
+	fn array<const N: usize>(&mut self) -> Result<[u8; N], SettlementError> {
+		Ok(self.take(N)?.try_into().expect("length checked"))
+	}
+
+	fn u8(&mut self) -> Result<u8, SettlementError> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn u16(&mut self) -> Result<u16, SettlementError> {
+		self.array().map(u16::from_be_bytes)
+	}
+
+	fn u32(&mut self) -> Result<u32, SettlementError> {
+		self.array().map(u32::from_be_bytes)
+	}
+}
+
+fn invalid_vaa(reason: &str) -> SettlementError {
+	SettlementError::ValidationFailed(format!("Invalid VAA: {}", reason))
+}
+
+/// Minimum number of guardian signatures for a guardian set of the given size.
+fn quorum(guardian_count: usize) -> usize {
+	guardian_count * 2 / 3 + 1
+}
+
+/// Returns whether an output oracle message carries the fill of an order by
+/// a solver.
+///
+/// Messages are encoded as `application (bytes32) || count (uint16)` followed
+/// by `count` payloads, each prefixed with its uint16 length. The application
+/// is the output settler, and fill payloads start with the solver and the
+/// order ID as bytes32 values.
+fn message_carries_fill(payload: &[u8], application: B256, solver: B256, order_id: B256) -> bool {
+	find_fill(payload, application, solver, order_id).unwrap_or(false)
+}
+
+/// Reads an output oracle message, failing on malformed messages.
+fn find_fill(
+	payload: &[u8],
+	application: B256,
+	solver: B256,
+	order_id: B256,
+) -> Result<bool, SettlementError> {
+	let mut reader = Reader(payload);
+	if B256::from(reader.array::<32>()?) != application {
+		return Ok(false);
+	}
+	for _ in 0..reader.u16()? {
+		let len = reader.u16()? as usize;
+		let fill = reader.take(len)?;
+		if fill.len() >= 64 && fill[..32] == solver[..] && fill[32..64] == order_id[..] {
+			return Ok(true);
+		}
+	}
+	Ok(false)
+}
+
+/// Returns the solver of an order left-padded to 32 bytes, as in fill payloads.
+fn solver_word(order: &Order) -> Result<B256, SettlementError> {
+	if order.solver_address.0.len() != 20 {
+		return Err(SettlementError::ValidationFailed(format!(
+			"Invalid solver address length {}",
+			order.solver_address.0.len()
+		)));
+	}
+	Ok(AlloyAddress::from_slice(&order.solver_address.0).into_word())
+}
+
+/// Checks that a VAA attests the fill of an order by a solver.
+///
+/// The VAA must carry a single observation emitted from `emitter_chain_id`
+/// by one of `emitters`, whose payload carries the fill.
+fn check_fill_message(
+	vaa: &Vaa,
+	emitter_chain_id: u16,
+	emitters: &[B256],
+	application: B256,
+	solver: B256,
+	order_id: B256,
+) -> Result<(), SettlementError> {
+	let VaaBody::Observation {
+		emitter_chain_id: vaa_chain_id,
+		emitter_address,
+		payload,
+		..
+	} = &vaa.body
+	else {
+		return Err(invalid_vaa("batch VAAs do not carry fill messages"));
+	};
+	if *vaa_chain_id != emitter_chain_id {
+		return Err(invalid_vaa(&format!(
+			"emitted from Wormhole chain {}, expected {}",
+			vaa_chain_id, emitter_chain_id
+		)));
+	}
+	if !emitters.contains(emitter_address) {
+		return Err(invalid_vaa(&format!(
+			"emitter {} is not an output oracle",
+			emitter_address
+		)));
+	}
+	if !message_carries_fill(payload, application, solver, order_id) {
+		return Err(invalid_vaa("payload does not carry the fill of the order"));
+	}
+	Ok(())
+}
+
+/// Response of the Wormhole API VAA search.
+#[derive(Debug, Deserialize)]
+struct VaaSearchResponse {
+	data: Vec<VaaRecord>,
+}
+
+/// Signed VAA as returned by the Wormhole API.
+#[derive(Debug, Deserialize)]
+struct VaaRecord {
+	/// Sequence of the attested message.
+	sequence: u64,
+	/// Base64-encoded VAA.
+	vaa: String,
+}
+
+/// Fetches the signed VAA of a message published by a transaction.
+pub(crate) async fn fetch_signed_vaa(
+	client: &reqwest::Client,
+	api_url: &str,
+	tx_hash: &TransactionHash,
+	sequence: u64,
+) -> Result<Vec<u8>, SettlementError> {
+	let url = format!(
+		"{}/api/v1/vaas/?txHash={}",
+		api_url,
+		with_0x_prefix(&hex::encode(&tx_hash.0))
+	);
+	let body = client
+		.get(&url)
+		.send()
+		.await
+		.and_then(|response| response.error_for_status())
+		.map_err(|e| SettlementError::ValidationFailed(format!("Failed to fetch VAA: {}", e)))?
+		.bytes()
+		.await
+		.map_err(|e| SettlementError::ValidationFailed(format!("Failed to fetch VAA: {}", e)))?;
+	let response: VaaSearchResponse = serde_json::from_slice(&body).map_err(|e| {
+		SettlementError::ValidationFailed(format!("Invalid Wormhole API response: {}", e))
+	})?;
+
+	let record = response
+		.data
+		.into_iter()
+		.find(|record| record.sequence == sequence)
+		.ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"VAA for sequence {} not signed yet",
+				sequence
+			))
+		})?;
+
+	base64::engine::general_purpose::STANDARD
+		.decode(&record.vaa)
+		.map_err(|e| invalid_vaa(&e.to_string()))
+}
+
+/// Wormhole settlement implementation.
+///
+/// Attests fills with the VAA of the message the output oracle published
+/// for the fill through the core bridge, and verifies it on the origin chain
+/// before claiming.
+pub struct WormholeSettlement {
+	/// RPC providers for each supported network.
+	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
+	/// Oracle configuration including addresses and routes.
+	oracle_config: OracleConfig,
+	/// Wormhole core bridge address per chain.
+	core_addresses: HashMap<u64, AlloyAddress>,
+	/// Wormhole chain ID of each chain.
+	wormhole_chain_ids: HashMap<u64, u16>,
+	/// Output settler address per chain, the application of fill messages.
+	output_settlers: HashMap<u64, AlloyAddress>,
+	/// Oldest guardian set whose VAAs are accepted.
+	guardian_set_index: u32,
+	/// Base URL of the Wormhole API.
+	api_url: String,
+	/// HTTP client for the Wormhole API.
+	client: reqwest::Client,
+}
+
+impl WormholeSettlement {
+	/// Creates a new WormholeSettlement instance.
+	///
+	/// Creates RPC providers for every chain that has oracles or a core
+	/// bridge configured.
+	pub fn new(
+		networks: &NetworksConfig,
+		oracle_config: OracleConfig,
+		core_addresses: HashMap<u64, AlloyAddress>,
+		wormhole_chain_ids: HashMap<u64, u16>,
+		guardian_set_index: u32,
+		api_url: String,
+	) -> Result<Self, SettlementError> {
+		let mut providers = HashMap::new();
+		let mut output_settlers = HashMap::new();
+
+		// Collect unique network IDs from oracles and core bridges
+		let mut all_network_ids: Vec<u64> = oracle_config
+			.input_oracles
+			.keys()
+			.chain(oracle_config.output_oracles.keys())
+			.map(|chain_id| chain_id.as_u64())
+			.chain(core_addresses.keys().copied())
+			.collect();
+		all_network_ids.sort_unstable();
+		all_network_ids.dedup();
+
+		for network_id in all_network_ids {
+			let network = networks.get(&network_id).ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"Network {} not found in configuration",
+					network_id
+				))
+			})?;
+
+			let http_url = network.get_http_url().ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No HTTP RPC URL configured for network {}",
+					network_id
+				))
+			})?;
+			let provider = RootProvider::new_http(http_url.parse().map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Invalid RPC URL for network {}: {}",
+					network_id, e
+				))
+			})?);
+
+			providers.insert(network_id, provider);
+			if network.output_settler_address.0.len() == 20 {
+				output_settlers.insert(
+					network_id,
+					AlloyAddress::from_slice(&network.output_settler_address.0),
+				);
+			}
+		}
+
+		let client = reqwest::Client::builder()
+			.timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+			.build()
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to build HTTP client: {}", e))
+			})?;
+
+		Ok(Self {
+			providers,
+			oracle_config,
+			core_addresses,
+			wormhole_chain_ids,
+			output_settlers,
+			guardian_set_index,
+			api_url: api_url.trim_end_matches('/').to_string(),
+			client,
+		})
+	}
+
+	/// Gets the provider for a chain.
+	fn provider(
+		&self,
+		chain_id: u64,
+	) -> Result<&RootProvider<Http<reqwest::Client>>, SettlementError> {
+		self.providers.get(&chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No provider configured for chain {}",
+				chain_id
+			))
+		})
+	}
+
+	/// Gets the core bridge address for a chain.
+	fn core_address(&self, chain_id: u64) -> Result<AlloyAddress, SettlementError> {
+		self.core_addresses.get(&chain_id).copied().ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No Wormhole core bridge configured for chain {}",
+				chain_id
+			))
+		})
+	}
+
+	/// Calls a view function of the core bridge on a chain.
+	async fn call_core<C: SolCall>(
+		&self,
+		chain_id: u64,
+		call: C,
+	) -> Result<C::Return, SettlementError> {
+		let result = self
+			.provider(chain_id)?
+			.call(
+				&TransactionRequest::default()
+					.to(self.core_address(chain_id)?)
+					.input(call.abi_encode().into()),
+			)
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Failed to call Wormhole core bridge: {}",
+					e
+				))
+			})?;
+
+		C::abi_decode_returns(&result, true).map_err(|e| {
+			SettlementError::ValidationFailed(format!(
+				"Invalid Wormhole core bridge response: {}",
+				e
+			))
+		})
+	}
+
+	/// Returns the Wormhole chain ID of a chain.
+	fn wormhole_chain_id(&self, chain_id: u64) -> Result<u16, SettlementError> {
+		self.wormhole_chain_ids
+			.get(&chain_id)
+			.copied()
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No Wormhole chain ID configured for chain {}",
+					chain_id
+				))
+			})
+	}
+
+	/// Returns the output settler of a chain, left-padded to 32 bytes.
+	fn output_settler(&self, chain_id: u64) -> Result<B256, SettlementError> {
+		self.output_settlers
+			.get(&chain_id)
+			.map(|address| address.into_word())
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No output settler configured for chain {}",
+					chain_id
+				))
+			})
+	}
+
+	/// Returns the output oracles of a chain, left-padded to 32 bytes.
+	fn output_oracle_words(&self, chain_id: u64) -> Vec<B256> {
+		self.get_output_oracles(chain_id)
+			.iter()
+			.filter(|oracle| oracle.0.len() == 20)
+			.map(|oracle| AlloyAddress::from_slice(&oracle.0).into_word())
+			.collect()
+	}
+
+	/// Checks that a VAA attests the fill of an order by its solver on the
+	/// order's destination chain.
+	fn check_fill_vaa(
+		&self,
+		vaa: &Vaa,
+		order: &Order,
+		destination_chain_id: u64,
+		order_id: [u8; 32],
+	) -> Result<(), SettlementError> {
+		check_fill_message(
+			vaa,
+			self.wormhole_chain_id(destination_chain_id)?,
+			&self.output_oracle_words(destination_chain_id),
+			self.output_settler(destination_chain_id)?,
+			solver_word(order)?,
+			B256::from(order_id),
+		)
+	}
+
+	/// Finds the message an output oracle published for the fill of an order
+	/// in the blocks following the fill.
+	///
+	/// Returns the hash of the transaction publishing the message and the
+	/// message's sequence.
+	async fn find_fill_message(
+		&self,
+		order: &Order,
+		destination_chain_id: u64,
+		fill_block: u64,
+		order_id: [u8; 32],
+	) -> Result<(TransactionHash, u64), SettlementError> {
+		let provider = self.provider(destination_chain_id)?;
+		let emitters = self.output_oracle_words(destination_chain_id);
+		if emitters.is_empty() {
+			return Err(SettlementError::ValidationFailed(format!(
+				"No output oracle configured for chain {}",
+				destination_chain_id
+			)));
+		}
+		let application = self.output_settler(destination_chain_id)?;
+		let solver = solver_word(order)?;
+
+		let current_block = provider.get_block_number().await.map_err(|e| {
+			SettlementError::ValidationFailed(format!("Failed to get block number: {}", e))
+		})?;
+		let filter = Filter::new()
+			.address(vec![self.core_address(destination_chain_id)?])
+			.event_signature(vec![LogMessagePublished::SIGNATURE_HASH])
+			.topic1(emitters)
+			.from_block(fill_block)
+			.to_block(current_block.min(fill_block + MAX_MESSAGE_SEARCH_BLOCKS));
+		let logs = provider
+			.get_logs(&filter)
+			.await
+			.map_err(|e| SettlementError::ValidationFailed(format!("Failed to get logs: {}", e)))?;
+
+		logs.iter()
+			.find_map(|log| {
+				let event = LogMessagePublished::decode_log(&log.inner, true).ok()?;
+				if !message_carries_fill(&event.payload, application, solver, B256::from(order_id))
+				{
+					return None;
+				}
+				Some((
+					TransactionHash(log.transaction_hash?.to_vec()),
+					event.sequence,
+				))
+			})
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(
+					"No Wormhole message published for the fill yet".to_string(),
+				)
+			})
+	}
+
+	/// Verifies on a chain that a VAA is signed by a quorum of its guardian set.
+	async fn verify_vaa(&self, chain_id: u64, vaa: &Vaa) -> Result<bool, SettlementError> {
+		if vaa.guardian_set_index < self.guardian_set_index {
+			return Ok(false);
+		}
+
+		let guardian_set = self
+			.call_core(
+				chain_id,
+				IWormhole::getGuardianSetCall {
+					index: vaa.guardian_set_index,
+				},
+			)
+			.await?
+			._0;
+		if vaa.signatures.len() < quorum(guardian_set.keys.len()) {
+			return Ok(false);
+		}
+
+		let (valid, reason) = match vaa.to_vm() {
+			Some(vm) => {
+				let result = self
+					.call_core(chain_id, IWormhole::verifyVMCall { vm })
+					.await?;
+				(result.valid, result.reason)
+			},
+			None => {
+				let result = self
+					.call_core(
+						chain_id,
+						IWormhole::verifySignaturesCall {
+							hash: vaa.digest,
+							signatures: vaa.signatures.clone(),
+							guardianSet: guardian_set,
+						},
+					)
+					.await?;
+				(result.valid, result.reason)
+			},
+		};
+
+		if !valid {
+			tracing::debug!(chain_id, reason = %reason, "Wormhole VAA rejected");
+		}
+		Ok(valid)
+	}
+}
+
+/// Configuration schema for WormholeSettlement.
+pub struct WormholeSettlementSchema;
+
+impl WormholeSettlementSchema {
+	/// Static validation method for use before instance creation
+	pub fn validate_config(config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let instance = Self;
+		instance.validate(config)
+	}
+
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		Schema::new(
+			// Required fields
+			vec![
+				Field::new(
+					"wormhole_core_address",
+					FieldType::Table(Schema::new(vec![], vec![])),
+				)
+				.with_description("Wormhole core bridge address, by chain ID")
+				.with_validator(|value| {
+					let table = value
+						.as_table()
+						.ok_or_else(|| "wormhole_core_address must be a table".to_string())?;
+					if table.is_empty() {
+						return Err("At least one core bridge address is required".to_string());
+					}
+					for (chain_id, address) in table {
+						chain_id.parse::<u64>().map_err(|_| {
+							format!("Invalid chain ID in wormhole_core_address: {}", chain_id)
+						})?;
+						address
+							.as_str()
+							.ok_or_else(|| {
+								format!(
+									"Core bridge address for chain {} must be a string",
+									chain_id
+								)
+							})?
+							.parse::<AlloyAddress>()
+							.map_err(|e| {
+								format!("Invalid core bridge address for chain {}: {}", chain_id, e)
+							})?;
+					}
+					Ok(())
+				}),
+				Field::new(
+					"wormhole_chain_ids",
+					FieldType::Table(Schema::new(vec![], vec![])),
+				)
+				.with_description("Wormhole chain ID, by chain ID")
+				.with_validator(|value| {
+					let table = value
+						.as_table()
+						.ok_or_else(|| "wormhole_chain_ids must be a table".to_string())?;
+					for (chain_id, wormhole_chain_id) in table {
+						chain_id.parse::<u64>().map_err(|_| {
+							format!("Invalid chain ID in wormhole_chain_ids: {}", chain_id)
+						})?;
+						wormhole_chain_id
+							.as_integer()
+							.and_then(|id| u16::try_from(id).ok())
+							.ok_or_else(|| {
+								format!("Invalid Wormhole chain ID for chain {}", chain_id)
+							})?;
+					}
+					Ok(())
+				}),
+				Field::new(
+					"guardian_set_index",
+					FieldType::Integer {
+						min: Some(0),
+						max: Some(u32::MAX as i64),
+					},
+				)
+				.with_description("Oldest guardian set whose VAAs are accepted"),
+				Field::new(
+					"oracles",
+					FieldType::Table(Schema::new(
+						vec![
+							Field::new("input", FieldType::Table(Schema::new(vec![], vec![])))
+								.with_description("Oracle addresses on origin chains, by chain ID"),
+							Field::new("output", FieldType::Table(Schema::new(vec![], vec![])))
+								.with_description(
+									"Oracle addresses on destination chains, by chain ID",
+								),
+						],
+						vec![],
+					)),
+				)
+				.with_description("Input and output oracle addresses per chain"),
+				Field::new("routes", FieldType::Table(Schema::new(vec![], vec![])))
+					.with_description(
+						"Destination chains reachable from each origin chain, by chain ID",
+					),
+			],
+			// Optional fields
+			vec![
+				Field::new("wormhole_api_url", FieldType::String)
+					.with_description("Wormhole API serving signed VAAs")
+					.with_default(DEFAULT_WORMHOLE_API_URL),
+				Field::new("oracle_selection_strategy", FieldType::String)
+					.with_description(
+						"Oracle used when several are configured: First, RoundRobin or Random",
+					)
+					.with_default("First"),
+			],
+		)
+	}
+}
+
+impl ConfigSchema for WormholeSettlementSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
+	}
+}
+
+#[async_trait]
+impl SettlementInterface for WormholeSettlement {
+	fn oracle_config(&self) -> &OracleConfig {
+		&self.oracle_config
+	}
+
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(WormholeSettlementSchema)
+	}
+
+	/// Gets attestation data for a filled order.
+	///
+	/// Finds the message an output oracle published for the fill through the
+	/// core bridge of the destination chain, fetches its signed VAA, checks
+	/// that it carries the fill and verifies it with `parseAndVerifyVM`
+	/// before caching it in the fill proof. Fails until the message is
+	/// published and signed.
+	async fn get_attestation(
+		&self,
+		order: &Order,
+		tx_hash: &TransactionHash,
+	) -> Result<FillProof, SettlementError> {
+		let origin_chain_id = *order.input_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No input chains in order".to_string())
+		})?;
+		let destination_chain_id = *order.output_chain_ids.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("No output chains in order".to_string())
+		})?;
+
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		// Select the input oracle using the order nonce for deterministic selection
+		let oracle_addresses = self.get_input_oracles(origin_chain_id);
+		let oracle_address = self
+			.select_oracle(
+				origin_chain_id,
+				&oracle_addresses,
+				Some(order_data.nonce.saturating_to::<u64>()),
+			)
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No input oracle configured for chain {}",
+					origin_chain_id
+				))
+			})?;
+
+		let provider = self.provider(destination_chain_id)?;
+		let receipt = provider
+			.get_transaction_receipt(FixedBytes::<32>::from_slice(&tx_hash.0))
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to get receipt: {}", e))
+			})?
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed("Transaction not found".to_string())
+			})?;
+
+		if !receipt.status() {
+			return Err(SettlementError::ValidationFailed(
+				"Transaction failed".to_string(),
+			));
+		}
+
+		// The output oracle publishes the fill in a transaction of its own
+		let tx_block = receipt.block_number.unwrap_or(0);
+		let (message_tx_hash, sequence) = self
+			.find_fill_message(order, destination_chain_id, tx_block, order_data.order_id)
+			.await?;
+
+		let vaa = fetch_signed_vaa(&self.client, &self.api_url, &message_tx_hash, sequence).await?;
+		self.check_fill_vaa(
+			&Vaa::parse(&vaa)?,
+			order,
+			destination_chain_id,
+			order_data.order_id,
+		)?;
+		let verified = self
+			.call_core(
+				destination_chain_id,
+				IWormhole::parseAndVerifyVMCall {
+					encodedVM: vaa.clone().into(),
+				},
+			)
+			.await?;
+		if !verified.valid {
+			return Err(SettlementError::ValidationFailed(format!(
+				"Wormhole VAA rejected: {}",
+				verified.reason
+			)));
+		}
+
+		let block_timestamp = provider
+			.get_block_by_number(
+				alloy_rpc_types::BlockNumberOrTag::Number(tx_block),
+				BlockTransactionsKind::Hashes,
+			)
+			.await
+			.map_err(|e| SettlementError::ValidationFailed(format!("Failed to get block: {}", e)))?
+			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?
+			.header
+			.timestamp;
+
+		tracing::debug!(
+			order_id = %truncate_id(&order.id),
+			sequence,
+			"Fetched Wormhole VAA for fill"
+		);
+
+		Ok(FillProof::Standard(StandardFillProof {
+			tx_hash: tx_hash.clone(),
+			block_number: tx_block,
+			oracle_address: with_0x_prefix(&hex::encode(&oracle_address.0)),
+			attestation_data: Some(vaa),
+			filled_timestamp: block_timestamp,
+			proof_version: PROOF_VERSION_1,
+		}))
+	}
+
+	/// Checks if an order is ready to be claimed.
+	///
+	/// Requires the cached VAA to attest the fill of the order by its solver
+	/// and to be signed by a quorum of a guardian set no older than the
+	/// configured one, as verified by the core bridge of the origin chain.
+	async fn can_claim(&self, order: &Order, fill_proof: &FillProof) -> bool {
+		// VAAs are cached in oracle attested proofs only
+		let FillProof::Standard(fill_proof) = fill_proof else {
+			return false;
+		};
+
+		let (Some(&origin_chain_id), Some(&destination_chain_id)) = (
+			order.input_chain_ids.first(),
+			order.output_chain_ids.first(),
+		) else {
+			return false;
+		};
+		let Ok(order_data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+			return false;
+		};
+
+		let vaa = match fill_proof.attestation_data.as_deref().map(Vaa::parse) {
+			Some(Ok(vaa)) => vaa,
+			_ => return false,
+		};
+		if let Err(e) = self.check_fill_vaa(&vaa, order, destination_chain_id, order_data.order_id)
+		{
+			tracing::warn!(
+				order_id = %truncate_id(&order.id),
+				error = %e,
+				"Wormhole VAA does not attest the fill"
+			);
+			return false;
+		}
+
+		match self.verify_vaa(origin_chain_id, &vaa).await {
+			Ok(valid) => valid,
+			Err(e) => {
+				tracing::debug!(
+					order_id = %truncate_id(&order.id),
+					error = %e,
+					"Failed to verify Wormhole VAA"
+				);
+				false
+			},
+		}
+	}
+}
+
+/// Factory function to create a Wormhole settlement from configuration.
+///
+/// Required configuration parameters:
+/// - `wormhole_core_address`: Table mapping chain ID -> Wormhole core bridge address
+/// - `wormhole_chain_ids`: Table mapping chain ID -> Wormhole chain ID
+/// - `guardian_set_index`: Oldest guardian set whose VAAs are accepted
+/// - `oracles`: Input and output oracle addresses per chain
+/// - `routes`: Supported routes between chains
+///
+/// Optional configuration parameters:
+/// - `wormhole_api_url`: Wormhole API serving signed VAAs (default: Wormholescan)
+/// - `oracle_selection_strategy`: Oracle selection strategy
+pub fn create_settlement(
+	config: &toml::Value,
+	networks: &NetworksConfig,
+) -> Result<Box<dyn SettlementInterface>, SettlementError> {
+	// Validate configuration first
+	WormholeSettlementSchema::validate_config(config)
+		.map_err(|e| SettlementError::ValidationFailed(format!("Invalid configuration: {}", e)))?;
+
+	// Parse oracle configuration using common utilities
+	let oracle_config = parse_oracle_config(config)?;
+
+	let core_addresses = config
+		.get("wormhole_core_address")
+		.and_then(|v| v.as_table())
+		.expect("wormhole_core_address already validated")
+		.iter()
+		.map(|(chain_id, address)| {
+			let chain_id = chain_id.parse::<u64>().expect("chain ID already validated");
+			let address = address
+				.as_str()
+				.and_then(|s| s.parse::<AlloyAddress>().ok())
+				.expect("core bridge address already validated");
+			(chain_id, address)
+		})
+		.collect();
+
+	let wormhole_chain_ids = config
+		.get("wormhole_chain_ids")
+		.and_then(|v| v.as_table())
+		.expect("wormhole_chain_ids already validated")
+		.iter()
+		.map(|(chain_id, wormhole_chain_id)| {
+			let chain_id = chain_id.parse::<u64>().expect("chain ID already validated");
+			let wormhole_chain_id = wormhole_chain_id
+				.as_integer()
+				.and_then(|id| u16::try_from(id).ok())
+				.expect("Wormhole chain ID already validated");
+			(chain_id, wormhole_chain_id)
+		})
+		.collect();
+
+	let guardian_set_index = config
+		.get("guardian_set_index")
+		.and_then(|v| v.as_integer())
+		.expect("guardian_set_index already validated") as u32;
+
+	let api_url = config
+		.get("wormhole_api_url")
+		.and_then(|v| v.as_str())
+		.unwrap_or(DEFAULT_WORMHOLE_API_URL)
+		.to_string();
+
+	let settlement = WormholeSettlement::new(
+		networks,
+		oracle_config,
+		core_addresses,
+		wormhole_chain_ids,
+		guardian_set_index,
+		api_url,
+	)?;
+
+	Ok(Box::new(settlement))
+}
+
+/// Registry for the Wormhole settlement implementation.
+pub struct Registry;
+
+impl solver_types::ImplementationRegistry for Registry {
+	const NAME: &'static str = "wormhole";
+	type Factory = crate::SettlementFactory;
+
+	fn factory() -> Self::Factory {
+		create_settlement
+	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(WormholeSettlementSchema)
+	}
+}
+
+impl crate::SettlementRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Encodes a VAA header with one signature per guardian index.
+	fn header(version: u8, guardian_indexes: &[u8]) -> Vec<u8> {
+		let mut bytes = vec![version];
+		bytes.extend_from_slice(&4u32.to_be_bytes());
+		bytes.push(guardian_indexes.len() as u8);
+		for &index in guardian_indexes {
+			bytes.push(index);
+			bytes.extend_from_slice(&[0x11; 32]);
+			bytes.extend_from_slice(&[0x22; 32]);
+			bytes.push(1);
+		}
+		bytes
+	}
+
+	#[test]
+	fn test_parse_v1_vaa() {
+		let mut body = Vec::new();
+		body.extend_from_slice(&1_700_000_000u32.to_be_bytes());
+		body.extend_from_slice(&7u32.to_be_bytes());
+		body.extend_from_slice(&2u16.to_be_bytes());
+		body.extend_from_slice(&[0xAA; 32]);
+		body.extend_from_slice(&42u64.to_be_bytes());
+		body.push(15);
+		body.extend_from_slice(b"fill");
+		let mut bytes = header(1, &[0, 3]);
+		bytes.extend_from_slice(&body);
+
+		let vaa = Vaa::parse(&bytes).unwrap();
+		assert_eq!(vaa.guardian_set_index, 4);
+		assert_eq!(vaa.signatures.len(), 2);
+		assert_eq!(vaa.signatures[1].guardianIndex, 3);
+		assert_eq!(vaa.signatures[1].v, 28);
+		assert_eq!(vaa.digest, keccak256(keccak256(&body)));
+
+		let vm = vaa.to_vm().unwrap();
+		assert_eq!(vm.emitterChainId, 2);
+		assert_eq!(vm.sequence, 42);
+		assert_eq!(vm.consistencyLevel, 15);
+		assert_eq!(vm.payload.as_ref(), b"fill");
+
+		assert!(Vaa::parse(&bytes[..bytes.len() - body.len() + 3]).is_err());
+	}
+
+	#[test]
+	fn test_parse_v2_vaa() {
+		let mut hashes = vec![2u8];
+		hashes.extend_from_slice(&[0x01; 32]);
+		hashes.extend_from_slice(&[0x02; 32]);
+		let mut bytes = header(2, &[1]);
+		bytes.extend_from_slice(&hashes);
+
+		let vaa = Vaa::parse(&bytes).unwrap();
+		assert_eq!(
+			vaa.body,
+			VaaBody::Batch {
+				hashes: vec![B256::repeat_byte(1), B256::repeat_byte(2)],
+			}
+		);
+		assert_eq!(vaa.digest, keccak256(keccak256(&hashes)));
+		assert!(vaa.to_vm().is_none());
+
+		bytes.push(0);
+		assert!(Vaa::parse(&bytes).is_err());
+		assert!(Vaa::parse(&header(3, &[])).is_err());
+	}
+
+	#[test]
+	fn test_quorum() {
+		assert_eq!(quorum(19), 13);
+		assert_eq!(quorum(1), 1);
+	}
+
+	/// Encodes an output oracle message carrying the given payloads.
+	fn message(application: B256, payloads: &[Vec<u8>]) -> Vec<u8> {
+		let mut bytes = application.to_vec();
+		bytes.extend_from_slice(&(payloads.len() as u16).to_be_bytes());
+		for payload in payloads {
+			bytes.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+			bytes.extend_from_slice(payload);
+		}
+		bytes
+	}
+
+	/// Encodes the start of a fill payload followed by the rest of the fill.
+	fn fill(solver: B256, order_id: B256) -> Vec<u8> {
+		[solver.as_slice(), order_id.as_slice(), &[0x33; 40]].concat()
+	}
+
+	/// Builds a version 1 VAA of a message.
+	fn observation(emitter_chain_id: u16, emitter: B256, payload: &[u8]) -> Vaa {
+		let mut bytes = header(1, &[0]);
+		bytes.extend_from_slice(&0u32.to_be_bytes());
+		bytes.extend_from_slice(&0u32.to_be_bytes());
+		bytes.extend_from_slice(&emitter_chain_id.to_be_bytes());
+		bytes.extend_from_slice(emitter.as_slice());
+		bytes.extend_from_slice(&1u64.to_be_bytes());
+		bytes.push(1);
+		bytes.extend_from_slice(payload);
+		Vaa::parse(&bytes).unwrap()
+	}
+
+	#[test]
+	fn test_message_carries_fill() {
+		let settler = B256::repeat_byte(1);
+		let solver = B256::repeat_byte(2);
+		let order_id = B256::repeat_byte(3);
+		let other = fill(solver, B256::repeat_byte(4));
+
+		let payload = message(settler, &[other.clone(), fill(solver, order_id)]);
+		assert!(message_carries_fill(&payload, settler, solver, order_id));
+		// Another application, another solver or another order
+		assert!(!message_carries_fill(
+			&payload,
+			B256::repeat_byte(9),
+			solver,
+			order_id
+		));
+		assert!(!message_carries_fill(
+			&payload,
+			settler,
+			B256::repeat_byte(9),
+			order_id
+		));
+		assert!(!message_carries_fill(
+			&message(settler, &[other]),
+			settler,
+			solver,
+			order_id
+		));
+		// Truncated messages
+		assert!(!message_carries_fill(
+			&payload[..payload.len() - 1],
+			settler,
+			solver,
+			order_id
+		));
+	}
+
+	#[test]
+	fn test_check_fill_message() {
+		let settler = B256::repeat_byte(1);
+		let solver = B256::repeat_byte(2);
+		let order_id = B256::repeat_byte(3);
+		let oracle = B256::repeat_byte(5);
+		let payload = message(settler, &[fill(solver, order_id)]);
+
+		let vaa = observation(24, oracle, &payload);
+		assert!(check_fill_message(&vaa, 24, &[oracle], settler, solver, order_id).is_ok());
+		// Emitted from another chain or by another contract
+		assert!(check_fill_message(&vaa, 2, &[oracle], settler, solver, order_id).is_err());
+		let vaa = observation(24, B256::repeat_byte(6), &payload);
+		assert!(check_fill_message(&vaa, 24, &[oracle], settler, solver, order_id).is_err());
+		// Carrying another order
+		let vaa = observation(24, oracle, &payload);
+		assert!(
+			check_fill_message(&vaa, 24, &[oracle], settler, solver, B256::repeat_byte(4)).is_err()
+		);
+	}
+}
@@ -6,9 +6,9 @@
 
 use async_trait::async_trait;
 use solver_types::{
-	oracle::{OracleInfo, OracleRoutes},
-	Address, ConfigSchema, FillProof, ImplementationRegistry, NetworksConfig, Order,
-	TransactionHash,
+	oracle::{OracleInfo, OracleRouteSource, OracleRoutes},
+	Address, ChainId, ConfigSchema, FillProof, ImplementationRegistry, NetworksConfig, Order,
+	Transaction, TransactionHash, TransactionTrace, ZkFillProof,
 };
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -18,11 +18,40 @@ use thiserror::Error;
 /// Re-export implementations
 pub mod implementations {
 	pub mod direct;
+	pub mod optimistic;
+	pub mod wormhole;
 }
 
 /// Common utilities for settlement implementations
 pub mod utils;
 
+/// Oracle health monitoring
+pub mod health;
+
+pub use health::{OracleHealth, OracleHealthChecker, OracleHealthMap};
+
+/// Circuit breaker on the claim success rate
+pub mod circuit_breaker;
+
+pub use circuit_breaker::{ClaimCircuitStatus, ClaimSuccessRateCircuitBreaker};
+
+/// Caching of fill proofs
+pub mod proof_cache;
+
+pub use proof_cache::ProofCache;
+
+/// Relay of fill proofs over cross-chain messaging
+pub mod relay;
+
+pub use relay::{ProofRelayer, RelayState};
+
+/// Dynamic oracle route discovery
+pub mod route_discovery;
+
+pub use route_discovery::{OracleRouteDiscovery, OracleRouteMap, RouteDiscoveryTarget};
+
+pub use utils::proof_registry::{ProofVersionRegistry, PROOF_VERSION_1};
+
 /// Errors that can occur during settlement operations.
 #[derive(Debug, Error)]
 pub enum SettlementError {
@@ -35,6 +64,19 @@ pub enum SettlementError {
 	/// Error that occurs when a fill doesn't match order requirements.
 	#[error("Fill does not match order requirements")]
 	FillMismatch,
+	/// Error that occurs when the chain cannot be reached.
+	#[error("Network error: {0}")]
+	Network(String),
+}
+
+/// Trait for estimating the gas of transactions without submitting them.
+///
+/// Implemented on top of the delivery service so that settlement
+/// implementations can check claims before they are submitted.
+#[async_trait]
+pub trait GasEstimator: Send + Sync {
+	/// Executes the transaction against the current chain state and returns its trace.
+	async fn estimate_gas(&self, tx: &Transaction) -> Result<TransactionTrace, SettlementError>;
 }
 
 /// Strategy for selecting oracles when multiple are available
@@ -58,13 +100,34 @@ impl Default for OracleSelectionStrategy {
 #[derive(Debug, Clone)]
 pub struct OracleConfig {
 	/// Input oracle addresses by chain ID (multiple per chain possible)
-	pub input_oracles: HashMap<u64, Vec<Address>>,
+	pub input_oracles: HashMap<ChainId, Vec<Address>>,
 	/// Output oracle addresses by chain ID (multiple per chain possible)
-	pub output_oracles: HashMap<u64, Vec<Address>>,
-	/// Valid routes: input_chain -> [output_chains]
-	pub routes: HashMap<u64, Vec<u64>>,
+	pub output_oracles: HashMap<ChainId, Vec<Address>>,
+	/// Valid routes: input_chain -> [output_chains], refreshed by route discovery
+	pub routes: OracleRouteMap,
 	/// Strategy for selecting oracles when multiple are available
 	pub selection_strategy: OracleSelectionStrategy,
+	/// Health of configured oracles, updated by the oracle health checker
+	pub health: OracleHealthMap,
+}
+
+impl OracleConfig {
+	/// Returns true unless the oracle has been marked unhealthy on the chain.
+	pub fn is_oracle_healthy(&self, chain_id: u64, oracle: &Address) -> bool {
+		self.health
+			.get(&(chain_id, oracle.clone()))
+			.is_none_or(|health| *health != OracleHealth::Unhealthy)
+	}
+
+	/// Returns the output chains currently reachable from an input chain.
+	pub fn route_outputs(&self, input_chain: u64) -> Vec<u64> {
+		self.routes
+			.read()
+			.unwrap_or_else(|e| e.into_inner())
+			.get(&input_chain)
+			.cloned()
+			.unwrap_or_default()
+	}
 }
 
 /// Trait defining the interface for settlement mechanisms.
@@ -80,9 +143,8 @@ pub trait SettlementInterface: Send + Sync {
 	/// Check if a specific route is supported
 	fn is_route_supported(&self, input_chain: u64, output_chain: u64) -> bool {
 		self.oracle_config()
-			.routes
-			.get(&input_chain)
-			.is_some_and(|outputs| outputs.contains(&output_chain))
+			.route_outputs(input_chain)
+			.contains(&output_chain)
 	}
 
 	/// Check if a specific input oracle is supported on a chain
@@ -121,8 +183,13 @@ pub trait SettlementInterface: Send + Sync {
 
 	/// Select an oracle from available options based on the configured strategy
 	/// If selection_context is None, uses an internal counter for round-robin/random
+	///
+	/// Oracles marked unhealthy on `chain_id` are skipped: if the strategy
+	/// picks one, the next healthy oracle in the list is used instead. Returns
+	/// None if all are unhealthy.
 	fn select_oracle(
 		&self,
+		chain_id: u64,
 		oracles: &[Address],
 		selection_context: Option<u64>,
 	) -> Option<Address> {
@@ -130,15 +197,14 @@ pub trait SettlementInterface: Send + Sync {
 			return None;
 		}
 
-		match self.oracle_config().selection_strategy {
-			OracleSelectionStrategy::First => oracles.first().cloned(),
+		let index = match self.oracle_config().selection_strategy {
+			OracleSelectionStrategy::First => 0,
 			OracleSelectionStrategy::RoundRobin => {
 				// For round-robin, we need a context value. If none provided,
 				// default to 0 (will select first oracle). Callers should provide
 				// proper context (e.g., order nonce) for deterministic distribution.
 				let context = selection_context.unwrap_or(0);
-				let index = (context as usize) % oracles.len();
-				oracles.get(index).cloned()
+				(context as usize) % oracles.len()
 			},
 			OracleSelectionStrategy::Random => {
 				use std::collections::hash_map::RandomState;
@@ -151,10 +217,15 @@ pub trait SettlementInterface: Send + Sync {
 						.unwrap_or(0)
 				});
 
-				let index = (RandomState::new().hash_one(context) as usize) % oracles.len();
-				oracles.get(index).cloned()
+				(RandomState::new().hash_one(context) as usize) % oracles.len()
 			},
-		}
+		};
+
+		// Fall through to the next healthy oracle if the selected one is unhealthy
+		(0..oracles.len())
+			.map(|offset| &oracles[(index + offset) % oracles.len()])
+			.find(|oracle| self.oracle_config().is_oracle_healthy(chain_id, oracle))
+			.cloned()
 	}
 
 	/// Returns the configuration schema for this settlement implementation.
@@ -171,12 +242,31 @@ pub trait SettlementInterface: Send + Sync {
 	/// 2. Parse logs/events to extract fill details
 	/// 3. Verify the fill satisfies the order requirements
 	/// 4. Build a FillProof containing all data needed for claiming
+	///
+	/// Implementations that prove fills with zero-knowledge proofs return
+	/// `FillProof::Zk`; all others return `FillProof::Standard`.
 	async fn get_attestation(
 		&self,
 		order: &Order,
 		tx_hash: &TransactionHash,
 	) -> Result<FillProof, SettlementError>;
 
+	/// Gets attestation data for several filled orders.
+	///
+	/// Returns one result per order, in the same order. The default
+	/// implementation calls `get_attestation` for each order in turn;
+	/// implementations override it to batch RPC requests.
+	async fn get_attestations_batch(
+		&self,
+		orders: &[(&Order, &TransactionHash)],
+	) -> Vec<Result<FillProof, SettlementError>> {
+		let mut proofs = Vec::with_capacity(orders.len());
+		for (order, tx_hash) in orders {
+			proofs.push(self.get_attestation(order, tx_hash).await);
+		}
+		proofs
+	}
+
 	/// Checks if the solver can claim rewards for this fill.
 	///
 	/// This method should check on-chain conditions such as:
@@ -185,6 +275,68 @@ pub trait SettlementInterface: Send + Sync {
 	/// - Solver permissions
 	/// - Reward availability
 	async fn can_claim(&self, order: &Order, fill_proof: &FillProof) -> bool;
+
+	/// Estimates the gas of a claim transaction before it is submitted.
+	///
+	/// Returns the estimated gas, or a validation error carrying the revert
+	/// reason if the claim would revert. Failures to reach the chain are
+	/// returned as network errors, as they say nothing about the claim.
+	async fn pre_validate_claim(
+		&self,
+		order: &Order,
+		_fill_proof: &FillProof,
+		claim_tx: &Transaction,
+		estimator: &dyn GasEstimator,
+	) -> Result<u64, SettlementError> {
+		let trace = estimator.estimate_gas(claim_tx).await?;
+		if !trace.success {
+			return Err(SettlementError::ValidationFailed(format!(
+				"Claim for order {} would revert: {}",
+				order.id,
+				trace
+					.revert_reason
+					.unwrap_or_else(|| "unknown reason".to_string())
+			)));
+		}
+		Ok(trace.gas_used.unwrap_or_default())
+	}
+
+	/// Checks whether the fill for this order has been challenged.
+	///
+	/// Settlement mechanisms without a challenge period never report challenges.
+	/// A challenged fill can never be claimed.
+	async fn is_challenged(&self, _order: &Order) -> bool {
+		false
+	}
+
+	/// Returns the fill proof version of the deployed settlement contract.
+	///
+	/// Implementations whose contract has been upgraded to a new attestation
+	/// format return the version registered for that format.
+	fn proof_version(&self) -> u8 {
+		PROOF_VERSION_1
+	}
+
+	/// Checks whether this settlement mechanism can verify the given zero-knowledge proof.
+	///
+	/// Implementations without zero-knowledge support never accept such proofs.
+	fn can_verify_zk_proof(&self, _proof: &ZkFillProof) -> bool {
+		false
+	}
+
+	/// Generates a single transaction claiming rewards for several filled orders.
+	///
+	/// `proofs` holds the fill proof for each order, in the same order.
+	/// Settlement mechanisms without batch claim support return an error.
+	async fn generate_batch_claim_transaction(
+		&self,
+		_orders: &[Order],
+		_proofs: &[FillProof],
+	) -> Result<Transaction, SettlementError> {
+		Err(SettlementError::ValidationFailed(
+			"Batch claims are not supported by this settlement".to_string(),
+		))
+	}
 }
 
 /// Type alias for settlement factory functions.
@@ -205,9 +357,33 @@ pub trait SettlementRegistry: ImplementationRegistry<Factory = SettlementFactory
 /// Returns a vector of (name, factory) tuples for all available settlement implementations.
 /// This is used by the factory registry to automatically register all implementations.
 pub fn get_all_implementations() -> Vec<(&'static str, SettlementFactory)> {
-	use implementations::direct;
+	use implementations::{direct, optimistic, wormhole};
 
-	vec![(direct::Registry::NAME, direct::Registry::factory())]
+	vec![
+		(direct::Registry::NAME, direct::Registry::factory()),
+		(optimistic::Registry::NAME, optimistic::Registry::factory()),
+		(wormhole::Registry::NAME, wormhole::Registry::factory()),
+	]
+}
+
+/// Get the configuration schemas of all registered settlement implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_implementations`].
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{direct, optimistic, wormhole};
+
+	vec![
+		(direct::Registry::NAME, direct::Registry::config_schema()),
+		(
+			optimistic::Registry::NAME,
+			optimistic::Registry::config_schema(),
+		),
+		(
+			wormhole::Registry::NAME,
+			wormhole::Registry::config_schema(),
+		),
+	]
 }
 
 /// Service managing settlement implementations.
@@ -217,6 +393,14 @@ pub struct SettlementService {
 	implementations: HashMap<String, Box<dyn SettlementInterface>>,
 	/// Track order count for round-robin selection
 	selection_counter: Arc<AtomicU64>,
+	/// Disables auto-claiming of implementations whose claims keep failing.
+	claim_circuit_breaker: ClaimSuccessRateCircuitBreaker,
+	/// Fill proofs already attested, by order ID.
+	proof_cache: ProofCache,
+	/// Relays fill proofs to the origin chain, if configured.
+	proof_relayer: Option<ProofRelayer>,
+	/// Formats of the fill proof versions.
+	proof_versions: ProofVersionRegistry,
 }
 
 impl SettlementService {
@@ -228,9 +412,61 @@ impl SettlementService {
 		Self {
 			implementations,
 			selection_counter: Arc::new(AtomicU64::new(0)),
+			claim_circuit_breaker: ClaimSuccessRateCircuitBreaker::default(),
+			proof_cache: ProofCache::default(),
+			proof_relayer: None,
+			proof_versions: ProofVersionRegistry::default(),
 		}
 	}
 
+	/// Sets the formats of the fill proof versions.
+	pub fn with_proof_versions(mut self, proof_versions: ProofVersionRegistry) -> Self {
+		self.proof_versions = proof_versions;
+		self
+	}
+
+	/// Sets the cache of fill proofs.
+	pub fn with_proof_cache(mut self, proof_cache: ProofCache) -> Self {
+		self.proof_cache = proof_cache;
+		self
+	}
+
+	/// Relays fill proofs to the origin chain and makes orders claimable
+	/// once their relayed proof has been received.
+	pub fn with_proof_relayer(mut self, proof_relayer: ProofRelayer) -> Self {
+		self.proof_relayer = Some(proof_relayer);
+		self
+	}
+
+	/// Returns the relayer of fill proofs, if configured.
+	pub fn proof_relayer(&self) -> Option<&ProofRelayer> {
+		self.proof_relayer.as_ref()
+	}
+
+	/// Drops the cached fill proof of an order, once the order reached a
+	/// terminal state.
+	pub fn invalidate_proof(&self, order_id: &str) {
+		self.proof_cache.invalidate(order_id);
+	}
+
+	/// Sets the circuit breaker on the claim success rate.
+	pub fn with_claim_circuit_breaker(mut self, breaker: ClaimSuccessRateCircuitBreaker) -> Self {
+		self.claim_circuit_breaker = breaker;
+		self
+	}
+
+	/// Returns the circuit breaker on the claim success rate.
+	pub fn claim_circuit_breaker(&self) -> &ClaimSuccessRateCircuitBreaker {
+		&self.claim_circuit_breaker
+	}
+
+	/// Returns the names of all settlement implementations.
+	pub fn implementation_names(&self) -> Vec<&str> {
+		let mut names: Vec<&str> = self.implementations.keys().map(String::as_str).collect();
+		names.sort();
+		names
+	}
+
 	/// Gets a specific settlement implementation by name.
 	///
 	/// Returns None if the implementation doesn't exist.
@@ -249,23 +485,21 @@ impl SettlementService {
 			for (input_chain, input_oracles) in &config.input_oracles {
 				for input_oracle in input_oracles {
 					let input_info = OracleInfo {
-						chain_id: *input_chain,
+						chain_id: input_chain.as_u64(),
 						oracle: input_oracle.clone(),
 					};
 
 					let mut valid_outputs = Vec::new();
 
 					// Add all valid output destinations
-					if let Some(dest_chains) = config.routes.get(input_chain) {
-						for dest_chain in dest_chains {
-							// Add all output oracles on that destination
-							if let Some(output_oracles) = config.output_oracles.get(dest_chain) {
-								for output_oracle in output_oracles {
-									valid_outputs.push(OracleInfo {
-										chain_id: *dest_chain,
-										oracle: output_oracle.clone(),
-									});
-								}
+					for dest_chain in config.route_outputs(input_chain.as_u64()) {
+						// Add all output oracles on that destination
+						if let Some(output_oracles) = config.output_oracles.get(&dest_chain) {
+							for output_oracle in output_oracles {
+								valid_outputs.push(OracleInfo {
+									chain_id: dest_chain,
+									oracle: output_oracle.clone(),
+								});
 							}
 						}
 					}
@@ -288,13 +522,25 @@ impl SettlementService {
 		oracle_address: &Address,
 		is_input: bool,
 	) -> Result<&dyn SettlementInterface, SettlementError> {
-		for settlement in self.implementations.values() {
-			if is_input {
-				if settlement.is_input_oracle_supported(chain_id, oracle_address) {
-					return Ok(settlement.as_ref());
-				}
-			} else if settlement.is_output_oracle_supported(chain_id, oracle_address) {
-				return Ok(settlement.as_ref());
+		self.get_named_settlement_for_oracle(chain_id, oracle_address, is_input)
+			.map(|(_, settlement)| settlement)
+	}
+
+	/// Find settlement by oracle address, together with its implementation name.
+	fn get_named_settlement_for_oracle(
+		&self,
+		chain_id: u64,
+		oracle_address: &Address,
+		is_input: bool,
+	) -> Result<(&str, &dyn SettlementInterface), SettlementError> {
+		for (name, settlement) in &self.implementations {
+			let supported = if is_input {
+				settlement.is_input_oracle_supported(chain_id, oracle_address)
+			} else {
+				settlement.is_output_oracle_supported(chain_id, oracle_address)
+			};
+			if supported {
+				return Ok((name.as_str(), settlement.as_ref()));
 			}
 		}
 		Err(SettlementError::ValidationFailed(format!(
@@ -314,6 +560,16 @@ impl SettlementService {
 		&self,
 		order: &Order,
 	) -> Result<&dyn SettlementInterface, SettlementError> {
+		self.find_named_settlement_for_order(order)
+			.map(|(_, settlement)| settlement)
+	}
+
+	/// Find settlement for an order based on its oracles, together with its
+	/// implementation name.
+	pub fn find_named_settlement_for_order(
+		&self,
+		order: &Order,
+	) -> Result<(&str, &dyn SettlementInterface), SettlementError> {
 		// Parse order data to get input oracle
 		let order_data: solver_types::Eip7683OrderData =
 			serde_json::from_value(order.data.to_owned()).map_err(|e| {
@@ -325,7 +581,74 @@ impl SettlementService {
 		let origin_chain = order_data.origin_chain_id.to::<u64>();
 
 		// Find settlement by input oracle
-		self.get_settlement_for_oracle(origin_chain, &input_oracle, true)
+		self.get_named_settlement_for_oracle(origin_chain, &input_oracle, true)
+	}
+
+	/// Creates a health checker probing every oracle configured in any implementation.
+	///
+	/// Probe results are recorded in each implementation's oracle health map,
+	/// which `select_oracle` consults to skip unhealthy oracles.
+	pub fn oracle_health_checker(
+		&self,
+		networks: &NetworksConfig,
+	) -> Result<OracleHealthChecker, SettlementError> {
+		let mut targets = Vec::new();
+
+		for settlement in self.implementations.values() {
+			let config = settlement.oracle_config();
+			for (chain_id, oracles) in config.input_oracles.iter().chain(&config.output_oracles) {
+				for oracle in oracles {
+					targets.push((chain_id.as_u64(), oracle.clone(), config.health.clone()));
+				}
+			}
+		}
+
+		OracleHealthChecker::new(networks, targets)
+	}
+
+	/// Creates a route discovery task querying the input settler contract of
+	/// every input chain configured in any implementation.
+	///
+	/// Discovered routes replace the routes of each implementation's oracle
+	/// configuration, which `is_route_supported` consults.
+	pub fn route_discovery(
+		&self,
+		networks: &NetworksConfig,
+	) -> Result<OracleRouteDiscovery, SettlementError> {
+		let mut targets = Vec::new();
+
+		for settlement in self.implementations.values() {
+			let config = settlement.oracle_config();
+			for chain_id in config.input_oracles.keys() {
+				let network = networks.get(chain_id).ok_or_else(|| {
+					SettlementError::ValidationFailed(format!(
+						"Network {} not found in configuration",
+						chain_id
+					))
+				})?;
+				if network.input_settler_address.0.len() != 20 {
+					return Err(SettlementError::ValidationFailed(format!(
+						"Invalid input settler address for network {}",
+						chain_id
+					)));
+				}
+
+				targets.push(RouteDiscoveryTarget {
+					chain_id: chain_id.as_u64(),
+					contract: alloy_primitives::Address::from_slice(
+						&network.input_settler_address.0,
+					),
+					output_chains: config
+						.output_oracles
+						.keys()
+						.map(|chain_id| chain_id.as_u64())
+						.collect(),
+					routes: config.routes.clone(),
+				});
+			}
+		}
+
+		OracleRouteDiscovery::new(networks, targets)
 	}
 
 	/// Get any settlement that supports a given chain (for quote generation).
@@ -355,13 +678,13 @@ impl SettlementService {
 		// If only one settlement, use it with oracle selection
 		if available_settlements.len() == 1 {
 			let (settlement, oracles) = &available_settlements[0];
-			let selected_oracle = settlement.select_oracle(oracles, Some(context))?;
+			let selected_oracle = settlement.select_oracle(chain_id, oracles, Some(context))?;
 			return Some((*settlement, selected_oracle));
 		}
 
 		// Multiple settlements - use first one but apply oracle selection
 		let (settlement, oracles) = &available_settlements[0];
-		let selected_oracle = settlement.select_oracle(oracles, Some(context))?;
+		let selected_oracle = settlement.select_oracle(chain_id, oracles, Some(context))?;
 		Some((*settlement, selected_oracle))
 	}
 
@@ -381,16 +704,285 @@ impl SettlementService {
 		order: &Order,
 		tx_hash: &TransactionHash,
 	) -> Result<FillProof, SettlementError> {
+		if let Some(proof) = self.proof_cache.get(&order.id) {
+			return Ok(proof);
+		}
 		let implementation = self.find_settlement_for_order(order)?;
-		implementation.get_attestation(order, tx_hash).await
+		let proof = implementation.get_attestation(order, tx_hash).await?;
+		let proof = check_proof_supported(implementation, proof)?;
+		let proof = self
+			.proof_versions
+			.tag(proof, implementation.proof_version())?;
+		self.proof_cache.insert(&order.id, proof.clone());
+		Ok(proof)
+	}
+
+	/// Gets attestations for several filled orders.
+	///
+	/// Orders are grouped by settlement implementation so that each
+	/// implementation can batch its requests. Returns one result per order,
+	/// in the same order.
+	pub async fn get_attestations_batch(
+		&self,
+		orders: &[(&Order, &TransactionHash)],
+	) -> Vec<Result<FillProof, SettlementError>> {
+		let mut results: Vec<Option<Result<FillProof, SettlementError>>> =
+			orders.iter().map(|_| None).collect();
+		let mut groups: Vec<(&dyn SettlementInterface, Vec<usize>)> = Vec::new();
+
+		for (index, (order, _)) in orders.iter().enumerate() {
+			if let Some(proof) = self.proof_cache.get(&order.id) {
+				results[index] = Some(Ok(proof));
+				continue;
+			}
+			match self.find_settlement_for_order(order) {
+				Ok(implementation) => {
+					match groups
+						.iter_mut()
+						.find(|(existing, _)| std::ptr::addr_eq(*existing, implementation))
+					{
+						Some((_, indices)) => indices.push(index),
+						None => groups.push((implementation, vec![index])),
+					}
+				},
+				Err(e) => results[index] = Some(Err(e)),
+			}
+		}
+
+		for (implementation, indices) in groups {
+			let batch: Vec<(&Order, &TransactionHash)> =
+				indices.iter().map(|&index| orders[index]).collect();
+			let proofs = implementation.get_attestations_batch(&batch).await;
+			for (index, proof) in indices.into_iter().zip(proofs) {
+				let proof = proof
+					.and_then(|proof| check_proof_supported(implementation, proof))
+					.and_then(|proof| {
+						self.proof_versions
+							.tag(proof, implementation.proof_version())
+					});
+				if let Ok(proof) = &proof {
+					self.proof_cache.insert(&orders[index].0.id, proof.clone());
+				}
+				results[index] = Some(proof);
+			}
+		}
+
+		results
+			.into_iter()
+			.map(|result| {
+				result.unwrap_or_else(|| {
+					Err(SettlementError::ValidationFailed(
+						"No attestation returned".to_string(),
+					))
+				})
+			})
+			.collect()
+	}
+
+	/// Generates a batch claim transaction using the settlement implementation of the first order.
+	///
+	/// All orders in the batch are expected to share the same settlement mechanism.
+	pub async fn generate_batch_claim_transaction(
+		&self,
+		orders: &[Order],
+		proofs: &[FillProof],
+	) -> Result<Transaction, SettlementError> {
+		let order = orders.first().ok_or_else(|| {
+			SettlementError::ValidationFailed("Batch claim requires at least one order".to_string())
+		})?;
+		let implementation = self.find_settlement_for_order(order)?;
+		implementation
+			.generate_batch_claim_transaction(orders, proofs)
+			.await
 	}
 
 	/// Checks if an order can be claimed using the appropriate settlement implementation.
+	///
+	/// The proof is decoded with the format of its version first, and orders
+	/// whose proof cannot be decoded are not claimable. With a proof relayer,
+	/// the messenger of the order's origin chain must also have received the
+	/// relayed proof.
 	pub async fn can_claim(&self, order: &Order, fill_proof: &FillProof) -> bool {
+		let fill_proof = match self.proof_versions.decode(fill_proof) {
+			Ok(proof) => proof,
+			Err(e) => {
+				tracing::warn!(order_id = %order.id, error = %e, "Failed to decode fill proof");
+				return false;
+			},
+		};
+		let fill_proof = &fill_proof;
+		let Ok(implementation) = self.find_settlement_for_order(order) else {
+			return false;
+		};
+		if !implementation.can_claim(order, fill_proof).await {
+			return false;
+		}
+		match &self.proof_relayer {
+			Some(relayer) => match relayer.is_relayed(order, fill_proof).await {
+				Ok(relayed) => relayed,
+				Err(e) => {
+					tracing::debug!(order_id = %order.id, error = %e, "Failed to check relayed proof");
+					false
+				},
+			},
+			None => true,
+		}
+	}
+
+	/// Estimates the gas of a claim transaction using the appropriate settlement implementation.
+	pub async fn pre_validate_claim(
+		&self,
+		order: &Order,
+		fill_proof: &FillProof,
+		claim_tx: &Transaction,
+		estimator: &dyn GasEstimator,
+	) -> Result<u64, SettlementError> {
+		self.find_settlement_for_order(order)?
+			.pre_validate_claim(order, fill_proof, claim_tx, estimator)
+			.await
+	}
+
+	/// Checks if the fill for an order has been challenged using the appropriate settlement implementation.
+	pub async fn is_challenged(&self, order: &Order) -> bool {
 		if let Ok(implementation) = self.find_settlement_for_order(order) {
-			implementation.can_claim(order, fill_proof).await
+			implementation.is_challenged(order).await
 		} else {
 			false
 		}
 	}
 }
+
+/// Routes are rebuilt on each read, so that discovered routes take effect
+/// immediately.
+impl OracleRouteSource for SettlementService {
+	fn oracle_routes(&self) -> OracleRoutes {
+		self.build_oracle_routes()
+	}
+}
+
+/// Rejects zero-knowledge proofs the implementation cannot verify.
+fn check_proof_supported(
+	implementation: &dyn SettlementInterface,
+	proof: FillProof,
+) -> Result<FillProof, SettlementError> {
+	if let FillProof::Zk(ref zk_proof) = proof {
+		if !implementation.can_verify_zk_proof(zk_proof) {
+			return Err(SettlementError::ValidationFailed(format!(
+				"Unsupported {} proof for verifier {}",
+				zk_proof.proving_scheme, zk_proof.verifier_contract
+			)));
+		}
+	}
+
+	Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct TestSettlement {
+		oracle_config: OracleConfig,
+	}
+
+	#[async_trait]
+	impl SettlementInterface for TestSettlement {
+		fn oracle_config(&self) -> &OracleConfig {
+			&self.oracle_config
+		}
+
+		fn config_schema(&self) -> Box<dyn ConfigSchema> {
+			unimplemented!()
+		}
+
+		async fn get_attestation(
+			&self,
+			_order: &Order,
+			_tx_hash: &TransactionHash,
+		) -> Result<FillProof, SettlementError> {
+			unimplemented!()
+		}
+
+		async fn can_claim(&self, _order: &Order, _fill_proof: &FillProof) -> bool {
+			false
+		}
+	}
+
+	#[test]
+	fn test_select_oracle_skips_unhealthy() {
+		let oracles = vec![Address(vec![1; 20]), Address(vec![2; 20])];
+		let settlement = TestSettlement {
+			oracle_config: OracleConfig {
+				input_oracles: HashMap::new(),
+				output_oracles: HashMap::new(),
+				routes: Default::default(),
+				selection_strategy: OracleSelectionStrategy::First,
+				health: Default::default(),
+			},
+		};
+
+		assert_eq!(
+			settlement.select_oracle(1, &oracles, None),
+			Some(oracles[0].clone())
+		);
+
+		// Health is tracked per chain
+		let health = &settlement.oracle_config.health;
+		health.insert((10, oracles[0].clone()), OracleHealth::Unhealthy);
+		assert_eq!(
+			settlement.select_oracle(1, &oracles, None),
+			Some(oracles[0].clone())
+		);
+
+		health.insert((1, oracles[0].clone()), OracleHealth::Unhealthy);
+		assert_eq!(
+			settlement.select_oracle(1, &oracles, None),
+			Some(oracles[1].clone())
+		);
+
+		health.insert((1, oracles[1].clone()), OracleHealth::Unhealthy);
+		assert_eq!(settlement.select_oracle(1, &oracles, None), None);
+
+		health.insert((1, oracles[0].clone()), OracleHealth::Healthy);
+		assert_eq!(
+			settlement.select_oracle(1, &oracles, Some(1)),
+			Some(oracles[0].clone())
+		);
+	}
+
+	#[test]
+	fn test_oracle_routes_follow_discovered_routes() {
+		let (input, output) = (Address(vec![1; 20]), Address(vec![2; 20]));
+		let routes: OracleRouteMap = Default::default();
+		routes.write().unwrap().insert(1, vec![10]);
+		let settlement = TestSettlement {
+			oracle_config: OracleConfig {
+				input_oracles: HashMap::from([(ChainId(1), vec![input.clone()])]),
+				output_oracles: HashMap::from([(ChainId(10), vec![output.clone()])]),
+				routes: routes.clone(),
+				selection_strategy: OracleSelectionStrategy::First,
+				health: Default::default(),
+			},
+		};
+		let service: Arc<dyn OracleRouteSource> =
+			Arc::new(SettlementService::new(HashMap::from([(
+				"test".to_string(),
+				Box::new(settlement) as Box<dyn SettlementInterface>,
+			)])));
+		let input_info = OracleInfo {
+			chain_id: 1,
+			oracle: input,
+		};
+		assert_eq!(
+			service.oracle_routes().supported_routes.get(&input_info),
+			Some(&vec![OracleInfo {
+				chain_id: 10,
+				oracle: output,
+			}])
+		);
+
+		// Routes removed by discovery are no longer valid
+		routes.write().unwrap().insert(1, Vec::new());
+		assert!(service.oracle_routes().supported_routes.is_empty());
+	}
+}
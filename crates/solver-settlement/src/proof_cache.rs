@@ -0,0 +1,96 @@
+//! Cache of fill proofs.
+//!
+//! Attestations of the same fill may be requested several times, e.g. by
+//! state recovery and by the regular settlement monitoring. Proofs are cached
+//! per order so that repeated requests do not query the oracle contracts
+//! again. Entries expire after a configurable time and are invalidated when
+//! the order reaches a terminal state.
+
+use dashmap::DashMap;
+use solver_types::FillProof;
+use std::time::{Duration, Instant};
+
+/// Default time fill proofs are cached for, in seconds.
+pub const DEFAULT_PROOF_CACHE_TTL_SECONDS: u64 = 3600;
+
+/// Fill proofs by order ID, with the time they were cached.
+pub struct ProofCache {
+	ttl: Duration,
+	proofs: DashMap<String, (Instant, FillProof)>,
+}
+
+impl Default for ProofCache {
+	fn default() -> Self {
+		Self::new(Duration::from_secs(DEFAULT_PROOF_CACHE_TTL_SECONDS))
+	}
+}
+
+impl ProofCache {
+	/// Creates a new cache keeping proofs for `ttl`. A zero TTL disables
+	/// caching.
+	pub fn new(ttl: Duration) -> Self {
+		Self {
+			ttl,
+			proofs: DashMap::new(),
+		}
+	}
+
+	/// Returns the cached proof of an order, unless it expired.
+	pub fn get(&self, order_id: &str) -> Option<FillProof> {
+		let entry = self.proofs.get(order_id)?;
+		if entry.0.elapsed() < self.ttl {
+			return Some(entry.1.clone());
+		}
+		drop(entry);
+		self.proofs.remove(order_id);
+		None
+	}
+
+	/// Caches the proof of an order.
+	pub fn insert(&self, order_id: &str, proof: FillProof) {
+		if !self.ttl.is_zero() {
+			self.proofs
+				.insert(order_id.to_string(), (Instant::now(), proof));
+		}
+	}
+
+	/// Removes the cached proof of an order.
+	pub fn invalidate(&self, order_id: &str) {
+		self.proofs.remove(order_id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{StandardFillProof, TransactionHash};
+
+	fn proof() -> FillProof {
+		FillProof::Standard(StandardFillProof {
+			tx_hash: TransactionHash(vec![1; 32]),
+			block_number: 1,
+			attestation_data: None,
+			filled_timestamp: 0,
+			oracle_address: "0x0".to_string(),
+			proof_version: 1,
+		})
+	}
+
+	#[test]
+	fn test_cached_proofs_expire_and_invalidate() {
+		let cache = ProofCache::new(Duration::from_millis(50));
+		cache.insert("order", proof());
+		assert!(cache.get("order").is_some());
+		cache.invalidate("order");
+		assert!(cache.get("order").is_none());
+
+		cache.insert("order", proof());
+		std::thread::sleep(Duration::from_millis(60));
+		assert!(cache.get("order").is_none());
+
+		// A zero TTL disables caching
+		let disabled = ProofCache::new(Duration::ZERO);
+		disabled.insert("order", proof());
+		assert!(disabled.get("order").is_none());
+	}
+}
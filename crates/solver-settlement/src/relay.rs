@@ -0,0 +1,583 @@
+//! Relay of fill proofs to the origin chain over cross-chain messaging.
+//!
+//! Once a fill is attested, its proof is encoded as a message and sent from
+//! the destination chain through the configured protocol, Wormhole or
+//! LayerZero, to a messenger contract on the origin chain. LayerZero
+//! executors deliver the message themselves, while the signed VAA of a
+//! Wormhole message is fetched from the Wormhole API and submitted to the
+//! messenger by the solver. Orders are claimable once the settlement
+//! implementation allows it and the messenger has received the proof.
+
+use crate::implementations::wormhole::{fetch_signed_vaa, LogMessagePublished};
+use crate::SettlementError;
+use alloy_primitives::{hex, keccak256, Address as AlloyAddress, Bytes, FixedBytes, B256, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::TransactionRequest;
+use alloy_sol_types::{sol, SolCall, SolEvent, SolValue};
+use alloy_transport_http::Http;
+use serde::{Deserialize, Serialize};
+use solver_types::oracle::{ProofRelayConfig, RelayProtocol};
+use solver_types::{
+	Address, Eip7683OrderData, FillProof, NetworksConfig, Order, Transaction, TransactionHash,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Timeout of requests to the Wormhole API.
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+sol! {
+	/// Messenger on the origin chain receiving relayed proofs.
+	interface IMessenger {
+		function hasMessage(bytes32 proofHash) external view returns (bool);
+		function receiveMessage(bytes calldata encodedVaa) external;
+	}
+
+	/// Wormhole core bridge subset used to publish proof messages.
+	interface IWormholeCore {
+		function messageFee() external view returns (uint256);
+		function publishMessage(uint32 nonce, bytes memory payload, uint8 consistencyLevel) external payable returns (uint64 sequence);
+	}
+
+	/// LayerZero V2 endpoint subset used to send proof messages.
+	interface ILayerZeroEndpointV2 {
+		struct MessagingParams {
+			uint32 dstEid;
+			bytes32 receiver;
+			bytes message;
+			bytes options;
+			bool payInLzToken;
+		}
+
+		struct MessagingFee {
+			uint256 nativeFee;
+			uint256 lzTokenFee;
+		}
+
+		struct MessagingReceipt {
+			bytes32 guid;
+			uint64 nonce;
+			MessagingFee fee;
+		}
+
+		function quote(MessagingParams calldata params, address sender) external view returns (MessagingFee memory);
+		function send(MessagingParams calldata params, address refundAddress) external payable returns (MessagingReceipt memory);
+	}
+}
+
+/// Progress of the relay of an order's fill proof.
+///
+/// Stored per order so that a proof is sent and delivered at most once,
+/// including across restarts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RelayState {
+	/// Transaction sending the proof from the destination chain.
+	pub send_tx_hash: Option<TransactionHash>,
+	/// Transaction submitting the signed Wormhole VAA to the messenger of
+	/// the origin chain.
+	pub delivery_tx_hash: Option<TransactionHash>,
+}
+
+/// Relays fill proofs to the messenger of the origin chain.
+pub struct ProofRelayer {
+	config: ProofRelayConfig,
+	/// Decoded LayerZero executor options.
+	layerzero_options: Vec<u8>,
+	/// RPC providers of the chains proofs are sent from or received on.
+	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
+	/// HTTP client for the Wormhole API.
+	client: reqwest::Client,
+}
+
+impl ProofRelayer {
+	/// Creates a new ProofRelayer.
+	///
+	/// Fails if the parameters of the configured protocol are missing or a
+	/// chain they reference has no HTTP RPC URL.
+	pub fn new(
+		config: ProofRelayConfig,
+		networks: &NetworksConfig,
+	) -> Result<Self, SettlementError> {
+		let (sending_chains, layerzero_options): (Vec<u64>, Vec<u8>) = match config.relay_protocol {
+			RelayProtocol::Wormhole => {
+				let wormhole = config.wormhole.as_ref().ok_or_else(|| {
+					SettlementError::ValidationFailed(
+						"Wormhole relay requires wormhole parameters".to_string(),
+					)
+				})?;
+				(wormhole.core_bridges.keys().copied().collect(), Vec::new())
+			},
+			RelayProtocol::LayerZero => {
+				let layerzero = config.layerzero.as_ref().ok_or_else(|| {
+					SettlementError::ValidationFailed(
+						"LayerZero relay requires layerzero parameters".to_string(),
+					)
+				})?;
+				let options = hex::decode(&layerzero.options).map_err(|e| {
+					SettlementError::ValidationFailed(format!("Invalid LayerZero options: {}", e))
+				})?;
+				(layerzero.endpoints.keys().copied().collect(), options)
+			},
+		};
+
+		let mut providers = HashMap::new();
+		for chain_id in config.messengers.keys().copied().chain(sending_chains) {
+			if providers.contains_key(&chain_id) {
+				continue;
+			}
+			let http_url = networks
+				.get(&chain_id)
+				.and_then(|network| network.get_http_url())
+				.ok_or_else(|| {
+					SettlementError::ValidationFailed(format!(
+						"No HTTP RPC URL configured for network {}",
+						chain_id
+					))
+				})?;
+			let provider = RootProvider::new_http(http_url.parse().map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Invalid RPC URL for network {}: {}",
+					chain_id, e
+				))
+			})?);
+			providers.insert(chain_id, provider);
+		}
+
+		let client = reqwest::Client::builder()
+			.timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+			.build()
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to build HTTP client: {}", e))
+			})?;
+
+		Ok(Self {
+			config,
+			layerzero_options,
+			providers,
+			client,
+		})
+	}
+
+	/// Builds the transaction sending the proof of an order's fill from its
+	/// destination chain to the messenger of its origin chain.
+	///
+	/// The transaction pays the messaging fee quoted by the protocol.
+	pub async fn relay_transaction(
+		&self,
+		order: &Order,
+		proof: &FillProof,
+	) -> Result<Transaction, SettlementError> {
+		let (origin_chain_id, destination_chain_id) = route(order)?;
+		let message = proof_message(order, proof)?;
+		let messenger = alloy_address(self.messenger(origin_chain_id)?)?;
+
+		let (to, data, value) = match self.config.relay_protocol {
+			RelayProtocol::Wormhole => {
+				let wormhole = self.config.wormhole.as_ref().ok_or_else(|| {
+					SettlementError::ValidationFailed("Wormhole relay not configured".to_string())
+				})?;
+				let core_bridge = chain_address(&wormhole.core_bridges, destination_chain_id)?;
+				let result = self
+					.call(
+						destination_chain_id,
+						core_bridge,
+						IWormholeCore::messageFeeCall {}.abi_encode(),
+					)
+					.await?;
+				let fee = IWormholeCore::messageFeeCall::abi_decode_returns(&result, true)
+					.map_err(|e| {
+						SettlementError::ValidationFailed(format!(
+							"Invalid Wormhole fee response: {}",
+							e
+						))
+					})?
+					._0;
+				let data = IWormholeCore::publishMessageCall {
+					nonce: 0,
+					payload: message.into(),
+					consistencyLevel: wormhole.consistency_level,
+				}
+				.abi_encode();
+				(core_bridge, data, fee)
+			},
+			RelayProtocol::LayerZero => {
+				let layerzero = self.config.layerzero.as_ref().ok_or_else(|| {
+					SettlementError::ValidationFailed("LayerZero relay not configured".to_string())
+				})?;
+				let endpoint = chain_address(&layerzero.endpoints, destination_chain_id)?;
+				let dst_eid = *layerzero.eids.get(&origin_chain_id).ok_or_else(|| {
+					SettlementError::ValidationFailed(format!(
+						"No LayerZero endpoint ID configured for chain {}",
+						origin_chain_id
+					))
+				})?;
+				let solver = alloy_address(&order.solver_address)?;
+				let params = ILayerZeroEndpointV2::MessagingParams {
+					dstEid: dst_eid,
+					receiver: messenger.into_word(),
+					message: message.into(),
+					options: Bytes::from(self.layerzero_options.clone()),
+					payInLzToken: false,
+				};
+				let result = self
+					.call(
+						destination_chain_id,
+						endpoint,
+						ILayerZeroEndpointV2::quoteCall {
+							params: params.clone(),
+							sender: solver,
+						}
+						.abi_encode(),
+					)
+					.await?;
+				let fee = ILayerZeroEndpointV2::quoteCall::abi_decode_returns(&result, true)
+					.map_err(|e| {
+						SettlementError::ValidationFailed(format!(
+							"Invalid LayerZero quote response: {}",
+							e
+						))
+					})?
+					._0;
+				let data = ILayerZeroEndpointV2::sendCall {
+					params,
+					refundAddress: solver,
+				}
+				.abi_encode();
+				(endpoint, data, fee.nativeFee)
+			},
+		};
+
+		Ok(Transaction {
+			to: Some(Address(to.to_vec())),
+			data,
+			value,
+			chain_id: destination_chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		})
+	}
+
+	/// Builds the transaction delivering a sent proof to the messenger of the
+	/// order's origin chain.
+	///
+	/// Returns `None` with LayerZero, whose executors deliver messages. With
+	/// Wormhole, reads the sequence of the message published by the send
+	/// transaction and submits its signed VAA to the messenger; fails while
+	/// the send transaction is pending or the VAA is not signed yet.
+	pub async fn delivery_transaction(
+		&self,
+		order: &Order,
+		send_tx_hash: &TransactionHash,
+	) -> Result<Option<Transaction>, SettlementError> {
+		let RelayProtocol::Wormhole = self.config.relay_protocol else {
+			return Ok(None);
+		};
+		let wormhole = self.config.wormhole.as_ref().ok_or_else(|| {
+			SettlementError::ValidationFailed("Wormhole relay not configured".to_string())
+		})?;
+		let (origin_chain_id, destination_chain_id) = route(order)?;
+		let core_bridge = chain_address(&wormhole.core_bridges, destination_chain_id)?;
+		let messenger = self.messenger(origin_chain_id)?;
+
+		let receipt = self
+			.provider(destination_chain_id)?
+			.get_transaction_receipt(B256::from_slice(&send_tx_hash.0))
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to get receipt: {}", e))
+			})?
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed("Relay transaction not mined yet".to_string())
+			})?;
+		if !receipt.status() {
+			return Err(SettlementError::ValidationFailed(
+				"Relay transaction reverted".to_string(),
+			));
+		}
+		let sequence = receipt
+			.inner
+			.logs()
+			.iter()
+			.filter(|log| log.address() == core_bridge)
+			.find_map(|log| LogMessagePublished::decode_log(&log.inner, true).ok())
+			.map(|event| event.sequence)
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(
+					"No Wormhole message published by relay transaction".to_string(),
+				)
+			})?;
+
+		let vaa = fetch_signed_vaa(
+			&self.client,
+			wormhole.api_url.trim_end_matches('/'),
+			send_tx_hash,
+			sequence,
+		)
+		.await?;
+		Ok(Some(Transaction {
+			to: Some(messenger.clone()),
+			data: IMessenger::receiveMessageCall {
+				encodedVaa: vaa.into(),
+			}
+			.abi_encode(),
+			value: U256::ZERO,
+			chain_id: origin_chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+			memo: None,
+			authorization_list: None,
+		}))
+	}
+
+	/// Checks whether the messenger of the order's origin chain has received
+	/// the relayed proof.
+	pub async fn is_relayed(
+		&self,
+		order: &Order,
+		proof: &FillProof,
+	) -> Result<bool, SettlementError> {
+		let (origin_chain_id, _) = route(order)?;
+		let messenger = alloy_address(self.messenger(origin_chain_id)?)?;
+		let proof_hash = keccak256(proof_message(order, proof)?);
+		let result = self
+			.call(
+				origin_chain_id,
+				messenger,
+				IMessenger::hasMessageCall {
+					proofHash: proof_hash,
+				}
+				.abi_encode(),
+			)
+			.await?;
+		IMessenger::hasMessageCall::abi_decode_returns(&result, true)
+			.map(|received| received._0)
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Invalid messenger response: {}", e))
+			})
+	}
+
+	/// Returns the messenger of an origin chain.
+	fn messenger(&self, chain_id: u64) -> Result<&Address, SettlementError> {
+		self.config.messengers.get(&chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No relay messenger configured for chain {}",
+				chain_id
+			))
+		})
+	}
+
+	/// Returns the provider of a chain.
+	fn provider(
+		&self,
+		chain_id: u64,
+	) -> Result<&RootProvider<Http<reqwest::Client>>, SettlementError> {
+		self.providers.get(&chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No provider configured for chain {}",
+				chain_id
+			))
+		})
+	}
+
+	/// Executes a read-only call on a chain.
+	async fn call(
+		&self,
+		chain_id: u64,
+		to: AlloyAddress,
+		data: Vec<u8>,
+	) -> Result<Bytes, SettlementError> {
+		self.provider(chain_id)?
+			.call(&TransactionRequest::default().to(to).input(data.into()))
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Relay call failed on chain {}: {}",
+					chain_id, e
+				))
+			})
+	}
+}
+
+/// Returns the origin and destination chains of an order.
+fn route(order: &Order) -> Result<(u64, u64), SettlementError> {
+	let origin_chain_id = *order
+		.input_chain_ids
+		.first()
+		.ok_or_else(|| SettlementError::ValidationFailed("No input chains in order".to_string()))?;
+	let destination_chain_id = *order.output_chain_ids.first().ok_or_else(|| {
+		SettlementError::ValidationFailed("No output chains in order".to_string())
+	})?;
+	Ok((origin_chain_id, destination_chain_id))
+}
+
+/// Encodes the relayed message of an order's fill proof.
+fn proof_message(order: &Order, proof: &FillProof) -> Result<Vec<u8>, SettlementError> {
+	let (_, destination_chain_id) = route(order)?;
+	let order_data: Eip7683OrderData = serde_json::from_value(order.data.clone()).map_err(|e| {
+		SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+	})?;
+	Ok(encode_proof_message(
+		order_data.order_id,
+		destination_chain_id,
+		proof,
+	))
+}
+
+/// Encodes a fill proof as
+/// `abi.encode(orderId, fillTxHash, destinationChainId, blockNumber, filledTimestamp, proofData)`.
+///
+/// The proof data is the oracle attestation of standard proofs and the proof
+/// bytes of zero-knowledge proofs. The messenger identifies the message by
+/// its keccak256 hash.
+pub fn encode_proof_message(
+	order_id: [u8; 32],
+	destination_chain_id: u64,
+	proof: &FillProof,
+) -> Vec<u8> {
+	let proof_data = match proof {
+		FillProof::Standard(proof) => proof.attestation_data.clone().unwrap_or_default(),
+		FillProof::Zk(proof) => proof.proof_bytes.clone(),
+	};
+	(
+		FixedBytes::<32>::from(order_id),
+		B256::from_slice(&proof.tx_hash().0),
+		U256::from(destination_chain_id),
+		U256::from(proof.block_number()),
+		U256::from(proof.filled_timestamp()),
+		Bytes::from(proof_data),
+	)
+		.abi_encode_params()
+}
+
+/// Returns the address configured for a chain.
+fn chain_address(
+	addresses: &HashMap<u64, Address>,
+	chain_id: u64,
+) -> Result<AlloyAddress, SettlementError> {
+	let address = addresses.get(&chain_id).ok_or_else(|| {
+		SettlementError::ValidationFailed(format!(
+			"No relay contract configured for chain {}",
+			chain_id
+		))
+	})?;
+	alloy_address(address)
+}
+
+/// Converts an address to its alloy representation.
+fn alloy_address(address: &Address) -> Result<AlloyAddress, SettlementError> {
+	if address.0.len() != 20 {
+		return Err(SettlementError::ValidationFailed(format!(
+			"Invalid address length {}",
+			address.0.len()
+		)));
+	}
+	Ok(AlloyAddress::from_slice(&address.0))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::networks::RpcEndpoint;
+	use solver_types::oracle::LayerZeroRelayConfig;
+	use solver_types::{ChainId, NetworkConfig, OrderStatus, StandardFillProof};
+
+	fn proof(attestation: u8) -> FillProof {
+		FillProof::Standard(StandardFillProof {
+			tx_hash: TransactionHash(vec![1u8; 32]),
+			block_number: 42,
+			attestation_data: Some(vec![attestation; 4]),
+			filled_timestamp: 1000,
+			oracle_address: String::new(),
+			proof_version: 1,
+		})
+	}
+
+	#[test]
+	fn test_proof_message_binds_proof() {
+		let message = encode_proof_message([7u8; 32], 10, &proof(1));
+		let (order_id, tx_hash, chain_id, block_number, filled_timestamp, data) =
+			<(FixedBytes<32>, B256, U256, U256, U256, Bytes)>::abi_decode_params(&message, true)
+				.unwrap();
+		assert_eq!(order_id, FixedBytes::from([7u8; 32]));
+		assert_eq!(tx_hash, B256::from([1u8; 32]));
+		assert_eq!(chain_id, U256::from(10));
+		assert_eq!(block_number, U256::from(42));
+		assert_eq!(filled_timestamp, U256::from(1000));
+		assert_eq!(data.to_vec(), vec![1u8; 4]);
+
+		assert_ne!(
+			keccak256(&message),
+			keccak256(encode_proof_message([7u8; 32], 10, &proof(2)))
+		);
+	}
+
+	#[test]
+	fn test_protocol_parameters_required() {
+		let config = ProofRelayConfig {
+			relay_protocol: RelayProtocol::LayerZero,
+			messengers: HashMap::new(),
+			wormhole: None,
+			layerzero: None,
+		};
+		assert!(ProofRelayer::new(config, &NetworksConfig::default()).is_err());
+	}
+
+	fn network() -> NetworkConfig {
+		NetworkConfig {
+			rpc_urls: vec![RpcEndpoint::http_only("http://127.0.0.1:1".to_string())],
+			input_settler_address: Address(vec![0u8; 20]),
+			output_settler_address: Address(vec![0u8; 20]),
+			tokens: Vec::new(),
+			input_settler_compact_address: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_layerzero_needs_no_delivery() {
+		let config = ProofRelayConfig {
+			relay_protocol: RelayProtocol::LayerZero,
+			messengers: HashMap::from([(1, Address(vec![1u8; 20]))]),
+			wormhole: None,
+			layerzero: Some(LayerZeroRelayConfig {
+				endpoints: HashMap::from([(2, Address(vec![2u8; 20]))]),
+				eids: HashMap::from([(1, 30101)]),
+				options: String::new(),
+			}),
+		};
+		let networks = NetworksConfig::from([(ChainId(1), network()), (ChainId(2), network())]);
+		let relayer = ProofRelayer::new(config, &networks).unwrap();
+		let order = Order {
+			id: "order-1".to_string(),
+			standard: "eip7683".to_string(),
+			created_at: 0,
+			updated_at: 0,
+			status: OrderStatus::Executed,
+			data: serde_json::json!({}),
+			solver_address: Address(vec![0u8; 20]),
+			quote_id: None,
+			input_chain_ids: vec![1],
+			output_chain_ids: vec![2],
+			execution_params: None,
+			prepare_tx_hash: None,
+			commit_tx_hash: None,
+			fill_tx_hash: None,
+			claim_tx_hash: None,
+			fill_proof: None,
+		};
+
+		// Executors deliver LayerZero messages, so there is nothing to submit
+		let delivery = relayer
+			.delivery_transaction(&order, &TransactionHash(vec![3u8; 32]))
+			.await
+			.unwrap();
+		assert!(delivery.is_none());
+	}
+}
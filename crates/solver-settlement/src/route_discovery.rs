@@ -0,0 +1,207 @@
+//! Dynamic oracle route discovery.
+//!
+//! Periodically asks each settlement contract for the output chains it
+//! currently supports and replaces the routes of the owning settlement
+//! implementation. Routes from the configuration are used until the first
+//! successful refresh, and the last known routes are kept when a query fails.
+
+use crate::SettlementError;
+use alloy_primitives::{hex, Address as AlloyAddress, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::TransactionRequest;
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport_http::Http;
+use solver_types::{with_0x_prefix, NetworksConfig};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+// Solidity type definitions for route queries.
+sol! {
+	/// Read-only interface of settlement contracts exposing their supported routes.
+	interface ISupportedRoutes {
+		function getSupportedRoutes() external view returns (uint256[] memory outputChainIds);
+	}
+}
+
+/// Shared map of valid routes: input chain -> output chains.
+pub type OracleRouteMap = Arc<RwLock<HashMap<u64, Vec<u64>>>>;
+
+/// Settlement contract whose supported routes are discovered.
+pub struct RouteDiscoveryTarget {
+	/// Chain the settlement contract is deployed on, the input chain of its routes.
+	pub chain_id: u64,
+	/// Address of the settlement contract.
+	pub contract: AlloyAddress,
+	/// Output chains with configured oracles. Other chains are ignored.
+	pub output_chains: HashSet<u64>,
+	/// Route map the discovered routes are written to.
+	pub routes: OracleRouteMap,
+}
+
+/// Background task refreshing oracle routes from settlement contracts.
+pub struct OracleRouteDiscovery {
+	/// RPC providers for each chain with a settlement contract to query.
+	providers: HashMap<u64, RootProvider<Http<reqwest::Client>>>,
+	/// Settlement contracts to query.
+	targets: Vec<RouteDiscoveryTarget>,
+}
+
+impl OracleRouteDiscovery {
+	/// Creates a new OracleRouteDiscovery for the given settlement contracts.
+	pub fn new(
+		networks: &NetworksConfig,
+		targets: Vec<RouteDiscoveryTarget>,
+	) -> Result<Self, SettlementError> {
+		let mut providers = HashMap::new();
+
+		for target in &targets {
+			if providers.contains_key(&target.chain_id) {
+				continue;
+			}
+
+			let network = networks.get(&target.chain_id).ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"Network {} not found in configuration",
+					target.chain_id
+				))
+			})?;
+			let http_url = network.get_http_url().ok_or_else(|| {
+				SettlementError::ValidationFailed(format!(
+					"No HTTP RPC URL configured for network {}",
+					target.chain_id
+				))
+			})?;
+			let provider = RootProvider::new_http(http_url.parse().map_err(|e| {
+				SettlementError::ValidationFailed(format!(
+					"Invalid RPC URL for network {}: {}",
+					target.chain_id, e
+				))
+			})?);
+
+			providers.insert(target.chain_id, provider);
+		}
+
+		Ok(Self { providers, targets })
+	}
+
+	/// Queries all settlement contracts once and updates their routes.
+	///
+	/// Returns the number of contracts whose routes were refreshed.
+	pub async fn refresh_all(&self) -> usize {
+		let mut refreshed = 0;
+
+		for target in &self.targets {
+			match self.fetch_routes(target).await {
+				Ok(outputs) => {
+					let mut routes = target.routes.write().unwrap_or_else(|e| e.into_inner());
+					if routes.get(&target.chain_id) != Some(&outputs) {
+						tracing::info!(
+							chain_id = target.chain_id,
+							routes = ?outputs,
+							"Updated oracle routes"
+						);
+					}
+					routes.insert(target.chain_id, outputs);
+					refreshed += 1;
+				},
+				Err(e) => {
+					tracing::warn!(
+						chain_id = target.chain_id,
+						contract = %with_0x_prefix(&hex::encode(target.contract)),
+						error = %e,
+						"Route discovery failed, keeping last known routes"
+					);
+				},
+			}
+		}
+
+		refreshed
+	}
+
+	/// Spawns a task that refreshes all routes at the given interval.
+	pub fn spawn(self, interval: Duration) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(interval);
+			loop {
+				interval.tick().await;
+				self.refresh_all().await;
+			}
+		})
+	}
+
+	/// Calls `getSupportedRoutes()` on a settlement contract.
+	async fn fetch_routes(
+		&self,
+		target: &RouteDiscoveryTarget,
+	) -> Result<Vec<u64>, SettlementError> {
+		let provider = self.providers.get(&target.chain_id).ok_or_else(|| {
+			SettlementError::ValidationFailed(format!(
+				"No provider configured for chain {}",
+				target.chain_id
+			))
+		})?;
+
+		let result = provider
+			.call(
+				&TransactionRequest::default().to(target.contract).input(
+					ISupportedRoutes::getSupportedRoutesCall {}
+						.abi_encode()
+						.into(),
+				),
+			)
+			.await
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to query routes: {}", e))
+			})?;
+
+		parse_supported_routes(&result, &target.output_chains)
+	}
+}
+
+/// Decodes a `getSupportedRoutes()` response, keeping chains with configured oracles.
+fn parse_supported_routes(
+	data: &[u8],
+	output_chains: &HashSet<u64>,
+) -> Result<Vec<u64>, SettlementError> {
+	let decoded = ISupportedRoutes::getSupportedRoutesCall::abi_decode_returns(data, true)
+		.map_err(|e| {
+			SettlementError::ValidationFailed(format!("Invalid routes response: {}", e))
+		})?;
+
+	let mut outputs: Vec<u64> = decoded
+		.outputChainIds
+		.into_iter()
+		.filter(|chain_id| *chain_id <= U256::from(u64::MAX))
+		.map(|chain_id| chain_id.to::<u64>())
+		.filter(|chain_id| output_chains.contains(chain_id))
+		.collect();
+	outputs.sort_unstable();
+	outputs.dedup();
+	Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_sol_types::SolValue;
+
+	#[test]
+	fn test_parse_supported_routes_filters_unknown_chains() {
+		let data = vec![
+			U256::from(10),
+			U256::from(1),
+			U256::from(999),
+			U256::from(10),
+		]
+		.abi_encode();
+		let output_chains = HashSet::from([1, 10, 8453]);
+
+		assert_eq!(
+			parse_supported_routes(&data, &output_chains).unwrap(),
+			vec![1, 10]
+		);
+		assert!(parse_supported_routes(&[0xde, 0xad], &output_chains).is_err());
+	}
+}
@@ -0,0 +1,106 @@
+//! Block lookup helpers shared by settlement implementations.
+
+use crate::SettlementError;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::BlockTransactionsKind;
+use alloy_transport_http::Http;
+use std::future::Future;
+
+/// Returns the timestamp of a block.
+pub async fn block_timestamp(
+	provider: &RootProvider<Http<reqwest::Client>>,
+	number: u64,
+) -> Result<u64, SettlementError> {
+	Ok(provider
+		.get_block_by_number(
+			alloy_rpc_types::BlockNumberOrTag::Number(number),
+			BlockTransactionsKind::Hashes,
+		)
+		.await
+		.map_err(|e| SettlementError::ValidationFailed(format!("Failed to get block: {}", e)))?
+		.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?
+		.header
+		.timestamp)
+}
+
+/// Returns the first block up to `latest_block` with a timestamp of at least
+/// `timestamp`, or `latest_block` if there is none.
+///
+/// Block timestamps are non-decreasing, so the block is found by binary
+/// search.
+pub async fn first_block_at<F, Fut>(
+	latest_block: u64,
+	timestamp: u64,
+	block_timestamp: F,
+) -> Result<u64, SettlementError>
+where
+	F: Fn(u64) -> Fut,
+	Fut: Future<Output = Result<u64, SettlementError>>,
+{
+	let (mut low, mut high) = (0, latest_block);
+	while low < high {
+		let middle = low + (high - low) / 2;
+		if block_timestamp(middle).await? < timestamp {
+			low = middle + 1;
+		} else {
+			high = middle;
+		}
+	}
+	Ok(low)
+}
+
+/// Splits the inclusive block range `from..=to` into consecutive ranges of
+/// at most `max_blocks` blocks, as accepted by RPC log queries.
+pub fn block_ranges(from: u64, to: u64, max_blocks: u64) -> impl Iterator<Item = (u64, u64)> {
+	let max_blocks = max_blocks.max(1);
+	std::iter::successors(Some(from), move |start| start.checked_add(max_blocks))
+		.take_while(move |start| *start <= to)
+		.map(move |start| (start, start.saturating_add(max_blocks - 1).min(to)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_first_block_at_fill_timestamp() {
+		// Twelve second blocks starting at timestamp 1000
+		let timestamps: Vec<u64> = (0..100).map(|number| 1000 + number * 12).collect();
+		let find = |timestamp| {
+			let timestamps = timestamps.clone();
+			async move {
+				first_block_at(99, timestamp, |number| {
+					let timestamp = timestamps[number as usize];
+					async move { Ok(timestamp) }
+				})
+				.await
+				.unwrap()
+			}
+		};
+
+		assert_eq!(find(0).await, 0);
+		assert_eq!(find(1000).await, 0);
+		assert_eq!(find(1001).await, 1);
+		assert_eq!(find(1000 + 50 * 12).await, 50);
+		assert_eq!(find(1000 + 50 * 12 - 5).await, 50);
+		// Timestamps after the latest block resolve to the latest block
+		assert_eq!(find(u64::MAX).await, 99);
+	}
+
+	#[tokio::test]
+	async fn test_first_block_at_propagates_errors() {
+		let result = first_block_at(10, 5, |_| async {
+			Err(SettlementError::ValidationFailed("unreachable".to_string()))
+		})
+		.await;
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn test_block_ranges_are_bounded() {
+		let ranges: Vec<_> = block_ranges(10, 35, 10).collect();
+		assert_eq!(ranges, vec![(10, 19), (20, 29), (30, 35)]);
+		assert_eq!(block_ranges(5, 5, 10).collect::<Vec<_>>(), vec![(5, 5)]);
+		assert_eq!(block_ranges(6, 5, 10).count(), 0);
+	}
+}
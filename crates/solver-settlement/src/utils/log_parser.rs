@@ -0,0 +1,145 @@
+//! Typed decoding of EVM event logs.
+//!
+//! Maps event signature hashes (the keccak256 of the ABI event signature,
+//! emitted as topic 0) to decoders of registered `sol!` event types, so that
+//! receipts and log batches can be decoded without per-event boilerplate.
+
+use alloy_primitives::{Address as AlloyAddress, Log as PrimLog, B256};
+use alloy_rpc_types::{Log, TransactionReceipt};
+use alloy_sol_types::SolEvent;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Decodes a log into a boxed event of a registered type.
+type Decoder = fn(&PrimLog) -> Option<Box<dyn Any + Send + Sync>>;
+
+/// An event log decoded into its registered type.
+pub struct ParsedLog {
+	/// Address of the contract that emitted the event.
+	pub address: AlloyAddress,
+	/// Index of the log in its block, if known.
+	pub log_index: Option<u64>,
+	/// ABI signature of the event, e.g. `Open(bytes32,bytes)`.
+	pub signature: &'static str,
+	/// The decoded event.
+	event: Box<dyn Any + Send + Sync>,
+}
+
+impl ParsedLog {
+	/// Returns the decoded event if it is of type `T`.
+	pub fn event<T: SolEvent + 'static>(&self) -> Option<&T> {
+		self.event.downcast_ref()
+	}
+
+	/// Consumes the log, returning the decoded event if it is of type `T`.
+	pub fn into_event<T: SolEvent + 'static>(self) -> Option<T> {
+		self.event.downcast().ok().map(|event| *event)
+	}
+}
+
+/// Decoder of event logs for a set of registered event types.
+///
+/// Logs of unregistered events, and logs that fail to decode, are skipped.
+#[derive(Default)]
+pub struct LogParser {
+	decoders: HashMap<B256, (&'static str, Decoder)>,
+}
+
+impl LogParser {
+	/// Creates a LogParser without registered events.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers an event type, keyed by its signature hash.
+	pub fn register<T: SolEvent + Send + Sync + 'static>(mut self) -> Self {
+		self.decoders
+			.insert(T::SIGNATURE_HASH, (T::SIGNATURE, decode::<T>));
+		self
+	}
+
+	/// Decodes all registered events emitted in a transaction.
+	pub fn parse(&self, receipt: &TransactionReceipt) -> Vec<ParsedLog> {
+		self.parse_logs(receipt.inner.logs())
+	}
+
+	/// Decodes all registered events in a batch of logs.
+	pub fn parse_logs(&self, logs: &[Log]) -> Vec<ParsedLog> {
+		logs.iter().filter_map(|log| self.parse_log(log)).collect()
+	}
+
+	/// Decodes a single log, returning None if its event is not registered or invalid.
+	pub fn parse_log(&self, log: &Log) -> Option<ParsedLog> {
+		let topic = log.topics().first()?;
+		let (signature, decoder) = self.decoders.get(topic)?;
+		let event = decoder(&log.inner)?;
+
+		Some(ParsedLog {
+			address: log.address(),
+			log_index: log.log_index,
+			signature,
+			event,
+		})
+	}
+}
+
+fn decode<T: SolEvent + Send + Sync + 'static>(
+	log: &PrimLog,
+) -> Option<Box<dyn Any + Send + Sync>> {
+	match T::decode_log(log, true) {
+		Ok(decoded) => Some(Box::new(decoded.data)),
+		Err(e) => {
+			tracing::debug!(event = T::SIGNATURE, error = %e, "Failed to decode event log");
+			None
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloy_primitives::{FixedBytes, U256};
+	use alloy_sol_types::sol;
+
+	sol! {
+		event Filled(bytes32 indexed orderId, uint256 amount);
+		event Cancelled(bytes32 indexed orderId);
+	}
+
+	fn rpc_log(event: &impl SolEvent) -> Log {
+		Log {
+			inner: PrimLog {
+				address: AlloyAddress::repeat_byte(1),
+				data: event.encode_log_data(),
+			},
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_parse_registered_events_only() {
+		let order_id = FixedBytes::<32>::repeat_byte(7);
+		let logs = vec![
+			rpc_log(&Cancelled { orderId: order_id }),
+			rpc_log(&Filled {
+				orderId: order_id,
+				amount: U256::from(100),
+			}),
+		];
+
+		let parsed = LogParser::new().register::<Filled>().parse_logs(&logs);
+		assert_eq!(parsed.len(), 1);
+		assert_eq!(parsed[0].signature, "Filled(bytes32,uint256)");
+		assert_eq!(parsed[0].address, AlloyAddress::repeat_byte(1));
+		assert!(parsed[0].event::<Cancelled>().is_none());
+
+		let filled = parsed
+			.into_iter()
+			.next()
+			.unwrap()
+			.into_event::<Filled>()
+			.unwrap();
+		assert_eq!(filled.orderId, order_id);
+		assert_eq!(filled.amount, U256::from(100));
+	}
+}
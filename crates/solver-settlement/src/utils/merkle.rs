@@ -0,0 +1,164 @@
+//! Merkle tree construction for batch claims.
+//!
+//! Builds a binary Merkle tree over order IDs so that several fills can be
+//! claimed in one transaction by submitting a single root and a proof per
+//! order. Leaves are the keccak256 hash of each order ID and sibling pairs are
+//! sorted before hashing, matching the OpenZeppelin `MerkleProof` library.
+//! A node without a sibling is promoted to the next level unchanged.
+
+use crate::SettlementError;
+use alloy_primitives::keccak256;
+
+/// Root and per-leaf proofs of a Merkle tree over order IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProofs {
+	/// Root hash of the tree.
+	pub root: [u8; 32],
+	/// Proof for each order ID, in the order the IDs were given.
+	pub proofs: Vec<Vec<[u8; 32]>>,
+}
+
+/// Builds Merkle roots and proofs over order IDs.
+pub struct MerkleProofBuilder {
+	/// Hashed leaves in insertion order.
+	leaves: Vec<[u8; 32]>,
+}
+
+impl MerkleProofBuilder {
+	/// Creates a builder for the given order IDs.
+	pub fn new(order_ids: Vec<[u8; 32]>) -> Self {
+		Self {
+			leaves: order_ids.iter().map(hash_leaf).collect(),
+		}
+	}
+
+	/// Builds the tree, returning the root hash and a proof for every order ID.
+	///
+	/// # Errors
+	///
+	/// Returns `SettlementError::ValidationFailed` if no order IDs were given.
+	pub fn build(&self) -> Result<MerkleProofs, SettlementError> {
+		if self.leaves.is_empty() {
+			return Err(SettlementError::ValidationFailed(
+				"Cannot build a Merkle tree without leaves".to_string(),
+			));
+		}
+
+		let mut proofs = vec![Vec::new(); self.leaves.len()];
+		// Position of each original leaf in the current level
+		let mut positions: Vec<usize> = (0..self.leaves.len()).collect();
+		let mut level = self.leaves.clone();
+
+		while level.len() > 1 {
+			for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+				let sibling = *position ^ 1;
+				if sibling < level.len() {
+					proof.push(level[sibling]);
+				}
+				*position /= 2;
+			}
+
+			level = level
+				.chunks(2)
+				.map(|pair| match pair {
+					[left, right] => hash_pair(left, right),
+					[single] => *single,
+					_ => unreachable!("chunks(2) yields one or two nodes"),
+				})
+				.collect();
+		}
+
+		Ok(MerkleProofs {
+			root: level[0],
+			proofs,
+		})
+	}
+}
+
+/// Verifies that an order ID is included in a tree with the given root.
+pub fn verify_proof(root: &[u8; 32], order_id: &[u8; 32], proof: &[[u8; 32]]) -> bool {
+	let computed = proof.iter().fold(hash_leaf(order_id), |node, sibling| {
+		hash_pair(&node, sibling)
+	});
+	&computed == root
+}
+
+/// Hashes an order ID into a leaf.
+fn hash_leaf(order_id: &[u8; 32]) -> [u8; 32] {
+	keccak256(order_id).0
+}
+
+/// Hashes two nodes in sorted order.
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	let mut data = [0u8; 64];
+	data[..32].copy_from_slice(first);
+	data[32..].copy_from_slice(second);
+	keccak256(data).0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn order_ids(count: u8) -> Vec<[u8; 32]> {
+		(0..count).map(|i| [i + 1; 32]).collect()
+	}
+
+	fn assert_all_proofs_verify(ids: &[[u8; 32]]) -> MerkleProofs {
+		let tree = MerkleProofBuilder::new(ids.to_vec()).build().unwrap();
+		assert_eq!(tree.proofs.len(), ids.len());
+		for (id, proof) in ids.iter().zip(&tree.proofs) {
+			assert!(verify_proof(&tree.root, id, proof));
+		}
+		tree
+	}
+
+	#[test]
+	fn test_single_leaf_root_is_leaf_hash() {
+		let ids = order_ids(1);
+		let tree = assert_all_proofs_verify(&ids);
+		assert_eq!(tree.root, hash_leaf(&ids[0]));
+		assert!(tree.proofs[0].is_empty());
+	}
+
+	#[test]
+	fn test_even_sized_tree() {
+		let ids = order_ids(4);
+		let tree = assert_all_proofs_verify(&ids);
+
+		let left = hash_pair(&hash_leaf(&ids[0]), &hash_leaf(&ids[1]));
+		let right = hash_pair(&hash_leaf(&ids[2]), &hash_leaf(&ids[3]));
+		assert_eq!(tree.root, hash_pair(&left, &right));
+		assert!(tree.proofs.iter().all(|proof| proof.len() == 2));
+	}
+
+	#[test]
+	fn test_odd_sized_trees() {
+		for count in [3, 5, 7, 9, 13] {
+			assert_all_proofs_verify(&order_ids(count));
+		}
+
+		// With three leaves the last one is promoted and needs a single sibling
+		let ids = order_ids(3);
+		let tree = assert_all_proofs_verify(&ids);
+		let left = hash_pair(&hash_leaf(&ids[0]), &hash_leaf(&ids[1]));
+		assert_eq!(tree.root, hash_pair(&left, &hash_leaf(&ids[2])));
+		assert_eq!(tree.proofs[2], vec![left]);
+	}
+
+	#[test]
+	fn test_proof_rejects_other_leaves_and_roots() {
+		let ids = order_ids(5);
+		let tree = MerkleProofBuilder::new(ids.clone()).build().unwrap();
+
+		assert!(!verify_proof(&tree.root, &ids[1], &tree.proofs[0]));
+		assert!(!verify_proof(&tree.root, &[0xff; 32], &tree.proofs[4]));
+		assert!(!verify_proof(&[0u8; 32], &ids[0], &tree.proofs[0]));
+	}
+
+	#[test]
+	fn test_empty_tree_is_rejected() {
+		assert!(MerkleProofBuilder::new(vec![]).build().is_err());
+	}
+}
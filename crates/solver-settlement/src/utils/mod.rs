@@ -3,9 +3,15 @@
 //! This module provides shared utilities for parsing oracle configurations
 //! from TOML config files, used by all settlement implementations.
 
+pub mod blocks;
+pub mod log_parser;
+pub mod merkle;
+pub mod proof_registry;
+
 use crate::{OracleConfig, OracleSelectionStrategy, SettlementError};
-use solver_types::{utils::parse_address, Address};
+use solver_types::{utils::parse_address, Address, ChainId};
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 /// Parse an oracle table from TOML configuration.
 ///
@@ -17,12 +23,12 @@ use std::collections::HashMap;
 /// ```
 pub fn parse_oracle_table(
 	table: &toml::Value,
-) -> Result<HashMap<u64, Vec<Address>>, SettlementError> {
+) -> Result<HashMap<ChainId, Vec<Address>>, SettlementError> {
 	let mut result = HashMap::new();
 
 	if let Some(table) = table.as_table() {
 		for (chain_id_str, oracles_value) in table {
-			let chain_id = chain_id_str.parse::<u64>().map_err(|e| {
+			let chain_id = chain_id_str.parse::<ChainId>().map_err(|e| {
 				SettlementError::ValidationFailed(format!(
 					"Invalid chain ID '{}': {}",
 					chain_id_str, e
@@ -183,15 +189,16 @@ pub fn parse_oracle_config(config: &toml::Value) -> Result<OracleConfig, Settlem
 	Ok(OracleConfig {
 		input_oracles,
 		output_oracles,
-		routes,
+		routes: Arc::new(RwLock::new(routes)),
 		selection_strategy,
+		health: Default::default(),
 	})
 }
 
 /// Validate that all routes reference chains with configured oracles.
 fn validate_routes(
-	input_oracles: &HashMap<u64, Vec<Address>>,
-	output_oracles: &HashMap<u64, Vec<Address>>,
+	input_oracles: &HashMap<ChainId, Vec<Address>>,
+	output_oracles: &HashMap<ChainId, Vec<Address>>,
 	routes: &HashMap<u64, Vec<u64>>,
 ) -> Result<(), SettlementError> {
 	for (from_chain, to_chains) in routes {
@@ -0,0 +1,147 @@
+//! Versioned formats of fill proof attestation data.
+//!
+//! Settlement implementations build fill proofs in the version 1 format. When
+//! a settlement contract is upgraded and expects attestations in another
+//! format, a new version is registered with an encoder converting version 1
+//! attestations into it and a decoder converting them back. Stored proofs keep
+//! their version, so proofs attested before an upgrade remain decodable.
+
+use crate::SettlementError;
+use solver_types::FillProof;
+use std::collections::HashMap;
+
+/// Format of attestations as built by the settlement implementations.
+pub const PROOF_VERSION_1: u8 = 1;
+
+/// Converts a version 1 attestation into the format of a version.
+pub type ProofEncoder = fn(&[u8]) -> Result<Vec<u8>, SettlementError>;
+
+/// Converts an attestation in the format of a version into version 1.
+pub type ProofDecoder = fn(&[u8]) -> Result<Vec<u8>, SettlementError>;
+
+/// Encoder and decoder of each fill proof version.
+#[derive(Clone)]
+pub struct ProofVersionRegistry {
+	codecs: HashMap<u8, (ProofEncoder, ProofDecoder)>,
+}
+
+impl Default for ProofVersionRegistry {
+	fn default() -> Self {
+		let mut registry = Self {
+			codecs: HashMap::new(),
+		};
+		registry.register(
+			PROOF_VERSION_1,
+			|data| Ok(data.to_vec()),
+			|data| Ok(data.to_vec()),
+		);
+		registry
+	}
+}
+
+impl ProofVersionRegistry {
+	/// Registers the encoder and decoder of a version, replacing any
+	/// previously registered for it.
+	pub fn register(&mut self, version: u8, encoder: ProofEncoder, decoder: ProofDecoder) {
+		self.codecs.insert(version, (encoder, decoder));
+	}
+
+	/// Converts a version 1 proof into the given version.
+	pub fn tag(&self, proof: FillProof, version: u8) -> Result<FillProof, SettlementError> {
+		let FillProof::Standard(mut proof) = proof else {
+			return Ok(proof);
+		};
+		let (encoder, _) = self.codec(version)?;
+		proof.attestation_data = proof.attestation_data.as_deref().map(encoder).transpose()?;
+		proof.proof_version = version;
+		Ok(FillProof::Standard(proof))
+	}
+
+	/// Converts a proof of any registered version into version 1.
+	///
+	/// Fails if the attestation data is not in the format of its version.
+	pub fn decode(&self, proof: &FillProof) -> Result<FillProof, SettlementError> {
+		let FillProof::Standard(proof) = proof else {
+			return Ok(proof.clone());
+		};
+		let (_, decoder) = self.codec(proof.proof_version)?;
+		let mut decoded = proof.clone();
+		decoded.attestation_data = proof.attestation_data.as_deref().map(decoder).transpose()?;
+		decoded.proof_version = PROOF_VERSION_1;
+		Ok(FillProof::Standard(decoded))
+	}
+
+	fn codec(&self, version: u8) -> Result<(ProofEncoder, ProofDecoder), SettlementError> {
+		self.codecs.get(&version).copied().ok_or_else(|| {
+			SettlementError::ValidationFailed(format!("Unsupported fill proof version {}", version))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::{StandardFillProof, TransactionHash};
+
+	/// Version 2 format: the attestation prefixed with its version and length.
+	fn encode_v2(data: &[u8]) -> Result<Vec<u8>, SettlementError> {
+		let mut encoded = vec![2];
+		encoded.extend_from_slice(&(data.len() as u32).to_be_bytes());
+		encoded.extend_from_slice(data);
+		Ok(encoded)
+	}
+
+	fn decode_v2(data: &[u8]) -> Result<Vec<u8>, SettlementError> {
+		match data {
+			[2, rest @ ..] if rest.len() >= 4 => {
+				let (length, attestation) = rest.split_at(4);
+				let length = u32::from_be_bytes(length.try_into().unwrap()) as usize;
+				if attestation.len() == length {
+					return Ok(attestation.to_vec());
+				}
+				Err(SettlementError::ValidationFailed(
+					"Invalid version 2 attestation length".to_string(),
+				))
+			},
+			_ => Err(SettlementError::ValidationFailed(
+				"Not a version 2 attestation".to_string(),
+			)),
+		}
+	}
+
+	fn proof(version: u8, attestation: Vec<u8>) -> FillProof {
+		FillProof::Standard(StandardFillProof {
+			tx_hash: TransactionHash(vec![1; 32]),
+			block_number: 1,
+			attestation_data: Some(attestation),
+			filled_timestamp: 0,
+			oracle_address: String::new(),
+			proof_version: version,
+		})
+	}
+
+	fn attestation(proof: &FillProof) -> Vec<u8> {
+		match proof {
+			FillProof::Standard(proof) => proof.attestation_data.clone().unwrap(),
+			FillProof::Zk(_) => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn test_v1_proof_rejected_by_v2_decoder() {
+		let mut registry = ProofVersionRegistry::default();
+		let v1 = proof(PROOF_VERSION_1, vec![0xab; 32]);
+		assert_eq!(attestation(&registry.decode(&v1).unwrap()), vec![0xab; 32]);
+		assert!(registry.tag(v1.clone(), 2).is_err());
+
+		registry.register(2, encode_v2, decode_v2);
+		let v2 = registry.tag(v1.clone(), 2).unwrap();
+		assert_eq!(attestation(&v2).len(), 37);
+		assert_eq!(attestation(&registry.decode(&v2).unwrap()), vec![0xab; 32]);
+
+		// A version 1 attestation mislabeled as version 2 is rejected
+		let mislabeled = proof(2, vec![0xab; 32]);
+		assert!(registry.decode(&mislabeled).is_err());
+		assert!(decode_v2(&attestation(&v1)).is_err());
+	}
+}
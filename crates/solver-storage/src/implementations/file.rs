@@ -3,16 +3,26 @@
 //! This module provides concrete implementations of the StorageInterface trait,
 //! currently supporting file-based storage for persistence.
 
-use crate::{QueryFilter, StorageError, StorageIndexes, StorageInterface};
+use crate::{
+	split_key, unscoped_namespace, IndexType, QueryFilter, StorageError, StorageIndexes,
+	StorageInterface,
+};
 use async_trait::async_trait;
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
-use solver_types::{ConfigSchema, Field, FieldType, Schema, StorageKey, ValidationError};
-use std::collections::{HashMap, HashSet};
+use solver_types::{
+	ConfigSchema, Field, FieldType, Schema, SchemaDefinition, StorageKey, ValidationError,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::fs;
 
+/// Counter making temp file names of concurrent writers unique.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 #[allow(clippy::doc_nested_refdefs)]
 /// Fixed-size file header for TTL support.
 ///
@@ -120,11 +130,122 @@ impl FileHeader {
 /// Index structure for a namespace.
 ///
 /// Maintains mappings from field values to sets of keys for efficient querying.
+/// Numeric and sorted fields are kept in ordered maps to serve range queries.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct NamespaceIndex {
 	/// Field -> Value -> Set of keys
 	/// Example: {"status": {"Pending": ["order1", "order2"], "Executed": ["order3"]}}
 	pub indexes: HashMap<String, HashMap<serde_json::Value, HashSet<String>>>,
+	/// Field -> Number -> Set of keys, ordered by number
+	#[serde(default)]
+	pub numeric: HashMap<String, BTreeMap<u64, HashSet<String>>>,
+	/// Field -> String -> Set of keys, ordered lexicographically
+	#[serde(default)]
+	pub sorted: HashMap<String, BTreeMap<String, HashSet<String>>>,
+}
+
+impl NamespaceIndex {
+	/// Adds a key under the indexed value of a field.
+	fn insert(&mut self, field: &str, value: &IndexType, key: &str) {
+		let keys = match value {
+			IndexType::Exact(value) => self
+				.indexes
+				.entry(field.to_string())
+				.or_default()
+				.entry(value.clone())
+				.or_default(),
+			IndexType::Numeric(value) => self
+				.numeric
+				.entry(field.to_string())
+				.or_default()
+				.entry(*value)
+				.or_default(),
+			IndexType::Sorted(value) => self
+				.sorted
+				.entry(field.to_string())
+				.or_default()
+				.entry(value.clone())
+				.or_default(),
+		};
+		keys.insert(key.to_string());
+	}
+
+	/// Removes a key from all indexes, dropping entries left empty.
+	fn remove_key(&mut self, key: &str) {
+		self.indexes.retain(|_, value_map| {
+			value_map.retain(|_, keys| {
+				keys.remove(key);
+				!keys.is_empty()
+			});
+			!value_map.is_empty()
+		});
+		self.numeric.retain(|_, value_map| {
+			value_map.retain(|_, keys| {
+				keys.remove(key);
+				!keys.is_empty()
+			});
+			!value_map.is_empty()
+		});
+		self.sorted.retain(|_, value_map| {
+			value_map.retain(|_, keys| {
+				keys.remove(key);
+				!keys.is_empty()
+			});
+			!value_map.is_empty()
+		});
+	}
+
+	/// Returns true if no key is indexed.
+	fn is_empty(&self) -> bool {
+		self.indexes.is_empty() && self.numeric.is_empty() && self.sorted.is_empty()
+	}
+
+	/// Returns all indexed keys.
+	fn all_keys(&self) -> HashSet<String> {
+		let exact = self.indexes.values().flat_map(|m| m.values());
+		let numeric = self.numeric.values().flat_map(|m| m.values());
+		let sorted = self.sorted.values().flat_map(|m| m.values());
+		exact
+			.chain(numeric)
+			.chain(sorted)
+			.flatten()
+			.cloned()
+			.collect()
+	}
+
+	/// Returns the keys whose value of a field is strictly above or below `value`.
+	///
+	/// Numbers query the numeric index of the field and strings its sorted
+	/// index. Other values match nothing.
+	fn range(&self, field: &str, value: &serde_json::Value, above: bool) -> HashSet<String> {
+		let matches: Vec<&HashSet<String>> = match value {
+			serde_json::Value::Number(number) => match (number.as_u64(), self.numeric.get(field)) {
+				(Some(number), Some(field_index)) => field_index
+					.range(range_bounds(number, above))
+					.map(|(_, keys)| keys)
+					.collect(),
+				_ => Vec::new(),
+			},
+			serde_json::Value::String(value) => match self.sorted.get(field) {
+				Some(field_index) => field_index
+					.range(range_bounds(value.clone(), above))
+					.map(|(_, keys)| keys)
+					.collect(),
+				None => Vec::new(),
+			},
+			_ => Vec::new(),
+		};
+		matches.into_iter().flatten().cloned().collect()
+	}
+}
+
+/// Returns the bounds of the values strictly above or below `value`.
+fn range_bounds<T>(value: T, above: bool) -> (Bound<T>, Bound<T>) {
+	if above {
+		(Bound::Excluded(value), Bound::Unbounded)
+	} else {
+		(Bound::Unbounded, Bound::Excluded(value))
+	}
 }
 
 /// TTL configuration for different storage keys.
@@ -197,7 +318,7 @@ impl FileStorage {
 	/// Gets the TTL for a given key based on its namespace.
 	fn get_ttl_for_key(&self, key: &str) -> Duration {
 		// Parse namespace from key (e.g., "orders:123" -> "orders")
-		let namespace = key.split(':').next().unwrap_or("");
+		let namespace = unscoped_namespace(key);
 
 		// Try to parse the namespace as a StorageKey
 		namespace
@@ -336,29 +457,13 @@ impl FileStorage {
 			};
 
 			// First, remove old index entries for this key if they exist
-			for (_, value_map) in namespace_index.indexes.iter_mut() {
-				for (_, keys) in value_map.iter_mut() {
-					keys.remove(&key_owned);
-				}
-			}
+			namespace_index.remove_key(&key_owned);
 
 			// Now add new index entries
 			for (field, value) in &indexes_owned.fields {
-				namespace_index
-					.indexes
-					.entry(field.clone())
-					.or_default()
-					.entry(value.clone())
-					.or_default()
-					.insert(key_owned.clone());
+				namespace_index.insert(field, value, &key_owned);
 			}
 
-			// Clean up empty entries
-			namespace_index.indexes.retain(|_, value_map| {
-				value_map.retain(|_, keys| !keys.is_empty());
-				!value_map.is_empty()
-			});
-
 			// Write index atomically
 			let temp_path = index_path_clone.with_extension("tmp");
 			fs::write(
@@ -398,20 +503,10 @@ impl FileStorage {
 				.map_err(|e| StorageError::Serialization(e.to_string()))?;
 
 			// Remove key from all indexes
-			for (_, value_map) in namespace_index.indexes.iter_mut() {
-				for (_, keys) in value_map.iter_mut() {
-					keys.remove(&key_owned);
-				}
-			}
-
-			// Clean up empty entries
-			namespace_index.indexes.retain(|_, value_map| {
-				value_map.retain(|_, keys| !keys.is_empty());
-				!value_map.is_empty()
-			});
+			namespace_index.remove_key(&key_owned);
 
 			// Check if the index is completely empty
-			if namespace_index.indexes.is_empty() {
+			if namespace_index.is_empty() {
 				// Delete the index file and lock file
 				fs::remove_file(&index_path_clone)
 					.await
@@ -447,6 +542,61 @@ impl FileStorage {
 		.await
 	}
 
+	/// Hard-links `source` to `path` in place of an expired file.
+	///
+	/// Runs under the exclusive lock of the key's namespace, so writers
+	/// replacing the same file take turns. The expired file is renamed to a
+	/// tombstone unique to this writer and the link retried, so only the
+	/// writer whose link succeeds gets `true`. A live file renamed by mistake,
+	/// because it was overwritten after the check, is linked back.
+	async fn replace_expired(
+		&self,
+		key: &str,
+		source: PathBuf,
+		path: PathBuf,
+	) -> Result<bool, StorageError> {
+		let (namespace, _) = split_key(key);
+		let index_path = self.base_path.join(format!("{}.index", namespace));
+
+		Self::with_index_lock(&index_path, move || async move {
+			loop {
+				if !is_absent_or_expired(&path).await? {
+					return Ok(false);
+				}
+
+				let tombstone = unique_path(&path, "expired");
+				match fs::rename(&path, &tombstone).await {
+					Ok(()) => {
+						let displaced_live = !is_absent_or_expired(&tombstone).await?;
+						if displaced_live {
+							match fs::hard_link(&tombstone, &path).await {
+								Ok(()) => {},
+								Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {},
+								Err(e) => return Err(StorageError::Backend(e.to_string())),
+							}
+						}
+						fs::remove_file(&tombstone)
+							.await
+							.map_err(|e| StorageError::Backend(e.to_string()))?;
+						if displaced_live {
+							return Ok(false);
+						}
+					},
+					Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+					Err(e) => return Err(StorageError::Backend(e.to_string())),
+				}
+
+				match fs::hard_link(&source, &path).await {
+					Ok(()) => return Ok(true),
+					// Another writer linked its entry first
+					Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {},
+					Err(e) => return Err(StorageError::Backend(e.to_string())),
+				}
+			}
+		})
+		.await
+	}
+
 	/// Removes all expired files from storage
 	async fn cleanup_expired_files(&self) -> Result<usize, StorageError> {
 		let mut removed = 0;
@@ -504,6 +654,21 @@ impl FileStorage {
 	}
 }
 
+/// Returns a path next to `path` that is unique to this writer.
+fn unique_path(path: &Path, suffix: &str) -> PathBuf {
+	let id = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+	path.with_extension(format!("{}.{}.{}", std::process::id(), id, suffix))
+}
+
+/// Returns whether the file at `path` is missing or holds an expired entry.
+async fn is_absent_or_expired(path: &Path) -> Result<bool, StorageError> {
+	match fs::read(path).await {
+		Ok(data) => Ok(FileHeader::deserialize(&data).is_ok_and(|header| header.is_expired())),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(true),
+		Err(e) => Err(StorageError::Backend(e.to_string())),
+	}
+}
+
 #[async_trait]
 impl StorageInterface for FileStorage {
 	async fn get_bytes(&self, key: &str) -> Result<Vec<u8>, StorageError> {
@@ -579,20 +744,68 @@ impl StorageInterface for FileStorage {
 
 		// Update indexes if provided
 		if let Some(indexes) = indexes {
-			let namespace = key.split(':').next().unwrap_or("");
+			let (namespace, _) = split_key(key);
 			self.update_indexes(namespace, key, &indexes).await?;
 		}
 
 		Ok(())
 	}
 
+	async fn set_bytes_if_absent(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		indexes: Option<StorageIndexes>,
+		ttl: Option<Duration>,
+	) -> Result<bool, StorageError> {
+		let path = self.get_file_path(key);
+
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)
+				.await
+				.map_err(|e| StorageError::Backend(e.to_string()))?;
+		}
+
+		let ttl = ttl.unwrap_or_else(|| self.get_ttl_for_key(key));
+		let mut file_data = Vec::with_capacity(FileHeader::SIZE + value.len());
+		file_data.extend_from_slice(&FileHeader::new(ttl).serialize());
+		file_data.extend_from_slice(&value);
+
+		// Write to a temp file unique to this writer, then hard-link it into
+		// place. Linking fails if the file exists, so only one writer wins.
+		let temp_path = unique_path(&path, "tmp");
+		fs::write(&temp_path, file_data)
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))?;
+		let linked = match fs::hard_link(&temp_path, &path).await {
+			Ok(()) => Ok(true),
+			Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+				self.replace_expired(key, temp_path.clone(), path).await
+			},
+			Err(e) => Err(StorageError::Backend(e.to_string())),
+		};
+		fs::remove_file(&temp_path)
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))?;
+		if !linked? {
+			return Ok(false);
+		}
+
+		if let Some(indexes) = indexes {
+			let (namespace, _) = split_key(key);
+			self.update_indexes(namespace, key, &indexes).await?;
+		}
+
+		Ok(true)
+	}
+
 	async fn delete(&self, key: &str) -> Result<(), StorageError> {
 		let path = self.get_file_path(key);
 
 		match fs::remove_file(&path).await {
 			Ok(_) => {
 				// Also remove from indexes
-				let namespace = key.split(':').next().unwrap_or("");
+				let (namespace, _) = split_key(key);
 				self.remove_from_indexes(namespace, key).await?;
 				Ok(())
 			},
@@ -640,16 +853,7 @@ impl StorageInterface for FileStorage {
 		.await?;
 
 		let matching_keys: Vec<String> = match filter {
-			QueryFilter::All => {
-				// Return all keys from all indexes
-				let mut all_keys = HashSet::new();
-				for value_map in namespace_index.indexes.values() {
-					for keys in value_map.values() {
-						all_keys.extend(keys.clone());
-					}
-				}
-				all_keys.into_iter().collect()
-			},
+			QueryFilter::All => namespace_index.all_keys().into_iter().collect(),
 			QueryFilter::Equals(field, value) => namespace_index
 				.indexes
 				.get(&field)
@@ -689,6 +893,14 @@ impl StorageInterface for FileStorage {
 				}
 				keys.into_iter().collect()
 			},
+			QueryFilter::GreaterThan(field, value) => namespace_index
+				.range(&field, &value, true)
+				.into_iter()
+				.collect(),
+			QueryFilter::LessThan(field, value) => namespace_index
+				.range(&field, &value, false)
+				.into_iter()
+				.collect(),
 		};
 
 		// Filter out expired entries
@@ -736,34 +948,42 @@ impl FileStorageSchema {
 		let instance = Self;
 		instance.validate(config)
 	}
-}
 
-impl ConfigSchema for FileStorageSchema {
-	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
-		// Build TTL fields dynamically based on StorageKey variants
-		let mut optional_fields = vec![Field::new("storage_path", FieldType::String)];
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		let mut optional_fields = vec![Field::new("storage_path", FieldType::String)
+			.with_description("Base directory of the stored files")
+			.with_default("./data/storage")];
 
-		// Add TTL fields for each StorageKey
+		// Add a TTL field for each StorageKey
 		for storage_key in StorageKey::all() {
-			let field_name = format!("ttl_{}", storage_key.as_str());
-			optional_fields.push(Field::new(
-				field_name.clone(),
-				FieldType::Integer {
-					min: Some(0),
-					max: None,
-				},
-			));
+			optional_fields.push(
+				Field::new(
+					format!("ttl_{}", storage_key.as_str()),
+					FieldType::Integer {
+						min: Some(0),
+						max: None,
+					},
+				)
+				.with_description(format!(
+					"Seconds {} are kept, or 0 to keep them forever",
+					storage_key.as_str()
+				))
+				.with_default(0),
+			);
 		}
 
-		let schema = Schema::new(
-			vec![], // No required fields
-			optional_fields,
-		);
+		Schema::new(vec![], optional_fields)
+	}
+}
 
-		// First validate against schema
-		schema.validate(config)?;
+impl ConfigSchema for FileStorageSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		Self::config_fields().validate(config)
+	}
 
-		Ok(())
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -803,6 +1023,136 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_storage
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(FileStorageSchema)
+	}
 }
 
 impl crate::StorageRegistry for Registry {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_namespace_index_range_queries() {
+		let mut index = NamespaceIndex::default();
+		for (key, updated_at) in [("a", 100), ("b", 200), ("c", 300)] {
+			index.insert("status", &IndexType::Exact("Pending".into()), key);
+			index.insert("updated_at", &IndexType::Numeric(updated_at), key);
+			index.insert("id", &IndexType::Sorted(key.to_string()), key);
+		}
+
+		let above = index.range("updated_at", &serde_json::json!(100), true);
+		assert_eq!(above, HashSet::from(["b".to_string(), "c".to_string()]));
+		let below = index.range("id", &serde_json::json!("c"), false);
+		assert_eq!(below, HashSet::from(["a".to_string(), "b".to_string()]));
+		assert!(index
+			.range("status", &serde_json::json!(0), true)
+			.is_empty());
+
+		for key in ["a", "b", "c"] {
+			index.remove_key(key);
+		}
+		assert!(index.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_health_check_removes_probe() {
+		let dir = tempfile::tempdir().unwrap();
+		let storage = FileStorage::new(
+			dir.path().to_path_buf(),
+			TtlConfig::from_config(&toml::Value::Table(Default::default())),
+		);
+		storage.health_check("health:probe").await.unwrap();
+		assert!(!storage.exists("health:probe").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_store_if_absent_inserts_once() {
+		let dir = tempfile::tempdir().unwrap();
+		let storage = std::sync::Arc::new(FileStorage::new(
+			dir.path().to_path_buf(),
+			TtlConfig::from_config(&toml::Value::Table(Default::default())),
+		));
+
+		let writers: Vec<_> = (0..8u8)
+			.map(|i| {
+				let storage = storage.clone();
+				tokio::spawn(async move {
+					let inserted = storage
+						.set_bytes_if_absent("orders:1", vec![i], None, None)
+						.await
+						.unwrap();
+					(i, inserted)
+				})
+			})
+			.collect();
+		let mut winners = Vec::new();
+		for writer in writers {
+			let (i, inserted) = writer.await.unwrap();
+			if inserted {
+				winners.push(i);
+			}
+		}
+
+		assert_eq!(winners.len(), 1);
+		assert_eq!(
+			storage.get_bytes("orders:1").await.unwrap(),
+			vec![winners[0]]
+		);
+		assert!(!storage
+			.set_bytes_if_absent("orders:1", vec![42], None, None)
+			.await
+			.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_store_if_absent_replaces_expired_once() {
+		let dir = tempfile::tempdir().unwrap();
+		let storage = std::sync::Arc::new(FileStorage::new(
+			dir.path().to_path_buf(),
+			TtlConfig::from_config(&toml::Value::Table(Default::default())),
+		));
+
+		let mut header = FileHeader::new(Duration::from_secs(60));
+		header.expires_at = 1;
+		let mut expired = header.serialize().to_vec();
+		expired.push(0);
+		std::fs::write(storage.get_file_path("orders:1"), expired).unwrap();
+
+		let writers: Vec<_> = (1..=8u8)
+			.map(|i| {
+				let storage = storage.clone();
+				tokio::spawn(async move {
+					let inserted = storage
+						.set_bytes_if_absent("orders:1", vec![i], None, None)
+						.await
+						.unwrap();
+					(i, inserted)
+				})
+			})
+			.collect();
+		let mut winners = Vec::new();
+		for writer in writers {
+			let (i, inserted) = writer.await.unwrap();
+			if inserted {
+				winners.push(i);
+			}
+		}
+
+		assert_eq!(winners.len(), 1);
+		assert_eq!(
+			storage.get_bytes("orders:1").await.unwrap(),
+			vec![winners[0]]
+		);
+		// No tombstones or temp files are left behind
+		let mut files: Vec<_> = std::fs::read_dir(dir.path())
+			.unwrap()
+			.map(|entry| entry.unwrap().file_name())
+			.collect();
+		files.sort();
+		assert_eq!(files, ["orders.lock", "orders_1.bin"]);
+	}
+}
@@ -3,9 +3,9 @@
 //! This module provides a memory-based implementation of the StorageInterface trait,
 //! useful for testing and development scenarios where persistence is not required.
 
-use crate::{QueryFilter, StorageError, StorageIndexes, StorageInterface};
+use crate::{QueryFilter, StorageError, StorageHealthReport, StorageIndexes, StorageInterface};
 use async_trait::async_trait;
-use solver_types::{ConfigSchema, Schema, ValidationError};
+use solver_types::{ConfigSchema, Schema, SchemaDefinition, ValidationError};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,6 +17,9 @@ use tokio::sync::RwLock;
 /// providing fast access but no persistence across restarts.
 /// TTL and indexes are ignored as this is primarily for testing
 /// and has no recovery capability.
+///
+/// Clones share the same underlying store.
+#[derive(Clone)]
 pub struct MemoryStorage {
 	/// The in-memory store protected by a read-write lock.
 	store: Arc<RwLock<HashMap<String, Vec<u8>>>>,
@@ -57,6 +60,22 @@ impl StorageInterface for MemoryStorage {
 		Ok(())
 	}
 
+	async fn set_bytes_if_absent(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		_indexes: Option<StorageIndexes>,
+		_ttl: Option<Duration>,
+	) -> Result<bool, StorageError> {
+		// Checked and inserted under the same write lock
+		let mut store = self.store.write().await;
+		if store.contains_key(key) {
+			return Ok(false);
+		}
+		store.insert(key.to_string(), value);
+		Ok(true)
+	}
+
 	async fn delete(&self, key: &str) -> Result<(), StorageError> {
 		let mut store = self.store.write().await;
 		store.remove(key);
@@ -74,12 +93,21 @@ impl StorageInterface for MemoryStorage {
 
 	async fn query(
 		&self,
-		_namespace: &str,
-		_filter: QueryFilter,
+		namespace: &str,
+		filter: QueryFilter,
 	) -> Result<Vec<String>, StorageError> {
-		// Memory storage doesn't support recovery, so querying is not meaningful.
-		// Return empty for compatibility.
-		Ok(Vec::new())
+		// Indexes are not kept, so only listing a whole namespace is supported.
+		// Other filters return empty for compatibility.
+		if !matches!(filter, QueryFilter::All) {
+			return Ok(Vec::new());
+		}
+		let prefix = format!("{}:", namespace);
+		let store = self.store.read().await;
+		Ok(store
+			.keys()
+			.filter(|key| key.starts_with(&prefix))
+			.cloned()
+			.collect())
 	}
 
 	async fn get_batch(&self, keys: &[String]) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
@@ -94,6 +122,11 @@ impl StorageInterface for MemoryStorage {
 
 		Ok(results)
 	}
+
+	async fn health_check(&self, _probe_key: &str) -> Result<StorageHealthReport, StorageError> {
+		// Memory storage is always available
+		Ok(StorageHealthReport { latency_ms: 0 })
+	}
 }
 
 /// Configuration schema for MemoryStorage.
@@ -105,13 +138,21 @@ impl MemoryStorageSchema {
 		let instance = Self;
 		instance.validate(config)
 	}
+
+	/// Fields accepted by the configuration.
+	fn config_fields() -> Schema {
+		// Memory storage has no configuration
+		Schema::new(vec![], vec![])
+	}
 }
 
 impl ConfigSchema for MemoryStorageSchema {
-	fn validate(&self, _config: &toml::Value) -> Result<(), ValidationError> {
-		// Memory storage has no required configuration
-		let schema = Schema::new(vec![], vec![]);
-		schema.validate(_config)
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		Self::config_fields().validate(config)
+	}
+
+	fn schema(&self) -> SchemaDefinition {
+		Self::config_fields().definition()
 	}
 }
 
@@ -137,6 +178,10 @@ impl solver_types::ImplementationRegistry for Registry {
 	fn factory() -> Self::Factory {
 		create_storage
 	}
+
+	fn config_schema() -> Box<dyn ConfigSchema> {
+		Box::new(MemoryStorageSchema)
+	}
 }
 
 impl crate::StorageRegistry for Registry {}
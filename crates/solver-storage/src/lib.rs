@@ -6,8 +6,9 @@
 
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use solver_types::{ConfigSchema, ImplementationRegistry};
+use solver_types::{ConfigSchema, ImplementationRegistry, StorageKey};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -16,6 +17,7 @@ pub mod implementations {
 	pub mod file;
 	pub mod memory;
 }
+pub mod write_protected;
 
 /// Query filter for storage operations.
 ///
@@ -32,18 +34,40 @@ pub enum QueryFilter {
 	In(String, Vec<serde_json::Value>),
 	/// Match items where field is not in list of values.
 	NotIn(String, Vec<serde_json::Value>),
+	/// Match items where field is strictly greater than value.
+	///
+	/// Requires a numeric or sorted index on the field.
+	GreaterThan(String, serde_json::Value),
+	/// Match items where field is strictly less than value.
+	///
+	/// Requires a numeric or sorted index on the field.
+	LessThan(String, serde_json::Value),
 	/// Match all items.
 	All,
 }
 
+/// Indexed value of a field, determining how backends index it.
+///
+/// Exact indexes serve equality filters. Numeric and sorted indexes are kept
+/// in ordered structures so that range filters do not scan every value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IndexType {
+	/// Low-cardinality value matched exactly, such as a status.
+	Exact(serde_json::Value),
+	/// Number queried by range, such as a timestamp.
+	Numeric(u64),
+	/// String queried by lexicographic range.
+	Sorted(String),
+}
+
 /// Index values for a stored item.
 ///
 /// Provides field values that backends can use for efficient querying.
 /// Backends are responsible for maintaining their own index structures.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct StorageIndexes {
-	/// Field name -> value for indexing.
-	pub fields: HashMap<String, serde_json::Value>,
+	/// Field name -> indexed value.
+	pub fields: HashMap<String, IndexType>,
 }
 
 impl StorageIndexes {
@@ -52,14 +76,27 @@ impl StorageIndexes {
 		Self::default()
 	}
 
-	/// Adds a field to be indexed.
+	/// Adds a field to be indexed for exact matching.
 	pub fn with_field(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
 		self.fields.insert(
 			name.into(),
-			serde_json::to_value(value).unwrap_or(serde_json::Value::Null),
+			IndexType::Exact(serde_json::to_value(value).unwrap_or(serde_json::Value::Null)),
 		);
 		self
 	}
+
+	/// Adds a numeric field to be indexed for range queries.
+	pub fn with_numeric(mut self, name: impl Into<String>, value: u64) -> Self {
+		self.fields.insert(name.into(), IndexType::Numeric(value));
+		self
+	}
+
+	/// Adds a string field to be indexed for lexicographic range queries.
+	pub fn with_sorted(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.fields
+			.insert(name.into(), IndexType::Sorted(value.into()));
+		self
+	}
 }
 
 /// Errors that can occur during storage operations.
@@ -77,6 +114,53 @@ pub enum StorageError {
 	/// Error that occurs during configuration validation.
 	#[error("Configuration error: {0}")]
 	Configuration(String),
+	/// Error that occurs when modifying an entry of an append-only namespace.
+	#[error("Write protected: {0}")]
+	WriteProtected(String),
+}
+
+/// Namespace of the entry written and removed by storage health checks.
+pub const STORAGE_HEALTH_NAMESPACE: &str = "health";
+
+/// ID of the entry written and removed by storage health checks.
+pub const STORAGE_HEALTH_PROBE_ID: &str = "probe";
+
+/// Maximum time a storage health check may take before the backend is
+/// considered unavailable.
+pub const STORAGE_HEALTH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Gauge of the duration of the last storage health check.
+pub const STORAGE_HEALTH_GAUGE: &str = "storage_health_probe_duration_ms";
+
+/// Entry written by storage health checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageHealthProbe {
+	/// Time the probe was written, in nanoseconds since the Unix epoch.
+	pub written_at_nanos: u128,
+}
+
+/// Number of leading bytes of a corrupted entry included in its log message.
+const CORRUPTED_DUMP_BYTES: usize = 64;
+
+/// Entry that could not be deserialized, moved aside by
+/// [`StorageService::retrieve_or_tombstone`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorruptedEntry {
+	/// Full storage key the entry was stored under.
+	pub key: String,
+	/// The deserialization error.
+	pub error: String,
+	/// Raw bytes of the entry.
+	pub data: Vec<u8>,
+	/// Time the entry was moved aside, in seconds since the Unix epoch.
+	pub corrupted_at: u64,
+}
+
+/// Result of a successful storage health check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageHealthReport {
+	/// Time taken to write, read back and delete the probe entry.
+	pub latency_ms: u64,
 }
 
 /// Trait defining the low-level interface for storage backends.
@@ -104,6 +188,19 @@ pub trait StorageInterface: Send + Sync {
 		ttl: Option<Duration>,
 	) -> Result<(), StorageError>;
 
+	/// Atomically stores raw bytes only if the key does not exist yet.
+	///
+	/// Returns `true` if the value was inserted and `false` if the key
+	/// already existed, in which case the stored value is left untouched.
+	/// Concurrent writers of the same key must see exactly one insertion.
+	async fn set_bytes_if_absent(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		indexes: Option<StorageIndexes>,
+		ttl: Option<Duration>,
+	) -> Result<bool, StorageError>;
+
 	/// Deletes the value associated with the given key.
 	///
 	/// Implementations must also remove the key from any indexes.
@@ -138,6 +235,72 @@ pub trait StorageInterface: Send + Sync {
 	async fn cleanup_expired(&self) -> Result<usize, StorageError> {
 		Ok(0) // Default implementation for backends without TTL support
 	}
+
+	/// Verifies that the backend is reachable and writeable.
+	///
+	/// The default implementation writes a [`StorageHealthProbe`] to
+	/// `probe_key`, reads it back, compares it with what was written and
+	/// deletes it.
+	async fn health_check(&self, probe_key: &str) -> Result<StorageHealthReport, StorageError> {
+		let started = std::time::Instant::now();
+		let probe = StorageHealthProbe {
+			written_at_nanos: std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap_or_default()
+				.as_nanos(),
+		};
+		let bytes =
+			serde_json::to_vec(&probe).map_err(|e| StorageError::Serialization(e.to_string()))?;
+
+		self.set_bytes(probe_key, bytes.clone(), None, None)
+			.await
+			.map_err(|e| StorageError::Backend(format!("failed to write probe: {}", e)))?;
+		let read = self
+			.get_bytes(probe_key)
+			.await
+			.map_err(|e| StorageError::Backend(format!("failed to read probe: {}", e)))?;
+		if read != bytes {
+			return Err(StorageError::Backend(
+				"probe read back differs from the probe written".to_string(),
+			));
+		}
+		self.delete(probe_key)
+			.await
+			.map_err(|e| StorageError::Backend(format!("failed to delete probe: {}", e)))?;
+
+		Ok(StorageHealthReport {
+			latency_ms: started.elapsed().as_millis() as u64,
+		})
+	}
+}
+
+/// Splits a backend key into its namespace and ID.
+///
+/// Keys take the form `namespace:id`, or `prefix:namespace:id` for services
+/// scoped to a solver, whose namespace is then `prefix:namespace`. Namespaces
+/// are [`StorageKey`] names, which tells the two forms apart. IDs may contain
+/// colons.
+pub fn split_key(key: &str) -> (&str, &str) {
+	let Some((first, rest)) = key.split_once(':') else {
+		return (key, "");
+	};
+	if first.parse::<StorageKey>().is_err() {
+		if let Some((namespace, id)) = rest.split_once(':') {
+			if namespace.parse::<StorageKey>().is_ok() {
+				return (&key[..first.len() + 1 + namespace.len()], id);
+			}
+		}
+	}
+	(first, rest)
+}
+
+/// Returns the namespace of a backend key without the prefix of scoped
+/// services, so that settings of a namespace apply to every solver.
+pub fn unscoped_namespace(key: &str) -> &str {
+	let (namespace, _) = split_key(key);
+	namespace
+		.rsplit_once(':')
+		.map_or(namespace, |(_, namespace)| namespace)
 }
 
 /// Type alias for storage factory functions.
@@ -165,6 +328,19 @@ pub fn get_all_implementations() -> Vec<(&'static str, StorageFactory)> {
 	]
 }
 
+/// Get the configuration schemas of all registered storage implementations.
+///
+/// Returns a vector of (name, schema) tuples in the same order as
+/// [`get_all_implementations`].
+pub fn get_all_schemas() -> Vec<(&'static str, Box<dyn ConfigSchema>)> {
+	use implementations::{file, memory};
+
+	vec![
+		(file::Registry::NAME, file::Registry::config_schema()),
+		(memory::Registry::NAME, memory::Registry::config_schema()),
+	]
+}
+
 /// High-level storage service that provides typed operations.
 ///
 /// The StorageService wraps a low-level storage backend and provides
@@ -172,13 +348,45 @@ pub fn get_all_implementations() -> Vec<(&'static str, StorageFactory)> {
 /// automatic serialization/deserialization.
 pub struct StorageService {
 	/// The underlying storage backend implementation.
-	backend: Box<dyn StorageInterface>,
+	backend: Arc<dyn StorageInterface>,
+	/// Prefix applied to all namespaces, isolating solvers that share a backend.
+	prefix: Option<String>,
 }
 
 impl StorageService {
 	/// Creates a new StorageService with the specified backend.
-	pub fn new(backend: Box<dyn StorageInterface>) -> Self {
-		Self { backend }
+	///
+	/// With a `namespace_prefix`, all entries are isolated under that prefix,
+	/// so services sharing a backend never see each other's entries. Keys take
+	/// the form `prefix:namespace:id`, as described by [`split_key`].
+	pub fn new(backend: Box<dyn StorageInterface>, namespace_prefix: Option<String>) -> Self {
+		Self::with_backend(backend.into(), namespace_prefix)
+	}
+
+	fn with_backend(backend: Arc<dyn StorageInterface>, namespace_prefix: Option<String>) -> Self {
+		Self {
+			backend,
+			prefix: namespace_prefix,
+		}
+	}
+
+	/// Creates a StorageService sharing this backend whose entries are
+	/// isolated under the given solver ID.
+	pub fn scoped(&self, solver_id: &str) -> Self {
+		Self::with_backend(self.backend.clone(), Some(solver_id.to_string()))
+	}
+
+	/// Builds the backend namespace of a namespace, prefixed for scoped services.
+	fn namespace(&self, namespace: &str) -> String {
+		match &self.prefix {
+			Some(prefix) => format!("{}:{}", prefix, namespace),
+			None => namespace.to_string(),
+		}
+	}
+
+	/// Builds the backend key for an ID in a namespace.
+	fn key(&self, namespace: &str, id: &str) -> String {
+		format!("{}:{}", self.namespace(namespace), id)
 	}
 
 	/// Stores a serializable value with optional indexes and time-to-live.
@@ -193,7 +401,7 @@ impl StorageService {
 		indexes: Option<StorageIndexes>,
 		ttl: Option<Duration>,
 	) -> Result<(), StorageError> {
-		let key = format!("{}:{}", namespace, id);
+		let key = self.key(namespace, id);
 		let bytes =
 			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
 		self.backend.set_bytes(&key, bytes, indexes, ttl).await
@@ -211,6 +419,39 @@ impl StorageService {
 			.await
 	}
 
+	/// Stores a serializable value unless the ID already exists.
+	///
+	/// Returns `true` if the value was stored and `false` if a value with the
+	/// same ID was already present, which is left unchanged.
+	pub async fn store_if_absent<T: Serialize>(
+		&self,
+		namespace: &str,
+		id: &str,
+		data: &T,
+		indexes: Option<StorageIndexes>,
+	) -> Result<bool, StorageError> {
+		self.store_if_absent_with_ttl(namespace, id, data, indexes, None)
+			.await
+	}
+
+	/// Stores a serializable value with a time-to-live unless the ID already
+	/// exists.
+	pub async fn store_if_absent_with_ttl<T: Serialize>(
+		&self,
+		namespace: &str,
+		id: &str,
+		data: &T,
+		indexes: Option<StorageIndexes>,
+		ttl: Option<Duration>,
+	) -> Result<bool, StorageError> {
+		let key = self.key(namespace, id);
+		let bytes =
+			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		self.backend
+			.set_bytes_if_absent(&key, bytes, indexes, ttl)
+			.await
+	}
+
 	/// Retrieves and deserializes a value from storage.
 	///
 	/// The namespace and id are combined to form the lookup key.
@@ -220,16 +461,108 @@ impl StorageService {
 		namespace: &str,
 		id: &str,
 	) -> Result<T, StorageError> {
-		let key = format!("{}:{}", namespace, id);
+		let key = self.key(namespace, id);
 		let bytes = self.backend.get_bytes(&key).await?;
 		serde_json::from_slice(&bytes).map_err(|e| StorageError::Serialization(e.to_string()))
 	}
 
+	/// Retrieves and deserializes a value, moving it aside if it is corrupted.
+	///
+	/// An entry that fails to deserialize is stored as a [`CorruptedEntry`] in
+	/// the corrupted namespace and removed from its original key, after which
+	/// `StorageError::NotFound` is returned. This keeps a single bad entry from
+	/// failing every subsequent read.
+	pub async fn retrieve_or_tombstone<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		id: &str,
+	) -> Result<T, StorageError> {
+		let key = self.key(namespace, id);
+		let bytes = self.backend.get_bytes(&key).await?;
+		match serde_json::from_slice(&bytes) {
+			Ok(value) => Ok(value),
+			Err(e) => {
+				self.tombstone(&key, bytes, e.to_string()).await?;
+				Err(StorageError::NotFound)
+			},
+		}
+	}
+
+	/// Moves an entry that could not be deserialized to the corrupted
+	/// namespace and removes it from its key.
+	async fn tombstone(
+		&self,
+		key: &str,
+		bytes: Vec<u8>,
+		error: String,
+	) -> Result<(), StorageError> {
+		let dump: String = bytes
+			.iter()
+			.take(CORRUPTED_DUMP_BYTES)
+			.map(|b| format!("{:02x}", b))
+			.collect();
+		tracing::error!(
+			key = %key,
+			error = %error,
+			size = bytes.len(),
+			dump = %dump,
+			"Moving corrupted storage entry aside"
+		);
+
+		let entry = CorruptedEntry {
+			key: key.to_string(),
+			error,
+			data: bytes,
+			corrupted_at: std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap_or_default()
+				.as_secs(),
+		};
+		self.store(StorageKey::Corrupted.as_str(), key, &entry, None)
+			.await?;
+		self.backend.delete(key).await
+	}
+
+	/// Retrieves and deserializes several values of a namespace at once.
+	///
+	/// Uses a single batch lookup of the backend. Results are returned in the
+	/// order of `ids`, each with its own outcome, so that a missing entry
+	/// yields `StorageError::NotFound` without failing the other entries.
+	pub async fn retrieve_batch<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		ids: &[&str],
+	) -> Vec<(String, Result<T, StorageError>)> {
+		let keys: Vec<String> = ids.iter().map(|id| self.key(namespace, id)).collect();
+		let mut found: HashMap<String, Vec<u8>> = match self.backend.get_batch(&keys).await {
+			Ok(results) => results.into_iter().collect(),
+			Err(e) => {
+				let message = e.to_string();
+				return ids
+					.iter()
+					.map(|id| (id.to_string(), Err(StorageError::Backend(message.clone()))))
+					.collect();
+			},
+		};
+
+		ids.iter()
+			.zip(keys)
+			.map(|(id, key)| {
+				let result = match found.remove(&key) {
+					Some(bytes) => serde_json::from_slice(&bytes)
+						.map_err(|e| StorageError::Serialization(e.to_string())),
+					None => Err(StorageError::NotFound),
+				};
+				(id.to_string(), result)
+			})
+			.collect()
+	}
+
 	/// Removes a value from storage.
 	///
 	/// The namespace and id are combined to form the key to delete.
 	pub async fn remove(&self, namespace: &str, id: &str) -> Result<(), StorageError> {
-		let key = format!("{}:{}", namespace, id);
+		let key = self.key(namespace, id);
 		self.backend.delete(&key).await
 	}
 
@@ -245,7 +578,7 @@ impl StorageService {
 		data: &T,
 		indexes: Option<StorageIndexes>,
 	) -> Result<(), StorageError> {
-		let key = format!("{}:{}", namespace, id);
+		let key = self.key(namespace, id);
 
 		// Check if the key exists first
 		if !self.backend.exists(&key).await? {
@@ -262,14 +595,15 @@ impl StorageService {
 	/// The namespace and id are combined to form the lookup key.
 	/// Returns true if the key exists, false otherwise.
 	pub async fn exists(&self, namespace: &str, id: &str) -> Result<bool, StorageError> {
-		let key = format!("{}:{}", namespace, id);
+		let key = self.key(namespace, id);
 		self.backend.exists(&key).await
 	}
 
 	/// Removes expired entries from storage.
 	///
 	/// Returns the number of entries that were removed.
-	/// This is a no-op for backends that don't support TTL.
+	/// This is a no-op for backends that don't support TTL. Cleanup applies to
+	/// the whole backend, including entries of other scoped services.
 	pub async fn cleanup_expired(&self) -> Result<usize, StorageError> {
 		self.backend.cleanup_expired().await
 	}
@@ -286,7 +620,7 @@ impl StorageService {
 		indexes: Option<StorageIndexes>,
 		ttl: Option<Duration>,
 	) -> Result<(), StorageError> {
-		let key = format!("{}:{}", namespace, id);
+		let key = self.key(namespace, id);
 
 		// Check if the key exists first
 		if !self.backend.exists(&key).await? {
@@ -301,22 +635,53 @@ impl StorageService {
 	/// Query items in a namespace based on a filter.
 	///
 	/// Returns a list of deserialized items matching the filter criteria.
+	/// Items that fail to deserialize are skipped.
 	pub async fn query<T: DeserializeOwned>(
 		&self,
 		namespace: &str,
 		filter: QueryFilter,
 	) -> Result<Vec<(String, T)>, StorageError> {
-		let keys = self.backend.query(namespace, filter).await?;
+		self.query_items(namespace, filter, false).await
+	}
+
+	/// Query items in a namespace based on a filter, moving corrupted items aside.
+	///
+	/// Like [`query`](Self::query), but items that fail to deserialize are
+	/// moved to the corrupted namespace as by
+	/// [`retrieve_or_tombstone`](Self::retrieve_or_tombstone).
+	pub async fn query_or_tombstone<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		filter: QueryFilter,
+	) -> Result<Vec<(String, T)>, StorageError> {
+		self.query_items(namespace, filter, true).await
+	}
+
+	/// Queries and deserializes items, optionally tombstoning corrupted ones.
+	async fn query_items<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		filter: QueryFilter,
+		tombstone: bool,
+	) -> Result<Vec<(String, T)>, StorageError> {
+		let namespace = self.namespace(namespace);
+		let scope = format!("{}:", namespace);
+		let mut keys = self.backend.query(&namespace, filter).await?;
+		// Only entries of this service's own scope, never those of other
+		// solvers sharing the backend
+		keys.retain(|key| key.starts_with(&scope));
 
 		// Use batch retrieval for efficiency
 		let results = self.backend.get_batch(&keys).await?;
 
 		let mut items = Vec::new();
 		for (key, bytes) in results {
-			// Extract ID from key (format: "namespace:id")
-			let id = key.split(':').nth(1).unwrap_or(&key).to_string();
+			let id = key[scope.len()..].to_string();
 			match serde_json::from_slice::<T>(&bytes) {
 				Ok(item) => items.push((id, item)),
+				Err(e) if tombstone => {
+					self.tombstone(&key, bytes, e.to_string()).await?;
+				},
 				Err(e) => {
 					tracing::warn!("Failed to deserialize item {}: {}", key, e);
 					// Continue with other items rather than failing entirely
@@ -336,4 +701,171 @@ impl StorageService {
 	) -> Result<Vec<(String, T)>, StorageError> {
 		self.query(namespace, QueryFilter::All).await
 	}
+
+	/// Checks that the backend is reachable and writeable.
+	///
+	/// Fails if the check takes longer than [`STORAGE_HEALTH_TIMEOUT`]. The
+	/// latency of successful checks is exported as [`STORAGE_HEALTH_GAUGE`].
+	pub async fn health_check(&self) -> Result<StorageHealthReport, StorageError> {
+		let probe_key = self.key(STORAGE_HEALTH_NAMESPACE, STORAGE_HEALTH_PROBE_ID);
+		let report = tokio::time::timeout(
+			STORAGE_HEALTH_TIMEOUT,
+			self.backend.health_check(&probe_key),
+		)
+		.await
+		.map_err(|_| {
+			StorageError::Backend(format!(
+				"health check timed out after {}ms",
+				STORAGE_HEALTH_TIMEOUT.as_millis()
+			))
+		})??;
+		metrics::gauge!(STORAGE_HEALTH_GAUGE).set(report.latency_ms as f64);
+		Ok(report)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::implementations::memory::MemoryStorage;
+
+	#[tokio::test]
+	async fn test_scoped_services_are_isolated() {
+		let backend = MemoryStorage::new();
+		let storage = StorageService::new(Box::new(backend.clone()), None);
+		let alpha = storage.scoped("alpha");
+		let beta = storage.scoped("beta");
+
+		alpha.store("orders", "1", &"alpha", None).await.unwrap();
+
+		assert_eq!(
+			alpha.retrieve::<String>("orders", "1").await.unwrap(),
+			"alpha"
+		);
+		assert!(!beta.exists("orders", "1").await.unwrap());
+		assert!(!storage.exists("orders", "1").await.unwrap());
+		assert!(storage
+			.retrieve_all::<String>("orders")
+			.await
+			.unwrap()
+			.is_empty());
+		assert!(backend.exists("alpha:orders:1").await.unwrap());
+	}
+
+	#[test]
+	fn test_split_key() {
+		assert_eq!(split_key("orders:1"), ("orders", "1"));
+		assert_eq!(split_key("alpha:orders:1"), ("alpha:orders", "1"));
+		assert_eq!(split_key("quote_fills:q:0"), ("quote_fills", "q:0"));
+		assert_eq!(
+			split_key("alpha:corrupted:alpha:orders:1"),
+			("alpha:corrupted", "alpha:orders:1")
+		);
+		assert_eq!(split_key("health:probe"), ("health", "probe"));
+		assert_eq!(unscoped_namespace("alpha:audit_log:1"), "audit_log");
+		assert_eq!(unscoped_namespace("audit_log:1"), "audit_log");
+	}
+
+	#[tokio::test]
+	async fn test_retrieve_or_tombstone_moves_corrupted_entries() {
+		let storage = StorageService::new(Box::new(MemoryStorage::new()), None);
+		storage.store("orders", "1", &"one", None).await.unwrap();
+		storage.store("orders", "2", &2, None).await.unwrap();
+
+		assert_eq!(
+			storage
+				.retrieve_or_tombstone::<String>("orders", "1")
+				.await
+				.unwrap(),
+			"one"
+		);
+		assert!(matches!(
+			storage.retrieve_or_tombstone::<String>("orders", "2").await,
+			Err(StorageError::NotFound)
+		));
+		assert!(!storage.exists("orders", "2").await.unwrap());
+
+		let entry: CorruptedEntry = storage
+			.retrieve(StorageKey::Corrupted.as_str(), "orders:2")
+			.await
+			.unwrap();
+		assert_eq!(entry.key, "orders:2");
+		assert_eq!(entry.data, b"2");
+	}
+
+	#[tokio::test]
+	async fn test_query_or_tombstone_moves_corrupted_entries() {
+		let storage = StorageService::new(Box::new(MemoryStorage::new()), None).scoped("alpha");
+		storage.store("orders", "1", &"one", None).await.unwrap();
+		storage.store("orders", "2", &2, None).await.unwrap();
+
+		let items = storage
+			.query_or_tombstone::<String>("orders", QueryFilter::All)
+			.await
+			.unwrap();
+		assert_eq!(items, vec![("1".to_string(), "one".to_string())]);
+		assert!(!storage.exists("orders", "2").await.unwrap());
+		assert!(storage
+			.exists(StorageKey::Corrupted.as_str(), "alpha:orders:2")
+			.await
+			.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_retrieve_batch_reports_missing_entries() {
+		let storage = StorageService::new(Box::new(MemoryStorage::new()), None).scoped("alpha");
+		storage.store("orders", "1", &"one", None).await.unwrap();
+		storage.store("orders", "3", &3, None).await.unwrap();
+
+		let results = storage
+			.retrieve_batch::<String>("orders", &["3", "2", "1"])
+			.await;
+		let ids: Vec<_> = results.iter().map(|(id, _)| id.as_str()).collect();
+		assert_eq!(ids, ["3", "2", "1"]);
+		assert!(matches!(results[0].1, Err(StorageError::Serialization(_))));
+		assert!(matches!(results[1].1, Err(StorageError::NotFound)));
+		assert_eq!(results[2].1.as_ref().unwrap(), "one");
+
+		assert!(storage
+			.retrieve_batch::<String>("orders", &[])
+			.await
+			.is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_prefixed_services_sharing_backend_are_isolated() {
+		let backend = MemoryStorage::new();
+		let alpha = StorageService::new(Box::new(backend.clone()), Some("alpha".to_string()));
+		let beta = StorageService::new(Box::new(backend), Some("beta".to_string()));
+
+		alpha.store("orders", "1", &"alpha", None).await.unwrap();
+		beta.store("orders", "1", &"beta", None).await.unwrap();
+		beta.store("orders", "2", &"beta", None).await.unwrap();
+
+		assert_eq!(
+			alpha.retrieve::<String>("orders", "1").await.unwrap(),
+			"alpha"
+		);
+		assert_eq!(
+			beta.retrieve::<String>("orders", "1").await.unwrap(),
+			"beta"
+		);
+		assert!(!alpha.exists("orders", "2").await.unwrap());
+
+		let alpha_orders = alpha.retrieve_all::<String>("orders").await.unwrap();
+		assert_eq!(alpha_orders, vec![("1".to_string(), "alpha".to_string())]);
+		let mut beta_orders = beta.retrieve_all::<String>("orders").await.unwrap();
+		beta_orders.sort();
+		assert_eq!(
+			beta_orders,
+			vec![
+				("1".to_string(), "beta".to_string()),
+				("2".to_string(), "beta".to_string())
+			]
+		);
+
+		alpha.remove("orders", "1").await.unwrap();
+		assert!(!alpha.exists("orders", "1").await.unwrap());
+		assert!(beta.exists("orders", "1").await.unwrap());
+	}
 }
@@ -0,0 +1,170 @@
+//! Append-only protection of storage namespaces.
+//!
+//! Wraps a storage backend so that entries of protected namespaces can be
+//! inserted but never overwritten or deleted, e.g. for audit records that
+//! must stay unaltered once written.
+
+use crate::{
+	unscoped_namespace, QueryFilter, StorageError, StorageHealthReport, StorageIndexes,
+	StorageInterface,
+};
+use async_trait::async_trait;
+use solver_types::ConfigSchema;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Storage backend rejecting updates and deletions in protected namespaces.
+pub struct WriteProtectedStorage {
+	inner: Box<dyn StorageInterface>,
+	/// Namespaces whose entries are append-only.
+	protected: HashSet<String>,
+}
+
+impl WriteProtectedStorage {
+	/// Wraps a backend, making the given namespaces append-only.
+	pub fn new<'a>(
+		inner: Box<dyn StorageInterface>,
+		protected: impl IntoIterator<Item = &'a str>,
+	) -> Self {
+		Self {
+			inner,
+			protected: protected.into_iter().map(str::to_string).collect(),
+		}
+	}
+
+	/// Returns the protected namespace of a key, if any.
+	fn protected_namespace<'k>(&self, key: &'k str) -> Option<&'k str> {
+		Some(unscoped_namespace(key)).filter(|namespace| self.protected.contains(*namespace))
+	}
+}
+
+#[async_trait]
+impl StorageInterface for WriteProtectedStorage {
+	async fn get_bytes(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+		self.inner.get_bytes(key).await
+	}
+
+	async fn set_bytes(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		indexes: Option<StorageIndexes>,
+		ttl: Option<Duration>,
+	) -> Result<(), StorageError> {
+		let Some(namespace) = self.protected_namespace(key) else {
+			return self.inner.set_bytes(key, value, indexes, ttl).await;
+		};
+		// Entries of protected namespaces are only ever inserted
+		if self
+			.inner
+			.set_bytes_if_absent(key, value, indexes, ttl)
+			.await?
+		{
+			Ok(())
+		} else {
+			Err(StorageError::WriteProtected(format!(
+				"entries of '{}' cannot be overwritten",
+				namespace
+			)))
+		}
+	}
+
+	async fn set_bytes_if_absent(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		indexes: Option<StorageIndexes>,
+		ttl: Option<Duration>,
+	) -> Result<bool, StorageError> {
+		self.inner
+			.set_bytes_if_absent(key, value, indexes, ttl)
+			.await
+	}
+
+	async fn delete(&self, key: &str) -> Result<(), StorageError> {
+		if let Some(namespace) = self.protected_namespace(key) {
+			return Err(StorageError::WriteProtected(format!(
+				"entries of '{}' cannot be deleted",
+				namespace
+			)));
+		}
+		self.inner.delete(key).await
+	}
+
+	async fn exists(&self, key: &str) -> Result<bool, StorageError> {
+		self.inner.exists(key).await
+	}
+
+	async fn query(
+		&self,
+		namespace: &str,
+		filter: QueryFilter,
+	) -> Result<Vec<String>, StorageError> {
+		self.inner.query(namespace, filter).await
+	}
+
+	async fn get_batch(&self, keys: &[String]) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
+		self.inner.get_batch(keys).await
+	}
+
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		self.inner.config_schema()
+	}
+
+	/// Does nothing, as cleanup of the inner backend would also delete
+	/// expired entries of protected namespaces.
+	async fn cleanup_expired(&self) -> Result<usize, StorageError> {
+		Ok(0)
+	}
+
+	async fn health_check(&self, probe_key: &str) -> Result<StorageHealthReport, StorageError> {
+		self.inner.health_check(probe_key).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::implementations::memory::MemoryStorage;
+	use crate::StorageService;
+
+	#[tokio::test]
+	async fn test_protected_namespace_is_append_only() {
+		let storage = StorageService::new(
+			Box::new(WriteProtectedStorage::new(
+				Box::new(MemoryStorage::new()),
+				["audit_log"],
+			)),
+			None,
+		);
+
+		storage.store("audit_log", "a", &1, None).await.unwrap();
+		assert!(matches!(
+			storage.store("audit_log", "a", &2, None).await,
+			Err(StorageError::WriteProtected(_))
+		));
+		assert!(matches!(
+			storage.update("audit_log", "a", &2, None).await,
+			Err(StorageError::WriteProtected(_))
+		));
+		assert!(matches!(
+			storage.remove("audit_log", "a").await,
+			Err(StorageError::WriteProtected(_))
+		));
+		assert_eq!(storage.retrieve::<u32>("audit_log", "a").await.unwrap(), 1);
+		assert_eq!(storage.cleanup_expired().await.unwrap(), 0);
+
+		// The namespace is protected for scoped services too
+		let scoped = storage.scoped("alpha");
+		scoped.store("audit_log", "a", &1, None).await.unwrap();
+		assert!(matches!(
+			scoped.store("audit_log", "a", &2, None).await,
+			Err(StorageError::WriteProtected(_))
+		));
+
+		// Other namespaces remain writable
+		storage.store("orders", "a", &1, None).await.unwrap();
+		storage.update("orders", "a", &2, None).await.unwrap();
+		storage.remove("orders", "a").await.unwrap();
+	}
+}
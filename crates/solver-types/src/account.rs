@@ -3,11 +3,16 @@
 //! This module defines types for blockchain addresses, signatures, and transactions
 //! that are used throughout the solver for account management and transaction processing.
 
-use crate::with_0x_prefix;
+use crate::{format_token_amount, with_0x_prefix};
 use alloy_primitives::{Address as AlloyAddress, Bytes, PrimitiveSignature, U256};
 use alloy_rpc_types::TransactionRequest;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+use thiserror::Error;
 
 /// Blockchain address representation.
 ///
@@ -56,6 +61,17 @@ impl fmt::Display for Address {
 	}
 }
 
+/// Account loaded by the solver, as listed by the admin API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountInfo {
+	/// Name of the account implementation, e.g. `local`.
+	pub name: String,
+	/// Address of the account.
+	pub address: Address,
+	/// Operations supported by the account, e.g. `sign_transaction`.
+	pub capabilities: Vec<String>,
+}
+
 /// Cryptographic signature representation.
 ///
 /// Stores signatures as raw bytes in the standard Ethereum format (r, s, v).
@@ -76,6 +92,17 @@ impl From<PrimitiveSignature> for Signature {
 	}
 }
 
+/// Signed EIP-7702 authorization tuple `(chain_id, address, nonce)`, delegating
+/// the code of the signing account to the contract at `address`.
+pub type Authorization = alloy_eips::eip7702::SignedAuthorization;
+
+/// Returns the code of an account delegating to `delegate` through EIP-7702.
+pub fn delegation_designator(delegate: &Address) -> Vec<u8> {
+	let mut code = vec![0xef, 0x01, 0x00];
+	code.extend_from_slice(&delegate.0);
+	code
+}
+
 /// Blockchain transaction representation.
 ///
 /// Contains all fields necessary for constructing and submitting transactions
@@ -100,6 +127,70 @@ pub struct Transaction {
 	pub max_fee_per_gas: Option<u128>,
 	/// Maximum priority fee per gas (EIP-1559).
 	pub max_priority_fee_per_gas: Option<u128>,
+	/// Optional reconciliation memo (up to 32 bytes), typically the order ID.
+	///
+	/// Delivery implementations may append a short tag derived from the memo
+	/// after the calldata so transactions can be matched to orders externally.
+	pub memo: Option<Vec<u8>>,
+	/// EIP-7702 authorizations to process before executing the transaction.
+	pub authorization_list: Option<Vec<Authorization>>,
+}
+
+/// Maximum length of a transaction memo in bytes.
+pub const MAX_TRANSACTION_MEMO_LENGTH: usize = 32;
+
+/// Length of the tag appended to calldata when transaction tagging is enabled.
+pub const TRANSACTION_TAG_LENGTH: usize = 4;
+
+impl Transaction {
+	/// Sets the reconciliation memo, truncating it to 32 bytes.
+	pub fn with_memo(mut self, memo: impl AsRef<[u8]>) -> Self {
+		let memo = memo.as_ref();
+		let len = memo.len().min(MAX_TRANSACTION_MEMO_LENGTH);
+		self.memo = Some(memo[..len].to_vec());
+		self
+	}
+
+	/// Returns the calldata with the memo tag appended.
+	///
+	/// The tag is the first 4 bytes of the memo, placed after the ABI-encoded
+	/// arguments. Contracts ignore trailing calldata, so the function selector
+	/// and argument decoding are unaffected. Memos shorter than 4 bytes are
+	/// appended whole, and the calldata is returned unchanged if the memo is
+	/// missing or empty.
+	pub fn tagged_data(&self) -> Vec<u8> {
+		let mut data = self.data.clone();
+		if let Some(memo) = self.memo.as_ref().filter(|m| !m.is_empty()) {
+			let len = memo.len().min(TRANSACTION_TAG_LENGTH);
+			data.extend_from_slice(&memo[..len]);
+		}
+		data
+	}
+}
+
+/// A contract call of an EIP-5792 call batch.
+///
+/// Calls of a batch are executed in order by a single wallet transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+	/// Address of the called contract.
+	pub to: Address,
+	/// Calldata of the call.
+	pub data: Vec<u8>,
+	/// Value to transfer in native currency.
+	pub value: U256,
+}
+
+/// Conversion of a transaction into a batch call, dropping its fee and nonce
+/// fields which are set for the whole batch.
+impl From<&Transaction> for Call {
+	fn from(tx: &Transaction) -> Self {
+		Call {
+			to: tx.to.clone().unwrap_or(Address(vec![0; 20])),
+			data: tx.data.clone(),
+			value: tx.value,
+		}
+	}
 }
 
 /// Conversion from Alloy's TransactionRequest to our Transaction type.
@@ -118,6 +209,8 @@ impl From<TransactionRequest> for Transaction {
 			gas_price: req.gas_price,
 			max_fee_per_gas: req.max_fee_per_gas,
 			max_priority_fee_per_gas: req.max_priority_fee_per_gas,
+			memo: None,
+			authorization_list: req.authorization_list,
 		}
 	}
 }
@@ -144,7 +237,148 @@ impl From<Transaction> for TransactionRequest {
 				input: Some(Bytes::from(tx.data)),
 				data: None,
 			},
+			authorization_list: tx.authorization_list,
 			..Default::default()
 		}
 	}
 }
+
+/// Errors that can occur when valuing token amounts.
+#[derive(Debug, Error)]
+pub enum AmountError {
+	/// The price is not a valid decimal number.
+	#[error("Invalid price: {0}")]
+	InvalidPrice(String),
+	/// The value does not fit in a decimal.
+	#[error("Amount overflow")]
+	Overflow,
+}
+
+/// Raw token amount with the decimals of its token.
+///
+/// Amounts are kept as integers of the token's smallest unit, so formatting
+/// and valuation never go through floating point. Amounts are only ordered
+/// against amounts of the same decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAmount {
+	/// Amount in the token's smallest unit.
+	pub amount: U256,
+	/// Number of decimals of the token.
+	pub decimals: u8,
+}
+
+impl TokenAmount {
+	/// Creates a new TokenAmount.
+	pub fn new(amount: U256, decimals: u8) -> Self {
+		Self { amount, decimals }
+	}
+
+	/// Returns the USD value of the amount at the given USD price per token.
+	pub fn to_usd(&self, price_usd: &str) -> Result<Decimal, AmountError> {
+		let price = Decimal::from_str(price_usd)
+			.map_err(|e| AmountError::InvalidPrice(format!("{}: {}", price_usd, e)))?;
+		let units = Decimal::from_str(&self.to_string()).map_err(|_| AmountError::Overflow)?;
+		units.checked_mul(price).ok_or(AmountError::Overflow)
+	}
+}
+
+impl fmt::Display for TokenAmount {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"{}",
+			format_token_amount(&self.amount.to_string(), self.decimals)
+		)
+	}
+}
+
+impl Add for TokenAmount {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		debug_assert_eq!(self.decimals, rhs.decimals);
+		Self::new(self.amount + rhs.amount, self.decimals)
+	}
+}
+
+impl Sub for TokenAmount {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		debug_assert_eq!(self.decimals, rhs.decimals);
+		Self::new(self.amount - rhs.amount, self.decimals)
+	}
+}
+
+impl Mul<u64> for TokenAmount {
+	type Output = Self;
+
+	fn mul(self, rhs: u64) -> Self {
+		Self::new(self.amount * U256::from(rhs), self.decimals)
+	}
+}
+
+impl PartialOrd for TokenAmount {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		(self.decimals == other.decimals).then(|| self.amount.cmp(&other.amount))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_token_amount() {
+		let usdc = TokenAmount::new(U256::from(1_500_000), 6);
+		assert_eq!(usdc.to_string(), "1.5");
+		assert_eq!((usdc + usdc).to_string(), "3");
+		assert_eq!((usdc * 3 - usdc).to_string(), "3");
+		assert!(usdc < usdc * 2);
+		assert_eq!(
+			usdc.partial_cmp(&TokenAmount::new(U256::from(1_500_000), 18)),
+			None
+		);
+
+		let eth = TokenAmount::new(U256::from(10).pow(U256::from(18)), 18);
+		assert_eq!(eth.to_string(), "1");
+		assert_eq!(
+			eth.to_usd("3000.25").unwrap(),
+			Decimal::from_str("3000.25").unwrap()
+		);
+		assert_eq!(usdc.to_usd("1").unwrap(), Decimal::from_str("1.5").unwrap());
+		assert!(usdc.to_usd("not a price").is_err());
+	}
+
+	fn transaction(data: &[u8]) -> Transaction {
+		let mut tx = Transaction::from(TransactionRequest::default());
+		tx.data = data.to_vec();
+		tx
+	}
+
+	#[test]
+	fn test_with_memo_truncates_to_max_length() {
+		let tx = transaction(&[]).with_memo([0xab; 40]);
+		assert_eq!(tx.memo, Some(vec![0xab; MAX_TRANSACTION_MEMO_LENGTH]));
+
+		let tx = transaction(&[]).with_memo([]);
+		assert_eq!(tx.memo, Some(Vec::new()));
+	}
+
+	#[test]
+	fn test_tagged_data_appends_memo_prefix() {
+		let calldata = [0x12, 0x34, 0x56, 0x78, 0x9a];
+
+		let tx = transaction(&calldata).with_memo([1, 2, 3, 4, 5, 6]);
+		assert_eq!(tx.tagged_data(), [&calldata[..], &[1, 2, 3, 4]].concat());
+
+		// Memos shorter than the tag are appended whole
+		let tx = transaction(&calldata).with_memo([1, 2]);
+		assert_eq!(tx.tagged_data(), [&calldata[..], &[1, 2]].concat());
+
+		// Without a memo, or with an empty one, the calldata is unchanged
+		assert_eq!(transaction(&calldata).tagged_data(), calldata);
+		let tx = transaction(&calldata).with_memo([]);
+		assert_eq!(tx.tagged_data(), calldata);
+	}
+}
@@ -140,6 +140,25 @@ pub struct Quote {
 	pub quote_id: String,
 	/// Provider identifier
 	pub provider: String, // not used by the solver, only relevant for the aggregator
+	/// Number of orders executed against this quote
+	#[serde(rename = "fillCount", default)]
+	pub fill_count: u32,
+}
+
+/// Token pair of GET /quotes, matching the first input and output of a quote.
+///
+/// All fields are required; they are optional only so that a missing one is
+/// reported as an invalid request.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct QuoteListQuery {
+	/// Chain of the quote's input.
+	pub input_chain: Option<u64>,
+	/// Chain of the quote's output.
+	pub output_chain: Option<u64>,
+	/// Input token address.
+	pub input_token: Option<String>,
+	/// Output token address.
+	pub output_token: Option<String>,
 }
 
 /// Settlement mechanism types.
@@ -157,6 +176,17 @@ pub struct GetQuoteResponse {
 	pub quotes: Vec<Quote>,
 }
 
+/// Outcome of one request of a batch quote request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchQuoteResult {
+	/// Position of the request in the batch
+	pub request_index: usize,
+	/// Available quotes, empty if the request failed
+	pub quotes: Vec<Quote>,
+	/// Reason the request failed
+	pub error: Option<String>,
+}
+
 /// Response containing order details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetOrderResponse {
@@ -164,6 +194,210 @@ pub struct GetOrderResponse {
 	pub order: crate::order::OrderResponse,
 }
 
+/// Result of previewing an intent via POST /admin/order/preview.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewResult {
+	/// Whether filling the order is expected to be profitable after gas.
+	pub profitable: bool,
+	/// Expected profit in USD, net of gas costs.
+	pub estimated_profit_usd: f64,
+	/// Expected gas cost of the fill transaction in USD.
+	pub estimated_gas_cost_usd: f64,
+	/// Estimated gas units for the fill transaction.
+	pub gas_units: u64,
+	/// Decision made by the configured execution strategy.
+	pub strategy_decision: String,
+}
+
+/// Query parameters of GET /analytics/orders.
+///
+/// All filters are optional; timestamps bound the order creation time and
+/// are inclusive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderAnalyticsQuery {
+	/// Earliest order creation timestamp.
+	pub from_ts: Option<u64>,
+	/// Latest order creation timestamp.
+	pub to_ts: Option<u64>,
+	/// Order status, e.g. "Finalized".
+	pub status: Option<String>,
+	/// Origin chain of the orders.
+	pub chain_id: Option<u64>,
+	/// Order standard, e.g. "eip7683".
+	pub standard: Option<String>,
+}
+
+/// Aggregated order statistics returned by GET /analytics/orders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderAnalytics {
+	/// Number of matching orders.
+	pub count: usize,
+	/// USD value of the inputs of the matching orders, at current prices.
+	/// Orders whose tokens cannot be priced are not included.
+	pub total_input_volume_usd: f64,
+	/// USD value of the outputs of the matching orders, at current prices.
+	pub total_output_volume_usd: f64,
+	/// Average time from order creation to fill, over filled orders.
+	pub avg_fill_time_seconds: Option<f64>,
+	/// Percentage of settled orders among settled and failed orders.
+	pub success_rate_pct: Option<f64>,
+}
+
+/// Output formats of GET /admin/report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+	#[default]
+	Json,
+	Csv,
+}
+
+/// Query parameters of GET /admin/report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportQuery {
+	/// UTC day of the report, as `YYYY-MM-DD`.
+	pub date: String,
+	/// Output format, JSON by default.
+	#[serde(default)]
+	pub format: ReportFormat,
+}
+
+/// Query parameters of GET /admin/audit.
+///
+/// Timestamps bound the time the fills were recorded and are inclusive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditQuery {
+	/// Earliest record timestamp.
+	pub from_ts: Option<u64>,
+	/// Latest record timestamp.
+	pub to_ts: Option<u64>,
+	/// Number of records to skip, oldest first.
+	#[serde(default)]
+	pub offset: usize,
+	/// Maximum number of records returned.
+	pub limit: Option<usize>,
+}
+
+/// Query parameters of GET /admin/events/log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventLogQuery {
+	/// Lowest sequence number returned.
+	#[serde(default)]
+	pub from_seq: u64,
+	/// Maximum number of events returned.
+	pub limit: Option<usize>,
+}
+
+/// Number of orders touching a chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainActivity {
+	pub chain_id: u64,
+	/// Orders with an input or output on the chain.
+	pub orders: usize,
+}
+
+/// Profit made on a token pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairProfit {
+	/// Token pair as `origin_chain:input_token:destination_chain:output_token`.
+	pub pair: String,
+	/// Number of filled orders of the pair.
+	pub fills: usize,
+	/// Revenue of the fills net of their gas spend.
+	pub net_pnl_usd: f64,
+}
+
+/// Daily solver report returned by GET /admin/report.
+///
+/// Orders are attributed to the day they were created on, gas spend and
+/// skipped orders to the day they happened on. Values are priced with the
+/// recorded price history, so amounts without a price snapshot are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyReport {
+	/// UTC day of the report, as `YYYY-MM-DD`.
+	pub date: String,
+	pub intents_received: usize,
+	pub intents_filled: usize,
+	pub intents_skipped: usize,
+	pub intents_failed: usize,
+	/// Input value of the filled orders minus their output value.
+	pub gross_revenue_usd: f64,
+	/// Gas spent by the solver's transactions.
+	pub gas_spend_usd: f64,
+	pub net_pnl_usd: f64,
+	/// Chains with the most orders, most active first.
+	pub most_active_chains: Vec<ChainActivity>,
+	/// Token pairs with the highest net PnL, most profitable first.
+	pub most_profitable_pairs: Vec<PairProfit>,
+}
+
+impl DailyReport {
+	/// Formats the report as `section,key,value` CSV rows for spreadsheet import.
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::from("section,key,value\n");
+		let summary = [
+			("date", self.date.clone()),
+			("intents_received", self.intents_received.to_string()),
+			("intents_filled", self.intents_filled.to_string()),
+			("intents_skipped", self.intents_skipped.to_string()),
+			("intents_failed", self.intents_failed.to_string()),
+			("gross_revenue_usd", self.gross_revenue_usd.to_string()),
+			("gas_spend_usd", self.gas_spend_usd.to_string()),
+			("net_pnl_usd", self.net_pnl_usd.to_string()),
+		];
+		for (key, value) in summary {
+			csv.push_str(&format!("summary,{},{}\n", key, value));
+		}
+		for chain in &self.most_active_chains {
+			csv.push_str(&format!(
+				"chain_orders,{},{}\n",
+				chain.chain_id, chain.orders
+			));
+		}
+		for pair in &self.most_profitable_pairs {
+			csv.push_str(&format!("pair_fills,{},{}\n", pair.pair, pair.fills));
+			csv.push_str(&format!(
+				"pair_net_pnl_usd,{},{}\n",
+				pair.pair, pair.net_pnl_usd
+			));
+		}
+		csv
+	}
+}
+
+/// Signing schemes supported by POST /sign-payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningScheme {
+	Permit2,
+	Erc3009,
+}
+
+/// Request for the EIP-712 typed data of a quote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignPayloadRequest {
+	/// ID of the stored quote to sign.
+	pub quote_id: String,
+	/// Signing scheme of the quote order to sign.
+	pub signing_scheme: SigningScheme,
+}
+
+/// EIP-712 typed data in `eth_signTypedData_v4` format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignPayloadResponse {
+	/// EIP-712 domain
+	pub domain: serde_json::Value,
+	/// Type definitions, including `EIP712Domain`
+	pub types: serde_json::Value,
+	/// Primary type of the message
+	#[serde(rename = "primaryType")]
+	pub primary_type: String,
+	/// Message to be signed
+	pub message: serde_json::Value,
+	/// EIP-712 digest of the message, for signers that sign raw hashes
+	pub digest: String,
+}
+
 /// API error response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
@@ -187,6 +421,14 @@ pub enum APIError {
 		message: String,
 		details: Option<serde_json::Value>,
 	},
+	/// Missing or invalid credentials (401)
+	Unauthorized { error_type: String, message: String },
+	/// Resource not found (404)
+	NotFound {
+		error_type: String,
+		message: String,
+		details: Option<serde_json::Value>,
+	},
 	/// Unprocessable entity for business logic failures (422)
 	UnprocessableEntity {
 		error_type: String,
@@ -208,6 +450,8 @@ impl APIError {
 	pub fn status_code(&self) -> u16 {
 		match self {
 			APIError::BadRequest { .. } => 400,
+			APIError::Unauthorized { .. } => 401,
+			APIError::NotFound { .. } => 404,
 			APIError::UnprocessableEntity { .. } => 422,
 			APIError::ServiceUnavailable { .. } => 503,
 			APIError::InternalServerError { .. } => 500,
@@ -227,6 +471,25 @@ impl APIError {
 				details: details.clone(),
 				retry_after: None,
 			},
+			APIError::Unauthorized {
+				error_type,
+				message,
+			} => ErrorResponse {
+				error: error_type.clone(),
+				message: message.clone(),
+				details: None,
+				retry_after: None,
+			},
+			APIError::NotFound {
+				error_type,
+				message,
+				details,
+			} => ErrorResponse {
+				error: error_type.clone(),
+				message: message.clone(),
+				details: details.clone(),
+				retry_after: None,
+			},
 			APIError::UnprocessableEntity {
 				error_type,
 				message,
@@ -264,6 +527,8 @@ impl fmt::Display for APIError {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		match self {
 			APIError::BadRequest { message, .. } => write!(f, "Bad Request: {}", message),
+			APIError::Unauthorized { message, .. } => write!(f, "Unauthorized: {}", message),
+			APIError::NotFound { message, .. } => write!(f, "Not Found: {}", message),
 			APIError::UnprocessableEntity { message, .. } => {
 				write!(f, "Unprocessable Entity: {}", message)
 			},
@@ -285,6 +550,8 @@ impl axum::response::IntoResponse for APIError {
 
 		let status = match self.status_code() {
 			400 => StatusCode::BAD_REQUEST,
+			401 => StatusCode::UNAUTHORIZED,
+			404 => StatusCode::NOT_FOUND,
 			422 => StatusCode::UNPROCESSABLE_ENTITY,
 			503 => StatusCode::SERVICE_UNAVAILABLE,
 			500 => StatusCode::INTERNAL_SERVER_ERROR,
@@ -402,3 +669,36 @@ impl From<GetOrderError> for APIError {
 		}
 	}
 }
+
+/// Errors that can occur while building a signing payload.
+#[derive(Debug, thiserror::Error)]
+pub enum SignPayloadError {
+	#[error("Quote not found: {0}")]
+	QuoteNotFound(String),
+	#[error("Invalid request: {0}")]
+	InvalidRequest(String),
+	#[error("Internal error: {0}")]
+	Internal(String),
+}
+
+/// Convert SignPayloadError to APIError with appropriate HTTP status codes.
+impl From<SignPayloadError> for APIError {
+	fn from(error: SignPayloadError) -> Self {
+		match error {
+			SignPayloadError::QuoteNotFound(id) => APIError::NotFound {
+				error_type: "QUOTE_NOT_FOUND".to_string(),
+				message: format!("Quote not found or expired: {}", id),
+				details: Some(serde_json::json!({ "quote_id": id })),
+			},
+			SignPayloadError::InvalidRequest(msg) => APIError::BadRequest {
+				error_type: "INVALID_REQUEST".to_string(),
+				message: msg,
+				details: None,
+			},
+			SignPayloadError::Internal(msg) => APIError::InternalServerError {
+				error_type: "INTERNAL_ERROR".to_string(),
+				message: format!("An internal error occurred: {}", msg),
+			},
+		}
+	}
+}
@@ -6,7 +6,7 @@
 /// Blockchain transaction hash representation.
 ///
 /// Stores transaction hashes as raw bytes to support different blockchain formats.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct TransactionHash(pub Vec<u8>);
 
 /// Transaction receipt containing execution details.
@@ -21,6 +21,12 @@ pub struct TransactionReceipt {
 	pub block_number: u64,
 	/// Whether the transaction executed successfully.
 	pub success: bool,
+	/// Gas consumed by the transaction.
+	#[serde(default)]
+	pub gas_used: u64,
+	/// Price paid per unit of gas in wei.
+	#[serde(default)]
+	pub effective_gas_price: u128,
 }
 
 /// Chain data structure containing current blockchain state information.
@@ -38,3 +44,143 @@ pub struct ChainData {
 	/// Timestamp when this data was fetched (Unix timestamp).
 	pub timestamp: u64,
 }
+
+/// Result of executing a transaction against the current chain state without submitting it.
+///
+/// Produced by `eth_call` and `eth_estimateGas`, or by `debug_traceCall` where
+/// the node supports it; a reverted call still yields a trace with the revert
+/// reason instead of an error.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TransactionTrace {
+	/// Whether the call executed without reverting.
+	pub success: bool,
+	/// Estimated gas usage, if the call succeeded.
+	pub gas_used: Option<u64>,
+	/// Raw data returned by the call (hex with 0x prefix).
+	pub return_data: String,
+	/// Decoded revert reason, if the call reverted.
+	pub revert_reason: Option<String>,
+	/// Logs emitted by the call, only available from `debug_traceCall`.
+	#[serde(default)]
+	pub logs: Vec<TraceLog>,
+}
+
+/// Log emitted during a simulated call.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TraceLog {
+	/// Address of the emitting contract (hex with 0x prefix).
+	pub address: String,
+	/// Log topics (hex with 0x prefix).
+	pub topics: Vec<String>,
+	/// Log data (hex with 0x prefix).
+	#[serde(default)]
+	pub data: String,
+}
+
+/// Output an order is expected to deliver when filled.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SimulatedOutput {
+	/// Chain ID where the output is delivered.
+	pub chain_id: u64,
+	/// Token address (hex with 0x prefix).
+	pub token: String,
+	/// Recipient address (hex with 0x prefix).
+	pub recipient: String,
+	/// Amount delivered as a decimal string.
+	pub amount: String,
+}
+
+/// Result of simulating an order fill.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SimulationResult {
+	/// Chain ID the fill transaction targets.
+	pub chain_id: u64,
+	/// Estimated gas for the fill transaction, if it would succeed.
+	pub estimated_gas: Option<u64>,
+	/// Outputs the fill is expected to deliver.
+	pub expected_outputs: Vec<SimulatedOutput>,
+	/// Revert reason, if the fill transaction would revert.
+	pub revert_reason: Option<String>,
+}
+
+/// Fee history of recent blocks, as returned by `eth_feeHistory`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FeeHistory {
+	/// Base fee per gas of each block in wei, followed by the base fee of the next block.
+	pub base_fee_per_gas: Vec<u128>,
+	/// Priority fee per gas in wei at each requested percentile, per block.
+	pub reward: Vec<Vec<u128>>,
+}
+
+/// How delivery sets the fees of submitted transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasStrategy {
+	/// Fees are estimated by the RPC provider.
+	#[default]
+	Provider,
+	/// Fees are bid from the recent fee history using the gas bid configuration.
+	Auto,
+}
+
+/// Chains delivery connects to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProviderMode {
+	/// The configured RPC endpoints of each network.
+	#[default]
+	Mainnet,
+	/// The configured RPC endpoints of each network, which are testnets.
+	/// With a fork block, each network is forked at that block on a local
+	/// Anvil node instead.
+	Testnet {
+		#[serde(default)]
+		fork_block: Option<u64>,
+	},
+	/// Fresh local Anvil nodes, one per network on consecutive ports starting
+	/// at `port`, started with the solver.
+	Anvil { port: u16 },
+}
+
+/// Configuration of fee bidding from the recent fee history.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GasBidConfig {
+	/// Percentile of recent priority fees to bid.
+	#[serde(default = "default_target_percentile")]
+	pub target_percentile: u8,
+	/// Multiplier applied to the base fee to absorb base fee increases.
+	#[serde(default = "default_base_fee_multiplier")]
+	pub base_fee_multiplier: f64,
+	/// Lower bound of the priority fee in gwei.
+	#[serde(default)]
+	pub min_priority_fee_gwei: u64,
+	/// Upper bound of the priority fee in gwei.
+	#[serde(default = "default_max_priority_fee_gwei")]
+	pub max_priority_fee_gwei: u64,
+}
+
+impl Default for GasBidConfig {
+	fn default() -> Self {
+		Self {
+			target_percentile: default_target_percentile(),
+			base_fee_multiplier: default_base_fee_multiplier(),
+			min_priority_fee_gwei: 0,
+			max_priority_fee_gwei: default_max_priority_fee_gwei(),
+		}
+	}
+}
+
+/// Returns the default priority fee percentile.
+fn default_target_percentile() -> u8 {
+	60
+}
+
+/// Returns the default base fee multiplier, covering one block of base fee increase.
+fn default_base_fee_multiplier() -> f64 {
+	1.25
+}
+
+/// Returns the default upper bound of the priority fee in gwei.
+fn default_max_priority_fee_gwei() -> u64 {
+	100
+}
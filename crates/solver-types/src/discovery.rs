@@ -37,4 +37,58 @@ pub struct IntentMetadata {
 	pub exclusive_until: Option<u64>,
 	/// Timestamp when this intent was discovered.
 	pub discovered_at: u64,
+	/// Tokens involved in the intent, populated by the enrichment pipeline.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub tokens: Vec<IntentToken>,
+	/// Number of the block containing the on-chain event that opened the intent.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub origin_block_number: Option<u64>,
+	/// Timestamp of the block containing the on-chain event that opened the intent.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub origin_block_timestamp: Option<u64>,
+}
+
+/// Information about a token involved in an intent.
+///
+/// Fields are filled in by intent enrichers and are `None` when no enricher
+/// provided them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentToken {
+	/// Chain ID where the token lives.
+	pub chain_id: u64,
+	/// Token address (hex with 0x prefix).
+	pub address: String,
+	/// Token symbol from the networks configuration.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub symbol: Option<String>,
+	/// Token decimals from the networks configuration.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub decimals: Option<u8>,
+	/// Price of one whole token in USD.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub price_usd: Option<f64>,
+}
+
+impl IntentMetadata {
+	/// Returns the entry for the given token, inserting an empty one if missing.
+	pub fn token_mut(&mut self, chain_id: u64, address: &str) -> &mut IntentToken {
+		let index = match self
+			.tokens
+			.iter()
+			.position(|t| t.chain_id == chain_id && t.address.eq_ignore_ascii_case(address))
+		{
+			Some(index) => index,
+			None => {
+				self.tokens.push(IntentToken {
+					chain_id,
+					address: address.to_string(),
+					symbol: None,
+					decimals: None,
+					price_usd: None,
+				});
+				self.tokens.len() - 1
+			},
+		};
+		&mut self.tokens[index]
+	}
 }
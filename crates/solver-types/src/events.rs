@@ -4,7 +4,10 @@
 //! communication between different components. Events flow through an event bus
 //! allowing services to react to state changes in other parts of the system.
 
-use crate::{ExecutionParams, FillProof, Intent, Order, TransactionHash, TransactionReceipt};
+use crate::{
+	Address, ExecutionParams, FillProof, Intent, Order, TransactionHash, TransactionReceipt,
+};
+use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -22,6 +25,35 @@ pub enum SolverEvent {
 	Delivery(DeliveryEvent),
 	/// Events from the settlement service.
 	Settlement(SettlementEvent),
+	/// An oracle failed its health probe and will be skipped during selection.
+	OracleUnhealthy {
+		chain_id: u64,
+		oracle_address: Address,
+	},
+	/// Gas spent on a chain in the last 24 hours reached 80% of its daily budget.
+	GasBudgetWarning {
+		chain_id: u64,
+		spent_wei: U256,
+		budget_wei: U256,
+	},
+	/// The same token is priced differently on two chains by more than the
+	/// configured spread.
+	ArbitrageOpportunity {
+		token: String,
+		from_chain: u64,
+		to_chain: u64,
+		spread_bps: u64,
+	},
+	/// The claim success rate of a settlement implementation dropped below
+	/// the configured minimum, and auto-claiming was disabled for it.
+	ClaimCircuitOpen { implementation_name: String },
+	/// Tokens were bridged to a chain whose available balance ran low.
+	RebalancingInitiated {
+		from_chain: u64,
+		to_chain: u64,
+		token: String,
+		amount: U256,
+	},
 }
 
 /// Events related to intent discovery.
@@ -56,6 +88,15 @@ pub enum OrderEvent {
 		order_id: String,
 		retry_after: Duration,
 	},
+	/// An order has been cancelled and will not be executed.
+	Cancelled { order_id: String, reason: String },
+	/// The fill deadline of an order passed while its fill transaction was
+	/// pending; the fill is monitored for the remaining seconds of the grace
+	/// period.
+	ExpiryGracePeriod {
+		order_id: String,
+		remaining_seconds: u64,
+	},
 }
 
 /// Events related to transaction delivery.
@@ -96,6 +137,8 @@ pub enum SettlementEvent {
 	ProofReady { order_id: String, proof: FillProof },
 	/// Order is ready to be claimed.
 	ClaimReady { order_id: String },
+	/// The fill was challenged during the challenge period and cannot be claimed.
+	FillChallenged { order_id: String },
 	/// Order settlement has been completed.
 	Completed { order_id: String },
 }
@@ -109,4 +152,8 @@ pub enum TransactionType {
 	Fill,
 	/// Transaction that claims rewards on the origin chain.
 	Claim,
+	/// Transaction committing to a fill ahead of revealing it.
+	Commit,
+	/// Transaction revealing a fill commitment to the contract verifying it.
+	Reveal,
 }
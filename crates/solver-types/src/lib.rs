@@ -20,6 +20,8 @@ pub mod networks;
 pub mod oracle;
 /// Order processing types including intents, orders, and execution contexts.
 pub mod order;
+/// Protocol deployments and token capabilities by chain.
+pub mod protocols;
 /// Registry trait for self-registering implementations.
 pub mod registry;
 /// Secure string type for handling sensitive data.
@@ -39,7 +41,7 @@ pub use api::*;
 pub use delivery::*;
 pub use discovery::*;
 pub use events::*;
-pub use networks::{NetworkConfig, NetworksConfig, TokenConfig};
+pub use networks::{ChainId, NetworkConfig, NetworksConfig, TokenConfig};
 pub use order::*;
 pub use registry::ImplementationRegistry;
 pub use secret_string::SecretString;
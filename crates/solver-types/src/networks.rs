@@ -6,7 +6,73 @@
 
 use crate::Address;
 use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Identifier of a blockchain network.
+///
+/// Wraps the numeric chain ID so that it cannot be confused with other
+/// integers such as block numbers or nonces. Maps keyed by `ChainId` can be
+/// looked up with a plain `u64`.
+#[derive(
+	Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct ChainId(pub u64);
+
+impl ChainId {
+	/// Ethereum mainnet.
+	pub const MAINNET: ChainId = ChainId(1);
+	/// Optimism mainnet.
+	pub const OPTIMISM: ChainId = ChainId(10);
+	/// Arbitrum One.
+	pub const ARBITRUM_ONE: ChainId = ChainId(42161);
+
+	/// Returns the numeric chain ID.
+	pub fn as_u64(&self) -> u64 {
+		self.0
+	}
+}
+
+impl From<u64> for ChainId {
+	fn from(chain_id: u64) -> Self {
+		Self(chain_id)
+	}
+}
+
+impl From<ChainId> for u64 {
+	fn from(chain_id: ChainId) -> Self {
+		chain_id.0
+	}
+}
+
+impl Borrow<u64> for ChainId {
+	fn borrow(&self) -> &u64 {
+		&self.0
+	}
+}
+
+impl PartialEq<u64> for ChainId {
+	fn eq(&self, other: &u64) -> bool {
+		self.0 == *other
+	}
+}
+
+impl fmt::Display for ChainId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+impl FromStr for ChainId {
+	type Err = std::num::ParseIntError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.parse().map(Self)
+	}
+}
 
 /// Configuration for RPC endpoints supporting both HTTP and WebSocket protocols.
 ///
@@ -80,12 +146,16 @@ pub struct TokenConfig {
 /// * `input_settler_address` - Address of the input settler contract (for origin chains)
 /// * `output_settler_address` - Address of the output settler contract (for destination chains)
 /// * `tokens` - List of supported tokens on this network
+/// * `input_settler_compact_address` - Optional address of the input settler for
+///   TheCompact resource-lock orders
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NetworkConfig {
 	pub rpc_urls: Vec<RpcEndpoint>,
 	pub input_settler_address: Address,
 	pub output_settler_address: Address,
 	pub tokens: Vec<TokenConfig>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub input_settler_compact_address: Option<Address>,
 }
 
 impl NetworkConfig {
@@ -122,22 +192,22 @@ impl NetworkConfig {
 
 /// Networks configuration mapping chain IDs to their configurations.
 ///
-/// This is a type alias for a HashMap that maps chain IDs (as u64) to
+/// This is a type alias for a HashMap that maps chain IDs to
 /// their corresponding network configurations. The configuration supports
 /// custom deserialization from TOML where chain IDs can be provided as
 /// string keys.
-pub type NetworksConfig = HashMap<u64, NetworkConfig>;
+pub type NetworksConfig = HashMap<ChainId, NetworkConfig>;
 
 /// Helper function to deserialize network configurations from TOML.
 ///
 /// This function handles the deserialization of network configurations where
 /// chain IDs are provided as string keys in TOML (since TOML doesn't support
-/// numeric keys in tables) and converts them to u64 keys for internal use.
+/// numeric keys in tables) and converts them to chain ID keys for internal use.
 ///
 /// # Errors
 ///
 /// Returns a deserialization error if:
-/// - A chain ID key cannot be parsed as a u64
+/// - A chain ID key cannot be parsed as a number
 /// - The underlying network configuration is invalid
 pub fn deserialize_networks<'de, D>(deserializer: D) -> Result<NetworksConfig, D::Error>
 where
@@ -148,10 +218,35 @@ where
 
 	for (key, value) in string_map {
 		let chain_id = key
-			.parse::<u64>()
+			.parse::<ChainId>()
 			.map_err(|e| serde::de::Error::custom(format!("Invalid chain_id '{}': {}", key, e)))?;
 		result.insert(chain_id, value);
 	}
 
 	Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_chain_id_keys() {
+		let json = serde_json::json!({
+			"10": {
+				"rpc_urls": [],
+				"input_settler_address": "0x0000000000000000000000000000000000000001",
+				"output_settler_address": "0x0000000000000000000000000000000000000002",
+				"tokens": []
+			}
+		});
+		let networks = deserialize_networks(json).unwrap();
+
+		assert!(networks.contains_key(&ChainId::OPTIMISM));
+		assert!(networks.contains_key(&10u64));
+		assert_eq!(u64::from(ChainId::ARBITRUM_ONE), 42161);
+		assert_eq!(ChainId::from(1), ChainId::MAINNET);
+		assert_eq!(ChainId::MAINNET.to_string(), "1");
+		assert!(deserialize_networks(serde_json::json!({ "optimism": {} })).is_err());
+	}
+}
@@ -1,5 +1,5 @@
 use crate::Address;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashMap;
 
 /// Oracle information combining chain and address
@@ -16,6 +16,48 @@ pub struct OracleRoutes {
 	pub supported_routes: HashMap<OracleInfo, Vec<OracleInfo>>,
 }
 
+/// Source of the oracle routes currently valid for order validation.
+///
+/// Routes change at runtime when they are discovered from settlement
+/// contracts, so validators read them on each use rather than keeping a copy.
+pub trait OracleRouteSource: Send + Sync {
+	/// Returns the current oracle routes.
+	fn oracle_routes(&self) -> OracleRoutes;
+}
+
+/// Fixed routes, for validation outside of a running solver.
+impl OracleRouteSource for OracleRoutes {
+	fn oracle_routes(&self) -> OracleRoutes {
+		self.clone()
+	}
+}
+
+/// Route from an input oracle as serialized in API responses.
+#[derive(Serialize)]
+struct OracleRoute<'a> {
+	input: &'a OracleInfo,
+	outputs: &'a [OracleInfo],
+}
+
+/// Serializes routes as a list ordered by input oracle, as JSON objects
+/// cannot be keyed by oracle info.
+impl Serialize for OracleRoutes {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut routes: Vec<OracleRoute> = self
+			.supported_routes
+			.iter()
+			.map(|(input, outputs)| OracleRoute { input, outputs })
+			.collect();
+		routes.sort_by(|a, b| {
+			(a.input.chain_id, &a.input.oracle.0).cmp(&(b.input.chain_id, &b.input.oracle.0))
+		});
+		serializer.collect_seq(routes)
+	}
+}
+
 /// Transaction types for oracle operations
 pub enum SettlementTransaction {
 	PostFill {
@@ -29,3 +71,66 @@ pub enum SettlementTransaction {
 		settlement_type: String,
 	},
 }
+
+/// Default Wormhole API serving signed VAAs.
+pub const DEFAULT_WORMHOLE_API_URL: &str = "https://api.wormholescan.io";
+
+/// Cross-chain messaging protocol relaying fill proofs to the origin chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelayProtocol {
+	Wormhole,
+	LayerZero,
+}
+
+/// Configuration of the relay of fill proofs to the origin chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofRelayConfig {
+	/// Protocol used to send proofs.
+	pub relay_protocol: RelayProtocol,
+	/// Messenger receiving relayed proofs, by origin chain ID.
+	pub messengers: HashMap<u64, Address>,
+	/// Wormhole parameters, required with the `wormhole` protocol.
+	#[serde(default)]
+	pub wormhole: Option<WormholeRelayConfig>,
+	/// LayerZero parameters, required with the `layerzero` protocol.
+	#[serde(default)]
+	pub layerzero: Option<LayerZeroRelayConfig>,
+}
+
+/// Wormhole connection parameters for proof relay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WormholeRelayConfig {
+	/// Core bridge publishing proof messages, by destination chain ID.
+	pub core_bridges: HashMap<u64, Address>,
+	/// Finality guardians wait for before signing a message.
+	/// Defaults to 1 (finalized) if not specified.
+	#[serde(default = "default_consistency_level")]
+	pub consistency_level: u8,
+	/// Wormhole API serving the signed VAAs of sent proofs.
+	/// Defaults to Wormholescan if not specified.
+	#[serde(default = "default_wormhole_api_url")]
+	pub api_url: String,
+}
+
+/// LayerZero connection parameters for proof relay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerZeroRelayConfig {
+	/// LayerZero V2 endpoint sending proof messages, by destination chain ID.
+	pub endpoints: HashMap<u64, Address>,
+	/// LayerZero endpoint ID of each origin chain.
+	pub eids: HashMap<u64, u32>,
+	/// Hex-encoded executor options of sent messages.
+	#[serde(default)]
+	pub options: String,
+}
+
+/// Returns the default Wormhole consistency level.
+fn default_consistency_level() -> u8 {
+	1
+}
+
+/// Returns the default Wormhole API URL.
+fn default_wormhole_api_url() -> String {
+	DEFAULT_WORMHOLE_API_URL.to_string()
+}
@@ -4,6 +4,7 @@
 //! and fill proofs used throughout the order lifecycle.
 
 use alloy_primitives::U256;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -47,6 +48,9 @@ pub struct Order {
 	/// Transaction hash of the prepare transaction (if applicable).
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub prepare_tx_hash: Option<TransactionHash>,
+	/// Transaction hash of the fill commitment (commit-reveal strategy only).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub commit_tx_hash: Option<TransactionHash>,
 	/// Transaction hash of the fill transaction.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub fill_tx_hash: Option<TransactionHash>,
@@ -67,6 +71,47 @@ pub struct ExecutionParams {
 	pub gas_price: U256,
 	/// Optional priority fee for EIP-1559 transactions.
 	pub priority_fee: Option<U256>,
+	/// Gas limit of the fill transaction, overriding the configured gas
+	/// limit strategy.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub gas_limit_override: Option<u64>,
+	/// Where the output tokens of the fill come from.
+	#[serde(
+		default,
+		skip_serializing_if = "OutputTokenSource::is_solver_inventory"
+	)]
+	pub output_source: OutputTokenSource,
+}
+
+/// Source of the output tokens paid by a fill.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputTokenSource {
+	/// Output tokens are paid from the solver's balance.
+	#[default]
+	SolverInventory,
+	/// Output tokens are bought on a DEX in the fill transaction.
+	AmmSwap {
+		dex: SwapDex,
+		/// Maximum price slippage of the swap in basis points.
+		max_slippage_bps: u16,
+	},
+}
+
+impl OutputTokenSource {
+	/// Returns true if output tokens are paid from the solver's balance.
+	pub fn is_solver_inventory(&self) -> bool {
+		matches!(self, Self::SolverInventory)
+	}
+}
+
+/// DEX output tokens are bought on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwapDex {
+	#[serde(rename = "uniswap_v3")]
+	UniswapV3,
+	#[serde(rename = "1inch")]
+	OneInch,
 }
 
 /// Context information for making execution decisions.
@@ -84,6 +129,9 @@ pub struct ExecutionContext {
 	pub solver_balances: HashMap<(u64, Option<String>), String>,
 	/// Timestamp when this context was built.
 	pub timestamp: u64,
+	/// Remaining daily gas budget in wei per chain.
+	/// Only chains with a configured budget are present.
+	pub gas_budget_remaining: HashMap<u64, U256>,
 }
 
 /// Decision made by an execution strategy.
@@ -99,11 +147,99 @@ pub enum ExecutionDecision {
 	Defer(std::time::Duration),
 }
 
+/// Template describing a recurring fill the solver is willing to make.
+///
+/// Orders are only executed when at least one template matches the
+/// token pair, chains and input amount range of the order.
+///
+/// Amounts of the two tokens are compared in whole tokens, using the decimals
+/// of the tokens configured for their networks, at the template's `rate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderTemplate {
+	/// Input token address on the input chain (hex string).
+	pub input_token: String,
+	/// Output token address on the output chain (hex string).
+	pub output_token: String,
+	/// Chain ID where the input is locked.
+	pub input_chain: u64,
+	/// Chain ID where the output is delivered.
+	pub output_chain: u64,
+	/// Minimum input amount accepted (inclusive).
+	pub min_amount: U256,
+	/// Maximum input amount accepted (inclusive).
+	pub max_amount: U256,
+	/// Whole output tokens worth one whole input token.
+	///
+	/// Defaults to 1, for pairs of equal value such as the same stablecoin
+	/// on two chains.
+	#[serde(default = "default_template_rate")]
+	pub rate: Decimal,
+	/// Maximum amount, in basis points of the input value at `rate`, by
+	/// which the output value may exceed the input value.
+	pub max_slippage_bps: u32,
+}
+
+/// Returns the default exchange rate of an order template.
+fn default_template_rate() -> Decimal {
+	Decimal::ONE
+}
+
+/// Scope within which the TWAP execution strategy limits fill frequency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TwapGranularity {
+	/// A single limit across all orders.
+	#[default]
+	Global,
+	/// One limit per destination chain.
+	Chain,
+	/// One limit per input and output token pair, including their chains.
+	TokenPair,
+}
+
 /// Proof that an order has been filled.
 ///
 /// Contains all information needed to claim rewards for filling an order.
+/// Serialized without a tag so that previously stored standard proofs
+/// remain readable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FillProof {
+	/// Oracle attested fill proof.
+	Standard(StandardFillProof),
+	/// Zero-knowledge proof of the fill.
+	Zk(ZkFillProof),
+}
+
+impl FillProof {
+	/// Returns the transaction hash of the fill.
+	pub fn tx_hash(&self) -> &TransactionHash {
+		match self {
+			FillProof::Standard(proof) => &proof.tx_hash,
+			FillProof::Zk(proof) => &proof.tx_hash,
+		}
+	}
+
+	/// Returns the block number where the fill was included.
+	pub fn block_number(&self) -> u64 {
+		match self {
+			FillProof::Standard(proof) => proof.block_number,
+			FillProof::Zk(proof) => proof.block_number,
+		}
+	}
+
+	/// Returns the timestamp when the order was filled.
+	pub fn filled_timestamp(&self) -> u64 {
+		match self {
+			FillProof::Standard(proof) => proof.filled_timestamp,
+			FillProof::Zk(proof) => proof.filled_timestamp,
+		}
+	}
+}
+
+/// Fill proof attested by an oracle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FillProof {
+pub struct StandardFillProof {
 	/// Transaction hash of the fill.
 	pub tx_hash: TransactionHash,
 	/// Block number where the fill was included.
@@ -114,6 +250,37 @@ pub struct FillProof {
 	pub filled_timestamp: u64,
 	/// Address of the oracle that attested to the fill.
 	pub oracle_address: String,
+	/// Version of the settlement contract format of the attestation data.
+	/// Proofs stored before versioning are version 1.
+	#[serde(default = "default_proof_version")]
+	pub proof_version: u8,
+}
+
+/// Returns the version of fill proofs stored without one.
+fn default_proof_version() -> u8 {
+	1
+}
+
+/// Zero-knowledge proof that an order has been filled.
+///
+/// Laid out so that `proof_bytes` and `public_inputs` can be passed directly
+/// to an on-chain verifier contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkFillProof {
+	/// Transaction hash of the fill.
+	pub tx_hash: TransactionHash,
+	/// Block number where the fill was included.
+	pub block_number: u64,
+	/// Timestamp when the order was filled.
+	pub filled_timestamp: u64,
+	/// Serialized proof.
+	pub proof_bytes: Vec<u8>,
+	/// Public inputs of the proof as field elements.
+	pub public_inputs: Vec<U256>,
+	/// Contract verifying the proof.
+	pub verifier_contract: Address,
+	/// Proving scheme used to generate the proof (e.g. "groth16", "plonk").
+	pub proving_scheme: String,
 }
 
 /// Settlement information for an order
@@ -153,6 +320,20 @@ pub struct OrderResponse {
 	/// Transaction details if order has been executed
 	#[serde(rename = "fillTransaction")]
 	pub fill_transaction: Option<serde_json::Value>,
+	/// Block number of the on-chain event that opened the order
+	#[serde(
+		rename = "originBlockNumber",
+		skip_serializing_if = "Option::is_none",
+		default
+	)]
+	pub origin_block_number: Option<u64>,
+	/// Block timestamp of the on-chain event that opened the order
+	#[serde(
+		rename = "originBlockTimestamp",
+		skip_serializing_if = "Option::is_none",
+		default
+	)]
+	pub origin_block_timestamp: Option<u64>,
 }
 
 /// Status of an order in the solver system.
@@ -171,6 +352,8 @@ pub enum OrderStatus {
 	Finalized,
 	/// Order execution failed with specific transaction type.
 	Failed(TransactionType),
+	/// Order was cancelled by its user before being filled.
+	Cancelled,
 }
 
 impl fmt::Display for OrderStatus {
@@ -182,6 +365,44 @@ impl fmt::Display for OrderStatus {
 			OrderStatus::Settled => write!(f, "Settled"),
 			OrderStatus::Finalized => write!(f, "Finalized"),
 			OrderStatus::Failed(_) => write!(f, "Failed"),
+			OrderStatus::Cancelled => write!(f, "Cancelled"),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_fill_proof_variants_round_trip() {
+		let standard = FillProof::Standard(StandardFillProof {
+			tx_hash: TransactionHash(vec![1u8; 32]),
+			block_number: 10,
+			attestation_data: None,
+			filled_timestamp: 1_700_000_000,
+			oracle_address: "0x0000000000000000000000000000000000000001".to_string(),
+			proof_version: 1,
+		});
+		let json = serde_json::to_value(&standard).unwrap();
+		// Standard proofs keep the original flat layout
+		assert!(json.get("oracle_address").is_some());
+		assert!(matches!(
+			serde_json::from_value::<FillProof>(json).unwrap(),
+			FillProof::Standard(_)
+		));
+
+		let zk = FillProof::Zk(ZkFillProof {
+			tx_hash: TransactionHash(vec![2u8; 32]),
+			block_number: 11,
+			filled_timestamp: 1_700_000_100,
+			proof_bytes: vec![0xab; 4],
+			public_inputs: vec![U256::from(7)],
+			verifier_contract: Address(vec![3u8; 20]),
+			proving_scheme: "groth16".to_string(),
+		});
+		let parsed: FillProof = serde_json::from_value(serde_json::to_value(&zk).unwrap()).unwrap();
+		assert!(matches!(parsed, FillProof::Zk(ref proof) if proof.proving_scheme == "groth16"));
+		assert_eq!(parsed.filled_timestamp(), 1_700_000_100);
+	}
+}
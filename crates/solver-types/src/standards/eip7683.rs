@@ -21,6 +21,19 @@ pub struct GasLimitOverrides {
 	pub prepare_gas_limit: Option<u64>,
 }
 
+/// Mechanism securing the inputs of an order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockType {
+	/// Inputs are escrowed by the input settler, through Permit2 for
+	/// off-chain orders.
+	#[default]
+	Permit2Escrow,
+	/// Inputs are locked in TheCompact and claimed through the compact
+	/// input settler.
+	ResourceLock,
+}
+
 /// EIP-7683 specific order data structure.
 ///
 /// Contains all the necessary information for processing a cross-chain order
@@ -57,6 +70,9 @@ pub struct Eip7683OrderData {
 	/// Optional sponsor address for off-chain orders
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub sponsor: Option<String>,
+	/// Mechanism securing the inputs of the order
+	#[serde(default)]
+	pub lock_type: LockType,
 }
 
 /// Represents a MandateOutput of the OIF contracts.
@@ -110,3 +126,35 @@ mod hex_string {
 		hex::decode(s).map_err(serde::de::Error::custom)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_lock_type_defaults_to_escrow() {
+		// Orders stored before lock types were recorded
+		let mut value = serde_json::json!({
+			"user": "0x0000000000000000000000000000000000000001",
+			"nonce": "0x1",
+			"origin_chain_id": "0x1",
+			"expires": 0,
+			"fill_deadline": 0,
+			"input_oracle": "0x0000000000000000000000000000000000000002",
+			"inputs": [],
+			"order_id": vec![0u8; 32],
+			"gas_limit_overrides": {},
+			"outputs": [],
+		});
+		let order_data: Eip7683OrderData = serde_json::from_value(value.clone()).unwrap();
+		assert_eq!(order_data.lock_type, LockType::Permit2Escrow);
+
+		value["lock_type"] = serde_json::json!("resource-lock");
+		let order_data: Eip7683OrderData = serde_json::from_value(value).unwrap();
+		assert_eq!(order_data.lock_type, LockType::ResourceLock);
+		assert_eq!(
+			serde_json::to_value(&order_data).unwrap()["lock_type"],
+			"resource-lock"
+		);
+	}
+}
@@ -16,6 +16,56 @@ pub enum StorageKey {
 	OrderByTxHash,
 	/// Key for storing quote data
 	Quotes,
+	/// Key for the fill slots claimed by orders executed against a quote
+	QuoteFills,
+	/// Key for storing historical token price snapshots
+	PriceHistory,
+	/// Key for storing auction bids per order
+	AuctionBids,
+	/// Key for storing per-chain gas spend
+	GasSpend,
+	/// Key for storing runtime log level overrides
+	LogLevels,
+	/// Key for storing hashed API keys
+	ApiKeys,
+	/// Key for caching aggregated order analytics
+	AnalyticsCache,
+	/// Key for recording how the solver last exited
+	LastExit,
+	/// Key for the periodic liveness heartbeat
+	Heartbeat,
+	/// Key for storing order nonces locked by pending off-chain orders
+	LockedNonces,
+	/// Key for caching ERC-20 metadata of tokens missing from the config
+	TokenMetadata,
+	/// Key for storing orders whose transactions were set aside after failing
+	DeadLetters,
+	/// Key for storing per-order analytics records such as fill times
+	Analytics,
+	/// Key for storing the latest recovery checkpoint
+	Checkpoints,
+	/// Key for storing the reports of the most recent state recoveries
+	RecoveryReports,
+	/// Key for the append-only audit log of order fills
+	AuditLog,
+	/// Key for storing webhook deliveries waiting to be retried
+	WebhookRetries,
+	/// Key for caching gas estimates of token approvals
+	TokenGasCache,
+	/// Key for the persisted log of all solver events
+	EventLog,
+	/// Key for the recent fill timestamps of the TWAP execution strategy
+	TwapState,
+	/// Key for entries moved aside because they could not be deserialized
+	Corrupted,
+	/// Key for sampled gas prices and their hourly averages
+	GasPriceHistory,
+	/// Key for the progress of fill proofs relayed to the origin chain
+	ProofRelays,
+	/// Key for the fill commitments of the commit-reveal execution strategy
+	CommitReveals,
+	/// Key for the last transfer over each rebalancing bridge
+	Rebalances,
 }
 
 impl StorageKey {
@@ -26,6 +76,31 @@ impl StorageKey {
 			StorageKey::Intents => "intents",
 			StorageKey::OrderByTxHash => "order_by_tx_hash",
 			StorageKey::Quotes => "quotes",
+			StorageKey::QuoteFills => "quote_fills",
+			StorageKey::PriceHistory => "price_history",
+			StorageKey::AuctionBids => "auction_bids",
+			StorageKey::GasSpend => "gas_spend",
+			StorageKey::LogLevels => "log_levels",
+			StorageKey::ApiKeys => "api_keys",
+			StorageKey::AnalyticsCache => "analytics_cache",
+			StorageKey::LastExit => "last_exit",
+			StorageKey::Heartbeat => "heartbeat",
+			StorageKey::LockedNonces => "locked_nonces",
+			StorageKey::TokenMetadata => "token_metadata",
+			StorageKey::DeadLetters => "dead_letters",
+			StorageKey::Analytics => "analytics",
+			StorageKey::Checkpoints => "checkpoints",
+			StorageKey::RecoveryReports => "recovery_reports",
+			StorageKey::AuditLog => "audit_log",
+			StorageKey::WebhookRetries => "webhook_retries",
+			StorageKey::TokenGasCache => "token_gas_cache",
+			StorageKey::EventLog => "event_log",
+			StorageKey::TwapState => "twap_state",
+			StorageKey::Corrupted => "corrupted",
+			StorageKey::GasPriceHistory => "gas_price_history",
+			StorageKey::ProofRelays => "proof_relays",
+			StorageKey::CommitReveals => "commit_reveals",
+			StorageKey::Rebalances => "rebalances",
 		}
 	}
 
@@ -36,6 +111,31 @@ impl StorageKey {
 			Self::Intents,
 			Self::OrderByTxHash,
 			Self::Quotes,
+			Self::QuoteFills,
+			Self::PriceHistory,
+			Self::AuctionBids,
+			Self::GasSpend,
+			Self::LogLevels,
+			Self::ApiKeys,
+			Self::AnalyticsCache,
+			Self::LastExit,
+			Self::Heartbeat,
+			Self::LockedNonces,
+			Self::TokenMetadata,
+			Self::DeadLetters,
+			Self::Analytics,
+			Self::Checkpoints,
+			Self::RecoveryReports,
+			Self::AuditLog,
+			Self::WebhookRetries,
+			Self::TokenGasCache,
+			Self::EventLog,
+			Self::TwapState,
+			Self::Corrupted,
+			Self::GasPriceHistory,
+			Self::ProofRelays,
+			Self::CommitReveals,
+			Self::Rebalances,
 		]
 		.into_iter()
 	}
@@ -50,6 +150,31 @@ impl FromStr for StorageKey {
 			"intents" => Ok(Self::Intents),
 			"order_by_tx_hash" => Ok(Self::OrderByTxHash),
 			"quotes" => Ok(Self::Quotes),
+			"quote_fills" => Ok(Self::QuoteFills),
+			"price_history" => Ok(Self::PriceHistory),
+			"auction_bids" => Ok(Self::AuctionBids),
+			"gas_spend" => Ok(Self::GasSpend),
+			"log_levels" => Ok(Self::LogLevels),
+			"api_keys" => Ok(Self::ApiKeys),
+			"analytics_cache" => Ok(Self::AnalyticsCache),
+			"last_exit" => Ok(Self::LastExit),
+			"heartbeat" => Ok(Self::Heartbeat),
+			"locked_nonces" => Ok(Self::LockedNonces),
+			"token_metadata" => Ok(Self::TokenMetadata),
+			"dead_letters" => Ok(Self::DeadLetters),
+			"analytics" => Ok(Self::Analytics),
+			"checkpoints" => Ok(Self::Checkpoints),
+			"recovery_reports" => Ok(Self::RecoveryReports),
+			"audit_log" => Ok(Self::AuditLog),
+			"webhook_retries" => Ok(Self::WebhookRetries),
+			"token_gas_cache" => Ok(Self::TokenGasCache),
+			"event_log" => Ok(Self::EventLog),
+			"twap_state" => Ok(Self::TwapState),
+			"corrupted" => Ok(Self::Corrupted),
+			"gas_price_history" => Ok(Self::GasPriceHistory),
+			"proof_relays" => Ok(Self::ProofRelays),
+			"commit_reveals" => Ok(Self::CommitReveals),
+			"rebalances" => Ok(Self::Rebalances),
 			_ => Err(()),
 		}
 	}
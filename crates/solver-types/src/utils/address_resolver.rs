@@ -0,0 +1,110 @@
+//! Resolution of bytes32-encoded addresses.
+//!
+//! EIP-7683 orders carry settler, oracle, token and recipient addresses as
+//! bytes32 values. The ABI encoding left-pads an address with zeros, but some
+//! clients right-pad it instead, which makes the address unreadable for code
+//! that takes the last 20 bytes. Right-padded values are rejected rather than
+//! rewritten: they are part of the signed order, and the settlement contracts
+//! read them as they were signed.
+
+use super::formatting::without_0x_prefix;
+use crate::Address;
+use alloy_primitives::hex;
+
+/// Number of padding bytes of an address encoded as bytes32.
+const PADDING_LEN: usize = 12;
+
+/// Checks that a bytes32 value is a left-padded address.
+///
+/// Zero values are considered left-padded. Returns an error naming the
+/// address if the value is right-padded.
+pub fn check_bytes32_address(value: &[u8; 32]) -> Result<(), String> {
+	if is_right_padded(value) {
+		return Err(format!(
+			"Right-padded address 0x{}, expected the ABI encoding 0x{}{}",
+			hex::encode(value),
+			"00".repeat(PADDING_LEN),
+			hex::encode(&value[..20])
+		));
+	}
+	Ok(())
+}
+
+/// Returns the address encoded in a left-padded bytes32 value.
+pub fn resolve_bytes32_address(value: &[u8; 32]) -> Result<Address, String> {
+	check_bytes32_address(value)?;
+	Ok(Address(value[PADDING_LEN..].to_vec()))
+}
+
+/// Resolves a left-padded bytes32 address given as a hex string of any case.
+pub fn resolve_bytes32_hex(value: &str) -> Result<Address, String> {
+	let bytes = hex::decode(without_0x_prefix(value)).map_err(|e| format!("Invalid hex: {}", e))?;
+	let bytes32: [u8; 32] = bytes
+		.try_into()
+		.map_err(|bytes: Vec<u8>| format!("Expected 32 bytes, got {}", bytes.len()))?;
+	resolve_bytes32_address(&bytes32)
+}
+
+/// Returns true if the address occupies the first 20 bytes of the value.
+///
+/// Zero values are considered left-padded.
+pub fn is_right_padded(value: &[u8; 32]) -> bool {
+	value[..PADDING_LEN].iter().any(|&b| b != 0) && value[20..].iter().all(|&b| b == 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ADDRESS: [u8; 20] = [
+		0x5f, 0xbd, 0xb2, 0x31, 0x56, 0x78, 0xaf, 0xec, 0xb3, 0x67, 0xf0, 0x32, 0xd9, 0x3f, 0x64,
+		0x2f, 0x64, 0x18, 0x0a, 0xa3,
+	];
+
+	fn left_padded() -> [u8; 32] {
+		let mut value = [0u8; 32];
+		value[PADDING_LEN..].copy_from_slice(&ADDRESS);
+		value
+	}
+
+	fn right_padded() -> [u8; 32] {
+		let mut value = [0u8; 32];
+		value[..20].copy_from_slice(&ADDRESS);
+		value
+	}
+
+	#[test]
+	fn test_resolve_left_padded() {
+		assert_eq!(
+			resolve_bytes32_address(&left_padded()),
+			Ok(Address(ADDRESS.to_vec()))
+		);
+		assert_eq!(
+			resolve_bytes32_address(&[0u8; 32]),
+			Ok(Address(vec![0u8; 20]))
+		);
+	}
+
+	#[test]
+	fn test_reject_right_padded() {
+		assert!(is_right_padded(&right_padded()));
+		assert!(check_bytes32_address(&right_padded()).is_err());
+		assert!(resolve_bytes32_address(&right_padded()).is_err());
+	}
+
+	#[test]
+	fn test_resolve_mixed_case_hex() {
+		let left = format!(
+			"0x{}5FbDB2315678afecb367f032d93F642f64180aa3",
+			"0".repeat(24)
+		);
+		let right = format!(
+			"0x5FbDB2315678afecb367f032d93F642f64180aa3{}",
+			"0".repeat(24)
+		);
+
+		assert_eq!(resolve_bytes32_hex(&left), Ok(Address(ADDRESS.to_vec())));
+		assert!(resolve_bytes32_hex(&right).is_err());
+		assert!(resolve_bytes32_hex("0x5FbD").is_err());
+	}
+}
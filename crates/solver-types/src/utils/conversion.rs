@@ -75,6 +75,8 @@ pub fn parse_address(hex_str: &str) -> Result<Address, String> {
 }
 
 #[cfg(test)]
+// Byte literals keep the casing of the checksummed addresses they spell
+#[allow(clippy::mixed_case_hex_literals)]
 mod tests {
 	use super::*;
 
@@ -9,6 +9,8 @@ use alloy_primitives::{keccak256, Address as AlloyAddress, B256, U256};
 
 // Common EIP-712 type strings used across the solver
 pub const DOMAIN_TYPE: &str = "EIP712Domain(string name,uint256 chainId,address verifyingContract)";
+pub const VERSIONED_DOMAIN_TYPE: &str =
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
 pub const NAME_PERMIT2: &str = "Permit2";
 pub const MANDATE_OUTPUT_TYPE: &str = "MandateOutput(bytes32 oracle,bytes32 settler,uint256 chainId,bytes32 token,uint256 amount,bytes32 recipient,bytes call,bytes context)";
 pub const PERMIT2_WITNESS_TYPE: &str =
@@ -16,6 +18,30 @@ pub const PERMIT2_WITNESS_TYPE: &str =
 pub const TOKEN_PERMISSIONS_TYPE: &str = "TokenPermissions(address token,uint256 amount)";
 pub const PERMIT_BATCH_WITNESS_TYPE: &str =
 	"PermitBatchWitnessTransferFrom(TokenPermissions[] permitted,address spender,uint256 nonce,uint256 deadline,Permit2Witness witness)";
+pub const RECEIVE_WITH_AUTHORIZATION_TYPE: &str =
+	"ReceiveWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)";
+
+/// Converts EIP-712 type strings into the `types` object of `eth_signTypedData_v4`.
+///
+/// Each type string has the form `Name(type field,...)`; the result maps every
+/// type name to its list of `{ name, type }` fields.
+pub fn typed_data_types(type_defs: &[&str]) -> serde_json::Value {
+	let mut types = serde_json::Map::new();
+	for type_def in type_defs {
+		let Some((name, fields)) = type_def.trim_end_matches(')').split_once('(') else {
+			continue;
+		};
+		let fields: Vec<serde_json::Value> = fields
+			.split(',')
+			.filter_map(|field| field.split_once(' '))
+			.map(
+				|(field_type, field_name)| serde_json::json!({ "name": field_name, "type": field_type }),
+			)
+			.collect();
+		types.insert(name.to_string(), serde_json::Value::Array(fields));
+	}
+	serde_json::Value::Object(types)
+}
 
 /// Compute EIP-712 domain hash (keccak256(abi.encode(typeHash, nameHash, chainId, verifyingContract))).
 pub fn compute_domain_hash(name: &str, chain_id: u64, verifying_contract: &AlloyAddress) -> B256 {
@@ -29,6 +55,24 @@ pub fn compute_domain_hash(name: &str, chain_id: u64, verifying_contract: &Alloy
 	keccak256(enc.finish())
 }
 
+/// Compute EIP-712 domain hash for domains that include a version
+/// (keccak256(abi.encode(typeHash, nameHash, versionHash, chainId, verifyingContract))).
+pub fn compute_versioned_domain_hash(
+	name: &str,
+	version: &str,
+	chain_id: u64,
+	verifying_contract: &AlloyAddress,
+) -> B256 {
+	let domain_type_hash = keccak256(VERSIONED_DOMAIN_TYPE.as_bytes());
+	let mut enc = Eip712AbiEncoder::new();
+	enc.push_b256(&domain_type_hash);
+	enc.push_b256(&keccak256(name.as_bytes()));
+	enc.push_b256(&keccak256(version.as_bytes()));
+	enc.push_u256(U256::from(chain_id));
+	enc.push_address(verifying_contract);
+	keccak256(enc.finish())
+}
+
 /// Compute the final EIP-712 digest: keccak256(0x1901 || domainHash || structHash).
 pub fn compute_final_digest(domain_hash: &B256, struct_hash: &B256) -> B256 {
 	let mut out = Vec::with_capacity(2 + 32 + 32);
@@ -86,3 +130,29 @@ impl Eip712AbiEncoder {
 		self.buf
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_typed_data_types() {
+		let types = typed_data_types(&[DOMAIN_TYPE, TOKEN_PERMISSIONS_TYPE]);
+
+		assert_eq!(
+			types["EIP712Domain"],
+			serde_json::json!([
+				{ "name": "name", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "verifyingContract", "type": "address" },
+			])
+		);
+		assert_eq!(
+			types["TokenPermissions"],
+			serde_json::json!([
+				{ "name": "token", "type": "address" },
+				{ "name": "amount", "type": "uint256" },
+			])
+		);
+	}
+}
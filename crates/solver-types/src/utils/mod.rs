@@ -3,15 +3,21 @@
 //! This module provides helper functions for converting between different
 //! data formats and string formatting commonly used throughout the solver system.
 
+pub mod address_resolver;
 pub mod conversion;
 pub mod eip712;
 pub mod formatting;
 pub mod helpers;
 
+pub use address_resolver::{
+	check_bytes32_address, is_right_padded, resolve_bytes32_address, resolve_bytes32_hex,
+};
 pub use conversion::{bytes20_to_alloy_address, bytes32_to_address, parse_address};
 pub use eip712::{
-	compute_domain_hash, compute_final_digest, Eip712AbiEncoder, DOMAIN_TYPE, MANDATE_OUTPUT_TYPE,
-	NAME_PERMIT2, PERMIT2_WITNESS_TYPE, PERMIT_BATCH_WITNESS_TYPE, TOKEN_PERMISSIONS_TYPE,
+	compute_domain_hash, compute_final_digest, compute_versioned_domain_hash, typed_data_types,
+	Eip712AbiEncoder, DOMAIN_TYPE, MANDATE_OUTPUT_TYPE, NAME_PERMIT2, PERMIT2_WITNESS_TYPE,
+	PERMIT_BATCH_WITNESS_TYPE, RECEIVE_WITH_AUTHORIZATION_TYPE, TOKEN_PERMISSIONS_TYPE,
+	VERSIONED_DOMAIN_TYPE,
 };
 pub use formatting::{format_token_amount, truncate_id, with_0x_prefix, without_0x_prefix};
 pub use helpers::current_timestamp;
@@ -5,14 +5,15 @@
 //! detailed error reporting.
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Errors that can occur during configuration validation.
 #[derive(Debug, Error)]
 pub enum ValidationError {
 	/// Error that occurs when a required field is missing.
-	#[error("Missing required field: {0}")]
-	MissingField(String),
+	#[error("Missing required field '{field}': expected {expected}")]
+	MissingField { field: String, expected: String },
 	/// Error that occurs when a field has an invalid value.
 	#[error("Invalid value for field '{field}': {message}")]
 	InvalidValue { field: String, message: String },
@@ -51,6 +52,20 @@ pub enum FieldType {
 	Table(Schema),
 }
 
+impl FieldType {
+	/// Returns the name of the type as shown in schemas and error messages,
+	/// such as `integer` or `array<string>`.
+	pub fn type_name(&self) -> String {
+		match self {
+			FieldType::String => "string".to_string(),
+			FieldType::Integer { .. } => "integer".to_string(),
+			FieldType::Boolean => "boolean".to_string(),
+			FieldType::Array(inner) => format!("array<{}>", inner.type_name()),
+			FieldType::Table(_) => "table".to_string(),
+		}
+	}
+}
+
 /// Type alias for field validator functions.
 ///
 /// Validators are custom functions that can perform additional validation
@@ -61,11 +76,14 @@ pub type FieldValidator = Box<dyn Fn(&toml::Value) -> Result<(), String> + Send
 /// Represents a field in a configuration schema.
 ///
 /// A field has a name, a type, and an optional custom validator function.
-/// Fields can be either required or optional within a schema.
+/// Fields can be either required or optional within a schema, and may be
+/// documented with a description and the default applied when omitted.
 pub struct Field {
 	pub name: String,
 	pub field_type: FieldType,
 	pub validator: Option<FieldValidator>,
+	pub description: Option<String>,
+	pub default: Option<toml::Value>,
 }
 
 impl std::fmt::Debug for Field {
@@ -74,6 +92,8 @@ impl std::fmt::Debug for Field {
 			.field("name", &self.name)
 			.field("field_type", &self.field_type)
 			.field("validator", &self.validator.is_some())
+			.field("description", &self.description)
+			.field("default", &self.default)
 			.finish()
 	}
 }
@@ -90,9 +110,23 @@ impl Field {
 			name: name.into(),
 			field_type,
 			validator: None,
+			description: None,
+			default: None,
 		}
 	}
 
+	/// Adds a human-readable description of this field.
+	pub fn with_description(mut self, description: impl Into<String>) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+
+	/// Documents the value used when this optional field is omitted.
+	pub fn with_default(mut self, default: impl Into<toml::Value>) -> Self {
+		self.default = Some(default.into());
+		self
+	}
+
 	/// Adds a custom validator to this field.
 	///
 	/// Custom validators allow for complex validation logic beyond simple type checking.
@@ -172,7 +206,10 @@ impl Schema {
 		for field in &self.required {
 			let value = table
 				.get(&field.name)
-				.ok_or_else(|| ValidationError::MissingField(field.name.clone()))?;
+				.ok_or_else(|| ValidationError::MissingField {
+					field: field.name.clone(),
+					expected: field.field_type.type_name(),
+				})?;
 
 			validate_field_type(&field.name, value, &field.field_type)?;
 
@@ -202,6 +239,81 @@ impl Schema {
 
 		Ok(())
 	}
+
+	/// Returns the structured description of the fields of this schema.
+	pub fn definition(&self) -> SchemaDefinition {
+		SchemaDefinition {
+			fields: field_specs(self),
+		}
+	}
+}
+
+/// Structured description of the fields accepted by a configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaDefinition {
+	/// Required fields followed by optional fields.
+	pub fields: Vec<FieldSpec>,
+}
+
+/// Description of a single configuration field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSpec {
+	/// Name of the field as it appears in the TOML configuration.
+	pub name: String,
+	/// Type of the field, such as `integer` or `array<string>`.
+	#[serde(rename = "type")]
+	pub field_type: String,
+	/// Whether the field must be present.
+	pub required: bool,
+	/// Value used when the field is omitted.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub default: Option<toml::Value>,
+	/// Human-readable description of the field.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub description: Option<String>,
+	/// Minimum allowed value of integers (inclusive).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub minimum: Option<i64>,
+	/// Maximum allowed value of integers (inclusive).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub maximum: Option<i64>,
+	/// Fields of tables, or of the tables within arrays.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub fields: Vec<FieldSpec>,
+}
+
+/// Describes the required and optional fields of a schema.
+fn field_specs(schema: &Schema) -> Vec<FieldSpec> {
+	let required = schema.required.iter().map(|field| (field, true));
+	let optional = schema.optional.iter().map(|field| (field, false));
+	required
+		.chain(optional)
+		.map(|(field, required)| {
+			// Bounds and nested fields of arrays are those of their elements
+			let mut element_type = &field.field_type;
+			while let FieldType::Array(inner) = element_type {
+				element_type = inner;
+			}
+			let (minimum, maximum) = match element_type {
+				FieldType::Integer { min, max } => (*min, *max),
+				_ => (None, None),
+			};
+			let fields = match element_type {
+				FieldType::Table(schema) => field_specs(schema),
+				_ => Vec::new(),
+			};
+			FieldSpec {
+				name: field.name.clone(),
+				field_type: field.field_type.type_name(),
+				required,
+				default: field.default.clone(),
+				description: field.description.clone(),
+				minimum,
+				maximum,
+				fields,
+			}
+		})
+		.collect()
 }
 
 /// Validates that a value matches the expected field type.
@@ -276,7 +388,7 @@ fn validate_field_type(
 				.as_array()
 				.ok_or_else(|| ValidationError::TypeMismatch {
 					field: field_name.to_string(),
-					expected: "array".to_string(),
+					expected: expected_type.type_name(),
 					actual: value.type_str().to_string(),
 				})?;
 
@@ -286,8 +398,11 @@ fn validate_field_type(
 		},
 		FieldType::Table(schema) => {
 			schema.validate(value).map_err(|e| match e {
-				ValidationError::MissingField(f) => {
-					ValidationError::MissingField(format!("{}.{}", field_name, f))
+				ValidationError::MissingField { field, expected } => {
+					ValidationError::MissingField {
+						field: format!("{}.{}", field_name, field),
+						expected,
+					}
 				},
 				ValidationError::InvalidValue { field, message } => ValidationError::InvalidValue {
 					field: format!("{}.{}", field_name, field),
@@ -324,4 +439,63 @@ pub trait ConfigSchema: Send + Sync {
 	/// - Field types are correct
 	/// - Values meet any constraints (ranges, patterns, etc.)
 	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError>;
+
+	/// Returns the structured description of all accepted fields, their
+	/// types, default values and descriptions.
+	fn schema(&self) -> SchemaDefinition;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn schema() -> Schema {
+		Schema::new(
+			vec![Field::new(
+				"pools",
+				FieldType::Array(Box::new(FieldType::Table(Schema::new(
+					vec![Field::new(
+						"chain_id",
+						FieldType::Integer {
+							min: Some(1),
+							max: None,
+						},
+					)
+					.with_description("Chain of the pool")],
+					vec![],
+				)))),
+			)],
+			vec![Field::new("api_url", FieldType::String)
+				.with_description("URL of the API")
+				.with_default("https://example.com")],
+		)
+	}
+
+	#[test]
+	fn test_definition_describes_fields_and_errors_name_expected_type() {
+		let definition = schema().definition();
+		let json = serde_json::to_value(&definition).unwrap();
+		assert_eq!(json["fields"][0]["type"], "array<table>");
+		assert_eq!(json["fields"][0]["required"], true);
+		assert_eq!(json["fields"][0]["fields"][0]["name"], "chain_id");
+		assert_eq!(json["fields"][0]["fields"][0]["minimum"], 1);
+		assert_eq!(json["fields"][1]["default"], "https://example.com");
+		assert_eq!(json["fields"][1]["description"], "URL of the API");
+
+		let missing = schema().validate(&toml::toml! { api_url = "x" }.into());
+		assert_eq!(
+			missing.unwrap_err().to_string(),
+			"Missing required field 'pools': expected array<table>"
+		);
+		let mismatch = schema().validate(&toml::toml! { pools = "x" }.into());
+		assert_eq!(
+			mismatch.unwrap_err().to_string(),
+			"Type mismatch for field 'pools': expected array<table>, got string"
+		);
+		let nested = schema().validate(&toml::toml! { pools = [{}] }.into());
+		assert_eq!(
+			nested.unwrap_err().to_string(),
+			"Missing required field 'pools[0].chain_id': expected integer"
+		);
+	}
 }
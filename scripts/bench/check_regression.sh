@@ -0,0 +1,49 @@
+#!/usr/bin/env bash
+#
+# Compares the results of the solver benchmarks against a Criterion baseline
+# recorded on the same machine and reports any benchmark more than 20% slower
+# than its baseline.
+#
+# Usage:
+#   git checkout main
+#   cargo bench -p solver-service --bench solver -- --save-baseline base-branch
+#   git checkout my-branch
+#   cargo bench -p solver-service --bench solver
+#   ./scripts/bench/check_regression.sh
+#
+# Results vary across hardware, so both runs must happen on the same machine.
+# Benchmarks without a baseline (e.g. newly added ones) are skipped. The
+# baseline needs its own name, as plain runs overwrite the default `base`.
+
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/../.." && pwd)"
+CRITERION_DIR="${CRITERION_DIR:-$ROOT/target/criterion}"
+BASELINE_NAME="${BASELINE_NAME:-base-branch}"
+MAX_REGRESSION_PCT="${MAX_REGRESSION_PCT:-20}"
+
+# Mean time in nanoseconds of a benchmark run
+mean_ns() {
+    jq -r '.mean.point_estimate' "$CRITERION_DIR/$1/$2/estimates.json"
+}
+
+failed=0
+for estimates in "$CRITERION_DIR"/*/new/estimates.json; do
+    [ -f "$estimates" ] || continue
+    name="$(basename "$(dirname "$(dirname "$estimates")")")"
+    if [ ! -f "$CRITERION_DIR/$name/$BASELINE_NAME/estimates.json" ]; then
+        echo "SKIPPED   $name: no baseline"
+        continue
+    fi
+    baseline="$(mean_ns "$name" "$BASELINE_NAME")"
+    current="$(mean_ns "$name" new)"
+    change_pct="$(awk -v c="$current" -v b="$baseline" 'BEGIN { printf "%.1f", (c - b) * 100 / b }')"
+    if awk -v p="$change_pct" -v max="$MAX_REGRESSION_PCT" 'BEGIN { exit !(p > max) }'; then
+        echo "REGRESSED $name: ${current%.*} ns vs ${baseline%.*} ns baseline (+${change_pct}%)"
+        failed=1
+    else
+        echo "OK        $name: ${current%.*} ns vs ${baseline%.*} ns baseline (${change_pct}%)"
+    fi
+done
+
+exit "$failed"